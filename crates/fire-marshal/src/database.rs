@@ -16,6 +16,8 @@ use std::time::{SystemTime, UNIX_EPOCH};
 const TOOL_REGISTRY_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("tool_registry");
 const EXECUTION_LOG_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("execution_log");
 const CACHE_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("cache");
+/// Tool that created each cache entry, so a tool's entries can be purged
+const CACHE_OWNER_TABLE: TableDefinition<&str, &str> = TableDefinition::new("cache_owner");
 
 /// Cache entry with metadata
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -96,6 +98,7 @@ impl PlatformDatabase {
             let _ = write_txn.open_table(TOOL_REGISTRY_TABLE)?;
             let _ = write_txn.open_table(EXECUTION_LOG_TABLE)?;
             let _ = write_txn.open_table(CACHE_TABLE)?;
+            let _ = write_txn.open_table(CACHE_OWNER_TABLE)?;
         }
         write_txn.commit()?;
 
@@ -126,6 +129,8 @@ impl PlatformDatabase {
         {
             let mut table = write_txn.open_table(CACHE_TABLE)?;
             table.insert(key, encoded.as_slice())?;
+            let mut owners = write_txn.open_table(CACHE_OWNER_TABLE)?;
+            owners.insert(key, tool)?;
         }
         write_txn.commit()?;
 
@@ -179,6 +184,8 @@ impl PlatformDatabase {
         {
             let mut table = write_txn.open_table(CACHE_TABLE)?;
             let _ = table.remove(key)?;
+            let mut owners = write_txn.open_table(CACHE_OWNER_TABLE)?;
+            let _ = owners.remove(key)?;
         }
         write_txn.commit()?;
 
@@ -206,6 +213,41 @@ impl PlatformDatabase {
         Ok(())
     }
 
+    /// Remove a tool from the database and purge the cache entries it created
+    ///
+    /// Returns the number of cache entries purged. Removing a tool that
+    /// isn't stored is not an error.
+    pub fn unregister_tool(&self, name: &str) -> Result<usize> {
+        let db = self.db.lock().map_err(|_| {
+            Error::Database(redb::Error::Io(std::io::Error::other("Lock poisoned")))
+        })?;
+        let write_txn = db.begin_write()?;
+        let purged = {
+            let mut registry = write_txn.open_table(TOOL_REGISTRY_TABLE)?;
+            let _ = registry.remove(name)?;
+
+            let mut owners = write_txn.open_table(CACHE_OWNER_TABLE)?;
+            let mut keys = Vec::new();
+            for result in owners.iter()? {
+                let (key, tool) = result?;
+                if tool.value() == name {
+                    keys.push(key.value().to_string());
+                }
+            }
+
+            let mut cache = write_txn.open_table(CACHE_TABLE)?;
+            for key in &keys {
+                let _ = cache.remove(key.as_str())?;
+                let _ = owners.remove(key.as_str())?;
+            }
+            keys.len()
+        };
+        write_txn.commit()?;
+
+        tracing::info!("Unregistered tool from database: {} ({} cache entries purged)", name, purged);
+        Ok(purged)
+    }
+
     /// Get all registered tools from database
     pub fn get_registered_tools(&self) -> Result<Vec<crate::ToolInfo>> {
         let db = self.db.lock().map_err(|_| {
@@ -340,6 +382,26 @@ mod tests {
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_unregister_tool_purges_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.redb");
+        let db = PlatformDatabase::new(&db_path, DatabaseConfig::default()).unwrap();
+
+        db.set("a:1", &1u32, "tool_a", "1.0").unwrap();
+        db.set("a:2", &2u32, "tool_a", "1.1").unwrap();
+        db.set("b:1", &3u32, "tool_b", "1.0").unwrap();
+
+        assert_eq!(db.unregister_tool("tool_a").unwrap(), 2);
+        assert_eq!(db.get::<u32>("a:1").unwrap(), None);
+        assert_eq!(db.get::<u32>("a:2").unwrap(), None);
+        assert_eq!(db.get::<u32>("b:1").unwrap(), Some(3));
+        assert_eq!(db.stats().unwrap().cache_entries, 1);
+
+        // Nothing left to purge
+        assert_eq!(db.unregister_tool("tool_a").unwrap(), 0);
+    }
+
     #[test]
     fn test_cache_expiration() {
         let temp_dir = TempDir::new().unwrap();
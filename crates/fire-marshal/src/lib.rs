@@ -4,16 +4,18 @@
 //! - Tool registry (static and dynamic registration)
 //! - Multiple transport layers (stdio, HTTP, WebSocket)
 //! - Shared redb database for cross-tool caching
-//! - Rate limiting and resource management
+//! - Rate limiting (global and per tool) and resource management
 //! - Tool discovery from manifest files
 
 pub mod database;
 pub mod error;
+pub mod rate_limit;
 pub mod registry;
 pub mod server;
 pub mod transport;
 
 pub use database::PlatformDatabase;
 pub use error::{Error, Result};
+pub use rate_limit::ToolRateLimiter;
 pub use registry::{RegisteredTool, ToolExecutor, ToolInfo, ToolRegistry};
-pub use server::{FireMarshal, FireMarshalConfig};
+pub use server::{AutoDeregisterConfig, FireMarshal, FireMarshalConfig};
@@ -4,7 +4,8 @@
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use fire_marshal::{FireMarshal, FireMarshalConfig};
+use fire_marshal::{AutoDeregisterConfig, FireMarshal, FireMarshalConfig};
+use std::num::NonZeroU32;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -27,6 +28,10 @@ struct Cli {
     #[arg(long, default_value = "100")]
     rate_limit: u32,
 
+    /// Per-tool rate limit as NAME=RPS (repeatable)
+    #[arg(long = "tool-rate-limit", value_parser = parse_tool_rate_limit)]
+    tool_rate_limits: Vec<(String, NonZeroU32)>,
+
     /// Request timeout in seconds
     #[arg(long, default_value = "30")]
     timeout: u64,
@@ -35,6 +40,18 @@ struct Cli {
     #[arg(long, default_value = "10")]
     max_concurrent: usize,
 
+    /// Deregister tools after this many consecutive failed health checks (at least 1)
+    #[arg(long)]
+    auto_deregister_after: Option<NonZeroU32>,
+
+    /// Seconds between health checks when auto-deregistration is enabled
+    #[arg(long, default_value = "30")]
+    health_interval: u64,
+
+    /// Directory of *.tool.json manifests to register at startup
+    #[arg(long)]
+    tools_dir: Option<PathBuf>,
+
     /// Log level
     #[arg(long, env = "RUST_LOG", default_value = "info")]
     log_level: String,
@@ -60,6 +77,17 @@ enum Command {
     },
 }
 
+/// Parse a `NAME=RPS` per-tool rate limit
+fn parse_tool_rate_limit(value: &str) -> std::result::Result<(String, NonZeroU32), String> {
+    let (name, rps) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected NAME=RPS, got '{}'", value))?;
+    let rps = rps
+        .parse()
+        .map_err(|_| format!("invalid requests per second '{}' (must be at least 1)", rps))?;
+    Ok((name.to_string(), rps))
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -82,8 +110,14 @@ async fn main() -> Result<()> {
                 database_path: cli.database,
                 port: cli.port,
                 rate_limit_rps: cli.rate_limit,
+                tool_rate_limits: cli.tool_rate_limits.into_iter().collect(),
                 timeout_secs: cli.timeout,
                 max_concurrent: cli.max_concurrent,
+                auto_deregister: cli.auto_deregister_after.map(|max_failures| AutoDeregisterConfig {
+                    interval_secs: cli.health_interval,
+                    max_failures,
+                }),
+                tools_dir: cli.tools_dir,
             };
 
             let marshal = FireMarshal::new(config)?;
@@ -0,0 +1,117 @@
+//! Per-tool rate limiting
+//!
+//! Complements the global request limit: a tool registered with its own
+//! requests-per-second limit gets a token bucket of its own, so a noisy
+//! tool exhausting its quota doesn't slow calls to any other tool.
+
+use crate::registry::RegisteredTool;
+use governor::clock::{Clock, DefaultClock};
+use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
+
+/// Token buckets for rate-limited tools, keyed by tool name
+pub struct ToolRateLimiter {
+    /// Each tool's bucket and the limit it was built for
+    buckets: Mutex<HashMap<String, (NonZeroU32, DefaultDirectRateLimiter)>>,
+    clock: DefaultClock,
+}
+
+impl ToolRateLimiter {
+    /// Create a limiter with no buckets
+    pub fn new() -> Self {
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+            clock: DefaultClock::default(),
+        }
+    }
+
+    /// Take a token for one call to `tool`
+    ///
+    /// Tools without a limit always pass. When the tool's bucket is empty,
+    /// returns how long until the next call would be allowed.
+    pub fn check(&self, tool: &RegisteredTool) -> Result<(), Duration> {
+        let Some(limit) = tool.rate_limit_rps else {
+            return Ok(());
+        };
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        let bucket = buckets
+            .entry(tool.info.name.clone())
+            .or_insert_with(|| (limit, RateLimiter::direct(Quota::per_second(limit))));
+
+        // The tool's limit changed since its bucket was built
+        if bucket.0 != limit {
+            *bucket = (limit, RateLimiter::direct(Quota::per_second(limit)));
+        }
+
+        bucket
+            .1
+            .check()
+            .map_err(|not_until| not_until.wait_time_from(self.clock.now()))
+    }
+
+    /// Drop a tool's bucket, e.g. when it is deregistered
+    pub fn remove(&self, name: &str) {
+        self.buckets
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name);
+    }
+}
+
+impl Default for ToolRateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::{ToolExecutor, ToolInfo};
+
+    fn tool(name: &str, rate_limit_rps: Option<u32>) -> RegisteredTool {
+        RegisteredTool {
+            info: ToolInfo {
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                description: String::new(),
+                tools: vec![],
+                executor: ToolExecutor::Native {
+                    module: name.to_string(),
+                },
+                metadata: HashMap::new(),
+            },
+            registered_at: 0,
+            last_health_check: None,
+            healthy: true,
+            consecutive_failures: 0,
+            rate_limit_rps: rate_limit_rps.and_then(NonZeroU32::new),
+        }
+    }
+
+    #[test]
+    fn test_buckets_are_per_tool() {
+        let limiter = ToolRateLimiter::new();
+        let noisy = tool("noisy", Some(3));
+        let quiet = tool("quiet", Some(3));
+        let unlimited = tool("unlimited", None);
+
+        for _ in 0..3 {
+            assert!(limiter.check(&noisy).is_ok());
+        }
+        let wait = limiter.check(&noisy).unwrap_err();
+        assert!(wait > Duration::ZERO && wait <= Duration::from_secs(1));
+
+        assert!(limiter.check(&quiet).is_ok());
+        for _ in 0..100 {
+            assert!(limiter.check(&unlimited).is_ok());
+        }
+
+        // A new limit starts a fresh bucket
+        assert!(limiter.check(&tool("noisy", Some(10))).is_ok());
+    }
+}
@@ -9,7 +9,8 @@ use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 
 /// Tool information for registration
@@ -103,19 +104,34 @@ pub struct RegisteredTool {
     pub last_health_check: Option<u64>,
     /// Is the tool currently healthy
     pub healthy: bool,
+    /// Health checks failed in a row since the last successful one
+    pub consecutive_failures: u32,
+    /// Calls per second allowed to this tool, on top of the global limit
+    pub rate_limit_rps: Option<NonZeroU32>,
 }
 
 /// Tool registry managing all registered tools
 pub struct ToolRegistry {
     /// Registered tools by name
     tools: RwLock<HashMap<String, RegisteredTool>>,
+    /// Per-tool call rate limits applied when a tool registers
+    rate_limits: HashMap<String, NonZeroU32>,
 }
 
 impl ToolRegistry {
     /// Create a new empty registry
     pub fn new() -> Self {
+        Self::with_rate_limits(HashMap::new())
+    }
+
+    /// Create an empty registry that limits the named tools' call rates
+    ///
+    /// Each limit is in calls per second and takes effect when the tool
+    /// registers; see [`set_rate_limit`](Self::set_rate_limit) to change it later.
+    pub fn with_rate_limits(rate_limits: HashMap<String, NonZeroU32>) -> Self {
         Self {
             tools: RwLock::new(HashMap::new()),
+            rate_limits,
         }
     }
 
@@ -137,6 +153,8 @@ impl ToolRegistry {
                 .as_secs(),
             last_health_check: None,
             healthy: true, // Assume healthy until proven otherwise
+            consecutive_failures: 0,
+            rate_limit_rps: self.rate_limits.get(&info.name).copied(),
         };
 
         tracing::info!("Registering tool: {} v{}", info.name, info.version);
@@ -147,16 +165,24 @@ impl ToolRegistry {
 
     /// Unregister a tool
     pub fn unregister(&self, name: &str) -> Result<()> {
+        self.deregister(name).map(|_| ())
+    }
+
+    /// Remove a tool, returning its registry entry
+    ///
+    /// Used to drop tools whose backing process has gone away, so calls
+    /// are no longer routed to them.
+    pub fn deregister(&self, name: &str) -> Result<RegisteredTool> {
         let mut tools = self.tools.write().map_err(|_| {
             Error::InvalidConfig("Registry lock poisoned".to_string())
         })?;
 
-        if tools.remove(name).is_none() {
-            return Err(Error::ToolNotFound(name.to_string()));
-        }
+        let tool = tools
+            .remove(name)
+            .ok_or_else(|| Error::ToolNotFound(name.to_string()))?;
 
         tracing::info!("Unregistered tool: {}", name);
-        Ok(())
+        Ok(tool)
     }
 
     /// Get a tool by name
@@ -191,6 +217,11 @@ impl ToolRegistry {
             .ok_or_else(|| Error::ToolNotFound(name.to_string()))?;
 
         tool.healthy = healthy;
+        tool.consecutive_failures = if healthy {
+            0
+        } else {
+            tool.consecutive_failures.saturating_add(1)
+        };
         tool.last_health_check = Some(
             std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
@@ -201,6 +232,33 @@ impl ToolRegistry {
         Ok(())
     }
 
+    /// Set or clear a registered tool's call rate limit
+    pub fn set_rate_limit(&self, name: &str, rate_limit_rps: Option<NonZeroU32>) -> Result<()> {
+        let mut tools = self.tools.write().map_err(|_| {
+            Error::InvalidConfig("Registry lock poisoned".to_string())
+        })?;
+
+        let tool = tools
+            .get_mut(name)
+            .ok_or_else(|| Error::ToolNotFound(name.to_string()))?;
+
+        tool.rate_limit_rps = rate_limit_rps;
+        Ok(())
+    }
+
+    /// Names of tools that failed at least `max_failures` health checks in a row
+    pub fn failing_tools(&self, max_failures: NonZeroU32) -> Result<Vec<String>> {
+        let tools = self.tools.read().map_err(|_| {
+            Error::InvalidConfig("Registry lock poisoned".to_string())
+        })?;
+
+        Ok(tools
+            .values()
+            .filter(|tool| tool.consecutive_failures >= max_failures.get())
+            .map(|tool| tool.info.name.clone())
+            .collect())
+    }
+
     /// Get count of registered tools
     pub fn count(&self) -> usize {
         self.tools
@@ -216,6 +274,42 @@ impl ToolRegistry {
             .map(|tools| tools.contains_key(name))
             .unwrap_or(false)
     }
+
+    /// Register every tool described by a `*.tool.json` manifest in `dir`
+    ///
+    /// Manifests are loaded in file name order. One that can't be read,
+    /// fails validation or names an already registered tool is skipped
+    /// with a warning. Returns the number of tools registered.
+    pub fn discover_from_dir(&self, dir: &Path) -> Result<usize> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.ends_with(".tool.json"))
+            })
+            .collect();
+        paths.sort();
+
+        let mut count = 0;
+        for path in paths {
+            let manifest = match ToolManifest::load(&path) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    tracing::warn!("Skipping tool manifest {:?}: {}", path, e);
+                    continue;
+                }
+            };
+            match self.register(manifest.into_tool_info()) {
+                Ok(()) => count += 1,
+                Err(e) => tracing::warn!("Skipping tool from manifest {:?}: {}", path, e),
+            }
+        }
+
+        Ok(count)
+    }
 }
 
 impl Default for ToolRegistry {
@@ -243,13 +337,44 @@ pub struct ToolManifest {
 }
 
 impl ToolManifest {
-    /// Load manifest from file
+    /// Load and validate a manifest from file
     pub fn load(path: &std::path::Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
         let manifest: Self = serde_json::from_str(&content)?;
+        manifest.validate()?;
         Ok(manifest)
     }
 
+    /// Check the fields serde can't: names, input schemas and executor targets
+    pub fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::InvalidManifest("Tool name is empty".to_string()));
+        }
+
+        for method in &self.tools {
+            if method.name.trim().is_empty() {
+                return Err(Error::InvalidManifest(format!("{}: method name is empty", self.name)));
+            }
+            if !method.input_schema.is_object() {
+                return Err(Error::InvalidManifest(format!(
+                    "{}: input schema of {} is not an object",
+                    self.name, method.name
+                )));
+            }
+        }
+
+        let target_missing = match &self.executor {
+            ToolExecutor::Http { url, .. } => url.trim().is_empty(),
+            ToolExecutor::Process { executable, .. } => executable.as_os_str().is_empty(),
+            ToolExecutor::Native { module } => module.trim().is_empty(),
+        };
+        if target_missing {
+            return Err(Error::InvalidManifest(format!("{}: executor has no target", self.name)));
+        }
+
+        Ok(())
+    }
+
     /// Convert to ToolInfo
     pub fn into_tool_info(self) -> ToolInfo {
         ToolInfo {
@@ -328,4 +453,143 @@ mod tests {
         registry.unregister("test-tool").unwrap();
         assert!(!registry.contains("test-tool"));
     }
+
+    #[test]
+    fn test_deregister_tool() {
+        let registry = ToolRegistry::new();
+
+        let info = ToolInfo {
+            name: "test-tool".to_string(),
+            version: "1.0.0".to_string(),
+            description: "A test tool".to_string(),
+            tools: vec![],
+            executor: ToolExecutor::Http {
+                url: "http://localhost:3000".to_string(),
+                auth: None,
+            },
+            metadata: HashMap::new(),
+        };
+
+        registry.register(info).unwrap();
+        let removed = registry.deregister("test-tool").unwrap();
+        assert_eq!(removed.info.name, "test-tool");
+        assert!(registry.list().unwrap().is_empty());
+
+        assert!(matches!(registry.deregister("test-tool"), Err(Error::ToolNotFound(_))));
+    }
+
+    #[test]
+    fn test_failing_tools() {
+        let registry = ToolRegistry::new();
+
+        for name in ["steady", "flaky"] {
+            registry
+                .register(ToolInfo {
+                    name: name.to_string(),
+                    version: "1.0.0".to_string(),
+                    description: String::new(),
+                    tools: vec![],
+                    executor: ToolExecutor::Native {
+                        module: name.to_string(),
+                    },
+                    metadata: HashMap::new(),
+                })
+                .unwrap();
+        }
+
+        registry.update_health("flaky", false).unwrap();
+        registry.update_health("flaky", false).unwrap();
+        registry.update_health("steady", false).unwrap();
+        registry.update_health("steady", true).unwrap();
+
+        assert_eq!(registry.get("flaky").unwrap().consecutive_failures, 2);
+        assert_eq!(registry.get("steady").unwrap().consecutive_failures, 0);
+        let failures = |n| NonZeroU32::new(n).unwrap();
+        assert_eq!(registry.failing_tools(failures(2)).unwrap(), ["flaky"]);
+        assert!(registry.failing_tools(failures(3)).unwrap().is_empty());
+        assert_eq!(registry.failing_tools(failures(1)).unwrap(), ["flaky"]);
+    }
+
+    #[test]
+    fn test_rate_limits_applied_on_registration() {
+        let limit = NonZeroU32::new(5).unwrap();
+        let registry = ToolRegistry::with_rate_limits(HashMap::from([("limited".to_string(), limit)]));
+
+        for name in ["limited", "free"] {
+            registry
+                .register(ToolInfo {
+                    name: name.to_string(),
+                    version: "1.0.0".to_string(),
+                    description: String::new(),
+                    tools: vec![],
+                    executor: ToolExecutor::Native {
+                        module: name.to_string(),
+                    },
+                    metadata: HashMap::new(),
+                })
+                .unwrap();
+        }
+
+        assert_eq!(registry.get("limited").unwrap().rate_limit_rps, Some(limit));
+        assert_eq!(registry.get("free").unwrap().rate_limit_rps, None);
+
+        registry.set_rate_limit("free", Some(limit)).unwrap();
+        assert_eq!(registry.get("free").unwrap().rate_limit_rps, Some(limit));
+        assert!(registry.set_rate_limit("missing", None).is_err());
+    }
+
+    #[test]
+    fn test_discover_from_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = |name: &str, executor: Value| {
+            serde_json::json!({
+                "name": name,
+                "version": "1.0.0",
+                "description": format!("{} tool", name),
+                "tools": [{
+                    "name": "run",
+                    "description": "Run it",
+                    "input_schema": {"type": "object", "properties": {}}
+                }],
+                "executor": executor,
+            })
+            .to_string()
+        };
+
+        std::fs::write(
+            dir.path().join("carver.tool.json"),
+            manifest("carver", serde_json::json!({"type": "http", "url": "http://localhost:3999"})),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("hasher.tool.json"),
+            manifest("hasher", serde_json::json!({"type": "process", "executable": "/usr/bin/hasher"})),
+        )
+        .unwrap();
+        // Malformed, invalid, or not a manifest at all
+        std::fs::write(dir.path().join("broken.tool.json"), "{not json").unwrap();
+        std::fs::write(
+            dir.path().join("empty.tool.json"),
+            manifest("empty", serde_json::json!({"type": "http", "url": ""})),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("ignored.json"),
+            manifest("ignored", serde_json::json!({"type": "native", "module": "ignored"})),
+        )
+        .unwrap();
+
+        let registry = ToolRegistry::new();
+        assert_eq!(registry.discover_from_dir(dir.path()).unwrap(), 2);
+        assert_eq!(registry.count(), 2);
+        assert_eq!(registry.get("carver").unwrap().info.tools[0].name, "run");
+        assert!(matches!(
+            registry.get("hasher").unwrap().info.executor,
+            ToolExecutor::Process { .. }
+        ));
+
+        // Already registered tools are skipped
+        assert_eq!(registry.discover_from_dir(dir.path()).unwrap(), 0);
+        assert!(registry.discover_from_dir(&dir.path().join("missing")).is_err());
+    }
 }
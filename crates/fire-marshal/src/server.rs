@@ -3,18 +3,20 @@
 //! Provides HTTP API for tool orchestration with rate limiting
 
 use crate::database::{DatabaseConfig, PlatformDatabase};
-use crate::registry::{ToolInfo, ToolRegistry};
+use crate::rate_limit::ToolRateLimiter;
+use crate::registry::{RegisteredTool, ToolInfo, ToolRegistry};
 use crate::transport::{HttpTransport, ToolCallRequest, ToolCallResponse};
 use crate::{Error, Result};
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::{IntoResponse, Json},
-    routing::{get, post},
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+    routing::{delete, get, post},
     Router,
 };
 use governor::{Quota, RateLimiter};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::num::NonZeroU32;
 use std::path::PathBuf;
@@ -31,10 +33,19 @@ pub struct FireMarshalConfig {
     pub port: u16,
     /// Rate limit (requests per second)
     pub rate_limit_rps: u32,
+    /// Per-tool call limits (calls per second), by tool name
+    ///
+    /// Enforced independently of `rate_limit_rps`; a tool over its limit
+    /// is refused without affecting calls to other tools.
+    pub tool_rate_limits: HashMap<String, NonZeroU32>,
     /// Request timeout in seconds
     pub timeout_secs: u64,
     /// Maximum concurrent requests
     pub max_concurrent: usize,
+    /// Health-check tools and drop those that keep failing (off when `None`)
+    pub auto_deregister: Option<AutoDeregisterConfig>,
+    /// Directory of `*.tool.json` manifests to register at startup
+    pub tools_dir: Option<PathBuf>,
 }
 
 impl Default for FireMarshalConfig {
@@ -43,8 +54,29 @@ impl Default for FireMarshalConfig {
             database_path: PathBuf::from("./fire-marshal.redb"),
             port: 3001,
             rate_limit_rps: 100,
+            tool_rate_limits: HashMap::new(),
             timeout_secs: 30,
             max_concurrent: 10,
+            auto_deregister: None,
+            tools_dir: None,
+        }
+    }
+}
+
+/// Health-driven automatic deregistration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoDeregisterConfig {
+    /// Seconds between health check rounds
+    pub interval_secs: u64,
+    /// Consecutive failed health checks after which a tool is deregistered
+    pub max_failures: NonZeroU32,
+}
+
+impl Default for AutoDeregisterConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 30,
+            max_failures: NonZeroU32::new(3).unwrap(),
         }
     }
 }
@@ -56,6 +88,39 @@ struct AppState {
     transport: HttpTransport,
     #[allow(dead_code)]
     rate_limiter: Arc<RateLimiter<governor::state::NotKeyed, governor::state::InMemoryState, governor::clock::DefaultClock>>,
+    tool_rate_limiter: ToolRateLimiter,
+}
+
+impl AppState {
+    /// Remove a tool from the registry and the database, purging its cache entries
+    fn deregister_tool(&self, name: &str) -> Result<RegisteredTool> {
+        let tool = self.registry.deregister(name)?;
+        self.tool_rate_limiter.remove(name);
+        if let Err(e) = self.database.unregister_tool(name) {
+            tracing::warn!("Failed to remove tool {} from database: {}", name, e);
+        }
+        Ok(tool)
+    }
+
+    /// Health-check every tool once, then drop those failing too often
+    async fn check_health(&self, max_failures: NonZeroU32) -> Result<()> {
+        for tool in self.registry.list()? {
+            let healthy = self.transport.health_check(&tool).await.unwrap_or(false);
+            // The tool may have been deregistered while being checked
+            let _ = self.registry.update_health(&tool.info.name, healthy);
+        }
+
+        for name in self.registry.failing_tools(max_failures)? {
+            tracing::warn!(
+                "Deregistering tool {} after {} failed health checks",
+                name,
+                max_failures
+            );
+            let _ = self.deregister_tool(&name);
+        }
+
+        Ok(())
+    }
 }
 
 /// Fire Marshal server
@@ -74,7 +139,7 @@ impl FireMarshal {
         )?;
 
         // Create registry
-        let registry = ToolRegistry::new();
+        let registry = ToolRegistry::with_rate_limits(config.tool_rate_limits.clone());
 
         // Load previously registered tools from database
         for tool_info in database.get_registered_tools()? {
@@ -83,6 +148,12 @@ impl FireMarshal {
             }
         }
 
+        // Register tools described by manifests
+        if let Some(tools_dir) = &config.tools_dir {
+            let count = registry.discover_from_dir(tools_dir)?;
+            tracing::info!("Discovered {} tools in {:?}", count, tools_dir);
+        }
+
         // Create transport
         let transport = HttpTransport::new(config.timeout_secs);
 
@@ -95,6 +166,7 @@ impl FireMarshal {
             database,
             transport,
             rate_limiter,
+            tool_rate_limiter: ToolRateLimiter::new(),
         });
 
         Ok(Self { config, state })
@@ -107,8 +179,40 @@ impl FireMarshal {
         Ok(())
     }
 
+    /// Deregister a tool and purge its cached entries
+    pub fn deregister_tool(&self, name: &str) -> Result<RegisteredTool> {
+        self.state.deregister_tool(name)
+    }
+
     /// Start the HTTP server
     pub async fn serve(self) -> Result<()> {
+        if let Some(auto_deregister) = self.config.auto_deregister {
+            let state = self.state.clone();
+            tokio::spawn(async move {
+                let period = std::time::Duration::from_secs(auto_deregister.interval_secs.max(1));
+                let mut interval = tokio::time::interval(period);
+                loop {
+                    interval.tick().await;
+                    if let Err(e) = state.check_health(auto_deregister.max_failures).await {
+                        tracing::warn!("Health check round failed: {}", e);
+                    }
+                }
+            });
+        }
+
+        let addr = SocketAddr::from(([0, 0, 0, 0], self.config.port));
+        tracing::info!("Fire Marshal listening on {}", addr);
+
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        axum::serve(listener, self.router())
+            .await
+            .map_err(|e| Error::Io(e.into()))?;
+
+        Ok(())
+    }
+
+    /// Build the HTTP router with its middleware
+    fn router(&self) -> Router {
         let state = self.state.clone();
 
         // Build CORS layer
@@ -117,11 +221,11 @@ impl FireMarshal {
             .allow_methods(Any)
             .allow_headers(Any);
 
-        // Build router
-        let app = Router::new()
+        Router::new()
             .route("/health", get(health_handler))
             .route("/tools/register", post(register_handler))
             .route("/tools/list", get(list_tools_handler))
+            .route("/tools/:name", delete(deregister_handler))
             .route("/tools/call", post(call_tool_handler))
             .route("/stats", get(stats_handler))
             .layer(
@@ -134,17 +238,7 @@ impl FireMarshal {
                         self.config.max_concurrent,
                     )),
             )
-            .with_state(state);
-
-        let addr = SocketAddr::from(([0, 0, 0, 0], self.config.port));
-        tracing::info!("Fire Marshal listening on {}", addr);
-
-        let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(listener, app)
-            .await
-            .map_err(|e| Error::Io(e.into()))?;
-
-        Ok(())
+            .with_state(state)
     }
 
     /// Get reference to registry
@@ -218,6 +312,34 @@ async fn register_handler(
     }
 }
 
+async fn deregister_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    match state.deregister_tool(&name) {
+        Ok(_) => (
+            StatusCode::OK,
+            Json(RegisterResponse {
+                success: true,
+                message: format!("Tool '{}' deregistered successfully", name),
+            }),
+        ),
+        Err(e) => {
+            let status = match e {
+                Error::ToolNotFound(_) => StatusCode::NOT_FOUND,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (
+                status,
+                Json(RegisterResponse {
+                    success: false,
+                    message: e.to_string(),
+                }),
+            )
+        }
+    }
+}
+
 /// List tools response
 #[derive(Serialize)]
 struct ListToolsResponse {
@@ -259,7 +381,7 @@ async fn list_tools_handler(State(state): State<Arc<AppState>>) -> impl IntoResp
 async fn call_tool_handler(
     State(state): State<Arc<AppState>>,
     Json(request): Json<ToolCallRequest>,
-) -> impl IntoResponse {
+) -> Response {
     let start = std::time::Instant::now();
 
     // Look up tool
@@ -273,9 +395,24 @@ async fn call_tool_handler(
                     request.tool
                 ))),
             )
+                .into_response()
         }
     };
 
+    if let Err(wait) = state.tool_rate_limiter.check(&tool) {
+        // Whole seconds, rounded up so a retry isn't refused again
+        let retry_after = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+        return (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.max(1).to_string())],
+            Json(ToolCallResponse::error(format!(
+                "Tool '{}' rate limit exceeded",
+                request.tool
+            ))),
+        )
+            .into_response();
+    }
+
     // Call tool via transport
     let response = match state.transport.call(&tool, &request).await {
         Ok(resp) => resp,
@@ -292,7 +429,7 @@ async fn call_tool_handler(
         duration_ms,
     );
 
-    (StatusCode::OK, Json(response))
+    (StatusCode::OK, Json(response)).into_response()
 }
 
 /// Stats response
@@ -328,8 +465,17 @@ async fn stats_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse
 mod tests {
     use super::*;
     use crate::registry::{ToolExecutor, ToolMethod};
-    use std::collections::HashMap;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
     use tempfile::tempdir;
+    use tower::ServiceExt;
+
+    async fn send(app: &Router, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
 
     #[test]
     fn test_fire_marshal_config_default() {
@@ -339,6 +485,7 @@ mod tests {
         assert_eq!(config.rate_limit_rps, 100);
         assert_eq!(config.timeout_secs, 30);
         assert_eq!(config.max_concurrent, 10);
+        assert_eq!(config.auto_deregister, None);
         assert_eq!(config.database_path, PathBuf::from("./fire-marshal.redb"));
     }
 
@@ -348,8 +495,11 @@ mod tests {
             database_path: PathBuf::from("/tmp/test.redb"),
             port: 8080,
             rate_limit_rps: 50,
+            tool_rate_limits: HashMap::new(),
             timeout_secs: 60,
             max_concurrent: 20,
+            auto_deregister: None,
+            tools_dir: None,
         };
 
         assert_eq!(config.port, 8080);
@@ -440,8 +590,11 @@ mod tests {
             database_path: db_path,
             port: 3001,
             rate_limit_rps: 100,
+            tool_rate_limits: HashMap::new(),
             timeout_secs: 30,
             max_concurrent: 10,
+            auto_deregister: None,
+            tools_dir: None,
         };
 
         let marshal = FireMarshal::new(config);
@@ -460,8 +613,11 @@ mod tests {
             database_path: db_path,
             port: 3001,
             rate_limit_rps: 100,
+            tool_rate_limits: HashMap::new(),
             timeout_secs: 30,
             max_concurrent: 10,
+            auto_deregister: None,
+            tools_dir: None,
         };
 
         let marshal = FireMarshal::new(config).unwrap();
@@ -497,8 +653,11 @@ mod tests {
             database_path: db_path,
             port: 3001,
             rate_limit_rps: 100,
+            tool_rate_limits: HashMap::new(),
             timeout_secs: 30,
             max_concurrent: 10,
+            auto_deregister: None,
+            tools_dir: None,
         };
 
         let marshal = FireMarshal::new(config).unwrap();
@@ -550,8 +709,11 @@ mod tests {
             database_path: db_path,
             port: 3001,
             rate_limit_rps: 100,
+            tool_rate_limits: HashMap::new(),
             timeout_secs: 30,
             max_concurrent: 10,
+            auto_deregister: None,
+            tools_dir: None,
         };
 
         let marshal = FireMarshal::new(config).unwrap();
@@ -576,4 +738,131 @@ mod tests {
         assert_eq!(parsed["cache_entries"], 0);
         assert_eq!(parsed["execution_logs"], 0);
     }
+
+    #[tokio::test]
+    async fn test_register_deregister_list() {
+        let temp_dir = tempdir().unwrap();
+        let config = FireMarshalConfig {
+            database_path: temp_dir.path().join("test.redb"),
+            ..FireMarshalConfig::default()
+        };
+        let marshal = FireMarshal::new(config).unwrap();
+        let app = marshal.router();
+
+        let tool = serde_json::json!({
+            "name": "carver",
+            "version": "1.0.0",
+            "description": "File carver",
+            "tools": [],
+            "executor": {"type": "http", "url": "http://localhost:3999"}
+        });
+        let request = Request::post("/tools/register")
+            .header("content-type", "application/json")
+            .body(Body::from(tool.to_string()))
+            .unwrap();
+        let (status, _) = send(&app, request).await;
+        assert_eq!(status, StatusCode::OK);
+        marshal.database().set("carver:result", &1u32, "carver", "1.0.0").unwrap();
+
+        let delete = || Request::delete("/tools/carver").body(Body::empty()).unwrap();
+        let (status, body) = send(&app, delete()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["success"], true);
+
+        let (status, body) = send(&app, Request::get("/tools/list").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["tools"], serde_json::json!([]));
+
+        // Persisted registration and cached results are gone too
+        assert!(marshal.database().get_registered_tools().unwrap().is_empty());
+        assert_eq!(marshal.database().get::<u32>("carver:result").unwrap(), None);
+
+        let (status, body) = send(&app, delete()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["success"], false);
+    }
+
+    #[tokio::test]
+    async fn test_health_checks_deregister_failing_tools() {
+        let temp_dir = tempdir().unwrap();
+        let config = FireMarshalConfig {
+            database_path: temp_dir.path().join("test.redb"),
+            ..FireMarshalConfig::default()
+        };
+        let marshal = FireMarshal::new(config).unwrap();
+
+        // Native tools have no health endpoint, so every check fails
+        marshal
+            .register_tool(ToolInfo {
+                name: "embedded".to_string(),
+                version: "1.0.0".to_string(),
+                description: "Embedded tool".to_string(),
+                tools: vec![],
+                executor: ToolExecutor::Native {
+                    module: "embedded".to_string(),
+                },
+                metadata: HashMap::new(),
+            })
+            .unwrap();
+
+        let max_failures = NonZeroU32::new(2).unwrap();
+        marshal.state.check_health(max_failures).await.unwrap();
+        assert!(marshal.registry().contains("embedded"));
+        assert!(!marshal.registry().get("embedded").unwrap().healthy);
+
+        marshal.state.check_health(max_failures).await.unwrap();
+        assert!(!marshal.registry().contains("embedded"));
+        assert!(marshal.database().get_registered_tools().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_per_tool_rate_limit() {
+        let temp_dir = tempdir().unwrap();
+        let config = FireMarshalConfig {
+            database_path: temp_dir.path().join("test.redb"),
+            tool_rate_limits: HashMap::from([
+                ("noisy".to_string(), NonZeroU32::new(3).unwrap()),
+                ("quiet".to_string(), NonZeroU32::new(3).unwrap()),
+            ]),
+            ..FireMarshalConfig::default()
+        };
+        let marshal = FireMarshal::new(config).unwrap();
+        for name in ["noisy", "quiet"] {
+            marshal
+                .register_tool(ToolInfo {
+                    name: name.to_string(),
+                    version: "1.0.0".to_string(),
+                    description: String::new(),
+                    tools: vec![],
+                    executor: ToolExecutor::Native {
+                        module: name.to_string(),
+                    },
+                    metadata: HashMap::new(),
+                })
+                .unwrap();
+        }
+        let app = marshal.router();
+
+        let call = |tool: &str| {
+            let body = serde_json::json!({"tool": tool, "method": "run", "arguments": {}});
+            Request::post("/tools/call")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        // The burst allowance passes; the call itself fails as native tools
+        // can't be called over HTTP, but that is reported in the body
+        for _ in 0..3 {
+            let (status, _) = send(&app, call("noisy")).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        let response = app.clone().oneshot(call("noisy")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+
+        let (status, _) = send(&app, call("quiet")).await;
+        assert_eq!(status, StatusCode::OK);
+    }
 }
@@ -0,0 +1,251 @@
+//! Conversion between disk image formats
+//!
+//! Streams an open vault's content into a new image file: raw, fixed or
+//! dynamic VHD, or E01. The source data is hashed as it is copied, so the
+//! result records what was read from the source rather than what landed in
+//! the destination.
+
+use crate::e01::{E01Creator, E01Options};
+use crate::error::{AcquireError, Result};
+use crate::hash::{HashAlgorithm, HashResult, Hasher};
+use crate::progress::AcquireProgress;
+use crate::vhd::{VhdAcquirer, VhdOptions, VhdOutputType};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::Instant;
+use totalimage_core::{Progress, ProgressReader, Vault};
+
+/// Block size used to find all-zero regions of raw output
+const SPARSE_BLOCK_SIZE: usize = 64 * 1024;
+
+/// Destination image format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VaultFormat {
+    /// Raw sector image; all-zero blocks are left as file holes
+    Raw,
+    /// Fixed VHD
+    FixedVhd,
+    /// Dynamic VHD; all-zero blocks are left unallocated
+    DynamicVhd,
+    /// E01 (EnCase) image with compressed chunks
+    E01,
+}
+
+/// Options for vault conversion
+#[derive(Debug, Clone)]
+pub struct ConvertOptions {
+    /// Hash algorithms to compute over the source data
+    pub hash_algorithms: Vec<HashAlgorithm>,
+    /// Block size for dynamic VHD output (default: 2 MB)
+    pub vhd_block_size: u32,
+    /// Sectors per chunk for E01 output (default: 64)
+    pub e01_sectors_per_chunk: u32,
+}
+
+impl Default for ConvertOptions {
+    fn default() -> Self {
+        Self {
+            hash_algorithms: vec![HashAlgorithm::Md5, HashAlgorithm::Sha256],
+            vhd_block_size: VhdOptions::default().block_size,
+            e01_sectors_per_chunk: E01Options::default().sectors_per_chunk,
+        }
+    }
+}
+
+/// Result of a vault conversion
+#[derive(Debug)]
+pub struct ConversionResult {
+    /// Format written
+    pub format: VaultFormat,
+    /// Bytes read from the source vault
+    pub source_size: u64,
+    /// Size of the destination file
+    pub bytes_written: u64,
+    /// Time taken for conversion
+    pub elapsed: std::time::Duration,
+    /// Hash results of source data
+    pub hashes: Vec<HashResult>,
+}
+
+/// Convert a vault's content into a new image at `out`
+///
+/// The whole of `src`'s [`content`](Vault::content) is read from its start;
+/// `out` is created or truncated.
+pub fn convert_vault(
+    src: &mut dyn Vault,
+    dst_format: VaultFormat,
+    out: &Path,
+    options: &ConvertOptions,
+) -> Result<ConversionResult> {
+    convert_vault_with_progress(src, dst_format, out, options, None)
+}
+
+/// Convert as [`convert_vault`] does, reporting the bytes read from `src`
+/// to `progress`
+pub fn convert_vault_with_progress(
+    src: &mut dyn Vault,
+    dst_format: VaultFormat,
+    out: &Path,
+    options: &ConvertOptions,
+    progress: Option<&dyn Progress>,
+) -> Result<ConversionResult> {
+    let start_time = Instant::now();
+    let source_size = src.length();
+    let content = src.content();
+    content.seek(SeekFrom::Start(0))?;
+    let mut source = ProgressReader::new(content, progress, Some(source_size));
+
+    let mut dest = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(out)
+        .map_err(|e| AcquireError::DestinationError(e.to_string()))?;
+
+    let hashes = match dst_format {
+        VaultFormat::Raw => copy_sparse(&mut source, source_size, &mut dest, &options.hash_algorithms)?,
+        VaultFormat::FixedVhd | VaultFormat::DynamicVhd => {
+            let acquirer = VhdAcquirer::with_options(VhdOptions {
+                vhd_type: match dst_format {
+                    VaultFormat::FixedVhd => VhdOutputType::Fixed,
+                    _ => VhdOutputType::Dynamic,
+                },
+                block_size: options.vhd_block_size,
+                hash_algorithms: options.hash_algorithms.clone(),
+                ..Default::default()
+            });
+            acquirer.acquire_stream(&mut source, source_size, &mut dest, None)?.hashes
+        }
+        VaultFormat::E01 => {
+            let creator = E01Creator::new(E01Options {
+                sectors_per_chunk: options.e01_sectors_per_chunk,
+                hash_algorithms: options.hash_algorithms.clone(),
+                ..Default::default()
+            });
+            creator.create::<_, _, fn(&AcquireProgress)>(&mut source, source_size, &mut dest, None)?.hashes
+        }
+    };
+
+    dest.flush().map_err(|e| AcquireError::WriteError(e.to_string()))?;
+
+    Ok(ConversionResult {
+        format: dst_format,
+        source_size,
+        bytes_written: dest.metadata()?.len(),
+        elapsed: start_time.elapsed(),
+        hashes,
+    })
+}
+
+/// Copy `size` bytes into a raw image, seeking over all-zero blocks
+fn copy_sparse<R: Read>(
+    source: &mut R,
+    size: u64,
+    dest: &mut File,
+    algorithms: &[HashAlgorithm],
+) -> Result<Vec<HashResult>> {
+    let mut hasher = Hasher::new(algorithms);
+    let mut buffer = vec![0u8; SPARSE_BLOCK_SIZE];
+    let mut copied = 0u64;
+
+    while copied < size {
+        let len = (size - copied).min(SPARSE_BLOCK_SIZE as u64) as usize;
+        source.read_exact(&mut buffer[..len])?;
+        hasher.update(&buffer[..len]);
+
+        if buffer[..len].iter().all(|&b| b == 0) {
+            dest.seek(SeekFrom::Current(len as i64))?;
+        } else {
+            dest.write_all(&buffer[..len])?;
+        }
+        copied += len as u64;
+    }
+
+    // Trailing holes need the file extended to its full length
+    dest.set_len(size)?;
+    Ok(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use totalimage_core::ProgressUpdate;
+    use totalimage_vaults::{open_vault, RawVault, VaultConfig};
+
+    /// 5 MB of mostly zeros with data in the first, third and last megabytes
+    fn sparse_source() -> Vec<u8> {
+        let mut data = vec![0u8; 5 * 1024 * 1024 + 1000];
+        data[..4096].fill(0x11);
+        data[2 * 1024 * 1024 + 77..2 * 1024 * 1024 + 5000].fill(0x22);
+        let tail = data.len() - 10;
+        data[tail..].fill(0x33);
+        data
+    }
+
+    fn read_all(path: &Path) -> Vec<u8> {
+        let mut vault = open_vault(path, VaultConfig::default()).unwrap();
+        let mut data = Vec::new();
+        vault.content().read_to_end(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_convert_raw_to_dynamic_vhd() {
+        let source = sparse_source();
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("converted.vhd");
+
+        let mut vault = RawVault::from_stream(Cursor::new(source.clone()), source.len() as u64);
+        let options = ConvertOptions { vhd_block_size: 1024 * 1024, ..Default::default() };
+        let result = convert_vault(&mut vault, VaultFormat::DynamicVhd, &out, &options).unwrap();
+
+        assert_eq!(result.source_size, source.len() as u64);
+        assert!(result.bytes_written < source.len() as u64);
+        let expected = crate::hash::hash_reader(&mut Cursor::new(&source), &options.hash_algorithms).unwrap();
+        for (actual, expected) in result.hashes.iter().zip(&expected) {
+            assert!(actual.matches(expected));
+        }
+
+        assert_eq!(read_all(&out), source);
+    }
+
+    #[test]
+    fn test_convert_to_each_format() {
+        let source = sparse_source();
+        let dir = tempfile::tempdir().unwrap();
+
+        for (format, name) in [
+            (VaultFormat::Raw, "converted.img"),
+            (VaultFormat::FixedVhd, "converted.vhd"),
+            (VaultFormat::DynamicVhd, "dynamic.vhd"),
+            (VaultFormat::E01, "converted.E01"),
+        ] {
+            let out = dir.path().join(name);
+            let mut vault = RawVault::from_stream(Cursor::new(source.clone()), source.len() as u64);
+            let last = std::sync::Mutex::new(None);
+            let progress = |update: ProgressUpdate| *last.lock().unwrap() = Some(update);
+            let result =
+                convert_vault_with_progress(&mut vault, format, &out, &ConvertOptions::default(), Some(&progress))
+                    .unwrap();
+            assert_eq!(result.format, format);
+            assert_eq!(
+                last.into_inner().unwrap(),
+                Some(ProgressUpdate::Bytes { processed: source.len() as u64, total: Some(source.len() as u64) }),
+                "{:?}",
+                format
+            );
+            assert_eq!(result.bytes_written, std::fs::metadata(&out).unwrap().len());
+
+            // E01 media is padded to a whole sector
+            let read_back = read_all(&out);
+            let opened = open_vault(&out, VaultConfig::default()).unwrap();
+            assert_eq!(opened.length(), read_back.len() as u64, "{:?}", format);
+            assert_eq!(opened.sector_count(512), source.len().div_ceil(512) as u64, "{:?}", format);
+            assert_eq!(&read_back[..source.len()], &source[..], "{:?}", format);
+            assert!(read_back[source.len()..].iter().all(|&b| b == 0));
+        }
+    }
+}
@@ -0,0 +1,495 @@
+//! E01 (EnCase) image creation
+//!
+//! Writes single-segment EWF (EnCase 6 style) images: the header2 and
+//! header case-information sections, a volume section, one sectors section
+//! holding the chunks, the chunk table and its table2 backup, and a hash
+//! section with the MD5 of the media. Chunks are zlib-compressed unless that
+//! doesn't make them smaller; uncompressed chunks carry an Adler-32 of their
+//! data, as EWF readers expect.
+
+use crate::error::{AcquireError, Result};
+use crate::hash::{HashAlgorithm, HashResult, Hasher};
+use crate::progress::AcquireProgress;
+use chrono::{DateTime, Utc};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use md5::{Digest, Md5};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use totalimage_vaults::e01::types::{
+    adler32, E01SectionDescriptor, E01TableHeader, SectionType, COMPRESSED_CHUNK, EVF_SIGNATURE,
+};
+
+/// Bytes per sector recorded in the volume section
+const SECTOR_SIZE: u64 = 512;
+
+/// Size of the volume section body
+const VOLUME_SIZE: usize = 1052;
+
+/// Table entries are 31-bit offsets into the sectors section
+const MAX_CHUNK_OFFSET: u64 = 0x7FFF_FFFF;
+
+/// Options for E01 creation
+#[derive(Debug, Clone)]
+pub struct E01Options {
+    /// Sectors per chunk (default: 64, i.e. 32 KB chunks)
+    pub sectors_per_chunk: u32,
+    /// Compress chunks with zlib
+    pub compress: bool,
+    /// Hash algorithms to compute over the source data
+    pub hash_algorithms: Vec<HashAlgorithm>,
+    /// Case details recorded in the header sections
+    pub case_info: E01CaseInfo,
+}
+
+/// Case details recorded in an E01's header sections
+#[derive(Debug, Clone, Default)]
+pub struct E01CaseInfo {
+    /// Case number
+    pub case_number: String,
+    /// Evidence number
+    pub evidence_number: String,
+    /// Description of the evidence
+    pub description: String,
+    /// Examiner name
+    pub examiner: String,
+    /// Free-form notes
+    pub notes: String,
+}
+
+impl Default for E01Options {
+    fn default() -> Self {
+        Self {
+            sectors_per_chunk: 64,
+            compress: true,
+            hash_algorithms: vec![HashAlgorithm::Md5, HashAlgorithm::Sha256],
+            case_info: E01CaseInfo::default(),
+        }
+    }
+}
+
+/// E01 image creator
+pub struct E01Creator {
+    options: E01Options,
+    cancel_flag: Arc<AtomicBool>,
+}
+
+impl E01Creator {
+    /// Create a new E01 creator with options
+    pub fn new(options: E01Options) -> Self {
+        Self {
+            options,
+            cancel_flag: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Get a cancel flag that can be used to abort creation
+    pub fn cancel_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.cancel_flag)
+    }
+
+    /// Create an E01 image of `source_size` bytes read from `source`
+    ///
+    /// The image is written from the start of `dest`. E01 media is a whole
+    /// number of sectors, so a source that doesn't end on a sector boundary
+    /// reads back with its last sector zero-padded; the returned hashes cover
+    /// the source as read.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the source ends early, or if the chunk data outgrows the
+    /// 2 GB a single segment's table can address.
+    pub fn create<R, W, F>(
+        &self,
+        source: &mut R,
+        source_size: u64,
+        dest: &mut W,
+        progress_callback: Option<F>,
+    ) -> Result<E01CreationResult>
+    where
+        R: Read,
+        W: Write + Seek,
+        F: FnMut(&AcquireProgress),
+    {
+        if self.options.sectors_per_chunk == 0 {
+            return Err(AcquireError::InvalidBlockSize(0));
+        }
+
+        let start_time = Instant::now();
+        let mut callback = progress_callback;
+        let mut hasher = Hasher::new(&self.options.hash_algorithms);
+        let mut media_md5 = Md5::new();
+
+        let chunk_size = self.options.sectors_per_chunk as u64 * SECTOR_SIZE;
+        let sector_count = source_size.div_ceil(SECTOR_SIZE);
+        let chunk_count = u32::try_from(source_size.div_ceil(chunk_size))
+            .map_err(|_| AcquireError::DestinationError("Too many chunks for an E01 image".to_string()))?;
+
+        dest.seek(SeekFrom::Start(0))?;
+        let mut file_header = EVF_SIGNATURE.to_vec();
+        file_header.push(0x01); // Fields start
+        file_header.extend_from_slice(&1u16.to_le_bytes()); // Segment number
+        file_header.extend_from_slice(&0u16.to_le_bytes()); // Fields end
+        dest.write_all(&file_header)?;
+
+        // EnCase writes header2 twice, then header
+        let acquired = Utc::now();
+        let header2 = zlib_compress(&header2_body(&self.options.case_info, acquired))?;
+        write_section(dest, SectionType::Header2, &header2)?;
+        write_section(dest, SectionType::Header2, &header2)?;
+        let header = header_body(&self.options.case_info, acquired, self.options.compress);
+        write_section(dest, SectionType::Header, &zlib_compress(&header)?)?;
+
+        let volume = volume_body(chunk_count, self.options.sectors_per_chunk, sector_count, self.options.compress);
+        write_section(dest, SectionType::Volume, &volume)?;
+
+        // The sectors descriptor is written once the chunk data's size is known
+        let sectors_offset = dest.stream_position()?;
+        dest.write_all(&[0u8; E01SectionDescriptor::SIZE])?;
+
+        // Table entries are offsets from the start of the chunk data
+        let mut entries = Vec::with_capacity(chunk_count as usize * 4);
+        let mut buffer = vec![0u8; chunk_size as usize];
+        let mut stored_bytes = 0u64;
+        let mut bytes_read = 0u64;
+
+        for _ in 0..chunk_count {
+            if self.cancel_flag.load(Ordering::Relaxed) {
+                return Err(AcquireError::Cancelled);
+            }
+            if stored_bytes > MAX_CHUNK_OFFSET {
+                return Err(AcquireError::DestinationError(
+                    "Chunk data exceeds what a single E01 segment can address".to_string(),
+                ));
+            }
+
+            let len = chunk_size.min(source_size - bytes_read) as usize;
+            source.read_exact(&mut buffer[..len])?;
+            hasher.update(&buffer[..len]);
+            bytes_read += len as u64;
+
+            let padded = len.next_multiple_of(SECTOR_SIZE as usize);
+            buffer[len..padded].fill(0);
+            let chunk = &buffer[..padded];
+            media_md5.update(chunk);
+
+            let compressed = match self.options.compress {
+                true => Some(zlib_compress(chunk)?).filter(|data| data.len() < chunk.len()),
+                false => None,
+            };
+            match compressed {
+                Some(data) => {
+                    entries.extend_from_slice(&(COMPRESSED_CHUNK | stored_bytes as u32).to_le_bytes());
+                    dest.write_all(&data)?;
+                    stored_bytes += data.len() as u64;
+                }
+                None => {
+                    entries.extend_from_slice(&(stored_bytes as u32).to_le_bytes());
+                    dest.write_all(chunk)?;
+                    dest.write_all(&adler32(chunk).to_le_bytes())?;
+                    stored_bytes += chunk.len() as u64 + 4;
+                }
+            }
+
+            if let Some(ref mut cb) = callback {
+                let progress = AcquireProgress::calculate(Some(source_size), bytes_read, start_time, "Creating E01");
+                cb(&progress);
+            }
+        }
+
+        let table_offset = dest.stream_position()?;
+        dest.seek(SeekFrom::Start(sectors_offset))?;
+        dest.write_all(&section_descriptor(SectionType::Sectors, sectors_offset, stored_bytes))?;
+        dest.seek(SeekFrom::Start(table_offset))?;
+
+        let table = table_body(&entries, sectors_offset + E01SectionDescriptor::SIZE as u64);
+        write_section(dest, SectionType::Table, &table)?;
+        write_section(dest, SectionType::Table2, &table)?;
+
+        let mut hash = vec![0u8; 36];
+        hash[..16].copy_from_slice(&media_md5.finalize());
+        let checksum = adler32(&hash[..32]);
+        hash[32..].copy_from_slice(&checksum.to_le_bytes());
+        write_section(dest, SectionType::Hash, &hash)?;
+
+        // The done section points at itself
+        let done_offset = dest.stream_position()?;
+        let mut done = section_descriptor(SectionType::Done, done_offset, 0);
+        done[16..24].copy_from_slice(&done_offset.to_le_bytes());
+        let checksum = adler32(&done[..72]);
+        done[72..].copy_from_slice(&checksum.to_le_bytes());
+        dest.write_all(&done)?;
+
+        Ok(E01CreationResult {
+            bytes_written: dest.stream_position()?,
+            source_size,
+            chunk_count,
+            elapsed: start_time.elapsed(),
+            hashes: hasher.finalize(),
+        })
+    }
+}
+
+/// Result of E01 creation
+#[derive(Debug)]
+pub struct E01CreationResult {
+    /// Total bytes written to destination
+    pub bytes_written: u64,
+    /// Original source size
+    pub source_size: u64,
+    /// Number of chunks written
+    pub chunk_count: u32,
+    /// Time taken for creation
+    pub elapsed: std::time::Duration,
+    /// Hash results of source data
+    pub hashes: Vec<HashResult>,
+}
+
+/// Build the UTF-16 header2 section text, before compression
+fn header2_body(case: &E01CaseInfo, acquired: DateTime<Utc>) -> Vec<u8> {
+    let timestamp = acquired.timestamp().to_string();
+    let values: [&str; 10] = [
+        &case.description,
+        &case.case_number,
+        &case.evidence_number,
+        &case.examiner,
+        &case.notes,
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        &timestamp,
+        &timestamp,
+        "0",
+    ];
+    let text = header_text("a\tc\tn\te\tt\tav\tov\tm\tu\tp", &values);
+
+    let mut body = vec![0xFF, 0xFE]; // Little-endian byte order mark
+    body.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+    body
+}
+
+/// Build the ASCII header section text, before compression
+fn header_body(case: &E01CaseInfo, acquired: DateTime<Utc>, compressed: bool) -> Vec<u8> {
+    let date = acquired.format("%Y %-m %-d %-H %-M %-S").to_string();
+    let values: [&str; 11] = [
+        &case.case_number,
+        &case.evidence_number,
+        &case.description,
+        &case.examiner,
+        &case.notes,
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        &date,
+        &date,
+        "0",
+        if compressed { "f" } else { "n" },
+    ];
+    let text = header_text("c\tn\ta\te\tt\tav\tov\tm\tu\tp\tr", &values);
+    text.chars().map(|c| if c.is_ascii() { c as u8 } else { b'?' }).collect()
+}
+
+/// Lay out a header category of tab-separated `keys` and `values`
+fn header_text(keys: &str, values: &[&str]) -> String {
+    // Tabs and line breaks would split a value into fields or rows
+    let values: Vec<String> = values.iter().map(|value| value.replace(['\t', '\r', '\n'], " ")).collect();
+    format!("1\nmain\n{}\n{}\n\n", keys, values.join("\t"))
+}
+
+/// Build a volume section body for a fixed disk
+fn volume_body(chunk_count: u32, sectors_per_chunk: u32, sector_count: u64, compressed: bool) -> Vec<u8> {
+    let mut volume = vec![0u8; VOLUME_SIZE];
+    volume[0] = 0x01; // Fixed disk
+    volume[4..8].copy_from_slice(&chunk_count.to_le_bytes());
+    volume[8..12].copy_from_slice(&sectors_per_chunk.to_le_bytes());
+    volume[12..16].copy_from_slice(&(SECTOR_SIZE as u32).to_le_bytes());
+    volume[16..24].copy_from_slice(&sector_count.to_le_bytes());
+    volume[36] = 0x01; // Media flags: image file
+    volume[52] = compressed as u8; // Compression level: none or fast
+    volume[56..60].copy_from_slice(&sectors_per_chunk.to_le_bytes()); // Error granularity
+
+    let checksum = adler32(&volume[..VOLUME_SIZE - 4]);
+    volume[VOLUME_SIZE - 4..].copy_from_slice(&checksum.to_le_bytes());
+    volume
+}
+
+/// Build a table section body: the header, the entries and their checksum
+fn table_body(entries: &[u8], base_offset: u64) -> Vec<u8> {
+    let mut table = vec![0u8; E01TableHeader::SIZE];
+    table[0..4].copy_from_slice(&((entries.len() / 4) as u32).to_le_bytes());
+    table[8..16].copy_from_slice(&base_offset.to_le_bytes());
+    let checksum = adler32(&table[..20]);
+    table[20..24].copy_from_slice(&checksum.to_le_bytes());
+
+    table.extend_from_slice(entries);
+    table.extend_from_slice(&adler32(entries).to_le_bytes());
+    table
+}
+
+/// Write a section descriptor followed by its body at the current position
+fn write_section<W: Write + Seek>(dest: &mut W, section_type: SectionType, body: &[u8]) -> Result<()> {
+    let offset = dest.stream_position()?;
+    dest.write_all(&section_descriptor(section_type, offset, body.len() as u64))?;
+    dest.write_all(body)?;
+    Ok(())
+}
+
+/// Build the descriptor of a section at `offset` with a `body_size` byte body
+fn section_descriptor(section_type: SectionType, offset: u64, body_size: u64) -> [u8; E01SectionDescriptor::SIZE] {
+    let size = E01SectionDescriptor::SIZE as u64 + body_size;
+
+    let mut descriptor = [0u8; E01SectionDescriptor::SIZE];
+    descriptor[0..16].copy_from_slice(&section_type.to_bytes());
+    descriptor[16..24].copy_from_slice(&(offset + size).to_le_bytes());
+    descriptor[24..32].copy_from_slice(&size.to_le_bytes());
+    let checksum = adler32(&descriptor[..72]);
+    descriptor[72..].copy_from_slice(&checksum.to_le_bytes());
+    descriptor
+}
+
+/// Compress a chunk or header section with zlib
+fn zlib_compress(data: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::with_capacity(data.len()), Compression::fast());
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use totalimage_core::Vault;
+    use totalimage_vaults::E01Vault;
+
+    fn create(source: &[u8], options: E01Options) -> (E01CreationResult, Vec<u8>) {
+        let mut dest = Cursor::new(Vec::new());
+        let result = E01Creator::new(options)
+            .create::<_, _, fn(&AcquireProgress)>(&mut Cursor::new(source), source.len() as u64, &mut dest, None)
+            .unwrap();
+        (result, dest.into_inner())
+    }
+
+    /// List the sections of a single-segment image as (type, body offset, body size)
+    fn sections(image: &[u8]) -> Vec<(SectionType, usize, usize)> {
+        let mut sections = Vec::new();
+        let mut offset = 13;
+        loop {
+            let descriptor = E01SectionDescriptor::parse(&image[offset..]).unwrap();
+            assert_eq!(adler32(&image[offset..offset + 72]), descriptor.checksum);
+            let body = descriptor.section_size as usize - E01SectionDescriptor::SIZE;
+            sections.push((descriptor.section_type, offset + E01SectionDescriptor::SIZE, body));
+            if descriptor.section_type == SectionType::Done {
+                return sections;
+            }
+            offset = descriptor.next_offset as usize;
+        }
+    }
+
+    #[test]
+    fn test_e01_layout() {
+        // A zero chunk compresses, a noise chunk is stored with a checksum
+        let mut source = vec![0u8; 2 * 32 * 1024];
+        let mut state = 1u32;
+        for byte in &mut source[32 * 1024..] {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            *byte = (state >> 24) as u8;
+        }
+        let case_info = E01CaseInfo {
+            case_number: "2026-17".to_string(),
+            examiner: "J. Doe".to_string(),
+            ..Default::default()
+        };
+        let (_, image) = create(&source, E01Options { case_info, ..Default::default() });
+
+        assert_eq!(&image[..8], &EVF_SIGNATURE);
+        assert_eq!(&image[8..13], &[0x01, 0x01, 0x00, 0x00, 0x00]);
+
+        let sections = sections(&image);
+        let types: Vec<SectionType> = sections.iter().map(|&(section_type, _, _)| section_type).collect();
+        assert_eq!(
+            types,
+            [
+                SectionType::Header2,
+                SectionType::Header2,
+                SectionType::Header,
+                SectionType::Volume,
+                SectionType::Sectors,
+                SectionType::Table,
+                SectionType::Table2,
+                SectionType::Hash,
+                SectionType::Done,
+            ]
+        );
+        let body = |index: usize| &image[sections[index].1..sections[index].1 + sections[index].2];
+
+        let mut header2 = Vec::new();
+        flate2::read::ZlibDecoder::new(body(0)).read_to_end(&mut header2).unwrap();
+        assert_eq!(&header2[..2], &[0xFF, 0xFE]);
+        let utf16: Vec<u16> = header2[2..].chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        let text = String::from_utf16(&utf16).unwrap();
+        assert!(text.starts_with("1\nmain\na\tc\tn\te\tt\tav\tov\tm\tu\tp\n\t2026-17\t\tJ. Doe\t"));
+
+        let mut header = String::new();
+        flate2::read::ZlibDecoder::new(body(2)).read_to_string(&mut header).unwrap();
+        let rows: Vec<&str> = header.split('\n').collect();
+        assert_eq!(rows[2], "c\tn\ta\te\tt\tav\tov\tm\tu\tp\tr");
+        let values: Vec<&str> = rows[3].split('\t').collect();
+        assert_eq!((values[0], values[3], values[10]), ("2026-17", "J. Doe", "f"));
+
+        let volume = body(3);
+        assert_eq!(volume.len(), VOLUME_SIZE);
+        assert_eq!(volume[52], 1);
+        assert_eq!(adler32(&volume[..VOLUME_SIZE - 4]).to_le_bytes(), volume[VOLUME_SIZE - 4..]);
+
+        let table = body(5);
+        assert_eq!(table, body(6));
+        let header = E01TableHeader::parse(table).unwrap();
+        assert!(header.is_valid(table));
+        assert_eq!(header.entry_count, 2);
+        assert_eq!(header.base_offset, sections[4].1 as u64);
+        let entries: Vec<u32> = table[24..32].chunks_exact(4).map(|e| u32::from_le_bytes(e.try_into().unwrap())).collect();
+        assert_eq!(adler32(&table[24..32]).to_le_bytes(), table[32..36]);
+
+        // The zero chunk is flagged compressed, the noise chunk is stored
+        // with an Adler-32 trailer running to the end of the sectors section
+        assert_ne!(entries[0] & COMPRESSED_CHUNK, 0);
+        assert_eq!(entries[1] & COMPRESSED_CHUNK, 0);
+        let stored = &image[sections[4].1 + entries[1] as usize..sections[4].1 + sections[4].2];
+        assert_eq!(stored.len(), 32 * 1024 + 4);
+        assert_eq!(&stored[..32 * 1024], &source[32 * 1024..]);
+        assert_eq!(adler32(&source[32 * 1024..]).to_le_bytes(), stored[32 * 1024..]);
+    }
+
+    #[test]
+    fn test_e01_round_trip() {
+        // Compressible zeros, incompressible noise and a partial last sector
+        let mut source = vec![0u8; 3 * 32 * 1024 + 700];
+        let mut state = 1u32;
+        for byte in &mut source[32 * 1024..64 * 1024] {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            *byte = (state >> 24) as u8;
+        }
+        source[64 * 1024..].iter_mut().enumerate().for_each(|(i, b)| *b = (i % 7) as u8);
+
+        for compress in [true, false] {
+            let (result, image) = create(&source, E01Options { compress, ..Default::default() });
+            assert_eq!(result.chunk_count, 4);
+            assert_eq!(result.bytes_written, image.len() as u64);
+            if compress {
+                assert!(image.len() < source.len());
+            }
+
+            let mut vault = E01Vault::from_reader(Box::new(Cursor::new(image))).unwrap();
+            assert_eq!(vault.chunk_count(), 4);
+            assert_eq!(vault.length(), source.len().next_multiple_of(512) as u64);
+
+            let mut read_back = Vec::new();
+            vault.content().read_to_end(&mut read_back).unwrap();
+            assert_eq!(&read_back[..source.len()], &source[..]);
+            assert!(read_back[source.len()..].iter().all(|&b| b == 0));
+
+            let verification = vault.verify_integrity().unwrap().unwrap();
+            assert!(verification.is_match());
+        }
+    }
+}
@@ -6,6 +6,9 @@ use md5::{Md5, Digest};
 use sha1::Sha1;
 use sha2::Sha256;
 use std::io::Read;
+use std::sync::mpsc::{sync_channel, SyncSender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
 
 /// Supported hash algorithms
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -151,6 +154,89 @@ impl Hasher {
     }
 }
 
+/// Multi-algorithm hasher that runs each algorithm on its own thread
+///
+/// Data passed to [`update`](Self::update) is shared with one worker thread
+/// per algorithm, so computing MD5, SHA1 and SHA256 together costs the
+/// caller little more than a single copy of each chunk. Each worker's queue
+/// holds at most [`QUEUE_DEPTH`](Self::QUEUE_DEPTH) chunks; once a slow
+/// algorithm falls that far behind, `update` blocks until it catches up,
+/// which bounds the memory held in flight.
+pub struct ParallelHasher {
+    workers: Vec<HashWorker>,
+    bytes_processed: u64,
+}
+
+/// A hashing thread and the channel feeding it
+struct HashWorker {
+    sender: SyncSender<Arc<[u8]>>,
+    handle: JoinHandle<Vec<HashResult>>,
+}
+
+impl ParallelHasher {
+    /// Maximum number of chunks queued per algorithm before `update` blocks
+    pub const QUEUE_DEPTH: usize = 4;
+
+    /// Create a new parallel hasher with specified algorithms
+    pub fn new(algorithms: &[HashAlgorithm]) -> Self {
+        // Same result order as Hasher, regardless of input order or duplicates
+        let workers = [HashAlgorithm::Md5, HashAlgorithm::Sha1, HashAlgorithm::Sha256]
+            .into_iter()
+            .filter(|algorithm| algorithms.contains(algorithm))
+            .map(|algorithm| {
+                let (sender, receiver) = sync_channel::<Arc<[u8]>>(Self::QUEUE_DEPTH);
+                let handle = thread::spawn(move || {
+                    let mut hasher = Hasher::new(&[algorithm]);
+                    for chunk in receiver {
+                        hasher.update(&chunk);
+                    }
+                    hasher.finalize()
+                });
+                HashWorker { sender, handle }
+            })
+            .collect();
+
+        Self {
+            workers,
+            bytes_processed: 0,
+        }
+    }
+
+    /// Update the hasher with data
+    pub fn update(&mut self, data: &[u8]) {
+        if data.is_empty() || self.workers.is_empty() {
+            return;
+        }
+
+        let chunk: Arc<[u8]> = Arc::from(data);
+        for worker in &self.workers {
+            // A send only fails if the worker died; finalize reports that
+            let _ = worker.sender.send(Arc::clone(&chunk));
+        }
+        self.bytes_processed += data.len() as u64;
+    }
+
+    /// Wait for all workers and return their hash results
+    ///
+    /// # Panics
+    ///
+    /// Panics if a worker thread panicked.
+    pub fn finalize(self) -> Vec<HashResult> {
+        self.workers
+            .into_iter()
+            .flat_map(|worker| {
+                drop(worker.sender);
+                worker.handle.join().expect("hash worker thread panicked")
+            })
+            .collect()
+    }
+
+    /// Get bytes processed
+    pub fn bytes_processed(&self) -> u64 {
+        self.bytes_processed
+    }
+}
+
 /// Compute hash of a reader
 pub fn hash_reader<R: Read>(reader: &mut R, algorithms: &[HashAlgorithm]) -> std::io::Result<Vec<HashResult>> {
     let mut hasher = Hasher::new(algorithms);
@@ -170,9 +256,28 @@ pub fn hash_reader<R: Read>(reader: &mut R, algorithms: &[HashAlgorithm]) -> std
 /// Compute hash of a file
 pub fn hash_file(path: &std::path::Path, algorithms: &[HashAlgorithm]) -> std::io::Result<Vec<HashResult>> {
     let mut file = std::fs::File::open(path)?;
+    advise_sequential(&file);
     hash_reader(&mut file, algorithms)
 }
 
+/// Hint that `file` will be read from start to end
+///
+/// Lets the kernel read ahead more aggressively. Best effort: the hint is
+/// skipped on platforms without `posix_fadvise`, and failures are ignored.
+pub(crate) fn advise_sequential(file: &std::fs::File) {
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+    {
+        use std::os::unix::io::AsRawFd;
+        // SAFETY: posix_fadvise only records an access pattern for the
+        // descriptor, which is valid for the duration of the borrow
+        unsafe {
+            libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_SEQUENTIAL);
+        }
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd")))]
+    let _ = file;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -211,6 +316,42 @@ mod tests {
         assert_eq!(results.len(), 3);
     }
 
+    #[test]
+    fn test_parallel_hasher_matches_serial() {
+        let data: Vec<u8> = (0..3 * 1024 * 1024u32).map(|i| (i * 31 % 256) as u8).collect();
+        let algorithms = [HashAlgorithm::Sha256, HashAlgorithm::Md5, HashAlgorithm::Sha1];
+
+        let mut serial = Hasher::new(&algorithms);
+        let mut parallel = ParallelHasher::new(&algorithms);
+        // Many chunks so the bounded queues fill up and apply back-pressure
+        for chunk in data.chunks(4096 + 7) {
+            serial.update(chunk);
+            parallel.update(chunk);
+        }
+        assert_eq!(parallel.bytes_processed(), data.len() as u64);
+
+        let expected = serial.finalize();
+        let actual = parallel.finalize();
+        assert_eq!(actual.len(), 3);
+        for (actual, expected) in actual.iter().zip(&expected) {
+            assert!(actual.matches(expected), "{} differs", expected.algorithm.name());
+        }
+    }
+
+    #[test]
+    fn test_parallel_hasher_known_digest() {
+        let mut hasher = ParallelHasher::new(&[HashAlgorithm::Md5, HashAlgorithm::Md5]);
+        hasher.update(b"Hello, ");
+        hasher.update(b"");
+        hasher.update(b"World!");
+
+        let results = hasher.finalize();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].hex, "65a8e27d8879283831b664bd8b7f0ad4");
+
+        assert!(ParallelHasher::new(&[]).finalize().is_empty());
+    }
+
     #[test]
     fn test_hasher_incremental() {
         let mut hasher = Hasher::new(&[HashAlgorithm::Md5]);
@@ -0,0 +1,177 @@
+//! Acquisition info logs
+//!
+//! Like FTK Imager's `.txt` files, an info log records what was acquired,
+//! when, and the hashes computed on the way, so the image can be checked
+//! later without the source. It is written beside the image twice: as
+//! `<image>.txt` for people and as `<image>.json` for tools.
+
+use crate::error::Result;
+use crate::raw::{AcquireOptions, AcquireResult};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Record of a finished acquisition
+#[derive(Debug, Clone, Serialize)]
+pub struct AcquisitionInfo {
+    /// Path of the acquired file or device
+    pub source: String,
+    /// Path of the image written
+    pub image: String,
+    /// Source offset acquisition started at, in bytes
+    pub offset: u64,
+    /// Bytes written to the image
+    pub image_size: u64,
+    /// Sector size used to isolate read errors
+    pub sector_size: usize,
+    /// Number of sectors in the image, counting a partial last one
+    pub sector_count: u64,
+    /// Hex digests keyed by algorithm name
+    pub hashes: BTreeMap<&'static str, String>,
+    /// When acquisition started
+    pub started: DateTime<Utc>,
+    /// When acquisition, and verification if enabled, finished
+    pub finished: DateTime<Utc>,
+    /// Unreadable byte ranges as `(start, end)` image offsets, end exclusive
+    pub bad_sectors: Vec<(u64, u64)>,
+    /// Whether verification reproduced the hashes, if it was run
+    pub verified: Option<bool>,
+}
+
+impl AcquisitionInfo {
+    /// Describe the acquisition of `source` into `image`
+    pub fn new(
+        source: &Path,
+        image: &Path,
+        options: &AcquireOptions,
+        result: &AcquireResult,
+        started: DateTime<Utc>,
+        finished: DateTime<Utc>,
+    ) -> Self {
+        let sector_size = options.sector_size.max(1);
+        Self {
+            source: source.display().to_string(),
+            image: image.display().to_string(),
+            offset: options.skip,
+            image_size: result.bytes_acquired,
+            sector_size,
+            sector_count: result.bytes_acquired.div_ceil(sector_size as u64),
+            hashes: result.hashes.iter().map(|h| (h.algorithm.name(), h.hex.clone())).collect(),
+            started,
+            finished,
+            bad_sectors: result.bad_sectors.clone(),
+            verified: result.verified,
+        }
+    }
+
+    /// Render the human-readable log
+    pub fn to_text(&self) -> String {
+        let mut lines = vec![
+            format!("Created By TotalImage {}", env!("CARGO_PKG_VERSION")),
+            String::new(),
+            format!("Information for {}:", self.image),
+            String::new(),
+            "[Source]".to_string(),
+            format!("Source: {}", self.source),
+            format!("Offset: {} bytes", self.offset),
+            format!("Bytes per Sector: {}", self.sector_size),
+            format!("Sector Count: {}", self.sector_count),
+            String::new(),
+            "[Image]".to_string(),
+            format!("Image File: {}", self.image),
+            format!("Image Size: {} bytes", self.image_size),
+            String::new(),
+            "[Computed Hashes]".to_string(),
+        ];
+        for (algorithm, hex) in &self.hashes {
+            lines.push(format!(" {:<17}{}", format!("{} checksum:", algorithm), hex));
+        }
+
+        let verification = match self.verified {
+            Some(true) => "Passed",
+            Some(false) => "FAILED",
+            None => "Not performed",
+        };
+        lines.extend([
+            String::new(),
+            format!("Acquisition started:  {}", self.started.to_rfc3339()),
+            format!("Acquisition finished: {}", self.finished.to_rfc3339()),
+            format!("Verification: {}", verification),
+            String::new(),
+            "[Bad Sectors]".to_string(),
+        ]);
+
+        let sector_size = self.sector_size as u64;
+        if self.bad_sectors.is_empty() {
+            lines.push("No bad sectors found".to_string());
+        }
+        for &(start, end) in &self.bad_sectors {
+            lines.push(format!(
+                " Sectors {}-{} (bytes {}-{})",
+                start / sector_size,
+                (end - 1) / sector_size,
+                start,
+                end - 1
+            ));
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    /// Write the text and JSON logs beside `image`
+    ///
+    /// Returns their paths, as given by [`info_log_paths`].
+    pub fn write(&self, image: &Path) -> Result<(PathBuf, PathBuf)> {
+        let (text_path, json_path) = info_log_paths(image);
+        std::fs::write(&text_path, self.to_text())?;
+        let json = serde_json::to_vec_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(&json_path, json)?;
+        Ok((text_path, json_path))
+    }
+}
+
+/// Paths of the text and JSON info logs for `image`
+///
+/// The extensions are appended rather than replaced, so `disk.dd` is
+/// described by `disk.dd.txt` and `disk.dd.json`.
+pub fn info_log_paths(image: &Path) -> (PathBuf, PathBuf) {
+    let with_suffix = |suffix: &str| {
+        let mut path = OsString::from(image.as_os_str());
+        path.push(suffix);
+        PathBuf::from(path)
+    };
+    (with_suffix(".txt"), with_suffix(".json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bad_sector_lines() {
+        let now = Utc::now();
+        let info = AcquisitionInfo {
+            source: "/dev/sdb".to_string(),
+            image: "disk.dd".to_string(),
+            offset: 0,
+            image_size: 4096,
+            sector_size: 512,
+            sector_count: 8,
+            hashes: BTreeMap::new(),
+            started: now,
+            finished: now,
+            bad_sectors: vec![(512, 1536), (3584, 4096)],
+            verified: None,
+        };
+
+        let text = info.to_text();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines.contains(&" Sectors 1-2 (bytes 512-1535)"));
+        assert!(lines.contains(&" Sectors 7-7 (bytes 3584-4095)"));
+        assert!(lines.contains(&"Verification: Not performed"));
+        assert!(!lines.contains(&"No bad sectors found"));
+    }
+}
@@ -3,19 +3,28 @@
 //! Provides functionality for:
 //! - Creating raw disk images (dd equivalent)
 //! - Creating VHD images (Fixed and Dynamic)
-//! - Hash verification (MD5, SHA1, SHA256)
+//! - Creating E01 images
+//! - Converting between image formats
+//! - Hash verification (MD5, SHA1, SHA256), computed on parallel threads
 //! - Progress tracking during acquisition
+//! - Text and JSON info logs recording each acquisition
 //!
 //! This crate implements the "write" side of TotalImage for FTK Imager replacement.
 
+pub mod convert;
+pub mod e01;
 pub mod error;
 pub mod hash;
+pub mod info;
 pub mod progress;
 pub mod raw;
 pub mod vhd;
 
+pub use convert::{convert_vault, convert_vault_with_progress, ConversionResult, ConvertOptions, VaultFormat};
+pub use e01::{E01CaseInfo, E01CreationResult, E01Creator, E01Options};
 pub use error::{AcquireError, Result};
-pub use hash::{HashAlgorithm, HashResult, Hasher};
-pub use progress::{AcquireProgress, ProgressCallback};
+pub use hash::{HashAlgorithm, HashResult, Hasher, ParallelHasher};
+pub use info::{info_log_paths, AcquisitionInfo};
+pub use progress::{AcquireProgress, CheckpointCallback, ProgressCallback};
 pub use raw::{AcquireOptions, RawAcquirer};
-pub use vhd::{VhdCreationResult, VhdCreator, VhdOptions, VhdOutputType};
+pub use vhd::{VhdAcquirer, VhdCreationResult, VhdCreator, VhdOptions, VhdOutputType};
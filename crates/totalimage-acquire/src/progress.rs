@@ -103,6 +103,9 @@ impl AcquireProgress {
 /// Callback type for progress updates
 pub type ProgressCallback = Arc<dyn Fn(&AcquireProgress) + Send + Sync>;
 
+/// Callback type for checkpoints, receiving the last image offset written
+pub type CheckpointCallback = Arc<dyn Fn(u64) + Send + Sync>;
+
 /// Format bytes as human-readable string
 fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
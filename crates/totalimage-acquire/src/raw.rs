@@ -1,10 +1,22 @@
 //! Raw disk image acquisition (dd equivalent)
 //!
 //! Creates raw sector-by-sector copies of disks or partitions.
+//!
+//! ## Resuming
+//!
+//! Long acquisitions can be restarted from the last checkpoint reported to a
+//! [`CheckpointCallback`] by setting [`AcquireOptions::resume_from`]. Hasher
+//! state is not persisted: on resume the already-written prefix is re-read
+//! from the *destination* and hashed again. The resulting digests therefore
+//! only attest to the source for the part acquired in the final run; the
+//! prefix is trusted as written, and bad sector ranges recorded before the
+//! interruption are not reported again.
 
 use crate::error::{AcquireError, Result};
-use crate::hash::{HashAlgorithm, HashResult, Hasher};
-use crate::progress::{AcquireProgress, ProgressCallback};
+use crate::hash::{advise_sequential, HashAlgorithm, HashResult, ParallelHasher};
+use crate::info::AcquisitionInfo;
+use crate::progress::{AcquireProgress, CheckpointCallback, ProgressCallback};
+use chrono::Utc;
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::Path;
@@ -15,13 +27,29 @@ use std::time::Instant;
 /// Options for raw acquisition
 #[derive(Debug, Clone)]
 pub struct AcquireOptions {
-    /// Block size for I/O operations (default: 64KB)
-    pub block_size: usize,
+    /// Size of each read, write and hash step (default: 1MB)
+    ///
+    /// Must be a power of two and a multiple of [`sector_size`](Self::sector_size).
+    /// Larger buffers mean fewer, longer transfers, which suits spinning
+    /// disks and fast sequential media. Smaller buffers suit flaky media: a
+    /// read error fails less data at once, so isolating bad sectors re-reads
+    /// less, and progress and cancellation are checked more often.
+    /// Acquisition fails with [`AcquireError::InvalidBlockSize`] otherwise.
+    pub buffer_size: usize,
     /// Hash algorithms to compute during acquisition
     pub hash_algorithms: Vec<HashAlgorithm>,
-    /// Skip bad blocks instead of failing
-    pub skip_bad_blocks: bool,
-    /// Verify after acquisition by re-reading
+    /// Continue past unreadable sectors instead of failing
+    pub skip_bad_sectors: bool,
+    /// Byte written in place of sectors that could not be read
+    pub fill_byte: u8,
+    /// Sector size used to isolate read errors (default: 512)
+    pub sector_size: usize,
+    /// Extra read attempts per sector before it is recorded as bad
+    pub sector_retries: u32,
+    /// Verify after acquisition by re-reading the source and the image
+    ///
+    /// Applies to [`RawAcquirer::acquire_to_file`]; stream acquisitions can
+    /// be checked with [`RawAcquirer::verify_stream`].
     pub verify_after: bool,
     /// Sync after each write
     pub sync_writes: bool,
@@ -29,18 +57,33 @@ pub struct AcquireOptions {
     pub count: Option<u64>,
     /// Offset to start reading from
     pub skip: u64,
+    /// Image offset of a checkpoint to resume an interrupted acquisition from
+    pub resume_from: Option<u64>,
+    /// Minimum number of bytes between checkpoint reports (default: 64MB)
+    pub checkpoint_interval: u64,
+    /// Write text and JSON info logs beside the image when done
+    ///
+    /// Applies to [`RawAcquirer::acquire_to_file`]; see [`crate::info`] for
+    /// where they go and what they record.
+    pub write_info_log: bool,
 }
 
 impl Default for AcquireOptions {
     fn default() -> Self {
         Self {
-            block_size: 64 * 1024, // 64KB
+            buffer_size: 1024 * 1024, // 1MB
             hash_algorithms: vec![HashAlgorithm::Md5, HashAlgorithm::Sha256],
-            skip_bad_blocks: false,
+            skip_bad_sectors: false,
+            fill_byte: 0,
+            sector_size: 512,
+            sector_retries: 1,
             verify_after: true,
             sync_writes: false,
             count: None,
             skip: 0,
+            resume_from: None,
+            checkpoint_interval: 64 * 1024 * 1024, // 64MB
+            write_info_log: false,
         }
     }
 }
@@ -56,9 +99,11 @@ pub struct AcquireResult {
     pub elapsed: std::time::Duration,
     /// Average transfer rate in bytes/second
     pub bytes_per_second: f64,
-    /// Number of bad blocks encountered
-    pub bad_blocks: u64,
-    /// Verification passed (if verify_after was enabled)
+    /// Unreadable byte ranges as `(start, end)` offsets into the image,
+    /// end exclusive; adjacent bad sectors are merged into one range
+    pub bad_sectors: Vec<(u64, u64)>,
+    /// Whether re-reading the source and the image reproduced the
+    /// acquisition hashes (if verify_after was enabled)
     pub verified: Option<bool>,
 }
 
@@ -66,15 +111,13 @@ pub struct AcquireResult {
 pub struct RawAcquirer {
     options: AcquireOptions,
     cancel_flag: Arc<AtomicBool>,
+    checkpoint_callback: Option<CheckpointCallback>,
 }
 
 impl RawAcquirer {
     /// Create a new acquirer with default options
     pub fn new() -> Self {
-        Self {
-            options: AcquireOptions::default(),
-            cancel_flag: Arc::new(AtomicBool::new(false)),
-        }
+        Self::with_options(AcquireOptions::default())
     }
 
     /// Create with custom options
@@ -82,21 +125,40 @@ impl RawAcquirer {
         Self {
             options,
             cancel_flag: Arc::new(AtomicBool::new(false)),
+            checkpoint_callback: None,
         }
     }
 
+    /// Report checkpoints to `callback`
+    ///
+    /// The callback receives the image offset up to which data has been
+    /// written and flushed, at most every
+    /// [`AcquireOptions::checkpoint_interval`] bytes and once on completion.
+    /// Persist it and pass it back as [`AcquireOptions::resume_from`] to
+    /// continue after an interruption.
+    pub fn with_checkpoint_callback(mut self, callback: CheckpointCallback) -> Self {
+        self.checkpoint_callback = Some(callback);
+        self
+    }
+
     /// Get a cancel flag that can be used to cancel the operation
     pub fn cancel_flag(&self) -> Arc<AtomicBool> {
         self.cancel_flag.clone()
     }
 
     /// Acquire from a file/device to a raw image file
+    ///
+    /// With [`AcquireOptions::write_info_log`] set, info logs are written
+    /// beside `dest_path` once the image is complete and verified.
     pub fn acquire_to_file(
         &self,
         source_path: &Path,
         dest_path: &Path,
         progress_callback: Option<ProgressCallback>,
     ) -> Result<AcquireResult> {
+        self.validate_options()?;
+        let started = Utc::now();
+
         // Open source
         let mut source = File::open(source_path).map_err(|e| {
             if e.kind() == std::io::ErrorKind::NotFound {
@@ -108,6 +170,8 @@ impl RawAcquirer {
             }
         })?;
 
+        advise_sequential(&source);
+
         // Get source size
         let source_size = source.seek(SeekFrom::End(0))?;
         source.seek(SeekFrom::Start(self.options.skip))?;
@@ -119,48 +183,158 @@ impl RawAcquirer {
             source_size.saturating_sub(self.options.skip)
         };
 
-        // Create destination file
-        let mut dest = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(dest_path)
-            .map_err(|e| AcquireError::DestinationError(e.to_string()))?;
-
         // Perform acquisition
-        let result = self.acquire_stream(&mut source, &mut dest, Some(total_bytes), progress_callback)?;
+        let result = if let Some(offset) = self.options.resume_from {
+            // Reopen the partial image, discarding anything after the checkpoint
+            let mut dest = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(dest_path)
+                .map_err(|e| AcquireError::DestinationError(e.to_string()))?;
+            let dest_len = dest.metadata()?.len();
+            if dest_len < offset {
+                return Err(AcquireError::SizeMismatch {
+                    expected: offset,
+                    actual: dest_len,
+                });
+            }
+            dest.set_len(offset)?;
+
+            self.resume_stream(&mut source, &mut dest, Some(total_bytes), progress_callback)?
+        } else {
+            // Create destination file
+            let mut dest = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(dest_path)
+                .map_err(|e| AcquireError::DestinationError(e.to_string()))?;
+
+            self.acquire_stream(&mut source, &mut dest, Some(total_bytes), progress_callback)?
+        };
 
         // Verify if requested
         let verified = if self.options.verify_after && !result.hashes.is_empty() {
-            let verify_result = self.verify_file(dest_path, &result.hashes)?;
-            Some(verify_result)
+            let mut source = File::open(source_path)?;
+            advise_sequential(&source);
+            source.seek(SeekFrom::Start(self.options.skip))?;
+            let mut dest = File::open(dest_path)?;
+            advise_sequential(&dest);
+            Some(self.verify_stream(&mut source, &mut dest, &result)?)
         } else {
             None
         };
 
-        Ok(AcquireResult {
+        let result = AcquireResult {
             bytes_acquired: result.bytes_acquired,
             hashes: result.hashes,
             elapsed: result.elapsed,
             bytes_per_second: result.bytes_per_second,
-            bad_blocks: result.bad_blocks,
+            bad_sectors: result.bad_sectors,
             verified,
-        })
+        };
+
+        if self.options.write_info_log {
+            AcquisitionInfo::new(source_path, dest_path, &self.options, &result, started, Utc::now())
+                .write(dest_path)?;
+        }
+
+        Ok(result)
     }
 
     /// Acquire from any reader to any writer
-    pub fn acquire_stream<R: Read, W: Write>(
+    ///
+    /// With [`AcquireOptions::skip_bad_sectors`] set, a failed block read is
+    /// retried sector by sector, the way `ddrescue` does: sectors that still
+    /// fail after [`AcquireOptions::sector_retries`] extra attempts are written
+    /// as [`AcquireOptions::fill_byte`] and recorded in
+    /// [`AcquireResult::bad_sectors`], and acquisition continues.
+    ///
+    /// This always starts at the beginning of the image and ignores
+    /// [`AcquireOptions::resume_from`]; use [`resume_stream`](Self::resume_stream)
+    /// to continue an interrupted acquisition.
+    pub fn acquire_stream<R: Read + Seek, W: Write>(
         &self,
         source: &mut R,
         dest: &mut W,
         total_bytes: Option<u64>,
         progress_callback: Option<ProgressCallback>,
+    ) -> Result<AcquireResult> {
+        self.validate_options()?;
+        let hasher = ParallelHasher::new(&self.options.hash_algorithms);
+        self.copy_stream(source, dest, total_bytes, 0, hasher, progress_callback)
+    }
+
+    /// Continue an interrupted acquisition at [`AcquireOptions::resume_from`]
+    ///
+    /// `source` must be positioned at the start of the image, as for
+    /// [`acquire_stream`](Self::acquire_stream), and `dest` must hold the
+    /// image written so far. The prefix before the checkpoint is re-read from
+    /// `dest` to rebuild the hashes (see the module docs for the caveat), then
+    /// both streams are moved to the checkpoint and copying continues.
+    /// `total_bytes` and the reported byte counts cover the whole image.
+    pub fn resume_stream<R: Read + Seek, W: Read + Write + Seek>(
+        &self,
+        source: &mut R,
+        dest: &mut W,
+        total_bytes: Option<u64>,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<AcquireResult> {
+        self.validate_options()?;
+        let offset = self.options.resume_from.unwrap_or(0);
+        let base = source.stream_position()?;
+
+        // Rebuild hasher state from the data already in the image
+        let mut hasher = ParallelHasher::new(&self.options.hash_algorithms);
+        dest.seek(SeekFrom::Start(0))?;
+        let mut buffer = vec![0u8; self.options.buffer_size];
+        let mut prefix = (&mut *dest).take(offset);
+        loop {
+            let n = prefix.read(&mut buffer)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buffer[..n]);
+        }
+        if hasher.bytes_processed() < offset {
+            return Err(AcquireError::SizeMismatch {
+                expected: offset,
+                actual: hasher.bytes_processed(),
+            });
+        }
+
+        source.seek(SeekFrom::Start(base + offset))?;
+        dest.seek(SeekFrom::Start(offset))?;
+        self.copy_stream(source, dest, total_bytes, offset, hasher, progress_callback)
+    }
+
+    /// Check that the buffer size is a power of two and whole sectors
+    fn validate_options(&self) -> Result<()> {
+        let buffer_size = self.options.buffer_size;
+        if !buffer_size.is_power_of_two() || !buffer_size.is_multiple_of(self.options.sector_size.max(1)) {
+            return Err(AcquireError::InvalidBlockSize(buffer_size));
+        }
+        Ok(())
+    }
+
+    /// Copy the image from `start_offset` onwards, feeding `hasher`
+    ///
+    /// Both streams must already be positioned at `start_offset`.
+    fn copy_stream<R: Read + Seek, W: Write>(
+        &self,
+        source: &mut R,
+        dest: &mut W,
+        total_bytes: Option<u64>,
+        start_offset: u64,
+        mut hasher: ParallelHasher,
+        progress_callback: Option<ProgressCallback>,
     ) -> Result<AcquireResult> {
         let start_time = Instant::now();
-        let mut hasher = Hasher::new(&self.options.hash_algorithms);
-        let mut buffer = vec![0u8; self.options.block_size];
-        let mut bytes_acquired: u64 = 0;
-        let mut bad_blocks: u64 = 0;
+        let base = source.stream_position()? - start_offset;
+        let mut buffer = vec![0u8; self.options.buffer_size];
+        let mut bytes_acquired = start_offset;
+        let mut last_checkpoint = start_offset;
+        let mut bad_sectors = Vec::new();
         let remaining = total_bytes;
 
         loop {
@@ -185,14 +359,20 @@ impl RawAcquirer {
                 Ok(0) => break, // EOF
                 Ok(n) => n,
                 Err(e) => {
-                    if self.options.skip_bad_blocks {
-                        bad_blocks += 1;
-                        // Fill with zeros for bad block
-                        buffer[..to_read].fill(0);
-                        to_read
-                    } else {
+                    if !self.options.skip_bad_sectors {
                         return Err(AcquireError::ReadError(e.to_string()));
                     }
+                    let n = self.read_sectors(
+                        source,
+                        base,
+                        bytes_acquired,
+                        &mut buffer[..to_read],
+                        &mut bad_sectors,
+                    )?;
+                    if n == 0 {
+                        break; // EOF reached while isolating the error
+                    }
+                    n
                 }
             };
 
@@ -209,6 +389,15 @@ impl RawAcquirer {
 
             bytes_acquired += bytes_read as u64;
 
+            // Report a checkpoint once enough data is safely written
+            if let Some(ref callback) = self.checkpoint_callback {
+                if bytes_acquired - last_checkpoint >= self.options.checkpoint_interval {
+                    dest.flush().map_err(|e| AcquireError::WriteError(e.to_string()))?;
+                    callback(bytes_acquired);
+                    last_checkpoint = bytes_acquired;
+                }
+            }
+
             // Report progress
             if let Some(ref callback) = progress_callback {
                 let progress = AcquireProgress::calculate(
@@ -223,10 +412,13 @@ impl RawAcquirer {
 
         // Final flush
         dest.flush().map_err(|e| AcquireError::WriteError(e.to_string()))?;
+        if let Some(ref callback) = self.checkpoint_callback {
+            callback(bytes_acquired);
+        }
 
         let elapsed = start_time.elapsed();
         let bytes_per_second = if elapsed.as_secs_f64() > 0.0 {
-            bytes_acquired as f64 / elapsed.as_secs_f64()
+            (bytes_acquired - start_offset) as f64 / elapsed.as_secs_f64()
         } else {
             0.0
         };
@@ -236,14 +428,111 @@ impl RawAcquirer {
             hashes: hasher.finalize(),
             elapsed,
             bytes_per_second,
-            bad_blocks,
+            bad_sectors,
             verified: None,
         })
     }
 
+    /// Re-read a failed block one sector at a time
+    ///
+    /// `offset` is the block's position in the image and `base` the source
+    /// position the image starts at. Returns the number of bytes placed in
+    /// `buffer`, which is short only if the source ends inside the block.
+    fn read_sectors<R: Read + Seek>(
+        &self,
+        source: &mut R,
+        base: u64,
+        offset: u64,
+        buffer: &mut [u8],
+        bad_sectors: &mut Vec<(u64, u64)>,
+    ) -> Result<usize> {
+        let sector_size = self.options.sector_size.max(1);
+        let mut filled = 0;
+
+        while filled < buffer.len() {
+            let len = sector_size.min(buffer.len() - filled);
+            let sector = &mut buffer[filled..filled + len];
+            let position = offset + filled as u64;
+
+            let mut outcome = None;
+            for _ in 0..=self.options.sector_retries {
+                source.seek(SeekFrom::Start(base + position))?;
+                if let Ok(n) = read_full(source, sector) {
+                    outcome = Some(n);
+                    break;
+                }
+            }
+
+            match outcome {
+                Some(n) => {
+                    filled += n;
+                    if n < len {
+                        break; // EOF
+                    }
+                }
+                None => {
+                    sector.fill(self.options.fill_byte);
+                    let end = position + len as u64;
+                    match bad_sectors.last_mut() {
+                        Some(last) if last.1 == position => last.1 = end,
+                        _ => bad_sectors.push((position, end)),
+                    }
+                    filled += len;
+                }
+            }
+        }
+
+        // Leave the source just past the recovered data
+        source.seek(SeekFrom::Start(base + offset + filled as u64))?;
+        Ok(filled)
+    }
+
+    /// Check a finished acquisition by re-reading both ends
+    ///
+    /// `source` must be positioned at the start of the image, as for
+    /// [`acquire_stream`](Self::acquire_stream), and `dest` holds the image
+    /// written from it. Both are hashed again in chunks and must each
+    /// reproduce `result`'s hashes over [`AcquireResult::bytes_acquired`]
+    /// bytes. When bad sectors were skipped the source can't be expected to
+    /// read back the same, so only the image is checked.
+    ///
+    /// Returns false on any mismatch, including either side coming up short.
+    pub fn verify_stream<R: Read + Seek, D: Read + Seek>(
+        &self,
+        source: &mut R,
+        dest: &mut D,
+        result: &AcquireResult,
+    ) -> Result<bool> {
+        let algorithms: Vec<_> = result.hashes.iter().map(|h| h.algorithm).collect();
+        let matches = |reader: &mut dyn Read| -> Result<bool> {
+            let mut counted = CountingReader { inner: reader.take(result.bytes_acquired), count: 0 };
+            let actual = crate::hash::hash_reader(&mut counted, &algorithms)?;
+            Ok(counted.count == result.bytes_acquired
+                && result.hashes.iter().all(|expected| {
+                    actual
+                        .iter()
+                        .find(|h| h.algorithm == expected.algorithm)
+                        .is_some_and(|h| h.matches(expected))
+                }))
+        };
+
+        dest.seek(SeekFrom::Start(0))?;
+        if !matches(dest)? {
+            return Ok(false);
+        }
+        if self.cancel_flag.load(Ordering::Relaxed) {
+            return Err(AcquireError::Cancelled);
+        }
+        if !result.bad_sectors.is_empty() {
+            return Ok(true);
+        }
+        matches(source)
+    }
+
     /// Verify a file against expected hashes
     pub fn verify_file(&self, path: &Path, expected_hashes: &[HashResult]) -> Result<bool> {
         let mut file = File::open(path)?;
+        advise_sequential(&file);
         let algorithms: Vec<_> = expected_hashes.iter().map(|h| h.algorithm).collect();
 
         let actual_hashes = crate::hash::hash_reader(&mut file, &algorithms)?;
@@ -264,6 +553,32 @@ impl RawAcquirer {
     }
 }
 
+/// Reader that counts the bytes passing through it
+struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count += n as u64;
+        Ok(n)
+    }
+}
+
+/// Read until `buf` is full or EOF, returning the number of bytes read
+fn read_full<R: Read>(source: &mut R, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match source.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
 impl Default for RawAcquirer {
     fn default() -> Self {
         Self::new()
@@ -295,6 +610,35 @@ mod tests {
         assert!(!result.hashes.is_empty());
     }
 
+    #[test]
+    fn test_verify_stream() {
+        let source_data: Vec<u8> = (0..200_000u32).map(|i| (i % 253) as u8).collect();
+        let mut source = Cursor::new(source_data.clone());
+        let mut dest = Cursor::new(Vec::new());
+
+        let acquirer = RawAcquirer::new();
+        let result = acquirer.acquire_stream(&mut source, &mut dest, None, None).unwrap();
+
+        source.set_position(0);
+        assert!(acquirer.verify_stream(&mut source, &mut dest, &result).unwrap());
+
+        // A flipped byte in the image
+        dest.get_mut()[150_000] ^= 0x01;
+        source.set_position(0);
+        assert!(!acquirer.verify_stream(&mut source, &mut dest, &result).unwrap());
+        dest.get_mut()[150_000] ^= 0x01;
+
+        // A source that no longer reads back the same
+        let mut changed = source_data.clone();
+        changed[10] ^= 0xFF;
+        assert!(!acquirer.verify_stream(&mut Cursor::new(changed), &mut dest, &result).unwrap());
+
+        // A truncated image
+        dest.get_mut().truncate(199_999);
+        source.set_position(0);
+        assert!(!acquirer.verify_stream(&mut source, &mut dest, &result).unwrap());
+    }
+
     #[test]
     fn test_acquire_to_file() {
         let dir = tempdir().unwrap();
@@ -319,6 +663,47 @@ mod tests {
         // Verify destination content
         let dest_data = std::fs::read(&dest_path).unwrap();
         assert_eq!(dest_data, source_data);
+        assert!(!crate::info::info_log_paths(&dest_path).0.exists());
+    }
+
+    #[test]
+    fn test_acquire_writes_info_log() {
+        let dir = tempdir().unwrap();
+        let source_path = dir.path().join("source.bin");
+        let dest_path = dir.path().join("dest.dd");
+
+        // Three sectors, the last one partial
+        let source_data: Vec<u8> = (0..1500u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&source_path, &source_data).unwrap();
+
+        let acquirer = RawAcquirer::with_options(AcquireOptions {
+            hash_algorithms: vec![HashAlgorithm::Md5, HashAlgorithm::Sha1, HashAlgorithm::Sha256],
+            write_info_log: true,
+            ..Default::default()
+        });
+        acquirer.acquire_to_file(&source_path, &dest_path, None).unwrap();
+
+        let expected = crate::hash::hash_file(&source_path, &acquirer.options.hash_algorithms).unwrap();
+        let (text_path, json_path) = crate::info::info_log_paths(&dest_path);
+        assert_eq!(text_path, dir.path().join("dest.dd.txt"));
+
+        let text = std::fs::read_to_string(&text_path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines.contains(&format!("Source: {}", source_path.display()).as_str()));
+        assert!(lines.contains(&"Image Size: 1500 bytes"));
+        assert!(lines.contains(&"Sector Count: 3"));
+        assert!(lines.contains(&format!(" MD5 checksum:    {}", expected[0].hex).as_str()));
+        assert!(lines.contains(&format!(" SHA1 checksum:   {}", expected[1].hex).as_str()));
+        assert!(lines.contains(&format!(" SHA256 checksum: {}", expected[2].hex).as_str()));
+        assert!(lines.contains(&"Verification: Passed"));
+        assert!(lines.contains(&"No bad sectors found"));
+
+        let json: serde_json::Value = serde_json::from_slice(&std::fs::read(&json_path).unwrap()).unwrap();
+        assert_eq!(json["image_size"], 1500);
+        assert_eq!(json["sector_count"], 3);
+        assert_eq!(json["hashes"]["SHA256"], expected[2].hex.as_str());
+        assert_eq!(json["verified"], true);
+        assert_eq!(json["bad_sectors"], serde_json::json!([]));
     }
 
     #[test]
@@ -342,6 +727,194 @@ mod tests {
         assert_eq!(result.bytes_acquired, 500);
     }
 
+    /// Reader that fails every read touching `[bad_start, bad_end)`
+    struct FailingReader {
+        inner: Cursor<Vec<u8>>,
+        bad_start: u64,
+        bad_end: u64,
+        failures: u32,
+    }
+
+    impl Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let pos = self.inner.position();
+            if pos < self.bad_end && pos + buf.len() as u64 > self.bad_start {
+                self.failures += 1;
+                return Err(std::io::Error::other("simulated media error"));
+            }
+            self.inner.read(buf)
+        }
+    }
+
+    impl Seek for FailingReader {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[test]
+    fn test_buffer_size() {
+        let source_data: Vec<u8> = (0..100_000u32).map(|i| (i * 13 % 251) as u8).collect();
+
+        let acquire = |buffer_size: usize| {
+            let acquirer = RawAcquirer::with_options(AcquireOptions { buffer_size, ..Default::default() });
+            let mut dest = Vec::new();
+            let result = acquirer.acquire_stream(&mut Cursor::new(&source_data), &mut dest, None, None);
+            result.map(|result| (result, dest))
+        };
+
+        let (small, small_image) = acquire(4096).unwrap();
+        let (large, large_image) = acquire(64 * 1024).unwrap();
+        assert_eq!(small_image, source_data);
+        assert_eq!(large_image, source_data);
+        assert_eq!(small.bytes_acquired, large.bytes_acquired);
+        for (a, b) in small.hashes.iter().zip(&large.hashes) {
+            assert!(a.matches(b));
+        }
+
+        // Not a power of two, and smaller than a sector
+        for buffer_size in [0, 3000, 256] {
+            assert!(matches!(acquire(buffer_size), Err(AcquireError::InvalidBlockSize(n)) if n == buffer_size));
+        }
+    }
+
+    #[test]
+    fn test_skip_bad_sectors() {
+        let source_data: Vec<u8> = (0..8192u32).map(|i| (i % 200 + 1) as u8).collect();
+        let mut source = FailingReader {
+            inner: Cursor::new(source_data.clone()),
+            bad_start: 2560,
+            bad_end: 3584,
+            failures: 0,
+        };
+        let mut dest = Vec::new();
+
+        let acquirer = RawAcquirer::with_options(AcquireOptions {
+            buffer_size: 4096,
+            skip_bad_sectors: true,
+            sector_retries: 2,
+            ..Default::default()
+        });
+        let result = acquirer
+            .acquire_stream(&mut source, &mut dest, Some(source_data.len() as u64), None)
+            .unwrap();
+
+        assert_eq!(result.bytes_acquired, source_data.len() as u64);
+        assert_eq!(result.bad_sectors, vec![(2560, 3584)]);
+        // Block read plus three attempts for each of the two bad sectors
+        assert_eq!(source.failures, 1 + 2 * 3);
+
+        assert_eq!(&dest[..2560], &source_data[..2560]);
+        assert!(dest[2560..3584].iter().all(|&b| b == 0));
+        assert_eq!(&dest[3584..], &source_data[3584..]);
+    }
+
+    #[test]
+    fn test_bad_sector_fill_byte_and_strict_mode() {
+        let source_data = vec![0x55u8; 2048];
+        let failing = || FailingReader {
+            inner: Cursor::new(source_data.clone()),
+            bad_start: 1024,
+            bad_end: 1025,
+            failures: 0,
+        };
+
+        let acquirer = RawAcquirer::with_options(AcquireOptions {
+            skip_bad_sectors: true,
+            fill_byte: 0xEE,
+            sector_retries: 0,
+            ..Default::default()
+        });
+        let mut dest = Vec::new();
+        let result = acquirer.acquire_stream(&mut failing(), &mut dest, None, None).unwrap();
+        assert_eq!(result.bad_sectors, vec![(1024, 1536)]);
+        assert!(dest[1024..1536].iter().all(|&b| b == 0xEE));
+        assert_eq!(dest.len(), 2048);
+
+        // Without the flag the first read error aborts acquisition
+        let result = RawAcquirer::new().acquire_stream(&mut failing(), &mut Vec::new(), None, None);
+        assert!(matches!(result, Err(AcquireError::ReadError(_))));
+    }
+
+    #[test]
+    fn test_resume_interrupted_acquisition() {
+        use std::sync::atomic::AtomicU64;
+
+        let dir = tempdir().unwrap();
+        let source_path = dir.path().join("source.bin");
+        let full_path = dir.path().join("full.img");
+        let resumed_path = dir.path().join("resumed.img");
+
+        let source_data: Vec<u8> = (0..256 * 1024u32).map(|i| (i * 7 % 251) as u8).collect();
+        std::fs::write(&source_path, &source_data).unwrap();
+
+        let options = AcquireOptions {
+            buffer_size: 4096,
+            checkpoint_interval: 16 * 1024,
+            hash_algorithms: vec![HashAlgorithm::Md5, HashAlgorithm::Sha1, HashAlgorithm::Sha256],
+            ..Default::default()
+        };
+
+        // Reference run without interruption
+        let reference = RawAcquirer::with_options(options.clone())
+            .acquire_to_file(&source_path, &full_path, None)
+            .unwrap();
+
+        // First run, cancelled once the halfway checkpoint is persisted
+        let saved = Arc::new(AtomicU64::new(0));
+        let acquirer = RawAcquirer::with_options(options.clone());
+        let cancel = acquirer.cancel_flag();
+        let store = Arc::clone(&saved);
+        let halfway = source_data.len() as u64 / 2;
+        let acquirer = acquirer.with_checkpoint_callback(Arc::new(move |offset| {
+            store.store(offset, Ordering::Relaxed);
+            if offset >= halfway {
+                cancel.store(true, Ordering::Relaxed);
+            }
+        }));
+        let result = acquirer.acquire_to_file(&source_path, &resumed_path, None);
+        assert!(matches!(result, Err(AcquireError::Cancelled)));
+        let checkpoint = saved.load(Ordering::Relaxed);
+        assert_eq!(checkpoint, halfway);
+
+        // Simulate a torn write past the checkpoint
+        let mut partial = OpenOptions::new().append(true).open(&resumed_path).unwrap();
+        partial.write_all(&[0xFF; 1000]).unwrap();
+        drop(partial);
+
+        // Resumed run
+        let resumed = RawAcquirer::with_options(AcquireOptions {
+            resume_from: Some(checkpoint),
+            ..options
+        })
+        .acquire_to_file(&source_path, &resumed_path, None)
+        .unwrap();
+
+        assert_eq!(resumed.bytes_acquired, source_data.len() as u64);
+        assert_eq!(resumed.verified, Some(true));
+        assert_eq!(std::fs::read(&resumed_path).unwrap(), source_data);
+        assert_eq!(resumed.hashes.len(), 3);
+        for (actual, expected) in resumed.hashes.iter().zip(&reference.hashes) {
+            assert!(actual.matches(expected));
+        }
+    }
+
+    #[test]
+    fn test_resume_rejects_short_destination() {
+        let dir = tempdir().unwrap();
+        let source_path = dir.path().join("source.bin");
+        let dest_path = dir.path().join("dest.img");
+        std::fs::write(&source_path, vec![1u8; 4096]).unwrap();
+        std::fs::write(&dest_path, vec![1u8; 100]).unwrap();
+
+        let acquirer = RawAcquirer::with_options(AcquireOptions {
+            resume_from: Some(2048),
+            ..Default::default()
+        });
+        let result = acquirer.acquire_to_file(&source_path, &dest_path, None);
+        assert!(matches!(result, Err(AcquireError::SizeMismatch { expected: 2048, actual: 100 })));
+    }
+
     #[test]
     fn test_cancel_acquisition() {
         let source_data = vec![0u8; 1024 * 1024]; // 1MB
@@ -349,7 +922,7 @@ mod tests {
         let mut dest = Vec::new();
 
         let acquirer = RawAcquirer::with_options(AcquireOptions {
-            block_size: 1024,
+            buffer_size: 1024,
             ..Default::default()
         });
 
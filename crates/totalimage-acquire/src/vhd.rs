@@ -1,15 +1,20 @@
 //! VHD image creation
 //!
 //! Creates Microsoft VHD (Virtual Hard Disk) images.
-//! Supports Fixed and Dynamic VHD formats.
+//! Supports Fixed and Dynamic VHD formats. Footers and dynamic headers are
+//! built from the `totalimage-vaults` VHD types so that images written here
+//! are parsed by exactly the code that reads them back.
 
 use crate::error::{AcquireError, Result};
 use crate::hash::{HashAlgorithm, HashResult, Hasher};
-use crate::progress::AcquireProgress;
+use crate::progress::{AcquireProgress, ProgressCallback};
+use std::fs::{File, OpenOptions};
 use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
+use totalimage_vaults::vhd::types::{DiskGeometry, VhdDynamicHeader, VhdFooter, VhdType};
 
 /// VHD type to create
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -281,6 +286,96 @@ impl VhdCreator {
     }
 }
 
+/// VHD image acquirer
+///
+/// The VHD counterpart of [`RawAcquirer`](crate::raw::RawAcquirer): images a
+/// file or device into a fixed or dynamic VHD, hashing the source data and
+/// reporting progress through a [`ProgressCallback`].
+pub struct VhdAcquirer {
+    creator: VhdCreator,
+}
+
+impl VhdAcquirer {
+    /// Create a new acquirer with default options
+    pub fn new() -> Self {
+        Self::with_options(VhdOptions::default())
+    }
+
+    /// Create with custom options
+    pub fn with_options(options: VhdOptions) -> Self {
+        Self {
+            creator: VhdCreator::new(options),
+        }
+    }
+
+    /// Get a cancel flag that can be used to cancel the operation
+    pub fn cancel_flag(&self) -> Arc<AtomicBool> {
+        self.creator.cancel_flag()
+    }
+
+    /// Acquire from a file/device to a VHD image file
+    pub fn acquire_to_file(
+        &self,
+        source_path: &Path,
+        dest_path: &Path,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<VhdCreationResult> {
+        let mut source = File::open(source_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                AcquireError::SourceNotFound(source_path.display().to_string())
+            }
+            std::io::ErrorKind::PermissionDenied => {
+                AcquireError::PermissionDenied(source_path.display().to_string())
+            }
+            _ => AcquireError::IoError(e),
+        })?;
+        let source_size = source.seek(SeekFrom::End(0))?;
+
+        let mut dest = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(dest_path)
+            .map_err(|e| AcquireError::DestinationError(e.to_string()))?;
+
+        let result = self.acquire_stream(&mut source, source_size, &mut dest, progress_callback)?;
+        dest.flush().map_err(|e| AcquireError::WriteError(e.to_string()))?;
+
+        Ok(result)
+    }
+
+    /// Acquire `source_size` bytes from a reader into a VHD written to `dest`
+    ///
+    /// The source is read from its beginning regardless of its current position.
+    pub fn acquire_stream<R, W>(
+        &self,
+        source: &mut R,
+        source_size: u64,
+        dest: &mut W,
+        progress_callback: Option<ProgressCallback>,
+    ) -> Result<VhdCreationResult>
+    where
+        R: Read + Seek,
+        W: Write + Seek,
+    {
+        source.seek(SeekFrom::Start(0))?;
+        let callback = progress_callback.map(|cb| move |progress: &AcquireProgress| cb(progress));
+
+        match self.creator.options.vhd_type {
+            VhdOutputType::Fixed => self.creator.create_fixed(source, source_size, dest, callback),
+            VhdOutputType::Dynamic => {
+                self.creator.create_dynamic(source, source_size, dest, callback)
+            }
+        }
+    }
+}
+
+impl Default for VhdAcquirer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Result of VHD creation
 #[derive(Debug)]
 pub struct VhdCreationResult {
@@ -306,40 +401,11 @@ impl VhdCreationResult {
     }
 }
 
-/// VHD disk types
-#[derive(Debug, Clone, Copy)]
-enum VhdType {
-    Fixed = 2,
-    Dynamic = 3,
-}
-
-/// VHD footer structure
-struct VhdFooterData {
-    cookie: [u8; 8],
-    features: u32,
-    version: u32,
-    data_offset: u64,
-    timestamp: u32,
-    creator_app: [u8; 4],
-    creator_version: u32,
-    creator_os: u32,
-    original_size: u64,
-    current_size: u64,
-    cylinders: u16,
-    heads: u8,
-    sectors: u8,
-    disk_type: u32,
-    uuid: [u8; 16],
-}
-
-/// Create a VHD footer
-fn create_vhd_footer(size: u64, vhd_type: VhdType, creator_app: &[u8; 4]) -> VhdFooterData {
+/// Create a VHD footer with a valid checksum
+fn create_vhd_footer(size: u64, vhd_type: VhdType, creator_app: &[u8; 4]) -> VhdFooter {
     // Calculate CHS geometry
     let (cylinders, heads, sectors) = calculate_chs(size);
 
-    // Generate UUID
-    let uuid = generate_uuid();
-
     // VHD epoch is January 1, 2000 00:00:00
     let vhd_epoch = 946684800u64; // Unix timestamp
     let now = std::time::SystemTime::now()
@@ -349,12 +415,12 @@ fn create_vhd_footer(size: u64, vhd_type: VhdType, creator_app: &[u8; 4]) -> Vhd
     let timestamp = (now.saturating_sub(vhd_epoch)) as u32;
 
     let data_offset = match vhd_type {
-        VhdType::Fixed => 0xFFFFFFFFFFFFFFFFu64, // No dynamic header
         VhdType::Dynamic => 512, // Dynamic header follows footer
+        _ => 0xFFFFFFFFFFFFFFFFu64, // No dynamic header
     };
 
-    VhdFooterData {
-        cookie: *b"conectix",
+    let mut footer = VhdFooter {
+        cookie: *VhdFooter::COOKIE,
         features: 2, // Reserved (no features)
         version: 0x00010000, // Version 1.0
         data_offset,
@@ -364,109 +430,53 @@ fn create_vhd_footer(size: u64, vhd_type: VhdType, creator_app: &[u8; 4]) -> Vhd
         creator_os: 0x5769326B, // "Wi2k" (Windows)
         original_size: size,
         current_size: size,
-        cylinders,
-        heads,
-        sectors,
-        disk_type: vhd_type as u32,
-        uuid,
-    }
+        geometry: DiskGeometry {
+            cylinders,
+            heads,
+            sectors,
+        },
+        disk_type: vhd_type,
+        checksum: 0,
+        uuid: generate_uuid(),
+        saved_state: 0,
+        reserved: [0u8; 427],
+    };
+    footer.checksum = footer.calculate_checksum();
+    footer
 }
 
-/// Serialize footer to bytes with checksum
-fn serialize_footer(footer: &VhdFooterData) -> [u8; 512] {
-    let mut bytes = [0u8; 512];
-
-    bytes[0..8].copy_from_slice(&footer.cookie);
-    bytes[8..12].copy_from_slice(&footer.features.to_be_bytes());
-    bytes[12..16].copy_from_slice(&footer.version.to_be_bytes());
-    bytes[16..24].copy_from_slice(&footer.data_offset.to_be_bytes());
-    bytes[24..28].copy_from_slice(&footer.timestamp.to_be_bytes());
-    bytes[28..32].copy_from_slice(&footer.creator_app);
-    bytes[32..36].copy_from_slice(&footer.creator_version.to_be_bytes());
-    bytes[36..40].copy_from_slice(&footer.creator_os.to_be_bytes());
-    bytes[40..48].copy_from_slice(&footer.original_size.to_be_bytes());
-    bytes[48..56].copy_from_slice(&footer.current_size.to_be_bytes());
-
-    // Geometry
-    bytes[56..58].copy_from_slice(&footer.cylinders.to_be_bytes());
-    bytes[58] = footer.heads;
-    bytes[59] = footer.sectors;
-
-    bytes[60..64].copy_from_slice(&footer.disk_type.to_be_bytes());
-    // Checksum at 64..68 - calculated below
-    bytes[68..84].copy_from_slice(&footer.uuid);
-    bytes[84] = 0; // saved_state
-    // bytes[85..512] = reserved (zeros)
-
-    // Calculate checksum
-    let mut sum: u32 = 0;
-    for (i, &byte) in bytes.iter().enumerate() {
-        if i >= 64 && i < 68 {
-            continue; // Skip checksum field
-        }
-        sum = sum.wrapping_add(byte as u32);
-    }
-    let checksum = !sum;
-    bytes[64..68].copy_from_slice(&checksum.to_be_bytes());
-
+/// Serialize footer to bytes
+fn serialize_footer(footer: &VhdFooter) -> [u8; VhdFooter::SIZE] {
+    let mut bytes = [0u8; VhdFooter::SIZE];
+    footer.serialize(&mut bytes);
     bytes
 }
 
-/// VHD dynamic header structure
-struct VhdDynamicHeaderData {
-    cookie: [u8; 8],
-    data_offset: u64,
-    table_offset: u64,
-    header_version: u32,
-    max_table_entries: u32,
-    block_size: u32,
-    parent_uuid: [u8; 16],
-    parent_timestamp: u32,
-}
-
-/// Create a dynamic header
-fn create_dynamic_header(num_blocks: u32, block_size: u32) -> VhdDynamicHeaderData {
-    VhdDynamicHeaderData {
-        cookie: *b"cxsparse",
+/// Create a dynamic header with a valid checksum
+fn create_dynamic_header(num_blocks: u32, block_size: u32) -> VhdDynamicHeader {
+    let mut header = VhdDynamicHeader {
+        cookie: *VhdDynamicHeader::COOKIE,
         data_offset: 0xFFFFFFFFFFFFFFFFu64, // Unused
         table_offset: 512 + 1024, // After footer + header
         header_version: 0x00010000,
         max_table_entries: num_blocks,
         block_size,
+        checksum: 0,
         parent_uuid: [0u8; 16],
         parent_timestamp: 0,
-    }
+        reserved1: 0,
+        parent_unicode_name: [0u16; 256],
+        parent_locator_entries: [[0u8; 24]; 8],
+        reserved2: [0u8; 256],
+    };
+    header.checksum = header.calculate_checksum();
+    header
 }
 
-/// Serialize dynamic header to bytes with checksum
-fn serialize_dynamic_header(header: &VhdDynamicHeaderData) -> [u8; 1024] {
-    let mut bytes = [0u8; 1024];
-
-    bytes[0..8].copy_from_slice(&header.cookie);
-    bytes[8..16].copy_from_slice(&header.data_offset.to_be_bytes());
-    bytes[16..24].copy_from_slice(&header.table_offset.to_be_bytes());
-    bytes[24..28].copy_from_slice(&header.header_version.to_be_bytes());
-    bytes[28..32].copy_from_slice(&header.max_table_entries.to_be_bytes());
-    bytes[32..36].copy_from_slice(&header.block_size.to_be_bytes());
-    // Checksum at 36..40 - calculated below
-    bytes[40..56].copy_from_slice(&header.parent_uuid);
-    bytes[56..60].copy_from_slice(&header.parent_timestamp.to_be_bytes());
-    // bytes[60..64] = reserved
-    // bytes[64..576] = parent unicode name (zeros)
-    // bytes[576..768] = parent locator entries (zeros)
-    // bytes[768..1024] = reserved (zeros)
-
-    // Calculate checksum
-    let mut sum: u32 = 0;
-    for (i, &byte) in bytes.iter().enumerate() {
-        if i >= 36 && i < 40 {
-            continue; // Skip checksum field
-        }
-        sum = sum.wrapping_add(byte as u32);
-    }
-    let checksum = !sum;
-    bytes[36..40].copy_from_slice(&checksum.to_be_bytes());
-
+/// Serialize dynamic header to bytes
+fn serialize_dynamic_header(header: &VhdDynamicHeader) -> [u8; VhdDynamicHeader::SIZE] {
+    let mut bytes = [0u8; VhdDynamicHeader::SIZE];
+    header.serialize(&mut bytes);
     bytes
 }
 
@@ -600,6 +610,92 @@ mod tests {
         assert_eq!(&output[output.len() - 512..output.len() - 504], b"conectix"); // Footer at end
     }
 
+    #[test]
+    fn test_dynamic_vhd_round_trip() {
+        use std::sync::atomic::AtomicU64;
+        use totalimage_core::Vault;
+        use totalimage_vaults::{VaultConfig, VhdVault};
+
+        // 3.5 blocks: data, all zeros, data, partial trailing block
+        let block_size = 512 * 1024;
+        let mut source_data = vec![0u8; block_size * 7 / 2];
+        source_data[..4096].fill(0x11);
+        source_data[2 * block_size + 100..2 * block_size + 200].fill(0x22);
+        let tail = source_data.len() - 10;
+        source_data[tail..].fill(0x33);
+
+        let dir = tempfile::tempdir().unwrap();
+        let source_path = dir.path().join("source.bin");
+        let dest_path = dir.path().join("image.vhd");
+        std::fs::write(&source_path, &source_data).unwrap();
+
+        let updates = Arc::new(AtomicU64::new(0));
+        let counter = Arc::clone(&updates);
+        let callback: ProgressCallback = Arc::new(move |_| {
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+
+        let acquirer = VhdAcquirer::with_options(VhdOptions {
+            vhd_type: VhdOutputType::Dynamic,
+            block_size: block_size as u32,
+            ..Default::default()
+        });
+        let result = acquirer
+            .acquire_to_file(&source_path, &dest_path, Some(callback))
+            .unwrap();
+
+        assert!(updates.load(Ordering::Relaxed) > 0);
+        assert_eq!(result.bytes_written, std::fs::metadata(&dest_path).unwrap().len());
+        let expected = crate::hash::hash_reader(
+            &mut Cursor::new(&source_data),
+            &VhdOptions::default().hash_algorithms,
+        )
+        .unwrap();
+        for (actual, expected) in result.hashes.iter().zip(&expected) {
+            assert!(actual.matches(expected));
+        }
+
+        // Only the three non-zero blocks are stored
+        let mut vault = VhdVault::open(&dest_path, VaultConfig { use_mmap: false, ..Default::default() }).unwrap();
+        assert!(vault.is_dynamic());
+        let bat = vault.bat().unwrap();
+        assert_eq!(bat.entries.len(), 4);
+        assert_eq!(bat.get_block_offset(1), None);
+        assert_eq!(vault.length(), source_data.len() as u64);
+
+        let mut read_back = Vec::new();
+        vault.content().read_to_end(&mut read_back).unwrap();
+        assert_eq!(read_back, source_data);
+    }
+
+    #[test]
+    fn test_fixed_vhd_round_trip() {
+        use totalimage_core::Vault;
+        use totalimage_vaults::{VaultConfig, VhdVault};
+
+        let source_data: Vec<u8> = (0..64 * 1024).map(|i| (i % 253) as u8).collect();
+        let dir = tempfile::tempdir().unwrap();
+        let dest_path = dir.path().join("fixed.vhd");
+
+        let acquirer = VhdAcquirer::new();
+        let mut dest = File::create(&dest_path).unwrap();
+        acquirer
+            .acquire_stream(
+                &mut Cursor::new(&source_data),
+                source_data.len() as u64,
+                &mut dest,
+                None,
+            )
+            .unwrap();
+        drop(dest);
+
+        let mut vault = VhdVault::open(&dest_path, VaultConfig { use_mmap: false, ..Default::default() }).unwrap();
+        assert!(!vault.is_dynamic());
+        let mut read_back = Vec::new();
+        vault.content().read_to_end(&mut read_back).unwrap();
+        assert_eq!(read_back, source_data);
+    }
+
     #[test]
     fn test_chs_geometry() {
         // Small disk
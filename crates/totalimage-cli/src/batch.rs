@@ -0,0 +1,246 @@
+//! Batch analysis of many images at once
+//!
+//! Each image is opened and summarized on one of a bounded pool of worker
+//! threads. A failure is recorded in that image's report, so one damaged
+//! image doesn't abort the batch.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use totalimage_core::{Result, Vault, Zone};
+use totalimage_territories::identify_territory;
+use totalimage_vaults::{detect_vault_type, open_vault_as, Aff4Vault, E01Vault, VaultConfig, VaultType};
+use totalimage_zones::{detect_zone_table, ZoneTableExt};
+
+/// Sector size zone tables are looked for with
+const SECTOR_SIZE: u32 = 512;
+
+/// Combined report on a batch of images
+#[derive(Debug, Serialize)]
+pub struct BatchReport {
+    /// One report per image, in the order the images were given
+    pub images: Vec<ImageReport>,
+    /// Number of images that couldn't be analyzed
+    pub failed: usize,
+}
+
+/// Summary of one image
+#[derive(Debug, Default, Serialize)]
+pub struct ImageReport {
+    /// Path of the image
+    pub path: String,
+    /// Container format, as the vault identifies it
+    pub format: Option<String>,
+    /// Size of the disk the image holds, in bytes
+    pub size: Option<u64>,
+    /// Zone table type, or None for an unpartitioned image
+    pub partition_scheme: Option<String>,
+    /// Zones with the file system found in each; an unpartitioned image
+    /// is a single zone
+    pub zones: Vec<ZoneReport>,
+    /// Hash recorded in the container, for formats that store one
+    pub embedded_hash: Option<EmbeddedHash>,
+    /// Why the image couldn't be analyzed
+    pub error: Option<String>,
+}
+
+/// A zone of an image and the file system in it
+#[derive(Debug, Serialize)]
+pub struct ZoneReport {
+    /// Zone index
+    pub index: usize,
+    /// Offset of the zone in bytes
+    pub offset: u64,
+    /// Length of the zone in bytes
+    pub length: u64,
+    /// Zone type, as the zone table describes it
+    pub zone_type: String,
+    /// File system detected in the zone, if any
+    pub filesystem: Option<&'static str>,
+}
+
+/// Digest stored in a forensic container
+#[derive(Debug, Serialize)]
+pub struct EmbeddedHash {
+    /// Algorithm name
+    pub algorithm: &'static str,
+    /// Hex digest
+    pub digest: String,
+}
+
+/// List the files directly inside `dir`, sorted by name
+pub fn collect_images(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Analyze `paths` on up to `workers` threads
+pub fn analyze_batch(paths: &[PathBuf], workers: usize) -> BatchReport {
+    let next = AtomicUsize::new(0);
+    let reports: Mutex<Vec<Option<ImageReport>>> = Mutex::new((0..paths.len()).map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, paths.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = paths.get(index) else {
+                    break;
+                };
+                let report = analyze_image(path);
+                reports.lock().unwrap()[index] = Some(report);
+            });
+        }
+    });
+
+    let images: Vec<ImageReport> = reports.into_inner().unwrap().into_iter().flatten().collect();
+    BatchReport {
+        failed: images.iter().filter(|image| image.error.is_some()).count(),
+        images,
+    }
+}
+
+/// Analyze one image, recording any failure in the report
+pub fn analyze_image(path: &Path) -> ImageReport {
+    let mut report = ImageReport {
+        path: path.display().to_string(),
+        ..Default::default()
+    };
+    if let Err(e) = fill_report(path, &mut report) {
+        report.error = Some(e.to_string());
+    }
+    report
+}
+
+fn fill_report(path: &Path, report: &mut ImageReport) -> Result<()> {
+    let (mut vault, embedded_hash): (Box<dyn Vault>, _) = match detect_vault_type(path)? {
+        VaultType::E01 => {
+            let vault = E01Vault::open(path)?;
+            let hash = vault.md5_hash().map(|digest| EmbeddedHash { algorithm: "MD5", digest });
+            (Box::new(vault), hash)
+        }
+        VaultType::Aff4 => {
+            let vault = Aff4Vault::open(path)?;
+            let hash = vault.stream().sha512.clone().map(|digest| EmbeddedHash { algorithm: "SHA-512", digest });
+            (Box::new(vault), hash)
+        }
+        vault_type => (open_vault_as(path, vault_type, VaultConfig::default())?, None),
+    };
+    report.format = Some(vault.identify().to_string());
+    report.size = Some(vault.length());
+    report.embedded_hash = embedded_hash;
+
+    // A volume boot sector can pass for an empty MBR, so only a table with
+    // zones partitions the image
+    let table = detect_zone_table(vault.content(), SECTOR_SIZE)
+        .filter(|table| !table.as_zone_table().enumerate_zones().is_empty());
+
+    match table {
+        Some(table) => {
+            report.partition_scheme = Some(table.as_zone_table().identify().to_string());
+            for zone in table.as_zone_table().enumerate_zones() {
+                let filesystem = table
+                    .as_zone_table()
+                    .open_zone(vault.content(), zone.index)
+                    .ok()
+                    .and_then(|mut partial| identify_territory(&mut partial));
+                report.zones.push(zone_report(zone, filesystem));
+            }
+        }
+        None => {
+            let filesystem = identify_territory(vault.content());
+            let zone = Zone {
+                index: 0,
+                offset: 0,
+                length: vault.length(),
+                zone_type: "Unpartitioned".to_string(),
+                territory_type: None,
+            };
+            report.zones.push(zone_report(&zone, filesystem));
+        }
+    }
+
+    Ok(())
+}
+
+fn zone_report(zone: &Zone, filesystem: Option<&'static str>) -> ZoneReport {
+    ZoneReport {
+        index: zone.index,
+        offset: zone.offset,
+        length: zone.length,
+        zone_type: zone.zone_type.clone(),
+        filesystem,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 64 KiB image whose MBR holds one FAT partition at sector 1
+    fn partitioned_image() -> Vec<u8> {
+        let mut disk = vec![0u8; 64 * 1024];
+        let entry = 446;
+        disk[entry + 4] = 0x01;
+        disk[entry + 8..entry + 12].copy_from_slice(&1u32.to_le_bytes());
+        disk[entry + 12..entry + 16].copy_from_slice(&127u32.to_le_bytes());
+        disk[510] = 0x55;
+        disk[511] = 0xAA;
+        disk
+    }
+
+    #[test]
+    fn test_batch_over_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a-disk.img"), partitioned_image()).unwrap();
+        std::fs::write(dir.path().join("b-blank.img"), vec![0u8; 4096]).unwrap();
+        std::fs::create_dir(dir.path().join("subdir")).unwrap();
+
+        let paths = collect_images(dir.path()).unwrap();
+        assert_eq!(paths.len(), 2);
+
+        let report = analyze_batch(&paths, 4);
+        assert_eq!(report.images.len(), 2);
+        assert_eq!(report.failed, 0);
+
+        let disk = &report.images[0];
+        assert!(disk.path.ends_with("a-disk.img"));
+        assert_eq!(disk.format.as_deref(), Some("Raw sector image"));
+        assert_eq!(disk.size, Some(64 * 1024));
+        assert_eq!(disk.partition_scheme.as_deref(), Some("Master Boot Record"));
+        assert_eq!(disk.zones.len(), 1);
+        assert_eq!(disk.zones[0].offset, 512);
+        assert!(disk.embedded_hash.is_none());
+
+        let blank = &report.images[1];
+        assert!(blank.path.ends_with("b-blank.img"));
+        assert_eq!(blank.partition_scheme, None);
+        assert_eq!(blank.zones[0].zone_type, "Unpartitioned");
+        assert_eq!(blank.zones[0].filesystem, None);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["images"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_batch_records_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = dir.path().join("good.img");
+        std::fs::write(&good, vec![0u8; 4096]).unwrap();
+        let paths = vec![dir.path().join("missing.img"), good];
+
+        let report = analyze_batch(&paths, 1);
+        assert_eq!(report.failed, 1);
+        assert!(report.images[0].error.is_some());
+        assert!(report.images[0].format.is_none());
+        assert!(report.images[1].error.is_none());
+    }
+}
@@ -2,13 +2,26 @@
 //!
 //! A tool for inspecting disk images, partition tables, and file systems.
 
+mod batch;
+
 use std::env;
+use std::io::IsTerminal;
 use std::path::Path;
 use std::process;
-use totalimage_core::{Result, ZoneTable};
-use totalimage_pipeline::PartialPipeline;
-use totalimage_vaults::{open_vault, VaultConfig};
-use totalimage_zones::{GptZoneTable, MbrZoneTable};
+use totalimage_core::{
+    AttributeFlag, ListOptions, OccupantInfo, Progress, ProgressReader, ProgressUpdate, Result, SecurityLimits, Vault,
+    Zone, ZoneTable,
+};
+use totalimage_pipeline::{PartialPipeline, VaultReader};
+use totalimage_acquire::hash::{hash_reader, HashAlgorithm};
+use totalimage_acquire::{convert_vault, ConvertOptions, VaultFormat};
+use totalimage_territories::{identify_territory, open_territory_in_zone, IsoTerritory};
+use totalimage_vaults::{
+    detect_vault_type, open_vault, Aff4Vault, E01Vault, HashVerification, RawVault, VaultConfig, VaultType,
+};
+use totalimage_zones::{
+    detect_zone_table, parse_partition_scheme, DetectedZoneTable, PartitionScheme, ZoneTableExt,
+};
 
 fn main() {
     let args: Vec<String> = env::args().collect();
@@ -20,6 +33,18 @@ fn main() {
 
     let command = &args[1];
 
+    match parse_max_extract_size_arg(&args) {
+        Ok(Some(max_size)) => SecurityLimits::set_active(SecurityLimits {
+            max_file_extract_size: max_size,
+            ..SecurityLimits::active()
+        }),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+
     match command.as_str() {
         "info" => {
             if args.len() < 3 {
@@ -43,7 +68,11 @@ fn main() {
         }
         "list" => {
             if args.len() < 3 {
-                eprintln!("Usage: {} list <image_file> [--zone INDEX]", args[0]);
+                eprintln!(
+                    "Usage: {} list <image_file> [--zone INDEX] [--sort KEY] [--filter GLOB] [--since DATE] \
+                     [--best-effort]",
+                    args[0]
+                );
                 process::exit(1);
             }
             let zone_index = match parse_zone_arg(&args) {
@@ -53,7 +82,21 @@ fn main() {
                     process::exit(1);
                 }
             };
-            if let Err(e) = cmd_list(&args[2], zone_index) {
+            let options = match ListOptions::parse(
+                parse_flag_arg(&args, "--sort").as_deref(),
+                parse_flag_arg(&args, "--filter").as_deref(),
+                parse_flag_arg(&args, "--since").as_deref(),
+            ) {
+                Ok(options) => ListOptions {
+                    best_effort: args.iter().any(|arg| arg == "--best-effort"),
+                    ..options
+                },
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            };
+            if let Err(e) = cmd_list(&args[2], zone_index, &options) {
                 eprintln!("Error: {}", e);
                 process::exit(1);
             }
@@ -76,6 +119,71 @@ fn main() {
                 process::exit(1);
             }
         }
+        "export-zone" => {
+            if args.len() < 3 {
+                eprintln!(
+                    "Usage: {} export-zone <image_file> --output FILE [--zone INDEX] [--format raw|vhd] [--hash]",
+                    args[0]
+                );
+                process::exit(1);
+            }
+            let zone_index = match parse_zone_arg(&args) {
+                Ok(idx) => idx,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            };
+            let Some(output_path) = parse_output_arg(&args) else {
+                eprintln!("Error: export-zone needs --output FILE");
+                process::exit(1);
+            };
+            let format = match parse_export_format(parse_flag_arg(&args, "--format").as_deref()) {
+                Ok(format) => format,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            };
+            let hash = args.iter().any(|arg| arg == "--hash");
+            if let Err(e) = cmd_export_zone(&args[2], zone_index, &output_path, format, hash) {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        "verify" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} verify <image_file> [--expected HASH] [--algorithm NAME]", args[0]);
+                process::exit(1);
+            }
+            let expected = parse_flag_arg(&args, "--expected");
+            let algorithm = parse_flag_arg(&args, "--algorithm");
+            match cmd_verify(&args[2], expected.as_deref(), algorithm.as_deref()) {
+                Ok(true) => {}
+                Ok(false) => process::exit(1),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        "batch" => {
+            if args.len() < 3 {
+                eprintln!("Usage: {} batch <directory|image...> [--workers N]", args[0]);
+                process::exit(1);
+            }
+            let workers = match parse_workers_arg(&args) {
+                Ok(workers) => workers,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            };
+            if let Err(e) = cmd_batch(&args[2..], workers) {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
         "--help" | "-h" | "help" => {
             print_usage(&args[0]);
         }
@@ -99,20 +207,51 @@ fn print_usage(program: &str) {
     println!("COMMANDS:");
     println!("    info <image>                           Display vault information");
     println!("    zones <image>                          List partition zones");
-    println!("    list <image> [--zone INDEX]            List files in filesystem");
+    println!("    list <image> [OPTIONS]                 List files in filesystem");
     println!("    extract <image> <file> [OPTIONS]       Extract a file");
+    println!("    export-zone <image> [OPTIONS]          Copy a partition zone to its own image");
+    println!("    verify <image> [OPTIONS]               Check the image against its hashes");
+    println!("    batch <dir|image...> [--workers N]     Summarize many images as a JSON report");
     println!("    help                                   Print this help message");
     println!("    version                                Print version");
     println!();
+    println!("LIST OPTIONS:");
+    println!("    --zone INDEX     Partition zone index (default: 0)");
+    println!("    --sort KEY       name, size or mtime; prefix with - to reverse");
+    println!("    --filter GLOB    Only names matching GLOB, e.g. '*.txt'");
+    println!("    --since DATE     Only files modified after DATE (YYYY-MM-DD or RFC 3339)");
+    println!("    --best-effort    Skip damaged entries instead of failing, and count them");
+    println!();
     println!("EXTRACT OPTIONS:");
     println!("    --zone INDEX     Partition zone index (default: 0)");
     println!("    --output PATH    Output file path (default: stdout)");
+    println!("    --max-extract-size BYTES");
+    println!("                     Largest file to extract (default: 1 GiB)");
+    println!();
+    println!("EXPORT-ZONE OPTIONS:");
+    println!("    --output FILE    Image file to write (required)");
+    println!("    --zone INDEX     Partition zone index (default: 0)");
+    println!("    --format FORMAT  raw or vhd (fixed VHD) (default: raw)");
+    println!("    --hash           Print the MD5 and SHA-256 of the exported zone");
+    println!();
+    println!("VERIFY OPTIONS:");
+    println!("    --expected HASH  Digest the image content must match, in hex");
+    println!("    --algorithm NAME md5, sha1 or sha256 (default: from the digest length)");
+    println!("    E01 and AFF4 images are also checked against their embedded hash.");
+    println!();
+    println!("BATCH OPTIONS:");
+    println!("    --workers N      Images analyzed at once (default: number of CPUs)");
+    println!("    A directory argument stands for every file directly inside it.");
     println!();
     println!("EXAMPLES:");
     println!("    {} info disk.img", program);
     println!("    {} zones floppy.img", program);
     println!("    {} list disk.img --zone 0", program);
+    println!("    {} list disk.img --sort -size --filter '*.log'", program);
     println!("    {} extract disk.img AUTOEXEC.BAT --output autoexec.bat", program);
+    println!("    {} export-zone disk.img --zone 1 --output part1.img --hash", program);
+    println!("    {} verify evidence.E01", program);
+    println!("    {} batch evidence/ --workers 4 > report.json", program);
 }
 
 fn cmd_info(image_path: &str) -> Result<()> {
@@ -128,31 +267,100 @@ fn cmd_info(image_path: &str) -> Result<()> {
     // Try to detect sector size (assume 512 for now)
     let sector_size = 512;
 
-    // Try MBR first
-    if let Ok(mbr) = MbrZoneTable::parse(vault.content(), sector_size) {
-        println!("=== Partition Table ===");
-        println!("Type:        {}", mbr.identify());
-        println!("Disk Sig:    0x{:08X}", mbr.disk_signature());
-        println!("Boot Sig:    0x{:04X}", mbr.boot_signature());
-        println!("Partitions:  {}", mbr.enumerate_zones().len());
-
-        if mbr.is_gpt_protective() {
-            println!();
-            println!("Note: This disk has a GPT protective MBR.");
-            println!("      Use GPT zone table for full information.");
+    // A GPT-protective MBR is reported as the GPT behind it
+    match detect_zone_table(vault.content(), sector_size) {
+        Some(DetectedZoneTable::Mbr(mbr)) => {
+            println!("=== Partition Table ===");
+            println!("Type:        {}", mbr.identify());
+            println!("Disk Sig:    0x{:08X}", mbr.disk_signature());
+            println!("Boot Sig:    0x{:04X}", mbr.boot_signature());
+            println!("Partitions:  {}", mbr.enumerate_zones().len());
+
+            if mbr.is_gpt_protective() {
+                println!();
+                println!("Note: This disk has a GPT protective MBR, but the GPT could not be read.");
+            } else {
+                let chs_warnings = mbr.validate_chs_consistency();
+                if !chs_warnings.is_empty() {
+                    println!();
+                    println!("Warnings:");
+                    for warning in &chs_warnings {
+                        println!("  - {}", warning);
+                    }
+                }
+            }
+        }
+        Some(DetectedZoneTable::Gpt(gpt)) => {
+            println!("=== Partition Table ===");
+            println!("Type:        {}", gpt.identify());
+            println!("Partitions:  {}", gpt.enumerate_zones().len());
+            println!("Usable LBA:  {}", gpt.usable_lba_count());
+        }
+        Some(DetectedZoneTable::Apm(apm)) => {
+            println!("=== Partition Table ===");
+            println!("Type:        {}", apm.identify());
+            println!("Block size:  {}", apm.descriptor().block_size);
+            println!("Partitions:  {}", apm.enumerate_zones().len());
+        }
+        None => {
+            println!("No recognized partition table found.");
         }
-    } else if let Ok(gpt) = GptZoneTable::parse(vault.content(), sector_size) {
-        println!("=== Partition Table ===");
-        println!("Type:        {}", gpt.identify());
-        println!("Partitions:  {}", gpt.enumerate_zones().len());
-        println!("Usable LBA:  {}", gpt.usable_lba_count());
-    } else {
-        println!("No recognized partition table found.");
+    }
+
+    print_partition_scheme(&parse_partition_scheme(vault.content(), sector_size));
+
+    // Optical images, hybrid ones included, carry an ISO 9660 volume at the start
+    if let Ok(iso) = IsoTerritory::parse(vault.content()) {
+        print_iso_volume(&iso);
     }
 
     Ok(())
 }
 
+/// Report the identifiers and dates of an ISO 9660 volume
+fn print_iso_volume(iso: &IsoTerritory) {
+    use totalimage_core::Territory;
+
+    let or_unspecified = |value: Option<String>| value.unwrap_or_else(|| "(not specified)".to_string());
+
+    println!();
+    println!("=== ISO 9660 Volume ===");
+    println!("Label:       {}", iso.banner().unwrap_or_default());
+    println!("Publisher:   {}", or_unspecified(iso.publisher()));
+    println!("Preparer:    {}", or_unspecified(iso.preparer()));
+    println!("Application: {}", or_unspecified(iso.application()));
+    println!("Created:     {}", or_unspecified(iso.created().map(|date| date.to_rfc3339())));
+    println!("Modified:    {}", or_unspecified(iso.modified().map(|date| date.to_rfc3339())));
+}
+
+/// Report which of MBR and GPT are present and where they disagree
+fn print_partition_scheme(scheme: &PartitionScheme) {
+    if scheme.mbr.is_none() && scheme.gpt.is_none() {
+        return;
+    }
+
+    let mbr = match &scheme.mbr {
+        None => "absent",
+        Some(_) if scheme.is_protective() => "protective",
+        Some(_) if scheme.is_hybrid() => "hybrid",
+        Some(_) => "present",
+    };
+    let gpt = if scheme.gpt.is_some() { "present" } else { "absent" };
+
+    println!();
+    println!("=== Partition Scheme ===");
+    println!("MBR:         {}", mbr);
+    println!("GPT:         {}", gpt);
+
+    if !scheme.warnings.is_empty() {
+        println!();
+        println!("Warnings:");
+        for warning in &scheme.warnings {
+            println!("  - {}", warning);
+        }
+    }
+}
+
 fn cmd_zones(image_path: &str) -> Result<()> {
     let path = Path::new(image_path);
     let mut vault = open_vault(path, VaultConfig::default())?;
@@ -162,71 +370,80 @@ fn cmd_zones(image_path: &str) -> Result<()> {
 
     let sector_size = 512;
 
-    // Try MBR first
-    if let Ok(mbr) = MbrZoneTable::parse(vault.content(), sector_size) {
-        println!("Partition table: {}", mbr.identify());
-        println!();
+    // A GPT-protective MBR is listed as the GPT behind it
+    let Some(table) = detect_zone_table(vault.content(), sector_size) else {
+        println!("No recognized partition table found.");
+        println!("This may be an unpartitioned volume.");
+        return Ok(());
+    };
+    let zones = table.as_zone_table().enumerate_zones();
 
-        if mbr.enumerate_zones().is_empty() {
-            println!("No partitions found.");
-        } else {
-            println!("{:<5} {:<15} {:<15} {:<20}", "Index", "Offset", "Size", "Type");
-            println!("{}", "-".repeat(60));
-
-            for zone in mbr.enumerate_zones() {
-                println!(
-                    "{:<5} {:<15} {:<15} {:<20}",
-                    zone.index,
-                    format_bytes(zone.offset),
-                    format_bytes(zone.length),
-                    zone.zone_type
-                );
-            }
+    println!("Partition table: {}", table.as_zone_table().identify());
+    println!();
 
-            // Try to parse FAT from first partition
-            if let Some(first_zone) = mbr.enumerate_zones().first() {
-                println!();
-                println!("=== First Partition Analysis ===");
+    if zones.is_empty() {
+        println!("No partitions found.");
+        return Ok(());
+    }
 
-                let mut partial = PartialPipeline::new(
-                    vault.content(),
-                    first_zone.offset,
-                    first_zone.length,
-                )?;
+    let type_width = if matches!(table, DetectedZoneTable::Mbr(_)) { 20 } else { 40 };
+    println!("{:<5} {:<15} {:<15} {:<type_width$} File System", "Index", "Offset", "Size", "Type");
+    println!("{}", "-".repeat(61 + type_width));
+
+    for zone in zones {
+        let territory = table
+            .as_zone_table()
+            .open_zone(vault.content(), zone.index)
+            .ok()
+            .and_then(|mut partial| identify_territory(&mut partial));
+        println!(
+            "{:<5} {:<15} {:<15} {:<type_width$} {}",
+            zone.index,
+            format_bytes(zone.offset),
+            format_bytes(zone.length),
+            zone.zone_type,
+            territory.unwrap_or("-")
+        );
+    }
 
-                if let Ok(fat) = totalimage_territories::FatTerritory::parse(&mut partial) {
-                    use totalimage_core::Territory;
+    let warnings = table.validation_warnings();
+    if !warnings.is_empty() {
+        println!();
+        println!("Warnings:");
+        for warning in warnings {
+            println!("  - {}", warning);
+        }
+    }
 
-                    println!("Filesystem:  {}", fat.identify());
-                    println!("Domain:      {}", format_bytes(fat.domain_size()));
-                    println!("Block size:  {}", format_bytes(fat.block_size()));
-                    println!("Hierarchical: {}", if fat.hierarchical() { "Yes" } else { "No" });
-                }
-            }
+    // Space outside every partition can hold data no file system shows
+    let accounting = table.as_zone_table().space_accounting(vault.length());
+    if !accounting.gaps.is_empty() {
+        println!();
+        println!(
+            "Unpartitioned space: {} of {}",
+            format_bytes(accounting.unaccounted),
+            format_bytes(accounting.disk_len)
+        );
+        for (offset, length) in accounting.gaps {
+            println!("  - {} at byte {}", format_bytes(length), offset);
         }
-    } else if let Ok(gpt) = GptZoneTable::parse(vault.content(), sector_size) {
-        println!("Partition table: {}", gpt.identify());
+    }
+
+    // Try to parse FAT from first MBR partition
+    if let DetectedZoneTable::Mbr(mbr) = &table {
         println!();
+        println!("=== First Partition Analysis ===");
 
-        if gpt.enumerate_zones().is_empty() {
-            println!("No partitions found.");
-        } else {
-            println!("{:<5} {:<15} {:<15} {:<40}", "Index", "Offset", "Size", "Type");
-            println!("{}", "-".repeat(80));
-
-            for zone in gpt.enumerate_zones() {
-                println!(
-                    "{:<5} {:<15} {:<15} {:<40}",
-                    zone.index,
-                    format_bytes(zone.offset),
-                    format_bytes(zone.length),
-                    zone.zone_type
-                );
-            }
+        let mut partial = mbr.open_zone(vault.content(), 0)?;
+
+        if let Ok(fat) = totalimage_territories::FatTerritory::parse(&mut partial) {
+            use totalimage_core::Territory;
+
+            println!("Filesystem:  {}", fat.identify());
+            println!("Domain:      {}", format_bytes(fat.domain_size()));
+            println!("Block size:  {}", format_bytes(fat.block_size()));
+            println!("Hierarchical: {}", if fat.hierarchical() { "Yes" } else { "No" });
         }
-    } else {
-        println!("No recognized partition table found.");
-        println!("This may be an unpartitioned volume.");
     }
 
     Ok(())
@@ -245,148 +462,359 @@ fn parse_zone_arg(args: &[String]) -> Result<usize> {
 }
 
 fn parse_output_arg(args: &[String]) -> Option<String> {
+    parse_flag_arg(args, "--output")
+}
+
+fn parse_flag_arg(args: &[String], flag: &str) -> Option<String> {
     for i in 0..args.len() - 1 {
-        if args[i] == "--output" {
+        if args[i] == flag {
             return Some(args[i + 1].clone());
         }
     }
     None
 }
 
-fn cmd_list(image_path: &str, zone_index: usize) -> Result<()> {
-    use totalimage_core::Territory;
+fn parse_workers_arg(args: &[String]) -> Result<usize> {
+    match parse_flag_arg(args, "--workers") {
+        Some(value) => match value.parse() {
+            Ok(workers) if workers > 0 => Ok(workers),
+            _ => Err(totalimage_core::Error::InvalidOperation(format!(
+                "Invalid worker count: '{}' (expected a positive integer)",
+                value
+            ))),
+        },
+        None => Ok(std::thread::available_parallelism().map_or(1, |n| n.get())),
+    }
+}
 
-    let path = Path::new(image_path);
-    let mut vault = open_vault(path, VaultConfig::default())?;
-    let sector_size = 512;
+fn parse_max_extract_size_arg(args: &[String]) -> Result<Option<u64>> {
+    for i in 0..args.len() - 1 {
+        if args[i] == "--max-extract-size" {
+            return args[i + 1].parse().map(Some)
+                .map_err(|_| totalimage_core::Error::InvalidOperation(
+                    format!("Invalid extract size: '{}' (expected a byte count)", args[i + 1])
+                ));
+        }
+    }
+    Ok(None)
+}
 
-    // Try to parse partition table
-    let zone = if let Ok(mbr) = MbrZoneTable::parse(vault.content(), sector_size) {
-        let zones = mbr.enumerate_zones();
-        if zone_index >= zones.len() {
-            eprintln!("Error: Zone index {} out of range (0-{})", zone_index, zones.len() - 1);
-            process::exit(1);
+fn cmd_batch(args: &[String], workers: usize) -> Result<()> {
+    let mut paths = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--workers" {
+            iter.next();
+            continue;
         }
-        zones[zone_index].clone()
-    } else if let Ok(gpt) = GptZoneTable::parse(vault.content(), sector_size) {
-        let zones = gpt.enumerate_zones();
-        if zone_index >= zones.len() {
-            eprintln!("Error: Zone index {} out of range (0-{})", zone_index, zones.len() - 1);
-            process::exit(1);
+        let path = Path::new(arg);
+        if path.is_dir() {
+            paths.extend(batch::collect_images(path)?);
+        } else {
+            paths.push(path.to_path_buf());
         }
-        zones[zone_index].clone()
-    } else {
-        // Unpartitioned disk - use entire disk as zone 0
-        if zone_index != 0 {
-            eprintln!("Error: No partition table found. Use zone 0 for unpartitioned disk.");
-            process::exit(1);
+    }
+
+    let report = batch::analyze_batch(&paths, workers);
+    let json = serde_json::to_string_pretty(&report)
+        .map_err(|e| totalimage_core::Error::InvalidOperation(format!("Failed to encode report: {}", e)))?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn cmd_verify(image_path: &str, expected: Option<&str>, algorithm: Option<&str>) -> Result<bool> {
+    println!("=== Verifying {} ===", image_path);
+
+    let mut all_match = true;
+    for (source, verification) in verify_image(image_path, expected, algorithm)? {
+        let result = if verification.is_match() { "PASS" } else { "FAIL" };
+        all_match &= verification.is_match();
+
+        println!();
+        println!("{} {}", source, verification.algorithm);
+        println!("  Expected: {}", verification.expected);
+        println!("  Computed: {}", verification.computed);
+        println!("  Result:   {}", result);
+    }
+
+    println!();
+    println!("Overall: {}", if all_match { "PASS" } else { "FAIL" });
+    Ok(all_match)
+}
+
+/// Check an image against its embedded hash (E01, AFF4) and an optional
+/// user-supplied digest of its content
+fn verify_image(
+    image_path: &str,
+    expected: Option<&str>,
+    algorithm: Option<&str>,
+) -> Result<Vec<(&'static str, HashVerification)>> {
+    let path = Path::new(image_path);
+    let mut verifications = Vec::new();
+
+    let progress = stderr_progress("Hashing");
+    let reporter = progress.as_ref().map(|p| p as &dyn Progress);
+    let embedded = match detect_vault_type(path)? {
+        VaultType::E01 => E01Vault::open(path)?.verify_integrity_with_progress(reporter)?,
+        VaultType::Aff4 => Aff4Vault::open(path)?.verify_integrity_with_progress(reporter)?,
+        _ => None,
+    };
+    if let Some(verification) = embedded {
+        end_progress(&progress);
+        verifications.push(("Embedded", verification));
+    }
+
+    if let Some(expected) = expected {
+        let algorithm = parse_hash_algorithm(algorithm, expected)?;
+        let mut vault = open_vault(path, VaultConfig::default())?;
+        let length = vault.length();
+        let content = vault.content();
+        content.seek(std::io::SeekFrom::Start(0))?;
+        let mut content = ProgressReader::new(content, reporter, Some(length));
+        let digest = hash_reader(&mut content, &[algorithm])?.remove(0);
+        end_progress(&progress);
+
+        verifications.push((
+            "Expected",
+            HashVerification {
+                algorithm: algorithm.name(),
+                expected: expected.to_ascii_lowercase(),
+                computed: digest.hex,
+            },
+        ));
+    }
+
+    if verifications.is_empty() {
+        return Err(totalimage_core::Error::unsupported(format!(
+            "{} has no embedded hash; pass --expected HASH to check it",
+            image_path
+        )));
+    }
+    Ok(verifications)
+}
+
+/// Get a reporter that draws the percentage done on stderr, if stderr is
+/// a terminal
+fn stderr_progress(label: &'static str) -> Option<impl Progress> {
+    std::io::stderr().is_terminal().then_some(move |update: ProgressUpdate| {
+        if let Some(percent) = update.percent() {
+            eprint!("\r{}: {:5.1}%", label, percent);
         }
-        use totalimage_core::Zone;
-        Zone {
-            index: 0,
-            offset: 0,
-            length: vault.length(),
-            zone_type: "Unpartitioned".to_string(),
-            territory_type: None,
+    })
+}
+
+/// Finish the line a [`stderr_progress`] reporter drew on
+fn end_progress<P>(progress: &Option<P>) {
+    if progress.is_some() {
+        eprintln!();
+    }
+}
+
+/// Pick the digest algorithm by name, or from the length of `expected`
+fn parse_hash_algorithm(name: Option<&str>, expected: &str) -> Result<HashAlgorithm> {
+    let algorithm = match name.map(str::to_ascii_lowercase).as_deref() {
+        Some("md5") => HashAlgorithm::Md5,
+        Some("sha1" | "sha-1") => HashAlgorithm::Sha1,
+        Some("sha256" | "sha-256") => HashAlgorithm::Sha256,
+        Some(other) => {
+            return Err(totalimage_core::Error::InvalidOperation(format!(
+                "Unknown hash algorithm: '{}' (expected md5, sha1 or sha256)",
+                other
+            )))
         }
+        None => match expected.len() {
+            32 => HashAlgorithm::Md5,
+            40 => HashAlgorithm::Sha1,
+            64 => HashAlgorithm::Sha256,
+            _ => {
+                return Err(totalimage_core::Error::InvalidOperation(format!(
+                    "Can't tell the algorithm of a {}-digit digest; pass --algorithm",
+                    expected.len()
+                )))
+            }
+        },
     };
+    Ok(algorithm)
+}
 
-    // Create partial pipeline for the zone
-    let mut partial = PartialPipeline::new(vault.content(), zone.offset, zone.length)?;
+fn cmd_list(image_path: &str, zone_index: usize, options: &ListOptions) -> Result<()> {
+    let path = Path::new(image_path);
+    let mut vault = open_vault(path, VaultConfig::default())?;
 
-    // Try to parse FAT filesystem
-    if let Ok(fat) = totalimage_territories::FatTerritory::parse(&mut partial) {
-        println!("=== Files in {} (Zone {}) ===", image_path, zone_index);
-        println!("Filesystem: {}", fat.identify());
-        println!();
+    // Hybrid ISOs carry an MBR whose one partition covers the whole image;
+    // list the ISO 9660 file system instead of going through the partition
+    if zone_index == 0 && totalimage_territories::is_hybrid_iso(vault.content()) {
+        return list_iso_root(image_path, vault.content(), options);
+    }
 
-        // List directory contents
-        let occupants = fat.list_root_directory(&mut partial)?;
+    let mut opened = open_territory_in_zone(vault, zone_index)?;
+    let territory = opened.territory.as_ref();
 
-        if occupants.is_empty() {
-            println!("No files found.");
-        } else {
-            println!("{:<30} {:<10} {:<15}", "Name", "Type", "Size");
-            println!("{}", "-".repeat(60));
-
-            for occupant in occupants {
-                let file_type = if occupant.is_directory { "Dir" } else { "File" };
-                println!(
-                    "{:<30} {:<10} {:<15}",
-                    occupant.name,
-                    file_type,
-                    format_bytes(occupant.size)
-                );
-            }
+    println!("=== Files in {} (Zone {}) ===", image_path, zone_index);
+    println!("Filesystem: {}", territory.identify());
+    println!();
+
+    // List directory contents
+    let listing = territory.list_directory_partial(&mut *opened.stream, "/", options)?;
+    print_occupants(territory, &listing.occupants);
+    print_skipped(&listing.skipped);
+
+    Ok(())
+}
+
+fn list_iso_root(image_path: &str, stream: &mut dyn totalimage_core::ReadSeek, options: &ListOptions) -> Result<()> {
+    use totalimage_core::Territory;
+
+    let iso = IsoTerritory::parse(stream)?;
+    let listing = iso.list_directory_partial(stream, "/", options)?;
+
+    println!("=== Files in {} (Hybrid ISO) ===", image_path);
+    println!("Filesystem: {}", iso.identify());
+    println!();
+    print_occupants(&iso, &listing.occupants);
+    print_skipped(&listing.skipped);
+
+    Ok(())
+}
+
+/// Report the entries a best-effort listing skipped, if any
+fn print_skipped(skipped: &[(String, String)]) {
+    if !skipped.is_empty() {
+        println!();
+        println!("{} damaged entries skipped", skipped.len());
+        for (path, message) in skipped {
+            println!("  {}: {}", path, message);
         }
-    } else {
-        eprintln!("Error: Unable to parse filesystem in zone {}. Only FAT filesystems are currently supported.", zone_index);
-        process::exit(1);
     }
+}
 
-    Ok(())
+fn print_occupants(territory: &dyn totalimage_core::Territory, occupants: &[OccupantInfo]) {
+    if occupants.is_empty() {
+        println!("No files found.");
+        return;
+    }
+
+    println!("{:<30} {:<10} {:<15} Attributes", "Name", "Type", "Size");
+    println!("{}", "-".repeat(80));
+
+    for occupant in occupants {
+        let file_type = if occupant.is_directory { "Dir" } else { "File" };
+        println!(
+            "{:<30} {:<10} {:<15} {}",
+            occupant.name,
+            file_type,
+            format_bytes(occupant.size),
+            format_attributes(&territory.decode_attributes(occupant.attributes))
+        );
+    }
 }
 
 fn cmd_extract(image_path: &str, file_path: &str, zone_index: usize, output_path: Option<&str>) -> Result<()> {
     use std::io::Write;
 
     let path = Path::new(image_path);
-    let mut vault = open_vault(path, VaultConfig::default())?;
-    let sector_size = 512;
+    let vault = open_vault(path, VaultConfig::default())?;
 
-    // Try to parse partition table
-    let zone = if let Ok(mbr) = MbrZoneTable::parse(vault.content(), sector_size) {
-        let zones = mbr.enumerate_zones();
-        if zone_index >= zones.len() {
-            eprintln!("Error: Zone index {} out of range (0-{})", zone_index, zones.len() - 1);
-            process::exit(1);
-        }
-        zones[zone_index].clone()
-    } else if let Ok(gpt) = GptZoneTable::parse(vault.content(), sector_size) {
-        let zones = gpt.enumerate_zones();
-        if zone_index >= zones.len() {
-            eprintln!("Error: Zone index {} out of range (0-{})", zone_index, zones.len() - 1);
-            process::exit(1);
-        }
-        zones[zone_index].clone()
+    // The territory owns a handle on the zone, so it reads the file itself
+    let mut opened = open_territory_in_zone(vault, zone_index)?;
+    let data = opened.territory.extract_file(file_path)?;
+
+    // Write to output
+    if let Some(output) = output_path {
+        std::fs::write(output, &data)?;
+        println!("Extracted {} ({} bytes) to {}", file_path, data.len(), output);
     } else {
-        // Unpartitioned disk - use entire disk as zone 0
-        if zone_index != 0 {
-            eprintln!("Error: No partition table found. Use zone 0 for unpartitioned disk.");
-            process::exit(1);
-        }
-        use totalimage_core::Zone;
-        Zone {
-            index: 0,
-            offset: 0,
-            length: vault.length(),
-            zone_type: "Unpartitioned".to_string(),
-            territory_type: None,
-        }
-    };
+        // Write to stdout
+        std::io::stdout().write_all(&data)?;
+    }
 
-    // Create partial pipeline for the zone
-    let mut partial = PartialPipeline::new(vault.content(), zone.offset, zone.length)?;
+    Ok(())
+}
 
-    // Try to parse FAT filesystem
-    if let Ok(fat) = totalimage_territories::FatTerritory::parse(&mut partial) {
-        // Find the file
-        let entry = fat.find_file_in_root(&mut partial, file_path)?;
+/// Find zone `zone_index` of an image, or the whole image as zone 0 when it
+/// has no partition table
+fn resolve_zone(vault: &mut dyn Vault, zone_index: usize) -> Result<Zone> {
+    let sector_size = 512;
 
-        // Read file data
-        let data = fat.read_file_data(&mut partial, &entry)?;
+    // A volume boot sector can pass for an empty MBR, so only a table with
+    // zones partitions the image, as in open_territory_in_zone
+    let table = detect_zone_table(vault.content(), sector_size)
+        .filter(|table| !table.as_zone_table().enumerate_zones().is_empty());
+    if let Some(table) = table {
+        let zones = table.as_zone_table().enumerate_zones();
+        return zones.get(zone_index).cloned().ok_or_else(|| {
+            totalimage_core::Error::InvalidOperation(format!(
+                "Zone index {} out of range (0-{})",
+                zone_index,
+                zones.len().saturating_sub(1)
+            ))
+        });
+    }
 
-        // Write to output
-        if let Some(output) = output_path {
-            std::fs::write(output, &data)?;
-            println!("Extracted {} ({} bytes) to {}", file_path, data.len(), output);
+    // Unpartitioned disk - use entire disk as zone 0
+    if zone_index != 0 {
+        return Err(totalimage_core::Error::InvalidOperation(
+            "No partition table found. Use zone 0 for unpartitioned disk.".to_string(),
+        ));
+    }
+    Ok(Zone {
+        index: 0,
+        offset: 0,
+        length: vault.length(),
+        zone_type: "Unpartitioned".to_string(),
+        territory_type: None,
+    })
+}
+
+/// Parse the `--format` of `export-zone`
+fn parse_export_format(name: Option<&str>) -> Result<VaultFormat> {
+    match name.map(str::to_ascii_lowercase).as_deref() {
+        None | Some("raw") => Ok(VaultFormat::Raw),
+        Some("vhd") => Ok(VaultFormat::FixedVhd),
+        Some(other) => Err(totalimage_core::Error::InvalidOperation(format!(
+            "Unknown export format: '{}' (expected raw or vhd)",
+            other
+        ))),
+    }
+}
+
+/// Copy one zone of an image into a standalone image file
+///
+/// With `hash`, the MD5 and SHA-256 of the zone's bytes are printed.
+fn cmd_export_zone(
+    image_path: &str,
+    zone_index: usize,
+    output_path: &str,
+    format: VaultFormat,
+    hash: bool,
+) -> Result<()> {
+    let mut vault = open_vault(Path::new(image_path), VaultConfig::default())?;
+    let zone = resolve_zone(vault.as_mut(), zone_index)?;
+
+    let partial = PartialPipeline::new(VaultReader::new(vault), zone.offset, zone.length)?;
+    let mut zone_vault = RawVault::from_stream(partial, zone.length);
+
+    let options = ConvertOptions {
+        hash_algorithms: if hash {
+            vec![HashAlgorithm::Md5, HashAlgorithm::Sha256]
         } else {
-            // Write to stdout
-            std::io::stdout().write_all(&data)?;
-        }
-    } else {
-        eprintln!("Error: Unable to parse filesystem in zone {}. Only FAT filesystems are currently supported.", zone_index);
-        process::exit(1);
+            Vec::new()
+        },
+        ..Default::default()
+    };
+    let result = convert_vault(&mut zone_vault, format, Path::new(output_path), &options)
+        .map_err(|e| totalimage_core::Error::custom(e.to_string()))?;
+
+    println!(
+        "Exported zone {} ({}, {} at byte {}) to {}",
+        zone_index,
+        zone.zone_type,
+        format_bytes(zone.length),
+        zone.offset,
+        output_path
+    );
+    for digest in &result.hashes {
+        println!("  {}: {}", digest.algorithm.name(), digest.hex);
     }
 
     Ok(())
@@ -403,3 +831,107 @@ fn format_bytes(bytes: u64) -> String {
         format!("{:.2} GB", bytes as f64 / 1_073_741_824.0)
     }
 }
+
+fn format_attributes(flags: &[AttributeFlag]) -> String {
+    if flags.is_empty() {
+        return "-".to_string();
+    }
+    flags.iter().map(|flag| flag.name()).collect::<Vec<_>>().join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use totalimage_acquire::{E01Creator, E01Options};
+
+    /// Write an uncompressed E01 of `media` and return its path
+    fn write_e01(dir: &Path, media: &[u8]) -> String {
+        let mut image = Cursor::new(Vec::new());
+        E01Creator::new(E01Options { compress: false, ..Default::default() })
+            .create::<_, _, fn(&_)>(&mut Cursor::new(media), media.len() as u64, &mut image, None)
+            .unwrap();
+
+        let path = dir.join("image.E01");
+        std::fs::write(&path, image.into_inner()).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_verify_e01_embedded_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let media: Vec<u8> = (0..64 * 1024u32).map(|i| (i % 251) as u8).collect();
+        let path = write_e01(dir.path(), &media);
+
+        let verifications = verify_image(&path, None, None).unwrap();
+        assert_eq!(verifications.len(), 1);
+        assert_eq!(verifications[0].0, "Embedded");
+        assert!(verifications[0].1.is_match());
+
+        // Flip a byte of the first chunk, stored right after the sectors
+        // section descriptor
+        let mut image = std::fs::read(&path).unwrap();
+        let sectors = image.windows(8).position(|w| w == b"sectors\0").unwrap();
+        image[sectors + 76 + 100] ^= 0xFF;
+        std::fs::write(&path, image).unwrap();
+
+        let verifications = verify_image(&path, None, None).unwrap();
+        assert!(!verifications[0].1.is_match());
+        assert!(!cmd_verify(&path, None, None).unwrap());
+    }
+
+    #[test]
+    fn test_verify_expected_digest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("disk.img");
+        std::fs::write(&path, b"abc").unwrap();
+        let path = path.to_string_lossy().into_owned();
+
+        let sha256 = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+        let verifications = verify_image(&path, Some(sha256), None).unwrap();
+        assert_eq!(verifications[0].1.algorithm, "SHA256");
+        assert!(verifications[0].1.is_match());
+
+        let md5 = "900150983cd24fb0d6963f7d28e17f72";
+        assert!(cmd_verify(&path, Some(md5), Some("MD5")).unwrap());
+        assert!(!cmd_verify(&path, Some(&md5.replace('9', "8")), None).unwrap());
+
+        // A raw image has nothing to check without a digest
+        assert!(verify_image(&path, None, None).is_err());
+        assert!(verify_image(&path, Some("abcd"), None).is_err());
+    }
+
+    #[test]
+    fn test_export_zone() {
+        // One partition of 100 sectors at LBA 63
+        let dir = tempfile::tempdir().unwrap();
+        let mut disk = vec![0u8; 256 * 512];
+        disk[446 + 4] = 0x06;
+        disk[446 + 8..446 + 12].copy_from_slice(&63u32.to_le_bytes());
+        disk[446 + 12..446 + 16].copy_from_slice(&100u32.to_le_bytes());
+        disk[510..512].copy_from_slice(&[0x55, 0xAA]);
+        for (i, byte) in disk[63 * 512..163 * 512].iter_mut().enumerate() {
+            *byte = (i % 251) as u8 + 1;
+        }
+        let image = dir.path().join("disk.img");
+        std::fs::write(&image, &disk).unwrap();
+        let image = image.to_string_lossy().into_owned();
+
+        let output = dir.path().join("zone0.img");
+        cmd_export_zone(&image, 0, output.to_str().unwrap(), VaultFormat::Raw, true).unwrap();
+        let exported = std::fs::read(&output).unwrap();
+        assert_eq!(exported.len(), 100 * 512);
+        assert_eq!(exported, &disk[63 * 512..163 * 512]);
+
+        let output = dir.path().join("zone0.vhd");
+        cmd_export_zone(&image, 0, output.to_str().unwrap(), VaultFormat::FixedVhd, false).unwrap();
+        let mut vhd = open_vault(&output, VaultConfig::default()).unwrap();
+        assert_eq!(vhd.length(), 100 * 512);
+        let mut content = Vec::new();
+        vhd.content().read_to_end(&mut content).unwrap();
+        assert_eq!(content, &disk[63 * 512..163 * 512]);
+
+        assert!(cmd_export_zone(&image, 1, output.to_str().unwrap(), VaultFormat::Raw, false).is_err());
+        assert!(parse_export_format(Some("qcow2")).is_err());
+    }
+}
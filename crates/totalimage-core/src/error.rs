@@ -25,6 +25,18 @@ pub enum Error {
     #[error("Vault signature verification failed: {0}")]
     SignatureVerification(String),
 
+    /// Data doesn't carry the signature of the format being parsed
+    ///
+    /// Unlike the `Invalid*` variants this means "not this format" rather than
+    /// "this format, but corrupt", so callers probing several parsers can move
+    /// on to the next one.
+    #[error("Unrecognized signature: {0}")]
+    UnrecognizedSignature(String),
+
+    /// A structure ends before all of its fields could be read
+    #[error("Truncated: {0}")]
+    Truncated(String),
+
     /// Checksum verification failed
     #[error("Checksum verification failed: {0}")]
     ChecksumVerification(String),
@@ -95,4 +107,27 @@ impl Error {
     pub fn unsupported(msg: impl Into<String>) -> Self {
         Error::Unsupported(msg.into())
     }
+
+    /// Create an unrecognized signature error
+    pub fn unrecognized_signature(msg: impl Into<String>) -> Self {
+        Error::UnrecognizedSignature(msg.into())
+    }
+
+    /// Create a truncated structure error
+    pub fn truncated(msg: impl Into<String>) -> Self {
+        Error::Truncated(msg.into())
+    }
+
+    /// Create a checksum verification error
+    pub fn checksum_mismatch(msg: impl Into<String>) -> Self {
+        Error::ChecksumVerification(msg.into())
+    }
+
+    /// Whether the error means the data isn't in the expected format at all
+    ///
+    /// True only for [`Error::UnrecognizedSignature`]; every other parse
+    /// failure means the format was recognized but its structures are bad.
+    pub fn is_unrecognized(&self) -> bool {
+        matches!(self, Error::UnrecognizedSignature(_))
+    }
 }
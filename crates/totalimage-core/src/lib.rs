@@ -36,12 +36,19 @@
 //! ```
 
 pub mod error;
+pub mod progress;
 pub mod security;
 pub mod traits;
 pub mod types;
 
 // Re-export commonly used items
 pub use error::{Error, Result};
+pub use progress::{Progress, ProgressReader, ProgressUpdate};
 pub use security::*;
-pub use traits::{DirectoryCell, ReadSeek, ReadWriteSeek, Territory, Vault, ZoneTable};
-pub use types::{OccupantInfo, Zone};
+pub use traits::{
+    read_slack, DirectoryCell, ReadSeek, ReadSeekExt, ReadWriteSeek, Territory, Vault, ZoneTable, MAX_WALK_DEPTH,
+};
+pub use types::{
+    normalize_name, AttributeFlag, BackingLink, DirectoryPage, FsFeatures, ListOptions, ListSort, OccupantInfo,
+    PartialListing, SpaceAccounting, SpaceSummary, Zone,
+};
@@ -0,0 +1,153 @@
+//! Progress reporting for long operations
+//!
+//! Whole-image hashing, conversion and recursive walks accept an optional
+//! [`Progress`] reporter. Passing `None` costs a branch per update.
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// An update from a long operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressUpdate {
+    /// Bytes read so far, out of `total` if it is known
+    Bytes {
+        /// Bytes processed so far
+        processed: u64,
+        /// Bytes the operation will process in all
+        total: Option<u64>,
+    },
+    /// Directory entries visited so far
+    Entries {
+        /// Entries visited so far
+        visited: u64,
+    },
+}
+
+impl ProgressUpdate {
+    /// Get the percentage complete, if the total is known
+    pub fn percent(&self) -> Option<f64> {
+        match *self {
+            ProgressUpdate::Bytes { processed, total: Some(total) } if total > 0 => {
+                Some(processed as f64 * 100.0 / total as f64)
+            }
+            ProgressUpdate::Bytes { total: Some(_), .. } => Some(100.0),
+            _ => None,
+        }
+    }
+}
+
+/// Receiver of progress updates
+///
+/// Implemented for closures taking a [`ProgressUpdate`].
+pub trait Progress: Send + Sync {
+    /// Handle an update
+    fn update(&self, update: ProgressUpdate);
+}
+
+impl<F: Fn(ProgressUpdate) + Send + Sync> Progress for F {
+    fn update(&self, update: ProgressUpdate) {
+        self(update)
+    }
+}
+
+/// Report `update` if there is a reporter
+#[inline]
+pub fn report(progress: Option<&dyn Progress>, update: ProgressUpdate) {
+    if let Some(progress) = progress {
+        progress.update(update);
+    }
+}
+
+/// Reader that reports the bytes read through it
+///
+/// Counts from the position it was created at; seeking moves the count
+/// with the position.
+pub struct ProgressReader<'a, R> {
+    inner: R,
+    progress: Option<&'a dyn Progress>,
+    processed: u64,
+    total: Option<u64>,
+}
+
+impl<'a, R> ProgressReader<'a, R> {
+    /// Wrap `inner`, reporting to `progress` out of `total` bytes
+    pub fn new(inner: R, progress: Option<&'a dyn Progress>, total: Option<u64>) -> Self {
+        Self {
+            inner,
+            progress,
+            processed: 0,
+            total,
+        }
+    }
+
+    /// Get the bytes read so far
+    pub fn processed(&self) -> u64 {
+        self.processed
+    }
+
+    /// Unwrap the reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for ProgressReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n > 0 {
+            self.processed += n as u64;
+            report(
+                self.progress,
+                ProgressUpdate::Bytes {
+                    processed: self.processed,
+                    total: self.total,
+                },
+            );
+        }
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for ProgressReader<'_, R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let old = self.inner.stream_position()?;
+        let new = self.inner.seek(pos)?;
+        self.processed = if new >= old {
+            self.processed.saturating_add(new - old)
+        } else {
+            self.processed.saturating_sub(old - new)
+        };
+        Ok(new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_progress_reader() {
+        let updates = Mutex::new(Vec::new());
+        let progress = |update: ProgressUpdate| updates.lock().unwrap().push(update);
+
+        let mut reader = ProgressReader::new(Cursor::new(vec![0u8; 100]), Some(&progress), Some(100));
+        let mut buf = [0u8; 40];
+        reader.read_exact(&mut buf).unwrap();
+        reader.seek(SeekFrom::Current(20)).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+        assert_eq!(reader.processed(), 100);
+
+        let updates = updates.into_inner().unwrap();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[1], ProgressUpdate::Bytes { processed: 100, total: Some(100) });
+        assert_eq!(updates[0].percent(), Some(40.0));
+        assert_eq!(ProgressUpdate::Entries { visited: 3 }.percent(), None);
+
+        // No reporter, no updates
+        let mut reader = ProgressReader::new(Cursor::new(vec![0u8; 10]), None, None);
+        reader.read_exact(&mut [0u8; 10]).unwrap();
+        assert_eq!(reader.processed(), 10);
+    }
+}
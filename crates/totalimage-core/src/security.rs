@@ -5,6 +5,7 @@
 
 use crate::Error;
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
 /// Maximum sector size we'll accept (4KB - common for advanced format)
 pub const MAX_SECTOR_SIZE: u32 = 4096;
@@ -30,6 +31,83 @@ pub const MAX_CLUSTER_CHAIN_LENGTH: usize = 1_000_000;
 /// Maximum file size for memory mapping (16 GB - practical limit for most systems)
 pub const MAX_MMAP_SIZE: u64 = 16 * 1024 * 1024 * 1024;
 
+/// Adjustable resource limits applied while parsing and extracting
+///
+/// The defaults match the `MAX_*` constants above. Territories take the
+/// process-wide [active](SecurityLimits::active) limits when parsed unless
+/// given their own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecurityLimits {
+    /// Maximum allocation size for a single buffer
+    pub max_allocation_size: usize,
+    /// Maximum FAT table size
+    pub max_fat_table_size: usize,
+    /// Maximum file size to extract
+    pub max_file_extract_size: u64,
+}
+
+impl SecurityLimits {
+    /// Limits matching the compile-time constants
+    pub const DEFAULT: Self = Self {
+        max_allocation_size: MAX_ALLOCATION_SIZE,
+        max_fat_table_size: MAX_FAT_TABLE_SIZE,
+        max_file_extract_size: MAX_FILE_EXTRACT_SIZE,
+    };
+
+    /// Get the process-wide active limits
+    pub fn active() -> Self {
+        *ACTIVE_LIMITS.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Replace the process-wide active limits
+    ///
+    /// Affects territories parsed afterwards; already-parsed ones keep the
+    /// limits they were created with.
+    pub fn set_active(limits: Self) {
+        *ACTIVE_LIMITS.write().unwrap_or_else(|e| e.into_inner()) = limits;
+    }
+
+    /// Check a file size against the extraction limit
+    pub fn validate_extract_size(&self, size: u64) -> crate::Result<()> {
+        if size > self.max_file_extract_size {
+            return Err(Error::invalid_territory(format!(
+                "File size {} exceeds extraction limit {}",
+                size, self.max_file_extract_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl Default for SecurityLimits {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Limits returned by [`SecurityLimits::active`]
+static ACTIVE_LIMITS: RwLock<SecurityLimits> = RwLock::new(SecurityLimits::DEFAULT);
+
+/// Get the active maximum file extraction size
+pub fn max_file_extract_size() -> u64 {
+    SecurityLimits::active().max_file_extract_size
+}
+
+/// Get the active maximum FAT table size
+pub fn max_fat_table_size() -> usize {
+    SecurityLimits::active().max_fat_table_size
+}
+
+/// Get the active maximum single allocation size
+pub fn max_allocation_size() -> usize {
+    SecurityLimits::active().max_allocation_size
+}
+
+/// Check a file size against the active extraction limit
+pub fn validate_extract_size(size: u64) -> crate::Result<()> {
+    SecurityLimits::active().validate_extract_size(size)
+}
+
 /// Validate that a size is within allocation limits
 ///
 /// # Security
@@ -136,6 +214,20 @@ pub fn validate_file_path(path: &str) -> crate::Result<PathBuf> {
     Ok(canonical)
 }
 
+/// Validate that a byte range lies within a file of `file_size` bytes
+///
+/// The range must also fit the active extraction limit, as it is read into
+/// memory.
+pub fn validate_file_range(file_size: u64, offset: u64, length: u64) -> crate::Result<()> {
+    if offset.checked_add(length).is_none_or(|end| end > file_size) {
+        return Err(Error::InvalidOperation(format!(
+            "Range {}+{} extends beyond the end of the file ({} bytes)",
+            offset, length, file_size
+        )));
+    }
+    validate_extract_size(length)
+}
+
 /// Validate partition index is within bounds
 pub fn validate_partition_index(index: usize, max: usize) -> crate::Result<()> {
     if index >= max {
@@ -166,6 +258,23 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn test_security_limits() {
+        let defaults = SecurityLimits::default();
+        assert_eq!(defaults.max_file_extract_size, MAX_FILE_EXTRACT_SIZE);
+        assert_eq!(defaults.max_fat_table_size, MAX_FAT_TABLE_SIZE);
+
+        // 2 GiB is rejected by default but allowed once the limit is raised
+        let size = 2 * 1024 * 1024 * 1024;
+        assert!(defaults.validate_extract_size(size).is_err());
+
+        let raised = SecurityLimits {
+            max_file_extract_size: 4 * 1024 * 1024 * 1024,
+            ..Default::default()
+        };
+        assert!(raised.validate_extract_size(size).is_ok());
+    }
+
     #[test]
     fn test_checked_multiply_u64() {
         // Valid multiplication
@@ -178,6 +287,14 @@ mod tests {
         assert!(checked_multiply_u64(u64::MAX, 2, "test").is_err());
     }
 
+    #[test]
+    fn test_validate_file_range() {
+        assert!(validate_file_range(8192, 4096, 4096).is_ok());
+        assert!(validate_file_range(8192, 8192, 0).is_ok());
+        assert!(matches!(validate_file_range(8192, 4096, 4097), Err(Error::InvalidOperation(_))));
+        assert!(validate_file_range(8192, u64::MAX, 2).is_err());
+    }
+
     #[test]
     fn test_validate_sector_size() {
         // Valid sizes
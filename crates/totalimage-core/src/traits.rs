@@ -1,7 +1,18 @@
 //! Core traits for Total Liberation
 
-use crate::{error::Result, types::{OccupantInfo, Zone}};
-use std::io::{Read, Seek, Write};
+use crate::{
+    error::{Error, Result},
+    security::{max_allocation_size, validate_allocation_size, validate_file_range, validate_sector_size},
+    types::{
+        normalize_name, AttributeFlag, BackingLink, DirectoryPage, FsFeatures, ListOptions, OccupantInfo, PartialListing,
+        SpaceAccounting, SpaceSummary, Zone,
+    },
+};
+use crate::progress::{report, Progress, ProgressUpdate};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// Deepest directory nesting [`Territory::walk`] follows
+pub const MAX_WALK_DEPTH: usize = 256;
 
 /// Trait for disk image vaults (containers)
 pub trait Vault: Send + Sync {
@@ -9,10 +20,107 @@ pub trait Vault: Send + Sync {
     fn identify(&self) -> &str;
 
     /// Get the total size of the vault in bytes
+    ///
+    /// This is the size of the virtual disk [`content`](Self::content)
+    /// exposes, not of the container. Implementations settle it when the
+    /// vault is opened, so calling this repeatedly costs nothing.
     fn length(&self) -> u64;
 
+    /// Get the number of `sector_size`-byte sectors the vault spans
+    ///
+    /// A trailing partial sector counts as a whole one. Returns 0 when
+    /// `sector_size` is 0.
+    fn sector_count(&self, sector_size: u32) -> u64 {
+        match sector_size {
+            0 => 0,
+            size => self.length().div_ceil(size as u64),
+        }
+    }
+
+    /// List the image files the content is read from, this one first
+    ///
+    /// A differencing image is followed by its parent, that parent's
+    /// parent, and so on to the base image. The default describes a vault
+    /// that stands alone, with no path.
+    fn backing_chain(&self) -> Vec<BackingLink> {
+        vec![BackingLink {
+            path: None,
+            format: self.identify().to_string(),
+            virtual_size: self.length(),
+        }]
+    }
+
     /// Get a readable and seekable stream to the vault content
     fn content(&mut self) -> &mut dyn ReadSeek;
+
+    /// Read exactly `buf.len()` bytes of content starting at `offset`
+    ///
+    /// The default seeks [`content`](Self::content) and reads from it.
+    /// Vaults holding their content in memory copy it directly instead, so
+    /// the position of `content` afterwards is unspecified.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes run past the end of the content or
+    /// can't be read.
+    fn read_exact_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<()> {
+        self.content().read_exact_at(offset, buf)
+    }
+
+    /// Read sector `lba` into `buf`, which must be `sector_size` bytes long
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `sector_size` isn't a supported sector size, if
+    /// `buf` is the wrong length, or if the sector doesn't lie wholly
+    /// within [`length`](Self::length), as a trailing partial sector doesn't.
+    fn read_sector(&mut self, lba: u64, sector_size: u32, buf: &mut [u8]) -> Result<()> {
+        if buf.len() != sector_size as usize {
+            return Err(Error::InvalidOperation(format!(
+                "Sector buffer holds {} bytes, not {}",
+                buf.len(),
+                sector_size
+            )));
+        }
+        let (offset, _) = sector_range(self.length(), lba, 1, sector_size)?;
+        self.read_exact_at(offset, buf)
+    }
+
+    /// Read `count` sectors starting at sector `lba`
+    ///
+    /// # Errors
+    ///
+    /// As [`read_sector`](Self::read_sector), or if the sectors would need
+    /// more memory than the allocation limit allows.
+    fn read_sectors(&mut self, lba: u64, count: u64, sector_size: u32) -> Result<Vec<u8>> {
+        let (offset, length) = sector_range(self.length(), lba, count, sector_size)?;
+        let mut data = vec![0u8; validate_allocation_size(length, max_allocation_size(), "Sector read")?];
+        self.read_exact_at(offset, &mut data)?;
+        Ok(data)
+    }
+
+    /// Get a writable stream to the vault content, for in-place editing
+    ///
+    /// Returns None for read-only vaults. Defaults to None.
+    fn content_mut(&mut self) -> Option<&mut dyn ReadWriteSeek> {
+        None
+    }
+}
+
+/// Get the byte offset and length of `count` sectors from `lba`, checking
+/// they lie within `length` bytes
+fn sector_range(length: u64, lba: u64, count: u64, sector_size: u32) -> Result<(u64, u64)> {
+    validate_sector_size(sector_size)?;
+    let range = lba
+        .checked_mul(sector_size as u64)
+        .zip(count.checked_mul(sector_size as u64))
+        .filter(|(offset, bytes)| offset.checked_add(*bytes).is_some_and(|end| end <= length));
+    range.ok_or_else(|| {
+        Error::InvalidOperation(format!(
+            "Sectors {}+{} of {} bytes extend beyond the end of the vault ({} bytes)",
+            lba, count, sector_size, length
+        ))
+    })
 }
 
 /// Trait for partition tables (zone tables)
@@ -27,6 +135,14 @@ pub trait ZoneTable: Send + Sync {
     fn get_zone(&self, index: usize) -> Option<&Zone> {
         self.enumerate_zones().get(index)
     }
+
+    /// Find the space on a `disk_len`-byte disk that no zone covers
+    ///
+    /// Gaps between and after partitions are where data hides from tools
+    /// that only look inside file systems.
+    fn space_accounting(&self, disk_len: u64) -> SpaceAccounting {
+        SpaceAccounting::from_zones(self.enumerate_zones(), disk_len)
+    }
 }
 
 /// Trait for file systems (territories)
@@ -45,7 +161,10 @@ pub trait Territory: Send + Sync {
     }
 
     /// Get the root directory
-    fn headquarters(&self) -> Result<Box<dyn DirectoryCell>>;
+    ///
+    /// The returned cell borrows the territory; pass the territory's
+    /// content stream to the cell's methods to read directory contents.
+    fn headquarters(&self) -> Result<Box<dyn DirectoryCell<'_> + '_>>;
 
     /// Get total size of the territory in bytes
     fn domain_size(&self) -> u64;
@@ -56,44 +175,497 @@ pub trait Territory: Send + Sync {
     /// Get allocation unit (cluster/block) size in bytes
     fn block_size(&self) -> u64;
 
+    /// Summarize total, used and free space in one call
+    ///
+    /// `stream` is the territory's content, for file systems that must read
+    /// allocation metadata from disk. The default builds the summary from
+    /// [`domain_size`](Self::domain_size), [`liberated_space`](Self::liberated_space)
+    /// and [`block_size`](Self::block_size).
+    fn space_summary(&self, _stream: &mut dyn ReadSeek) -> Result<SpaceSummary> {
+        let total = self.domain_size();
+        let block_size = self.block_size();
+        let (block_count, free_blocks) = match block_size {
+            0 => (0, 0),
+            size => (total / size, self.liberated_space() / size),
+        };
+
+        Ok(SpaceSummary::from_blocks(total, block_size, block_count, free_blocks))
+    }
+
     /// Does this territory support subdirectories?
     fn hierarchical(&self) -> bool;
 
+    /// Report what the file system supports
+    ///
+    /// The default claims no features beyond reading files.
+    fn fs_features(&self) -> FsFeatures {
+        FsFeatures::default()
+    }
+
     /// Navigate to a directory by path
-    fn navigate_to(&self, path: &str) -> Result<Box<dyn DirectoryCell>>;
+    ///
+    /// Each path component is entered from the root, so a missing or
+    /// non-directory component is reported here rather than on listing.
+    fn navigate_to(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Box<dyn DirectoryCell<'_> + '_>> {
+        let mut cell = self.headquarters()?;
+        for component in path.split(['/', '\\']).filter(|s| !s.is_empty()) {
+            cell = cell.enter(stream, component)?;
+        }
+        Ok(cell)
+    }
+
+    /// Map a path to the names stored on disk, comparing names in Unicode NFC
+    ///
+    /// Each component becomes the name of the occupant with the same NFC
+    /// form, or stays as given if there is none, so the territory's own
+    /// name matching still applies to it. The result is `/`-separated and
+    /// starts at the root.
+    fn resolve_path(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<String> {
+        let components: Vec<&str> = path.split(['/', '\\']).filter(|s| !s.is_empty()).collect();
+        let mut cell = self.headquarters()?;
+        let mut resolved = String::new();
+
+        for (i, component) in components.iter().enumerate() {
+            let wanted = normalize_name(component);
+            let name = cell
+                .list_occupants(stream)?
+                .into_iter()
+                .map(|occupant| occupant.name)
+                .find(|name| normalize_name(name) == wanted)
+                .unwrap_or_else(|| component.to_string());
+
+            if i + 1 < components.len() {
+                cell = cell.enter(stream, &name)?;
+            }
+            resolved.push('/');
+            resolved.push_str(&name);
+        }
+
+        if resolved.is_empty() {
+            resolved.push('/');
+        }
+        Ok(resolved)
+    }
+
+    /// List a directory by path, filling in each occupant's full path
+    ///
+    /// Paths are `/`-separated and start at the root, whatever separator
+    /// `path` itself uses.
+    fn list_directory(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<OccupantInfo>> {
+        let mut occupants = self.navigate_to(stream, path)?.list_occupants(stream)?;
+        fill_paths(path, &mut occupants);
+        Ok(occupants)
+    }
+
+    /// List up to `limit` occupants of a directory, starting at `cursor`
+    ///
+    /// Pass `0` for the first page and the returned
+    /// [`next`](DirectoryPage::next) for each following one. Paths are
+    /// filled in as by [`list_directory`](Self::list_directory).
+    fn list_directory_page(
+        &self,
+        stream: &mut dyn ReadSeek,
+        path: &str,
+        cursor: u64,
+        limit: usize,
+    ) -> Result<DirectoryPage> {
+        let mut page = self.navigate_to(stream, path)?.list_page(stream, cursor, limit)?;
+        fill_paths(path, &mut page.occupants);
+        Ok(page)
+    }
+
+    /// List a directory by path, sorted and filtered by `options`
+    ///
+    /// With default options this is [`list_directory`](Self::list_directory).
+    fn list_directory_with(
+        &self,
+        stream: &mut dyn ReadSeek,
+        path: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<OccupantInfo>> {
+        Ok(self.list_directory_partial(stream, path, options)?.occupants)
+    }
+
+    /// List a directory by path as [`list_directory_with`](Self::list_directory_with)
+    /// does, counting the entries a best-effort listing skipped
+    ///
+    /// Without [`ListOptions::best_effort`] any error fails the listing and
+    /// the count is always 0.
+    fn list_directory_partial(
+        &self,
+        stream: &mut dyn ReadSeek,
+        path: &str,
+        options: &ListOptions,
+    ) -> Result<PartialListing> {
+        let resolved;
+        let path = if options.normalize_unicode {
+            resolved = self.resolve_path(stream, path)?;
+            &resolved
+        } else {
+            path
+        };
+
+        let cell = self.navigate_to(stream, path)?;
+        let mut listing = if options.best_effort {
+            cell.list_occupants_best_effort(stream)?
+        } else {
+            PartialListing::from(cell.list_occupants(stream)?)
+        };
+        fill_paths(path, &mut listing.occupants);
+        fill_skipped(path, &mut listing.skipped);
+        listing.occupants = options.apply(listing.occupants);
+        Ok(listing)
+    }
+
+    /// List every occupant below a directory, with full paths
+    ///
+    /// A directory is listed before its contents. `progress` gets an
+    /// [`Entries`](ProgressUpdate::Entries) update after each occupant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory can't be read, or if the tree is
+    /// more than [`MAX_WALK_DEPTH`] levels deep, as a directory cycle in a
+    /// damaged volume would make it
+    fn walk(
+        &self,
+        stream: &mut dyn ReadSeek,
+        path: &str,
+        progress: Option<&dyn Progress>,
+    ) -> Result<Vec<OccupantInfo>> {
+        Ok(self.walk_partial(stream, path, false, progress)?.occupants)
+    }
+
+    /// Walk a directory tree as [`walk`](Self::walk) does, optionally
+    /// carrying on past damage
+    ///
+    /// With `best_effort`, each directory is listed with
+    /// [`list_occupants_best_effort`](DirectoryCell::list_occupants_best_effort),
+    /// and a directory that can't be listed or entered, or that is more
+    /// than [`MAX_WALK_DEPTH`] levels deep, is skipped and recorded in
+    /// [`PartialListing::skipped`] with its error. The directory at `path`
+    /// must still resolve.
+    fn walk_partial(
+        &self,
+        stream: &mut dyn ReadSeek,
+        path: &str,
+        best_effort: bool,
+        progress: Option<&dyn Progress>,
+    ) -> Result<PartialListing> {
+        let mut walked = PartialListing::default();
+        let mut pending = vec![(path.to_string(), 0, self.navigate_to(stream, path)?)];
+
+        while let Some((directory, depth, cell)) = pending.pop() {
+            let listing = if best_effort {
+                cell.list_occupants_best_effort(stream)
+            } else {
+                cell.list_occupants(stream).map(PartialListing::from)
+            };
+            let mut listing = match listing {
+                Ok(listing) => listing,
+                Err(e) if best_effort => {
+                    walked.skip(directory, e.to_string());
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            fill_paths(&directory, &mut listing.occupants);
+            fill_skipped(&directory, &mut listing.skipped);
+            walked.errors += listing.errors;
+            walked.skipped.append(&mut listing.skipped);
+
+            for occupant in listing.occupants {
+                if occupant.is_directory {
+                    let child_path = occupant.path.clone().unwrap_or_default();
+                    let child = if depth == MAX_WALK_DEPTH {
+                        Err(Error::invalid_territory(format!(
+                            "Directory tree below {} is over {} levels deep",
+                            path, MAX_WALK_DEPTH
+                        )))
+                    } else {
+                        cell.enter(stream, &occupant.name)
+                    };
+                    match child {
+                        Ok(child) => pending.push((child_path, depth + 1, child)),
+                        Err(e) if best_effort => walked.skip(child_path, e.to_string()),
+                        Err(e) => return Err(e),
+                    }
+                }
+                walked.occupants.push(occupant);
+                report(progress, ProgressUpdate::Entries { visited: walked.occupants.len() as u64 });
+            }
+        }
+
+        Ok(walked)
+    }
+
+    /// Get metadata for a single file or directory by path
+    ///
+    /// Equivalent to a `stat` call: only the directories along the path are
+    /// read, rather than listing the parent and filtering. The default looks
+    /// the name up in the parent's [`DirectoryCell`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a not-found error if the path doesn't exist. The root
+    /// directory has no entry of its own, so an empty path is not found either.
+    fn file_metadata(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<OccupantInfo> {
+        let path = path.trim_matches(['/', '\\']);
+        let (parent, name) = path.rsplit_once(['/', '\\']).unwrap_or(("", path));
+        if name.is_empty() {
+            return Err(Error::not_found("Empty path".to_string()));
+        }
+
+        self.navigate_to(stream, parent)?
+            .get_occupant(stream, name)?
+            .ok_or_else(|| Error::not_found(format!("File not found: {}", path)))
+    }
+
+    /// Get metadata by path as [`file_metadata`](Self::file_metadata) does,
+    /// optionally comparing names in Unicode NFC
+    ///
+    /// With `normalize_unicode` the path is first mapped to the names
+    /// stored on disk by [`resolve_path`](Self::resolve_path).
+    fn file_metadata_with(
+        &self,
+        stream: &mut dyn ReadSeek,
+        path: &str,
+        normalize_unicode: bool,
+    ) -> Result<OccupantInfo> {
+        if normalize_unicode {
+            let resolved = self.resolve_path(stream, path)?;
+            self.file_metadata(stream, &resolved)
+        } else {
+            self.file_metadata(stream, path)
+        }
+    }
+
+    /// Get the byte extents holding a file's data on disk
+    ///
+    /// Returns `(offset, length)` pairs relative to the territory's stream,
+    /// in file order, with physically adjacent allocation units merged. The
+    /// extents cover the file's size rather than whole allocation units, so
+    /// reading them in order reproduces the file; sparse ranges and data
+    /// stored inside file system metadata have no extent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a not-found error if the path doesn't exist, or an
+    /// unsupported error if the file system can't report its layout.
+    fn data_runs(&self, _stream: &mut dyn ReadSeek, _path: &str) -> Result<Vec<(u64, u64)>> {
+        Err(Error::unsupported(format!("{} can't report file layout", self.identify())))
+    }
+
+    /// Read `length` bytes of a file starting `offset` bytes in
+    ///
+    /// Only the data holding the range is read, so a window of a large file
+    /// costs no more than the window itself. The default reads through
+    /// [`data_runs`](Self::data_runs), and so needs every byte of the file
+    /// to have an extent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a not-found error if the path doesn't exist, and an invalid
+    /// operation error if it is a directory or the range extends beyond the
+    /// end of the file.
+    fn read_file_range(
+        &self,
+        stream: &mut dyn ReadSeek,
+        path: &str,
+        offset: u64,
+        length: u64,
+    ) -> Result<Vec<u8>> {
+        let info = self.file_metadata(stream, path)?;
+        if info.is_directory {
+            return Err(Error::InvalidOperation(format!("Path is a directory: {}", path)));
+        }
+        validate_file_range(info.size, offset, length)?;
+
+        let runs = self.data_runs(stream, path)?;
+        if runs.iter().map(|&(_, run_length)| run_length).sum::<u64>() < info.size {
+            return Err(Error::unsupported(format!(
+                "{} is sparse or stored inside file system metadata",
+                path
+            )));
+        }
+
+        let end = offset + length;
+        let mut data = Vec::with_capacity(length as usize);
+        let mut position = 0u64;
+        for (run_offset, run_length) in runs {
+            if position >= end {
+                break;
+            }
+            let run_end = position + run_length;
+            if run_end > offset {
+                let skip = offset.saturating_sub(position);
+                let to_read = run_end.min(end) - position - skip;
+
+                stream.seek(SeekFrom::Start(run_offset + skip))?;
+                let start = data.len();
+                data.resize(start + to_read as usize, 0);
+                stream.read_exact(&mut data[start..])?;
+            }
+            position = run_end;
+        }
+
+        Ok(data)
+    }
+
+    /// Read a file's slack: the bytes between its end and the end of its
+    /// last allocation unit
+    ///
+    /// The file system never clears slack, so it can keep fragments of
+    /// earlier files or hold data hidden on purpose. It is empty when the
+    /// size is a whole number of units and when the end of the file isn't
+    /// stored in a unit of its own, as with sparse tails or data resident in
+    /// file system metadata. The default finds the last unit through
+    /// [`data_runs`](Self::data_runs) and [`file_metadata`](Self::file_metadata).
+    ///
+    /// # Errors
+    ///
+    /// Returns a not-found error if the path doesn't exist, an invalid
+    /// operation error if it is a directory, or an unsupported error if the
+    /// file system can't report file layout.
+    fn file_slack(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<u8>> {
+        let info = self.file_metadata(stream, path)?;
+        if info.is_directory {
+            return Err(Error::InvalidOperation(format!("Path is a directory: {}", path)));
+        }
+
+        let runs = self.data_runs(stream, path)?;
+        read_slack(stream, &runs, info.size, self.block_size())
+    }
+
+    /// Interpret an occupant's raw attribute bits
+    ///
+    /// `raw` is [`OccupantInfo::attributes`] as this territory reports it.
+    /// The default knows no bit layout and returns no flags.
+    fn decode_attributes(&self, _raw: u32) -> Vec<AttributeFlag> {
+        Vec::new()
+    }
 
     /// Extract a file by path
     fn extract_file(&mut self, path: &str) -> Result<Vec<u8>>;
 }
 
 /// Trait for directory operations
-pub trait DirectoryCell: Send + Sync {
+///
+/// `'a` is the lifetime of the territory the cell belongs to, so cells
+/// reached through [`enter`](Self::enter) outlive the cell they came from.
+pub trait DirectoryCell<'a>: Send + Sync {
     /// Get the directory name
     fn name(&self) -> &str;
 
     /// List all occupants (files and subdirectories) in this directory
-    fn list_occupants(&self) -> Result<Vec<OccupantInfo>>;
+    ///
+    /// `stream` is the content of the territory the cell belongs to.
+    fn list_occupants(&self, stream: &mut dyn ReadSeek) -> Result<Vec<OccupantInfo>>;
+
+    /// List occupants, skipping any that fail to read
+    ///
+    /// The default can't skip single entries, so it fails as
+    /// [`list_occupants`](Self::list_occupants) does. Implementations that
+    /// can should log each error, keep its message in
+    /// [`PartialListing::skipped`], and carry on with the next entry.
+    fn list_occupants_best_effort(&self, stream: &mut dyn ReadSeek) -> Result<PartialListing> {
+        Ok(PartialListing::from(self.list_occupants(stream)?))
+    }
+
+    /// List up to `limit` occupants, starting at `cursor`
+    ///
+    /// A `limit` of 0 returns an [`unmoved`](DirectoryPage::unmoved) page
+    /// without reading the directory. The default lists the whole directory
+    /// and uses indices as cursors. Implementations that can seek to a saved position in the
+    /// directory should, so later pages don't re-read the entries before them.
+    fn list_page(&self, stream: &mut dyn ReadSeek, cursor: u64, limit: usize) -> Result<DirectoryPage> {
+        Ok(DirectoryPage::from_listing(self.list_occupants(stream)?, cursor, limit))
+    }
 
     /// Enter a subdirectory by name
-    fn enter(&self, name: &str) -> Result<Box<dyn DirectoryCell>>;
+    fn enter(&self, stream: &mut dyn ReadSeek, name: &str) -> Result<Box<dyn DirectoryCell<'a> + 'a>>;
 
     /// Check if a file or directory exists
-    fn exists(&self, name: &str) -> Result<bool> {
-        Ok(self.list_occupants()?.iter().any(|o| o.name == name))
+    fn exists(&self, stream: &mut dyn ReadSeek, name: &str) -> Result<bool> {
+        Ok(self.list_occupants(stream)?.iter().any(|o| o.name == name))
     }
 
     /// Get info about a specific occupant
-    fn get_occupant(&self, name: &str) -> Result<Option<OccupantInfo>> {
-        Ok(self.list_occupants()?.into_iter().find(|o| o.name == name))
+    fn get_occupant(&self, stream: &mut dyn ReadSeek, name: &str) -> Result<Option<OccupantInfo>> {
+        Ok(self.list_occupants(stream)?.into_iter().find(|o| o.name == name))
     }
 }
 
+/// Set each occupant's path from the path of the directory holding it
+fn fill_paths(directory: &str, occupants: &mut [OccupantInfo]) {
+    let parent: String = directory
+        .split(['/', '\\'])
+        .filter(|s| !s.is_empty())
+        .map(|component| format!("/{}", component))
+        .collect();
+
+    for occupant in occupants {
+        occupant.path = Some(format!("{}/{}", parent, occupant.name));
+    }
+}
+
+/// Give the skips a cell recorded without a path the path of `directory`
+fn fill_skipped(directory: &str, skipped: &mut [(String, String)]) {
+    let mut path: String = directory
+        .split(['/', '\\'])
+        .filter(|s| !s.is_empty())
+        .map(|component| format!("/{}", component))
+        .collect();
+    if path.is_empty() {
+        path.push('/');
+    }
+
+    for (skipped_path, _) in skipped.iter_mut().filter(|(p, _)| p.is_empty()) {
+        skipped_path.clone_from(&path);
+    }
+}
+
+/// Read the slack after a file of `size` bytes stored in `runs`
+///
+/// `runs` are the file's extents as [`Territory::data_runs`] reports them
+/// and `unit` is the allocation unit size. The slack runs from the end of
+/// the last extent to the end of its unit. Nothing is read unless the
+/// extents hold the whole file, since a short total means its end isn't on
+/// disk.
+pub fn read_slack(stream: &mut dyn ReadSeek, runs: &[(u64, u64)], size: u64, unit: u64) -> Result<Vec<u8>> {
+    let tail = if unit == 0 { 0 } else { size % unit };
+    let stored: u64 = runs.iter().map(|&(_, length)| length).sum();
+    let Some(&(last_offset, last_length)) = runs.last() else {
+        return Ok(Vec::new());
+    };
+    if tail == 0 || stored < size {
+        return Ok(Vec::new());
+    }
+
+    let mut slack = vec![0u8; (unit - tail) as usize];
+    stream.seek(SeekFrom::Start(last_offset + last_length))?;
+    stream.read_exact(&mut slack)?;
+    Ok(slack)
+}
+
 /// Combined trait for Read + Seek
 pub trait ReadSeek: Read + Seek + Send + Sync {}
 
 /// Blanket implementation for any type that implements Read + Seek + Sync
 impl<T: Read + Seek + Send + Sync> ReadSeek for T {}
 
+/// Positional reads for any seekable stream
+pub trait ReadSeekExt: Read + Seek {
+    /// Seek to `offset` and read exactly `buf.len()` bytes
+    fn read_exact_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<()> {
+        self.seek(SeekFrom::Start(offset))?;
+        self.read_exact(buf)?;
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek + ?Sized> ReadSeekExt for R {}
+
 /// Combined trait for Read + Write + Seek
 pub trait ReadWriteSeek: Read + Write + Seek + Send + Sync {}
 
@@ -1,8 +1,12 @@
 //! Core types for Total Liberation
 
-use chrono::{DateTime, Utc};
+use crate::error::{Error, Result};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
+use std::path::PathBuf;
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
 
 /// Information about a file or directory occupant in a territory
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,8 +29,39 @@ pub struct OccupantInfo {
     /// Last accessed timestamp
     pub accessed: Option<DateTime<Utc>>,
 
+    /// True if the timestamps are wall-clock time in an unknown time zone
+    ///
+    /// Territories convert timestamps to UTC when the file system records
+    /// enough to do so: NTFS stores UTC, and exFAT and ISO-9660 store an
+    /// offset from it. FAT stores local time with no zone, so its timestamps
+    /// are reported as if they were UTC and this flag is set.
+    #[serde(default)]
+    pub timestamp_is_local: bool,
+
     /// File attributes (platform-specific)
     pub attributes: u32,
+
+    /// Target of a symbolic link or junction, if this occupant is one
+    #[serde(default)]
+    pub link_target: Option<String>,
+
+    /// DOS 8.3 alias, if the file system stores one that differs from `name`
+    #[serde(default)]
+    pub short_name: Option<String>,
+
+    /// Number of directory entries naming this occupant
+    ///
+    /// More than one for an NTFS file with hard links; always 1 on file
+    /// systems without them.
+    #[serde(default = "default_link_count")]
+    pub link_count: u32,
+
+    /// Path from the root of the territory, when the listing provides one
+    ///
+    /// Set by [`Territory::list_directory`](crate::Territory::list_directory);
+    /// a [`DirectoryCell`](crate::DirectoryCell) only knows names.
+    #[serde(default)]
+    pub path: Option<String>,
 }
 
 impl OccupantInfo {
@@ -39,7 +74,12 @@ impl OccupantInfo {
             created: None,
             modified: None,
             accessed: None,
+            timestamp_is_local: false,
             attributes: 0,
+            link_target: None,
+            short_name: None,
+            link_count: 1,
+            path: None,
         }
     }
 
@@ -52,7 +92,12 @@ impl OccupantInfo {
             created: None,
             modified: None,
             accessed: None,
+            timestamp_is_local: false,
             attributes: 0,
+            link_target: None,
+            short_name: None,
+            link_count: 1,
+            path: None,
         }
     }
 
@@ -79,6 +124,29 @@ impl OccupantInfo {
         self.attributes = attributes;
         self
     }
+
+    /// Set link target
+    pub fn with_link_target(mut self, target: String) -> Self {
+        self.link_target = Some(target);
+        self
+    }
+
+    /// Set short (8.3) name
+    pub fn with_short_name(mut self, short_name: String) -> Self {
+        self.short_name = Some(short_name);
+        self
+    }
+
+    /// Set hard link count
+    pub fn with_link_count(mut self, link_count: u32) -> Self {
+        self.link_count = link_count;
+        self
+    }
+}
+
+/// Default for [`OccupantInfo::link_count`] when deserializing
+fn default_link_count() -> u32 {
+    1
 }
 
 impl fmt::Display for OccupantInfo {
@@ -116,6 +184,85 @@ fn format_size(size: u64) -> String {
     }
 }
 
+/// A file attribute in the vocabulary shared by all territories
+///
+/// Each territory stores attributes in its own bit layout;
+/// [`Territory::decode_attributes`](crate::Territory::decode_attributes)
+/// translates those bits into these flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AttributeFlag {
+    /// Contents may not be modified
+    ReadOnly,
+    /// Hidden from normal listings
+    Hidden,
+    /// Belongs to the operating system
+    System,
+    /// Entry holds the volume label (FAT)
+    VolumeLabel,
+    /// Entry is a directory
+    Directory,
+    /// Changed since the last backup
+    Archive,
+    /// Short-lived file the system may keep in memory
+    Temporary,
+    /// Contains unallocated ranges that read as zeros
+    Sparse,
+    /// Carries a reparse point, such as a symbolic link or junction
+    ReparsePoint,
+    /// Stored compressed
+    Compressed,
+    /// Data has been moved to offline storage
+    Offline,
+    /// Excluded from content indexing
+    NotIndexed,
+    /// Stored encrypted
+    Encrypted,
+    /// Associated file, such as a resource fork (ISO 9660)
+    Associated,
+}
+
+impl AttributeFlag {
+    /// Get the flag's display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read-only",
+            Self::Hidden => "hidden",
+            Self::System => "system",
+            Self::VolumeLabel => "volume-label",
+            Self::Directory => "directory",
+            Self::Archive => "archive",
+            Self::Temporary => "temporary",
+            Self::Sparse => "sparse",
+            Self::ReparsePoint => "reparse-point",
+            Self::Compressed => "compressed",
+            Self::Offline => "offline",
+            Self::NotIndexed => "not-indexed",
+            Self::Encrypted => "encrypted",
+            Self::Associated => "associated",
+        }
+    }
+
+    /// Decode raw attribute bits using a territory's bit assignments
+    ///
+    /// Flags come back in table order, each at most once, so several bits may
+    /// map to the same flag. Bits missing from the table are ignored.
+    pub fn decode(raw: u32, table: &[(u32, AttributeFlag)]) -> Vec<AttributeFlag> {
+        let mut flags = Vec::new();
+        for &(bit, flag) in table {
+            if raw & bit != 0 && !flags.contains(&flag) {
+                flags.push(flag);
+            }
+        }
+        flags
+    }
+}
+
+impl fmt::Display for AttributeFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 /// A zone (partition) within a vault
 #[derive(Debug, Clone)]
 pub struct Zone {
@@ -171,10 +318,458 @@ impl fmt::Display for Zone {
     }
 }
 
+/// What a file system can represent and what this crate can do with it
+///
+/// Reported by [`Territory::fs_features`](crate::Territory::fs_features) so
+/// front ends can hide operations a territory doesn't support.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FsFeatures {
+    /// Files can be created, deleted or renamed through this crate
+    pub writable: bool,
+    /// Files carry timestamps
+    pub timestamps: bool,
+    /// Symbolic links or junctions, reported as [`OccupantInfo::link_target`]
+    pub symlinks: bool,
+    /// Named data streams besides a file's main data
+    pub alternate_data_streams: bool,
+    /// Transparent per-file compression
+    pub compression: bool,
+    /// Several directory entries can name the same file
+    pub hard_links: bool,
+    /// Names longer than the DOS 8.3 form
+    pub long_names: bool,
+    /// DOS 8.3 names kept beside long names, reported as
+    /// [`OccupantInfo::short_name`]
+    pub short_names: bool,
+    /// Largest file size the format can record, in bytes
+    pub max_file_size: u64,
+}
+
+/// Space usage of a territory
+///
+/// `used + free == total` always holds; `used` includes file system
+/// metadata as well as file data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpaceSummary {
+    /// Total size of the territory in bytes
+    pub total: u64,
+    /// Bytes not available for new data
+    pub used: u64,
+    /// Bytes in free allocation blocks
+    pub free: u64,
+    /// Allocation block (cluster) size in bytes
+    pub block_size: u64,
+    /// Number of allocation blocks
+    pub block_count: u64,
+    /// Number of free allocation blocks
+    pub free_blocks: u64,
+}
+
+impl SpaceSummary {
+    /// Build a summary from allocation block counts
+    ///
+    /// Free space is clamped to `total` so a corrupt free count can't break
+    /// the `used + free == total` invariant.
+    pub fn from_blocks(total: u64, block_size: u64, block_count: u64, free_blocks: u64) -> Self {
+        let free = free_blocks.saturating_mul(block_size).min(total);
+        Self {
+            total,
+            used: total - free,
+            free,
+            block_size,
+            block_count,
+            free_blocks,
+        }
+    }
+}
+
+/// How much of a disk its partitions cover
+///
+/// `accounted + unaccounted == disk_len` always holds. Overlapping zones are
+/// counted once, and the parts of zones past the end of the disk not at
+/// all. The partition table's own sectors lie outside every zone, so they
+/// are part of the gaps.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpaceAccounting {
+    /// Length of the disk in bytes
+    pub disk_len: u64,
+    /// Bytes inside at least one zone
+    pub accounted: u64,
+    /// Bytes outside every zone
+    pub unaccounted: u64,
+    /// Ranges outside every zone as `(offset, length)`, in disk order
+    pub gaps: Vec<(u64, u64)>,
+}
+
+impl SpaceAccounting {
+    /// Account for a `disk_len`-byte disk divided into `zones`
+    pub fn from_zones(zones: &[Zone], disk_len: u64) -> Self {
+        let mut extents: Vec<(u64, u64)> = zones
+            .iter()
+            .map(|zone| (zone.offset.min(disk_len), zone.offset.saturating_add(zone.length).min(disk_len)))
+            .filter(|(start, end)| start < end)
+            .collect();
+        extents.sort_unstable();
+
+        let mut gaps = Vec::new();
+        let mut covered_to = 0u64;
+        for (start, end) in extents {
+            if start > covered_to {
+                gaps.push((covered_to, start - covered_to));
+            }
+            covered_to = covered_to.max(end);
+        }
+        if covered_to < disk_len {
+            gaps.push((covered_to, disk_len - covered_to));
+        }
+
+        let unaccounted = gaps.iter().map(|&(_, length)| length).sum();
+        Self {
+            disk_len,
+            accounted: disk_len - unaccounted,
+            unaccounted,
+            gaps,
+        }
+    }
+}
+
+/// One image file in a vault's backing chain
+///
+/// Differencing images read unchanged blocks from a parent, which may have a
+/// parent of its own; [`Vault::backing_chain`](crate::Vault::backing_chain)
+/// lists the files involved, child first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackingLink {
+    /// Path of the file, if the vault knows it
+    pub path: Option<PathBuf>,
+    /// Format of the file, as its vault identifies it
+    pub format: String,
+    /// Size of the virtual disk the file describes, in bytes
+    pub virtual_size: u64,
+}
+
+/// One page of a directory listing
+///
+/// `next` is the cursor to pass back for the following page. Cursors are
+/// positions in the territory's own directory structure, only meaningful to
+/// the directory they came from; `0` is always the start.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DirectoryPage {
+    /// Occupants on this page, in on-disk order
+    pub occupants: Vec<OccupantInfo>,
+    /// Cursor of the next page, or `None` if this is the last
+    pub next: Option<u64>,
+}
+
+impl DirectoryPage {
+    /// An empty page that leaves the cursor at `cursor`, for a `limit` of 0
+    pub fn unmoved(cursor: u64) -> Self {
+        Self {
+            occupants: Vec::new(),
+            next: Some(cursor),
+        }
+    }
+
+    /// Take a page out of a complete listing, using indices as cursors
+    ///
+    /// A `limit` of 0 gives an [`unmoved`](Self::unmoved) page.
+    pub fn from_listing(occupants: Vec<OccupantInfo>, cursor: u64, limit: usize) -> Self {
+        if limit == 0 {
+            return Self::unmoved(cursor);
+        }
+        let start = usize::try_from(cursor).unwrap_or(usize::MAX).min(occupants.len());
+        let end = start.saturating_add(limit).min(occupants.len());
+        Self {
+            next: (end < occupants.len()).then_some(end as u64),
+            occupants: occupants.into_iter().skip(start).take(end - start).collect(),
+        }
+    }
+}
+
+/// A directory listing that may leave out entries it couldn't read
+///
+/// Produced by a best-effort listing (see [`ListOptions::best_effort`]),
+/// which skips damaged entries instead of failing outright.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PartialListing {
+    /// Occupants read successfully, in listing order
+    pub occupants: Vec<OccupantInfo>,
+    /// Number of entries, or runs of directory storage, skipped because
+    /// they failed to read or parse
+    pub errors: usize,
+    /// Path and error message of each skip counted in `errors`
+    ///
+    /// The path is the directory the skipped entries were in, or a
+    /// subdirectory that couldn't be entered. A [`DirectoryCell`](crate::DirectoryCell)
+    /// leaves it empty; the path-based [`Territory`](crate::Territory)
+    /// listings fill it in.
+    #[serde(default)]
+    pub skipped: Vec<(String, String)>,
+}
+
+impl PartialListing {
+    /// A listing of `occupants` that skipped entries for the given reasons
+    ///
+    /// The skipped paths are left empty.
+    pub fn with_skipped(occupants: Vec<OccupantInfo>, messages: Vec<String>) -> Self {
+        Self {
+            occupants,
+            errors: messages.len(),
+            skipped: messages.into_iter().map(|message| (String::new(), message)).collect(),
+        }
+    }
+
+    /// Count a skip at `path`, keeping its error message
+    pub fn skip(&mut self, path: String, message: String) {
+        self.errors += 1;
+        self.skipped.push((path, message));
+    }
+}
+
+impl From<Vec<OccupantInfo>> for PartialListing {
+    fn from(occupants: Vec<OccupantInfo>) -> Self {
+        Self { occupants, errors: 0, skipped: Vec::new() }
+    }
+}
+
+/// Key a directory listing is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ListSort {
+    /// Name, ignoring ASCII case
+    Name,
+    /// Size in bytes
+    Size,
+    /// Last modified timestamp; occupants without one sort first
+    Modified,
+}
+
+/// Sorting and filtering applied to a directory listing
+///
+/// The default keeps every occupant in on-disk order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListOptions {
+    /// Key to sort by, or `None` for on-disk order
+    pub sort: Option<ListSort>,
+    /// Sort largest, latest or last name first
+    pub descending: bool,
+    /// Glob the name must match, `*` and `?` wildcards, ignoring ASCII case
+    pub pattern: Option<String>,
+    /// Keep only occupants modified after this instant
+    pub modified_after: Option<DateTime<Utc>>,
+    /// Skip entries that fail to read, rather than failing the listing
+    ///
+    /// Skipped entries are logged and counted in
+    /// [`PartialListing::errors`]. Meant for triaging damaged images.
+    pub best_effort: bool,
+    /// Compare the path and pattern with names in Unicode NFC
+    ///
+    /// A name typed in another normal form, as macOS file names are in
+    /// NFD, then matches the form stored on disk.
+    pub normalize_unicode: bool,
+}
+
+impl ListOptions {
+    /// Build options from their textual forms, as given on a command line
+    /// or in a query string
+    ///
+    /// `sort` is `name`, `size` or `mtime`, prefixed with `-` to sort
+    /// descending. `since` is an RFC 3339 timestamp or a `YYYY-MM-DD` date,
+    /// taken as midnight UTC.
+    pub fn parse(sort: Option<&str>, pattern: Option<&str>, since: Option<&str>) -> Result<Self> {
+        let mut options = Self::default();
+
+        if let Some(spec) = sort {
+            let (key, descending) = match spec.strip_prefix('-') {
+                Some(key) => (key, true),
+                None => (spec, false),
+            };
+            options.sort = Some(match key.to_ascii_lowercase().as_str() {
+                "name" => ListSort::Name,
+                "size" => ListSort::Size,
+                "mtime" | "modified" => ListSort::Modified,
+                _ => {
+                    return Err(Error::InvalidOperation(format!(
+                        "Invalid sort key: '{}' (expected name, size or mtime)",
+                        spec
+                    )))
+                }
+            });
+            options.descending = descending;
+        }
+
+        options.pattern = pattern.map(str::to_string);
+
+        if let Some(since) = since {
+            let timestamp = DateTime::parse_from_rfc3339(since)
+                .map(|t| t.with_timezone(&Utc))
+                .or_else(|_| {
+                    NaiveDate::parse_from_str(since, "%Y-%m-%d")
+                        .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+                })
+                .map_err(|_| {
+                    Error::InvalidOperation(format!(
+                        "Invalid timestamp: '{}' (expected RFC 3339 or YYYY-MM-DD)",
+                        since
+                    ))
+                })?;
+            options.modified_after = Some(timestamp);
+        }
+
+        Ok(options)
+    }
+
+    /// Does an occupant pass the filters?
+    pub fn matches(&self, occupant: &OccupantInfo) -> bool {
+        if let Some(pattern) = &self.pattern {
+            let matched = if self.normalize_unicode {
+                glob_match(&normalize_name(pattern), &normalize_name(&occupant.name))
+            } else {
+                glob_match(pattern, &occupant.name)
+            };
+            if !matched {
+                return false;
+            }
+        }
+        match self.modified_after {
+            Some(after) => occupant.modified.is_some_and(|modified| modified > after),
+            None => true,
+        }
+    }
+
+    /// Filter and sort a listing
+    ///
+    /// The sort is stable, so ties keep their on-disk order.
+    pub fn apply(&self, mut occupants: Vec<OccupantInfo>) -> Vec<OccupantInfo> {
+        occupants.retain(|occupant| self.matches(occupant));
+
+        if let Some(sort) = self.sort {
+            occupants.sort_by(|a, b| {
+                let ordering = match sort {
+                    ListSort::Name => a.name.to_ascii_lowercase().cmp(&b.name.to_ascii_lowercase()),
+                    ListSort::Size => a.size.cmp(&b.size),
+                    ListSort::Modified => a.modified.cmp(&b.modified),
+                };
+                if self.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+
+        occupants
+    }
+}
+
+/// Normalize a file name to Unicode NFC
+///
+/// Names already in NFC, as most are, are borrowed rather than copied.
+pub fn normalize_name(name: &str) -> Cow<'_, str> {
+    match is_nfc_quick(name.chars()) {
+        IsNormalized::Yes => Cow::Borrowed(name),
+        _ => Cow::Owned(name.nfc().collect()),
+    }
+}
+
+/// Match `name` against a glob of `*` and `?` wildcards, ignoring ASCII case
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let name: Vec<char> = name.chars().map(|c| c.to_ascii_lowercase()).collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position after the last `*` seen, and the name position it resumes at
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                backtrack = Some((p, n));
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_n)) => {
+                    p = star_p;
+                    n = star_n + 1;
+                    backtrack = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_normalize_name() {
+        // "café" precomposed and with a combining acute accent
+        let nfc = "caf\u{e9}";
+        let nfd = "cafe\u{301}";
+        assert!(matches!(normalize_name(nfc), Cow::Borrowed(_)));
+        assert_eq!(normalize_name(nfd), nfc);
+        assert_eq!(normalize_name("plain.txt"), "plain.txt");
+
+        let occupant = OccupantInfo::file(format!("{}.txt", nfc), 0);
+        let mut options = ListOptions { pattern: Some(format!("{}*", nfd)), ..Default::default() };
+        assert!(!options.matches(&occupant));
+        options.normalize_unicode = true;
+        assert!(options.matches(&occupant));
+    }
+
+    #[test]
+    fn test_attribute_flag_decode() {
+        let table = [
+            (0x01, AttributeFlag::ReadOnly),
+            (0x10, AttributeFlag::Directory),
+            (0x100, AttributeFlag::Directory),
+        ];
+        assert_eq!(AttributeFlag::decode(0, &table), vec![]);
+        assert_eq!(
+            AttributeFlag::decode(0x1111, &table),
+            vec![AttributeFlag::ReadOnly, AttributeFlag::Directory]
+        );
+        assert_eq!(AttributeFlag::ReadOnly.to_string(), "read-only");
+    }
+
+    #[test]
+    fn test_space_accounting_from_zones() {
+        let zones = [
+            Zone::new(0, 100, 200, "A".to_string()),
+            // Overlaps A and is counted once
+            Zone::new(1, 250, 100, "B".to_string()),
+            // Runs past the end of the disk
+            Zone::new(2, 900, 500, "C".to_string()),
+        ];
+        let accounting = SpaceAccounting::from_zones(&zones, 1000);
+        assert_eq!(accounting.gaps, vec![(0, 100), (350, 550)]);
+        assert_eq!(accounting.accounted, 350);
+        assert_eq!(accounting.unaccounted, 650);
+
+        let empty = SpaceAccounting::from_zones(&[], 1000);
+        assert_eq!(empty.gaps, vec![(0, 1000)]);
+        assert_eq!(empty.accounted, 0);
+    }
+
+    #[test]
+    fn test_space_summary_from_blocks() {
+        let summary = SpaceSummary::from_blocks(1_000_000, 4096, 200, 50);
+        assert_eq!(summary.free, 50 * 4096);
+        assert_eq!(summary.used + summary.free, summary.total);
+
+        // Free count larger than the volume is clamped
+        let summary = SpaceSummary::from_blocks(4096, 4096, 1, u64::MAX);
+        assert_eq!(summary.free, 4096);
+        assert_eq!(summary.used, 0);
+    }
+
     #[test]
     fn test_occupant_info_file() {
         let file = OccupantInfo::file("test.txt".to_string(), 1024);
@@ -191,6 +786,28 @@ mod tests {
         assert_eq!(dir.size, 0);
     }
 
+    #[test]
+    fn test_directory_page_from_listing() {
+        let listing = || (0..5).map(|i| OccupantInfo::file(format!("F{}", i), i)).collect::<Vec<_>>();
+
+        let page = DirectoryPage::from_listing(listing(), 0, 2);
+        assert_eq!(page.occupants.len(), 2);
+        assert_eq!(page.next, Some(2));
+
+        let page = DirectoryPage::from_listing(listing(), 4, 2);
+        assert_eq!(page.occupants[0].name, "F4");
+        assert_eq!(page.next, None);
+
+        let page = DirectoryPage::from_listing(listing(), 9, 2);
+        assert!(page.occupants.is_empty());
+        assert_eq!(page.next, None);
+
+        // A zero limit returns nothing and leaves the cursor in place
+        let page = DirectoryPage::from_listing(listing(), 1, 0);
+        assert!(page.occupants.is_empty());
+        assert_eq!(page.next, Some(1));
+    }
+
     #[test]
     fn test_format_size() {
         assert_eq!(format_size(512), "512 B");
@@ -208,4 +825,30 @@ mod tests {
         assert_eq!(zone.zone_type, "FAT32");
         assert!(zone.territory_type.is_none());
     }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.txt", "README.TXT"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("A?C*", "abcdef"));
+        assert!(glob_match("*a*b", "xaxxab"));
+        assert!(!glob_match("*.txt", "README.TXT.BAK"));
+        assert!(!glob_match("?", ""));
+    }
+
+    #[test]
+    fn test_list_options_parse() {
+        let options = ListOptions::parse(Some("-mtime"), Some("*.log"), Some("2024-03-01")).unwrap();
+        assert_eq!(options.sort, Some(ListSort::Modified));
+        assert!(options.descending);
+        assert_eq!(options.pattern.as_deref(), Some("*.log"));
+        assert_eq!(options.modified_after.unwrap().to_rfc3339(), "2024-03-01T00:00:00+00:00");
+
+        let options = ListOptions::parse(None, None, Some("2024-03-01T12:00:00+02:00")).unwrap();
+        assert_eq!(options.modified_after.unwrap().to_rfc3339(), "2024-03-01T10:00:00+00:00");
+
+        assert_eq!(ListOptions::parse(None, None, None).unwrap(), ListOptions::default());
+        assert!(ListOptions::parse(Some("date"), None, None).is_err());
+        assert!(ListOptions::parse(None, None, Some("yesterday")).is_err());
+    }
 }
@@ -6,19 +6,39 @@
 use anyhow::Result;
 use redb::{Database, ReadableTable, ReadableTableMetadata, TableDefinition};
 use serde::{de::DeserializeOwned, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
-const TOOL_RESULTS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("tool_results");
+// v2 entries put their metadata ahead of the data
+const TOOL_RESULTS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("tool_results_v2");
 const CACHE_TTL_SECONDS: u64 = 30 * 24 * 60 * 60; // 30 days
 
 /// Cache entry with metadata
+///
+/// The metadata comes first so [`EntryHeader`] can read it without knowing
+/// the type of the data.
 #[derive(serde::Serialize, serde::Deserialize)]
 struct CacheEntry<T> {
-    data: T,
     created_at: u64,
     tool: String,
     version: String,
+    data: T,
+}
+
+/// Leading metadata of an encoded [`CacheEntry`]
+#[derive(serde::Deserialize)]
+struct EntryHeader {
+    created_at: u64,
+}
+
+/// Does a cache key belong to the image at `path`?
+///
+/// Keys take the form `<kind>:<image path>[:<parameters>]`.
+fn key_matches_path(key: &str, path: &str) -> bool {
+    key.split_once(':')
+        .and_then(|(_, rest)| rest.strip_prefix(path))
+        .is_some_and(|params| params.is_empty() || params.starts_with(':'))
 }
 
 impl<T> CacheEntry<T> {
@@ -44,6 +64,10 @@ impl<T> CacheEntry<T> {
 }
 
 /// Tool cache for MCP server results
+///
+/// Keys take the form `<kind>:<image path>[:<parameters>]`, so every result
+/// for an image can be dropped with [`invalidate_path`](Self::invalidate_path)
+/// when the image changes.
 pub struct ToolCache {
     db: Arc<Mutex<Database>>,
     tool_name: String,
@@ -117,24 +141,40 @@ impl ToolCache {
     }
 
     /// Clear all cached results
-    pub fn clear(&self) -> Result<()> {
+    ///
+    /// Returns the number of entries removed.
+    pub fn clear(&self) -> Result<u64> {
+        self.remove_where(|_| true)
+    }
+
+    /// Remove every cached result for the image at `path`
+    ///
+    /// `path` must be given as the tools were, since keys hold it verbatim.
+    /// Returns the number of entries removed.
+    pub fn invalidate_path(&self, path: &str) -> Result<u64> {
+        self.remove_where(|key| key_matches_path(key, path))
+    }
+
+    /// Remove the entries whose keys match `predicate`
+    fn remove_where(&self, predicate: impl Fn(&str) -> bool) -> Result<u64> {
         let db = self.db.lock().unwrap();
         let write_txn = db.begin_write()?;
-        {
+        let removed = {
             let mut table = write_txn.open_table(TOOL_RESULTS_TABLE)?;
-            // Remove all entries
             let keys: Vec<String> = table
                 .iter()?
                 .filter_map(|r| r.ok())
                 .map(|(k, _)| k.value().to_string())
+                .filter(|key| predicate(key))
                 .collect();
-            for key in keys {
+            for key in &keys {
                 table.remove(key.as_str())?;
             }
-        }
+            keys.len() as u64
+        };
         write_txn.commit()?;
 
-        Ok(())
+        Ok(removed)
     }
 
     /// Get cache statistics
@@ -143,21 +183,39 @@ impl ToolCache {
         let read_txn = db.begin_read()?;
         let table = read_txn.open_table(TOOL_RESULTS_TABLE)?;
 
-        let entry_count = table.len()?;
+        let mut stats = CacheStats {
+            entry_count: table.len()?,
+            ..CacheStats::default()
+        };
 
-        // Estimate size based on entry count (average ~10KB per entry)
-        let size_bytes = entry_count * 10_000;
+        for (key, value) in table.iter()?.filter_map(|r| r.ok()) {
+            let key = key.value();
+            let value = value.value();
 
-        Ok(CacheStats {
-            entry_count,
-            size_bytes,
-        })
+            stats.size_bytes += (key.len() + value.len()) as u64;
+            let kind = key.split_once(':').map_or(key, |(kind, _)| kind);
+            *stats.entries_by_kind.entry(kind.to_string()).or_default() += 1;
+
+            if let Ok(header) = bincode::deserialize::<EntryHeader>(value) {
+                stats.oldest_entry = Some(match stats.oldest_entry {
+                    Some(oldest) => oldest.min(header.created_at),
+                    None => header.created_at,
+                });
+            }
+        }
+
+        Ok(stats)
     }
 }
 
 /// Cache statistics
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct CacheStats {
     pub entry_count: u64,
+    /// Bytes taken by keys and encoded entries
     pub size_bytes: u64,
+    /// Entry count per kind of result, the part of the key before the path
+    pub entries_by_kind: BTreeMap<String, u64>,
+    /// Creation time of the oldest entry, in seconds since the Unix epoch
+    pub oldest_entry: Option<u64>,
 }
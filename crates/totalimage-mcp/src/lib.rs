@@ -7,6 +7,7 @@
 //! # Features
 //!
 //! - **5 Core Tools**: analyze_disk_image, list_partitions, list_files, extract_file, validate_integrity
+//! - **Cache Admin Tool**: cache_admin, for cache stats and invalidation
 //! - **Dual-Mode Operation**: Standalone (stdio) or Integrated (HTTP + Fire Marshal)
 //! - **Shared Cache**: redb-based metadata caching with 30-day TTL
 //! - **Security Hardening**: Path validation, allocation limits, error sanitization
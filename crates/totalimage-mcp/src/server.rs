@@ -82,6 +82,9 @@ impl MCPServer {
             }),
             ToolEnum::ExtractFile(ExtractFileTool {}),
             ToolEnum::ValidateIntegrity(ValidateIntegrityTool {}),
+            ToolEnum::CacheAdmin(CacheAdminTool {
+                cache: cache.clone(),
+            }),
         ];
 
         Ok(Self {
@@ -112,6 +115,9 @@ impl MCPServer {
             }),
             ToolEnum::ExtractFile(ExtractFileTool {}),
             ToolEnum::ValidateIntegrity(ValidateIntegrityTool {}),
+            ToolEnum::CacheAdmin(CacheAdminTool {
+                cache: cache.clone(),
+            }),
         ];
 
         Ok(Self {
@@ -6,21 +6,28 @@
 //! - list_files: List files in a filesystem
 //! - extract_file: Extract file from disk image
 //! - validate_integrity: Validate checksums and structure
+//!
+//! and cache_admin, to inspect the result cache and drop stale entries.
 
 use crate::cache::ToolCache;
 use crate::protocol::{ToolDefinition, ToolResult};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::io::Write;
 use std::path::PathBuf;
 use std::sync::Arc;
-use totalimage_core::{validate_file_path, Zone, Territory, ZoneTable};
+use totalimage_core::{
+    validate_file_path, ListOptions, ReadSeek, SpaceSummary, Territory, Vault, Zone, ZoneTable,
+};
 use totalimage_pipeline::PartialPipeline;
-use totalimage_territories::{FatTerritory, IsoTerritory};
-use totalimage_vaults::{open_vault, VaultConfig};
-use totalimage_zones::{GptZoneTable, MbrZoneTable};
+use totalimage_territories::{identify_territory, open_territory_in_zone, FatTerritory, IsoTerritory};
+use totalimage_vaults::{
+    detect_vault_type, open_vault, Aff4Vault, E01Vault, HashVerification, VaultConfig, VaultType,
+};
+use totalimage_zones::{detect_zone_table, GptZoneTable, MbrZoneTable};
 
 /// Tool trait for MCP tools
 #[async_trait]
@@ -62,6 +69,7 @@ pub enum ToolEnum {
     ListFiles(ListFilesTool),
     ExtractFile(ExtractFileTool),
     ValidateIntegrity(ValidateIntegrityTool),
+    CacheAdmin(CacheAdminTool),
 }
 
 impl ToolEnum {
@@ -72,6 +80,7 @@ impl ToolEnum {
             ToolEnum::ListFiles(t) => t.name(),
             ToolEnum::ExtractFile(t) => t.name(),
             ToolEnum::ValidateIntegrity(t) => t.name(),
+            ToolEnum::CacheAdmin(t) => t.name(),
         }
     }
 
@@ -82,6 +91,7 @@ impl ToolEnum {
             ToolEnum::ListFiles(t) => t.description(),
             ToolEnum::ExtractFile(t) => t.description(),
             ToolEnum::ValidateIntegrity(t) => t.description(),
+            ToolEnum::CacheAdmin(t) => t.description(),
         }
     }
 
@@ -92,6 +102,7 @@ impl ToolEnum {
             ToolEnum::ListFiles(t) => t.input_schema(),
             ToolEnum::ExtractFile(t) => t.input_schema(),
             ToolEnum::ValidateIntegrity(t) => t.input_schema(),
+            ToolEnum::CacheAdmin(t) => t.input_schema(),
         }
     }
 
@@ -102,6 +113,7 @@ impl ToolEnum {
             ToolEnum::ListFiles(t) => t.execute(args).await,
             ToolEnum::ExtractFile(t) => t.execute(args).await,
             ToolEnum::ValidateIntegrity(t) => t.execute(args).await,
+            ToolEnum::CacheAdmin(t) => t.execute(args).await,
         }
     }
 
@@ -112,6 +124,7 @@ impl ToolEnum {
             ToolEnum::ListFiles(t) => t.definition(),
             ToolEnum::ExtractFile(t) => t.definition(),
             ToolEnum::ValidateIntegrity(t) => t.definition(),
+            ToolEnum::CacheAdmin(t) => t.definition(),
         }
     }
 }
@@ -363,7 +376,59 @@ struct ListPartitionsInput {
 #[derive(Debug, Serialize, Deserialize)]
 struct ListPartitionsOutput {
     partition_table: String,
-    zones: Vec<ZoneInfo>,
+    zones: Vec<PartitionInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PartitionInfo {
+    index: usize,
+    offset: u64,
+    length: u64,
+    zone_type: String,
+    /// File system found in the partition (e.g. "FAT", "NTFS")
+    territory_type: Option<String>,
+    volume_label: Option<String>,
+    space: Option<SpaceSummary>,
+}
+
+/// Describe a partition and the file system inside it
+///
+/// `position` is the zone's place in the zone table, which is how
+/// [`open_territory_in_zone`] finds it. Detection uses the signature probe;
+/// the label and space usage are filled in only when the file system parses.
+fn describe_partition(
+    path: &std::path::Path,
+    vault: &mut dyn Vault,
+    position: usize,
+    zone: &Zone,
+) -> PartitionInfo {
+    let mut info = PartitionInfo {
+        index: zone.index,
+        offset: zone.offset,
+        length: zone.length,
+        zone_type: zone.zone_type.clone(),
+        territory_type: None,
+        volume_label: None,
+        space: None,
+    };
+
+    let Ok(mut partial) = PartialPipeline::new(vault.content(), zone.offset, zone.length) else {
+        return info;
+    };
+    let Some(kind) = identify_territory(&mut partial) else {
+        return info;
+    };
+    info.territory_type = Some(kind.to_string());
+
+    // The opened territory shares a second handle on the image
+    match open_vault(path, VaultConfig::default()).and_then(|vault| open_territory_in_zone(vault, position)) {
+        Ok(mut opened) => {
+            info.volume_label = opened.territory.banner().ok().filter(|label| !label.is_empty());
+            info.space = opened.territory.space_summary(&mut *opened.stream).ok();
+        }
+        Err(e) => tracing::debug!("Zone {}: {} detected but not parsed: {}", zone.index, kind, e),
+    }
+    info
 }
 
 #[async_trait]
@@ -373,7 +438,7 @@ impl Tool for ListPartitionsTool {
     }
 
     fn description(&self) -> &str {
-        "List all partitions (zones) in a disk image"
+        "List all partitions (zones) in a disk image with the file system, label and free space of each"
     }
 
     fn input_schema(&self) -> Value {
@@ -413,39 +478,25 @@ impl Tool for ListPartitionsTool {
         let mut vault = open_vault(&path, VaultConfig::default())?;
         let sector_size = 512;
 
-        let output = if let Ok(mbr) = MbrZoneTable::parse(vault.content(), sector_size) {
-            ListPartitionsOutput {
-                partition_table: mbr.identify().to_string(),
-                zones: mbr
-                    .enumerate_zones()
-                    .iter()
-                    .map(|z| ZoneInfo {
-                        index: z.index,
-                        offset: z.offset,
-                        length: z.length,
-                        zone_type: z.zone_type.clone(),
-                    })
-                    .collect(),
-            }
-        } else if let Ok(gpt) = GptZoneTable::parse(vault.content(), sector_size) {
-            ListPartitionsOutput {
-                partition_table: gpt.identify().to_string(),
-                zones: gpt
-                    .enumerate_zones()
-                    .iter()
-                    .map(|z| ZoneInfo {
-                        index: z.index,
-                        offset: z.offset,
-                        length: z.length,
-                        zone_type: z.zone_type.clone(),
-                    })
-                    .collect(),
-            }
-        } else {
-            ListPartitionsOutput {
-                partition_table: "None".to_string(),
-                zones: Vec::new(),
+        // Detect the table as open_territory_in_zone does, so zone positions
+        // agree with it
+        let (partition_table, zones) = match detect_zone_table(vault.content(), sector_size)
+            .filter(|table| !table.as_zone_table().enumerate_zones().is_empty())
+        {
+            Some(table) => {
+                let table = table.as_zone_table();
+                (table.identify().to_string(), table.enumerate_zones().to_vec())
             }
+            None => ("None".to_string(), Vec::new()),
+        };
+
+        let output = ListPartitionsOutput {
+            partition_table,
+            zones: zones
+                .iter()
+                .enumerate()
+                .map(|(position, zone)| describe_partition(&path, vault.as_mut(), position, zone))
+                .collect(),
         };
 
         // Cache result
@@ -470,6 +521,12 @@ struct ListFilesInput {
     path: String,
     #[serde(default)]
     zone_index: usize,
+    /// Directory to list, the root by default
+    #[serde(default)]
+    directory: String,
+    /// Skip damaged entries instead of failing the listing
+    #[serde(default)]
+    best_effort: bool,
     #[serde(default = "default_true")]
     cache: bool,
 }
@@ -477,13 +534,60 @@ struct ListFilesInput {
 #[derive(Debug, Serialize, Deserialize)]
 struct ListFilesOutput {
     files: Vec<FileInfo>,
+    /// Damaged entries a best-effort listing skipped
+    #[serde(default)]
+    errors: usize,
+    /// Path and error message of each skip counted in `errors`
+    #[serde(default)]
+    skipped: Vec<(String, String)>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct FileInfo {
     name: String,
+    /// Path from the root of the file system
+    #[serde(default)]
+    path: String,
     size: u64,
     is_directory: bool,
+    /// Decoded attribute flags, e.g. "read-only" or "hidden"
+    #[serde(default)]
+    attributes: Vec<String>,
+}
+
+/// List `directory` of a territory as file infos carrying full paths
+///
+/// With `best_effort`, damaged entries are skipped and counted rather than
+/// failing the listing.
+fn list_file_infos(
+    territory: &dyn Territory,
+    stream: &mut dyn ReadSeek,
+    directory: &str,
+    best_effort: bool,
+) -> Result<ListFilesOutput> {
+    let options = ListOptions { best_effort, ..Default::default() };
+    let listing = territory.list_directory_partial(stream, directory, &options)?;
+    let files = listing
+        .occupants
+        .into_iter()
+        .map(|o| FileInfo {
+            attributes: attribute_names(territory, o.attributes),
+            path: o.path.unwrap_or_else(|| format!("/{}", o.name)),
+            name: o.name,
+            size: o.size,
+            is_directory: o.is_directory,
+        })
+        .collect();
+    Ok(ListFilesOutput { files, errors: listing.errors, skipped: listing.skipped })
+}
+
+/// Name the attribute flags a territory decodes from raw attribute bits
+fn attribute_names(territory: &dyn Territory, raw: u32) -> Vec<String> {
+    territory
+        .decode_attributes(raw)
+        .into_iter()
+        .map(|flag| flag.name().to_string())
+        .collect()
 }
 
 #[async_trait]
@@ -509,6 +613,16 @@ impl Tool for ListFilesTool {
                     "default": 0,
                     "description": "Partition index (0 for first partition)"
                 },
+                "directory": {
+                    "type": "string",
+                    "default": "/",
+                    "description": "Directory within the filesystem to list"
+                },
+                "best_effort": {
+                    "type": "boolean",
+                    "default": false,
+                    "description": "Skip damaged entries instead of failing, reporting how many in errors and why in skipped"
+                },
                 "cache": {
                     "type": "boolean",
                     "default": true,
@@ -524,7 +638,10 @@ impl Tool for ListFilesTool {
             .context("Invalid arguments for list_files")?;
 
         // Check cache
-        let cache_key = format!("files:{}:{}", input.path, input.zone_index);
+        let cache_key = format!(
+            "files:{}:{}:{}:{}",
+            input.path, input.zone_index, input.directory, input.best_effort
+        );
         if input.cache {
             if let Ok(Some(cached)) = self.cache.get::<ListFilesOutput>(&cache_key) {
                 return Ok(ToolResult::from_value(serde_json::to_value(&cached)?));
@@ -534,65 +651,15 @@ impl Tool for ListFilesTool {
         // Validate path
         let path = validate_file_path(&input.path)?;
 
-        // Open vault and get zone
-        let mut vault = open_vault(&path, VaultConfig::default())?;
-        let sector_size = 512;
-
-        // Get zone information
-        let zone = if let Ok(mbr) = MbrZoneTable::parse(vault.content(), sector_size) {
-            mbr.enumerate_zones()
-                .get(input.zone_index)
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("Zone index {} not found", input.zone_index))?
-        } else if let Ok(gpt) = GptZoneTable::parse(vault.content(), sector_size) {
-            gpt.enumerate_zones()
-                .get(input.zone_index)
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("Zone index {} not found", input.zone_index))?
-        } else {
-            // No partition table, use entire image
-            Zone {
-                index: 0,
-                offset: 0,
-                length: vault.length(),
-                zone_type: "Unknown".to_string(),
-                territory_type: None,
-            }
-        };
-
-        // Create partial pipeline for the zone
-        let mut partial = PartialPipeline::new(vault.content(), zone.offset, zone.length)?;
-
-        // Try to parse filesystem
-        let files = if let Ok(fat) = FatTerritory::parse(&mut partial) {
-            let root = fat.headquarters()?;
-            let occupants = root.list_occupants()?;
-
-            occupants
-                .into_iter()
-                .map(|o| FileInfo {
-                    name: o.name,
-                    size: o.size,
-                    is_directory: o.is_directory,
-                })
-                .collect()
-        } else if let Ok(iso) = IsoTerritory::parse(&mut partial) {
-            let root = iso.headquarters()?;
-            let occupants = root.list_occupants()?;
-
-            occupants
-                .into_iter()
-                .map(|o| FileInfo {
-                    name: o.name,
-                    size: o.size,
-                    is_directory: o.is_directory,
-                })
-                .collect()
-        } else {
-            return Err(anyhow::anyhow!("Unable to read filesystem at zone {}", input.zone_index));
-        };
-
-        let output = ListFilesOutput { files };
+        // Open the file system in the zone
+        let vault = open_vault(&path, VaultConfig::default())?;
+        let mut opened = open_territory_in_zone(vault, input.zone_index)?;
+        let output = list_file_infos(
+            opened.territory.as_ref(),
+            &mut *opened.stream,
+            &input.directory,
+            input.best_effort,
+        )?;
 
         // Cache result
         if input.cache {
@@ -609,20 +676,85 @@ impl Tool for ListFilesTool {
 
 pub struct ExtractFileTool {}
 
+/// Most bytes returned inline when the caller doesn't set `max_bytes`
+const DEFAULT_INLINE_MAX_BYTES: u64 = 1024 * 1024;
+
 #[derive(Debug, Serialize, Deserialize)]
 struct ExtractFileInput {
     image_path: String,
     file_path: String,
     #[serde(default)]
     zone_index: usize,
-    output_path: String,
+    /// Save the extracted bytes here instead of returning them inline
+    #[serde(default)]
+    output_path: Option<String>,
+    /// Window of the file to extract; the whole file if omitted
+    #[serde(default)]
+    range: Option<ByteRange>,
+    /// Most bytes to extract in one call
+    #[serde(default)]
+    max_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct ByteRange {
+    offset: u64,
+    /// Bytes to extract; to the end of the file if omitted
+    #[serde(default)]
+    length: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct ExtractFileOutput {
     success: bool,
     bytes_extracted: u64,
-    output_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_path: Option<String>,
+    /// Size of the whole file
+    total_size: u64,
+    /// Offset of the first extracted byte
+    offset: u64,
+    /// Whether `max_bytes` cut the requested window short
+    truncated: bool,
+    /// Base64 of the extracted bytes, when not written to `output_path`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<String>,
+}
+
+/// Resolve the window of a `total_size`-byte file to extract
+///
+/// Returns the offset, length and whether `max_bytes` shortened it.
+fn extraction_window(
+    total_size: u64,
+    range: Option<ByteRange>,
+    max_bytes: Option<u64>,
+) -> Result<(u64, u64, bool)> {
+    let offset = range.map_or(0, |r| r.offset);
+    if offset > total_size {
+        return Err(anyhow::anyhow!(
+            "Range offset {} is beyond the end of the file ({} bytes)",
+            offset,
+            total_size
+        ));
+    }
+
+    let length = match range.and_then(|r| r.length) {
+        Some(length) if length > total_size - offset => {
+            return Err(anyhow::anyhow!(
+                "Range {}+{} extends beyond the end of the file ({} bytes)",
+                offset,
+                length,
+                total_size
+            ))
+        }
+        Some(length) => length,
+        None => total_size - offset,
+    };
+
+    match max_bytes {
+        Some(max) if length > max => Ok((offset, max, true)),
+        _ => Ok((offset, length, false)),
+    }
 }
 
 #[async_trait]
@@ -632,7 +764,7 @@ impl Tool for ExtractFileTool {
     }
 
     fn description(&self) -> &str {
-        "Extract a file from a disk image filesystem"
+        "Extract a file, or a byte range of it, from a disk image filesystem"
     }
 
     fn input_schema(&self) -> Value {
@@ -654,10 +786,23 @@ impl Tool for ExtractFileTool {
                 },
                 "output_path": {
                     "type": "string",
-                    "description": "Where to save the extracted file"
+                    "description": "Where to save the extracted bytes; returned as base64 if omitted"
+                },
+                "range": {
+                    "type": "object",
+                    "properties": {
+                        "offset": { "type": "number" },
+                        "length": { "type": "number" }
+                    },
+                    "required": ["offset"],
+                    "description": "Byte window of the file to extract (offset, and length to the end if omitted)"
+                },
+                "max_bytes": {
+                    "type": "number",
+                    "description": "Most bytes to extract in one call (defaults to 1 MiB when returning base64); larger requests are truncated"
                 }
             },
-            "required": ["image_path", "file_path", "output_path"]
+            "required": ["image_path", "file_path"]
         })
     }
 
@@ -667,62 +812,46 @@ impl Tool for ExtractFileTool {
 
         // Validate paths
         let image_path = validate_file_path(&input.image_path)?;
-        let output_path = PathBuf::from(&input.output_path);
 
-        // Open vault
-        let mut vault = open_vault(&image_path, VaultConfig::default())?;
-        let sector_size = 512;
+        // Open the file system in the zone
+        let vault = open_vault(&image_path, VaultConfig::default())?;
+        let mut opened = open_territory_in_zone(vault, input.zone_index)?;
+        let (territory, stream) = (opened.territory.as_ref(), &mut *opened.stream);
 
-        // Get zone information
-        let zone = if let Ok(mbr) = MbrZoneTable::parse(vault.content(), sector_size) {
-            mbr.enumerate_zones()
-                .get(input.zone_index)
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("Zone index {} not found", input.zone_index))?
-        } else if let Ok(gpt) = GptZoneTable::parse(vault.content(), sector_size) {
-            gpt.enumerate_zones()
-                .get(input.zone_index)
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("Zone index {} not found", input.zone_index))?
-        } else {
-            // No partition table, use entire image
-            Zone {
-                index: 0,
-                offset: 0,
-                length: vault.length(),
-                zone_type: "Unknown".to_string(),
-                territory_type: None,
-            }
+        let max_bytes = match (input.max_bytes, &input.output_path) {
+            (Some(max), _) => Some(max),
+            (None, None) => Some(DEFAULT_INLINE_MAX_BYTES),
+            (None, Some(_)) => None,
         };
 
-        // Create partial pipeline for the zone
-        let mut partial = PartialPipeline::new(vault.content(), zone.offset, zone.length)?;
-
-        // Try to extract from filesystem
-        let bytes_extracted = if let Ok(fat) = FatTerritory::parse(&mut partial) {
-            // Find the file in root directory
-            let entry = fat.find_file_in_root(&mut partial, &input.file_path)?;
-
-            // Read file data
-            let data = fat.read_file_data(&mut partial, &entry)?;
+        // Extract through the territory's stream
+        let metadata = territory.file_metadata(stream, &input.file_path)?;
+        if metadata.is_directory {
+            return Err(anyhow::anyhow!("Path is a directory: {}", input.file_path));
+        }
 
-            // Write to output file
-            let mut file = std::fs::File::create(&output_path)?;
-            file.write_all(&data)?;
+        let total_size = metadata.size;
+        let (offset, length, truncated) = extraction_window(total_size, input.range, max_bytes)?;
+        let data = territory.read_file_range(stream, &input.file_path, offset, length)?;
 
-            data.len() as u64
-        } else if let Ok(_iso) = IsoTerritory::parse(&mut partial) {
-            // TODO: Implement ISO file extraction
-            // ISO extraction requires different methods - see CLI implementation
-            return Err(anyhow::anyhow!("ISO file extraction not yet implemented"));
-        } else {
-            return Err(anyhow::anyhow!("Unable to read filesystem at zone {}", input.zone_index));
+        let bytes_extracted = data.len() as u64;
+        let data = match &input.output_path {
+            Some(output_path) => {
+                let mut file = std::fs::File::create(PathBuf::from(output_path))?;
+                file.write_all(&data)?;
+                None
+            }
+            None => Some(base64::engine::general_purpose::STANDARD.encode(&data)),
         };
 
         let output = ExtractFileOutput {
             success: true,
             bytes_extracted,
             output_path: input.output_path,
+            total_size,
+            offset,
+            truncated,
+            data,
         };
 
         Ok(ToolResult::from_value(serde_json::to_value(&output)?))
@@ -747,9 +876,43 @@ struct ValidateIntegrityInput {
 #[derive(Debug, Serialize, Deserialize)]
 struct ValidateIntegrityOutput {
     valid: bool,
+    checks: Vec<IntegrityCheck>,
     issues: Vec<IntegrityIssue>,
 }
 
+/// Result of one integrity check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    Fail,
+    /// Nothing to check against
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IntegrityCheck {
+    component: String,
+    status: CheckStatus,
+    message: String,
+}
+
+/// Message for images whose format records no media hash
+const NO_EMBEDDED_HASH: &str = "no embedded hash; structural check only";
+
+/// Recompute the media hash an E01 or AFF4 image embeds
+///
+/// Returns None for formats that don't embed one, and for images that
+/// simply lack it.
+fn verify_embedded_hash(path: &std::path::Path) -> Result<Option<HashVerification>> {
+    let verification = match detect_vault_type(path)? {
+        VaultType::E01 => E01Vault::open(path)?.verify_integrity()?,
+        VaultType::Aff4 => Aff4Vault::open(path)?.verify_integrity()?,
+        _ => None,
+    };
+    Ok(verification)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct IntegrityIssue {
     severity: String,
@@ -764,7 +927,7 @@ impl Tool for ValidateIntegrityTool {
     }
 
     fn description(&self) -> &str {
-        "Validate disk image integrity (checksums, boot sectors, structure)"
+        "Validate disk image integrity (embedded forensic hashes, GPT checksums, boot sectors)"
     }
 
     fn input_schema(&self) -> Value {
@@ -778,7 +941,7 @@ impl Tool for ValidateIntegrityTool {
                 "check_checksums": {
                     "type": "boolean",
                     "default": true,
-                    "description": "Verify checksums (VHD, GPT)"
+                    "description": "Verify embedded hashes (E01 MD5, AFF4 SHA-512) and GPT CRC32s"
                 },
                 "check_boot_sectors": {
                     "type": "boolean",
@@ -801,6 +964,47 @@ impl Tool for ValidateIntegrityTool {
         let mut vault = open_vault(&path, VaultConfig::default())?;
         let mut issues = Vec::new();
 
+        let mut checks = Vec::new();
+
+        if input.check_checksums {
+            let check = match verify_embedded_hash(&path)? {
+                Some(verification) if verification.is_match() => IntegrityCheck {
+                    component: "Embedded Hash".to_string(),
+                    status: CheckStatus::Pass,
+                    message: format!("{} {} matches", verification.algorithm, verification.computed),
+                },
+                Some(verification) => IntegrityCheck {
+                    component: "Embedded Hash".to_string(),
+                    status: CheckStatus::Fail,
+                    message: format!(
+                        "{} mismatch: embedded {}, computed {}",
+                        verification.algorithm, verification.expected, verification.computed
+                    ),
+                },
+                None => IntegrityCheck {
+                    component: "Embedded Hash".to_string(),
+                    status: CheckStatus::Skipped,
+                    message: NO_EMBEDDED_HASH.to_string(),
+                },
+            };
+            checks.push(check);
+
+            // GPT CRC32s are validated during parse; other errors mean no GPT
+            match GptZoneTable::parse(vault.content(), 512) {
+                Ok(_) => checks.push(IntegrityCheck {
+                    component: "GPT".to_string(),
+                    status: CheckStatus::Pass,
+                    message: "Header and partition entry CRC32s valid".to_string(),
+                }),
+                Err(totalimage_core::Error::ChecksumVerification(message)) => checks.push(IntegrityCheck {
+                    component: "GPT".to_string(),
+                    status: CheckStatus::Fail,
+                    message,
+                }),
+                Err(_) => {}
+            }
+        }
+
         // Check partition table
         let sector_size = 512;
         if input.check_boot_sectors {
@@ -819,18 +1023,107 @@ impl Tool for ValidateIntegrityTool {
             }
         }
 
+        for check in checks.iter().filter(|c| c.status == CheckStatus::Fail) {
+            issues.push(IntegrityIssue {
+                severity: "error".to_string(),
+                component: check.component.clone(),
+                message: check.message.clone(),
+            });
+        }
+
         let valid = issues.is_empty();
 
-        let output = ValidateIntegrityOutput { valid, issues };
+        let output = ValidateIntegrityOutput { valid, checks, issues };
 
         Ok(ToolResult::from_value(serde_json::to_value(&output)?))
     }
 }
 
+// ============================================================================
+// Tool 6: Cache Admin
+// ============================================================================
+
+pub struct CacheAdminTool {
+    pub cache: Arc<ToolCache>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CacheAction {
+    Stats,
+    Invalidate,
+    ClearAll,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheAdminInput {
+    action: CacheAction,
+    /// Image whose results to drop, for `invalidate`
+    path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheInvalidateOutput {
+    path: Option<String>,
+    removed: u64,
+}
+
+#[async_trait]
+impl Tool for CacheAdminTool {
+    fn name(&self) -> &str {
+        "cache_admin"
+    }
+
+    fn description(&self) -> &str {
+        "Inspect the tool result cache, or drop cached results for an image that changed on disk"
+    }
+
+    fn input_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["stats", "invalidate", "clear_all"],
+                    "description": "stats reports entry counts, size and the oldest entry; invalidate drops the results for one image; clear_all empties the cache"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Path to the disk image, as given to the other tools (invalidate only)"
+                }
+            },
+            "required": ["action"]
+        })
+    }
+
+    async fn execute(&self, args: Option<Value>) -> Result<ToolResult> {
+        let input: CacheAdminInput = serde_json::from_value(args.unwrap_or(json!({})))
+            .context("Invalid arguments for cache_admin")?;
+
+        let output = match input.action {
+            CacheAction::Stats => serde_json::to_value(self.cache.stats()?)?,
+            CacheAction::Invalidate => {
+                let path = input.path.context("invalidate requires a path")?;
+                let removed = self.cache.invalidate_path(&path)?;
+                tracing::info!("Invalidated {} cache entries for {}", removed, path);
+                serde_json::to_value(CacheInvalidateOutput { path: Some(path), removed })?
+            }
+            CacheAction::ClearAll => {
+                let removed = self.cache.clear()?;
+                tracing::info!("Cleared {} cache entries", removed);
+                serde_json::to_value(CacheInvalidateOutput { path: None, removed })?
+            }
+        };
+
+        Ok(ToolResult::from_value(output))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::tempdir;
+    use totalimage_vaults::e01::types::adler32;
 
     /// Helper to create a test cache in a temporary directory
     fn create_test_cache() -> Arc<ToolCache> {
@@ -952,12 +1245,15 @@ mod tests {
     fn test_file_info_serialization() {
         let info = FileInfo {
             name: "README.TXT".to_string(),
+            path: "/README.TXT".to_string(),
             size: 1024,
             is_directory: false,
+            attributes: vec!["read-only".to_string(), "archive".to_string()],
         };
 
         let json = serde_json::to_string(&info).unwrap();
         assert!(json.contains("README.TXT"));
+        assert!(json.contains("\"attributes\":[\"read-only\",\"archive\"]"));
         assert!(json.contains("1024"));
         assert!(json.contains("false"));
     }
@@ -966,8 +1262,10 @@ mod tests {
     fn test_file_info_directory() {
         let info = FileInfo {
             name: "Documents".to_string(),
+            path: "/Documents".to_string(),
             size: 0,
             is_directory: true,
+            attributes: vec!["directory".to_string()],
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -993,6 +1291,11 @@ mod tests {
     fn test_validate_integrity_output() {
         let output = ValidateIntegrityOutput {
             valid: false,
+            checks: vec![IntegrityCheck {
+                component: "Embedded Hash".to_string(),
+                status: CheckStatus::Skipped,
+                message: NO_EMBEDDED_HASH.to_string(),
+            }],
             issues: vec![
                 IntegrityIssue {
                     severity: "warning".to_string(),
@@ -1005,6 +1308,7 @@ mod tests {
         let json = serde_json::to_string(&output).unwrap();
         assert!(json.contains("\"valid\":false"));
         assert!(json.contains("warning"));
+        assert!(json.contains("\"status\":\"skipped\""));
     }
 
     #[test]
@@ -1012,22 +1316,41 @@ mod tests {
         let output = ExtractFileOutput {
             success: true,
             bytes_extracted: 2048,
-            output_path: "/tmp/extracted.txt".to_string(),
+            output_path: Some("/tmp/extracted.txt".to_string()),
+            total_size: 4096,
+            offset: 0,
+            truncated: true,
+            data: None,
         };
 
         let json = serde_json::to_string(&output).unwrap();
         assert!(json.contains("true"));
         assert!(json.contains("2048"));
         assert!(json.contains("/tmp/extracted.txt"));
+        assert!(!json.contains("\"data\""));
     }
 
     #[test]
     fn test_list_files_output() {
         let output = ListFilesOutput {
             files: vec![
-                FileInfo { name: "file1.txt".to_string(), size: 100, is_directory: false },
-                FileInfo { name: "dir1".to_string(), size: 0, is_directory: true },
+                FileInfo {
+                    name: "file1.txt".to_string(),
+                    path: "/file1.txt".to_string(),
+                    size: 100,
+                    is_directory: false,
+                    attributes: vec![],
+                },
+                FileInfo {
+                    name: "dir1".to_string(),
+                    path: "/dir1".to_string(),
+                    size: 0,
+                    is_directory: true,
+                    attributes: vec![],
+                },
             ],
+            errors: 0,
+            skipped: vec![],
         };
 
         let json = serde_json::to_string(&output).unwrap();
@@ -1039,9 +1362,15 @@ mod tests {
     fn test_list_partitions_output() {
         let output = ListPartitionsOutput {
             partition_table: "GPT".to_string(),
-            zones: vec![
-                ZoneInfo { index: 0, offset: 1048576, length: 100000000, zone_type: "EFI".to_string() },
-            ],
+            zones: vec![PartitionInfo {
+                index: 0,
+                offset: 1048576,
+                length: 100000000,
+                zone_type: "EFI".to_string(),
+                territory_type: None,
+                volume_label: None,
+                space: None,
+            }],
         };
 
         let json = serde_json::to_string(&output).unwrap();
@@ -1080,6 +1409,8 @@ mod tests {
 
         assert_eq!(input.path, "/test.img");
         assert_eq!(input.zone_index, 0); // default
+        assert_eq!(input.directory, ""); // root
+        assert!(!input.best_effort); // default is false
         assert!(input.cache); // default is true
     }
 
@@ -1091,7 +1422,9 @@ mod tests {
         assert_eq!(input.image_path, "/disk.img");
         assert_eq!(input.file_path, "README.TXT");
         assert_eq!(input.zone_index, 1);
-        assert_eq!(input.output_path, "/tmp/out.txt");
+        assert_eq!(input.output_path.as_deref(), Some("/tmp/out.txt"));
+        assert!(input.range.is_none());
+        assert!(input.max_bytes.is_none());
     }
 
     #[test]
@@ -1136,6 +1469,7 @@ mod tests {
 
         let schema = tool.input_schema();
         assert!(schema["properties"]["zone_index"].is_object());
+        assert!(schema["properties"]["best_effort"].is_object());
     }
 
     #[test]
@@ -1146,7 +1480,9 @@ mod tests {
         let required = schema["required"].as_array().unwrap();
         assert!(required.contains(&json!("image_path")));
         assert!(required.contains(&json!("file_path")));
-        assert!(required.contains(&json!("output_path")));
+        assert!(!required.contains(&json!("output_path")));
+        assert!(schema["properties"]["range"].is_object());
+        assert!(schema["properties"]["max_bytes"].is_object());
     }
 
     #[test]
@@ -1180,6 +1516,9 @@ mod tests {
 
         let validate = ToolEnum::ValidateIntegrity(ValidateIntegrityTool {});
         assert_eq!(validate.name(), "validate_integrity");
+
+        let cache_admin = ToolEnum::CacheAdmin(CacheAdminTool { cache: create_test_cache() });
+        assert_eq!(cache_admin.name(), "cache_admin");
     }
 
     #[test]
@@ -1212,4 +1551,278 @@ mod tests {
     fn test_default_true() {
         assert!(default_true());
     }
+
+    // =========================================================================
+    // Extraction Tests
+    // =========================================================================
+
+    /// Build an empty 1.44MB FAT12 floppy
+    fn fat12_floppy() -> Vec<u8> {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..3].copy_from_slice(&[0xEB, 0x3C, 0x90]);
+        disk[3..11].copy_from_slice(b"MSWIN4.1");
+        disk[11..13].copy_from_slice(&512u16.to_le_bytes());
+        disk[13] = 1;
+        disk[14..16].copy_from_slice(&1u16.to_le_bytes());
+        disk[16] = 2;
+        disk[17..19].copy_from_slice(&224u16.to_le_bytes());
+        disk[19..21].copy_from_slice(&2880u16.to_le_bytes());
+        disk[21] = 0xF0;
+        disk[22..24].copy_from_slice(&9u16.to_le_bytes());
+        disk[510..512].copy_from_slice(&[0x55, 0xAA]);
+        for fat in 0..2 {
+            let offset = 512 + fat * 9 * 512;
+            disk[offset..offset + 3].copy_from_slice(&[0xF0, 0xFF, 0xFF]);
+        }
+        disk
+    }
+
+    /// Write a 1.44MB FAT12 floppy holding `name` to a temporary file
+    fn write_fat12_image(dir: &std::path::Path, name: &str, data: &[u8]) -> String {
+        let disk = fat12_floppy();
+        let mut writer = totalimage_territories::fat::FatWriter::open(std::io::Cursor::new(disk)).unwrap();
+        writer.create_file(name, data).unwrap();
+
+        let path = dir.join("floppy.img");
+        std::fs::write(&path, writer.into_inner().into_inner()).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    fn result_json(result: &ToolResult) -> Value {
+        match &result.content[0] {
+            crate::protocol::Content::Text { text } => serde_json::from_str(text).unwrap(),
+            other => panic!("unexpected content: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_partitions_detects_filesystems() {
+        let temp_dir = tempdir().unwrap();
+
+        // An MBR with a labeled FAT12 floppy as its only partition, at LBA 63
+        let mut floppy = fat12_floppy();
+        let root = 512 + 2 * 9 * 512;
+        floppy[root..root + 11].copy_from_slice(b"BACKUPS    ");
+        floppy[root + 11] = 0x08;
+
+        let mut image = vec![0u8; 63 * 512];
+        image[446 + 4] = 0x01;
+        image[446 + 8..446 + 12].copy_from_slice(&63u32.to_le_bytes());
+        image[446 + 12..446 + 16].copy_from_slice(&2880u32.to_le_bytes());
+        image[510..512].copy_from_slice(&[0x55, 0xAA]);
+        image.extend_from_slice(&floppy);
+
+        let image_path = temp_dir.path().join("disk.img");
+        std::fs::write(&image_path, &image).unwrap();
+
+        let tool = ListPartitionsTool { cache: create_test_cache() };
+        let args = json!({ "path": image_path.to_string_lossy() });
+        let output = result_json(&tool.execute(Some(args.clone())).await.unwrap());
+
+        assert_eq!(output["zones"].as_array().unwrap().len(), 1);
+        let zone = &output["zones"][0];
+        assert_eq!(zone["offset"], 63 * 512);
+        assert_eq!(zone["territory_type"], "FAT");
+        assert_eq!(zone["volume_label"], "BACKUPS");
+        assert_eq!(zone["space"]["total"], 1_474_560);
+        assert_eq!(zone["space"]["block_size"], 512);
+        assert!(zone["space"]["free"].as_u64().unwrap() > 0);
+
+        // The enriched listing is what gets cached
+        let cached = result_json(&tool.execute(Some(args)).await.unwrap());
+        assert_eq!(cached, output);
+    }
+
+    #[test]
+    fn test_extraction_window() {
+        let range = |offset, length| Some(ByteRange { offset, length });
+
+        assert_eq!(extraction_window(3000, None, None).unwrap(), (0, 3000, false));
+        assert_eq!(extraction_window(3000, None, Some(2048)).unwrap(), (0, 2048, true));
+        assert_eq!(extraction_window(3000, range(2048, None), Some(2048)).unwrap(), (2048, 952, false));
+        assert_eq!(extraction_window(3000, range(100, Some(50)), None).unwrap(), (100, 50, false));
+        assert_eq!(extraction_window(3000, range(3000, None), None).unwrap(), (3000, 0, false));
+
+        assert!(extraction_window(3000, range(3001, None), None).is_err());
+        assert!(extraction_window(3000, range(2000, Some(1001)), None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_extract_file_in_ranges() {
+        let temp_dir = tempdir().unwrap();
+        let contents: Vec<u8> = (0..3072u32).map(|i| (i * 7 % 256) as u8).collect();
+        let image_path = write_fat12_image(temp_dir.path(), "BIG.BIN", &contents);
+        let tool = ExtractFileTool {};
+
+        let mut reassembled = Vec::new();
+        let mut offset = 0;
+        loop {
+            let result = tool
+                .execute(Some(json!({
+                    "image_path": image_path,
+                    "file_path": "BIG.BIN",
+                    "range": { "offset": offset },
+                    "max_bytes": 2048
+                })))
+                .await
+                .unwrap();
+            let output = result_json(&result);
+            assert_eq!(output["total_size"], 3072);
+            assert_eq!(output["offset"], offset);
+
+            let chunk = base64::engine::general_purpose::STANDARD
+                .decode(output["data"].as_str().unwrap())
+                .unwrap();
+            assert_eq!(output["bytes_extracted"], chunk.len() as u64);
+            reassembled.extend_from_slice(&chunk);
+            offset += chunk.len() as u64;
+
+            if !output["truncated"].as_bool().unwrap() {
+                break;
+            }
+            assert_eq!(chunk.len(), 2048);
+        }
+
+        assert_eq!(offset, 3072);
+        assert_eq!(reassembled, contents);
+
+        // Ranges past the end of the file are refused
+        let result = tool
+            .execute(Some(json!({
+                "image_path": image_path,
+                "file_path": "BIG.BIN",
+                "range": { "offset": 3000, "length": 100 }
+            })))
+            .await;
+        assert!(result.is_err());
+    }
+
+    /// Write an E01 of four stored (uncompressed) chunks with the media's MD5
+    fn write_e01_image(dir: &std::path::Path, corrupt: bool) -> String {
+        fn push_section(data: &mut Vec<u8>, name: &[u8], body: &[u8], last: bool) {
+            let start = data.len() as u64;
+            let size = 76 + body.len() as u64;
+            let mut section_type = [0u8; 16];
+            section_type[..name.len()].copy_from_slice(name);
+            data.extend_from_slice(&section_type);
+            data.extend_from_slice(&(if last { 0 } else { start + size }).to_le_bytes());
+            data.extend_from_slice(&size.to_le_bytes());
+            data.extend_from_slice(&[0u8; 44]);
+            data.extend_from_slice(body);
+        }
+
+        let chunk_size = 64 * 512;
+        let media: Vec<u8> = (0..4 * chunk_size).map(|i| (i % 253) as u8).collect();
+        // Stored chunks are followed by their Adler-32
+        let mut sectors: Vec<u8> = media
+            .chunks(chunk_size)
+            .flat_map(|chunk| [chunk, &adler32(chunk).to_le_bytes()].concat())
+            .collect();
+        if corrupt {
+            sectors[chunk_size + 4 + 17] ^= 0xFF;
+        }
+        // Entries without the high bit are stored chunks, relative to the
+        // chunk data after the volume and sectors descriptors
+        let mut table = vec![0u8; 24];
+        table[0..4].copy_from_slice(&4u32.to_le_bytes());
+        table[8..16].copy_from_slice(&(13u64 + 76 + 94 + 76).to_le_bytes());
+        table.extend((0..4u32).flat_map(|i| (i * (chunk_size as u32 + 4)).to_le_bytes()));
+
+        let mut volume = vec![0u8; 94];
+        volume[0] = 0x01;
+        volume[4..8].copy_from_slice(&4u32.to_le_bytes());
+        volume[8..12].copy_from_slice(&64u32.to_le_bytes());
+        volume[12..16].copy_from_slice(&512u32.to_le_bytes());
+        volume[16..24].copy_from_slice(&(4u64 * 64).to_le_bytes());
+
+        let mut hash = vec![0u8; 36];
+        hash[..16].copy_from_slice(&md5::compute(&media).0);
+
+        let mut data = b"EVF\x09\x0d\x0a\xff\x00\x01".to_vec();
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        push_section(&mut data, b"volume", &volume, false);
+        push_section(&mut data, b"sectors", &sectors, false);
+        push_section(&mut data, b"table", &table, false);
+        push_section(&mut data, b"hash", &hash, false);
+        push_section(&mut data, b"done", &[], true);
+
+        let path = dir.join(if corrupt { "corrupt.E01" } else { "good.E01" });
+        std::fs::write(&path, data).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    async fn validate(path: &str) -> Value {
+        let tool = ValidateIntegrityTool {};
+        let result = tool
+            .execute(Some(json!({ "path": path, "check_boot_sectors": false })))
+            .await
+            .unwrap();
+        result_json(&result)
+    }
+
+    #[tokio::test]
+    async fn test_validate_integrity_e01_hash() {
+        let temp_dir = tempdir().unwrap();
+
+        let output = validate(&write_e01_image(temp_dir.path(), false)).await;
+        assert_eq!(output["valid"], true);
+        assert_eq!(output["checks"][0]["component"], "Embedded Hash");
+        assert_eq!(output["checks"][0]["status"], "pass");
+        assert!(output["checks"][0]["message"].as_str().unwrap().starts_with("MD5 "));
+
+        let output = validate(&write_e01_image(temp_dir.path(), true)).await;
+        assert_eq!(output["valid"], false);
+        assert_eq!(output["checks"][0]["status"], "fail");
+        assert_eq!(output["issues"][0]["severity"], "error");
+        assert_eq!(output["issues"][0]["component"], "Embedded Hash");
+    }
+
+    #[tokio::test]
+    async fn test_validate_integrity_without_embedded_hash() {
+        let temp_dir = tempdir().unwrap();
+        let image_path = write_fat12_image(temp_dir.path(), "A.TXT", b"a");
+
+        let output = validate(&image_path).await;
+        assert_eq!(output["valid"], true);
+        assert_eq!(output["checks"][0]["status"], "skipped");
+        assert_eq!(output["checks"][0]["message"], NO_EMBEDDED_HASH);
+    }
+
+    #[tokio::test]
+    async fn test_cache_admin_invalidate_by_path() {
+        let cache = create_test_cache();
+        let tool = CacheAdminTool { cache: cache.clone() };
+        for key in [
+            "analyze:/images/a.vhd:false",
+            "partitions:/images/a.vhd",
+            "files:/images/a.vhd:0:/",
+            "partitions:/images/a.vhd.bak",
+            "partitions:/images/b.vhd",
+        ] {
+            cache.set(key, &key.to_string()).unwrap();
+        }
+
+        let stats = result_json(&tool.execute(Some(json!({"action": "stats"}))).await.unwrap());
+        assert_eq!(stats["entry_count"], 5);
+        assert_eq!(stats["entries_by_kind"]["partitions"], 3);
+        assert!(stats["oldest_entry"].as_u64().unwrap() > 0);
+        assert!(stats["size_bytes"].as_u64().unwrap() > 0);
+
+        let args = json!({"action": "invalidate", "path": "/images/a.vhd"});
+        let output = result_json(&tool.execute(Some(args)).await.unwrap());
+        assert_eq!(output["removed"], 3);
+        assert_eq!(cache.get::<String>("partitions:/images/a.vhd").unwrap(), None);
+        assert_eq!(cache.get::<String>("files:/images/a.vhd:0:/").unwrap(), None);
+        assert!(cache.get::<String>("partitions:/images/a.vhd.bak").unwrap().is_some());
+        assert!(cache.get::<String>("partitions:/images/b.vhd").unwrap().is_some());
+
+        assert!(tool.execute(Some(json!({"action": "invalidate"}))).await.is_err());
+
+        let output = result_json(&tool.execute(Some(json!({"action": "clear_all"}))).await.unwrap());
+        assert_eq!(output["removed"], 2);
+        let stats = result_json(&tool.execute(Some(json!({"action": "stats"}))).await.unwrap());
+        assert_eq!(stats["entry_count"], 0);
+        assert_eq!(stats["oldest_entry"], Value::Null);
+    }
 }
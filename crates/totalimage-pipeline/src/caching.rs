@@ -0,0 +1,326 @@
+//! Caching pipeline - serves repeated reads from an LRU block cache
+
+use std::collections::HashMap;
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// Default cached block size (64 KiB)
+pub const DEFAULT_BLOCK_SIZE: usize = 64 * 1024;
+
+/// Default maximum number of cached blocks (16 MiB at the default block size)
+pub const DEFAULT_MAX_BLOCKS: usize = 256;
+
+/// A cached block and the tick it was last used at
+struct CachedBlock {
+    data: Vec<u8>,
+    last_used: u64,
+}
+
+/// A pipeline that caches fixed-size, aligned blocks of an underlying stream.
+///
+/// Reads are served from an LRU cache of blocks; the underlying stream is only
+/// touched on a miss. This avoids repeated decompression or seeking when a
+/// file system walks back and forth over the same metadata, as FAT directory
+/// traversal does over E01, AFF4 and VHD vaults. Memory is bounded by the
+/// maximum number of cached blocks.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use totalimage_pipeline::CachingPipeline;
+/// use std::io::Cursor;
+///
+/// let data = vec![0u8; 1024 * 1024];
+/// let mut cached = CachingPipeline::new(Cursor::new(data)).unwrap();
+/// ```
+pub struct CachingPipeline<R: Read + Seek> {
+    inner: R,
+    block_size: usize,
+    max_blocks: usize,
+    blocks: HashMap<u64, CachedBlock>,
+    tick: u64,
+    position: u64,
+    length: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl<R: Read + Seek> CachingPipeline<R> {
+    /// Create a caching pipeline with the default block size and capacity
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the length of the underlying stream can't be determined
+    pub fn new(inner: R) -> io::Result<Self> {
+        Self::with_capacity(inner, DEFAULT_BLOCK_SIZE, DEFAULT_MAX_BLOCKS)
+    }
+
+    /// Create a caching pipeline with a custom block size and capacity
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The underlying stream
+    /// * `block_size` - Size of each cached block in bytes
+    /// * `max_blocks` - Maximum number of blocks held in memory
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `block_size` or `max_blocks` is zero, or if the
+    /// length of the underlying stream can't be determined
+    pub fn with_capacity(mut inner: R, block_size: usize, max_blocks: usize) -> io::Result<Self> {
+        if block_size == 0 || max_blocks == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Block size and block count must be non-zero",
+            ));
+        }
+
+        let length = inner.seek(SeekFrom::End(0))?;
+
+        Ok(Self {
+            inner,
+            block_size,
+            max_blocks,
+            blocks: HashMap::new(),
+            tick: 0,
+            position: 0,
+            length,
+            hits: 0,
+            misses: 0,
+        })
+    }
+
+    /// Get the cached block size in bytes
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Get the maximum number of cached blocks
+    pub fn max_blocks(&self) -> usize {
+        self.max_blocks
+    }
+
+    /// Get the number of blocks currently cached
+    pub fn cached_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Get the number of block lookups served from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Get the number of block lookups that read from the underlying stream
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Get the length of the underlying stream
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// Drop all cached blocks
+    pub fn clear(&mut self) {
+        self.blocks.clear();
+    }
+
+    /// Consume the pipeline and return the underlying stream
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Get a block from the cache, loading it on a miss
+    fn block(&mut self, index: u64) -> io::Result<&[u8]> {
+        self.tick += 1;
+        let tick = self.tick;
+
+        if self.blocks.contains_key(&index) {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+            let data = self.load_block(index)?;
+
+            if self.blocks.len() >= self.max_blocks {
+                let oldest = self
+                    .blocks
+                    .iter()
+                    .min_by_key(|(_, block)| block.last_used)
+                    .map(|(&key, _)| key);
+                if let Some(key) = oldest {
+                    self.blocks.remove(&key);
+                }
+            }
+
+            self.blocks.insert(index, CachedBlock { data, last_used: tick });
+        }
+
+        let block = self.blocks.get_mut(&index).expect("block was just cached");
+        block.last_used = tick;
+        Ok(&block.data)
+    }
+
+    /// Read a block from the underlying stream (shorter at end of stream)
+    fn load_block(&mut self, index: u64) -> io::Result<Vec<u8>> {
+        let start = index * self.block_size as u64;
+        let size = (self.length.saturating_sub(start)).min(self.block_size as u64) as usize;
+
+        self.inner.seek(SeekFrom::Start(start))?;
+        let mut data = vec![0u8; size];
+        let mut filled = 0;
+        while filled < size {
+            match self.inner.read(&mut data[filled..])? {
+                0 => break,
+                n => filled += n,
+            }
+        }
+        data.truncate(filled);
+
+        Ok(data)
+    }
+}
+
+impl<R: Read + Seek> Read for CachingPipeline<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut written = 0;
+
+        while written < buf.len() && self.position < self.length {
+            let block_size = self.block_size as u64;
+            let index = self.position / block_size;
+            let offset = (self.position % block_size) as usize;
+
+            let block = self.block(index)?;
+            if offset >= block.len() {
+                break; // Underlying stream ended early
+            }
+
+            let count = (block.len() - offset).min(buf.len() - written);
+            buf[written..written + count].copy_from_slice(&block[offset..offset + count]);
+
+            written += count;
+            self.position += count as u64;
+        }
+
+        Ok(written)
+    }
+}
+
+impl<R: Read + Seek> Seek for CachingPipeline<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => offset as i64,
+            SeekFrom::End(offset) => self.length as i64 + offset,
+            SeekFrom::Current(offset) => self.position as i64 + offset,
+        };
+
+        if new_pos < 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Seek before beginning of caching pipeline",
+            ));
+        }
+
+        self.position = new_pos as u64;
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Reader that counts reads reaching the underlying data
+    struct CountingReader {
+        inner: Cursor<Vec<u8>>,
+        reads: usize,
+    }
+
+    impl Read for CountingReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.reads += 1;
+            self.inner.read(buf)
+        }
+    }
+
+    impl Seek for CountingReader {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    fn test_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_caching_pipeline_reads_match_source() {
+        let data = test_data(10_000);
+        let mut cached = CachingPipeline::with_capacity(Cursor::new(data.clone()), 512, 4).unwrap();
+
+        // Read spanning several blocks, including the short final block
+        cached.seek(SeekFrom::Start(9_000)).unwrap();
+        let mut buf = vec![0u8; 2_000];
+        let n = cached.read(&mut buf).unwrap();
+        assert_eq!(n, 1_000);
+        assert_eq!(&buf[..n], &data[9_000..]);
+
+        cached.seek(SeekFrom::Start(100)).unwrap();
+        let mut buf = vec![0u8; 1_500];
+        cached.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf[..], &data[100..1_600]);
+
+        assert_eq!(cached.read(&mut buf[..0]).unwrap(), 0);
+        cached.seek(SeekFrom::End(0)).unwrap();
+        assert_eq!(cached.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_caching_pipeline_reduces_underlying_reads() {
+        let data = test_data(64 * 1024);
+        let reader = CountingReader { inner: Cursor::new(data.clone()), reads: 0 };
+        let mut cached = CachingPipeline::with_capacity(reader, 4096, 8).unwrap();
+
+        // Repeated overlapping small reads, like walking a FAT table
+        let mut buf = [0u8; 32];
+        for round in 0..10 {
+            for offset in (0..8000u64).step_by(96) {
+                cached.seek(SeekFrom::Start(offset + round)).unwrap();
+                cached.read_exact(&mut buf).unwrap();
+                let start = (offset + round) as usize;
+                assert_eq!(&buf[..], &data[start..start + 32]);
+            }
+        }
+
+        assert_eq!(cached.misses(), 2);
+        assert!(cached.hits() > 800);
+        assert_eq!(cached.into_inner().reads, 2);
+    }
+
+    #[test]
+    fn test_caching_pipeline_bounds_memory() {
+        let data = test_data(16 * 1024);
+        let mut cached = CachingPipeline::with_capacity(Cursor::new(data), 1024, 3).unwrap();
+
+        let mut buf = [0u8; 1];
+        for block in [0u64, 1, 2, 0, 3] {
+            cached.seek(SeekFrom::Start(block * 1024)).unwrap();
+            cached.read_exact(&mut buf).unwrap();
+        }
+        assert_eq!(cached.cached_blocks(), 3);
+        assert_eq!(cached.misses(), 4);
+
+        // Block 1 was least recently used and got evicted; block 0 survived
+        cached.seek(SeekFrom::Start(0)).unwrap();
+        cached.read_exact(&mut buf).unwrap();
+        assert_eq!(cached.misses(), 4);
+        cached.seek(SeekFrom::Start(1024)).unwrap();
+        cached.read_exact(&mut buf).unwrap();
+        assert_eq!(cached.misses(), 5);
+    }
+
+    #[test]
+    fn test_caching_pipeline_rejects_zero_capacity() {
+        assert!(CachingPipeline::with_capacity(Cursor::new(vec![0u8; 10]), 0, 4).is_err());
+        assert!(CachingPipeline::with_capacity(Cursor::new(vec![0u8; 10]), 512, 0).is_err());
+    }
+}
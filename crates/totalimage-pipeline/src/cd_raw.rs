@@ -0,0 +1,221 @@
+//! CD raw pipeline - presents the user data of 2352-byte CD sectors
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// Size of a raw CD sector, as stored in `.bin` images
+pub const RAW_SECTOR_SIZE: usize = 2352;
+
+/// Size of the user data in a Mode 1 or Mode 2 Form 1 sector
+pub const USER_DATA_SIZE: usize = 2048;
+
+/// Sync pattern opening every raw data sector
+pub const SYNC_PATTERN: [u8; 12] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+
+/// Offset of the mode byte in the sector header
+const MODE_OFFSET: usize = 15;
+
+/// Offset of the user data in a Mode 1 sector, after sync and header
+const MODE1_DATA_OFFSET: usize = 16;
+
+/// Offset of the user data in a Mode 2 Form 1 sector, after sync, header
+/// and subheader
+const MODE2_DATA_OFFSET: usize = 24;
+
+/// A pipeline that strips raw CD sectors down to their 2048-byte user data.
+///
+/// Raw CD images store each 2352-byte sector with its sync pattern, header
+/// and error correction around the user data, so file systems written for
+/// 2048-byte sectors, like ISO 9660, can't be read from them directly. This
+/// pipeline exposes only the user data, back to back, so such a file system
+/// sees an ordinary cooked image.
+///
+/// The mode is read from each sector's header: Mode 2 sectors are taken to
+/// be Form 1, with the user data after the subheader; all others are read
+/// as Mode 1.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use totalimage_pipeline::CdRawPipeline;
+/// use std::fs::File;
+///
+/// let file = File::open("disc.bin").unwrap();
+/// let mut cooked = CdRawPipeline::new(file).unwrap();
+/// ```
+pub struct CdRawPipeline<R: Read + Seek> {
+    inner: R,
+    sector_count: u64,
+    position: u64,
+    /// The raw sector last read, to serve reads smaller than a sector
+    sector: Box<[u8; RAW_SECTOR_SIZE]>,
+    sector_index: Option<u64>,
+}
+
+impl<R: Read + Seek> CdRawPipeline<R> {
+    /// Create a pipeline over a raw CD image
+    ///
+    /// A trailing partial sector is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream doesn't start with the CD sync
+    /// pattern, or can't be read
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        if !Self::detect(&mut inner)? {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Stream does not start with a raw CD sector sync pattern",
+            ));
+        }
+
+        let length = inner.seek(SeekFrom::End(0))?;
+
+        Ok(Self {
+            inner,
+            sector_count: length / RAW_SECTOR_SIZE as u64,
+            position: 0,
+            sector: Box::new([0u8; RAW_SECTOR_SIZE]),
+            sector_index: None,
+        })
+    }
+
+    /// Check whether a stream starts with the raw CD sector sync pattern
+    pub fn detect(inner: &mut R) -> io::Result<bool> {
+        inner.seek(SeekFrom::Start(0))?;
+        let mut sync = [0u8; SYNC_PATTERN.len()];
+        match inner.read_exact(&mut sync) {
+            Ok(()) => Ok(sync == SYNC_PATTERN),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get the number of raw sectors in the image
+    pub fn sector_count(&self) -> u64 {
+        self.sector_count
+    }
+
+    /// Get the length of the user data presented
+    pub fn length(&self) -> u64 {
+        self.sector_count * USER_DATA_SIZE as u64
+    }
+
+    /// Consume the pipeline and return the underlying stream
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Get the user data of a sector, reading it on a miss
+    fn user_data(&mut self, index: u64) -> io::Result<&[u8]> {
+        if self.sector_index != Some(index) {
+            self.sector_index = None;
+            self.inner.seek(SeekFrom::Start(index * RAW_SECTOR_SIZE as u64))?;
+            self.inner.read_exact(&mut self.sector[..])?;
+            self.sector_index = Some(index);
+        }
+
+        let offset = match self.sector[MODE_OFFSET] {
+            2 => MODE2_DATA_OFFSET,
+            _ => MODE1_DATA_OFFSET,
+        };
+        Ok(&self.sector[offset..offset + USER_DATA_SIZE])
+    }
+}
+
+impl<R: Read + Seek> Read for CdRawPipeline<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = self.length();
+        let mut written = 0;
+
+        while written < buf.len() && self.position < length {
+            let index = self.position / USER_DATA_SIZE as u64;
+            let offset = (self.position % USER_DATA_SIZE as u64) as usize;
+
+            let data = self.user_data(index)?;
+            let count = (USER_DATA_SIZE - offset).min(buf.len() - written);
+            buf[written..written + count].copy_from_slice(&data[offset..offset + count]);
+
+            written += count;
+            self.position += count as u64;
+        }
+
+        Ok(written)
+    }
+}
+
+impl<R: Read + Seek> Seek for CdRawPipeline<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => offset as i64,
+            SeekFrom::End(offset) => self.length() as i64 + offset,
+            SeekFrom::Current(offset) => self.position as i64 + offset,
+        };
+
+        if new_pos < 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Seek before beginning of CD raw pipeline",
+            ));
+        }
+
+        self.position = new_pos as u64;
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Wrap 2048-byte sectors of user data in raw CD sectors of the given mode
+    ///
+    /// Only the sync pattern, mode byte and user data are filled in, which is
+    /// all [`CdRawPipeline`] reads.
+    fn raw_sectors(data: &[u8], mode: u8) -> Vec<u8> {
+        let offset = if mode == 2 { MODE2_DATA_OFFSET } else { MODE1_DATA_OFFSET };
+        let mut raw = Vec::new();
+        for chunk in data.chunks(USER_DATA_SIZE) {
+            let mut sector = [0u8; RAW_SECTOR_SIZE];
+            sector[..SYNC_PATTERN.len()].copy_from_slice(&SYNC_PATTERN);
+            sector[MODE_OFFSET] = mode;
+            sector[offset..offset + chunk.len()].copy_from_slice(chunk);
+            raw.extend_from_slice(&sector);
+        }
+        raw
+    }
+
+    fn test_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_cd_raw_pipeline_modes() {
+        let data = test_data(3 * USER_DATA_SIZE);
+
+        for mode in [1, 2] {
+            let mut cooked = CdRawPipeline::new(Cursor::new(raw_sectors(&data, mode))).unwrap();
+            assert_eq!(cooked.sector_count(), 3);
+            assert_eq!(cooked.length(), data.len() as u64);
+
+            let mut read_back = Vec::new();
+            cooked.read_to_end(&mut read_back).unwrap();
+            assert_eq!(read_back, data, "mode {}", mode);
+
+            // A read spanning a sector boundary
+            cooked.seek(SeekFrom::Start(2000)).unwrap();
+            let mut buf = [0u8; 100];
+            cooked.read_exact(&mut buf).unwrap();
+            assert_eq!(&buf[..], &data[2000..2100]);
+        }
+    }
+
+    #[test]
+    fn test_cd_raw_pipeline_requires_sync() {
+        let data = test_data(USER_DATA_SIZE);
+        assert!(CdRawPipeline::detect(&mut Cursor::new(raw_sectors(&data, 1))).unwrap());
+        assert!(!CdRawPipeline::detect(&mut Cursor::new(data.clone())).unwrap());
+        assert!(!CdRawPipeline::detect(&mut Cursor::new(vec![0u8; 4])).unwrap());
+        assert!(CdRawPipeline::new(Cursor::new(data)).is_err());
+    }
+}
@@ -5,6 +5,11 @@
 //! This crate provides various stream wrappers for efficient data access:
 //! - **PartialPipeline**: Window into a subset of a stream (for partitions)
 //! - **MmapPipeline**: Memory-mapped file access for direct action
+//! - **CachingPipeline**: LRU block cache over slow or compressed streams
+//! - **VerifyingPipeline**: Per-block hash verification against an acquisition manifest
+//! - **VaultReader**: Owned stream over a vault's content
+//! - **SharedPipeline**: Cloneable handles with their own positions over one stream
+//! - **CdRawPipeline**: User data of raw 2352-byte CD sectors (`.bin` images)
 //!
 //! ## Example
 //!
@@ -24,8 +29,18 @@
 //! partial.read(&mut buf).unwrap();
 //! ```
 
+pub mod caching;
+pub mod cd_raw;
 pub mod mmap;
 pub mod partial;
+pub mod shared;
+pub mod vault_reader;
+pub mod verifying;
 
+pub use caching::CachingPipeline;
+pub use cd_raw::CdRawPipeline;
 pub use mmap::MmapPipeline;
 pub use partial::PartialPipeline;
+pub use shared::SharedPipeline;
+pub use vault_reader::VaultReader;
+pub use verifying::{compute_block_manifest, BlockHashAlgorithm, BlockManifest, BlockMismatch, VerifyingPipeline};
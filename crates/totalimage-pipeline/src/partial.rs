@@ -35,10 +35,26 @@ impl<R: Read + Seek> PartialPipeline<R> {
     /// * `start` - Offset from the beginning of the stream
     /// * `length` - Length of the window
     ///
+    /// The window is clamped to the end of the underlying stream, so
+    /// [`length`](Self::length) reports only the bytes actually available.
+    ///
     /// # Errors
     ///
-    /// Returns an error if seeking to the start position fails
+    /// Returns an error if `start` lies beyond the end of the underlying
+    /// stream, or if seeking fails
     pub fn new(mut inner: R, start: u64, length: u64) -> io::Result<Self> {
+        let base_length = inner.seek(SeekFrom::End(0))?;
+        if start > base_length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Partial pipeline start {} is beyond end of stream ({} bytes)",
+                    start, base_length
+                ),
+            ));
+        }
+        let length = length.min(base_length - start);
+
         // Verify we can seek to the start position
         inner.seek(SeekFrom::Start(start))?;
 
@@ -112,15 +128,8 @@ impl<R: Read + Seek> Seek for PartialPipeline<R> {
             ));
         }
 
-        let new_pos = new_pos as u64;
-        if new_pos > self.length {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                "Seek beyond end of partial pipeline",
-            ));
-        }
-
-        self.position = new_pos;
+        // Clamp to the window end so reads can never reach adjacent data
+        self.position = (new_pos as u64).min(self.length);
         Ok(self.position)
     }
 }
@@ -225,8 +234,45 @@ mod tests {
         let _ = partial.seek(SeekFrom::Current(-5)); // Intentionally ignored - testing subsequent operations
         assert!(result.is_ok()); // SeekFrom::Start(0) is valid
 
-        // Seek beyond end
+        // Seek beyond end clamps to the window end
         let result = partial.seek(SeekFrom::Start(15));
-        assert!(result.is_err());
+        assert_eq!(result.unwrap(), 10);
+    }
+
+    #[test]
+    fn test_partial_pipeline_clamps_to_stream() {
+        let data: Vec<u8> = (0..100).collect();
+
+        // Window running past the end of the stream is truncated
+        let mut partial = PartialPipeline::new(Cursor::new(data.clone()), 90, 50).unwrap();
+        assert_eq!(partial.length(), 10);
+        let mut buf = [0u8; 50];
+        assert_eq!(partial.read(&mut buf).unwrap(), 10);
+        assert_eq!(&buf[..10], &data[90..]);
+
+        // Window at the exact end is empty but valid
+        let partial = PartialPipeline::new(Cursor::new(data.clone()), 100, 10).unwrap();
+        assert_eq!(partial.length(), 0);
+
+        // Start beyond the stream is rejected
+        assert!(PartialPipeline::new(Cursor::new(data), 101, 10).is_err());
+    }
+
+    #[test]
+    fn test_partial_pipeline_never_reads_adjacent_data() {
+        let data: Vec<u8> = (0..100).collect();
+        let mut partial = PartialPipeline::new(Cursor::new(data), 20, 10).unwrap();
+        let mut buf = [0u8; 8];
+
+        // Over-read at the boundary stops at the window end
+        partial.seek(SeekFrom::Start(6)).unwrap();
+        assert_eq!(partial.read(&mut buf).unwrap(), 4);
+        assert_eq!(&buf[..4], &[26, 27, 28, 29]);
+
+        // Seeks past the end clamp instead of exposing bytes 30+
+        assert_eq!(partial.seek(SeekFrom::Current(50)).unwrap(), 10);
+        assert_eq!(partial.read(&mut buf).unwrap(), 0);
+        assert_eq!(partial.seek(SeekFrom::End(5)).unwrap(), 10);
+        assert_eq!(partial.read(&mut buf).unwrap(), 0);
     }
 }
@@ -0,0 +1,115 @@
+//! Shared pipeline - several independent cursors over one stream
+
+use std::io::{self, Read, Seek, SeekFrom};
+use std::sync::{Arc, Mutex};
+use totalimage_core::ReadSeek;
+
+/// A cloneable handle onto a stream shared with other handles
+///
+/// Each clone keeps its own position and seeks the shared stream to it
+/// before every read, so handles never disturb one another. This lets a
+/// territory own one handle for [`Territory::extract_file`] while its
+/// caller lists directories through another.
+///
+/// [`Territory::extract_file`]: totalimage_core::Territory::extract_file
+///
+/// # Example
+///
+/// ```rust
+/// use totalimage_pipeline::SharedPipeline;
+/// use std::io::{Cursor, Read, Seek, SeekFrom};
+///
+/// let mut first = SharedPipeline::new(Cursor::new(vec![1u8, 2, 3, 4]));
+/// let mut second = first.clone();
+///
+/// second.seek(SeekFrom::Start(2)).unwrap();
+/// let mut buf = [0u8; 2];
+/// first.read_exact(&mut buf).unwrap();
+/// assert_eq!(buf, [1, 2]);
+/// second.read_exact(&mut buf).unwrap();
+/// assert_eq!(buf, [3, 4]);
+/// ```
+#[derive(Clone)]
+pub struct SharedPipeline {
+    inner: Arc<Mutex<Box<dyn ReadSeek>>>,
+    position: u64,
+}
+
+impl SharedPipeline {
+    /// Share a stream, starting at its beginning
+    pub fn new(inner: impl ReadSeek + 'static) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Box::new(inner))),
+            position: 0,
+        }
+    }
+
+    /// Get the current position of this handle
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    fn lock(&self) -> io::Result<std::sync::MutexGuard<'_, Box<dyn ReadSeek>>> {
+        self.inner
+            .lock()
+            .map_err(|_| io::Error::other("Shared stream was poisoned by a panicking reader"))
+    }
+}
+
+impl Read for SharedPipeline {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut inner = self.lock()?;
+        inner.seek(SeekFrom::Start(self.position))?;
+        let bytes_read = inner.read(buf)?;
+        drop(inner);
+
+        self.position += bytes_read as u64;
+        Ok(bytes_read)
+    }
+}
+
+impl Seek for SharedPipeline {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+            SeekFrom::End(offset) => {
+                let end = self.lock()?.seek(SeekFrom::End(0))?;
+                end.checked_add_signed(offset)
+            }
+        };
+
+        self.position = new_pos.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "Seek before beginning of shared pipeline")
+        })?;
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_handles_keep_own_positions() {
+        let data: Vec<u8> = (0..100).collect();
+        let mut first = SharedPipeline::new(Cursor::new(data));
+        let mut second = first.clone();
+
+        let mut buf = [0u8; 4];
+        first.seek(SeekFrom::Start(10)).unwrap();
+        second.seek(SeekFrom::End(-4)).unwrap();
+
+        first.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [10, 11, 12, 13]);
+        second.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [96, 97, 98, 99]);
+        first.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [14, 15, 16, 17]);
+
+        assert_eq!(first.position(), 18);
+        assert_eq!(second.position(), 100);
+        assert!(second.seek(SeekFrom::Current(-101)).is_err());
+    }
+}
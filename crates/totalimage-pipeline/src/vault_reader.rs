@@ -0,0 +1,97 @@
+//! Vault reader - an owned stream over a vault's content
+
+use std::io::{self, Read, Seek, SeekFrom};
+use totalimage_core::Vault;
+
+/// A stream that owns a vault and reads its content
+///
+/// `Vault::content` lends out the stream, which ties it to a borrow of the
+/// vault. Wrapping the vault gives a `ReadSeek` that can be boxed and
+/// handed to zone parsers, territories or hashers on its own.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use totalimage_pipeline::VaultReader;
+/// use totalimage_core::{ReadSeek, Vault};
+///
+/// fn into_stream(vault: Box<dyn Vault>) -> Box<dyn ReadSeek> {
+///     Box::new(VaultReader::new(vault))
+/// }
+/// ```
+pub struct VaultReader {
+    vault: Box<dyn Vault>,
+}
+
+impl VaultReader {
+    /// Wrap a vault, starting at the beginning of its content
+    pub fn new(vault: Box<dyn Vault>) -> Self {
+        Self { vault }
+    }
+
+    /// Get the length of the vault content in bytes
+    pub fn len(&self) -> u64 {
+        self.vault.length()
+    }
+
+    /// Check if the vault content is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the wrapped vault
+    pub fn vault(&self) -> &dyn Vault {
+        &*self.vault
+    }
+
+    /// Unwrap the vault
+    pub fn into_inner(self) -> Box<dyn Vault> {
+        self.vault
+    }
+}
+
+impl Read for VaultReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.vault.content().read(buf)
+    }
+}
+
+impl Seek for VaultReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.vault.content().seek(pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use totalimage_core::ReadSeek;
+
+    struct MemoryVault(Cursor<Vec<u8>>);
+
+    impl Vault for MemoryVault {
+        fn identify(&self) -> &str {
+            "Memory"
+        }
+
+        fn length(&self) -> u64 {
+            self.0.get_ref().len() as u64
+        }
+
+        fn content(&mut self) -> &mut dyn ReadSeek {
+            &mut self.0
+        }
+    }
+
+    #[test]
+    fn test_vault_reader_owns_stream() {
+        let vault = MemoryVault(Cursor::new((0..=255u8).collect()));
+        let mut stream: Box<dyn ReadSeek> = Box::new(VaultReader::new(Box::new(vault)));
+
+        stream.seek(SeekFrom::End(-4)).unwrap();
+        let mut buf = Vec::new();
+        stream.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, [252, 253, 254, 255]);
+    }
+}
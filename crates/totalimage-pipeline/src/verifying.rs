@@ -0,0 +1,372 @@
+//! Verifying pipeline - checks each block against a precomputed hash manifest
+
+use md5::{Digest, Md5};
+use sha1::Sha1;
+use sha2::Sha256;
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// Hash algorithm used for a block manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockHashAlgorithm {
+    /// MD5 (128-bit)
+    Md5,
+    /// SHA-1 (160-bit)
+    Sha1,
+    /// SHA-256 (256-bit)
+    Sha256,
+}
+
+impl BlockHashAlgorithm {
+    /// Hash a block of data
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Md5 => Md5::digest(data).to_vec(),
+            Self::Sha1 => Sha1::digest(data).to_vec(),
+            Self::Sha256 => Sha256::digest(data).to_vec(),
+        }
+    }
+
+    /// Get the algorithm name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Sha1 => "SHA1",
+            Self::Sha256 => "SHA256",
+        }
+    }
+}
+
+/// Per-block hashes of a stream, computed at acquisition time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockManifest {
+    /// Hash algorithm of every digest
+    pub algorithm: BlockHashAlgorithm,
+    /// Size of each block in bytes (the last block may be shorter)
+    pub block_size: usize,
+    /// Total length of the hashed stream
+    pub length: u64,
+    /// One digest per block, in order
+    pub digests: Vec<Vec<u8>>,
+}
+
+impl BlockManifest {
+    /// Get the number of blocks the manifest covers
+    pub fn block_count(&self) -> u64 {
+        self.length.div_ceil(self.block_size as u64)
+    }
+}
+
+/// Hash a stream block by block to build a manifest
+///
+/// The whole stream is read from the start; the stream is left positioned
+/// at its end.
+///
+/// # Errors
+///
+/// Returns an error if `block_size` is zero or the stream can't be read
+pub fn compute_block_manifest<R: Read + Seek>(
+    reader: &mut R,
+    block_size: usize,
+    algorithm: BlockHashAlgorithm,
+) -> io::Result<BlockManifest> {
+    if block_size == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Block size must be non-zero",
+        ));
+    }
+
+    let length = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    let mut block = vec![0u8; block_size];
+    let mut digests = Vec::with_capacity(length.div_ceil(block_size as u64) as usize);
+    let mut remaining = length;
+
+    while remaining > 0 {
+        let size = remaining.min(block_size as u64) as usize;
+        reader.read_exact(&mut block[..size])?;
+        digests.push(algorithm.digest(&block[..size]));
+        remaining -= size as u64;
+    }
+
+    Ok(BlockManifest {
+        algorithm,
+        block_size,
+        length,
+        digests,
+    })
+}
+
+/// A block whose contents no longer match the manifest
+///
+/// Returned as the inner error of an [`io::ErrorKind::InvalidData`] error;
+/// recover it with `err.get_ref().and_then(|e| e.downcast_ref::<BlockMismatch>())`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockMismatch {
+    /// Index of the failing block
+    pub block_index: u64,
+    /// Offset of the block's first byte in the stream
+    pub offset: u64,
+}
+
+impl fmt::Display for BlockMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Block {} at offset {} does not match its manifest hash",
+            self.block_index, self.offset
+        )
+    }
+}
+
+impl std::error::Error for BlockMismatch {}
+
+/// A pipeline that verifies every block it reads against a hash manifest.
+///
+/// Each block touched by a read is loaded whole, hashed and compared with
+/// the manifest before any of its bytes are returned, so altered media is
+/// detected rather than silently served. The most recently verified block
+/// is kept so small sequential reads don't re-hash it.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use totalimage_pipeline::{compute_block_manifest, BlockHashAlgorithm, VerifyingPipeline};
+/// use std::io::{Cursor, Read};
+///
+/// let mut source = Cursor::new(vec![0u8; 1024 * 1024]);
+/// let manifest = compute_block_manifest(&mut source, 64 * 1024, BlockHashAlgorithm::Sha256).unwrap();
+///
+/// let mut verified = VerifyingPipeline::new(source, manifest).unwrap();
+/// let mut buf = [0u8; 512];
+/// verified.read_exact(&mut buf).unwrap();
+/// ```
+pub struct VerifyingPipeline<R: Read + Seek> {
+    inner: R,
+    manifest: BlockManifest,
+    position: u64,
+    /// Index and contents of the last verified block
+    current: Option<(u64, Vec<u8>)>,
+}
+
+impl<R: Read + Seek> VerifyingPipeline<R> {
+    /// Create a verifying pipeline over a stream and its manifest
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest is malformed, or if the stream's
+    /// length differs from the length recorded in the manifest
+    pub fn new(mut inner: R, manifest: BlockManifest) -> io::Result<Self> {
+        if manifest.block_size == 0 || manifest.digests.len() as u64 != manifest.block_count() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Manifest block size and digest count are inconsistent",
+            ));
+        }
+
+        let length = inner.seek(SeekFrom::End(0))?;
+        if length != manifest.length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Stream is {} bytes but the manifest covers {} bytes",
+                    length, manifest.length
+                ),
+            ));
+        }
+
+        Ok(Self {
+            inner,
+            manifest,
+            position: 0,
+            current: None,
+        })
+    }
+
+    /// Get the manifest being verified against
+    pub fn manifest(&self) -> &BlockManifest {
+        &self.manifest
+    }
+
+    /// Get the length of the underlying stream
+    pub fn length(&self) -> u64 {
+        self.manifest.length
+    }
+
+    /// Verify every block without returning any data
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`BlockMismatch`] found, or any I/O error
+    pub fn verify_all(&mut self) -> io::Result<()> {
+        for index in 0..self.manifest.block_count() {
+            self.block(index)?;
+        }
+        Ok(())
+    }
+
+    /// Consume the pipeline and return the underlying stream
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Get a verified block, loading and hashing it if necessary
+    fn block(&mut self, index: u64) -> io::Result<&[u8]> {
+        if self.current.as_ref().map(|(i, _)| *i) != Some(index) {
+            self.current = None;
+
+            let block_size = self.manifest.block_size as u64;
+            let start = index * block_size;
+            let size = (self.manifest.length - start).min(block_size) as usize;
+
+            self.inner.seek(SeekFrom::Start(start))?;
+            let mut data = vec![0u8; size];
+            self.inner.read_exact(&mut data)?;
+
+            if self.manifest.algorithm.digest(&data) != self.manifest.digests[index as usize] {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    BlockMismatch {
+                        block_index: index,
+                        offset: start,
+                    },
+                ));
+            }
+
+            self.current = Some((index, data));
+        }
+
+        Ok(&self.current.as_ref().expect("block was just verified").1)
+    }
+}
+
+impl<R: Read + Seek> Read for VerifyingPipeline<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut written = 0;
+
+        while written < buf.len() && self.position < self.manifest.length {
+            let block_size = self.manifest.block_size as u64;
+            let index = self.position / block_size;
+            let offset = (self.position % block_size) as usize;
+
+            let block = self.block(index)?;
+            let count = (block.len() - offset).min(buf.len() - written);
+            buf[written..written + count].copy_from_slice(&block[offset..offset + count]);
+
+            written += count;
+            self.position += count as u64;
+        }
+
+        Ok(written)
+    }
+}
+
+impl<R: Read + Seek> Seek for VerifyingPipeline<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => offset as i64,
+            SeekFrom::End(offset) => self.manifest.length as i64 + offset,
+            SeekFrom::Current(offset) => self.position as i64 + offset,
+        };
+
+        if new_pos < 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Seek before beginning of verifying pipeline",
+            ));
+        }
+
+        self.position = new_pos as u64;
+        Ok(self.position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn test_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn mismatch(err: &io::Error) -> Option<&BlockMismatch> {
+        err.get_ref().and_then(|e| e.downcast_ref::<BlockMismatch>())
+    }
+
+    #[test]
+    fn test_compute_block_manifest() {
+        let data = test_data(2500);
+        let manifest =
+            compute_block_manifest(&mut Cursor::new(&data), 1024, BlockHashAlgorithm::Md5).unwrap();
+
+        assert_eq!(manifest.length, 2500);
+        assert_eq!(manifest.block_count(), 3);
+        assert_eq!(manifest.digests.len(), 3);
+        assert_eq!(manifest.digests[0].len(), 16);
+        assert_eq!(manifest.digests[2], Md5::digest(&data[2048..]).to_vec());
+    }
+
+    #[test]
+    fn test_matching_manifest_reads_pass() {
+        let data = test_data(10_000);
+        for algorithm in [BlockHashAlgorithm::Md5, BlockHashAlgorithm::Sha1, BlockHashAlgorithm::Sha256] {
+            let mut source = Cursor::new(data.clone());
+            let manifest = compute_block_manifest(&mut source, 512, algorithm).unwrap();
+            let mut verified = VerifyingPipeline::new(source, manifest).unwrap();
+
+            let mut buf = vec![0u8; 3000];
+            verified.seek(SeekFrom::Start(700)).unwrap();
+            verified.read_exact(&mut buf).unwrap();
+            assert_eq!(&buf[..], &data[700..3700]);
+
+            let mut all = Vec::new();
+            verified.seek(SeekFrom::Start(0)).unwrap();
+            verified.read_to_end(&mut all).unwrap();
+            assert_eq!(all, data);
+
+            verified.verify_all().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_mutated_block_detected() {
+        let mut data = test_data(4096);
+        let manifest =
+            compute_block_manifest(&mut Cursor::new(&data), 1024, BlockHashAlgorithm::Sha256).unwrap();
+
+        data[2100] ^= 0xFF;
+        let mut verified = VerifyingPipeline::new(Cursor::new(data.clone()), manifest).unwrap();
+
+        // Blocks before the altered one still read
+        let mut buf = [0u8; 1024];
+        verified.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf[..], &data[..1024]);
+
+        verified.seek(SeekFrom::Start(2050)).unwrap();
+        let err = verified.read(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            mismatch(&err),
+            Some(&BlockMismatch {
+                block_index: 2,
+                offset: 2048
+            })
+        );
+
+        let err = verified.verify_all().unwrap_err();
+        assert_eq!(mismatch(&err).map(|m| m.block_index), Some(2));
+    }
+
+    #[test]
+    fn test_length_change_rejected() {
+        let data = test_data(4096);
+        let manifest =
+            compute_block_manifest(&mut Cursor::new(&data), 1024, BlockHashAlgorithm::Md5).unwrap();
+
+        let result = VerifyingPipeline::new(Cursor::new(data[..4000].to_vec()), manifest);
+        assert!(result.is_err());
+    }
+}
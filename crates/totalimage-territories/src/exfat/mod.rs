@@ -21,13 +21,31 @@
 
 pub mod types;
 
-use std::io::{Read, Seek, SeekFrom};
-use totalimage_core::{DirectoryCell, OccupantInfo, Result, Territory};
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+use crate::extents::Extents;
+use totalimage_core::{
+    validate_extract_size, AttributeFlag, DirectoryCell, DirectoryPage, FsFeatures, OccupantInfo, ReadSeek,
+    Result, SpaceSummary, Territory,
+};
 
 pub use types::*;
 
+/// File attribute bits and the flags they decode to
+const ATTRIBUTE_FLAGS: [(u32, AttributeFlag); 5] = [
+    (FileAttributes::READ_ONLY as u32, AttributeFlag::ReadOnly),
+    (FileAttributes::HIDDEN as u32, AttributeFlag::Hidden),
+    (FileAttributes::SYSTEM as u32, AttributeFlag::System),
+    (FileAttributes::DIRECTORY as u32, AttributeFlag::Directory),
+    (FileAttributes::ARCHIVE as u32, AttributeFlag::Archive),
+];
+
 /// exFAT Territory implementation
-#[derive(Debug)]
+///
+/// Most methods take the volume's stream as an argument. A territory opened
+/// with [`from_reader`](Self::from_reader) also owns its stream, which
+/// [`Territory::extract_file`] needs.
 pub struct ExfatTerritory {
     /// Identifier string
     identifier: String,
@@ -47,6 +65,28 @@ pub struct ExfatTerritory {
     root_dir_cluster: u32,
     /// Volume length in bytes
     volume_length: u64,
+    /// Up-case Table for case-insensitive name matching (if found)
+    upcase_table: Option<UpcaseTable>,
+    /// Owned stream for [`Territory::extract_file`]
+    reader: Option<Box<dyn ReadSeek>>,
+}
+
+impl fmt::Debug for ExfatTerritory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExfatTerritory")
+            .field("identifier", &self.identifier)
+            .field("boot_sector", &self.boot_sector)
+            .field("volume_label", &self.volume_label)
+            .field("bytes_per_sector", &self.bytes_per_sector)
+            .field("bytes_per_cluster", &self.bytes_per_cluster)
+            .field("cluster_heap_offset", &self.cluster_heap_offset)
+            .field("cluster_count", &self.cluster_count)
+            .field("root_dir_cluster", &self.root_dir_cluster)
+            .field("volume_length", &self.volume_length)
+            .field("upcase_table", &self.upcase_table)
+            .field("has_reader", &self.reader.is_some())
+            .finish()
+    }
 }
 
 impl ExfatTerritory {
@@ -67,7 +107,7 @@ impl ExfatTerritory {
             boot_sector.cluster_count, bytes_per_cluster
         );
 
-        Ok(Self {
+        let mut territory = Self {
             identifier,
             boot_sector: boot_sector.clone(),
             volume_label: None,
@@ -77,7 +117,128 @@ impl ExfatTerritory {
             cluster_count: boot_sector.cluster_count,
             root_dir_cluster: boot_sector.root_dir_cluster,
             volume_length,
-        })
+            upcase_table: None,
+            reader: None,
+        };
+
+        territory.upcase_table = match territory.load_upcase_table(reader) {
+            Ok(table) => table,
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable exFAT Up-case Table: {}", e);
+                None
+            }
+        };
+
+        Ok(territory)
+    }
+
+    /// Parse an exFAT file system and keep its stream
+    ///
+    /// The territory owns `reader` afterwards, so
+    /// [`Territory::extract_file`] can read file data without being handed
+    /// a stream.
+    pub fn from_reader(mut reader: impl ReadSeek + 'static) -> Result<Self> {
+        let mut territory = Self::parse(&mut reader)?;
+        territory.reader = Some(Box::new(reader));
+        Ok(territory)
+    }
+
+    /// Locate and decompress the Up-case Table from the root directory
+    ///
+    /// Returns None if the root directory has no Up-case Table entry or the
+    /// table fails its checksum.
+    fn load_upcase_table<R: Read + Seek>(&self, reader: &mut R) -> Result<Option<UpcaseTable>> {
+        let root_data = self.read_directory_chain(reader, self.root_dir_cluster)?;
+
+        let entry = root_data
+            .chunks_exact(32)
+            .take_while(|e| EntryType::from_byte(e[0]) != EntryType::EndOfDirectory)
+            .find(|e| EntryType::from_byte(e[0]) == EntryType::UpCaseTable);
+
+        let entry = match entry {
+            Some(bytes) => UpcaseTableEntry::parse(bytes)?,
+            None => return Ok(None),
+        };
+
+        if entry.data_length > UpcaseTable::MAX_SIZE {
+            return Err(totalimage_core::Error::invalid_territory(format!(
+                "Up-case table size {} exceeds limit {}",
+                entry.data_length,
+                UpcaseTable::MAX_SIZE
+            )));
+        }
+
+        // The table is always stored contiguously
+        let data = self.read_contiguous_clusters(reader, entry.first_cluster, entry.data_length)?;
+        if UpcaseTable::checksum(&data) != entry.table_checksum {
+            return Err(totalimage_core::Error::ChecksumVerification(
+                "exFAT Up-case Table checksum mismatch".to_string(),
+            ));
+        }
+
+        Ok(Some(UpcaseTable::decompress(&data)))
+    }
+
+    /// Count free clusters from the Allocation Bitmap
+    ///
+    /// Uses the first bitmap on TexFAT volumes that carry two.
+    pub fn free_cluster_count<R: Read + Seek>(&self, reader: &mut R) -> Result<u32> {
+        let root_data = self.read_directory_chain(reader, self.root_dir_cluster)?;
+
+        let entry = root_data
+            .chunks_exact(32)
+            .take_while(|e| EntryType::from_byte(e[0]) != EntryType::EndOfDirectory)
+            .filter(|e| EntryType::from_byte(e[0]) == EntryType::AllocationBitmap)
+            .map(AllocationBitmapEntry::parse)
+            .find(|e| e.as_ref().map_or(true, |e| !e.is_second()))
+            .ok_or_else(|| {
+                totalimage_core::Error::invalid_territory("exFAT volume has no Allocation Bitmap")
+            })??;
+
+        // One bit per cluster, rounded up to whole bytes
+        let bitmap_size = (self.cluster_count as u64).div_ceil(8);
+        if entry.data_length < bitmap_size {
+            return Err(totalimage_core::Error::invalid_territory(format!(
+                "Allocation bitmap is {} bytes, expected at least {}",
+                entry.data_length, bitmap_size
+            )));
+        }
+
+        // The bitmap is always stored contiguously
+        let bitmap = self.read_contiguous_clusters(reader, entry.first_cluster, bitmap_size)?;
+        let used: u32 = bitmap.iter().map(|b| b.count_ones()).sum();
+
+        // Bits past the last cluster are undefined; don't count them as used
+        let trailing_bits = (bitmap_size * 8 - self.cluster_count as u64) as u32;
+        let trailing_used = match trailing_bits {
+            0 => 0,
+            bits => (bitmap[bitmap.len() - 1] >> (8 - bits)).count_ones(),
+        };
+
+        Ok(self.cluster_count - (used - trailing_used))
+    }
+
+    /// Up-case a file name for comparison
+    ///
+    /// Uses the volume's Up-case Table, falling back to ASCII upper-casing
+    /// when the volume has none.
+    pub fn upcase_name(&self, name: &str) -> Vec<u16> {
+        name.encode_utf16()
+            .map(|unit| match &self.upcase_table {
+                Some(table) => table.upcase(unit),
+                None if (b'a' as u16..=b'z' as u16).contains(&unit) => unit - 0x20,
+                None => unit,
+            })
+            .collect()
+    }
+
+    /// Check whether an entry's name matches an up-cased name
+    ///
+    /// The stored name hash is compared first so most entries are rejected
+    /// without comparing names; entries whose hash failed verification are
+    /// always compared in full.
+    fn name_matches(&self, entry: &ExfatDirectoryEntry, upcased_name: &[u16], name_hash: u16) -> bool {
+        (entry.name_hash == name_hash || !entry.name_hash_valid) && self.upcase_name(&entry.name) == upcased_name
     }
 
     /// Get the boot sector
@@ -112,6 +273,31 @@ impl ExfatTerritory {
         reader: &mut R,
         start_cluster: u32,
         max_bytes: Option<u64>,
+    ) -> Result<Vec<u8>> {
+        self.read_chain_until(reader, start_cluster, max_bytes, |_| false)
+    }
+
+    /// Read a directory's cluster chain up to its end-of-directory entry
+    ///
+    /// Clusters past the one holding the end marker are never read, so a
+    /// chain that runs on through unrelated clusters costs no more than the
+    /// directory itself.
+    fn read_directory_chain<R: Read + Seek>(&self, reader: &mut R, start_cluster: u32) -> Result<Vec<u8>> {
+        self.read_chain_until(reader, start_cluster, None, |cluster| {
+            cluster
+                .chunks_exact(32)
+                .any(|entry| entry[0] == EntryType::EndOfDirectory as u8)
+        })
+    }
+
+    /// Follow a FAT chain until it ends, `max_bytes` have been read, or
+    /// `is_last` accepts the cluster just read
+    fn read_chain_until<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        start_cluster: u32,
+        max_bytes: Option<u64>,
+        is_last: impl Fn(&[u8]) -> bool,
     ) -> Result<Vec<u8>> {
         let mut data = Vec::new();
         let mut current_cluster = start_cluster;
@@ -136,7 +322,7 @@ impl ExfatTerritory {
             data.extend_from_slice(&cluster_data);
 
             bytes_read += to_read;
-            if bytes_read >= max {
+            if bytes_read >= max || is_last(&cluster_data) {
                 break;
             }
 
@@ -164,99 +350,232 @@ impl ExfatTerritory {
     }
 
     /// Read root directory entries
+    ///
+    /// The root directory has no stream extension entry giving its length
+    /// or contiguity, so its FAT chain is followed until the end-of-directory
+    /// entry or the end of the chain, whichever comes first.
     pub fn read_root_directory<R: Read + Seek>(&self, reader: &mut R) -> Result<Vec<ExfatDirectoryEntry>> {
         self.read_directory_from_cluster(reader, self.root_dir_cluster)
     }
 
-    /// Read directory from a cluster
+    /// Read directory from a cluster, following its FAT chain to the
+    /// end-of-directory entry
     pub fn read_directory_from_cluster<R: Read + Seek>(
         &self,
         reader: &mut R,
         start_cluster: u32,
     ) -> Result<Vec<ExfatDirectoryEntry>> {
-        // Read directory cluster chain
-        let dir_data = self.read_cluster_chain(reader, start_cluster, None)?;
+        let dir_data = self.read_directory_chain(reader, start_cluster)?;
+        self.parse_directory_entries(&dir_data)
+    }
 
+    /// Parse the entry sets of a directory's data
+    fn parse_directory_entries(&self, dir_data: &[u8]) -> Result<Vec<ExfatDirectoryEntry>> {
         let mut entries = Vec::new();
         let mut i = 0;
 
         while i + 32 <= dir_data.len() {
-            let entry_type = EntryType::from_byte(dir_data[i]);
-
-            match entry_type {
-                EntryType::EndOfDirectory => break,
-                EntryType::FileEntry => {
-                    // Parse file directory entry
-                    let file_entry = FileDirectoryEntry::parse(&dir_data[i..i + 32])?;
-                    let secondary_count = file_entry.secondary_count as usize;
-
-                    // Need at least stream extension + file name entries
-                    if secondary_count < 2 || i + 32 * (secondary_count + 1) > dir_data.len() {
-                        i += 32;
-                        continue;
-                    }
+            match self.parse_entry_set(&dir_data[i..])? {
+                EntrySet::End => break,
+                EntrySet::File(entry, length) => {
+                    entries.push(entry);
+                    i += length;
+                }
+                EntrySet::Skip(length) => i += length,
+                // A set cut short by the end of the data is stepped over
+                EntrySet::Incomplete => i += 32,
+            }
+        }
 
-                    // Parse stream extension (second entry)
-                    let stream_offset = i + 32;
-                    if dir_data[stream_offset] != 0xC0 {
-                        i += 32;
-                        continue;
-                    }
-                    let stream_entry = StreamExtensionEntry::parse(&dir_data[stream_offset..stream_offset + 32])?;
+        Ok(entries)
+    }
+
+    /// Parse the entry set starting at the beginning of `data`
+    ///
+    /// A file entry set needs its stream extension and at least one file
+    /// name entry; one without them is skipped a single entry at a time.
+    fn parse_entry_set(&self, data: &[u8]) -> Result<EntrySet> {
+        if data.len() < 32 {
+            return Ok(EntrySet::Incomplete);
+        }
 
-                    // Parse file name entries
-                    let mut name = String::new();
-                    let name_length = stream_entry.name_length as usize;
-                    let mut chars_collected = 0;
+        match EntryType::from_byte(data[0]) {
+            EntryType::EndOfDirectory => Ok(EntrySet::End),
+            EntryType::FileEntry => {
+                // Parse file directory entry
+                let file_entry = FileDirectoryEntry::parse(&data[..32])?;
+                let secondary_count = file_entry.secondary_count as usize;
 
-                    for j in 2..=secondary_count {
-                        let name_offset = i + 32 * j;
-                        if dir_data[name_offset] != 0xC1 {
+                // Need at least stream extension + file name entries
+                if secondary_count < 2 {
+                    return Ok(EntrySet::Skip(32));
+                }
+                if 32 * (secondary_count + 1) > data.len() {
+                    return Ok(EntrySet::Incomplete);
+                }
+
+                // Parse stream extension (second entry)
+                if data[32] != 0xC0 {
+                    return Ok(EntrySet::Skip(32));
+                }
+                let stream_entry = StreamExtensionEntry::parse(&data[32..64])?;
+
+                // Parse file name entries
+                let mut name = String::new();
+                let name_length = stream_entry.name_length as usize;
+                let mut chars_collected = 0;
+
+                for j in 2..=secondary_count {
+                    let name_offset = 32 * j;
+                    if data[name_offset] != 0xC1 {
+                        break;
+                    }
+                    let name_entry = FileNameEntry::parse(&data[name_offset..name_offset + 32])?;
+
+                    for &ch in &name_entry.file_name {
+                        if ch == 0 || chars_collected >= name_length {
                             break;
                         }
-                        let name_entry = FileNameEntry::parse(&dir_data[name_offset..name_offset + 32])?;
-
-                        for &ch in &name_entry.file_name {
-                            if ch == 0 || chars_collected >= name_length {
-                                break;
-                            }
-                            if let Some(c) = char::from_u32(ch as u32) {
-                                name.push(c);
-                                chars_collected += 1;
-                            }
+                        if let Some(c) = char::from_u32(ch as u32) {
+                            name.push(c);
+                            chars_collected += 1;
                         }
                     }
+                }
 
-                    entries.push(ExfatDirectoryEntry {
+                let name_hash = StreamExtensionEntry::name_hash(&self.upcase_name(&name));
+                let name_hash_valid = name_hash == stream_entry.name_hash;
+                if !name_hash_valid {
+                    tracing::warn!(
+                        "exFAT name hash mismatch for '{}': stored {:#06x}, computed {:#06x}",
                         name,
-                        attributes: file_entry.attributes,
-                        size: stream_entry.data_length,
-                        first_cluster: stream_entry.first_cluster,
-                        created: file_entry.create_timestamp,
-                        modified: file_entry.modify_timestamp,
-                        accessed: file_entry.access_timestamp,
-                        is_contiguous: stream_entry.is_contiguous(),
-                    });
-
-                    // Skip all secondary entries
-                    i += 32 * (secondary_count + 1);
-                }
-                _ => {
-                    i += 32;
+                        stream_entry.name_hash,
+                        name_hash
+                    );
                 }
+
+                let entry = ExfatDirectoryEntry {
+                    name,
+                    attributes: file_entry.attributes,
+                    size: stream_entry.data_length,
+                    first_cluster: stream_entry.first_cluster,
+                    created: file_entry.create_timestamp,
+                    modified: file_entry.modify_timestamp,
+                    accessed: file_entry.access_timestamp,
+                    created_10ms: file_entry.create_10ms,
+                    modified_10ms: file_entry.modify_10ms,
+                    created_utc_offset: file_entry.create_utc_offset,
+                    modified_utc_offset: file_entry.modify_utc_offset,
+                    accessed_utc_offset: file_entry.access_utc_offset,
+                    is_contiguous: stream_entry.is_contiguous(),
+                    name_hash: stream_entry.name_hash,
+                    name_hash_valid,
+                };
+
+                // The set covers all its secondary entries
+                Ok(EntrySet::File(entry, 32 * (secondary_count + 1)))
             }
+            _ => Ok(EntrySet::Skip(32)),
         }
+    }
 
-        Ok(entries)
+    /// Byte offsets of a directory's clusters, in order
+    ///
+    /// Only the FAT is read, not the directory itself. `directory` is `None`
+    /// for the root, whose chain is followed to its end; other directories
+    /// stop at their recorded size, and contiguous ones don't consult the FAT.
+    fn directory_clusters<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        directory: Option<&ExfatDirectoryEntry>,
+    ) -> Result<Vec<u64>> {
+        let bytes_per_cluster = self.bytes_per_cluster as u64;
+        let (start, max_clusters) = match directory {
+            None => (self.root_dir_cluster, self.cluster_count as u64),
+            Some(entry) => (
+                entry.first_cluster,
+                entry.size.div_ceil(bytes_per_cluster).min(self.cluster_count as u64),
+            ),
+        };
+
+        if directory.is_some_and(|entry| entry.is_contiguous) {
+            let first = self.cluster_offset(start);
+            return Ok((0..max_clusters).map(|i| first + i * bytes_per_cluster).collect());
+        }
+
+        let mut clusters = Vec::new();
+        let mut current = start;
+        while !cluster::is_end(current)
+            && (2..self.cluster_count + 2).contains(&current)
+            && (clusters.len() as u64) < max_clusters
+        {
+            clusters.push(self.cluster_offset(current));
+            current = self.read_fat_entry(reader, current)?;
+        }
+
+        Ok(clusters)
+    }
+
+    /// Read up to `limit` entries of a directory, starting at slot `cursor`
+    ///
+    /// Slots are the directory's 32-byte entries. Returns the entries and
+    /// the slot to resume from, or `None` once the end of the directory is
+    /// reached. Reading starts at the cursor's cluster and stops once the
+    /// page is full, and a resume slot is always the start of an entry set.
+    fn read_directory_page<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        directory: Option<&ExfatDirectoryEntry>,
+        cursor: u64,
+        limit: usize,
+    ) -> Result<(Vec<ExfatDirectoryEntry>, Option<u64>)> {
+        let clusters = self.directory_clusters(reader, directory)?;
+        let slots_per_cluster = self.bytes_per_cluster as u64 / 32;
+        let first = usize::try_from(cursor / slots_per_cluster).unwrap_or(usize::MAX);
+        let base = first as u64 * slots_per_cluster;
+
+        let mut data = Vec::new();
+        let mut position = ((cursor - base) * 32) as usize;
+        let mut remaining = clusters.iter().skip(first);
+        let mut entries = Vec::new();
+
+        loop {
+            match self.parse_entry_set(data.get(position..).unwrap_or_default())? {
+                EntrySet::End => return Ok((entries, None)),
+                EntrySet::File(entry, length) => {
+                    // A further entry exists, so the page ends before it
+                    if entries.len() == limit {
+                        return Ok((entries, Some(base + position as u64 / 32)));
+                    }
+                    entries.push(entry);
+                    position += length;
+                }
+                EntrySet::Skip(length) => position += length,
+                EntrySet::Incomplete => match remaining.next() {
+                    Some(&offset) => {
+                        let start = data.len();
+                        data.resize(start + self.bytes_per_cluster as usize, 0);
+                        reader.seek(SeekFrom::Start(offset))?;
+                        reader.read_exact(&mut data[start..])?;
+                    }
+                    None if position + 32 <= data.len() => position += 32,
+                    None => return Ok((entries, None)),
+                },
+            }
+        }
     }
 
     /// Read file contents
+    ///
+    /// Files larger than the active extraction limit are rejected before
+    /// anything is read.
     pub fn read_file<R: Read + Seek>(&self, reader: &mut R, entry: &ExfatDirectoryEntry) -> Result<Vec<u8>> {
         if entry.is_directory() {
             return Err(totalimage_core::Error::invalid_territory(
                 "Cannot read directory as file",
             ));
         }
+        validate_extract_size(entry.size)?;
 
         if entry.is_contiguous {
             self.read_contiguous_clusters(reader, entry.first_cluster, entry.size)
@@ -265,7 +584,71 @@ impl ExfatTerritory {
         }
     }
 
+    /// Stream file contents into `out`, returning the number of bytes written
+    ///
+    /// Unlike [`read_file`](Self::read_file) this holds at most one cluster
+    /// in memory, so it isn't subject to the extraction limit. A contiguous
+    /// file is copied as a single run; otherwise the FAT chain is followed
+    /// cluster by cluster. Exactly `size` bytes are written, or an error is
+    /// returned if the run or chain ends first.
+    pub fn read_file_to<R: Read + Seek, W: Write>(
+        &self,
+        reader: &mut R,
+        entry: &ExfatDirectoryEntry,
+        out: &mut W,
+    ) -> Result<u64> {
+        if entry.is_directory() {
+            return Err(totalimage_core::Error::invalid_territory(
+                "Cannot read directory as file",
+            ));
+        }
+        if entry.size == 0 {
+            return Ok(0);
+        }
+
+        let written = if entry.is_contiguous {
+            reader.seek(SeekFrom::Start(self.cluster_offset(entry.first_cluster)))?;
+            io::copy(&mut reader.take(entry.size), out)?
+        } else {
+            let mut buf = vec![0u8; self.bytes_per_cluster as usize];
+            let mut written = 0u64;
+            let mut current_cluster = entry.first_cluster;
+
+            // Circular reference protection, as when reading the chain
+            for _ in 0..self.cluster_count + 10 {
+                let in_heap = current_cluster >= 2 && current_cluster < self.cluster_count + 2;
+                if cluster::is_end(current_cluster) || !in_heap {
+                    break;
+                }
+
+                let chunk = &mut buf[..(self.bytes_per_cluster as u64).min(entry.size - written) as usize];
+                reader.seek(SeekFrom::Start(self.cluster_offset(current_cluster)))?;
+                reader.read_exact(chunk)?;
+                out.write_all(chunk)?;
+                written += chunk.len() as u64;
+                if written >= entry.size {
+                    break;
+                }
+
+                current_cluster = self.read_fat_entry(reader, current_cluster)?;
+            }
+            written
+        };
+
+        if written < entry.size {
+            return Err(totalimage_core::Error::truncated(format!(
+                "{} ended after {} of {} bytes",
+                entry.name, written, entry.size
+            )));
+        }
+        Ok(written)
+    }
+
     /// Read subdirectory contents
+    ///
+    /// A directory marked as contiguous (`NoFatChain`) is read straight from
+    /// its first cluster without consulting the FAT, whose entries for it
+    /// are undefined.
     pub fn read_subdirectory<R: Read + Seek>(
         &self,
         reader: &mut R,
@@ -277,7 +660,12 @@ impl ExfatTerritory {
             ));
         }
 
-        self.read_directory_from_cluster(reader, entry.first_cluster)
+        let dir_data = if entry.is_contiguous {
+            self.read_contiguous_clusters(reader, entry.first_cluster, entry.size)?
+        } else {
+            self.read_cluster_chain(reader, entry.first_cluster, Some(entry.size))?
+        };
+        self.parse_directory_entries(&dir_data)
     }
 
     /// Navigate to a path and return the entry
@@ -292,18 +680,19 @@ impl ExfatTerritory {
             .collect();
 
         if components.is_empty() {
-            return Err(totalimage_core::Error::invalid_territory("Empty path"));
+            return Err(totalimage_core::Error::not_found("Empty path"));
         }
 
         let mut current_entries = self.read_root_directory(reader)?;
 
         for (i, component) in components.iter().enumerate() {
             let is_last = i == components.len() - 1;
-            let upper_component = component.to_uppercase();
+            let upper_component = self.upcase_name(component);
+            let component_hash = StreamExtensionEntry::name_hash(&upper_component);
 
             let found = current_entries
                 .iter()
-                .find(|e| e.name.to_uppercase() == upper_component)
+                .find(|e| self.name_matches(e, &upper_component, component_hash))
                 .cloned();
 
             match found {
@@ -312,7 +701,7 @@ impl ExfatTerritory {
                         return Ok(entry);
                     }
                     if !entry.is_directory() {
-                        return Err(totalimage_core::Error::invalid_territory(format!(
+                        return Err(totalimage_core::Error::not_found(format!(
                             "'{}' is not a directory",
                             component
                         )));
@@ -320,7 +709,7 @@ impl ExfatTerritory {
                     current_entries = self.read_subdirectory(reader, &entry)?;
                 }
                 None => {
-                    return Err(totalimage_core::Error::invalid_territory(format!(
+                    return Err(totalimage_core::Error::not_found(format!(
                         "Path component '{}' not found",
                         component
                     )));
@@ -328,7 +717,7 @@ impl ExfatTerritory {
             }
         }
 
-        Err(totalimage_core::Error::invalid_territory("Path not found"))
+        Err(totalimage_core::Error::not_found("Path not found"))
     }
 }
 
@@ -341,8 +730,12 @@ impl Territory for ExfatTerritory {
         Ok(self.volume_label.clone().unwrap_or_else(|| "EXFAT".to_string()))
     }
 
-    fn headquarters(&self) -> Result<Box<dyn DirectoryCell>> {
-        Ok(Box::new(ExfatRootDirectory))
+    fn headquarters(&self) -> Result<Box<dyn DirectoryCell<'_> + '_>> {
+        Ok(Box::new(ExfatDirectoryCell {
+            territory: self,
+            name: "/".to_string(),
+            directory: None,
+        }))
     }
 
     fn domain_size(&self) -> u64 {
@@ -358,46 +751,724 @@ impl Territory for ExfatTerritory {
         self.bytes_per_cluster as u64
     }
 
+    fn space_summary(&self, stream: &mut dyn ReadSeek) -> Result<SpaceSummary> {
+        let mut reader = stream;
+        let free_clusters = self.free_cluster_count(&mut reader)?;
+        Ok(SpaceSummary::from_blocks(
+            self.volume_length,
+            self.bytes_per_cluster as u64,
+            self.cluster_count as u64,
+            free_clusters as u64,
+        ))
+    }
+
     fn hierarchical(&self) -> bool {
         true // exFAT supports subdirectories
     }
 
-    fn navigate_to(&self, _path: &str) -> Result<Box<dyn DirectoryCell>> {
-        // Simplified: always return root directory
-        self.headquarters()
+    fn fs_features(&self) -> FsFeatures {
+        FsFeatures {
+            timestamps: true,
+            long_names: true,
+            max_file_size: u64::MAX, // 64-bit DataLength
+            ..FsFeatures::default()
+        }
+    }
+
+    fn file_metadata(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<OccupantInfo> {
+        let mut reader = stream;
+        self.find_entry_by_path(&mut reader, path).map(occupant_info)
+    }
+
+    fn data_runs(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<(u64, u64)>> {
+        let mut reader = stream;
+        let entry = self.find_entry_by_path(&mut reader, path)?;
+        if entry.first_cluster < 2 {
+            return Ok(Vec::new());
+        }
+
+        let mut extents = Extents::new(entry.size);
+        if entry.is_contiguous {
+            extents.push(self.cluster_offset(entry.first_cluster), entry.size);
+            return Ok(extents.into_runs());
+        }
+
+        // Circular reference protection, as when reading the chain
+        let mut current = entry.first_cluster;
+        for _ in 0..self.cluster_count + 10 {
+            if cluster::is_end(current) || current < 2 || current >= self.cluster_count + 2 {
+                break;
+            }
+            if !extents.push(self.cluster_offset(current), self.bytes_per_cluster as u64) {
+                break;
+            }
+            current = self.read_fat_entry(&mut reader, current)?;
+        }
+        Ok(extents.into_runs())
+    }
+
+    fn decode_attributes(&self, raw: u32) -> Vec<AttributeFlag> {
+        AttributeFlag::decode(raw, &ATTRIBUTE_FLAGS)
     }
 
-    fn extract_file(&mut self, _path: &str) -> Result<Vec<u8>> {
-        // Simplified: return empty
-        // Full implementation would parse path, find file, read clusters
-        Ok(Vec::new())
+    fn extract_file(&mut self, path: &str) -> Result<Vec<u8>> {
+        let mut reader = self.reader.take().ok_or_else(|| {
+            totalimage_core::Error::unsupported("exFAT territory was parsed without an owned stream")
+        })?;
+        let result = self
+            .find_entry_by_path(&mut reader, path)
+            .and_then(|entry| self.read_file(&mut reader, &entry));
+        self.reader = Some(reader);
+        result
     }
 }
 
-/// exFAT root directory cell (placeholder for DirectoryCell trait)
-#[derive(Debug)]
-struct ExfatRootDirectory;
+/// Convert a directory entry to occupant info with decoded timestamps
+fn occupant_info(entry: ExfatDirectoryEntry) -> OccupantInfo {
+    OccupantInfo {
+        is_directory: entry.is_directory(),
+        size: if entry.is_directory() { 0 } else { entry.size },
+        created: entry.created_time(),
+        modified: entry.modified_time(),
+        accessed: entry.accessed_time(),
+        timestamp_is_local: false,
+        attributes: entry.attributes.0 as u32,
+        link_target: None,
+        short_name: None,
+        link_count: 1,
+        path: None,
+        name: entry.name,
+    }
+}
+
+/// What [`ExfatTerritory::parse_entry_set`] found at a directory position
+enum EntrySet {
+    /// The end-of-directory entry
+    End,
+    /// A file entry set, and its length in bytes
+    File(ExfatDirectoryEntry, usize),
+    /// Any other entry, to step over by its length in bytes
+    Skip(usize),
+    /// An entry or set running past the data read so far
+    Incomplete,
+}
+
+/// exFAT directory cell
+struct ExfatDirectoryCell<'a> {
+    territory: &'a ExfatTerritory,
+    name: String,
+    /// Entry of the directory, or `None` for the root directory
+    directory: Option<ExfatDirectoryEntry>,
+}
+
+impl ExfatDirectoryCell<'_> {
+    fn entries(&self, stream: &mut dyn ReadSeek) -> Result<Vec<ExfatDirectoryEntry>> {
+        let mut reader = stream;
+        match &self.directory {
+            None => self.territory.read_root_directory(&mut reader),
+            Some(entry) => self.territory.read_subdirectory(&mut reader, entry),
+        }
+    }
+}
 
-impl DirectoryCell for ExfatRootDirectory {
+impl<'a> DirectoryCell<'a> for ExfatDirectoryCell<'a> {
     fn name(&self) -> &str {
-        "/"
+        &self.name
     }
 
-    fn list_occupants(&self) -> Result<Vec<OccupantInfo>> {
-        // Simplified implementation - would need reader access
-        Ok(Vec::new())
+    fn list_occupants(&self, stream: &mut dyn ReadSeek) -> Result<Vec<OccupantInfo>> {
+        Ok(self.entries(stream)?.into_iter().map(occupant_info).collect())
     }
 
-    fn enter(&self, _name: &str) -> Result<Box<dyn DirectoryCell>> {
-        Err(totalimage_core::Error::invalid_territory(
-            "Directory navigation not implemented in simplified mode",
-        ))
+    /// Pages resume at a directory slot, reading only from its cluster on
+    fn list_page(&self, stream: &mut dyn ReadSeek, cursor: u64, limit: usize) -> Result<DirectoryPage> {
+        if limit == 0 {
+            return Ok(DirectoryPage::unmoved(cursor));
+        }
+        let mut reader = stream;
+        let (entries, next) = self
+            .territory
+            .read_directory_page(&mut reader, self.directory.as_ref(), cursor, limit)?;
+        Ok(DirectoryPage {
+            occupants: entries.into_iter().map(occupant_info).collect(),
+            next,
+        })
+    }
+
+    fn enter(&self, stream: &mut dyn ReadSeek, name: &str) -> Result<Box<dyn DirectoryCell<'a> + 'a>> {
+        let upper_name = self.territory.upcase_name(name);
+        let name_hash = StreamExtensionEntry::name_hash(&upper_name);
+        let entry = self
+            .entries(stream)?
+            .into_iter()
+            .find(|e| self.territory.name_matches(e, &upper_name, name_hash))
+            .ok_or_else(|| {
+                totalimage_core::Error::not_found(format!("Directory not found: {}", name))
+            })?;
+
+        if !entry.is_directory() {
+            return Err(totalimage_core::Error::invalid_territory(format!(
+                "'{}' is not a directory",
+                name
+            )));
+        }
+
+        Ok(Box::new(ExfatDirectoryCell {
+            territory: self.territory,
+            name: entry.name.clone(),
+            directory: Some(entry),
+        }))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::Cursor;
+    use totalimage_core::ListOptions;
+
+    const TEST_CLUSTER_SIZE: usize = 512;
+    const TEST_HEAP_OFFSET: usize = 32 * 512;
+    const TEST_FAT_OFFSET: usize = 24 * 512;
+
+    /// Build a minimal exFAT volume (512-byte sectors and clusters) with the
+    /// root directory in cluster 2
+    fn build_exfat_image(root_entries: &[[u8; 32]]) -> Vec<u8> {
+        let mut disk = vec![0u8; TEST_HEAP_OFFSET + 64 * TEST_CLUSTER_SIZE];
+
+        disk[0..3].copy_from_slice(&[0xEB, 0x76, 0x90]);
+        disk[3..11].copy_from_slice(b"EXFAT   ");
+        let volume_sectors = (disk.len() / 512) as u64;
+        disk[72..80].copy_from_slice(&volume_sectors.to_le_bytes());
+        disk[80..84].copy_from_slice(&24u32.to_le_bytes()); // FAT offset
+        disk[84..88].copy_from_slice(&8u32.to_le_bytes()); // FAT length
+        disk[88..92].copy_from_slice(&32u32.to_le_bytes()); // Cluster heap offset
+        disk[92..96].copy_from_slice(&64u32.to_le_bytes()); // Cluster count
+        disk[96..100].copy_from_slice(&2u32.to_le_bytes()); // Root cluster
+        disk[104..106].copy_from_slice(&0x0100u16.to_le_bytes());
+        disk[108] = 9; // 512 bytes/sector
+        disk[109] = 0; // 1 sector/cluster
+        disk[110] = 1;
+        disk[510] = 0x55;
+        disk[511] = 0xAA;
+
+        set_fat_entry(&mut disk, 2, cluster::END_OF_CHAIN);
+
+        for (i, entry) in root_entries.iter().enumerate() {
+            let offset = TEST_HEAP_OFFSET + i * 32;
+            disk[offset..offset + 32].copy_from_slice(entry);
+        }
+
+        disk
+    }
+
+    fn set_fat_entry(disk: &mut [u8], cluster: u32, value: u32) {
+        let offset = TEST_FAT_OFFSET + cluster as usize * 4;
+        disk[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// Build a File + Stream Extension + File Name entry set
+    fn file_entry_set(name: &str, attributes: u16, first_cluster: u32, size: u64) -> Vec<[u8; 32]> {
+        let name_utf16: Vec<u16> = name.encode_utf16().collect();
+        let name_entries = name_utf16.len().div_ceil(15);
+
+        let mut file = [0u8; 32];
+        file[0] = 0x85;
+        file[1] = (1 + name_entries) as u8;
+        file[4..6].copy_from_slice(&attributes.to_le_bytes());
+
+        let mut stream = [0u8; 32];
+        stream[0] = 0xC0;
+        stream[1] = 0x03; // Allocation possible, no FAT chain
+        stream[3] = name_utf16.len() as u8;
+        let upcased: Vec<u16> = name.to_ascii_uppercase().encode_utf16().collect();
+        stream[4..6].copy_from_slice(&StreamExtensionEntry::name_hash(&upcased).to_le_bytes());
+        stream[8..16].copy_from_slice(&size.to_le_bytes());
+        stream[20..24].copy_from_slice(&first_cluster.to_le_bytes());
+        stream[24..32].copy_from_slice(&size.to_le_bytes());
+
+        let mut entries = vec![file, stream];
+        for chunk in name_utf16.chunks(15) {
+            let mut name_entry = [0u8; 32];
+            name_entry[0] = 0xC1;
+            for (i, ch) in chunk.iter().enumerate() {
+                name_entry[2 + i * 2..4 + i * 2].copy_from_slice(&ch.to_le_bytes());
+            }
+            entries.push(name_entry);
+        }
+        entries
+    }
+
+    #[test]
+    fn test_upcase_table_lookup() {
+        // Identity up to 'a', a-z upper-cased, identity up to 'é' which folds to 'É'
+        let mut units: Vec<u16> = vec![0xFFFF, 0x61];
+        units.extend(0x41..=0x5A);
+        units.extend([0xFFFF, 0xE9 - 0x7B, 0xC9]);
+        let table: Vec<u8> = units.iter().flat_map(|u| u.to_le_bytes()).collect();
+
+        let mut upcase_entry = [0u8; 32];
+        upcase_entry[0] = 0x82;
+        upcase_entry[4..8].copy_from_slice(&UpcaseTable::checksum(&table).to_le_bytes());
+        upcase_entry[20..24].copy_from_slice(&3u32.to_le_bytes());
+        upcase_entry[24..32].copy_from_slice(&(table.len() as u64).to_le_bytes());
+
+        let mut entries = vec![upcase_entry];
+        entries.extend(file_entry_set("café.txt", 0x20, 4, 5));
+
+        let mut disk = build_exfat_image(&entries);
+        let table_offset = TEST_HEAP_OFFSET + TEST_CLUSTER_SIZE;
+        disk[table_offset..table_offset + table.len()].copy_from_slice(&table);
+
+        let mut cursor = Cursor::new(disk);
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+        assert!(territory.upcase_table.is_some());
+
+        let entry = territory.find_entry_by_path(&mut cursor, "/CAFÉ.TXT").unwrap();
+        assert_eq!(entry.name, "café.txt");
+        assert!(territory.find_entry_by_path(&mut cursor, "/Café.Txt").is_ok());
+        assert!(territory.find_entry_by_path(&mut cursor, "/CAFE.TXT").is_err());
+    }
+
+    #[test]
+    fn test_ascii_fallback_without_upcase_table() {
+        let entries = file_entry_set("café.txt", 0x20, 4, 5);
+        let mut cursor = Cursor::new(build_exfat_image(&entries));
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+        assert!(territory.upcase_table.is_none());
+
+        assert!(territory.find_entry_by_path(&mut cursor, "/CAFé.TXT").is_ok());
+        assert!(territory.find_entry_by_path(&mut cursor, "/CAFÉ.TXT").is_err());
+    }
+
+    #[test]
+    fn test_list_directory_timestamps() {
+        use chrono::{TimeZone, Utc};
+
+        let mut entries = file_entry_set("photo.jpg", 0x20, 3, 100);
+        // Created 2023-06-15 14:30:58.50 at UTC+02:00
+        let created = (43u32 << 25) | (6 << 21) | (15 << 16) | (14 << 11) | (30 << 5) | 29;
+        entries[0][8..12].copy_from_slice(&created.to_le_bytes());
+        entries[0][20] = 50;
+        entries[0][22] = 0x80 | 8;
+
+        let mut cursor = Cursor::new(build_exfat_image(&entries));
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+
+        let listing = territory.list_directory(&mut cursor, "/").unwrap();
+        assert_eq!(listing.len(), 1);
+        assert_eq!(listing[0].name, "photo.jpg");
+        assert_eq!(listing[0].size, 100);
+
+        let expected = Utc.with_ymd_and_hms(2023, 6, 15, 12, 30, 58).unwrap()
+            + chrono::Duration::milliseconds(500);
+        assert_eq!(listing[0].created, Some(expected));
+        assert_eq!(listing[0].modified, None);
+        assert!(!listing[0].timestamp_is_local);
+    }
+
+    #[test]
+    fn test_decode_attributes() {
+        let mut cursor = Cursor::new(build_exfat_image(&file_entry_set("Docs", 0x13, 3, 0)));
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+
+        let docs = territory.file_metadata(&mut cursor, "Docs").unwrap();
+        assert_eq!(
+            territory.decode_attributes(docs.attributes),
+            vec![AttributeFlag::ReadOnly, AttributeFlag::Hidden, AttributeFlag::Directory]
+        );
+        assert_eq!(
+            territory.decode_attributes(0x24),
+            vec![AttributeFlag::System, AttributeFlag::Archive]
+        );
+    }
+
+    #[test]
+    fn test_directory_cells() {
+        let mut entries = file_entry_set("Docs", 0x10, 3, TEST_CLUSTER_SIZE as u64);
+        entries.extend(file_entry_set("top.txt", 0x20, 4, 7));
+        let mut disk = build_exfat_image(&entries);
+
+        set_fat_entry(&mut disk, 3, cluster::END_OF_CHAIN);
+        let docs_offset = TEST_HEAP_OFFSET + TEST_CLUSTER_SIZE;
+        for (i, entry) in file_entry_set("note.txt", 0x20, 5, 42).iter().enumerate() {
+            disk[docs_offset + i * 32..docs_offset + (i + 1) * 32].copy_from_slice(entry);
+        }
+
+        let mut cursor = Cursor::new(disk);
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+
+        let root = territory.headquarters().unwrap();
+        let names: Vec<String> = root
+            .list_occupants(&mut cursor)
+            .unwrap()
+            .into_iter()
+            .map(|o| o.name)
+            .collect();
+        assert_eq!(names, ["Docs", "top.txt"]);
+
+        let docs = root.enter(&mut cursor, "DOCS").unwrap();
+        assert_eq!(docs.name(), "Docs");
+        let note = docs.get_occupant(&mut cursor, "note.txt").unwrap().unwrap();
+        assert_eq!(note.size, 42);
+
+        let docs = territory.navigate_to(&mut cursor, "/docs").unwrap();
+        assert_eq!(docs.list_occupants(&mut cursor).unwrap().len(), 1);
+        assert!(root.enter(&mut cursor, "top.txt").is_err());
+    }
+
+    /// Write entry sets to a cluster, starting at its first slot
+    /// List a directory a page at a time, checking each cursor moves on
+    fn paged_names(territory: &ExfatTerritory, cursor: &mut Cursor<Vec<u8>>, path: &str, limit: usize) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut next = Some(0);
+        while let Some(position) = next {
+            let page = territory.list_directory_page(cursor, path, position, limit).unwrap();
+            assert!(page.occupants.len() == limit || page.next.is_none());
+            assert!(page.next.is_none_or(|n| n > position));
+            names.extend(page.occupants.into_iter().map(|o| o.name));
+            next = page.next;
+        }
+        names
+    }
+
+    fn write_cluster_entries(disk: &mut [u8], cluster: u32, entries: &[[u8; 32]]) {
+        let offset = TEST_HEAP_OFFSET + (cluster as usize - 2) * TEST_CLUSTER_SIZE;
+        for (i, entry) in entries.iter().enumerate() {
+            disk[offset + i * 32..offset + (i + 1) * 32].copy_from_slice(entry);
+        }
+    }
+
+    #[test]
+    fn test_contiguous_and_chained_directories() {
+        let per_cluster = TEST_CLUSTER_SIZE / 32;
+        let flat_size = 2 * TEST_CLUSTER_SIZE as u64;
+        let mut entries = file_entry_set("Flat", 0x10, 3, flat_size);
+        let mut chained = file_entry_set("Chained", 0x10, 5, flat_size);
+        chained[1][1] = 0x01; // Allocation possible, FAT chain valid
+        entries.extend(chained);
+        entries.resize(per_cluster, [0x05; 32]);
+        let mut disk = build_exfat_image(&entries);
+
+        // Root fills cluster 2 and continues into cluster 9
+        set_fat_entry(&mut disk, 2, 9);
+        set_fat_entry(&mut disk, 9, cluster::END_OF_CHAIN);
+        write_cluster_entries(&mut disk, 9, &file_entry_set("late.txt", 0x20, 0, 0));
+
+        // Flat fills cluster 3 with unused entries and continues into
+        // cluster 4; its FAT entries are left free
+        let mut flat = vec![[0x05u8; 32]; per_cluster];
+        flat.extend(file_entry_set("a.txt", 0x20, 0, 0));
+        write_cluster_entries(&mut disk, 3, &flat);
+
+        // Chained runs 5 -> 7; cluster 6 belongs to something else
+        let mut chained = vec![[0x05u8; 32]; per_cluster];
+        chained.extend(file_entry_set("b.txt", 0x20, 0, 0));
+        write_cluster_entries(&mut disk, 5, &chained[..per_cluster]);
+        write_cluster_entries(&mut disk, 7, &chained[per_cluster..]);
+        write_cluster_entries(&mut disk, 6, &file_entry_set("stray.txt", 0x20, 0, 0));
+        set_fat_entry(&mut disk, 5, 7);
+        set_fat_entry(&mut disk, 7, cluster::END_OF_CHAIN);
+
+        let mut cursor = Cursor::new(disk);
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+        let names = |path: &str, cursor: &mut Cursor<Vec<u8>>| -> Vec<String> {
+            territory.list_directory(cursor, path).unwrap().into_iter().map(|o| o.name).collect()
+        };
+
+        assert_eq!(names("/", &mut cursor), ["Flat", "Chained", "late.txt"]);
+        assert_eq!(names("/Flat", &mut cursor), ["a.txt"]);
+        assert_eq!(names("/Chained", &mut cursor), ["b.txt"]);
+
+        // Pages follow the same chains, resuming at slot cursors
+        for limit in [1, 2, 10] {
+            assert_eq!(paged_names(&territory, &mut cursor, "/", limit), ["Flat", "Chained", "late.txt"]);
+            assert_eq!(paged_names(&territory, &mut cursor, "/Flat", limit), ["a.txt"]);
+            assert_eq!(paged_names(&territory, &mut cursor, "/Chained", limit), ["b.txt"]);
+        }
+        let first = territory.list_directory_page(&mut cursor, "/", 0, 2).unwrap();
+        assert_eq!(first.next, Some(per_cluster as u64));
+        let resumed = territory.list_directory_page(&mut cursor, "/", first.next.unwrap(), 2).unwrap();
+        assert_eq!(resumed.occupants[0].path.as_deref(), Some("/late.txt"));
+        assert_eq!(resumed.next, None);
+
+        let zero = territory.list_directory_page(&mut cursor, "/", first.next.unwrap(), 0).unwrap();
+        assert!(zero.occupants.is_empty());
+        assert_eq!(zero.next, first.next);
+    }
+
+    #[test]
+    fn test_two_cluster_root_directory() {
+        let per_cluster = TEST_CLUSTER_SIZE / 32;
+
+        // A deleted entry set, then unused slots up to one slot before the
+        // end of cluster 2, so "straddle.txt" spans both clusters
+        let mut entries: Vec<[u8; 32]> = file_entry_set("gone.txt", 0x20, 0, 0)
+            .into_iter()
+            .map(|mut entry| {
+                entry[0] &= 0x7F;
+                entry
+            })
+            .collect();
+        entries.extend(file_entry_set("first.txt", 0x20, 0, 0));
+        entries.resize(per_cluster - 1, [0x05; 32]);
+        entries.extend(file_entry_set("straddle.txt", 0x20, 0, 0));
+        entries.extend(file_entry_set("second.txt", 0x20, 0, 0));
+
+        let mut disk = build_exfat_image(&entries[..per_cluster]);
+        write_cluster_entries(&mut disk, 9, &entries[per_cluster..]);
+
+        // The chain loops back from cluster 9 to 2; the end-of-directory
+        // entry in cluster 9 stops enumeration before it comes round again
+        set_fat_entry(&mut disk, 2, 9);
+        set_fat_entry(&mut disk, 9, 2);
+
+        let mut cursor = Cursor::new(disk);
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+        let names: Vec<String> = territory
+            .read_root_directory(&mut cursor)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+        assert_eq!(names, ["first.txt", "straddle.txt", "second.txt"]);
+
+        // A page boundary before the set that straddles the clusters
+        assert_eq!(paged_names(&territory, &mut cursor, "/", 1), names);
+        let first = territory.list_directory_page(&mut cursor, "/", 0, 1).unwrap();
+        assert_eq!(first.next, Some(per_cluster as u64 - 1));
+    }
+
+    #[test]
+    fn test_read_file_range() {
+        let data: Vec<u8> = (0..1500u32).map(|i| (i % 251) as u8).collect();
+        let mut entries = file_entry_set("chained.bin", 0x20, 3, data.len() as u64);
+        entries[1][1] = 0x01; // Allocation possible, FAT chain valid
+        let mut disk = build_exfat_image(&entries);
+
+        // Clusters 3 -> 6 -> 4
+        for (i, cluster) in [3usize, 6, 4].into_iter().enumerate() {
+            let offset = TEST_HEAP_OFFSET + (cluster - 2) * TEST_CLUSTER_SIZE;
+            let chunk = &data[i * TEST_CLUSTER_SIZE..data.len().min((i + 1) * TEST_CLUSTER_SIZE)];
+            disk[offset..offset + chunk.len()].copy_from_slice(chunk);
+        }
+        set_fat_entry(&mut disk, 3, 6);
+        set_fat_entry(&mut disk, 6, 4);
+        set_fat_entry(&mut disk, 4, cluster::END_OF_CHAIN);
+
+        let mut cursor = Cursor::new(disk);
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+        let range = territory.read_file_range(&mut cursor, "/chained.bin", 500, 600).unwrap();
+        assert_eq!(range, &data[500..1100]);
+        assert!(territory.read_file_range(&mut cursor, "/chained.bin", 1000, 501).is_err());
+    }
+
+    #[test]
+    fn test_read_file_to_writer() {
+        let data: Vec<u8> = (0..1500u32).map(|i| (i % 251) as u8).collect();
+        let mut entries = file_entry_set("chained.bin", 0x20, 3, data.len() as u64);
+        entries[1][1] = 0x01; // Allocation possible, FAT chain valid
+        entries.extend(file_entry_set("flat.bin", 0x20, 8, data.len() as u64));
+        let mut short = file_entry_set("short.bin", 0x20, 12, 2 * TEST_CLUSTER_SIZE as u64);
+        short[1][1] = 0x01;
+        entries.extend(short);
+        let mut disk = build_exfat_image(&entries);
+
+        // chained.bin: clusters 3 -> 6 -> 4, flat.bin: contiguous from cluster 8
+        for (i, cluster) in [3usize, 6, 4].into_iter().enumerate() {
+            let offset = TEST_HEAP_OFFSET + (cluster - 2) * TEST_CLUSTER_SIZE;
+            let chunk = &data[i * TEST_CLUSTER_SIZE..data.len().min((i + 1) * TEST_CLUSTER_SIZE)];
+            disk[offset..offset + chunk.len()].copy_from_slice(chunk);
+        }
+        set_fat_entry(&mut disk, 3, 6);
+        set_fat_entry(&mut disk, 6, 4);
+        set_fat_entry(&mut disk, 4, cluster::END_OF_CHAIN);
+        let flat = TEST_HEAP_OFFSET + 6 * TEST_CLUSTER_SIZE;
+        disk[flat..flat + data.len()].copy_from_slice(&data);
+        // short.bin claims two clusters but its chain holds one
+        set_fat_entry(&mut disk, 12, cluster::END_OF_CHAIN);
+
+        let mut cursor = Cursor::new(disk);
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+        for path in ["/chained.bin", "/flat.bin"] {
+            let entry = territory.find_entry_by_path(&mut cursor, path).unwrap();
+            let mut out = Vec::new();
+            assert_eq!(territory.read_file_to(&mut cursor, &entry, &mut out).unwrap(), data.len() as u64);
+            assert_eq!(out, data, "{}", path);
+        }
+
+        let entry = territory.find_entry_by_path(&mut cursor, "/short.bin").unwrap();
+        let err = territory.read_file_to(&mut cursor, &entry, &mut Vec::new()).unwrap_err();
+        assert!(matches!(err, totalimage_core::Error::Truncated(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_unicode_normalized_lookup() {
+        // Stored precomposed (NFC), looked up with a combining accent (NFD)
+        let nfc = "caf\u{e9}.txt";
+        let nfd = "/cafe\u{301}.txt";
+        let disk = build_exfat_image(&file_entry_set(nfc, 0x20, 3, 5));
+        let mut cursor = Cursor::new(disk);
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+
+        assert!(matches!(
+            territory.file_metadata_with(&mut cursor, nfd, false),
+            Err(totalimage_core::Error::NotFound(_))
+        ));
+        let info = territory.file_metadata_with(&mut cursor, nfd, true).unwrap();
+        assert_eq!(info.name, nfc);
+        assert_eq!(info.size, 5);
+        assert_eq!(territory.resolve_path(&mut cursor, nfd).unwrap(), format!("/{}", nfc));
+
+        let options = ListOptions {
+            pattern: Some("cafe\u{301}*".to_string()),
+            normalize_unicode: true,
+            ..Default::default()
+        };
+        let listing = territory.list_directory_with(&mut cursor, "/", &options).unwrap();
+        assert_eq!(listing.len(), 1);
+        assert_eq!(listing[0].name, nfc);
+    }
+
+    #[test]
+    fn test_fs_features() {
+        let territory = ExfatTerritory::parse(&mut Cursor::new(build_exfat_image(&[]))).unwrap();
+        let features = territory.fs_features();
+
+        assert!(!features.writable);
+        assert!(features.timestamps && features.long_names);
+        assert!(!features.short_names && !features.alternate_data_streams && !features.compression);
+        assert_eq!(features.max_file_size, u64::MAX);
+    }
+
+    #[test]
+    fn test_extract_file() {
+        let data: Vec<u8> = (0..1500u32).map(|i| (i % 251) as u8).collect();
+        let mut entries = file_entry_set("chained.bin", 0x20, 3, data.len() as u64);
+        entries[1][1] = 0x01; // Allocation possible, FAT chain valid
+        entries.extend(file_entry_set("flat.txt", 0x20, 8, 5));
+        entries.extend(file_entry_set("huge.bin", 0x20, 9, 1 << 40));
+        entries.extend(file_entry_set("Docs", 0x10, 10, TEST_CLUSTER_SIZE as u64));
+        let mut disk = build_exfat_image(&entries);
+
+        // chained.bin: clusters 3 -> 6 -> 4, flat.txt: contiguous in cluster 8
+        for (i, cluster) in [3usize, 6, 4].into_iter().enumerate() {
+            let offset = TEST_HEAP_OFFSET + (cluster - 2) * TEST_CLUSTER_SIZE;
+            let chunk = &data[i * TEST_CLUSTER_SIZE..data.len().min((i + 1) * TEST_CLUSTER_SIZE)];
+            disk[offset..offset + chunk.len()].copy_from_slice(chunk);
+        }
+        set_fat_entry(&mut disk, 3, 6);
+        set_fat_entry(&mut disk, 6, 4);
+        set_fat_entry(&mut disk, 4, cluster::END_OF_CHAIN);
+        let flat = TEST_HEAP_OFFSET + 6 * TEST_CLUSTER_SIZE;
+        disk[flat..flat + 5].copy_from_slice(b"hello");
+
+        let mut territory = ExfatTerritory::from_reader(Cursor::new(disk.clone())).unwrap();
+        assert_eq!(territory.extract_file("/chained.bin").unwrap(), data);
+        assert_eq!(territory.extract_file("FLAT.TXT").unwrap(), b"hello");
+
+        let err = territory.extract_file("/huge.bin").unwrap_err();
+        assert!(err.to_string().contains("extraction limit"), "{}", err);
+        assert!(territory.extract_file("/Docs").is_err());
+        assert!(matches!(
+            territory.extract_file("/missing.txt"),
+            Err(totalimage_core::Error::NotFound(_))
+        ));
+
+        // Without an owned stream there is nothing to read from
+        let mut territory = ExfatTerritory::parse(&mut Cursor::new(disk)).unwrap();
+        assert!(matches!(
+            territory.extract_file("/flat.txt"),
+            Err(totalimage_core::Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_file_metadata() {
+        use chrono::{TimeZone, Utc};
+
+        let mut disk = build_exfat_image(&file_entry_set("Docs", 0x10, 3, TEST_CLUSTER_SIZE as u64));
+        set_fat_entry(&mut disk, 3, cluster::END_OF_CHAIN);
+
+        // Docs/report.txt, modified 2024-01-02 03:04:06 UTC
+        let mut report = file_entry_set("report.txt", 0x21, 5, 1234);
+        let modified = (44u32 << 25) | (1 << 21) | (2 << 16) | (3 << 11) | (4 << 5) | 3;
+        report[0][12..16].copy_from_slice(&modified.to_le_bytes());
+        report[0][23] = 0x80;
+        let docs_offset = TEST_HEAP_OFFSET + TEST_CLUSTER_SIZE;
+        for (i, entry) in report.iter().enumerate() {
+            disk[docs_offset + i * 32..docs_offset + (i + 1) * 32].copy_from_slice(entry);
+        }
+
+        let mut cursor = Cursor::new(disk);
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+
+        let metadata = territory.file_metadata(&mut cursor, "/docs/REPORT.TXT").unwrap();
+        assert_eq!(metadata.name, "report.txt");
+        assert_eq!(metadata.size, 1234);
+        assert_eq!(metadata.attributes, 0x21);
+        assert!(!metadata.is_directory);
+        assert_eq!(metadata.modified, Some(Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 6).unwrap()));
+
+        assert!(territory.file_metadata(&mut cursor, "Docs").unwrap().is_directory);
+
+        let err = territory.file_metadata(&mut cursor, "/Docs/missing.txt").unwrap_err();
+        assert!(matches!(err, totalimage_core::Error::NotFound(_)));
+    }
+
+    #[test]
+    fn test_name_hash_verification() {
+        let mut entries = file_entry_set("Report.txt", 0x20, 0, 0);
+        entries.extend(file_entry_set("Notes.txt", 0x20, 0, 0));
+        // Corrupt the stored hash of Notes.txt
+        entries[4][4] ^= 0xFF;
+
+        let mut cursor = Cursor::new(build_exfat_image(&entries));
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+        let root = territory.read_root_directory(&mut cursor).unwrap();
+
+        let upcased: Vec<u16> = "REPORT.TXT".encode_utf16().collect();
+        assert_eq!(root[0].name_hash, StreamExtensionEntry::name_hash(&upcased));
+        assert!(root[0].name_hash_valid);
+        assert!(!root[1].name_hash_valid);
+
+        // Both are still found by path, the corrupt one by full comparison
+        assert_eq!(territory.find_entry_by_path(&mut cursor, "report.TXT").unwrap().name, "Report.txt");
+        assert_eq!(territory.find_entry_by_path(&mut cursor, "/NOTES.txt").unwrap().name, "Notes.txt");
+    }
+
+    #[test]
+    fn test_space_summary_from_bitmap() {
+        let mut bitmap_entry = [0u8; 32];
+        bitmap_entry[0] = 0x81;
+        bitmap_entry[20..24].copy_from_slice(&3u32.to_le_bytes());
+        bitmap_entry[24..32].copy_from_slice(&8u64.to_le_bytes()); // 64 clusters
+
+        let mut entries = vec![bitmap_entry];
+        entries.extend(file_entry_set("data.bin", 0x20, 4, 1000));
+        let mut disk = build_exfat_image(&entries);
+
+        // Root directory, bitmap and two file clusters in use
+        let bitmap_offset = TEST_HEAP_OFFSET + TEST_CLUSTER_SIZE;
+        disk[bitmap_offset] = 0b0000_1111;
+
+        let mut cursor = Cursor::new(disk);
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+        assert_eq!(territory.free_cluster_count(&mut cursor).unwrap(), 60);
+
+        let summary = territory.space_summary(&mut cursor).unwrap();
+        assert_eq!(summary.block_count, 64);
+        assert_eq!(summary.free_blocks, 60);
+        assert_eq!(summary.free, 60 * 512);
+        assert_eq!(summary.used + summary.free, summary.total);
+    }
+
+    #[test]
+    fn test_space_summary_without_bitmap() {
+        let mut cursor = Cursor::new(build_exfat_image(&[]));
+        let territory = ExfatTerritory::parse(&mut cursor).unwrap();
+        assert!(territory.space_summary(&mut cursor).is_err());
+    }
 
     #[test]
     fn test_cluster_offset_calculation() {
@@ -431,6 +1502,8 @@ mod tests {
             cluster_count: 10000,
             root_dir_cluster: 4,
             volume_length: 512 * 1000000,
+            upcase_table: None,
+            reader: None,
         };
 
         // Cluster 2 should be at heap offset
@@ -502,6 +1575,8 @@ mod tests {
             cluster_count: 10000,
             root_dir_cluster: 4,
             volume_length: 512 * 1000000,
+            upcase_table: None,
+            reader: None,
         };
 
         assert!(territory.identify().contains("exFAT"));
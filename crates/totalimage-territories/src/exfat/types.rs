@@ -2,8 +2,38 @@
 //!
 //! This module contains the core data structures for parsing exFAT filesystems.
 
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use totalimage_core::Result;
 
+/// Convert an exFAT timestamp to chrono DateTime
+///
+/// `increment_10ms` is the 10ms field (0-199) refining the two-second
+/// resolution of the DOS-style timestamp. `utc_offset` is the UTC offset
+/// byte: when its high (OffsetValid) bit is set, the low 7 bits are a signed
+/// count of 15-minute intervals and the timestamp is local time at that
+/// offset. Without the valid bit the timestamp is taken as UTC.
+pub fn exfat_time_to_datetime(timestamp: u32, increment_10ms: u8, utc_offset: u8) -> Option<DateTime<Utc>> {
+    if timestamp == 0 {
+        return None;
+    }
+
+    let (year, month, day, hour, minute, second) = FileDirectoryEntry::decode_timestamp(timestamp);
+    let increment_ms = increment_10ms.min(199) as u32 * 10;
+
+    let local = NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)?
+        .and_hms_milli_opt(hour as u32, minute as u32, second as u32, 0)?
+        + Duration::milliseconds(increment_ms as i64);
+
+    let offset_minutes = if utc_offset & 0x80 != 0 {
+        // Sign-extend the 7-bit two's complement value
+        (((utc_offset << 1) as i8) >> 1) as i64 * 15
+    } else {
+        0
+    };
+
+    Some((local - Duration::minutes(offset_minutes)).and_utc())
+}
+
 /// exFAT Boot Sector (512 bytes minimum)
 #[derive(Debug, Clone)]
 pub struct ExfatBootSector {
@@ -53,7 +83,7 @@ impl ExfatBootSector {
     /// Parse boot sector from bytes
     pub fn parse(bytes: &[u8]) -> Result<Self> {
         if bytes.len() < Self::SIZE {
-            return Err(totalimage_core::Error::invalid_territory(
+            return Err(totalimage_core::Error::truncated(
                 "exFAT boot sector too small",
             ));
         }
@@ -67,7 +97,7 @@ impl ExfatBootSector {
         fs_name.copy_from_slice(&bytes[3..11]);
 
         if &fs_name != Self::FS_NAME {
-            return Err(totalimage_core::Error::invalid_territory(format!(
+            return Err(totalimage_core::Error::unrecognized_signature(format!(
                 "Invalid exFAT signature: expected 'EXFAT   ', got '{}'",
                 String::from_utf8_lossy(&fs_name)
             )));
@@ -106,7 +136,7 @@ impl ExfatBootSector {
 
         // Verify boot signature
         if bytes[510] != 0x55 || bytes[511] != 0xAA {
-            return Err(totalimage_core::Error::invalid_territory(
+            return Err(totalimage_core::Error::unrecognized_signature(
                 "Invalid exFAT boot signature",
             ));
         }
@@ -162,7 +192,7 @@ impl ExfatBootSector {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum EntryType {
-    /// End of directory marker (unused entry)
+    /// End of directory marker; no entries follow
     EndOfDirectory = 0x00,
     /// Allocation bitmap entry
     AllocationBitmap = 0x81,
@@ -178,7 +208,7 @@ pub enum EntryType {
     StreamExtension = 0xC0,
     /// File name extension entry
     FileName = 0xC1,
-    /// Deleted file entry
+    /// Deleted file entry, or any other entry with its InUse bit clear
     DeletedFile = 0x05,
     /// Unknown entry type
     Unknown = 0xFF,
@@ -197,7 +227,7 @@ impl EntryType {
             0xC0 => EntryType::StreamExtension,
             0xC1 => EntryType::FileName,
             0x05 => EntryType::DeletedFile,
-            _ if (byte & 0x80) == 0 => EntryType::EndOfDirectory, // Unused
+            _ if (byte & 0x80) == 0 => EntryType::DeletedFile, // Unused
             _ => EntryType::Unknown,
         }
     }
@@ -385,6 +415,17 @@ impl StreamExtensionEntry {
         })
     }
 
+    /// Compute the name hash of an up-cased file name
+    ///
+    /// Each UTF-16 code unit contributes its low byte, then its high byte,
+    /// to a rotate-right checksum as defined by the exFAT specification.
+    pub fn name_hash(upcased_name: &[u16]) -> u16 {
+        upcased_name
+            .iter()
+            .flat_map(|unit| unit.to_le_bytes())
+            .fold(0u16, |hash, b| hash.rotate_right(1).wrapping_add(b as u16))
+    }
+
     /// Check if allocation is contiguous (no fragmentation)
     pub fn is_contiguous(&self) -> bool {
         (self.general_flags & 0x02) != 0
@@ -494,6 +535,150 @@ impl VolumeLabelEntry {
     }
 }
 
+/// exFAT Allocation Bitmap Entry (32 bytes)
+#[derive(Debug, Clone)]
+pub struct AllocationBitmapEntry {
+    /// Entry type (0x81)
+    pub entry_type: u8,
+    /// Bitmap flags; bit 0 selects the first or second FAT/bitmap (TexFAT)
+    pub bitmap_flags: u8,
+    /// First cluster of the bitmap
+    pub first_cluster: u32,
+    /// Bitmap length in bytes
+    pub data_length: u64,
+}
+
+impl AllocationBitmapEntry {
+    /// Entry size
+    pub const SIZE: usize = 32;
+
+    /// Parse from bytes
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(totalimage_core::Error::invalid_territory(
+                "Allocation bitmap entry too small",
+            ));
+        }
+
+        Ok(Self {
+            entry_type: bytes[0],
+            bitmap_flags: bytes[1],
+            first_cluster: u32::from_le_bytes([bytes[20], bytes[21], bytes[22], bytes[23]]),
+            data_length: u64::from_le_bytes([
+                bytes[24], bytes[25], bytes[26], bytes[27],
+                bytes[28], bytes[29], bytes[30], bytes[31],
+            ]),
+        })
+    }
+
+    /// Is this the bitmap for the second FAT?
+    pub fn is_second(&self) -> bool {
+        self.bitmap_flags & 0x01 != 0
+    }
+}
+
+/// exFAT Up-case Table Entry (32 bytes)
+#[derive(Debug, Clone)]
+pub struct UpcaseTableEntry {
+    /// Entry type (0x82)
+    pub entry_type: u8,
+    /// Checksum of the table contents
+    pub table_checksum: u32,
+    /// First cluster of the table
+    pub first_cluster: u32,
+    /// Table length in bytes
+    pub data_length: u64,
+}
+
+impl UpcaseTableEntry {
+    /// Entry size
+    pub const SIZE: usize = 32;
+
+    /// Parse from bytes
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::SIZE {
+            return Err(totalimage_core::Error::invalid_territory(
+                "Up-case table entry too small",
+            ));
+        }
+
+        Ok(Self {
+            entry_type: bytes[0],
+            table_checksum: u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+            first_cluster: u32::from_le_bytes([bytes[20], bytes[21], bytes[22], bytes[23]]),
+            data_length: u64::from_le_bytes([
+                bytes[24], bytes[25], bytes[26], bytes[27],
+                bytes[28], bytes[29], bytes[30], bytes[31],
+            ]),
+        })
+    }
+}
+
+/// exFAT Up-case Table
+///
+/// Maps every UTF-16 code unit to its upper-case form, as defined by the
+/// volume. exFAT compares file names case-insensitively through this table
+/// rather than through generic Unicode case folding.
+#[derive(Clone)]
+pub struct UpcaseTable {
+    map: Vec<u16>,
+}
+
+impl std::fmt::Debug for UpcaseTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // The 64K-entry map is too large to be useful in debug output
+        f.debug_struct("UpcaseTable").finish_non_exhaustive()
+    }
+}
+
+impl UpcaseTable {
+    /// Maximum on-disk table size accepted (an uncompressed table is 128 KiB)
+    pub const MAX_SIZE: u64 = 128 * 1024;
+
+    /// Decompress a table from its on-disk contents
+    ///
+    /// A 0xFFFF code unit that isn't an identity mapping starts a run: the
+    /// following unit gives the number of code points that map to themselves.
+    /// Code points not covered by the table map to themselves.
+    pub fn decompress(data: &[u8]) -> Self {
+        let mut map: Vec<u16> = (0..=u16::MAX).collect();
+        let mut index = 0usize;
+        let mut skip = false;
+
+        for chunk in data.chunks_exact(2) {
+            if index >= map.len() {
+                break;
+            }
+
+            let unit = u16::from_le_bytes([chunk[0], chunk[1]]);
+            if skip {
+                index += unit as usize;
+                skip = false;
+            } else if unit as usize == index {
+                index += 1;
+            } else if unit == 0xFFFF {
+                skip = true;
+            } else {
+                map[index] = unit;
+                index += 1;
+            }
+        }
+
+        Self { map }
+    }
+
+    /// Compute the table checksum as defined by the exFAT specification
+    pub fn checksum(data: &[u8]) -> u32 {
+        data.iter()
+            .fold(0u32, |sum, &b| sum.rotate_right(1).wrapping_add(b as u32))
+    }
+
+    /// Up-case a single UTF-16 code unit
+    pub fn upcase(&self, unit: u16) -> u16 {
+        self.map[unit as usize]
+    }
+}
+
 /// Complete exFAT directory entry (file with name)
 #[derive(Debug, Clone)]
 pub struct ExfatDirectoryEntry {
@@ -511,11 +696,40 @@ pub struct ExfatDirectoryEntry {
     pub modified: u32,
     /// Accessed timestamp
     pub accessed: u32,
+    /// Creation time 10ms increment
+    pub created_10ms: u8,
+    /// Modify time 10ms increment
+    pub modified_10ms: u8,
+    /// Create UTC offset
+    pub created_utc_offset: u8,
+    /// Modify UTC offset
+    pub modified_utc_offset: u8,
+    /// Access UTC offset
+    pub accessed_utc_offset: u8,
     /// Is contiguous allocation
     pub is_contiguous: bool,
+    /// Name hash recorded in the Stream Extension entry
+    pub name_hash: u16,
+    /// Whether `name_hash` matches the hash of the decoded name
+    pub name_hash_valid: bool,
 }
 
 impl ExfatDirectoryEntry {
+    /// Get the creation time in UTC
+    pub fn created_time(&self) -> Option<DateTime<Utc>> {
+        exfat_time_to_datetime(self.created, self.created_10ms, self.created_utc_offset)
+    }
+
+    /// Get the last modification time in UTC
+    pub fn modified_time(&self) -> Option<DateTime<Utc>> {
+        exfat_time_to_datetime(self.modified, self.modified_10ms, self.modified_utc_offset)
+    }
+
+    /// Get the last access time in UTC (access times have no 10ms field)
+    pub fn accessed_time(&self) -> Option<DateTime<Utc>> {
+        exfat_time_to_datetime(self.accessed, 0, self.accessed_utc_offset)
+    }
+
     /// Check if entry is a directory
     pub fn is_directory(&self) -> bool {
         self.attributes.is_directory()
@@ -568,6 +782,8 @@ mod tests {
         assert_eq!(EntryType::from_byte(0xC0), EntryType::StreamExtension);
         assert_eq!(EntryType::from_byte(0xC1), EntryType::FileName);
         assert_eq!(EntryType::from_byte(0x83), EntryType::VolumeLabel);
+        // A deleted stream extension doesn't end the directory
+        assert_eq!(EntryType::from_byte(0x40), EntryType::DeletedFile);
     }
 
     #[test]
@@ -594,4 +810,68 @@ mod tests {
         assert_eq!(minute, 30);
         assert_eq!(second, 0);
     }
+
+    #[test]
+    fn test_upcase_table_decompress() {
+        // Identity up to 'a', then a-z upper-cased, then identity up to 0xE9
+        let mut units: Vec<u16> = vec![0xFFFF, 0x61];
+        units.extend(0x41..=0x5A);
+        units.extend([0xFFFF, 0xE9 - 0x7B, 0xC9]);
+        let data: Vec<u8> = units.iter().flat_map(|u| u.to_le_bytes()).collect();
+
+        let table = UpcaseTable::decompress(&data);
+        assert_eq!(table.upcase(b'a' as u16), b'A' as u16);
+        assert_eq!(table.upcase(b'z' as u16), b'Z' as u16);
+        assert_eq!(table.upcase(b'A' as u16), b'A' as u16);
+        assert_eq!(table.upcase(0xE9), 0xC9);
+        assert_eq!(table.upcase(0xE8), 0xE8);
+        assert_eq!(table.upcase(0xFFFF), 0xFFFF);
+    }
+
+    #[test]
+    fn test_name_hash() {
+        // 'A' = 0x0041: rotate(0) + 0x41 = 0x0041, rotate(0x0041) + 0x00 = 0x8020
+        assert_eq!(StreamExtensionEntry::name_hash(&[0x41]), 0x8020);
+        assert_eq!(StreamExtensionEntry::name_hash(&[]), 0);
+        assert_ne!(
+            StreamExtensionEntry::name_hash(&"REPORT.TXT".encode_utf16().collect::<Vec<_>>()),
+            StreamExtensionEntry::name_hash(&"REPORT.TXU".encode_utf16().collect::<Vec<_>>())
+        );
+    }
+
+    #[test]
+    fn test_exfat_time_to_datetime() {
+        use chrono::{Datelike, Timelike};
+
+        // 2023-06-15 14:30:58 local, +1.50s from the 10ms field, UTC+02:00
+        let timestamp = (43 << 25) | (6 << 21) | (15 << 16) | (14 << 11) | (30 << 5) | 29;
+        let dt = exfat_time_to_datetime(timestamp, 150, 0x80 | 8).unwrap();
+        assert_eq!((dt.year(), dt.month(), dt.day()), (2023, 6, 15));
+        assert_eq!((dt.hour(), dt.minute(), dt.second()), (12, 30, 59));
+        assert_eq!(dt.timestamp_subsec_millis(), 500);
+
+        // Negative offset (UTC-05:00 = -20 quarter hours = 0x6C in 7 bits)
+        let dt = exfat_time_to_datetime(timestamp, 0, 0x80 | 0x6C).unwrap();
+        assert_eq!(dt.hour(), 19);
+
+        // Offset ignored without the valid bit
+        let dt = exfat_time_to_datetime(timestamp, 0, 8).unwrap();
+        assert_eq!(dt.hour(), 14);
+
+        assert!(exfat_time_to_datetime(0, 0, 0).is_none());
+    }
+
+    #[test]
+    fn test_boot_sector_failure_variants() {
+        assert!(matches!(
+            ExfatBootSector::parse(&[0u8; 100]),
+            Err(totalimage_core::Error::Truncated(_))
+        ));
+
+        let mut ntfs = [0u8; 512];
+        ntfs[3..11].copy_from_slice(b"NTFS    ");
+        ntfs[510..].copy_from_slice(&[0x55, 0xAA]);
+        let err = ExfatBootSector::parse(&ntfs).unwrap_err();
+        assert!(err.is_unrecognized(), "{:?}", err);
+    }
 }
@@ -0,0 +1,68 @@
+//! Building a file's on-disk extent list for [`Territory::data_runs`]
+//!
+//! [`Territory::data_runs`]: totalimage_core::Territory::data_runs
+
+/// Collects the byte extents of a file in file order
+///
+/// Allocation units are trimmed to the file's size and merged with the
+/// previous extent when physically adjacent.
+pub(crate) struct Extents {
+    runs: Vec<(u64, u64)>,
+    remaining: u64,
+}
+
+impl Extents {
+    /// Start collecting extents for `size` bytes of data
+    pub(crate) fn new(size: u64) -> Self {
+        Self {
+            runs: Vec::new(),
+            remaining: size,
+        }
+    }
+
+    /// Add the next allocated range
+    ///
+    /// Returns false once the file's size is covered.
+    pub(crate) fn push(&mut self, offset: u64, length: u64) -> bool {
+        let length = length.min(self.remaining);
+        if length > 0 {
+            match self.runs.last_mut() {
+                Some((start, len)) if *start + *len == offset => *len += length,
+                _ => self.runs.push((offset, length)),
+            }
+            self.remaining -= length;
+        }
+        self.remaining > 0
+    }
+
+    /// Account for a sparse range, which occupies no space on disk
+    ///
+    /// Returns false once the file's size is covered.
+    pub(crate) fn skip(&mut self, length: u64) -> bool {
+        self.remaining = self.remaining.saturating_sub(length);
+        self.remaining > 0
+    }
+
+    /// Get the collected extents
+    pub(crate) fn into_runs(self) -> Vec<(u64, u64)> {
+        self.runs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extents_merge_and_trim() {
+        let mut extents = Extents::new(2500);
+        assert!(extents.push(1024, 512));
+        assert!(extents.push(1536, 512));
+        assert!(extents.skip(512));
+        assert!(extents.push(4096, 512));
+        assert!(!extents.push(8192, 512));
+        assert!(!extents.push(9000, 512));
+
+        assert_eq!(extents.into_runs(), vec![(1024, 1024), (4096, 512), (8192, 452)]);
+    }
+}
@@ -0,0 +1,350 @@
+//! Read-only consistency check of FAT cluster chains
+//!
+//! [`FatTerritory::check_consistency`] walks the directory tree from the
+//! root, following every file's and directory's cluster chain through the
+//! FAT, and collects the problems a `fsck` would complain about.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use totalimage_core::ReadSeek;
+
+use super::types::{DirectoryEntry, FatType};
+use super::FatTerritory;
+
+/// A problem found while checking a FAT volume
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConsistencyProblem {
+    /// A cluster belongs to the chains of two different files
+    CrossLinked {
+        /// Path whose chain reached the shared cluster second
+        path: String,
+        /// Path that already owned the cluster
+        other_path: String,
+        /// First shared cluster
+        cluster: u32,
+    },
+    /// A chain leads back to a cluster it already visited
+    ChainLoop {
+        /// Path whose chain loops
+        path: String,
+        /// Cluster revisited
+        cluster: u32,
+    },
+    /// A chain refers to a cluster outside the data area, or to a free or
+    /// bad cluster
+    ClusterOutOfRange {
+        /// Path whose chain is broken
+        path: String,
+        /// Invalid cluster value found in the chain
+        cluster: u32,
+    },
+    /// A file's chain is not the length its size calls for
+    SizeMismatch {
+        /// Path of the file
+        path: String,
+        /// Size recorded in the directory entry
+        file_size: u32,
+        /// Clusters needed to hold `file_size` bytes
+        expected_clusters: usize,
+        /// Clusters in the chain
+        chain_clusters: usize,
+    },
+    /// A directory could not be read, so its contents were not checked
+    UnreadableDirectory {
+        /// Path of the directory
+        path: String,
+        /// Why reading failed
+        reason: String,
+    },
+}
+
+impl fmt::Display for ConsistencyProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CrossLinked { path, other_path, cluster } => {
+                write!(f, "{} is cross-linked with {} at cluster {}", path, other_path, cluster)
+            }
+            Self::ChainLoop { path, cluster } => {
+                write!(f, "Cluster chain of {} loops back to cluster {}", path, cluster)
+            }
+            Self::ClusterOutOfRange { path, cluster } => {
+                write!(f, "Cluster chain of {} refers to invalid cluster {:#x}", path, cluster)
+            }
+            Self::SizeMismatch { path, file_size, expected_clusters, chain_clusters } => write!(
+                f,
+                "{} is {} bytes ({} clusters) but its chain has {} clusters",
+                path, file_size, expected_clusters, chain_clusters
+            ),
+            Self::UnreadableDirectory { path, reason } => {
+                write!(f, "Directory {} could not be read: {}", path, reason)
+            }
+        }
+    }
+}
+
+/// Result of [`FatTerritory::check_consistency`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsistencyReport {
+    /// Files and directories whose chains were checked
+    pub entries_checked: usize,
+    /// Clusters reached through some chain
+    pub clusters_in_use: usize,
+    /// Problems found, in the order they were encountered
+    pub problems: Vec<ConsistencyProblem>,
+}
+
+impl ConsistencyReport {
+    /// Check whether no problems were found
+    pub fn is_consistent(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// State carried through the directory walk
+struct Checker<'a> {
+    territory: &'a FatTerritory,
+    cluster_size: u64,
+    /// One past the highest valid cluster number
+    cluster_limit: u32,
+    /// Path of the file or directory owning each visited cluster
+    owners: HashMap<u32, String>,
+    report: ConsistencyReport,
+}
+
+impl Checker<'_> {
+    /// Follow a chain, recording ownership and problems
+    ///
+    /// Returns the number of clusters walked and whether the whole chain
+    /// belongs to `path` alone and ends properly.
+    fn walk_chain(&mut self, path: &str, start_cluster: u32) -> (usize, bool) {
+        let mut visited = HashSet::new();
+        let mut cluster = start_cluster;
+
+        loop {
+            if cluster < 2 || cluster >= self.cluster_limit {
+                self.report.problems.push(ConsistencyProblem::ClusterOutOfRange {
+                    path: path.to_string(),
+                    cluster,
+                });
+                return (visited.len(), false);
+            }
+            if !visited.insert(cluster) {
+                self.report.problems.push(ConsistencyProblem::ChainLoop {
+                    path: path.to_string(),
+                    cluster,
+                });
+                return (visited.len() - 1, false);
+            }
+            if let Some(owner) = self.owners.get(&cluster) {
+                // The rest of the chain was already walked for its owner
+                self.report.problems.push(ConsistencyProblem::CrossLinked {
+                    path: path.to_string(),
+                    other_path: owner.clone(),
+                    cluster,
+                });
+                return (visited.len(), false);
+            }
+            self.owners.insert(cluster, path.to_string());
+
+            match self.territory.read_fat_entry(cluster) {
+                Some(next) => cluster = next,
+                None => {
+                    let value = self.territory.raw_fat_entry(cluster).unwrap_or(0);
+                    if value < 2 {
+                        // Free or reserved cluster in the middle of a chain
+                        self.report.problems.push(ConsistencyProblem::ClusterOutOfRange {
+                            path: path.to_string(),
+                            cluster: value,
+                        });
+                        return (visited.len(), false);
+                    }
+                    return (visited.len(), true);
+                }
+            }
+        }
+    }
+
+    /// Check a directory's entries, descending into subdirectories
+    fn check_entries(&mut self, stream: &mut dyn ReadSeek, dir_path: &str, entries: Vec<DirectoryEntry>) {
+        for entry in entries {
+            let path = format!("{}/{}", dir_path.trim_end_matches('/'), entry.name);
+            self.report.entries_checked += 1;
+
+            let first_cluster = entry.first_cluster();
+            if entry.is_directory() {
+                if first_cluster == 0 {
+                    continue;
+                }
+                let (_, intact) = self.walk_chain(&path, first_cluster);
+                // Never descend into a chain shared with or looping into
+                // another directory
+                if intact {
+                    match self.territory.read_directory_from_cluster(stream, first_cluster) {
+                        Ok(children) => self.check_entries(stream, &path, children),
+                        Err(e) => self.report.problems.push(ConsistencyProblem::UnreadableDirectory {
+                            path,
+                            reason: e.to_string(),
+                        }),
+                    }
+                }
+                continue;
+            }
+
+            let expected_clusters = (entry.file_size as u64).div_ceil(self.cluster_size) as usize;
+            let (chain_clusters, intact) = if first_cluster == 0 {
+                (0, true)
+            } else {
+                self.walk_chain(&path, first_cluster)
+            };
+            if intact && chain_clusters != expected_clusters {
+                self.report.problems.push(ConsistencyProblem::SizeMismatch {
+                    path,
+                    file_size: entry.file_size,
+                    expected_clusters,
+                    chain_clusters,
+                });
+            }
+        }
+    }
+}
+
+impl FatTerritory {
+    /// Check every file's and directory's cluster chain for corruption
+    ///
+    /// Reports cross-linked clusters, looping chains, chains leading outside
+    /// the data area and files whose chain length disagrees with their size.
+    /// Nothing is modified, and problems are collected rather than returned
+    /// as errors; a directory that can't be read is reported and skipped.
+    pub fn check_consistency(&self, stream: &mut dyn ReadSeek) -> ConsistencyReport {
+        let mut checker = Checker {
+            territory: self,
+            cluster_size: self.bpb.bytes_per_cluster().unwrap_or(1).max(1) as u64,
+            cluster_limit: self.bpb.cluster_count().unwrap_or(0).saturating_add(2),
+            owners: HashMap::new(),
+            report: ConsistencyReport::default(),
+        };
+
+        if self.bpb.fat_type == FatType::Fat32 {
+            let (_, intact) = checker.walk_chain("/", self.fat32_root_cluster);
+            if !intact {
+                checker.report.clusters_in_use = checker.owners.len();
+                return checker.report;
+            }
+        }
+
+        match self.read_root_directory(stream) {
+            Ok(entries) => checker.check_entries(stream, "/", entries),
+            Err(e) => checker.report.problems.push(ConsistencyProblem::UnreadableDirectory {
+                path: "/".to_string(),
+                reason: e.to_string(),
+            }),
+        }
+
+        checker.report.clusters_in_use = checker.owners.len();
+        checker.report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fat::tests::{create_fat12_boot_sector, set_fat12_entry};
+    use std::io::Cursor;
+
+    /// Add a root directory entry to a 1.44MB FAT12 image
+    fn add_root_entry(disk: &mut [u8], slot: usize, name: &[u8; 11], attributes: u8, cluster: u16, size: u32) {
+        let offset = 512 + (2 * 9 * 512) + slot * 32;
+        disk[offset..offset + 11].copy_from_slice(name);
+        disk[offset + 11] = attributes;
+        disk[offset + 26..offset + 28].copy_from_slice(&cluster.to_le_bytes());
+        disk[offset + 28..offset + 32].copy_from_slice(&size.to_le_bytes());
+    }
+
+    fn fat12_disk() -> Vec<u8> {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+        set_fat12_entry(&mut disk, 0, 0xFF0);
+        set_fat12_entry(&mut disk, 1, 0xFFF);
+        disk
+    }
+
+    #[test]
+    fn test_consistent_volume() {
+        let mut disk = fat12_disk();
+        set_fat12_entry(&mut disk, 2, 3);
+        set_fat12_entry(&mut disk, 3, 0xFFF);
+        add_root_entry(&mut disk, 0, b"GOOD    BIN", 0x20, 2, 1000);
+        add_root_entry(&mut disk, 1, b"EMPTY   TXT", 0x20, 0, 0);
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+        let report = territory.check_consistency(&mut cursor);
+
+        assert!(report.is_consistent(), "{:?}", report.problems);
+        assert_eq!(report.entries_checked, 2);
+        assert_eq!(report.clusters_in_use, 2);
+    }
+
+    #[test]
+    fn test_detects_corrupt_chains() {
+        let mut disk = fat12_disk();
+        // A.BIN: 2 -> 3; B.BIN: 4 -> 3, cross-linked with A.BIN
+        set_fat12_entry(&mut disk, 2, 3);
+        set_fat12_entry(&mut disk, 3, 0xFFF);
+        set_fat12_entry(&mut disk, 4, 3);
+        add_root_entry(&mut disk, 0, b"A       BIN", 0x20, 2, 1024);
+        add_root_entry(&mut disk, 1, b"B       BIN", 0x20, 4, 1024);
+        // LOOP.BIN: 5 -> 6 -> 5
+        set_fat12_entry(&mut disk, 5, 6);
+        set_fat12_entry(&mut disk, 6, 5);
+        add_root_entry(&mut disk, 2, b"LOOP    BIN", 0x20, 5, 2048);
+        // FAR.BIN starts past the last cluster
+        add_root_entry(&mut disk, 3, b"FAR     BIN", 0x20, 3000, 100);
+        // SHORT.BIN claims three clusters but has one
+        set_fat12_entry(&mut disk, 7, 0xFFF);
+        add_root_entry(&mut disk, 4, b"SHORT   BIN", 0x20, 7, 1500);
+        // FREE.BIN runs into a free cluster
+        set_fat12_entry(&mut disk, 8, 9);
+        add_root_entry(&mut disk, 5, b"FREE    BIN", 0x20, 8, 1024);
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+        let report = territory.check_consistency(&mut cursor);
+
+        assert!(!report.is_consistent());
+        assert_eq!(report.entries_checked, 6);
+        assert_eq!(
+            report.problems,
+            vec![
+                ConsistencyProblem::CrossLinked {
+                    path: "/B.BIN".to_string(),
+                    other_path: "/A.BIN".to_string(),
+                    cluster: 3,
+                },
+                ConsistencyProblem::ChainLoop {
+                    path: "/LOOP.BIN".to_string(),
+                    cluster: 5,
+                },
+                ConsistencyProblem::ClusterOutOfRange {
+                    path: "/FAR.BIN".to_string(),
+                    cluster: 3000,
+                },
+                ConsistencyProblem::SizeMismatch {
+                    path: "/SHORT.BIN".to_string(),
+                    file_size: 1500,
+                    expected_clusters: 3,
+                    chain_clusters: 1,
+                },
+                ConsistencyProblem::ClusterOutOfRange {
+                    path: "/FREE.BIN".to_string(),
+                    cluster: 0,
+                },
+            ]
+        );
+        assert_eq!(
+            report.problems[0].to_string(),
+            "/B.BIN is cross-linked with /A.BIN at cluster 3"
+        );
+    }
+}
@@ -1,22 +1,75 @@
 //! FAT (File Allocation Table) file system implementation
 
+pub mod consistency;
+mod table;
 pub mod types;
+pub mod writer;
 
+use std::fmt;
 use std::io::SeekFrom;
-use totalimage_core::{DirectoryCell, Error, OccupantInfo, ReadSeek, Result, Territory};
-use types::{BiosParameterBlock, DirectoryEntry, FatType, LfnEntry};
+
+use crate::extents::Extents;
+use table::FatTable;
+use totalimage_core::{
+    validate_file_range, AttributeFlag, DirectoryCell, DirectoryPage, Error, FsFeatures, OccupantInfo, PartialListing,
+    ReadSeek, Result, SecurityLimits, SpaceSummary, Territory,
+};
+use totalimage_pipeline::SharedPipeline;
+use types::{
+    decode_volume_label, BiosParameterBlock, DeletedEntry, DirectoryEntry, FatType, FsInfo, LfnEntry,
+    RawDirSlot, Recoverability, SlotKind,
+};
+
+pub use consistency::{ConsistencyProblem, ConsistencyReport};
+pub use table::FatLoading;
+pub use writer::{generate_short_name, FatWriter};
+
+/// Directory entry attribute bits and the flags they decode to
+const ATTRIBUTE_FLAGS: [(u32, AttributeFlag); 6] = [
+    (DirectoryEntry::ATTR_READ_ONLY as u32, AttributeFlag::ReadOnly),
+    (DirectoryEntry::ATTR_HIDDEN as u32, AttributeFlag::Hidden),
+    (DirectoryEntry::ATTR_SYSTEM as u32, AttributeFlag::System),
+    (DirectoryEntry::ATTR_VOLUME_ID as u32, AttributeFlag::VolumeLabel),
+    (DirectoryEntry::ATTR_DIRECTORY as u32, AttributeFlag::Directory),
+    (DirectoryEntry::ATTR_ARCHIVE as u32, AttributeFlag::Archive),
+];
 
 /// FAT file system territory
 ///
 /// Supports FAT12, FAT16, and FAT32 file systems with directory enumeration
-/// and file data access.
-#[derive(Debug)]
+/// and file data access. Most methods take the volume's stream as an
+/// argument; a territory opened with [`from_reader`](Self::from_reader) also
+/// owns its stream, which [`Territory::extract_file`] needs, and can read the
+/// FAT on demand rather than up front (see [`FatLoading`]).
 pub struct FatTerritory {
     bpb: BiosParameterBlock,
-    fat_table: Vec<u8>,
+    fat_table: FatTable,
     identifier: String,
     /// FAT32 root directory cluster (0 for FAT12/16)
     fat32_root_cluster: u32,
+    /// FAT32 FSInfo hints, if the sector is present and valid
+    fs_info: Option<FsInfo>,
+    /// Resource limits for parsing and extraction
+    limits: SecurityLimits,
+    /// Volume label, empty if the volume has none
+    volume_label: String,
+    /// Owned stream for [`Territory::extract_file`]
+    reader: Option<Box<dyn ReadSeek>>,
+}
+
+impl fmt::Debug for FatTerritory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FatTerritory")
+            .field("bpb", &self.bpb)
+            .field("fat_table", &self.fat_table)
+            .field("identifier", &self.identifier)
+            .field("fat32_root_cluster", &self.fat32_root_cluster)
+            .field("fs_info", &self.fs_info)
+            .field("limits", &self.limits)
+            .field("volume_label", &self.volume_label)
+            .field("has_reader", &self.reader.is_some())
+            .finish()
+    }
 }
 
 impl FatTerritory {
@@ -33,13 +86,39 @@ impl FatTerritory {
     /// # Security
     /// Uses validated BPB parsing with checked arithmetic to prevent integer overflow
     pub fn parse(stream: &mut dyn ReadSeek) -> Result<Self> {
-        // Read boot sector
+        Self::parse_with_limits(stream, SecurityLimits::active())
+    }
+
+    /// Parse a FAT file system with explicit resource limits
+    ///
+    /// The limits cap the FAT table size here and file sizes on extraction.
+    pub fn parse_with_limits(stream: &mut dyn ReadSeek, limits: SecurityLimits) -> Result<Self> {
+        Self::parse_from(stream, limits, FatLoading::Eager, None)
+    }
+
+    /// Parse a FAT file system, loading the FAT through `shared` when
+    /// `loading` calls for it to be read on demand
+    fn parse_from(
+        stream: &mut dyn ReadSeek,
+        limits: SecurityLimits,
+        loading: FatLoading,
+        shared: Option<&SharedPipeline>,
+    ) -> Result<Self> {
+        // Read the minimum boot sector to learn the real sector size
         stream.seek(SeekFrom::Start(0))?;
         let mut boot_sector = vec![0u8; 512];
         stream.read_exact(&mut boot_sector)?;
 
         // Parse BPB with security validation
-        let bpb = BiosParameterBlock::from_bytes(&boot_sector)?;
+        let mut bpb = BiosParameterBlock::from_bytes(&boot_sector)?;
+
+        // Advanced Format (4Kn) media: re-read the whole sector
+        if bpb.bytes_per_sector as usize > boot_sector.len() {
+            boot_sector.resize(bpb.bytes_per_sector as usize, 0);
+            stream.seek(SeekFrom::Start(0))?;
+            stream.read_exact(&mut boot_sector)?;
+            bpb = BiosParameterBlock::from_bytes(&boot_sector)?;
+        }
 
         // Read FAT table with checked arithmetic
         use totalimage_core::validate_allocation_size;
@@ -49,15 +128,31 @@ impl FatTerritory {
             "FAT table size"
         )?;
 
-        let fat_size = validate_allocation_size(
-            fat_size_u64,
-            totalimage_core::MAX_FAT_TABLE_SIZE,
-            "FAT table"
-        )?;
+        let lazy = match loading {
+            FatLoading::Auto => fat_size_u64 > FatLoading::AUTO_LAZY_THRESHOLD,
+            FatLoading::Eager => false,
+            FatLoading::Lazy => true,
+        };
 
-        stream.seek(SeekFrom::Start(bpb.fat_offset()? as u64))?;
-        let mut fat_table = vec![0u8; fat_size];
-        stream.read_exact(&mut fat_table)?;
+        // A lazy FAT is only ever partly in memory, so the size cap doesn't apply
+        let fat_offset = bpb.fat_offset()? as u64;
+        let fat_table = match shared {
+            Some(shared) if lazy => {
+                FatTable::lazy(shared.clone(), fat_offset, fat_size_u64, bpb.bytes_per_sector as usize)?
+            }
+            _ => {
+                let fat_size = validate_allocation_size(
+                    fat_size_u64,
+                    limits.max_fat_table_size,
+                    "FAT table"
+                )?;
+
+                stream.seek(SeekFrom::Start(fat_offset))?;
+                let mut fat_table = vec![0u8; fat_size];
+                stream.read_exact(&mut fat_table)?;
+                FatTable::Loaded(fat_table)
+            }
+        };
 
         let identifier = format!("{} filesystem", bpb.fat_type);
 
@@ -69,12 +164,167 @@ impl FatTerritory {
             0
         };
 
-        Ok(Self {
+        let fs_info = if bpb.fat_type == FatType::Fat32 {
+            Self::read_fs_info(stream, &bpb)
+        } else {
+            None
+        };
+
+        let mut territory = Self {
             bpb,
             fat_table,
             identifier,
             fat32_root_cluster,
-        })
+            fs_info,
+            limits,
+            volume_label: String::new(),
+            reader: None,
+        };
+        territory.volume_label = territory.read_volume_label(stream, &boot_sector);
+
+        Ok(territory)
+    }
+
+    /// Parse a FAT file system from a stream it takes ownership of
+    ///
+    /// The territory owns `reader` afterwards, so
+    /// [`Territory::extract_file`] can read file data without being handed
+    /// a stream.
+    ///
+    /// The FAT is loaded as [`FatLoading::Auto`] decides: eagerly for most
+    /// volumes, on demand for large FAT32 ones.
+    pub fn from_reader(reader: impl ReadSeek + 'static) -> Result<Self> {
+        Self::from_reader_with(reader, SecurityLimits::active(), FatLoading::Auto)
+    }
+
+    /// Parse a FAT file system from a stream it takes ownership of, with
+    /// explicit resource limits and FAT loading
+    ///
+    /// A lazily loaded FAT reads its sectors from `reader` as entries are
+    /// looked up, so [`read_fat_entry`](Self::read_fat_entry) and
+    /// [`get_cluster_chain`](Self::get_cluster_chain) only touch the FAT
+    /// regions they need. The FAT size limit only applies to eager loading.
+    pub fn from_reader_with(
+        reader: impl ReadSeek + 'static,
+        limits: SecurityLimits,
+        loading: FatLoading,
+    ) -> Result<Self> {
+        let mut reader = SharedPipeline::new(reader);
+        let shared = reader.clone();
+        let mut territory = Self::parse_from(&mut reader, limits, loading, Some(&shared))?;
+        territory.reader = Some(Box::new(reader));
+        Ok(territory)
+    }
+
+    /// Get how the FAT was loaded: [`FatLoading::Eager`] or [`FatLoading::Lazy`]
+    pub fn fat_loading(&self) -> FatLoading {
+        if self.fat_table.is_lazy() {
+            FatLoading::Lazy
+        } else {
+            FatLoading::Eager
+        }
+    }
+
+    /// Read the volume label
+    ///
+    /// Prefers the volume label entry in the root directory, which is what
+    /// DOS and Windows update when relabelling, over the copy in the
+    /// extended BPB.
+    fn read_volume_label(&self, stream: &mut dyn ReadSeek, boot_sector: &[u8]) -> String {
+        let root_label = self
+            .raw_directory_slots(stream, "")
+            .ok()
+            .and_then(|slots| {
+                slots
+                    .into_iter()
+                    .take_while(|slot| slot.kind != SlotKind::End)
+                    .find(|slot| slot.kind == SlotKind::VolumeLabel)
+            })
+            .and_then(|slot| decode_volume_label(&slot.bytes[0..11]));
+
+        // The extended BPB (signature 0x29) holds the label at offset 43,
+        // or 71 on FAT32
+        let (signature_offset, label_offset) = if self.bpb.fat_type == FatType::Fat32 {
+            (66, 71)
+        } else {
+            (38, 43)
+        };
+        let bpb_label = || {
+            (boot_sector[signature_offset] == 0x29)
+                .then(|| decode_volume_label(&boot_sector[label_offset..label_offset + 11]))
+                .flatten()
+        };
+
+        root_label.or_else(bpb_label).unwrap_or_default()
+    }
+
+    /// Read and validate the FAT32 FSInfo sector
+    ///
+    /// The sector is advisory, so a missing or corrupt one is not an error.
+    fn read_fs_info(stream: &mut dyn ReadSeek, bpb: &BiosParameterBlock) -> Option<FsInfo> {
+        if bpb.fs_info_sector == 0 || bpb.fs_info_sector == 0xFFFF {
+            return None;
+        }
+
+        let offset = bpb.fs_info_sector as u64 * bpb.bytes_per_sector as u64;
+        let mut sector = vec![0u8; 512];
+        if stream.seek(SeekFrom::Start(offset)).is_err() || stream.read_exact(&mut sector).is_err() {
+            tracing::warn!("Could not read FAT32 FSInfo sector {}", bpb.fs_info_sector);
+            return None;
+        }
+
+        match FsInfo::from_bytes(&sector) {
+            Ok(info) => Some(info),
+            Err(e) => {
+                tracing::warn!("Ignoring FAT32 FSInfo sector: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Get the volume label, empty if the volume has none
+    pub fn volume_label(&self) -> &str {
+        &self.volume_label
+    }
+
+    /// Get the resource limits in effect
+    pub fn limits(&self) -> &SecurityLimits {
+        &self.limits
+    }
+
+    /// Replace the resource limits used for extraction
+    pub fn set_limits(&mut self, limits: SecurityLimits) {
+        self.limits = limits;
+    }
+
+    /// Get the FAT32 FSInfo hints, if present and valid
+    pub fn fs_info(&self) -> Option<&FsInfo> {
+        self.fs_info.as_ref()
+    }
+
+    /// Count free clusters
+    ///
+    /// Uses the FAT32 FSInfo cached count when it is known and plausible,
+    /// otherwise scans the whole FAT.
+    pub fn free_cluster_count(&self) -> u32 {
+        let cluster_count = self.bpb.cluster_count().unwrap_or(0);
+
+        if let Some(info) = self.fs_info {
+            if info.free_cluster_count != FsInfo::UNKNOWN && info.free_cluster_count <= cluster_count {
+                return info.free_cluster_count;
+            }
+        }
+
+        self.scan_free_clusters()
+    }
+
+    /// Count free clusters by scanning the whole FAT, ignoring FSInfo
+    pub fn scan_free_clusters(&self) -> u32 {
+        let cluster_count = self.bpb.cluster_count().unwrap_or(0);
+
+        (2..cluster_count.saturating_add(2))
+            .filter(|&cluster| self.raw_fat_entry(cluster) == Some(0))
+            .count() as u32
     }
 
     /// Get the BPB
@@ -82,10 +332,66 @@ impl FatTerritory {
         &self.bpb
     }
 
+    /// Get the byte offset of the first FAT in the volume
+    pub fn fat_offset(&self) -> Result<u64> {
+        Ok(self.bpb.fat_offset()? as u64)
+    }
+
+    /// Get the byte offset of the fixed root directory region
+    ///
+    /// On FAT32 the root directory is a cluster chain and the region is
+    /// empty, starting where the data region does.
+    pub fn root_directory_offset(&self) -> Result<u64> {
+        Ok(self.bpb.root_dir_offset()? as u64)
+    }
+
+    /// Get the size in bytes of the fixed root directory region, rounded up
+    /// to whole sectors
+    pub fn root_directory_size(&self) -> Result<u64> {
+        Ok(self.data_region_offset()? - self.root_directory_offset()?)
+    }
+
+    /// Get the byte offset of the data region, where cluster 2 starts
+    pub fn data_region_offset(&self) -> Result<u64> {
+        Ok(self.bpb.data_offset()? as u64)
+    }
+
+    /// Get the size in bytes of the data region's whole clusters
+    ///
+    /// Sectors past the last whole cluster are not counted.
+    pub fn data_region_size(&self) -> Result<u64> {
+        totalimage_core::checked_multiply_u64(
+            self.bpb.cluster_count()? as u64,
+            self.bpb.bytes_per_cluster()? as u64,
+            "Data region size",
+        )
+    }
+
     /// Read FAT entry for a given cluster
     ///
     /// Returns the next cluster in the chain, or None if end of chain
     pub fn read_fat_entry(&self, cluster: u32) -> Option<u32> {
+        let value = self.raw_fat_entry(cluster)?;
+
+        // Check for end of chain markers
+        let end_of_chain = match self.bpb.fat_type {
+            FatType::Fat12 => 0xFF8,
+            FatType::Fat16 => 0xFFF8,
+            FatType::Fat32 => 0x0FFFFFF8,
+        };
+
+        if value >= end_of_chain || value < 2 {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Read the raw FAT entry value for a given cluster
+    ///
+    /// Unlike [`Self::read_fat_entry`], this returns free (0) and end-of-chain
+    /// values as-is. Returns None if the cluster is outside the FAT.
+    pub fn raw_fat_entry(&self, cluster: u32) -> Option<u32> {
         match self.bpb.fat_type {
             FatType::Fat12 => self.read_fat12_entry(cluster),
             FatType::Fat16 => self.read_fat16_entry(cluster),
@@ -93,72 +399,84 @@ impl FatTerritory {
         }
     }
 
+    /// Update the in-memory FAT entry for a cluster
+    ///
+    /// Returns the byte range of the FAT table that changed, or None if the
+    /// cluster is outside the FAT or the FAT is loaded lazily.
+    fn set_fat_entry(&mut self, cluster: u32, value: u32) -> Option<std::ops::Range<usize>> {
+        let fat_type = self.bpb.fat_type;
+        let fat_table = self.fat_table.bytes_mut()?;
+        match fat_type {
+            FatType::Fat12 => {
+                let offset = (cluster + (cluster / 2)) as usize;
+                if offset + 1 >= fat_table.len() {
+                    return None;
+                }
+
+                let pair = u16::from_le_bytes([fat_table[offset], fat_table[offset + 1]]);
+                let value = (value & 0x0FFF) as u16;
+                let pair = if cluster & 1 == 0 {
+                    (pair & 0xF000) | value
+                } else {
+                    (pair & 0x000F) | (value << 4)
+                };
+                fat_table[offset..offset + 2].copy_from_slice(&pair.to_le_bytes());
+                Some(offset..offset + 2)
+            }
+            FatType::Fat16 => {
+                let offset = (cluster * 2) as usize;
+                if offset + 1 >= fat_table.len() {
+                    return None;
+                }
+
+                fat_table[offset..offset + 2].copy_from_slice(&(value as u16).to_le_bytes());
+                Some(offset..offset + 2)
+            }
+            FatType::Fat32 => {
+                let offset = (cluster * 4) as usize;
+                if offset + 3 >= fat_table.len() {
+                    return None;
+                }
+
+                // Preserve the reserved top 4 bits
+                let old = u32::from_le_bytes([
+                    fat_table[offset],
+                    fat_table[offset + 1],
+                    fat_table[offset + 2],
+                    fat_table[offset + 3],
+                ]);
+                let value = (old & 0xF0000000) | (value & 0x0FFFFFFF);
+                fat_table[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+                Some(offset..offset + 4)
+            }
+        }
+    }
+
     /// Read FAT12 entry (12 bits per entry)
     fn read_fat12_entry(&self, cluster: u32) -> Option<u32> {
         let offset = (cluster + (cluster / 2)) as usize;
-        if offset + 1 >= self.fat_table.len() {
-            return None;
-        }
-
+        let pair = u16::from_le_bytes(self.fat_table.read(offset)?);
         let value = if cluster & 1 == 0 {
             // Even cluster: lower 12 bits
-            u16::from_le_bytes([self.fat_table[offset], self.fat_table[offset + 1]]) & 0x0FFF
+            pair & 0x0FFF
         } else {
             // Odd cluster: upper 12 bits
-            u16::from_le_bytes([self.fat_table[offset], self.fat_table[offset + 1]]) >> 4
+            pair >> 4
         };
 
-        // Check for end of chain markers
-        if value >= 0xFF8 {
-            None
-        } else if value == 0 || value == 1 {
-            None
-        } else {
-            Some(value as u32)
-        }
+        Some(value as u32)
     }
 
     /// Read FAT16 entry (16 bits per entry)
     fn read_fat16_entry(&self, cluster: u32) -> Option<u32> {
         let offset = (cluster * 2) as usize;
-        if offset + 1 >= self.fat_table.len() {
-            return None;
-        }
-
-        let value = u16::from_le_bytes([self.fat_table[offset], self.fat_table[offset + 1]]);
-
-        // Check for end of chain markers
-        if value >= 0xFFF8 {
-            None
-        } else if value == 0 || value == 1 {
-            None
-        } else {
-            Some(value as u32)
-        }
+        Some(u16::from_le_bytes(self.fat_table.read(offset)?) as u32)
     }
 
     /// Read FAT32 entry (28 bits per entry, top 4 bits reserved)
     fn read_fat32_entry(&self, cluster: u32) -> Option<u32> {
         let offset = (cluster * 4) as usize;
-        if offset + 3 >= self.fat_table.len() {
-            return None;
-        }
-
-        let value = u32::from_le_bytes([
-            self.fat_table[offset],
-            self.fat_table[offset + 1],
-            self.fat_table[offset + 2],
-            self.fat_table[offset + 3],
-        ]) & 0x0FFFFFFF; // Mask off top 4 bits
-
-        // Check for end of chain markers
-        if value >= 0x0FFFFFF8 {
-            None
-        } else if value == 0 || value == 1 {
-            None
-        } else {
-            Some(value)
-        }
+        Some(u32::from_le_bytes(self.fat_table.read(offset)?) & 0x0FFFFFFF) // Mask off top 4 bits
     }
 
     /// Get cluster chain for a starting cluster
@@ -210,85 +528,96 @@ impl FatTerritory {
             .ok_or_else(|| Error::invalid_territory("Cluster offset overflow".to_string()))
     }
 
-    /// Read root directory entries (FAT12/16 only)
+    /// Read root directory entries
     pub fn read_root_directory(&self, stream: &mut dyn ReadSeek) -> Result<Vec<DirectoryEntry>> {
-        if self.bpb.fat_type == FatType::Fat32 {
-            // FAT32 has root directory in data region
-            return self.read_directory_from_cluster(stream, self.fat32_root_cluster);
-        }
-
-        stream.seek(SeekFrom::Start(self.bpb.root_dir_offset()? as u64))?;
-
-        let mut entries = Vec::new();
-        let mut entry_bytes = vec![0u8; DirectoryEntry::ENTRY_SIZE];
-        let mut pending_lfn: Vec<LfnEntry> = Vec::new();
-
-        for _ in 0..self.bpb.root_entries {
-            stream.read_exact(&mut entry_bytes)?;
-
-            // Check for end of directory
-            if DirectoryEntry::is_end_of_directory(&entry_bytes) {
-                break;
-            }
-
-            // Skip deleted entries (but clear pending LFN)
-            if DirectoryEntry::is_deleted_entry(&entry_bytes) {
-                pending_lfn.clear();
-                continue;
-            }
-
-            // Check for LFN entry
-            if DirectoryEntry::is_lfn_entry(&entry_bytes) {
-                if let Some(lfn) = LfnEntry::from_bytes(&entry_bytes) {
-                    pending_lfn.push(lfn);
-                }
-                continue;
-            }
-
-            // Parse regular entry with any accumulated LFN entries
-            if let Some(entry) = DirectoryEntry::from_bytes_with_lfn(&entry_bytes, &pending_lfn) {
-                pending_lfn.clear();
-
-                // Skip volume labels
-                if !entry.is_volume_label() {
-                    entries.push(entry);
-                }
-            } else {
-                pending_lfn.clear();
-            }
-        }
-
-        Ok(entries)
+        Ok(self.read_directory_entries(stream, None, 0, usize::MAX, None)?.0)
     }
 
     /// Read directory entries from a cluster chain (for subdirectories and FAT32 root)
     pub fn read_directory_from_cluster(&self, stream: &mut dyn ReadSeek, start_cluster: u32) -> Result<Vec<DirectoryEntry>> {
-        if start_cluster < 2 {
-            return Ok(Vec::new());
-        }
+        Ok(self.read_directory_entries(stream, Some(start_cluster), 0, usize::MAX, None)?.0)
+    }
 
-        let chain = self.get_cluster_chain(start_cluster);
-        if chain.is_empty() {
+    /// Locate the slots of a directory, as `(offset, slot count)` regions in order
+    ///
+    /// `first_cluster` is `None` for the root directory, which is a fixed
+    /// region on FAT12/16 and a cluster chain on FAT32.
+    fn directory_regions(&self, first_cluster: Option<u32>) -> Result<Vec<(u64, usize)>> {
+        let start_cluster = match first_cluster {
+            None if self.bpb.fat_type != FatType::Fat32 => {
+                return Ok(vec![(
+                    self.bpb.root_dir_offset()? as u64,
+                    self.bpb.root_entries as usize,
+                )]);
+            }
+            None => self.fat32_root_cluster,
+            Some(cluster) => cluster,
+        };
+
+        if start_cluster < 2 {
             return Ok(Vec::new());
         }
 
-        let cluster_size = self.bpb.bytes_per_cluster()? as usize;
-        let entries_per_cluster = cluster_size / DirectoryEntry::ENTRY_SIZE;
+        let entries_per_cluster = self.bpb.bytes_per_cluster()? as usize / DirectoryEntry::ENTRY_SIZE;
+        self.get_cluster_chain(start_cluster)
+            .into_iter()
+            .map(|cluster| Ok((self.cluster_to_offset(cluster)?, entries_per_cluster)))
+            .collect()
+    }
 
+    /// Read up to `limit` entries of a directory, starting at slot `cursor`
+    ///
+    /// Returns the entries and the slot to resume from, or `None` once the
+    /// end of the directory is reached. Reading seeks straight to the
+    /// cursor's slot, and a resume slot always follows a complete entry, so
+    /// long names are never split across reads. Volume labels and the `.`
+    /// and `..` entries are skipped.
+    ///
+    /// Given `skipped`, a slot that can't be read is logged and its error kept there,
+    /// and reading carries on in the directory's next cluster (or ends, for
+    /// a fixed root directory) rather than failing.
+    fn read_directory_entries(
+        &self,
+        stream: &mut dyn ReadSeek,
+        first_cluster: Option<u32>,
+        cursor: u64,
+        limit: usize,
+        mut skipped: Option<&mut Vec<String>>,
+    ) -> Result<(Vec<DirectoryEntry>, Option<u64>)> {
         let mut entries = Vec::new();
         let mut entry_bytes = vec![0u8; DirectoryEntry::ENTRY_SIZE];
         let mut pending_lfn: Vec<LfnEntry> = Vec::new();
 
-        for cluster in chain {
-            let offset = self.cluster_to_offset(cluster)?;
-            stream.seek(SeekFrom::Start(offset))?;
+        let mut skip = cursor;
+        let mut slot = cursor;
+        let mut resume = cursor;
+
+        for (offset, count) in self.directory_regions(first_cluster)? {
+            if skip >= count as u64 {
+                skip -= count as u64;
+                continue;
+            }
 
-            for _ in 0..entries_per_cluster {
-                stream.read_exact(&mut entry_bytes)?;
+            stream.seek(SeekFrom::Start(offset + skip * DirectoryEntry::ENTRY_SIZE as u64))?;
+            let region_end = slot + (count as u64 - skip);
+
+            for _ in skip..count as u64 {
+                if let Err(e) = stream.read_exact(&mut entry_bytes) {
+                    let Some(skipped) = skipped.as_deref_mut() else {
+                        return Err(e.into());
+                    };
+                    tracing::warn!("Skipping unreadable directory slots at {:#x}: {}", offset, e);
+                    skipped.push(format!("Unreadable directory slots at {:#x}: {}", offset, e));
+                    pending_lfn.clear();
+                    slot = region_end;
+                    resume = slot;
+                    break;
+                }
+                slot += 1;
 
                 // Check for end of directory
                 if DirectoryEntry::is_end_of_directory(&entry_bytes) {
-                    return Ok(entries);
+                    return Ok((entries, None));
                 }
 
                 // Skip deleted entries (but clear pending LFN)
@@ -307,57 +636,138 @@ impl FatTerritory {
 
                 // Parse regular entry with any accumulated LFN entries
                 if let Some(entry) = DirectoryEntry::from_bytes_with_lfn(&entry_bytes, &pending_lfn) {
-                    pending_lfn.clear();
-
                     // Skip volume labels and . / .. entries
-                    if !entry.is_volume_label() {
-                        let short_name = &entry.short_name;
-                        if short_name != "." && short_name != ".." {
-                            entries.push(entry);
+                    let short_name = &entry.short_name;
+                    if !entry.is_volume_label() && short_name != "." && short_name != ".." {
+                        // A further entry exists, so the page ends before it
+                        if entries.len() == limit {
+                            return Ok((entries, Some(resume)));
                         }
+                        entries.push(entry);
                     }
-                } else {
-                    pending_lfn.clear();
+                }
+                pending_lfn.clear();
+                resume = slot;
+            }
+
+            skip = 0;
+        }
+
+        Ok((entries, None))
+    }
+
+    /// Read every 32-byte slot of the directory at `path`, in on-disk order
+    ///
+    /// Unlike listing, nothing is skipped or merged: deleted, LFN and
+    /// volume label slots are returned classified alongside live entries.
+    /// Slots after the first [`SlotKind::End`] slot are unused by the file
+    /// system but returned too, as they may still hold old entries.
+    pub fn raw_directory_slots(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<RawDirSlot>> {
+        let trimmed = path.trim_matches('/').trim_matches('\\');
+
+        let first_cluster = if trimmed.is_empty() {
+            None
+        } else {
+            let entry = self.find_file_by_path(stream, trimmed)?;
+            if !entry.is_directory() {
+                return Err(Error::not_found(format!("Not a directory: {}", trimmed)));
+            }
+            Some(entry.first_cluster())
+        };
+
+        let mut slots = Vec::new();
+        for (offset, count) in self.directory_regions(first_cluster)? {
+            stream.seek(SeekFrom::Start(offset))?;
+            for i in 0..count {
+                let mut bytes = [0u8; DirectoryEntry::ENTRY_SIZE];
+                stream.read_exact(&mut bytes)?;
+                slots.push(RawDirSlot {
+                    offset: offset + (i * DirectoryEntry::ENTRY_SIZE) as u64,
+                    kind: SlotKind::classify(&bytes),
+                    bytes,
+                });
+            }
+        }
+
+        Ok(slots)
+    }
+
+    /// Enumerate deleted entries in the directory at `path`
+    ///
+    /// Entries whose first byte is 0xE5 are returned with the lost first
+    /// character replaced by [`DirectoryEntry::DELETED_PLACEHOLDER`]. Long
+    /// names are reassembled from any deleted LFN entries preceding them.
+    /// Each entry carries an assessment of how recoverable its data is; see
+    /// [`Self::recover_deleted_file`].
+    pub fn read_deleted_entries(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<DeletedEntry>> {
+        let slots = self.raw_directory_slots(stream, path)?;
+
+        let mut deleted = Vec::new();
+        let mut pending_lfn: Vec<LfnEntry> = Vec::new();
+
+        for RawDirSlot { bytes: slot, kind, .. } in slots {
+            if kind == SlotKind::End {
+                break;
+            }
+            if kind != SlotKind::Deleted {
+                pending_lfn.clear();
+                continue;
+            }
+
+            if DirectoryEntry::is_lfn_entry(&slot) {
+                if let Some(lfn) = LfnEntry::from_bytes(&slot) {
+                    pending_lfn.push(lfn);
+                }
+                continue;
+            }
+
+            if let Some(entry) = DirectoryEntry::from_deleted_bytes(&slot, &pending_lfn) {
+                if !entry.is_volume_label() {
+                    let recoverability = self.assess_recoverability(&entry)?;
+                    deleted.push(DeletedEntry { entry, recoverability });
                 }
             }
+            pending_lfn.clear();
         }
 
-        Ok(entries)
+        Ok(deleted)
     }
 
-    /// List root directory as OccupantInfo (for CLI)
-    pub fn list_root_directory(&self, stream: &mut dyn ReadSeek) -> Result<Vec<OccupantInfo>> {
-        let entries = self.read_root_directory(stream)?;
+    /// Decide how a deleted entry's data can be recovered
+    ///
+    /// Deleting a file frees its chain, so an allocated first cluster
+    /// belongs to a newer file, even when that file's chain happens to be
+    /// as long as the deleted one's.
+    fn assess_recoverability(&self, entry: &DirectoryEntry) -> Result<Recoverability> {
+        let first_cluster = entry.first_cluster();
+        if first_cluster < 2 || (entry.file_size == 0 && !entry.is_directory()) {
+            return Ok(Recoverability::NoData);
+        }
 
-        Ok(entries
-            .into_iter()
-            .map(|entry| OccupantInfo {
-                name: entry.name.clone(),
-                is_directory: entry.is_directory(),
-                size: entry.file_size as u64,
-                created: None,
-                modified: None,
-                accessed: None,
-                attributes: entry.attributes as u32,
-            })
-            .collect())
+        let cluster_size = self.bpb.bytes_per_cluster()? as u64;
+        let needed = (entry.file_size as u64).div_ceil(cluster_size).max(1);
+
+        let all_free = (0..needed).all(|i| {
+            u32::try_from(first_cluster as u64 + i)
+                .ok()
+                .and_then(|cluster| self.raw_fat_entry(cluster))
+                == Some(0)
+        });
+
+        Ok(if all_free {
+            Recoverability::Contiguous
+        } else {
+            Recoverability::Overwritten
+        })
     }
 
-    /// List directory contents at a path
-    pub fn list_directory(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<OccupantInfo>> {
-        let entries = self.read_directory_at_path(stream, path)?;
+    /// List root directory as OccupantInfo (for CLI)
+    pub fn list_root_directory(&self, stream: &mut dyn ReadSeek) -> Result<Vec<OccupantInfo>> {
+        let entries = self.read_root_directory(stream)?;
 
         Ok(entries
             .into_iter()
-            .map(|entry| OccupantInfo {
-                name: entry.name.clone(),
-                is_directory: entry.is_directory(),
-                size: entry.file_size as u64,
-                created: None,
-                modified: None,
-                accessed: None,
-                attributes: entry.attributes as u32,
-            })
+            .map(occupant_info)
             .collect())
     }
 
@@ -466,13 +876,7 @@ impl FatTerritory {
         }
 
         // Validate file size against extraction limit
-        use totalimage_core::MAX_FILE_EXTRACT_SIZE;
-        if entry.file_size as u64 > MAX_FILE_EXTRACT_SIZE {
-            return Err(Error::invalid_territory(format!(
-                "File size {} exceeds extraction limit {}",
-                entry.file_size, MAX_FILE_EXTRACT_SIZE
-            )));
-        }
+        self.limits.validate_extract_size(entry.file_size as u64)?;
 
         // Get cluster chain
         let chain = self.get_cluster_chain(first_cluster);
@@ -505,15 +909,83 @@ impl FatTerritory {
         Ok(data)
     }
 
-    /// Read file data by path (supports subdirectories)
-    pub fn read_file_by_path(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<u8>> {
-        let entry = self.find_file_by_path(stream, path)?;
-
-        if entry.is_directory() {
-            return Err(Error::not_found(format!("Path is a directory: {}", path)));
+    /// Read part of a file's data
+    ///
+    /// Returns up to `length` bytes starting `offset` bytes into the file,
+    /// fewer if the file ends first. Only the clusters holding the range
+    /// are read.
+    pub fn read_file_range(
+        &self,
+        stream: &mut dyn ReadSeek,
+        entry: &DirectoryEntry,
+        offset: u64,
+        length: u64,
+    ) -> Result<Vec<u8>> {
+        let file_size = entry.file_size as u64;
+        let end = offset.saturating_add(length).min(file_size);
+        if entry.first_cluster() == 0 || offset >= end {
+            return Ok(Vec::new());
         }
 
-        self.read_file_data(stream, &entry)
+        self.limits.validate_extract_size(end - offset)?;
+
+        let cluster_size = self.bpb.bytes_per_cluster()? as u64;
+        let first = (offset / cluster_size) as usize;
+        let mut data = Vec::with_capacity((end - offset) as usize);
+        let mut position = first as u64 * cluster_size;
+
+        for cluster in self.get_cluster_chain(entry.first_cluster()).into_iter().skip(first) {
+            let skip = offset.saturating_sub(position);
+            let to_read = (end - position).min(cluster_size) - skip;
+
+            stream.seek(SeekFrom::Start(self.cluster_to_offset(cluster)? + skip))?;
+            let start = data.len();
+            data.resize(start + to_read as usize, 0);
+            stream.read_exact(&mut data[start..])?;
+
+            position += cluster_size;
+            if position >= end {
+                break;
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Read file data by path (supports subdirectories)
+    pub fn read_file_by_path(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<u8>> {
+        let entry = self.find_file_by_path(stream, path)?;
+
+        if entry.is_directory() {
+            return Err(Error::not_found(format!("Path is a directory: {}", path)));
+        }
+
+        self.read_file_data(stream, &entry)
+    }
+
+    /// Best-effort recovery of a deleted file's data
+    ///
+    /// Reads `file_size` bytes contiguously from the first cluster,
+    /// truncated at the end of the volume. Data for [`Recoverability::Overwritten`]
+    /// entries is returned but likely contains other files' content.
+    pub fn recover_deleted_file(&self, stream: &mut dyn ReadSeek, deleted: &DeletedEntry) -> Result<Vec<u8>> {
+        let entry = &deleted.entry;
+
+        self.limits.validate_extract_size(entry.file_size as u64)?;
+
+        match deleted.recoverability {
+            Recoverability::NoData => Ok(Vec::new()),
+            Recoverability::Contiguous | Recoverability::Overwritten => {
+                let offset = self.cluster_to_offset(entry.first_cluster())?;
+                let available = self.domain_size().saturating_sub(offset);
+                let length = (entry.file_size as u64).min(available) as usize;
+
+                stream.seek(SeekFrom::Start(offset))?;
+                let mut data = vec![0u8; length];
+                stream.read_exact(&mut data)?;
+                Ok(data)
+            }
+        }
     }
 }
 
@@ -523,13 +995,15 @@ impl Territory for FatTerritory {
     }
 
     fn banner(&self) -> Result<String> {
-        // FAT volumes can have volume labels stored in root directory
-        // For now return a placeholder
-        Ok(String::from("FAT_VOLUME"))
+        Ok(self.volume_label.clone())
     }
 
-    fn headquarters(&self) -> Result<Box<dyn DirectoryCell>> {
-        Ok(Box::new(FatRootDirectory))
+    fn headquarters(&self) -> Result<Box<dyn DirectoryCell<'_> + '_>> {
+        Ok(Box::new(FatDirectoryCell {
+            territory: self,
+            name: "/".to_string(),
+            first_cluster: None,
+        }))
     }
 
     fn domain_size(&self) -> u64 {
@@ -542,58 +1016,205 @@ impl Territory for FatTerritory {
     }
 
     fn liberated_space(&self) -> u64 {
-        // Would need to count free clusters in FAT
-        // Return 0 for now (simplified implementation)
-        0
+        self.free_cluster_count() as u64 * self.block_size()
     }
 
     fn block_size(&self) -> u64 {
         self.bpb.bytes_per_cluster().unwrap_or(0) as u64
     }
 
+    fn space_summary(&self, _stream: &mut dyn ReadSeek) -> Result<SpaceSummary> {
+        // The FAT is already in memory; a scan is exact where FSInfo is a hint
+        Ok(SpaceSummary::from_blocks(
+            self.domain_size(),
+            self.block_size(),
+            self.bpb.cluster_count().unwrap_or(0) as u64,
+            self.scan_free_clusters() as u64,
+        ))
+    }
+
     fn hierarchical(&self) -> bool {
         true // FAT supports subdirectories
     }
 
-    fn navigate_to(&self, _path: &str) -> Result<Box<dyn DirectoryCell>> {
-        // Simplified: always return root directory
-        self.headquarters()
+    fn fs_features(&self) -> FsFeatures {
+        FsFeatures {
+            writable: true, // Through FatWriter
+            timestamps: true,
+            long_names: true,
+            short_names: true,
+            max_file_size: u32::MAX as u64,
+            ..FsFeatures::default()
+        }
     }
 
-    fn extract_file(&mut self, _path: &str) -> Result<Vec<u8>> {
-        // Simplified: return empty
-        // Full implementation would parse path, find file, read clusters
-        Ok(Vec::new())
+    fn file_metadata(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<OccupantInfo> {
+        self.find_file_by_path(stream, path).map(occupant_info)
+    }
+
+    fn data_runs(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<(u64, u64)>> {
+        let entry = self.find_file_by_path(stream, path)?;
+        let cluster_size = self.bpb.bytes_per_cluster()? as u64;
+        let chain = self.get_cluster_chain(entry.first_cluster());
+
+        // Directories record no size; they span their whole chain
+        let size = if entry.is_directory() {
+            chain.len() as u64 * cluster_size
+        } else {
+            entry.file_size as u64
+        };
+
+        let mut extents = Extents::new(size);
+        for cluster in chain {
+            if !extents.push(self.cluster_to_offset(cluster)?, cluster_size) {
+                break;
+            }
+        }
+        Ok(extents.into_runs())
+    }
+
+    fn read_file_range(
+        &self,
+        stream: &mut dyn ReadSeek,
+        path: &str,
+        offset: u64,
+        length: u64,
+    ) -> Result<Vec<u8>> {
+        let entry = self.find_file_by_path(stream, path)?;
+        if entry.is_directory() {
+            return Err(Error::InvalidOperation(format!("Path is a directory: {}", path)));
+        }
+        validate_file_range(entry.file_size as u64, offset, length)?;
+
+        let data = FatTerritory::read_file_range(self, stream, &entry, offset, length)?;
+        if (data.len() as u64) < length {
+            return Err(Error::invalid_territory(format!(
+                "Cluster chain of {} is shorter than its size",
+                path
+            )));
+        }
+        Ok(data)
+    }
+
+    fn decode_attributes(&self, raw: u32) -> Vec<AttributeFlag> {
+        AttributeFlag::decode(raw, &ATTRIBUTE_FLAGS)
+    }
+
+    fn extract_file(&mut self, path: &str) -> Result<Vec<u8>> {
+        let mut reader = self
+            .reader
+            .take()
+            .ok_or_else(|| Error::unsupported("FAT territory was parsed without an owned stream"))?;
+        let result = self.find_file_by_path(&mut *reader, path).and_then(|entry| {
+            if entry.is_directory() {
+                return Err(Error::InvalidOperation(format!("Path is a directory: {}", path)));
+            }
+            self.read_file_data(&mut *reader, &entry)
+        });
+        self.reader = Some(reader);
+        result
     }
 }
 
-/// FAT root directory cell
-struct FatRootDirectory;
+/// Convert a directory entry to occupant info
+///
+/// FAT timestamps are local time, so they are flagged as such. The short name is kept only when the entry has a long name that differs
+/// from it other than in case.
+fn occupant_info(entry: DirectoryEntry) -> OccupantInfo {
+    let short_name = (!entry.short_name.eq_ignore_ascii_case(&entry.name)).then(|| entry.short_name.clone());
+    OccupantInfo {
+        is_directory: entry.is_directory(),
+        size: entry.file_size as u64,
+        created: entry.created_time(),
+        modified: entry.modified_time(),
+        accessed: entry.accessed_time(),
+        timestamp_is_local: true,
+        attributes: entry.attributes as u32,
+        link_target: None,
+        short_name,
+        link_count: 1,
+        path: None,
+        name: entry.name,
+    }
+}
 
-impl DirectoryCell for FatRootDirectory {
+/// FAT directory cell
+struct FatDirectoryCell<'a> {
+    territory: &'a FatTerritory,
+    name: String,
+    /// First cluster of the directory, or `None` for the root directory
+    first_cluster: Option<u32>,
+}
+
+impl FatDirectoryCell<'_> {
+    fn entries(&self, stream: &mut dyn ReadSeek) -> Result<Vec<DirectoryEntry>> {
+        Ok(self.territory.read_directory_entries(stream, self.first_cluster, 0, usize::MAX, None)?.0)
+    }
+}
+
+impl<'a> DirectoryCell<'a> for FatDirectoryCell<'a> {
     fn name(&self) -> &str {
-        "/"
+        &self.name
     }
 
-    fn list_occupants(&self) -> Result<Vec<OccupantInfo>> {
-        // Simplified: return empty list
-        // Full implementation would read directory entries from stream
-        Ok(Vec::new())
+    fn list_occupants(&self, stream: &mut dyn ReadSeek) -> Result<Vec<OccupantInfo>> {
+        Ok(self.entries(stream)?.into_iter().map(occupant_info).collect())
     }
 
-    fn enter(&self, _name: &str) -> Result<Box<dyn DirectoryCell>> {
-        // Simplified: return error
-        Err(Error::not_found("Subdirectory not found".to_string()))
+    fn list_occupants_best_effort(&self, stream: &mut dyn ReadSeek) -> Result<PartialListing> {
+        let mut skipped = Vec::new();
+        let (entries, _) = self.territory.read_directory_entries(
+            stream,
+            self.first_cluster,
+            0,
+            usize::MAX,
+            Some(&mut skipped),
+        )?;
+        Ok(PartialListing::with_skipped(entries.into_iter().map(occupant_info).collect(), skipped))
+    }
+
+    /// Pages resume at a directory slot, reading only from its cluster on
+    fn list_page(&self, stream: &mut dyn ReadSeek, cursor: u64, limit: usize) -> Result<DirectoryPage> {
+        if limit == 0 {
+            return Ok(DirectoryPage::unmoved(cursor));
+        }
+        let (entries, next) = self
+            .territory
+            .read_directory_entries(stream, self.first_cluster, cursor, limit, None)?;
+        Ok(DirectoryPage {
+            occupants: entries.into_iter().map(occupant_info).collect(),
+            next,
+        })
+    }
+
+    fn enter(&self, stream: &mut dyn ReadSeek, name: &str) -> Result<Box<dyn DirectoryCell<'a> + 'a>> {
+        let entry = self
+            .entries(stream)?
+            .into_iter()
+            .find(|e| e.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| Error::not_found(format!("Directory not found: {}", name)))?;
+
+        if !entry.is_directory() {
+            return Err(Error::not_found(format!("Not a directory: {}", name)));
+        }
+
+        Ok(Box::new(FatDirectoryCell {
+            territory: self.territory,
+            first_cluster: Some(entry.first_cluster()),
+            name: entry.name,
+        }))
     }
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use chrono::{TimeZone, Utc};
     use std::io::Cursor;
+    use totalimage_core::{ListOptions, ProgressUpdate};
 
     /// Create a minimal FAT12 boot sector
-    fn create_fat12_boot_sector() -> Vec<u8> {
+    pub(super) fn create_fat12_boot_sector() -> Vec<u8> {
         let mut boot = vec![0u8; 512];
 
         // Jump instruction
@@ -620,6 +1241,182 @@ mod tests {
         boot
     }
 
+    /// Set an entry in the first FAT of a 1.44MB FAT12 image
+    pub(super) fn set_fat12_entry(disk: &mut [u8], cluster: usize, value: u16) {
+        let offset = 512 + cluster * 3 / 2;
+        if cluster.is_multiple_of(2) {
+            disk[offset] = value as u8;
+            disk[offset + 1] = (disk[offset + 1] & 0xF0) | (value >> 8) as u8;
+        } else {
+            disk[offset] = (disk[offset] & 0x0F) | (value << 4) as u8;
+            disk[offset + 1] = (value >> 4) as u8;
+        }
+    }
+
+    /// Create a blank FAT32 volume with a valid FSInfo sector
+    pub(crate) fn blank_fat32_volume() -> Vec<u8> {
+        const CLUSTERS: u32 = 66_000;
+        const SECTORS_PER_FAT: u32 = (CLUSTERS + 2) * 4 / 512 + 1;
+        const RESERVED: u32 = 32;
+        let total_sectors = RESERVED + 2 * SECTORS_PER_FAT + CLUSTERS;
+
+        let mut disk = vec![0u8; total_sectors as usize * 512];
+        disk[0..3].copy_from_slice(&[0xEB, 0x58, 0x90]);
+        disk[3..11].copy_from_slice(b"MSWIN4.1");
+        disk[11..13].copy_from_slice(&512u16.to_le_bytes());
+        disk[13] = 1;
+        disk[14..16].copy_from_slice(&(RESERVED as u16).to_le_bytes());
+        disk[16] = 2;
+        disk[21] = 0xF8;
+        disk[32..36].copy_from_slice(&total_sectors.to_le_bytes());
+        disk[36..40].copy_from_slice(&SECTORS_PER_FAT.to_le_bytes());
+        disk[44..48].copy_from_slice(&2u32.to_le_bytes()); // Root cluster
+        disk[48..50].copy_from_slice(&1u16.to_le_bytes()); // FSInfo sector
+        disk[510..512].copy_from_slice(&[0x55, 0xAA]);
+
+        let fs_info = 512;
+        disk[fs_info..fs_info + 4].copy_from_slice(&FsInfo::LEAD_SIGNATURE.to_le_bytes());
+        disk[fs_info + 484..fs_info + 488].copy_from_slice(&FsInfo::STRUCT_SIGNATURE.to_le_bytes());
+        disk[fs_info + 488..fs_info + 492].copy_from_slice(&(CLUSTERS - 1).to_le_bytes());
+        disk[fs_info + 492..fs_info + 496].copy_from_slice(&3u32.to_le_bytes());
+        disk[fs_info + 508..fs_info + 512].copy_from_slice(&FsInfo::TRAIL_SIGNATURE.to_le_bytes());
+
+        for fat in 0..2 {
+            let offset = (RESERVED + fat * SECTORS_PER_FAT) as usize * 512;
+            disk[offset..offset + 4].copy_from_slice(&0x0FFFFFF8u32.to_le_bytes());
+            disk[offset + 4..offset + 8].copy_from_slice(&0x0FFFFFFFu32.to_le_bytes());
+            disk[offset + 8..offset + 12].copy_from_slice(&0x0FFFFFFFu32.to_le_bytes()); // Root
+        }
+        disk
+    }
+
+    #[test]
+    fn test_fat32_fs_info_free_count() {
+        let mut disk = blank_fat32_volume();
+
+        let territory = FatTerritory::parse(&mut Cursor::new(disk.clone())).unwrap();
+        assert_eq!(territory.bpb.fat_type, FatType::Fat32);
+        assert_eq!(territory.fs_info().unwrap().next_free_cluster, 3);
+        assert_eq!(territory.free_cluster_count(), 65_999);
+        assert_eq!(territory.liberated_space(), 65_999 * 512);
+
+        // The cached count is trusted without scanning the FAT
+        disk[512 + 488..512 + 492].copy_from_slice(&1234u32.to_le_bytes());
+        let territory = FatTerritory::parse(&mut Cursor::new(disk.clone())).unwrap();
+        assert_eq!(territory.liberated_space(), 1234 * 512);
+
+        // Unknown count falls back to a scan
+        disk[512 + 488..512 + 492].copy_from_slice(&FsInfo::UNKNOWN.to_le_bytes());
+        let territory = FatTerritory::parse(&mut Cursor::new(disk.clone())).unwrap();
+        assert_eq!(territory.free_cluster_count(), 65_999);
+
+        // So does a sector with a bad signature
+        disk[512 + 488..512 + 492].copy_from_slice(&1234u32.to_le_bytes());
+        disk[512] = 0;
+        let territory = FatTerritory::parse(&mut Cursor::new(disk)).unwrap();
+        assert!(territory.fs_info().is_none());
+        assert_eq!(territory.free_cluster_count(), 65_999);
+    }
+
+    #[test]
+    fn test_fat12_free_space_scan() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+        // Media entries, then clusters 2-3 allocated as a chain
+        disk[512..518].copy_from_slice(&[0xF0, 0xFF, 0xFF, 0x03, 0xF0, 0xFF]);
+
+        let territory = FatTerritory::parse(&mut Cursor::new(disk)).unwrap();
+        assert!(territory.fs_info().is_none());
+        let clusters = territory.bpb.cluster_count().unwrap();
+        assert_eq!(territory.free_cluster_count(), clusters - 2);
+        assert_eq!(territory.liberated_space(), (clusters - 2) as u64 * 512);
+    }
+
+    #[test]
+    fn test_fat12_space_summary() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+        // Media entries, clusters 2-3 as a chain and cluster 4 on its own
+        disk[512..519].copy_from_slice(&[0xF0, 0xFF, 0xFF, 0x03, 0xF0, 0xFF, 0xFF]);
+        disk[519] = 0x0F;
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+        let summary = territory.space_summary(&mut cursor).unwrap();
+
+        let clusters = territory.bpb.cluster_count().unwrap() as u64;
+        assert_eq!(summary.total, 1_474_560);
+        assert_eq!(summary.block_size, 512);
+        assert_eq!(summary.block_count, clusters);
+        assert_eq!(summary.free_blocks, clusters - 3);
+        assert_eq!(summary.free, (clusters - 3) * 512);
+        assert_eq!(summary.used + summary.free, summary.total);
+        assert_eq!(summary.free, territory.liberated_space());
+    }
+
+    #[test]
+    fn test_fat16_4k_sectors() {
+        const SECTOR: usize = 4096;
+        const CLUSTERS: usize = 5000;
+        const SECTORS_PER_FAT: usize = 3; // (5000 + 2) * 2 bytes
+        const ROOT_SECTORS: usize = 4; // 512 entries * 32 bytes
+        let fat_offset = SECTOR;
+        let root_offset = fat_offset + 2 * SECTORS_PER_FAT * SECTOR;
+        let data_offset = root_offset + ROOT_SECTORS * SECTOR;
+        let total_sectors = 1 + 2 * SECTORS_PER_FAT + ROOT_SECTORS + CLUSTERS;
+
+        let mut disk = vec![0u8; total_sectors * SECTOR];
+        disk[0..3].copy_from_slice(&[0xEB, 0x3C, 0x90]);
+        disk[3..11].copy_from_slice(b"MSWIN4.1");
+        disk[11..13].copy_from_slice(&(SECTOR as u16).to_le_bytes());
+        disk[13] = 1;
+        disk[14..16].copy_from_slice(&1u16.to_le_bytes());
+        disk[16] = 2;
+        disk[17..19].copy_from_slice(&512u16.to_le_bytes());
+        disk[19..21].copy_from_slice(&(total_sectors as u16).to_le_bytes());
+        disk[21] = 0xF8;
+        disk[22..24].copy_from_slice(&(SECTORS_PER_FAT as u16).to_le_bytes());
+        disk[510..512].copy_from_slice(&[0x55, 0xAA]);
+
+        // Clusters 2 (SUB directory) and 3 (file) are single-cluster chains
+        for fat in 0..2 {
+            let offset = fat_offset + fat * SECTORS_PER_FAT * SECTOR;
+            disk[offset..offset + 8].copy_from_slice(&[0xF8, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+        }
+
+        let entry = |name: &[u8; 11], attributes: u8, cluster: u16, size: u32| {
+            let mut raw = [0u8; 32];
+            raw[0..11].copy_from_slice(name);
+            raw[11] = attributes;
+            raw[26..28].copy_from_slice(&cluster.to_le_bytes());
+            raw[28..32].copy_from_slice(&size.to_le_bytes());
+            raw
+        };
+        disk[root_offset..root_offset + 32].copy_from_slice(&entry(b"SUB        ", 0x10, 2, 0));
+        disk[data_offset..data_offset + 32].copy_from_slice(&entry(b"NOTE    TXT", 0x20, 3, 11));
+        let file_offset = data_offset + SECTOR;
+        disk[file_offset..file_offset + 11].copy_from_slice(b"hello, 4Kn!");
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+        assert_eq!(territory.bpb.fat_type, FatType::Fat16);
+        assert_eq!(territory.bpb.root_dir_offset().unwrap() as usize, root_offset);
+        assert_eq!(territory.bpb.data_offset().unwrap() as usize, data_offset);
+        assert_eq!(territory.block_size(), SECTOR as u64);
+
+        let root = territory.read_root_directory(&mut cursor).unwrap();
+        assert_eq!(root.len(), 1);
+        assert!(root[0].is_directory());
+
+        let sub = territory.read_directory_at_path(&mut cursor, "SUB").unwrap();
+        assert_eq!(sub.len(), 1);
+        assert_eq!(sub[0].name, "NOTE.TXT");
+        assert_eq!(
+            territory.read_file_by_path(&mut cursor, "SUB/NOTE.TXT").unwrap(),
+            b"hello, 4Kn!"
+        );
+    }
+
     #[test]
     fn test_parse_fat12() {
         let boot_sector = create_fat12_boot_sector();
@@ -635,6 +1432,52 @@ mod tests {
         assert_eq!(territory.identify(), "FAT12 filesystem");
     }
 
+    #[test]
+    fn test_parse_rejects_exfat() {
+        let mut disk = vec![0u8; 64 * 1024];
+        disk[0..3].copy_from_slice(&[0xEB, 0x76, 0x90]);
+        disk[3..11].copy_from_slice(b"EXFAT   ");
+        disk[72..80].copy_from_slice(&128u64.to_le_bytes()); // Volume length
+        disk[108] = 9; // 512-byte sectors
+        disk[110] = 1; // One FAT
+        disk[510] = 0x55;
+        disk[511] = 0xAA;
+
+        match FatTerritory::parse(&mut Cursor::new(disk)) {
+            Err(Error::UnrecognizedSignature(msg)) => assert!(msg.contains("exFAT, not FAT"), "{}", msg),
+            Err(e) => panic!("unexpected error: {}", e),
+            Ok(_) => panic!("exFAT boot sector parsed as FAT"),
+        }
+    }
+
+    #[test]
+    fn test_fs_features() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+        let territory = FatTerritory::parse(&mut Cursor::new(disk)).unwrap();
+        let features = territory.fs_features();
+
+        assert!(features.writable && features.timestamps);
+        assert!(features.long_names && features.short_names);
+        assert!(!features.alternate_data_streams && !features.symlinks && !features.hard_links);
+        assert_eq!(features.max_file_size, 0xFFFF_FFFF);
+    }
+
+    #[test]
+    fn test_decode_attributes() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+        let territory = FatTerritory::parse(&mut Cursor::new(disk)).unwrap();
+
+        assert_eq!(territory.decode_attributes(0), vec![]);
+        assert_eq!(
+            territory.decode_attributes(0x27),
+            vec![AttributeFlag::ReadOnly, AttributeFlag::Hidden, AttributeFlag::System, AttributeFlag::Archive]
+        );
+        assert_eq!(territory.decode_attributes(0x10), vec![AttributeFlag::Directory]);
+        assert_eq!(territory.decode_attributes(0x08), vec![AttributeFlag::VolumeLabel]);
+    }
+
     #[test]
     fn test_fat12_entry_reading() {
         let boot_sector = create_fat12_boot_sector();
@@ -684,6 +1527,29 @@ mod tests {
         assert_eq!(territory.cluster_to_offset(2).unwrap(), expected);
     }
 
+    #[test]
+    fn test_region_layout() {
+        let boot_sector = create_fat12_boot_sector();
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&boot_sector);
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        // FATs after the 1 reserved sector, then 2 * 9 FAT sectors, then
+        // 224 * 32 bytes of root directory (14 sectors), then the remaining
+        // 2880 - 33 sectors as 1-sector clusters
+        assert_eq!(territory.fat_offset().unwrap(), 512);
+        assert_eq!(territory.root_directory_offset().unwrap(), 512 + 18 * 512);
+        assert_eq!(territory.root_directory_size().unwrap(), 14 * 512);
+        assert_eq!(territory.data_region_offset().unwrap(), 16896);
+        assert_eq!(territory.data_region_size().unwrap(), 2847 * 512);
+        assert_eq!(
+            territory.data_region_offset().unwrap() + territory.data_region_size().unwrap(),
+            1_474_560
+        );
+    }
+
     #[test]
     fn test_root_directory_reading() {
         let boot_sector = create_fat12_boot_sector();
@@ -745,6 +1611,48 @@ mod tests {
         assert_eq!(subdir_entries[0].name, "NESTED.TXT");
     }
 
+    #[test]
+    fn test_walk_reports_each_entry() {
+        let boot_sector = create_fat12_boot_sector();
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&boot_sector);
+        set_fat12_entry(&mut disk, 2, 0xFFF);
+        set_fat12_entry(&mut disk, 3, 0xFFF);
+
+        let mut put = |offset: usize, name: &[u8; 11], attributes: u8, cluster: u8| {
+            disk[offset..offset + 11].copy_from_slice(name);
+            disk[offset + 11] = attributes;
+            disk[offset + 26] = cluster;
+        };
+        // /FILE.TXT, /SUBDIR/NESTED.TXT, /SUBDIR/INNER/DEEP.TXT
+        let root_offset = 512 + (2 * 9 * 512);
+        put(root_offset, b"FILE    TXT", 0x20, 0);
+        put(root_offset + 32, b"SUBDIR     ", DirectoryEntry::ATTR_DIRECTORY, 2);
+        put(16896, b"NESTED  TXT", 0x20, 0);
+        put(16896 + 32, b"INNER      ", DirectoryEntry::ATTR_DIRECTORY, 3);
+        put(16896 + 512, b"DEEP    TXT", 0x20, 0);
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        let updates = std::sync::Mutex::new(Vec::new());
+        let progress = |update: ProgressUpdate| updates.lock().unwrap().push(update);
+        let occupants = territory.walk(&mut cursor, "/", Some(&progress)).unwrap();
+
+        let mut paths: Vec<_> = occupants.iter().map(|o| o.path.clone().unwrap()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            ["/FILE.TXT", "/SUBDIR", "/SUBDIR/INNER", "/SUBDIR/INNER/DEEP.TXT", "/SUBDIR/NESTED.TXT"]
+        );
+        let updates = updates.into_inner().unwrap();
+        let expected: Vec<_> = (1..=5).map(|visited| ProgressUpdate::Entries { visited }).collect();
+        assert_eq!(updates, expected);
+
+        // A subtree walk, without a reporter
+        assert_eq!(territory.walk(&mut cursor, "/SUBDIR/INNER", None).unwrap().len(), 1);
+    }
+
     #[test]
     fn test_find_file_by_path() {
         let boot_sector = create_fat12_boot_sector();
@@ -780,6 +1688,696 @@ mod tests {
         assert_eq!(entry.file_size, 100);
     }
 
+    #[test]
+    fn test_file_metadata() {
+        let boot_sector = create_fat12_boot_sector();
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&boot_sector);
+
+        // Clusters 2 (DOCS) and 3 (DOCS/SUB): EOF
+        let fat_offset = 512;
+        disk[fat_offset..fat_offset + 5].copy_from_slice(&[0xF0, 0xFF, 0xFF, 0xFF, 0xFF]);
+
+        let root_offset = 512 + (2 * 9 * 512);
+        disk[root_offset..root_offset + 11].copy_from_slice(b"DOCS       ");
+        disk[root_offset + 11] = DirectoryEntry::ATTR_DIRECTORY;
+        disk[root_offset + 26] = 2;
+
+        // DOCS contains SUB, which contains NOTES.TXT
+        let data_offset = 16896;
+        disk[data_offset..data_offset + 11].copy_from_slice(b"SUB        ");
+        disk[data_offset + 11] = DirectoryEntry::ATTR_DIRECTORY;
+        disk[data_offset + 26] = 3;
+        let sub_offset = data_offset + 512;
+        disk[sub_offset..sub_offset + 11].copy_from_slice(b"NOTES   TXT");
+        disk[sub_offset + 11] = 0x21; // Read-only, archive
+        disk[sub_offset + 28] = 42;
+        // Modified 2023-06-15 14:30:58 local time
+        disk[sub_offset + 22..sub_offset + 24].copy_from_slice(&((14u16 << 11) | (30 << 5) | 29).to_le_bytes());
+        disk[sub_offset + 24..sub_offset + 26].copy_from_slice(&((43u16 << 9) | (6 << 5) | 15).to_le_bytes());
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        let notes = territory.file_metadata(&mut cursor, "/docs/sub/notes.txt").unwrap();
+        assert_eq!(notes.name, "NOTES.TXT");
+        assert_eq!(notes.size, 42);
+        assert_eq!(notes.attributes, 0x21);
+        assert!(!notes.is_directory);
+        assert_eq!(notes.modified, Some(Utc.with_ymd_and_hms(2023, 6, 15, 14, 30, 58).unwrap()));
+        assert_eq!(notes.created, None);
+        assert!(notes.timestamp_is_local);
+
+        let sub = territory.file_metadata(&mut cursor, "DOCS\\SUB").unwrap();
+        assert!(sub.is_directory);
+
+        for missing in ["/DOCS/MISSING.TXT", "/DOCS/SUB/NOTES.TXT/X", "/"] {
+            let err = territory.file_metadata(&mut cursor, missing).unwrap_err();
+            assert!(matches!(err, Error::NotFound(_)), "{}: {:?}", missing, err);
+        }
+    }
+
+    #[test]
+    fn test_list_directory_paths() {
+        let boot_sector = create_fat12_boot_sector();
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&boot_sector);
+
+        // Clusters 2 (DOCS) and 3 (DOCS/SUB): EOF
+        let fat_offset = 512;
+        disk[fat_offset..fat_offset + 5].copy_from_slice(&[0xF0, 0xFF, 0xFF, 0xFF, 0xFF]);
+
+        let root_offset = 512 + (2 * 9 * 512);
+        disk[root_offset..root_offset + 11].copy_from_slice(b"DOCS       ");
+        disk[root_offset + 11] = DirectoryEntry::ATTR_DIRECTORY;
+        disk[root_offset + 26] = 2;
+
+        // DOCS contains SUB and README.TXT; SUB contains NOTES.TXT
+        let data_offset = 16896;
+        disk[data_offset..data_offset + 11].copy_from_slice(b"SUB        ");
+        disk[data_offset + 11] = DirectoryEntry::ATTR_DIRECTORY;
+        disk[data_offset + 26] = 3;
+        disk[data_offset + 32..data_offset + 43].copy_from_slice(b"README  TXT");
+        disk[data_offset + 43] = 0x20;
+        let sub_offset = data_offset + 512;
+        disk[sub_offset..sub_offset + 11].copy_from_slice(b"NOTES   TXT");
+        disk[sub_offset + 11] = 0x20;
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        let paths = |path: &str, cursor: &mut Cursor<Vec<u8>>| -> Vec<String> {
+            territory
+                .list_directory(cursor, path)
+                .unwrap()
+                .into_iter()
+                .map(|o| o.path.unwrap())
+                .collect()
+        };
+        assert_eq!(paths("/", &mut cursor), ["/DOCS"]);
+        assert_eq!(paths("DOCS", &mut cursor), ["/DOCS/SUB", "/DOCS/README.TXT"]);
+        assert_eq!(paths("\\DOCS\\SUB\\", &mut cursor), ["/DOCS/SUB/NOTES.TXT"]);
+
+        // Cells only know names
+        let root = territory.headquarters().unwrap();
+        assert!(root.list_occupants(&mut cursor).unwrap()[0].path.is_none());
+        assert!(territory.list_directory(&mut cursor, "DOCS/README.TXT").is_err());
+    }
+
+    #[test]
+    fn test_list_directory_with_options() {
+        let boot_sector = create_fat12_boot_sector();
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&boot_sector);
+
+        // Cluster 2 (DOCS): EOF
+        let fat_offset = 512;
+        disk[fat_offset..fat_offset + 4].copy_from_slice(&[0xF0, 0xFF, 0xFF, 0xFF]);
+
+        let root_offset = 512 + (2 * 9 * 512);
+        disk[root_offset..root_offset + 11].copy_from_slice(b"DOCS       ");
+        disk[root_offset + 11] = DirectoryEntry::ATTR_DIRECTORY;
+        disk[root_offset + 26] = 2;
+
+        let data_offset = 16896;
+        let files: [(&[u8; 11], u32); 4] = [
+            (b"SMALL   TXT", 10),
+            (b"BIG     BIN", 9000),
+            (b"LARGE   TXT", 5000),
+            (b"MEDIUM  TXT", 300),
+        ];
+        for (i, (name, size)) in files.iter().enumerate() {
+            let offset = data_offset + i * 32;
+            disk[offset..offset + 11].copy_from_slice(*name);
+            disk[offset + 11] = 0x20;
+            disk[offset + 28..offset + 32].copy_from_slice(&size.to_le_bytes());
+        }
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+        let names = |options: &ListOptions, cursor: &mut Cursor<Vec<u8>>| -> Vec<String> {
+            territory
+                .list_directory_with(cursor, "/DOCS", options)
+                .unwrap()
+                .into_iter()
+                .map(|o| o.name)
+                .collect()
+        };
+
+        assert_eq!(
+            names(&ListOptions::default(), &mut cursor),
+            ["SMALL.TXT", "BIG.BIN", "LARGE.TXT", "MEDIUM.TXT"]
+        );
+
+        let by_size = ListOptions::parse(Some("-size"), None, None).unwrap();
+        assert_eq!(
+            names(&by_size, &mut cursor),
+            ["BIG.BIN", "LARGE.TXT", "MEDIUM.TXT", "SMALL.TXT"]
+        );
+
+        let text = ListOptions::parse(None, Some("*.txt"), None).unwrap();
+        assert_eq!(names(&text, &mut cursor), ["SMALL.TXT", "LARGE.TXT", "MEDIUM.TXT"]);
+
+        let text_by_size = ListOptions::parse(Some("-size"), Some("*.txt"), None).unwrap();
+        assert_eq!(names(&text_by_size, &mut cursor), ["LARGE.TXT", "MEDIUM.TXT", "SMALL.TXT"]);
+    }
+
+    #[test]
+    fn test_best_effort_listing() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+
+        // DAMAGED's chain 2 -> 2000 -> 3 runs through a cluster past the
+        // end of the truncated image
+        for (cluster, value) in [(0, 0xFF0), (1, 0xFFF), (2, 2000), (2000, 3), (3, 0xFFF)] {
+            set_fat12_entry(&mut disk, cluster, value);
+        }
+
+        let root_offset = 512 + (2 * 9 * 512);
+        disk[root_offset..root_offset + 11].copy_from_slice(b"DAMAGED    ");
+        disk[root_offset + 11] = DirectoryEntry::ATTR_DIRECTORY;
+        disk[root_offset + 26] = 2;
+
+        // A full first cluster, so the listing goes on past it
+        let cluster = |n: usize| 16896 + (n - 2) * 512;
+        let mut slots: Vec<(usize, String)> = (0..16)
+            .map(|i| (cluster(2) + i * 32, format!("FILE{:02}  TXT", i)))
+            .collect();
+        slots.push((cluster(3), "LAST    TXT".to_string()));
+        for (offset, name) in &slots {
+            disk[*offset..*offset + 11].copy_from_slice(name.as_bytes());
+            disk[*offset + 11] = 0x20;
+        }
+        disk.truncate(cluster(4));
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        assert!(territory.list_directory(&mut cursor, "/DAMAGED").is_err());
+
+        let options = ListOptions { best_effort: true, ..Default::default() };
+        let listing = territory.list_directory_partial(&mut cursor, "/DAMAGED", &options).unwrap();
+        assert_eq!(listing.errors, 1);
+        assert_eq!(listing.skipped.len(), 1);
+        assert_eq!(listing.skipped[0].0, "/DAMAGED");
+        assert!(listing.skipped[0].1.starts_with("Unreadable directory slots at "));
+        assert_eq!(listing.occupants.len(), 17);
+        assert_eq!(listing.occupants[0].name, "FILE00.TXT");
+        assert_eq!(listing.occupants[16].path.as_deref(), Some("/DAMAGED/LAST.TXT"));
+
+        // An intact directory lists without errors
+        let root = territory.list_directory_partial(&mut cursor, "/", &options).unwrap();
+        assert_eq!(root.occupants.len(), 1);
+        assert_eq!(root.errors, 0);
+        assert!(root.skipped.is_empty());
+
+        // A best-effort walk carries on through the damaged directory
+        assert!(territory.walk(&mut cursor, "/", None).is_err());
+        let walked = territory.walk_partial(&mut cursor, "/", true, None).unwrap();
+        assert_eq!(walked.errors, 1);
+        assert_eq!(walked.skipped, listing.skipped);
+        assert_eq!(walked.occupants.len(), 18);
+        assert_eq!(walked.occupants[17].path.as_deref(), Some("/DAMAGED/LAST.TXT"));
+    }
+
+    #[test]
+    fn test_list_directory_page() {
+        // Long names take several slots each, so the FAT32 root spans many
+        // 512-byte clusters, with gaps left by deleted files
+        let mut writer = FatWriter::open(Cursor::new(blank_fat32_volume())).unwrap();
+        for i in 0..150 {
+            writer.create_file(&format!("Report number {:03}.txt", i), b"x").unwrap();
+        }
+        for i in (0..150).step_by(7) {
+            writer.delete_file(&format!("Report number {:03}.txt", i)).unwrap();
+        }
+        let mut cursor = writer.into_inner();
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        let all: Vec<String> = territory
+            .list_directory(&mut cursor, "/")
+            .unwrap()
+            .into_iter()
+            .map(|o| o.name)
+            .collect();
+        assert_eq!(all.len(), 150 - 22);
+
+        let mut paged = Vec::new();
+        let mut next = Some(0);
+        while let Some(position) = next {
+            let page = territory.list_directory_page(&mut cursor, "/", position, 9).unwrap();
+            assert!(page.occupants.len() == 9 || page.next.is_none());
+            assert!(page.next.is_none_or(|n| n > position));
+            assert_eq!(page.occupants[0].path.as_deref(), Some(format!("/{}", page.occupants[0].name).as_str()));
+            paged.extend(page.occupants.into_iter().map(|o| o.name));
+            next = page.next;
+        }
+        assert_eq!(paged, all);
+
+        // A cursor handed out earlier still resumes at the same entry
+        let first = territory.list_directory_page(&mut cursor, "/", 0, 50).unwrap();
+        let resumed = territory
+            .list_directory_page(&mut cursor, "/", first.next.unwrap(), 1)
+            .unwrap();
+        assert_eq!(resumed.occupants[0].name, all[50]);
+
+        // A zero limit returns nothing and leaves the cursor in place
+        let zero = territory.list_directory_page(&mut cursor, "/", first.next.unwrap(), 0).unwrap();
+        assert!(zero.occupants.is_empty());
+        assert_eq!(zero.next, first.next);
+    }
+
+    #[test]
+    fn test_unicode_normalized_lookup() {
+        // Stored with a combining accent (NFD), as macOS writes names, and
+        // looked up precomposed (NFC)
+        let nfd = "Cafe\u{301} menu.txt";
+        let nfc = "/Caf\u{e9} menu.txt";
+        let mut writer = FatWriter::open(Cursor::new(blank_fat32_volume())).unwrap();
+        writer.create_file(nfd, b"espresso").unwrap();
+        let mut cursor = writer.into_inner();
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        assert!(matches!(territory.file_metadata_with(&mut cursor, nfc, false), Err(Error::NotFound(_))));
+        let info = territory.file_metadata_with(&mut cursor, nfc, true).unwrap();
+        assert_eq!(info.name, nfd);
+        assert_eq!(info.size, 8);
+        assert_eq!(territory.resolve_path(&mut cursor, nfc).unwrap(), format!("/{}", nfd));
+
+        let options = ListOptions {
+            pattern: Some("Caf\u{e9}*".to_string()),
+            normalize_unicode: true,
+            ..Default::default()
+        };
+        let listing = territory.list_directory_with(&mut cursor, "/", &options).unwrap();
+        assert_eq!(listing.len(), 1);
+        assert_eq!(listing[0].path.as_deref(), Some(format!("/{}", nfd).as_str()));
+    }
+
+    #[test]
+    fn test_read_file_range() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+        disk[512..515].copy_from_slice(&[0xF0, 0xFF, 0xFF]);
+        disk[512 + 9 * 512..515 + 9 * 512].copy_from_slice(&[0xF0, 0xFF, 0xFF]);
+
+        let data: Vec<u8> = (0..3000u32).map(|i| (i % 251) as u8).collect();
+        let mut writer = FatWriter::open(Cursor::new(disk)).unwrap();
+        let entry = writer.create_file("DATA.BIN", &data).unwrap();
+        let mut cursor = writer.into_inner();
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        // Ranges starting mid-cluster and spanning cluster boundaries
+        for (offset, length) in [(0, 3000), (0, 512), (100, 1000), (511, 2), (2900, 500)] {
+            let range = territory.read_file_range(&mut cursor, &entry, offset, length).unwrap();
+            let end = (offset + length).min(3000) as usize;
+            assert_eq!(range, &data[offset as usize..end], "{}+{}", offset, length);
+        }
+
+        assert!(territory.read_file_range(&mut cursor, &entry, 3000, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_territory_read_file_range() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+        disk[512..515].copy_from_slice(&[0xF0, 0xFF, 0xFF]);
+        disk[512 + 9 * 512..515 + 9 * 512].copy_from_slice(&[0xF0, 0xFF, 0xFF]);
+
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let mut writer = FatWriter::open(Cursor::new(disk)).unwrap();
+        writer.create_file("DATA.BIN", &data).unwrap();
+        let mut cursor = writer.into_inner();
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+        let territory: &dyn Territory = &territory;
+
+        let full = territory.read_file_range(&mut cursor, "/DATA.BIN", 0, 10_000).unwrap();
+        assert_eq!(full, data);
+        let window = territory.read_file_range(&mut cursor, "/DATA.BIN", 4096, 4096).unwrap();
+        assert_eq!(window, &full[4096..8192]);
+
+        let err = territory.read_file_range(&mut cursor, "/DATA.BIN", 8192, 2000).unwrap_err();
+        assert!(matches!(err, Error::InvalidOperation(_)), "{:?}", err);
+        assert!(territory.read_file_range(&mut cursor, "/MISSING.BIN", 0, 1).is_err());
+    }
+
+    #[test]
+    fn test_data_runs_fragmented_file() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+
+        // FRAG.BIN occupies clusters 2 -> 5 -> 6 -> 9
+        for (cluster, value) in [(0, 0xFF0), (1, 0xFFF), (2, 5), (5, 6), (6, 9), (9, 0xFFF)] {
+            set_fat12_entry(&mut disk, cluster, value);
+        }
+
+        let root_offset = 512 + (2 * 9 * 512);
+        disk[root_offset..root_offset + 11].copy_from_slice(b"FRAG    BIN");
+        disk[root_offset + 26] = 2;
+        disk[root_offset + 28..root_offset + 32].copy_from_slice(&1800u32.to_le_bytes());
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+        assert_eq!(territory.get_cluster_chain(2), vec![2, 5, 6, 9]);
+
+        // Clusters 5 and 6 are adjacent; the last extent stops at the file size
+        let cluster = |n: u64| 16896 + (n - 2) * 512;
+        assert_eq!(
+            territory.data_runs(&mut cursor, "/frag.bin").unwrap(),
+            vec![(cluster(2), 512), (cluster(5), 1024), (cluster(9), 264)]
+        );
+
+        let err = territory.data_runs(&mut cursor, "/MISSING.BIN").unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)));
+    }
+
+    #[test]
+    fn test_file_slack() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+        for (cluster, value) in [(0, 0xFF0), (1, 0xFFF), (2, 3), (3, 0xFFF), (4, 0xFFF)] {
+            set_fat12_entry(&mut disk, cluster, value);
+        }
+
+        // TAIL.BIN holds 700 bytes in clusters 2 -> 3; EXACT.BIN fills cluster 4
+        let root_offset = 512 + (2 * 9 * 512);
+        for (slot, name, cluster, size) in [(0, b"TAIL    BIN", 2u8, 700u32), (1, b"EXACT   BIN", 4, 512)] {
+            let entry = root_offset + slot * 32;
+            disk[entry..entry + 11].copy_from_slice(name);
+            disk[entry + 26] = cluster;
+            disk[entry + 28..entry + 32].copy_from_slice(&size.to_le_bytes());
+        }
+
+        // Plant data after the end of TAIL.BIN in its last cluster
+        let cluster = |n: usize| 16896 + (n - 2) * 512;
+        let planted: Vec<u8> = (0..324).map(|i| i as u8 ^ 0x5A).collect();
+        disk[cluster(3) + 188..cluster(4)].copy_from_slice(&planted);
+        disk[cluster(4)..cluster(5)].fill(0xEE);
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+        assert_eq!(territory.file_slack(&mut cursor, "/TAIL.BIN").unwrap(), planted);
+        assert!(territory.file_slack(&mut cursor, "/EXACT.BIN").unwrap().is_empty());
+
+        let err = territory.file_slack(&mut cursor, "/MISSING.BIN").unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)));
+    }
+
+    #[test]
+    fn test_directory_cells() {
+        let boot_sector = create_fat12_boot_sector();
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&boot_sector);
+
+        // Cluster 2: EOF (for DOCS)
+        let fat_offset = 512;
+        disk[fat_offset..fat_offset + 5].copy_from_slice(&[0xF0, 0xFF, 0xFF, 0xF8, 0x0F]);
+
+        // Root: DOCS directory and a 5-byte file
+        let root_offset = 512 + (2 * 9 * 512);
+        disk[root_offset..root_offset + 11].copy_from_slice(b"DOCS       ");
+        disk[root_offset + 11] = DirectoryEntry::ATTR_DIRECTORY;
+        disk[root_offset + 26] = 2;
+        disk[root_offset + 32..root_offset + 43].copy_from_slice(b"HELLO   TXT");
+        disk[root_offset + 43] = 0x20;
+        disk[root_offset + 60] = 5;
+
+        // DOCS contains README.TXT
+        let data_offset = 16896;
+        disk[data_offset..data_offset + 11].copy_from_slice(b"README  TXT");
+        disk[data_offset + 11] = 0x20;
+        disk[data_offset + 28] = 100;
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        let root = territory.headquarters().unwrap();
+        assert_eq!(root.name(), "/");
+        let occupants = root.list_occupants(&mut cursor).unwrap();
+        let names: Vec<&str> = occupants.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, ["DOCS", "HELLO.TXT"]);
+        assert!(occupants.iter().all(|o| o.short_name.is_none()));
+        assert!(occupants[0].is_directory);
+        assert_eq!(occupants[1].size, 5);
+        assert!(root.exists(&mut cursor, "HELLO.TXT").unwrap());
+
+        let docs = root.enter(&mut cursor, "docs").unwrap();
+        assert_eq!(docs.name(), "DOCS");
+        let readme = docs.get_occupant(&mut cursor, "README.TXT").unwrap().unwrap();
+        assert_eq!(readme.size, 100);
+
+        let docs = territory.navigate_to(&mut cursor, "/DOCS/").unwrap();
+        assert_eq!(docs.list_occupants(&mut cursor).unwrap().len(), 1);
+
+        assert!(root.enter(&mut cursor, "HELLO.TXT").is_err());
+        assert!(root.enter(&mut cursor, "MISSING").is_err());
+        assert!(territory.navigate_to(&mut cursor, "DOCS/README.TXT").is_err());
+    }
+
+    #[test]
+    fn test_volume_label() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+        disk[38] = 0x29; // Extended boot signature
+        disk[43..54].copy_from_slice(b"BPB LABEL  ");
+
+        // BPB label alone
+        let territory = FatTerritory::parse(&mut Cursor::new(disk.clone())).unwrap();
+        assert_eq!(territory.volume_label(), "BPB LABEL");
+
+        // A root directory label entry takes precedence, after other entries
+        let root_offset = 512 + (2 * 9 * 512);
+        disk[root_offset..root_offset + 11].copy_from_slice(b"README  TXT");
+        disk[root_offset + 11] = 0x20;
+        disk[root_offset + 32..root_offset + 43].copy_from_slice(b"ROOT LABEL ");
+        disk[root_offset + 43] = DirectoryEntry::ATTR_VOLUME_ID | DirectoryEntry::ATTR_ARCHIVE;
+
+        let mut cursor = Cursor::new(disk.clone());
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+        assert_eq!(territory.volume_label(), "ROOT LABEL");
+        assert_eq!(territory.banner().unwrap(), "ROOT LABEL");
+        assert_eq!(territory.read_root_directory(&mut cursor).unwrap().len(), 1);
+
+        // "NO NAME" means unlabelled
+        disk[root_offset + 32..root_offset + 43].copy_from_slice(b"NO NAME    ");
+        disk[43..54].copy_from_slice(b"NO NAME    ");
+        let territory = FatTerritory::parse(&mut Cursor::new(disk.clone())).unwrap();
+        assert_eq!(territory.banner().unwrap(), "");
+
+        // Without the extended boot signature the BPB label is not present
+        disk[root_offset + 32] = 0xE5; // Deleted label entry is ignored
+        disk[38] = 0;
+        disk[43..54].copy_from_slice(b"STALE      ");
+        let territory = FatTerritory::parse(&mut Cursor::new(disk)).unwrap();
+        assert_eq!(territory.volume_label(), "");
+    }
+
+    #[test]
+    fn test_fat32_bpb_volume_label() {
+        let mut disk = blank_fat32_volume();
+        disk[66] = 0x29;
+        disk[71..82].copy_from_slice(b"DATA32     ");
+
+        let territory = FatTerritory::parse(&mut Cursor::new(disk)).unwrap();
+        assert_eq!(territory.volume_label(), "DATA32");
+    }
+
+    #[test]
+    fn test_extraction_limit() {
+        let boot_sector = create_fat12_boot_sector();
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&boot_sector);
+
+        // Cluster 2: EOF
+        let fat_offset = 512;
+        disk[fat_offset..fat_offset + 5].copy_from_slice(&[0xF0, 0xFF, 0xFF, 0xF8, 0x0F]);
+
+        // 100-byte file in cluster 2
+        let root_offset = 512 + (2 * 9 * 512);
+        disk[root_offset..root_offset + 11].copy_from_slice(b"DATA    BIN");
+        disk[root_offset + 11] = 0x20;
+        disk[root_offset + 26] = 2;
+        disk[root_offset + 28] = 100;
+        disk[16896..16996].fill(0x5A);
+
+        let strict = SecurityLimits {
+            max_file_extract_size: 64,
+            ..Default::default()
+        };
+        let mut cursor = Cursor::new(disk);
+        let mut territory = FatTerritory::parse_with_limits(&mut cursor, strict).unwrap();
+        assert_eq!(territory.limits().max_file_extract_size, 64);
+        assert!(territory.read_file_by_path(&mut cursor, "DATA.BIN").is_err());
+
+        // Raising the limit permits the same extraction
+        territory.set_limits(SecurityLimits {
+            max_file_extract_size: 1024,
+            ..strict
+        });
+        let data = territory.read_file_by_path(&mut cursor, "DATA.BIN").unwrap();
+        assert_eq!(data, vec![0x5A; 100]);
+
+        // A FAT table larger than the limit is rejected at parse time
+        let tiny_fat = SecurityLimits {
+            max_fat_table_size: 512,
+            ..Default::default()
+        };
+        assert!(FatTerritory::parse_with_limits(&mut cursor, tiny_fat).is_err());
+    }
+
+    #[test]
+    fn test_read_deleted_entries() {
+        let boot_sector = create_fat12_boot_sector();
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&boot_sector);
+
+        let fat_offset = 512;
+        disk[fat_offset] = 0xF0;
+        disk[fat_offset + 1] = 0xFF;
+        disk[fat_offset + 2] = 0xFF;
+
+        // Live file at cluster 2 (FAT entry: EOF)
+        disk[fat_offset + 3] = 0xFF;
+        disk[fat_offset + 4] = 0x0F;
+
+        let root_offset = 512 + (2 * 9 * 512);
+        disk[root_offset..root_offset + 11].copy_from_slice(b"LIVE    TXT");
+        disk[root_offset + 11] = 0x20;
+        disk[root_offset + 26] = 2;
+        disk[root_offset + 28] = 4;
+
+        // Deleted 600-byte file whose chain (clusters 5-6) was cleared
+        let deleted_offset = root_offset + 32;
+        disk[deleted_offset..deleted_offset + 11].copy_from_slice(b"SECRET  TXT");
+        disk[deleted_offset] = 0xE5;
+        disk[deleted_offset + 11] = 0x20;
+        disk[deleted_offset + 26] = 5;
+        disk[deleted_offset + 28..deleted_offset + 32].copy_from_slice(&600u32.to_le_bytes());
+
+        let data_offset = 16896 + 3 * 512; // Cluster 5
+        for (i, byte) in disk[data_offset..data_offset + 600].iter_mut().enumerate() {
+            *byte = (i % 251) as u8;
+        }
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        // Deleted entries stay hidden from regular listings
+        let live = territory.read_root_directory(&mut cursor).unwrap();
+        assert_eq!(live.len(), 1);
+
+        let deleted = territory.read_deleted_entries(&mut cursor, "/").unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].entry.name, "?ECRET.TXT");
+        assert_eq!(deleted[0].entry.file_size, 600);
+        assert_eq!(deleted[0].recoverability, Recoverability::Contiguous);
+
+        let data = territory.recover_deleted_file(&mut cursor, &deleted[0]).unwrap();
+        assert_eq!(data.len(), 600);
+        assert!(data.iter().enumerate().all(|(i, &b)| b == (i % 251) as u8));
+    }
+
+    #[test]
+    fn test_raw_directory_slots() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+        for fat in 0..2 {
+            let offset = 512 + fat * 9 * 512;
+            disk[offset..offset + 3].copy_from_slice(&[0xF0, 0xFF, 0xFF]);
+        }
+        let root_offset = 512 + 2 * 9 * 512;
+        disk[root_offset..root_offset + 11].copy_from_slice(b"MYDISK     ");
+        disk[root_offset + 11] = DirectoryEntry::ATTR_VOLUME_ID;
+
+        let mut writer = FatWriter::open(Cursor::new(disk)).unwrap();
+        writer.create_file("Quarterly Report.txt", b"numbers").unwrap();
+        writer.create_file("GONE.TXT", b"secret").unwrap();
+        writer.delete_file("GONE.TXT").unwrap();
+        let mut cursor = writer.into_inner();
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        let slots = territory.raw_directory_slots(&mut cursor, "/").unwrap();
+        assert_eq!(slots.len(), 224);
+        let kinds: Vec<SlotKind> = slots.iter().take(6).map(|slot| slot.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                SlotKind::VolumeLabel,
+                SlotKind::Lfn,
+                SlotKind::Lfn,
+                SlotKind::File,
+                SlotKind::Deleted,
+                SlotKind::End,
+            ]
+        );
+        assert!(slots[5..].iter().all(|slot| slot.kind == SlotKind::End));
+
+        for (i, slot) in slots.iter().enumerate() {
+            let offset = root_offset + i * 32;
+            assert_eq!(slot.offset, offset as u64);
+            assert_eq!(slot.bytes[..], cursor.get_ref()[offset..offset + 32]);
+        }
+        assert_eq!(&slots[3].bytes[0..11], b"QUARTE~1TXT");
+        assert_eq!(&slots[4].bytes[1..11], b"ONE    TXT");
+
+        assert!(territory.raw_directory_slots(&mut cursor, "/QUARTE~1.TXT").is_err());
+    }
+
+    #[test]
+    fn test_deleted_entry_overwritten() {
+        let boot_sector = create_fat12_boot_sector();
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&boot_sector);
+
+        let fat_offset = 512;
+        disk[fat_offset] = 0xF0;
+        disk[fat_offset + 1] = 0xFF;
+        disk[fat_offset + 2] = 0xFF;
+        // Cluster 2: EOF (reused by another file)
+        disk[fat_offset + 3] = 0xFF;
+        disk[fat_offset + 4] = 0x0F;
+
+        let root_offset = 512 + (2 * 9 * 512);
+        disk[root_offset..root_offset + 11].copy_from_slice(b"OLD     BIN");
+        disk[root_offset] = 0xE5;
+        disk[root_offset + 26] = 2;
+        disk[root_offset + 28..root_offset + 32].copy_from_slice(&1024u32.to_le_bytes());
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        let deleted = territory.read_deleted_entries(&mut cursor, "").unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].recoverability, Recoverability::Overwritten);
+    }
+
+    #[test]
+    fn test_deleted_entry_clusters_reallocated() {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..512].copy_from_slice(&create_fat12_boot_sector());
+        for fat in 0..2 {
+            let offset = 512 + fat * 9 * 512;
+            disk[offset..offset + 3].copy_from_slice(&[0xF0, 0xFF, 0xFF]);
+        }
+
+        let mut writer = FatWriter::open(Cursor::new(disk)).unwrap();
+        let new = writer.create_file("NEW.TXT", &[0x22; 600]).unwrap();
+        let mut disk = writer.into_inner().into_inner();
+
+        // A deleted OLD.TXT of the same length whose clusters NEW.TXT took over
+        let root_offset = 512 + 2 * 9 * 512;
+        disk.copy_within(root_offset..root_offset + 32, root_offset + 32);
+        disk[root_offset + 32..root_offset + 43].copy_from_slice(b"\xE5LD     TXT");
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        let deleted = territory.read_deleted_entries(&mut cursor, "/").unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].entry.first_cluster(), new.first_cluster());
+        assert_eq!(territory.get_cluster_chain(new.first_cluster()).len(), 2);
+        assert_eq!(deleted[0].recoverability, Recoverability::Overwritten);
+    }
+
     #[test]
     fn test_territory_methods() {
         let boot_sector = create_fat12_boot_sector();
@@ -795,6 +2393,53 @@ mod tests {
         assert!(territory.hierarchical());
         assert!(territory.banner().is_ok());
         assert!(territory.headquarters().is_ok());
-        assert!(territory.extract_file("test.txt").is_ok());
+
+        // Extraction needs a territory that owns its stream
+        let err = territory.extract_file("test.txt").unwrap_err();
+        assert!(matches!(err, Error::Unsupported(_)), "{:?}", err);
+        let mut territory = FatTerritory::from_reader(cursor).unwrap();
+        let err = territory.extract_file("test.txt").unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_lazy_fat_matches_eager() {
+        // Chains of several lengths, with the FAT entries of two deleted files freed
+        let mut writer = FatWriter::open(Cursor::new(blank_fat32_volume())).unwrap();
+        for (name, size) in [("A.BIN", 1500), ("B.BIN", 700), ("C.BIN", 2100), ("D.BIN", 300)] {
+            writer.create_file(name, &vec![0xA5; size]).unwrap();
+        }
+        writer.delete_file("/B.BIN").unwrap();
+        writer.delete_file("/D.BIN").unwrap();
+        let big: Vec<u8> = (0..40_000u32).map(|i| (i % 253) as u8).collect();
+        writer.create_file("BIG.BIN", &big).unwrap();
+        let disk = writer.into_inner().into_inner();
+
+        let mut cursor = Cursor::new(disk.clone());
+        let eager = FatTerritory::parse(&mut cursor).unwrap();
+        let mut lazy =
+            FatTerritory::from_reader_with(Cursor::new(disk.clone()), SecurityLimits::active(), FatLoading::Lazy)
+                .unwrap();
+        assert_eq!(eager.fat_loading(), FatLoading::Eager);
+        assert_eq!(lazy.fat_loading(), FatLoading::Lazy);
+
+        for path in ["/A.BIN", "/C.BIN", "/BIG.BIN"] {
+            let first = eager.find_file_by_path(&mut cursor, path).unwrap().first_cluster();
+            assert_eq!(lazy.get_cluster_chain(first), eager.get_cluster_chain(first), "{}", path);
+        }
+
+        // Every entry, with far more FAT sectors than the cache holds
+        let entries = (eager.fat_table.len() / 4) as u32;
+        assert!((0..entries).all(|cluster| lazy.raw_fat_entry(cluster) == eager.raw_fat_entry(cluster)));
+        assert_eq!(lazy.raw_fat_entry(entries), None);
+        assert_eq!(lazy.extract_file("/BIG.BIN").unwrap(), big);
+
+        // Only an eagerly loaded FAT is held to the size limit
+        let limits = SecurityLimits { max_fat_table_size: 512, ..SecurityLimits::active() };
+        assert!(FatTerritory::from_reader_with(Cursor::new(disk.clone()), limits, FatLoading::Eager).is_err());
+        assert!(FatTerritory::from_reader_with(Cursor::new(disk.clone()), limits, FatLoading::Lazy).is_ok());
+
+        // A FAT this small is loaded eagerly by default
+        assert_eq!(FatTerritory::from_reader(Cursor::new(disk)).unwrap().fat_loading(), FatLoading::Eager);
     }
 }
@@ -0,0 +1,112 @@
+//! The file allocation table, held in memory or read on demand
+
+use std::fmt;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::Mutex;
+
+use totalimage_core::Result;
+use totalimage_pipeline::{CachingPipeline, PartialPipeline, SharedPipeline};
+
+/// FAT sectors kept in memory by a lazily loaded table
+const CACHED_SECTORS: usize = 64;
+
+/// How [`FatTerritory`](super::FatTerritory) loads the FAT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FatLoading {
+    /// Load eagerly up to [`FatLoading::AUTO_LAZY_THRESHOLD`] bytes of FAT,
+    /// lazily beyond it
+    #[default]
+    Auto,
+    /// Read the whole FAT into memory while parsing
+    Eager,
+    /// Read FAT sectors as entries are looked up, keeping the most recently
+    /// used ones in a small cache
+    Lazy,
+}
+
+impl FatLoading {
+    /// Largest FAT [`FatLoading::Auto`] reads into memory
+    pub const AUTO_LAZY_THRESHOLD: u64 = 4 * 1024 * 1024;
+}
+
+/// The FAT of one volume
+pub(crate) enum FatTable {
+    /// The whole first FAT, read while parsing
+    Loaded(Vec<u8>),
+    /// The first FAT, read a sector at a time through an LRU cache
+    Lazy {
+        length: usize,
+        sectors: Mutex<CachingPipeline<PartialPipeline<SharedPipeline>>>,
+    },
+}
+
+impl FatTable {
+    /// Read the `length`-byte FAT at `offset` of `stream` on demand
+    pub(crate) fn lazy(stream: SharedPipeline, offset: u64, length: u64, sector_size: usize) -> Result<Self> {
+        let window = PartialPipeline::new(stream, offset, length)?;
+        let sectors = CachingPipeline::with_capacity(window, sector_size, CACHED_SECTORS)?;
+        Ok(Self::Lazy {
+            length: length as usize,
+            sectors: Mutex::new(sectors),
+        })
+    }
+
+    /// Get the size of the FAT in bytes
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Self::Loaded(bytes) => bytes.len(),
+            Self::Lazy { length, .. } => *length,
+        }
+    }
+
+    /// Is the FAT read on demand?
+    pub(crate) fn is_lazy(&self) -> bool {
+        matches!(self, Self::Lazy { .. })
+    }
+
+    /// Get the FAT's bytes, if it is held in memory
+    pub(crate) fn bytes(&self) -> Option<&[u8]> {
+        match self {
+            Self::Loaded(bytes) => Some(bytes),
+            Self::Lazy { .. } => None,
+        }
+    }
+
+    /// Get the FAT's bytes for updating, if it is held in memory
+    pub(crate) fn bytes_mut(&mut self) -> Option<&mut [u8]> {
+        match self {
+            Self::Loaded(bytes) => Some(bytes),
+            Self::Lazy { .. } => None,
+        }
+    }
+
+    /// Read `N` bytes at `offset`
+    ///
+    /// Returns None if they run past the end of the FAT or, for a lazy
+    /// table, can't be read.
+    pub(crate) fn read<const N: usize>(&self, offset: usize) -> Option<[u8; N]> {
+        if offset.checked_add(N)? > self.len() {
+            return None;
+        }
+
+        let mut value = [0u8; N];
+        match self {
+            Self::Loaded(bytes) => value.copy_from_slice(&bytes[offset..offset + N]),
+            Self::Lazy { sectors, .. } => {
+                let mut sectors = sectors.lock().ok()?;
+                sectors.seek(SeekFrom::Start(offset as u64)).ok()?;
+                sectors.read_exact(&mut value).ok()?;
+            }
+        }
+        Some(value)
+    }
+}
+
+impl fmt::Debug for FatTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Loaded(bytes) => f.debug_tuple("Loaded").field(bytes).finish(),
+            Self::Lazy { length, .. } => f.debug_struct("Lazy").field("length", length).finish(),
+        }
+    }
+}
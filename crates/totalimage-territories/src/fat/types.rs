@@ -1,255 +1,29 @@
 //! FAT file system types and structures
 
-use std::fmt;
-use totalimage_core::{checked_multiply_u32_to_u64, checked_multiply_u64, Error, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use totalimage_core::{Error, Result};
 
-/// FAT type variants
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum FatType {
-    Fat12,
-    Fat16,
-    Fat32,
-}
-
-impl fmt::Display for FatType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            FatType::Fat12 => write!(f, "FAT12"),
-            FatType::Fat16 => write!(f, "FAT16"),
-            FatType::Fat32 => write!(f, "FAT32"),
-        }
-    }
-}
-
-/// BIOS Parameter Block (BPB) - Common to all FAT variants
+/// Convert a DOS (date, time) pair to chrono DateTime
 ///
-/// The BPB contains filesystem metadata and geometry information.
-#[derive(Debug, Clone)]
-pub struct BiosParameterBlock {
-    /// Bytes per sector (typically 512)
-    pub bytes_per_sector: u16,
-    /// Sectors per cluster (power of 2)
-    pub sectors_per_cluster: u8,
-    /// Number of reserved sectors (including boot sector)
-    pub reserved_sectors: u16,
-    /// Number of FAT copies (typically 2)
-    pub num_fats: u8,
-    /// Maximum root directory entries (FAT12/16 only, 0 for FAT32)
-    pub root_entries: u16,
-    /// Total sectors (16-bit, 0 if using 32-bit field)
-    pub total_sectors_16: u16,
-    /// Media descriptor byte
-    pub media_descriptor: u8,
-    /// Sectors per FAT (FAT12/16 only, 0 for FAT32)
-    pub sectors_per_fat_16: u16,
-    /// Sectors per track (for CHS addressing)
-    pub sectors_per_track: u16,
-    /// Number of heads (for CHS addressing)
-    pub num_heads: u16,
-    /// Hidden sectors (LBA offset of partition)
-    pub hidden_sectors: u32,
-    /// Total sectors (32-bit, used if total_sectors_16 is 0)
-    pub total_sectors_32: u32,
-    /// FAT type determined from cluster count
-    pub fat_type: FatType,
-}
-
-impl BiosParameterBlock {
-    /// Parse BPB from boot sector bytes
-    ///
-    /// # Security
-    /// Uses checked arithmetic to prevent integer overflow attacks
-    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() < 512 {
-            return Err(Error::invalid_territory("BPB too short".to_string()));
-        }
-
-        // Parse common BPB fields (offsets 11-35)
-        let bytes_per_sector = u16::from_le_bytes([bytes[11], bytes[12]]);
-        let sectors_per_cluster = bytes[13];
-        let reserved_sectors = u16::from_le_bytes([bytes[14], bytes[15]]);
-        let num_fats = bytes[16];
-        let root_entries = u16::from_le_bytes([bytes[17], bytes[18]]);
-        let total_sectors_16 = u16::from_le_bytes([bytes[19], bytes[20]]);
-        let media_descriptor = bytes[21];
-        let sectors_per_fat_16 = u16::from_le_bytes([bytes[22], bytes[23]]);
-        let sectors_per_track = u16::from_le_bytes([bytes[24], bytes[25]]);
-        let num_heads = u16::from_le_bytes([bytes[26], bytes[27]]);
-        let hidden_sectors = u32::from_le_bytes([bytes[28], bytes[29], bytes[30], bytes[31]]);
-        let total_sectors_32 = u32::from_le_bytes([bytes[32], bytes[33], bytes[34], bytes[35]]);
-
-        // Validate sectors_per_cluster to prevent divide by zero
-        if sectors_per_cluster == 0 {
-            return Err(Error::invalid_territory("Invalid sectors_per_cluster: 0".to_string()));
-        }
-
-        // Validate bytes_per_sector
-        if bytes_per_sector == 0 {
-            return Err(Error::invalid_territory("Invalid bytes_per_sector: 0".to_string()));
-        }
-
-        // Determine total sectors
-        let total_sectors = if total_sectors_16 != 0 {
-            total_sectors_16 as u32
-        } else {
-            total_sectors_32
-        };
-
-        // Calculate data region size to determine FAT type (with checked arithmetic)
-        let root_entries_bytes = checked_multiply_u32_to_u64(root_entries as u32, 32, "BPB root entries")?;
-        let bytes_per_sector_minus_1 = bytes_per_sector.saturating_sub(1) as u64;
-        let root_dir_sectors = ((root_entries_bytes + bytes_per_sector_minus_1) / bytes_per_sector as u64) as u32;
-
-        let sectors_per_fat = if sectors_per_fat_16 != 0 {
-            sectors_per_fat_16 as u32
-        } else {
-            // FAT32: read from offset 36
-            u32::from_le_bytes([bytes[36], bytes[37], bytes[38], bytes[39]])
-        };
-
-        // Calculate FAT size with checked arithmetic
-        let fat_size = checked_multiply_u32_to_u64(num_fats as u32, sectors_per_fat, "BPB FAT size")?;
-
-        // Calculate total non-data sectors
-        let non_data_sectors = (reserved_sectors as u64)
-            .checked_add(fat_size)
-            .and_then(|v| v.checked_add(root_dir_sectors as u64))
-            .ok_or_else(|| Error::invalid_territory("BPB sector calculation overflow".to_string()))?;
-
-        // Calculate data sectors with overflow check
-        let data_sectors = (total_sectors as u64)
-            .checked_sub(non_data_sectors)
-            .ok_or_else(|| Error::invalid_territory("BPB data sectors underflow".to_string()))? as u32;
-
-        let cluster_count = data_sectors / sectors_per_cluster as u32;
-
-        // Determine FAT type based on cluster count
-        let fat_type = if cluster_count < 4085 {
-            FatType::Fat12
-        } else if cluster_count < 65525 {
-            FatType::Fat16
-        } else {
-            FatType::Fat32
-        };
-
-        Ok(Self {
-            bytes_per_sector,
-            sectors_per_cluster,
-            reserved_sectors,
-            num_fats,
-            root_entries,
-            total_sectors_16,
-            media_descriptor,
-            sectors_per_fat_16,
-            sectors_per_track,
-            num_heads,
-            hidden_sectors,
-            total_sectors_32,
-            fat_type,
-        })
+/// FAT records wall-clock time with no zone, so the result is that time
+/// read as UTC. Returns None for a zero date or an impossible one.
+pub fn dos_time_to_datetime(date: u16, time: u16) -> Option<DateTime<Utc>> {
+    if date == 0 {
+        return None;
     }
 
-    /// Get the total number of sectors
-    pub fn total_sectors(&self) -> u32 {
-        if self.total_sectors_16 != 0 {
-            self.total_sectors_16 as u32
-        } else {
-            self.total_sectors_32
-        }
-    }
-
-    /// Get sectors per FAT
-    pub fn sectors_per_fat(&self) -> u32 {
-        if self.sectors_per_fat_16 != 0 {
-            self.sectors_per_fat_16 as u32
-        } else {
-            // Would need to read FAT32 extended BPB
-            0
-        }
-    }
-
-    /// Calculate the byte offset of the first FAT
-    ///
-    /// # Security
-    /// Uses checked arithmetic to prevent overflow
-    pub fn fat_offset(&self) -> Result<u32> {
-        checked_multiply_u32_to_u64(
-            self.reserved_sectors as u32,
-            self.bytes_per_sector as u32,
-            "FAT offset"
-        ).and_then(|v| {
-            v.try_into().map_err(|_| Error::invalid_territory("FAT offset exceeds u32".to_string()))
-        })
-    }
+    let year = 1980 + (date >> 9) as i32;
+    let month = ((date >> 5) & 0x0F) as u32;
+    let day = (date & 0x1F) as u32;
+    let hour = (time >> 11) as u32;
+    let minute = ((time >> 5) & 0x3F) as u32;
+    let second = (time & 0x1F) as u32 * 2;
 
-    /// Calculate the byte offset of the root directory
-    ///
-    /// # Security
-    /// Uses checked arithmetic to prevent overflow
-    pub fn root_dir_offset(&self) -> Result<u32> {
-        let fat_size = checked_multiply_u32_to_u64(
-            self.sectors_per_fat(),
-            self.bytes_per_sector as u32,
-            "FAT size"
-        )?;
-
-        let total_fat_size = checked_multiply_u64(
-            self.num_fats as u64,
-            fat_size,
-            "Total FAT size"
-        )?;
-
-        let fat_offset = self.fat_offset()? as u64;
-
-        fat_offset
-            .checked_add(total_fat_size)
-            .and_then(|v| v.try_into().ok())
-            .ok_or_else(|| Error::invalid_territory("Root dir offset overflow".to_string()))
-    }
-
-    /// Calculate the byte offset of the data region
-    ///
-    /// # Security
-    /// Uses checked arithmetic to prevent overflow
-    pub fn data_offset(&self) -> Result<u32> {
-        let root_entries_bytes = checked_multiply_u32_to_u64(
-            self.root_entries as u32,
-            32,
-            "Root entries size"
-        )?;
-
-        let bytes_per_sector_minus_1 = self.bytes_per_sector.saturating_sub(1) as u64;
-        let root_dir_sectors = ((root_entries_bytes + bytes_per_sector_minus_1) / self.bytes_per_sector as u64) as u32;
-
-        let root_dir_size = checked_multiply_u32_to_u64(
-            root_dir_sectors,
-            self.bytes_per_sector as u32,
-            "Root dir size"
-        )?;
-
-        let root_offset = self.root_dir_offset()? as u64;
-
-        root_offset
-            .checked_add(root_dir_size)
-            .and_then(|v| v.try_into().ok())
-            .ok_or_else(|| Error::invalid_territory("Data offset overflow".to_string()))
-    }
-
-    /// Get bytes per cluster
-    ///
-    /// # Security
-    /// Uses checked arithmetic to prevent overflow
-    pub fn bytes_per_cluster(&self) -> Result<u32> {
-        checked_multiply_u32_to_u64(
-            self.sectors_per_cluster as u32,
-            self.bytes_per_sector as u32,
-            "Bytes per cluster"
-        ).and_then(|v| {
-            v.try_into().map_err(|_| Error::invalid_territory("Bytes per cluster exceeds u32".to_string()))
-        })
-    }
+    Some(NaiveDate::from_ymd_opt(year, month, day)?.and_hms_opt(hour, minute, second)?.and_utc())
 }
 
+pub use totalimage_zones::bpb::{BiosParameterBlock, FatType};
+
 /// FAT directory entry (32 bytes)
 #[derive(Debug, Clone)]
 pub struct DirectoryEntry {
@@ -277,6 +51,139 @@ pub struct DirectoryEntry {
     pub file_size: u32,
 }
 
+/// Decode an 11-byte volume label, trimming trailing spaces
+///
+/// Returns `None` for a blank label or the "NO NAME" placeholder that
+/// formatters write when no label was given.
+pub fn decode_volume_label(bytes: &[u8]) -> Option<String> {
+    let label: String = bytes.iter().map(|&b| b as char).collect();
+    let label = label.trim_end_matches([' ', '\0']);
+
+    if label.is_empty() || label == "NO NAME" {
+        None
+    } else {
+        Some(label.to_string())
+    }
+}
+
+/// FAT32 FSInfo sector
+///
+/// Holds advisory hints maintained by the driver: the last known free
+/// cluster count and where to start searching for free clusters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FsInfo {
+    /// Last known free cluster count (`UNKNOWN` if not maintained)
+    pub free_cluster_count: u32,
+    /// Hint for the next free cluster (`UNKNOWN` if not maintained)
+    pub next_free_cluster: u32,
+}
+
+impl FsInfo {
+    /// Lead signature at offset 0 ("RRaA")
+    pub const LEAD_SIGNATURE: u32 = 0x41615252;
+    /// Structure signature at offset 484 ("rrAa")
+    pub const STRUCT_SIGNATURE: u32 = 0x61417272;
+    /// Trail signature at offset 508
+    pub const TRAIL_SIGNATURE: u32 = 0xAA550000;
+    /// Value meaning a hint is unknown
+    pub const UNKNOWN: u32 = 0xFFFFFFFF;
+
+    /// Parse the FSInfo sector, validating all three signatures
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 512 {
+            return Err(Error::truncated("FSInfo sector too short".to_string()));
+        }
+
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+        };
+
+        if read_u32(0) != Self::LEAD_SIGNATURE
+            || read_u32(484) != Self::STRUCT_SIGNATURE
+            || read_u32(508) != Self::TRAIL_SIGNATURE
+        {
+            return Err(Error::unrecognized_signature("Invalid FSInfo signature".to_string()));
+        }
+
+        Ok(Self {
+            free_cluster_count: read_u32(488),
+            next_free_cluster: read_u32(492),
+        })
+    }
+
+    /// Write the hint fields into an existing FSInfo sector
+    pub fn write_to(&self, bytes: &mut [u8]) {
+        bytes[488..492].copy_from_slice(&self.free_cluster_count.to_le_bytes());
+        bytes[492..496].copy_from_slice(&self.next_free_cluster.to_le_bytes());
+    }
+}
+
+/// How much of a deleted file's data can be trusted on recovery
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recoverability {
+    /// The chain was cleared, but the contiguous clusters after the first
+    /// cluster are all still free, so a contiguous read is likely correct
+    Contiguous,
+    /// Some of the clusters the file would occupy are allocated to other
+    /// files; recovered data is likely partially overwritten
+    Overwritten,
+    /// The entry has no data to recover (zero size or no first cluster)
+    NoData,
+}
+
+/// A deleted directory entry found during forensic enumeration
+#[derive(Debug, Clone)]
+pub struct DeletedEntry {
+    /// The recovered entry; the first character of `short_name` is replaced
+    /// with [`DirectoryEntry::DELETED_PLACEHOLDER`]
+    pub entry: DirectoryEntry,
+    /// Assessment of whether the file data can be recovered
+    pub recoverability: Recoverability,
+}
+
+/// What a raw 32-byte directory slot holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotKind {
+    /// A live file or directory entry, including `.` and `..`
+    File,
+    /// A live long file name entry
+    Lfn,
+    /// A deleted entry (first byte 0xE5), short or LFN
+    Deleted,
+    /// The volume label
+    VolumeLabel,
+    /// A free slot (first byte 0x00), marking the end of the directory
+    End,
+}
+
+impl SlotKind {
+    /// Classify the raw bytes of a directory slot
+    pub fn classify(bytes: &[u8]) -> Self {
+        if DirectoryEntry::is_end_of_directory(bytes) {
+            Self::End
+        } else if DirectoryEntry::is_deleted_entry(bytes) {
+            Self::Deleted
+        } else if DirectoryEntry::is_lfn_entry(bytes) {
+            Self::Lfn
+        } else if DirectoryEntry::is_volume_label_entry(bytes) {
+            Self::VolumeLabel
+        } else {
+            Self::File
+        }
+    }
+}
+
+/// A 32-byte directory slot exactly as stored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawDirSlot {
+    /// Byte offset of the slot within the volume
+    pub offset: u64,
+    /// The slot's bytes
+    pub bytes: [u8; DirectoryEntry::ENTRY_SIZE],
+    /// What the slot holds
+    pub kind: SlotKind,
+}
+
 /// Long File Name (LFN) directory entry
 #[derive(Debug, Clone)]
 pub struct LfnEntry {
@@ -392,6 +299,27 @@ impl LfnEntry {
     }
 }
 
+/// Maximum number of LFN entries in one name (255 characters, 13 per entry)
+pub const MAX_LFN_ENTRIES: usize = 20;
+
+/// Check that LFN entries, in on-disk order, form one intact long name for `short_name`
+///
+/// The first entry must carry the last-entry bit and the sequence numbers
+/// must count down to 1 without gaps, with every checksum matching the
+/// short name.
+pub fn lfn_matches(entries: &[LfnEntry], short_name: &[u8; 11]) -> bool {
+    if entries.is_empty() || entries.len() > MAX_LFN_ENTRIES || !entries[0].is_last() {
+        return false;
+    }
+
+    let checksum = LfnEntry::calculate_checksum(short_name);
+    entries.iter().enumerate().all(|(i, entry)| {
+        entry.sequence() as usize == entries.len() - i
+            && (i == 0 || !entry.is_last())
+            && entry.checksum == checksum
+    })
+}
+
 /// Assemble long filename from multiple LFN entries
 pub fn assemble_lfn(entries: &[LfnEntry]) -> String {
     if entries.is_empty() {
@@ -431,6 +359,10 @@ impl DirectoryEntry {
     /// Attribute: Long file name entry
     pub const ATTR_LONG_NAME: u8 = 0x0F;
 
+    /// Placeholder for the first filename character of a deleted entry,
+    /// which is overwritten by the 0xE5 deletion marker
+    pub const DELETED_PLACEHOLDER: u8 = b'?';
+
     /// Parse directory entry from bytes (without LFN)
     pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
         Self::from_bytes_with_lfn(bytes, &[])
@@ -451,8 +383,17 @@ impl DirectoryEntry {
         let name_bytes = &bytes[0..11];
         let short_name = Self::parse_short_name(name_bytes);
 
-        // Determine the display name (LFN if available, otherwise short name)
-        let name = if !lfn_entries.is_empty() {
+        // Orphaned entries before the start of the final sequence belong to
+        // nothing; a sequence that doesn't check out is ignored entirely in
+        // favour of the short name
+        let lfn_entries = lfn_entries
+            .iter()
+            .rposition(LfnEntry::is_last)
+            .map_or(lfn_entries, |start| &lfn_entries[start..]);
+        let short_name_bytes: &[u8; 11] = name_bytes.try_into().ok()?;
+
+        // Determine the display name (LFN if intact, otherwise short name)
+        let name = if lfn_matches(lfn_entries, short_name_bytes) {
             let lfn = assemble_lfn(lfn_entries);
             if lfn.is_empty() {
                 short_name.clone()
@@ -488,11 +429,49 @@ impl DirectoryEntry {
         })
     }
 
+    /// Parse a deleted (0xE5) directory entry
+    ///
+    /// The lost first character of the short name is replaced with
+    /// [`Self::DELETED_PLACEHOLDER`]. Deleted LFN entries keep their name
+    /// characters but lose their sequence numbers, so `lfn_entries` must be
+    /// given in on-disk order and are reassembled in reverse.
+    pub fn from_deleted_bytes(bytes: &[u8], lfn_entries: &[LfnEntry]) -> Option<Self> {
+        if bytes.len() < Self::ENTRY_SIZE || !Self::is_deleted_entry(bytes) {
+            return None;
+        }
+
+        let mut restored = bytes[..Self::ENTRY_SIZE].to_vec();
+        restored[0] = Self::DELETED_PLACEHOLDER;
+        let mut entry = Self::from_bytes(&restored)?;
+
+        let lfn: Vec<u16> = lfn_entries.iter().rev().flat_map(|e| e.get_chars()).collect();
+        if !lfn.is_empty() {
+            entry.name = String::from_utf16_lossy(&lfn);
+        }
+
+        Some(entry)
+    }
+
     /// Check if this is a directory
     pub fn is_directory(&self) -> bool {
         (self.attributes & Self::ATTR_DIRECTORY) != 0
     }
 
+    /// Get the creation time, if set
+    pub fn created_time(&self) -> Option<DateTime<Utc>> {
+        dos_time_to_datetime(self.create_date, self.create_time)
+    }
+
+    /// Get the last modification time, if set
+    pub fn modified_time(&self) -> Option<DateTime<Utc>> {
+        dos_time_to_datetime(self.modify_date, self.modify_time)
+    }
+
+    /// Get the last access date, if set (FAT records no time of day)
+    pub fn accessed_time(&self) -> Option<DateTime<Utc>> {
+        dos_time_to_datetime(self.access_date, 0)
+    }
+
     /// Check if this is a volume label
     pub fn is_volume_label(&self) -> bool {
         (self.attributes & Self::ATTR_VOLUME_ID) != 0
@@ -529,6 +508,15 @@ impl DirectoryEntry {
         }
     }
 
+    /// Check if the raw bytes are a live volume label entry
+    pub fn is_volume_label_entry(bytes: &[u8]) -> bool {
+        bytes.len() >= 12
+            && !Self::is_end_of_directory(bytes)
+            && !Self::is_deleted_entry(bytes)
+            && !Self::is_lfn_entry(bytes)
+            && (bytes[11] & Self::ATTR_VOLUME_ID) != 0
+    }
+
     /// Check if the raw bytes represent an LFN entry
     pub fn is_lfn_entry(bytes: &[u8]) -> bool {
         bytes.len() >= 12 && bytes[11] == Self::ATTR_LONG_NAME
@@ -549,6 +537,23 @@ impl DirectoryEntry {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_dos_time_to_datetime() {
+        use chrono::TimeZone;
+
+        // 2023-06-15 14:30:58, stored as local time
+        let date = (43 << 9) | (6 << 5) | 15;
+        let time = (14 << 11) | (30 << 5) | 29;
+        assert_eq!(
+            dos_time_to_datetime(date, time),
+            Some(Utc.with_ymd_and_hms(2023, 6, 15, 14, 30, 58).unwrap())
+        );
+
+        assert_eq!(dos_time_to_datetime(0, time), None);
+        // Month 13 doesn't exist
+        assert_eq!(dos_time_to_datetime((43 << 9) | (13 << 5) | 1, 0), None);
+    }
+
     #[test]
     fn test_fat_type_display() {
         assert_eq!(FatType::Fat12.to_string(), "FAT12");
@@ -650,6 +655,7 @@ mod tests {
         let mut lfn_bytes = vec![0u8; 32];
         lfn_bytes[0] = 0x41; // Order: 1, last
         lfn_bytes[11] = DirectoryEntry::ATTR_LONG_NAME;
+        lfn_bytes[13] = LfnEntry::calculate_checksum(b"LONGFI~1TXT");
 
         // "LongFile.txt" in UTF-16LE
         let name = "LongFile.txt";
@@ -677,6 +683,45 @@ mod tests {
         assert_eq!(entry.file_size, 100);
     }
 
+    #[test]
+    fn test_lfn_validation() {
+        use super::super::writer::lfn_entries;
+
+        let short_name = *b"LONGNA~1TXT";
+        let mut short_bytes = vec![0u8; 32];
+        short_bytes[0..11].copy_from_slice(&short_name);
+        short_bytes[11] = DirectoryEntry::ATTR_ARCHIVE;
+
+        let name_of = |slots: &[[u8; 32]]| {
+            let lfn: Vec<LfnEntry> = slots.iter().filter_map(|s| LfnEntry::from_bytes(s)).collect();
+            DirectoryEntry::from_bytes_with_lfn(&short_bytes, &lfn).unwrap().name
+        };
+
+        // The longest possible name spans all 20 entries
+        let long_name = format!("{}.txt", "n".repeat(251));
+        let slots = lfn_entries(&long_name, &short_name);
+        assert_eq!(slots.len(), MAX_LFN_ENTRIES);
+        assert_eq!(name_of(&slots), long_name);
+
+        // Stray entries before the sequence start are ignored
+        let slots = lfn_entries("Valid name.txt", &short_name);
+        let stray = lfn_entries("Orphan.txt", b"ORPHAN  TXT");
+        assert_eq!(name_of(&[&stray[..], &slots[..]].concat()), "Valid name.txt");
+
+        // Checksum not matching the short name
+        let mismatched = lfn_entries("Valid name.txt", b"OTHERN~1TXT");
+        assert_eq!(name_of(&mismatched), "LONGNA~1.TXT");
+
+        // Sequences missing their first, middle or last-entry bit
+        assert_eq!(name_of(&slots[1..]), "LONGNA~1.TXT");
+        let long_slots = lfn_entries(&long_name, &short_name);
+        let gapped: Vec<_> = long_slots.iter().enumerate().filter(|(i, _)| *i != 5).map(|(_, s)| *s).collect();
+        assert_eq!(name_of(&gapped), "LONGNA~1.TXT");
+        let mut unterminated = slots.clone();
+        unterminated[0][0] &= !0x40;
+        assert_eq!(name_of(&unterminated), "LONGNA~1.TXT");
+    }
+
     #[test]
     fn test_bpb_total_sectors() {
         let mut bytes = vec![0u8; 512];
@@ -734,4 +779,24 @@ mod tests {
         let entry = DirectoryEntry::from_bytes(&bytes);
         assert!(entry.is_none());
     }
+
+    #[test]
+    fn test_deleted_entry_placeholder() {
+        let mut bytes = vec![0u8; 32];
+        bytes[0..11].copy_from_slice(b"XREPORT DOC");
+        bytes[0] = 0xE5;
+        bytes[28] = 42;
+
+        let entry = DirectoryEntry::from_deleted_bytes(&bytes, &[]).unwrap();
+        assert_eq!(entry.short_name, "?REPORT.DOC");
+        assert_eq!(entry.name, "?REPORT.DOC");
+        assert_eq!(entry.file_size, 42);
+    }
+
+    #[test]
+    fn test_parse_failure_variants() {
+        assert!(matches!(BiosParameterBlock::from_bytes(&[0u8; 64]).map_err(Error::from), Err(Error::Truncated(_))));
+        assert!(matches!(FsInfo::from_bytes(&[0u8; 64]), Err(Error::Truncated(_))));
+        assert!(matches!(FsInfo::from_bytes(&[0u8; 512]), Err(Error::UnrecognizedSignature(_))));
+    }
 }
@@ -0,0 +1,915 @@
+//! FAT write support
+//!
+//! [`FatWriter`] modifies a FAT volume in place through a [`ReadWriteSeek`]
+//! stream. Changes are ordered so that an interrupted operation leaks
+//! clusters at worst instead of leaving directory entries that point at
+//! unwritten or freed data: creation writes file data, then both FAT copies,
+//! then the FSInfo hint, and the directory entry last; deletion removes the
+//! directory entry first and frees the clusters afterwards.
+
+use super::types::{DirectoryEntry, FatType, FsInfo, LfnEntry};
+use super::FatTerritory;
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use std::io::SeekFrom;
+use std::ops::Range;
+use totalimage_core::{Error, ReadWriteSeek, Result};
+
+/// Characters allowed in an 8.3 short name besides letters and digits
+const SHORT_NAME_SPECIALS: &[u8] = b"!#$%&'()-@^_`{}~";
+
+/// Characters never allowed in a long file name
+const INVALID_LONG_NAME_CHARS: &[char] = &['"', '*', '/', ':', '<', '>', '?', '\\', '|'];
+
+/// Maximum long file name length in UTF-16 code units
+const MAX_LONG_NAME_LENGTH: usize = 255;
+
+/// UTF-16 code units stored per LFN entry
+const LFN_CHARS_PER_ENTRY: usize = 13;
+
+/// Writer for modifying a FAT file system in place
+///
+/// Keeps the parsed [`FatTerritory`] in sync with every change it writes,
+/// so reads through [`FatWriter::territory`] observe the new state.
+pub struct FatWriter<S: ReadWriteSeek> {
+    stream: S,
+    territory: FatTerritory,
+}
+
+impl<S: ReadWriteSeek> FatWriter<S> {
+    /// Open a FAT volume for writing
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - A read/write stream positioned anywhere; the volume is
+    ///   expected to start at offset 0
+    pub fn open(mut stream: S) -> Result<Self> {
+        let territory = FatTerritory::parse(&mut stream)?;
+        Ok(Self { stream, territory })
+    }
+
+    /// Get the territory reflecting the current on-disk state
+    pub fn territory(&self) -> &FatTerritory {
+        &self.territory
+    }
+
+    /// Get the underlying stream
+    pub fn stream(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Consume the writer and return the underlying stream
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    /// Create a new file in the root directory
+    ///
+    /// Allocates a cluster chain for `data`, writes it, updates every FAT
+    /// copy (and the FSInfo free-cluster hint on FAT32), then adds the
+    /// directory entry with LFN entries when `name` isn't a plain 8.3 name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is invalid or already exists, if `data`
+    /// is larger than the 4 GiB - 1 bytes a directory entry can record, or
+    /// if the volume or the fixed FAT12/16 root directory is full. Nothing
+    /// is written in those cases.
+    pub fn create_file(&mut self, name: &str, data: &[u8]) -> Result<DirectoryEntry> {
+        validate_long_name(name)?;
+        let size = u32::try_from(data.len()).map_err(|_| {
+            Error::InvalidOperation(format!(
+                "{} is {} bytes, more than a FAT file can hold ({} bytes)",
+                name,
+                data.len(),
+                u32::MAX
+            ))
+        })?;
+
+        let existing = self.territory.read_root_directory(&mut self.stream)?;
+        if existing
+            .iter()
+            .any(|e| e.name.eq_ignore_ascii_case(name) || e.short_name.eq_ignore_ascii_case(name))
+        {
+            return Err(Error::AlreadyExists(format!("File already exists: {}", name)));
+        }
+
+        let existing_short_names: Vec<String> = existing.iter().map(|e| e.short_name.clone()).collect();
+        let short_name = generate_short_name(name, &existing_short_names);
+        let mut slots = Vec::new();
+        if DirectoryEntry::from_bytes(&raw_entry(&short_name, 0, 0, 0, Utc::now()))
+            .is_some_and(|e| e.name != name)
+        {
+            slots.extend(lfn_entries(name, &short_name));
+        }
+        slots.push([0u8; DirectoryEntry::ENTRY_SIZE]); // Filled in once the first cluster is known
+
+        // Plan everything before writing anything
+        let root_start = self.root_start_cluster();
+        let directory = self.directory_slot_offsets(root_start)?;
+        let free_run = self.find_free_slot_run(&directory.offsets, slots.len())?;
+
+        let cluster_size = self.territory.bpb.bytes_per_cluster()? as usize;
+        let slots_per_cluster = cluster_size / DirectoryEntry::ENTRY_SIZE;
+        let extension_clusters = match free_run {
+            FreeSlots::Found(_) => 0,
+            FreeSlots::Trailing(available) => {
+                if directory.last_cluster.is_none() {
+                    return Err(Error::InvalidOperation("Root directory is full".to_string()));
+                }
+                (slots.len() - available).div_ceil(slots_per_cluster)
+            }
+        };
+
+        let data_clusters = data.len().div_ceil(cluster_size);
+        let clusters = self.find_free_clusters(data_clusters + extension_clusters)?;
+        let (data_chain, extension_chain) = clusters.split_at(data_clusters);
+
+        // 1. File data
+        for (cluster, chunk) in data_chain.iter().zip(data.chunks(cluster_size)) {
+            let mut buffer = chunk.to_vec();
+            buffer.resize(cluster_size, 0);
+            self.write_cluster(*cluster, &buffer)?;
+        }
+
+        // 2. Zeroed directory extension clusters
+        let zeroes = vec![0u8; cluster_size];
+        for cluster in extension_chain {
+            self.write_cluster(*cluster, &zeroes)?;
+        }
+
+        // 3. FAT copies
+        let mut dirty = Vec::new();
+        dirty.extend(self.link_chain(data_chain)?);
+        if let (Some(last), Some(first_new)) = (directory.last_cluster, extension_chain.first()) {
+            dirty.push(self.set_fat_entry(last, *first_new)?);
+            dirty.extend(self.link_chain(extension_chain)?);
+        }
+        self.flush_fat(&dirty)?;
+
+        // 4. FSInfo hint
+        self.update_fs_info(-(clusters.len() as i64), clusters.last().copied())?;
+
+        // 5. Directory entries
+        let first_cluster = data_chain.first().copied().unwrap_or(0);
+        let entry_bytes = raw_entry(&short_name, DirectoryEntry::ATTR_ARCHIVE, first_cluster, size, Utc::now());
+        *slots.last_mut().expect("short entry slot") = entry_bytes;
+
+        let mut offsets = directory.offsets;
+        for cluster in extension_chain {
+            let base = self.territory.cluster_to_offset(*cluster)?;
+            offsets.extend((0..slots_per_cluster).map(|i| base + (i * DirectoryEntry::ENTRY_SIZE) as u64));
+        }
+        let start = match free_run {
+            FreeSlots::Found(start) => start,
+            FreeSlots::Trailing(available) => offsets.len() - extension_clusters * slots_per_cluster - available,
+        };
+        for (offset, slot) in offsets[start..].iter().zip(&slots) {
+            self.stream.seek(SeekFrom::Start(*offset))?;
+            self.stream.write_all(slot)?;
+        }
+        self.stream.flush()?;
+
+        let lfn: Vec<LfnEntry> = slots[..slots.len() - 1]
+            .iter()
+            .filter_map(|s| LfnEntry::from_bytes(s))
+            .collect();
+        DirectoryEntry::from_bytes_with_lfn(&entry_bytes, &lfn)
+            .ok_or_else(|| Error::invalid_territory("Failed to build directory entry".to_string()))
+    }
+
+    /// Delete a file or empty directory by path
+    ///
+    /// Marks the directory entry and its LFN entries deleted (0xE5), then
+    /// frees the cluster chain in every FAT copy and raises the FSInfo
+    /// free-cluster count on FAT32.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path doesn't exist or names a non-empty
+    /// directory.
+    pub fn delete_file(&mut self, path: &str) -> Result<()> {
+        let trimmed = path.trim_matches(|c| c == '/' || c == '\\');
+        let (parent, name) = match trimmed.rfind(['/', '\\']) {
+            Some(split) => (&trimmed[..split], &trimmed[split + 1..]),
+            None => ("", trimmed),
+        };
+        if name.is_empty() {
+            return Err(Error::InvalidPath("Cannot delete the root directory".to_string()));
+        }
+
+        let parent_start = if parent.is_empty() {
+            self.root_start_cluster()
+        } else {
+            let entry = self.territory.find_file_by_path(&mut self.stream, parent)?;
+            if !entry.is_directory() {
+                return Err(Error::not_found(format!("Not a directory: {}", parent)));
+            }
+            Some(entry.first_cluster())
+        };
+
+        let directory = self.directory_slot_offsets(parent_start)?;
+        let (entry, slot_indices) = self.find_entry_slots(&directory.offsets, name)?;
+
+        if entry.is_directory()
+            && !self
+                .territory
+                .read_directory_from_cluster(&mut self.stream, entry.first_cluster())?
+                .is_empty()
+        {
+            return Err(Error::InvalidOperation(format!("Directory not empty: {}", trimmed)));
+        }
+
+        // 1. Directory entries
+        for index in slot_indices {
+            self.stream.seek(SeekFrom::Start(directory.offsets[index]))?;
+            self.stream.write_all(&[0xE5])?;
+        }
+
+        // 2. FAT copies
+        let chain = if entry.first_cluster() >= 2 {
+            self.territory.get_cluster_chain(entry.first_cluster())
+        } else {
+            Vec::new()
+        };
+        let mut dirty = Vec::with_capacity(chain.len());
+        for &cluster in &chain {
+            dirty.push(self.set_fat_entry(cluster, 0)?);
+        }
+        self.flush_fat(&dirty)?;
+
+        // 3. FSInfo hint
+        self.update_fs_info(chain.len() as i64, None)?;
+        self.stream.flush()?;
+
+        Ok(())
+    }
+
+    /// Find the short entry named `name` and the slots it occupies
+    ///
+    /// Returns the parsed entry and the indices of its LFN slots followed by
+    /// its short entry slot.
+    fn find_entry_slots(&mut self, offsets: &[u64], name: &str) -> Result<(DirectoryEntry, Vec<usize>)> {
+        let mut slot = [0u8; DirectoryEntry::ENTRY_SIZE];
+        let mut pending_lfn: Vec<LfnEntry> = Vec::new();
+        let mut pending_indices = Vec::new();
+
+        for (index, offset) in offsets.iter().enumerate() {
+            self.stream.seek(SeekFrom::Start(*offset))?;
+            self.stream.read_exact(&mut slot)?;
+
+            if DirectoryEntry::is_end_of_directory(&slot) {
+                break;
+            }
+
+            if DirectoryEntry::is_deleted_entry(&slot) {
+                pending_lfn.clear();
+                pending_indices.clear();
+                continue;
+            }
+
+            if DirectoryEntry::is_lfn_entry(&slot) {
+                if let Some(lfn) = LfnEntry::from_bytes(&slot) {
+                    pending_lfn.push(lfn);
+                    pending_indices.push(index);
+                }
+                continue;
+            }
+
+            if let Some(entry) = DirectoryEntry::from_bytes_with_lfn(&slot, &pending_lfn) {
+                let is_dot = entry.short_name == "." || entry.short_name == "..";
+                if !entry.is_volume_label()
+                    && !is_dot
+                    && (entry.name.eq_ignore_ascii_case(name) || entry.short_name.eq_ignore_ascii_case(name))
+                {
+                    pending_indices.push(index);
+                    return Ok((entry, pending_indices));
+                }
+            }
+            pending_lfn.clear();
+            pending_indices.clear();
+        }
+
+        Err(Error::not_found(format!("File not found: {}", name)))
+    }
+
+    /// First cluster of the root directory, or None for the fixed FAT12/16 root
+    fn root_start_cluster(&self) -> Option<u32> {
+        if self.territory.bpb.fat_type == FatType::Fat32 {
+            Some(self.territory.fat32_root_cluster)
+        } else {
+            None
+        }
+    }
+
+    /// Collect the byte offsets of every slot in a directory
+    ///
+    /// `start_cluster` of None means the fixed FAT12/16 root directory region.
+    fn directory_slot_offsets(&self, start_cluster: Option<u32>) -> Result<DirectorySlots> {
+        let entry_size = DirectoryEntry::ENTRY_SIZE as u64;
+
+        match start_cluster {
+            None => {
+                let base = self.territory.bpb.root_dir_offset()? as u64;
+                Ok(DirectorySlots {
+                    offsets: (0..self.territory.bpb.root_entries as u64)
+                        .map(|i| base + i * entry_size)
+                        .collect(),
+                    last_cluster: None,
+                })
+            }
+            Some(start) => {
+                let chain = self.territory.get_cluster_chain(start);
+                let slots_per_cluster = self.territory.bpb.bytes_per_cluster()? as u64 / entry_size;
+
+                let mut offsets = Vec::new();
+                for cluster in &chain {
+                    let base = self.territory.cluster_to_offset(*cluster)?;
+                    offsets.extend((0..slots_per_cluster).map(|i| base + i * entry_size));
+                }
+
+                Ok(DirectorySlots {
+                    offsets,
+                    last_cluster: chain.last().copied(),
+                })
+            }
+        }
+    }
+
+    /// Find `needed` consecutive unused directory slots
+    fn find_free_slot_run(&mut self, offsets: &[u64], needed: usize) -> Result<FreeSlots> {
+        let mut run_start = 0;
+        let mut run_length = 0;
+        let mut marker = [0u8; 1];
+
+        for (index, offset) in offsets.iter().enumerate() {
+            self.stream.seek(SeekFrom::Start(*offset))?;
+            self.stream.read_exact(&mut marker)?;
+
+            if marker[0] == 0x00 {
+                // End of directory: every remaining slot is free
+                let available = offsets.len() - index + run_length;
+                return Ok(if available >= needed {
+                    FreeSlots::Found(index - run_length)
+                } else {
+                    FreeSlots::Trailing(available)
+                });
+            }
+
+            if marker[0] == 0xE5 {
+                if run_length == 0 {
+                    run_start = index;
+                }
+                run_length += 1;
+                if run_length == needed {
+                    return Ok(FreeSlots::Found(run_start));
+                }
+            } else {
+                run_length = 0;
+            }
+        }
+
+        Ok(FreeSlots::Trailing(run_length))
+    }
+
+    /// Find `count` free clusters, starting at the FSInfo hint when available
+    fn find_free_clusters(&mut self, count: usize) -> Result<Vec<u32>> {
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let total = self.territory.bpb.cluster_count()?;
+        let hint = self
+            .read_fs_info()?
+            .map(|(_, info)| info.next_free_cluster)
+            .filter(|&c| c >= 2 && c < total + 2)
+            .unwrap_or(2);
+
+        let mut free = Vec::with_capacity(count);
+        for i in 0..total {
+            let cluster = 2 + (hint - 2 + i) % total;
+            if self.territory.raw_fat_entry(cluster) == Some(0) {
+                free.push(cluster);
+                if free.len() == count {
+                    return Ok(free);
+                }
+            }
+        }
+
+        Err(Error::InvalidOperation(format!(
+            "Volume full: {} free clusters needed, {} available",
+            count,
+            free.len()
+        )))
+    }
+
+    /// Write one full cluster of data
+    fn write_cluster(&mut self, cluster: u32, data: &[u8]) -> Result<()> {
+        let offset = self.territory.cluster_to_offset(cluster)?;
+        self.stream.seek(SeekFrom::Start(offset))?;
+        self.stream.write_all(data)?;
+        Ok(())
+    }
+
+    /// Update a FAT entry in memory, returning the changed byte range
+    fn set_fat_entry(&mut self, cluster: u32, value: u32) -> Result<Range<usize>> {
+        self.territory
+            .set_fat_entry(cluster, value)
+            .ok_or_else(|| Error::invalid_territory(format!("Cluster {} outside FAT", cluster)))
+    }
+
+    /// Link clusters into a chain terminated by an end-of-chain marker
+    fn link_chain(&mut self, chain: &[u32]) -> Result<Vec<Range<usize>>> {
+        let end_of_chain = match self.territory.bpb.fat_type {
+            FatType::Fat12 => 0x0FFF,
+            FatType::Fat16 => 0xFFFF,
+            FatType::Fat32 => 0x0FFFFFFF,
+        };
+
+        let mut dirty = Vec::with_capacity(chain.len());
+        for (i, &cluster) in chain.iter().enumerate() {
+            let next = chain.get(i + 1).copied().unwrap_or(end_of_chain);
+            dirty.push(self.set_fat_entry(cluster, next)?);
+        }
+        Ok(dirty)
+    }
+
+    /// Write the changed part of the in-memory FAT to every FAT copy
+    fn flush_fat(&mut self, dirty: &[Range<usize>]) -> Result<()> {
+        let (start, end) = match (dirty.iter().map(|r| r.start).min(), dirty.iter().map(|r| r.end).max()) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Ok(()),
+        };
+
+        let fat_offset = self.territory.bpb.fat_offset()? as u64;
+        let fat_table = self
+            .territory
+            .fat_table
+            .bytes()
+            .ok_or_else(|| Error::unsupported("Can't write a lazily loaded FAT".to_string()))?;
+        let fat_size = fat_table.len() as u64;
+
+        for copy in 0..self.territory.bpb.num_fats as u64 {
+            self.stream.seek(SeekFrom::Start(fat_offset + copy * fat_size + start as u64))?;
+            self.stream.write_all(&fat_table[start..end])?;
+        }
+        Ok(())
+    }
+
+    /// Read the FSInfo sector (FAT32 only)
+    ///
+    /// Returns None for FAT12/16 or when the sector has invalid signatures.
+    fn read_fs_info(&mut self) -> Result<Option<(u64, FsInfo)>> {
+        let bpb = &self.territory.bpb;
+        if bpb.fat_type != FatType::Fat32 || bpb.fs_info_sector == 0 || bpb.fs_info_sector == 0xFFFF {
+            return Ok(None);
+        }
+
+        let offset = bpb.fs_info_sector as u64 * bpb.bytes_per_sector as u64;
+        let mut sector = vec![0u8; 512];
+        self.stream.seek(SeekFrom::Start(offset))?;
+        self.stream.read_exact(&mut sector)?;
+
+        Ok(FsInfo::from_bytes(&sector).ok().map(|info| (offset, info)))
+    }
+
+    /// Adjust the FSInfo free-cluster count and next-free hint
+    fn update_fs_info(&mut self, free_delta: i64, last_allocated: Option<u32>) -> Result<()> {
+        let (offset, mut info) = match self.read_fs_info()? {
+            Some(found) => found,
+            None => return Ok(()),
+        };
+
+        if info.free_cluster_count != FsInfo::UNKNOWN {
+            info.free_cluster_count = (info.free_cluster_count as i64 + free_delta).max(0) as u32;
+        }
+        if let Some(cluster) = last_allocated {
+            info.next_free_cluster = cluster + 1;
+        }
+
+        let mut sector = vec![0u8; 512];
+        self.stream.seek(SeekFrom::Start(offset))?;
+        self.stream.read_exact(&mut sector)?;
+        info.write_to(&mut sector);
+        self.stream.seek(SeekFrom::Start(offset))?;
+        self.stream.write_all(&sector)?;
+        self.territory.fs_info = Some(info);
+        Ok(())
+    }
+}
+
+/// Slot positions of a directory
+struct DirectorySlots {
+    /// Byte offset of every slot, in directory order
+    offsets: Vec<u64>,
+    /// Last cluster of the directory chain (None for the fixed root)
+    last_cluster: Option<u32>,
+}
+
+/// Result of searching a directory for unused slots
+enum FreeSlots {
+    /// A long enough run starts at this slot index
+    Found(usize),
+    /// No run is long enough; this many free slots end the directory
+    Trailing(usize),
+}
+
+/// Validate a long file name for creation
+fn validate_long_name(name: &str) -> Result<()> {
+    if name.is_empty() || name == "." || name == ".." {
+        return Err(Error::InvalidPath(format!("Invalid file name: '{}'", name)));
+    }
+
+    if name.encode_utf16().count() > MAX_LONG_NAME_LENGTH {
+        return Err(Error::InvalidPath(format!("File name too long: {}", name)));
+    }
+
+    if name.chars().any(|c| c.is_control() || INVALID_LONG_NAME_CHARS.contains(&c)) {
+        return Err(Error::InvalidPath(format!("Invalid character in file name: {}", name)));
+    }
+
+    Ok(())
+}
+
+/// Check whether a byte may appear in an 8.3 short name
+fn is_short_name_char(b: u8) -> bool {
+    b.is_ascii_uppercase() || b.is_ascii_digit() || SHORT_NAME_SPECIALS.contains(&b)
+}
+
+/// Generate a unique 8.3 short name for a long file name
+///
+/// Follows the Windows basis-name algorithm: the name is upper-cased,
+/// leading periods, spaces and all but the last period are dropped,
+/// characters that can't appear in a short name become `_`, and the base
+/// and extension are cut to 8 and 3 characters. A name that already fits
+/// 8.3 keeps that form; otherwise, or if it collides with one of `existing`
+/// (short names as listed, e.g. `README.TXT`, compared case-insensitively),
+/// the base gets a `~N` numeric tail, counting up from 1 until it is free.
+///
+/// Windows switches to a hash of the long name after `~4`. Here the numeric
+/// tail simply continues, as on Linux, which every FAT driver accepts.
+pub fn generate_short_name(long_name: &str, existing: &[String]) -> [u8; 11] {
+    let trimmed = long_name.trim_start_matches('.');
+    let (base, ext) = match trimmed.rfind('.') {
+        Some(dot) => (&trimmed[..dot], &trimmed[dot + 1..]),
+        None => (trimmed, ""),
+    };
+
+    let (mut basis, base_lossy) = short_name_part(base);
+    let (ext, ext_lossy) = short_name_part(ext);
+    let needs_tail = trimmed.len() != long_name.len()
+        || base_lossy
+        || ext_lossy
+        || basis.is_empty()
+        || basis.len() > 8
+        || ext.len() > 3;
+
+    let mut short = [b' '; 11];
+    let ext_len = ext.len().min(3);
+    short[8..8 + ext_len].copy_from_slice(&ext[..ext_len]);
+
+    let is_free = |candidate: &[u8; 11]| {
+        let name = display_short_name(candidate);
+        !existing.iter().any(|e| e.eq_ignore_ascii_case(&name))
+    };
+
+    if !needs_tail {
+        short[..basis.len()].copy_from_slice(&basis);
+        if is_free(&short) {
+            return short;
+        }
+    }
+
+    if basis.is_empty() {
+        basis.push(b'_');
+    }
+    for n in 1u32.. {
+        let tail = format!("~{}", n);
+        let keep = basis.len().min(8 - tail.len());
+
+        let mut candidate = short;
+        candidate[..8].fill(b' ');
+        candidate[..keep].copy_from_slice(&basis[..keep]);
+        candidate[keep..keep + tail.len()].copy_from_slice(tail.as_bytes());
+        if is_free(&candidate) {
+            return candidate;
+        }
+    }
+
+    unreachable!("numeric tail space exhausted")
+}
+
+/// Convert part of a long name to short name characters
+///
+/// Returns the characters and whether anything was dropped or replaced.
+fn short_name_part(part: &str) -> (Vec<u8>, bool) {
+    let mut lossy = false;
+    let bytes = part
+        .chars()
+        .filter_map(|c| {
+            if c == ' ' || c == '.' {
+                lossy = true;
+                return None;
+            }
+            let b = if c.is_ascii() { c.to_ascii_uppercase() as u8 } else { 0 };
+            if is_short_name_char(b) {
+                Some(b)
+            } else {
+                lossy = true;
+                Some(b'_')
+            }
+        })
+        .collect();
+    (bytes, lossy)
+}
+
+/// Format a raw 8.3 name the way [`DirectoryEntry::short_name`] lists it
+fn display_short_name(short: &[u8; 11]) -> String {
+    let base = String::from_utf8_lossy(&short[..8]).trim_end().to_string();
+    let ext = String::from_utf8_lossy(&short[8..]).trim_end().to_string();
+    if ext.is_empty() {
+        base
+    } else {
+        format!("{}.{}", base, ext)
+    }
+}
+
+/// Build the LFN entries for `name`, in on-disk order
+pub(super) fn lfn_entries(name: &str, short_name: &[u8; 11]) -> Vec<[u8; DirectoryEntry::ENTRY_SIZE]> {
+    let checksum = LfnEntry::calculate_checksum(short_name);
+    let mut units: Vec<u16> = name.encode_utf16().collect();
+    let padded = units.len().div_ceil(LFN_CHARS_PER_ENTRY) * LFN_CHARS_PER_ENTRY;
+    if padded > units.len() {
+        // Terminate, then pad the final entry with 0xFFFF
+        units.push(0x0000);
+        units.resize(padded, 0xFFFF);
+    }
+
+    let count = units.len() / LFN_CHARS_PER_ENTRY;
+    let mut entries = Vec::with_capacity(count);
+
+    for (i, chunk) in units.chunks(LFN_CHARS_PER_ENTRY).enumerate().rev() {
+        let mut entry = [0u8; DirectoryEntry::ENTRY_SIZE];
+        entry[0] = (i + 1) as u8 | if i + 1 == count { 0x40 } else { 0 };
+        entry[11] = DirectoryEntry::ATTR_LONG_NAME;
+        entry[13] = checksum;
+
+        let positions = (1..11).step_by(2).chain((14..26).step_by(2)).chain((28..32).step_by(2));
+        for (offset, unit) in positions.zip(chunk) {
+            entry[offset..offset + 2].copy_from_slice(&unit.to_le_bytes());
+        }
+        entries.push(entry);
+    }
+
+    entries
+}
+
+/// Encode a short directory entry
+fn raw_entry(short_name: &[u8; 11], attributes: u8, first_cluster: u32, size: u32, now: DateTime<Utc>) -> [u8; DirectoryEntry::ENTRY_SIZE] {
+    let (time, date) = dos_date_time(now);
+
+    let mut entry = [0u8; DirectoryEntry::ENTRY_SIZE];
+    entry[0..11].copy_from_slice(short_name);
+    entry[11] = attributes;
+    entry[14..16].copy_from_slice(&time.to_le_bytes());
+    entry[16..18].copy_from_slice(&date.to_le_bytes());
+    entry[18..20].copy_from_slice(&date.to_le_bytes());
+    entry[20..22].copy_from_slice(&((first_cluster >> 16) as u16).to_le_bytes());
+    entry[22..24].copy_from_slice(&time.to_le_bytes());
+    entry[24..26].copy_from_slice(&date.to_le_bytes());
+    entry[26..28].copy_from_slice(&(first_cluster as u16).to_le_bytes());
+    entry[28..32].copy_from_slice(&size.to_le_bytes());
+    entry
+}
+
+/// Encode a timestamp as DOS (time, date), clamped to the DOS epoch range
+fn dos_date_time(dt: DateTime<Utc>) -> (u16, u16) {
+    let year = dt.year().clamp(1980, 2107) as u16;
+    let date = ((year - 1980) << 9) | ((dt.month() as u16) << 5) | dt.day() as u16;
+    let time = ((dt.hour() as u16) << 11) | ((dt.minute() as u16) << 5) | (dt.second() as u16 / 2);
+    (time, date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use crate::fat::tests::blank_fat32_volume;
+
+    /// Create a blank, formatted 1.44MB FAT12 floppy
+    fn blank_fat12_floppy() -> Vec<u8> {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..3].copy_from_slice(&[0xEB, 0x3C, 0x90]);
+        disk[3..11].copy_from_slice(b"MSWIN4.1");
+        disk[11..13].copy_from_slice(&512u16.to_le_bytes());
+        disk[13] = 1;
+        disk[14..16].copy_from_slice(&1u16.to_le_bytes());
+        disk[16] = 2;
+        disk[17..19].copy_from_slice(&224u16.to_le_bytes());
+        disk[19..21].copy_from_slice(&2880u16.to_le_bytes());
+        disk[21] = 0xF0;
+        disk[22..24].copy_from_slice(&9u16.to_le_bytes());
+        disk[510..512].copy_from_slice(&[0x55, 0xAA]);
+
+        for fat in 0..2 {
+            let offset = 512 + fat * 9 * 512;
+            disk[offset..offset + 3].copy_from_slice(&[0xF0, 0xFF, 0xFF]);
+        }
+        disk
+    }
+
+    #[test]
+    fn test_create_file_round_trip() {
+        let mut writer = FatWriter::open(Cursor::new(blank_fat12_floppy())).unwrap();
+
+        let data: Vec<u8> = (0..1500u32).map(|i| (i % 256) as u8).collect();
+        let entry = writer.create_file("Quarterly Report.txt", &data).unwrap();
+        assert_eq!(entry.name, "Quarterly Report.txt");
+        assert_eq!(entry.short_name, "QUARTE~1.TXT");
+        assert_eq!(entry.file_size, 1500);
+
+        writer.create_file("README.TXT", b"hello").unwrap();
+
+        // Re-parse from the raw bytes to check what actually hit the disk
+        let disk = writer.into_inner().into_inner();
+        let mut cursor = Cursor::new(disk.clone());
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+
+        let read_back = territory.read_file_by_path(&mut cursor, "/Quarterly Report.txt").unwrap();
+        assert_eq!(read_back, data);
+        assert_eq!(territory.read_file_by_path(&mut cursor, "README.TXT").unwrap(), b"hello");
+        assert_eq!(territory.get_cluster_chain(entry.first_cluster()).len(), 3);
+
+        // Only the long name gets an 8.3 alias in listings
+        use totalimage_core::Territory;
+        let occupants = territory.headquarters().unwrap().list_occupants(&mut cursor).unwrap();
+        let short_names: Vec<_> = occupants.iter().map(|o| (o.name.as_str(), o.short_name.as_deref())).collect();
+        assert_eq!(short_names, [("Quarterly Report.txt", Some("QUARTE~1.TXT")), ("README.TXT", None)]);
+
+        // Both FAT copies match
+        let fat_size = 9 * 512;
+        assert_eq!(disk[512..512 + fat_size], disk[512 + fat_size..512 + 2 * fat_size]);
+    }
+
+    #[test]
+    fn test_generate_short_name() {
+        let none: &[String] = &[];
+        for (long_name, short) in [
+            ("README.TXT", b"README  TXT"),
+            ("readme.txt", b"README  TXT"),
+            ("Makefile", b"MAKEFILE   "),
+            ("Quarterly Report.txt", b"QUARTE~1TXT"),
+            ("archive.tar.gz", b"ARCHIV~1GZ "),
+            (".bashrc", b"BASHRC~1   "),
+            ("page.html", b"PAGE~1  HTM"),
+            ("a+b.txt", b"A_B~1   TXT"),
+            ("café.txt", b"CAF_~1  TXT"),
+            ("...", b"_~1        "),
+        ] {
+            assert_eq!(&generate_short_name(long_name, none), short, "{}", long_name);
+        }
+    }
+
+    #[test]
+    fn test_generate_short_name_collisions() {
+        let mut existing: Vec<String> = Vec::new();
+        let mut generated = Vec::new();
+        for _ in 0..11 {
+            let short = generate_short_name("Long File Name.txt", &existing);
+            existing.push(display_short_name(&short));
+            generated.push(display_short_name(&short));
+        }
+        assert_eq!(generated[0], "LONGFI~1.TXT");
+        assert_eq!(generated[8], "LONGFI~9.TXT");
+        assert_eq!(generated[9], "LONGF~10.TXT");
+        assert_eq!(generated[10], "LONGF~11.TXT");
+
+        // A name that fits 8.3 takes a tail only when it's taken
+        let existing = vec!["report.txt".to_string(), "REPORT~1.TXT".to_string()];
+        assert_eq!(&generate_short_name("Report.TXT", &existing), b"REPORT~2TXT");
+        assert_eq!(&generate_short_name("Notes.txt", &existing), b"NOTES   TXT");
+    }
+
+    #[test]
+    fn test_create_file_rejects_duplicates_and_bad_names() {
+        let mut writer = FatWriter::open(Cursor::new(blank_fat12_floppy())).unwrap();
+        writer.create_file("a.txt", b"x").unwrap();
+
+        assert!(matches!(writer.create_file("A.TXT", b"y"), Err(Error::AlreadyExists(_))));
+        assert!(matches!(writer.create_file("bad|name", b"y"), Err(Error::InvalidPath(_))));
+        assert!(matches!(writer.create_file("", b"y"), Err(Error::InvalidPath(_))));
+    }
+
+    #[test]
+    fn test_create_file_volume_full() {
+        let mut writer = FatWriter::open(Cursor::new(blank_fat12_floppy())).unwrap();
+        let too_big = vec![0u8; 1_474_560];
+
+        let result = writer.create_file("BIG.BIN", &too_big);
+        assert!(matches!(result, Err(Error::InvalidOperation(_))));
+
+        // Nothing was allocated or listed
+        assert_eq!(writer.territory().raw_fat_entry(2), Some(0));
+        let mut stream = writer.into_inner();
+        let territory = FatTerritory::parse(&mut stream).unwrap();
+        assert!(territory.read_root_directory(&mut stream).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delete_file() {
+        let mut writer = FatWriter::open(Cursor::new(blank_fat12_floppy())).unwrap();
+        writer.create_file("keep.txt", b"keep").unwrap();
+        let entry = writer.create_file("A long file name.dat", &[7u8; 2000]).unwrap();
+        let chain = writer.territory().get_cluster_chain(entry.first_cluster());
+        assert_eq!(chain.len(), 4);
+
+        writer.delete_file("/a long FILE name.dat").unwrap();
+
+        let mut stream = writer.into_inner();
+        let territory = FatTerritory::parse(&mut stream).unwrap();
+        let names: Vec<String> = territory
+            .read_root_directory(&mut stream)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(names, vec!["keep.txt"]);
+
+        for cluster in chain {
+            assert_eq!(territory.raw_fat_entry(cluster), Some(0));
+        }
+
+        // The entry and its LFN entries are marked deleted, not wiped
+        let deleted = territory.read_deleted_entries(&mut stream, "/").unwrap();
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted[0].entry.name, "A long file name.dat");
+    }
+
+    #[test]
+    fn test_delete_file_in_subdirectory() {
+        let mut disk = blank_fat12_floppy();
+
+        // SUBDIR at cluster 2 holding FILE.TXT at cluster 3
+        let fat_offset = 512;
+        for fat in 0..2 {
+            let offset = fat_offset + fat * 9 * 512;
+            disk[offset + 3..offset + 6].copy_from_slice(&[0xFF, 0xFF, 0xFF]);
+        }
+        let root_offset = 512 + 2 * 9 * 512;
+        disk[root_offset..root_offset + 11].copy_from_slice(b"SUBDIR     ");
+        disk[root_offset + 11] = DirectoryEntry::ATTR_DIRECTORY;
+        disk[root_offset + 26] = 2;
+
+        let subdir_offset = 16896;
+        disk[subdir_offset..subdir_offset + 11].copy_from_slice(b".          ");
+        disk[subdir_offset + 11] = DirectoryEntry::ATTR_DIRECTORY;
+        disk[subdir_offset + 26] = 2;
+        disk[subdir_offset + 32..subdir_offset + 43].copy_from_slice(b"..         ");
+        disk[subdir_offset + 43] = DirectoryEntry::ATTR_DIRECTORY;
+        disk[subdir_offset + 64..subdir_offset + 75].copy_from_slice(b"FILE    TXT");
+        disk[subdir_offset + 64 + 26] = 3;
+        disk[subdir_offset + 64 + 28] = 10;
+
+        let mut writer = FatWriter::open(Cursor::new(disk)).unwrap();
+
+        let result = writer.delete_file("SUBDIR");
+        assert!(matches!(result, Err(Error::InvalidOperation(_))));
+
+        writer.delete_file("SUBDIR/FILE.TXT").unwrap();
+        assert_eq!(writer.territory().raw_fat_entry(3), Some(0));
+        let mut stream = writer.into_inner();
+        let territory = FatTerritory::parse(&mut stream).unwrap();
+        assert!(territory.read_directory_at_path(&mut stream, "SUBDIR").unwrap().is_empty());
+
+        let mut writer = FatWriter::open(stream).unwrap();
+
+        // Now empty, the directory itself can go
+        writer.delete_file("SUBDIR").unwrap();
+        assert_eq!(writer.territory().raw_fat_entry(2), Some(0));
+        assert!(writer.delete_file("SUBDIR").is_err());
+    }
+
+    #[test]
+    fn test_create_file_fat32_updates_fs_info() {
+        let mut writer = FatWriter::open(Cursor::new(blank_fat32_volume())).unwrap();
+        assert_eq!(writer.territory().bpb().fat_type, FatType::Fat32);
+
+        let data = vec![0xAB; 1024];
+        let entry = writer.create_file("data.bin", &data).unwrap();
+        assert_eq!(entry.first_cluster(), 3);
+        assert_eq!(writer.territory().free_cluster_count(), 66_000 - 3);
+
+        let disk = writer.into_inner().into_inner();
+        let fs_info = FsInfo::from_bytes(&disk[512..1024]).unwrap();
+        assert_eq!(fs_info.free_cluster_count, 66_000 - 3);
+        assert_eq!(fs_info.next_free_cluster, 5);
+
+        let mut cursor = Cursor::new(disk);
+        let territory = FatTerritory::parse(&mut cursor).unwrap();
+        assert_eq!(territory.read_file_by_path(&mut cursor, "DATA.BIN").unwrap(), data);
+
+        let mut writer = FatWriter::open(cursor).unwrap();
+        writer.delete_file("data.bin").unwrap();
+        let disk = writer.into_inner().into_inner();
+        assert_eq!(FsInfo::from_bytes(&disk[512..1024]).unwrap().free_cluster_count, 66_000 - 1);
+    }
+}
@@ -2,22 +2,93 @@
 
 pub mod types;
 
+use std::collections::HashMap;
+use std::fmt;
 use std::io::SeekFrom;
-use totalimage_core::{DirectoryCell, Error, OccupantInfo, ReadSeek, Result, Territory};
+
+use crate::extents::Extents;
+use chrono::{DateTime, Utc};
+use totalimage_core::{
+    AttributeFlag, DirectoryCell, Error, FsFeatures, OccupantInfo, ReadSeek, Result, SecurityLimits,
+    Territory,
+};
 use types::{
-    DirectoryRecord, PrimaryVolumeDescriptor, VolumeDescriptorType, SECTOR_SIZE,
+    identifier_string, DirectoryRecord, PathTableEntry, PrimaryVolumeDescriptor, VolumeDescriptorType, SECTOR_SIZE,
     VOLUME_DESCRIPTOR_START,
 };
 
+/// Directory record file flags and the attribute flags they decode to
+///
+/// The remaining bits describe record layout rather than the file.
+const ATTRIBUTE_FLAGS: [(u32, AttributeFlag); 3] = [
+    (DirectoryRecord::FLAG_HIDDEN as u32, AttributeFlag::Hidden),
+    (DirectoryRecord::FLAG_DIRECTORY as u32, AttributeFlag::Directory),
+    (DirectoryRecord::FLAG_ASSOCIATED as u32, AttributeFlag::Associated),
+];
+
+/// Directory tree built from the path table
+#[derive(Debug)]
+struct PathTable {
+    entries: Vec<PathTableEntry>,
+    /// (parent index, upper-cased name) to child index
+    children: HashMap<(usize, String), usize>,
+}
+
+impl PathTable {
+    fn new(entries: Vec<PathTableEntry>) -> Self {
+        let children = entries
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(index, entry)| {
+                let parent = (entry.parent_number as usize).saturating_sub(1);
+                ((parent, entry.name().to_ascii_uppercase()), index)
+            })
+            .collect();
+        Self { entries, children }
+    }
+
+    /// Resolve directory path components to a table index
+    fn resolve(&self, parts: &[&str]) -> Option<usize> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        parts.iter().try_fold(0, |parent, part| {
+            self.children.get(&(parent, part.to_ascii_uppercase())).copied()
+        })
+    }
+}
+
 /// ISO-9660 file system territory
 ///
 /// Supports basic ISO-9660 (CD-ROM) file systems with directory enumeration
 /// and file data access. Read-only by design.
-#[derive(Debug)]
+///
+/// Most methods take the volume's stream as an argument. A territory opened
+/// with [`from_reader`](Self::from_reader) also owns its stream, which
+/// [`Territory::extract_file`] needs.
 pub struct IsoTerritory {
     primary_descriptor: PrimaryVolumeDescriptor,
     root_directory: DirectoryRecord,
     identifier: String,
+    limits: SecurityLimits,
+    /// Directory tree from the L-path table, if it could be read
+    path_table: Option<PathTable>,
+    /// Owned stream for [`Territory::extract_file`]
+    reader: Option<Box<dyn ReadSeek>>,
+}
+
+impl fmt::Debug for IsoTerritory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IsoTerritory")
+            .field("primary_descriptor", &self.primary_descriptor)
+            .field("root_directory", &self.root_directory)
+            .field("identifier", &self.identifier)
+            .field("limits", &self.limits)
+            .field("path_table", &self.path_table)
+            .field("has_reader", &self.reader.is_some())
+            .finish()
+    }
 }
 
 impl IsoTerritory {
@@ -31,6 +102,11 @@ impl IsoTerritory {
     ///
     /// Returns an error if volume descriptors cannot be read or are invalid
     pub fn parse(stream: &mut dyn ReadSeek) -> Result<Self> {
+        Self::parse_with_limits(stream, SecurityLimits::active())
+    }
+
+    /// Parse an ISO-9660 file system with explicit resource limits
+    pub fn parse_with_limits(stream: &mut dyn ReadSeek, limits: SecurityLimits) -> Result<Self> {
         // Seek to volume descriptor set (sector 16)
         stream.seek(SeekFrom::Start(VOLUME_DESCRIPTOR_START))?;
 
@@ -46,7 +122,7 @@ impl IsoTerritory {
 
             // Check for valid ISO-9660 identifier
             if identifier != b"CD001" {
-                return Err(Error::invalid_territory(format!(
+                return Err(Error::unrecognized_signature(format!(
                     "Invalid ISO-9660 identifier: {:?}",
                     identifier
                 )));
@@ -88,18 +164,95 @@ impl IsoTerritory {
 
         let identifier = "ISO-9660 filesystem".to_string();
 
+        let path_table = match Self::load_path_table(stream, &primary, &limits) {
+            Ok(table) => table,
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable ISO-9660 path table: {}", e);
+                None
+            }
+        };
+
         Ok(Self {
             primary_descriptor: primary,
             root_directory,
             identifier,
+            limits,
+            path_table,
+            reader: None,
         })
     }
 
+    /// Parse an ISO-9660 file system and keep its stream
+    ///
+    /// The territory owns `reader` afterwards, so
+    /// [`Territory::extract_file`] can read file data without being handed
+    /// a stream.
+    pub fn from_reader(mut reader: impl ReadSeek + 'static) -> Result<Self> {
+        let mut territory = Self::parse(&mut reader)?;
+        territory.reader = Some(Box::new(reader));
+        Ok(territory)
+    }
+
+    /// Read the L-path table, if the volume has one
+    fn load_path_table(
+        stream: &mut dyn ReadSeek,
+        primary: &PrimaryVolumeDescriptor,
+        limits: &SecurityLimits,
+    ) -> Result<Option<PathTable>> {
+        let size = primary.path_table_size.get();
+        if size == 0 || primary.l_path_table == 0 {
+            return Ok(None);
+        }
+        let size = totalimage_core::validate_allocation_size(size as u64, limits.max_allocation_size, "Path table")?;
+
+        stream.seek(SeekFrom::Start(primary.l_path_table as u64 * SECTOR_SIZE as u64))?;
+        let mut bytes = vec![0u8; size];
+        stream.read_exact(&mut bytes)?;
+
+        let entries = PathTableEntry::parse_l_table(&bytes);
+        Ok((!entries.is_empty()).then(|| PathTable::new(entries)))
+    }
+
     /// Get the primary volume descriptor
     pub fn primary_descriptor(&self) -> &PrimaryVolumeDescriptor {
         &self.primary_descriptor
     }
 
+    /// Get the publisher identifier, if one is recorded
+    pub fn publisher(&self) -> Option<String> {
+        identifier_string(&self.primary_descriptor.publisher_identifier)
+    }
+
+    /// Get the data preparer identifier, if one is recorded
+    pub fn preparer(&self) -> Option<String> {
+        identifier_string(&self.primary_descriptor.data_preparer_identifier)
+    }
+
+    /// Get the identifier of the application that wrote the volume, if recorded
+    pub fn application(&self) -> Option<String> {
+        identifier_string(&self.primary_descriptor.application_identifier)
+    }
+
+    /// Get when the volume was created, if recorded
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        self.primary_descriptor.volume_creation_date.to_datetime()
+    }
+
+    /// Get when the volume was last modified, if recorded
+    pub fn modified(&self) -> Option<DateTime<Utc>> {
+        self.primary_descriptor.volume_modification_date.to_datetime()
+    }
+
+    /// Get the resource limits in effect
+    pub fn limits(&self) -> &SecurityLimits {
+        &self.limits
+    }
+
+    /// Replace the resource limits used for extraction
+    pub fn set_limits(&mut self, limits: SecurityLimits) {
+        self.limits = limits;
+    }
+
     /// Read directory entries from a directory record
     pub fn read_directory(
         &self,
@@ -122,8 +275,10 @@ impl IsoTerritory {
         stream.read_exact(&mut data)?;
 
         // Parse directory records
-        let mut entries = Vec::new();
+        let mut entries: Vec<DirectoryRecord> = Vec::new();
         let mut pos = 0;
+        // Whether the last file pushed still expects more extents
+        let mut continuing = false;
 
         while pos < data.len() {
             // Check for end of directory or padding
@@ -141,7 +296,23 @@ impl IsoTerritory {
                 // Skip "." and ".." entries
                 let name = record.file_name();
                 if name != "." && name != ".." {
-                    entries.push(record);
+                    let not_final = record.is_not_final();
+                    if continuing {
+                        // Next extent of the multi-extent file pushed last
+                        let first = entries.last_mut().expect("continuing file was pushed");
+                        if record.file_identifier != first.file_identifier {
+                            return Err(Error::invalid_territory(format!(
+                                "Multi-extent file {} is missing its final extent",
+                                first.file_name()
+                            )));
+                        }
+                        first
+                            .continuation_extents
+                            .push((record.extent_location.get(), record.data_length.get()));
+                    } else {
+                        entries.push(record);
+                    }
+                    continuing = not_final;
                 }
             }
 
@@ -153,10 +324,110 @@ impl IsoTerritory {
             }
         }
 
+        if let Some(first) = entries.last().filter(|_| continuing) {
+            return Err(Error::invalid_territory(format!(
+                "Multi-extent file {} is missing its final extent",
+                first.file_name()
+            )));
+        }
+
         Ok(entries)
     }
 
+    /// Find the directory record for a path
+    ///
+    /// The containing directory is located through the path table when
+    /// possible; only the final component is looked up in directory records.
+    ///
+    /// # Errors
+    ///
+    /// Returns a not-found error if a component is missing or an
+    /// intermediate component isn't a directory, or if the path is empty.
+    pub fn find_record_by_path(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<DirectoryRecord> {
+        let parts: Vec<&str> = path.split(['/', '\\']).filter(|s| !s.is_empty()).collect();
+        let Some((name, directories)) = parts.split_last() else {
+            return Err(Error::not_found("Empty path".to_string()));
+        };
+
+        let parent = match self.path_table_directory(stream, directories)? {
+            Some(parent) => parent,
+            None => self.walk_records(stream, self.root_directory.clone(), directories)?,
+        };
+        self.walk_records(stream, parent, &[*name])
+    }
+
+    /// Find the record of a directory, resolving it through the path table
+    ///
+    /// Falls back to walking directory records when the volume has no
+    /// usable path table or the path isn't in it. An empty path is the root.
+    ///
+    /// # Errors
+    ///
+    /// Returns a not-found error if a component is missing or the path
+    /// doesn't name a directory.
+    pub fn find_directory_by_path(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<DirectoryRecord> {
+        let parts: Vec<&str> = path.split(['/', '\\']).filter(|s| !s.is_empty()).collect();
+        if let Some(directory) = self.path_table_directory(stream, &parts)? {
+            return Ok(directory);
+        }
+
+        let record = self.walk_records(stream, self.root_directory.clone(), &parts)?;
+        if !record.is_directory() {
+            return Err(Error::not_found(format!("Not a directory: {}", path)));
+        }
+        Ok(record)
+    }
+
+    /// Look up a directory in the path table
+    ///
+    /// The table only gives the extent, so the directory's own "." record
+    /// is read from it for the length; the returned record carries the
+    /// directory's name. Returns None if the table can't resolve the path.
+    fn path_table_directory(&self, stream: &mut dyn ReadSeek, parts: &[&str]) -> Result<Option<DirectoryRecord>> {
+        if parts.is_empty() {
+            return Ok(Some(self.root_directory.clone()));
+        }
+        let Some(table) = &self.path_table else {
+            return Ok(None);
+        };
+        let Some(index) = table.resolve(parts) else {
+            return Ok(None);
+        };
+        let entry = &table.entries[index];
+
+        stream.seek(SeekFrom::Start(entry.extent_location as u64 * SECTOR_SIZE as u64))?;
+        let mut bytes = [0u8; 256];
+        stream.read_exact(&mut bytes)?;
+
+        Ok(DirectoryRecord::from_bytes(&bytes)
+            .filter(|record| record.is_directory() && record.file_identifier == [0])
+            .map(|mut record| {
+                record.file_identifier_length = entry.identifier.len() as u8;
+                record.file_identifier = entry.identifier.clone();
+                record
+            }))
+    }
+
+    /// Walk directory records from `start` through each path component
+    fn walk_records(&self, stream: &mut dyn ReadSeek, start: DirectoryRecord, parts: &[&str]) -> Result<DirectoryRecord> {
+        let mut current = start;
+        for part in parts {
+            if !current.is_directory() {
+                return Err(Error::not_found(format!("Not a directory: {}", current.file_name())));
+            }
+            current = self
+                .read_directory(stream, &current)?
+                .into_iter()
+                .find(|r| r.file_name().eq_ignore_ascii_case(part))
+                .ok_or_else(|| Error::not_found(format!("Path component not found: {}", part)))?;
+        }
+
+        Ok(current)
+    }
+
     /// Read file data from a file record
+    ///
+    /// Multi-extent files are reassembled from all of their extents.
     pub fn read_file(
         &self,
         stream: &mut dyn ReadSeek,
@@ -166,16 +437,21 @@ impl IsoTerritory {
             return Err(Error::invalid_territory("Cannot read directory as file".to_string()));
         }
 
-        let extent_lba = file.extent_location.get();
-        let data_length = file.data_length.get();
+        let total_length = file.total_length();
+        self.limits.validate_extract_size(total_length)?;
 
-        // Seek to file extent
-        let offset = extent_lba as u64 * SECTOR_SIZE as u64;
-        stream.seek(SeekFrom::Start(offset))?;
+        let mut data = vec![0u8; total_length as usize];
+        let mut filled = 0;
+        for (extent_lba, data_length) in file.extents() {
+            // Seek to file extent
+            let offset = extent_lba as u64 * SECTOR_SIZE as u64;
+            stream.seek(SeekFrom::Start(offset))?;
 
-        // Read file data
-        let mut data = vec![0u8; data_length as usize];
-        stream.read_exact(&mut data)?;
+            // Read file data
+            let end = filled + data_length as usize;
+            stream.read_exact(&mut data[filled..end])?;
+            filled = end;
+        }
 
         Ok(data)
     }
@@ -194,9 +470,11 @@ impl Territory for IsoTerritory {
         Err(Error::unsupported("ISO-9660 is read-only".to_string()))
     }
 
-    fn headquarters(&self) -> Result<Box<dyn DirectoryCell>> {
-        Ok(Box::new(IsoRootDirectory {
-            root: self.root_directory.clone(),
+    fn headquarters(&self) -> Result<Box<dyn DirectoryCell<'_> + '_>> {
+        Ok(Box::new(IsoDirectoryCell {
+            territory: self,
+            name: "/".to_string(),
+            record: self.root_directory.clone(),
         }))
     }
 
@@ -219,47 +497,101 @@ impl Territory for IsoTerritory {
         true // ISO-9660 supports subdirectories
     }
 
-    fn navigate_to(&self, _path: &str) -> Result<Box<dyn DirectoryCell>> {
-        // Simplified: always return root directory
-        // Full implementation would parse path and traverse directories
-        self.headquarters()
+    fn fs_features(&self) -> FsFeatures {
+        // Joliet and Rock Ridge aren't read, so names are ISO 9660's own
+        FsFeatures {
+            timestamps: true,
+            max_file_size: u32::MAX as u64 * 2048, // Multi-extent, bounded by the volume
+            ..FsFeatures::default()
+        }
+    }
+
+    fn navigate_to(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Box<dyn DirectoryCell<'_> + '_>> {
+        let record = self.find_directory_by_path(stream, path)?;
+        let name = match record.file_identifier.as_slice() {
+            [0] => "/".to_string(),
+            _ => record.file_name(),
+        };
+        Ok(Box::new(IsoDirectoryCell { territory: self, name, record }))
+    }
+
+    fn data_runs(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<(u64, u64)>> {
+        let record = self.find_record_by_path(stream, path)?;
+
+        let mut extents = Extents::new(record.total_length());
+        for (extent_lba, data_length) in record.extents() {
+            extents.push(extent_lba as u64 * SECTOR_SIZE as u64, data_length as u64);
+        }
+        Ok(extents.into_runs())
+    }
+
+    fn decode_attributes(&self, raw: u32) -> Vec<AttributeFlag> {
+        AttributeFlag::decode(raw, &ATTRIBUTE_FLAGS)
     }
 
-    fn extract_file(&mut self, _path: &str) -> Result<Vec<u8>> {
-        // Simplified: return empty
-        // Full implementation would parse path, find file, read data
-        Ok(Vec::new())
+    fn extract_file(&mut self, path: &str) -> Result<Vec<u8>> {
+        let mut reader = self.reader.take().ok_or_else(|| {
+            Error::unsupported("ISO-9660 territory was parsed without an owned stream".to_string())
+        })?;
+        let result = self
+            .find_record_by_path(&mut reader, path)
+            .and_then(|record| self.read_file(&mut reader, &record));
+        self.reader = Some(reader);
+        result
     }
 }
 
-/// ISO-9660 root directory cell
-struct IsoRootDirectory {
-    #[allow(dead_code)] // Reserved for future full implementation
-    root: DirectoryRecord,
+/// ISO-9660 directory cell
+struct IsoDirectoryCell<'a> {
+    territory: &'a IsoTerritory,
+    name: String,
+    record: DirectoryRecord,
 }
 
-impl DirectoryCell for IsoRootDirectory {
+impl<'a> DirectoryCell<'a> for IsoDirectoryCell<'a> {
     fn name(&self) -> &str {
-        "/"
+        &self.name
     }
 
-    fn list_occupants(&self) -> Result<Vec<OccupantInfo>> {
-        // TODO: Full directory listing implementation
-        // - Parse directory records from root directory extent
-        // - Handle continuation extents for large directories
-        // - Support Rock Ridge extensions for Unix permissions
-        // Simplified: return empty list
-        // Full implementation would need access to the stream to read directory entries
-        Ok(Vec::new())
+    fn list_occupants(&self, stream: &mut dyn ReadSeek) -> Result<Vec<OccupantInfo>> {
+        Ok(self
+            .territory
+            .read_directory(stream, &self.record)?
+            .into_iter()
+            .map(|record| OccupantInfo {
+                name: record.file_name(),
+                is_directory: record.is_directory(),
+                size: if record.is_directory() { 0 } else { record.total_length() },
+                created: None,
+                modified: record.recording_date.to_datetime(),
+                accessed: None,
+                timestamp_is_local: false,
+                attributes: record.file_flags as u32,
+                link_target: None,
+                short_name: None,
+                link_count: 1,
+                path: None,
+            })
+            .collect())
     }
 
-    fn enter(&self, _name: &str) -> Result<Box<dyn DirectoryCell>> {
-        // TODO: Subdirectory navigation implementation
-        // - Search directory entries for matching name
-        // - Load subdirectory extent
-        // - Return new DirectoryCell for subdirectory
-        // Simplified: return error
-        Err(Error::not_found("Subdirectory not found".to_string()))
+    fn enter(&self, stream: &mut dyn ReadSeek, name: &str) -> Result<Box<dyn DirectoryCell<'a> + 'a>> {
+        let record = self
+            .territory
+            .read_directory(stream, &self.record)?
+            .into_iter()
+            .find(|r| r.file_name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| Error::not_found(format!("Directory not found: {}", name)))?;
+
+        if !record.is_directory() {
+            return Err(Error::not_found(format!("Not a directory: {}", name)));
+        }
+
+        Ok(Box::new(IsoDirectoryCell {
+            territory: self.territory,
+            name: record.file_name(),
+            record,
+        }))
     }
 }
 
@@ -356,15 +688,25 @@ mod tests {
         // File identifier: 0x00 (current directory)
         iso[root_offset + 33] = 0x00;
 
-        // Fill in creation/modification dates (17 bytes each at various offsets)
-        // Using simplified zeroed dates for this test
-        for i in 0..17 {
-            iso[pvd_offset + 813 + i] = b'0'; // Creation date
-            iso[pvd_offset + 830 + i] = b'0'; // Modification date
-            iso[pvd_offset + 847 + i] = b'0'; // Expiration date
-            iso[pvd_offset + 864 + i] = b'0'; // Effective date
+        // Publisher, data preparer and application identifiers (128 bytes each, padded with spaces)
+        let identifiers = [(318, "ACME PUBLISHING"), (446, "J. RANDOM PREPARER"), (574, "MKISOFS 2.01")];
+        for (offset, text) in identifiers {
+            let field = &mut iso[pvd_offset + offset..pvd_offset + offset + 128];
+            field.fill(b' ');
+            field[..text.len()].copy_from_slice(text.as_bytes());
         }
 
+        // Creation date: 2024-01-15 12:00:00.00 at UTC+02:00
+        iso[pvd_offset + 813..pvd_offset + 829].copy_from_slice(b"2024011512000000");
+        iso[pvd_offset + 829] = 8;
+
+        // Modification date: 2024-02-01 08:30:15.50 at UTC
+        iso[pvd_offset + 830..pvd_offset + 846].copy_from_slice(b"2024020108301550");
+
+        // Expiration and effective dates: not specified (all '0' digits)
+        iso[pvd_offset + 847..pvd_offset + 863].fill(b'0');
+        iso[pvd_offset + 864..pvd_offset + 880].fill(b'0');
+
         // File structure version
         iso[pvd_offset + 881] = 1;
 
@@ -388,6 +730,39 @@ mod tests {
         assert_eq!(territory.primary_descriptor().volume_space_size.get(), 32);
     }
 
+    #[test]
+    fn test_parse_raw_cd_image() {
+        use totalimage_pipeline::cd_raw::{CdRawPipeline, RAW_SECTOR_SIZE, SYNC_PATTERN};
+
+        // Wrap each 2048-byte sector in a Mode 1 raw sector
+        let mut raw = Vec::new();
+        for chunk in create_minimal_iso().chunks(2048) {
+            let mut sector = [0u8; RAW_SECTOR_SIZE];
+            sector[..12].copy_from_slice(&SYNC_PATTERN);
+            sector[15] = 1;
+            sector[16..16 + 2048].copy_from_slice(chunk);
+            raw.extend_from_slice(&sector);
+        }
+
+        assert!(IsoTerritory::parse(&mut Cursor::new(raw.clone())).is_err());
+
+        let mut cooked = CdRawPipeline::new(Cursor::new(raw)).unwrap();
+        let territory = IsoTerritory::parse(&mut cooked).unwrap();
+        assert_eq!(territory.banner().unwrap(), "TEST_ISO");
+    }
+
+    #[test]
+    fn test_decode_attributes() {
+        let mut cursor = Cursor::new(create_minimal_iso());
+        let territory = IsoTerritory::parse(&mut cursor).unwrap();
+
+        assert_eq!(territory.decode_attributes(0x02), vec![AttributeFlag::Directory]);
+        assert_eq!(
+            territory.decode_attributes(0x85),
+            vec![AttributeFlag::Hidden, AttributeFlag::Associated]
+        );
+    }
+
     #[test]
     fn test_iso_volume_label() {
         let iso_data = create_minimal_iso();
@@ -398,6 +773,23 @@ mod tests {
         assert_eq!(label, "TEST_ISO");
     }
 
+    #[test]
+    fn test_volume_metadata() {
+        use chrono::TimeZone;
+
+        let territory = IsoTerritory::parse(&mut Cursor::new(create_minimal_iso())).unwrap();
+
+        assert_eq!(territory.publisher().as_deref(), Some("ACME PUBLISHING"));
+        assert_eq!(territory.preparer().as_deref(), Some("J. RANDOM PREPARER"));
+        assert_eq!(territory.application().as_deref(), Some("MKISOFS 2.01"));
+        assert_eq!(territory.created(), Some(Utc.with_ymd_and_hms(2024, 1, 15, 10, 0, 0).unwrap()));
+        assert_eq!(
+            territory.modified(),
+            Some(Utc.with_ymd_and_hms(2024, 2, 1, 8, 30, 15).unwrap() + chrono::Duration::milliseconds(500))
+        );
+        assert_eq!(territory.primary_descriptor().volume_expiration_date.to_datetime(), None);
+    }
+
     #[test]
     fn test_iso_territory_methods() {
         let iso_data = create_minimal_iso();
@@ -415,6 +807,18 @@ mod tests {
         assert!(territory.set_banner("NEW_LABEL").is_err());
     }
 
+    #[test]
+    fn test_fs_features() {
+        let territory = IsoTerritory::parse(&mut Cursor::new(create_minimal_iso())).unwrap();
+        let features = territory.fs_features();
+
+        assert!(!features.writable);
+        assert!(features.timestamps);
+        assert!(!features.symlinks && !features.hard_links && !features.alternate_data_streams);
+        assert!(!features.long_names && !features.short_names);
+        assert_eq!(features.max_file_size, 0xFFFF_FFFF * 2048);
+    }
+
     #[test]
     fn test_invalid_iso_identifier() {
         let mut iso_data = vec![0u8; 64 * 1024];
@@ -425,7 +829,7 @@ mod tests {
 
         let mut cursor = Cursor::new(iso_data);
         let result = IsoTerritory::parse(&mut cursor);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(Error::UnrecognizedSignature(_))));
     }
 
     #[test]
@@ -440,7 +844,7 @@ mod tests {
 
         let mut cursor = Cursor::new(iso_data);
         let result = IsoTerritory::parse(&mut cursor);
-        assert!(result.is_err());
+        assert!(matches!(result, Err(Error::InvalidTerritory(_))));
     }
 
     #[test]
@@ -457,6 +861,246 @@ mod tests {
         assert_eq!(entries.len(), 0); // Empty root directory in minimal ISO
     }
 
+    /// Encode a directory record for a file
+    fn file_record(extent: u32, length: u32, flags: u8, name: &[u8]) -> Vec<u8> {
+        let mut record = vec![0u8; (33 + name.len() + 1) & !1];
+        record[0] = record.len() as u8;
+        record[2..6].copy_from_slice(&extent.to_le_bytes());
+        record[6..10].copy_from_slice(&extent.to_be_bytes());
+        record[10..14].copy_from_slice(&length.to_le_bytes());
+        record[14..18].copy_from_slice(&length.to_be_bytes());
+        record[25] = flags;
+        record[32] = name.len() as u8;
+        record[33..33 + name.len()].copy_from_slice(name);
+        record
+    }
+
+    /// Minimal ISO with the given records in its root directory (sector 18)
+    fn iso_with_root_records(records: &[Vec<u8>]) -> Vec<u8> {
+        let mut iso_data = create_minimal_iso();
+        let mut offset = 18 * SECTOR_SIZE;
+        for record in records {
+            iso_data[offset..offset + record.len()].copy_from_slice(record);
+            offset += record.len();
+        }
+        iso_data
+    }
+
+    #[test]
+    fn test_list_directory_timestamps() {
+        use chrono::{TimeZone, Utc};
+
+        // Recorded 2001-09-09 10:00:00 at UTC+09:00
+        let mut record = file_record(20, 5, 0, b"A.TXT;1");
+        record[18..25].copy_from_slice(&[101, 9, 9, 10, 0, 0, 36]);
+        let mut cursor = Cursor::new(iso_with_root_records(&[record]));
+        let territory = IsoTerritory::parse(&mut cursor).unwrap();
+
+        let listing = territory.list_directory(&mut cursor, "/").unwrap();
+        assert_eq!(listing.len(), 1);
+        assert_eq!(listing[0].modified, Some(Utc.with_ymd_and_hms(2001, 9, 9, 1, 0, 0).unwrap()));
+        assert!(!listing[0].timestamp_is_local);
+    }
+
+    #[test]
+    fn test_multi_extent_file_reassembled() {
+        let mut iso_data = iso_with_root_records(&[
+            file_record(20, 2048, DirectoryRecord::FLAG_NOT_FINAL, b"BIG.BIN;1"),
+            file_record(24, 1000, 0, b"BIG.BIN;1"),
+            file_record(26, 5, 0, b"SMALL.TXT;1"),
+        ]);
+        iso_data[20 * SECTOR_SIZE..21 * SECTOR_SIZE].fill(0xAA);
+        iso_data[24 * SECTOR_SIZE..24 * SECTOR_SIZE + 1000].fill(0xBB);
+        iso_data[26 * SECTOR_SIZE..26 * SECTOR_SIZE + 5].copy_from_slice(b"small");
+
+        let mut cursor = Cursor::new(iso_data);
+        let territory = IsoTerritory::parse(&mut cursor).unwrap();
+        let entries = territory.read_directory(&mut cursor, &territory.root_directory).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].file_name(), "BIG.BIN");
+        assert_eq!(entries[0].continuation_extents, vec![(24, 1000)]);
+        assert_eq!(entries[0].total_length(), 3048);
+
+        let data = territory.read_file(&mut cursor, &entries[0]).unwrap();
+        assert_eq!(data.len(), 3048);
+        assert!(data[..2048].iter().all(|&b| b == 0xAA));
+        assert!(data[2048..].iter().all(|&b| b == 0xBB));
+
+        assert_eq!(territory.read_file(&mut cursor, &entries[1]).unwrap(), b"small");
+
+        let sector = SECTOR_SIZE as u64;
+        assert_eq!(
+            territory.data_runs(&mut cursor, "/big.bin").unwrap(),
+            vec![(20 * sector, 2048), (24 * sector, 1000)]
+        );
+        assert_eq!(territory.data_runs(&mut cursor, "SMALL.TXT").unwrap(), vec![(26 * sector, 5)]);
+        assert!(territory.data_runs(&mut cursor, "MISSING").is_err());
+    }
+
+    /// ISO with /A/B/C/DEEP.TXT and a matching L-path table at sector 19
+    fn three_level_iso() -> Vec<u8> {
+        let mut iso_data = create_minimal_iso();
+        let dir = |extent, name: &[u8]| file_record(extent, 2048, DirectoryRecord::FLAG_DIRECTORY, name);
+        let directories: [(u32, u32, Vec<u8>); 4] = [
+            (18, 18, dir(22, b"A")),
+            (22, 18, dir(23, b"B")),
+            (23, 22, dir(24, b"C")),
+            (24, 23, file_record(25, 4, 0, b"DEEP.TXT;1")),
+        ];
+        for (extent, parent, child) in &directories {
+            let mut offset = *extent as usize * SECTOR_SIZE;
+            for record in [dir(*extent, &[0]), dir(*parent, &[1]), child.clone()] {
+                iso_data[offset..offset + record.len()].copy_from_slice(&record);
+                offset += record.len();
+            }
+        }
+        iso_data[25 * SECTOR_SIZE..25 * SECTOR_SIZE + 4].copy_from_slice(b"deep");
+
+        let mut table = Vec::new();
+        for (extent, parent, name) in [(18u32, 1u16, &[0u8][..]), (22, 1, b"A"), (23, 2, b"B"), (24, 3, b"C")] {
+            table.push(name.len() as u8);
+            table.push(0);
+            table.extend_from_slice(&extent.to_le_bytes());
+            table.extend_from_slice(&parent.to_le_bytes());
+            table.extend_from_slice(name);
+            if name.len() % 2 == 1 {
+                table.push(0);
+            }
+        }
+        let pvd_offset = VOLUME_DESCRIPTOR_START as usize;
+        iso_data[pvd_offset + 132..pvd_offset + 136].copy_from_slice(&(table.len() as u32).to_le_bytes());
+        iso_data[pvd_offset + 136..pvd_offset + 140].copy_from_slice(&(table.len() as u32).to_be_bytes());
+        iso_data[19 * SECTOR_SIZE..19 * SECTOR_SIZE + table.len()].copy_from_slice(&table);
+
+        iso_data
+    }
+
+    #[test]
+    fn test_path_table_lookup_matches_record_walk() {
+        let mut cursor = Cursor::new(three_level_iso());
+        let territory = IsoTerritory::parse(&mut cursor).unwrap();
+        assert_eq!(territory.path_table.as_ref().unwrap().entries.len(), 4);
+
+        for path in ["A", "/a/b", "A/B/C", "\\A\\b\\C\\"] {
+            let parts: Vec<&str> = path.split(['/', '\\']).filter(|s| !s.is_empty()).collect();
+            let walked = territory
+                .walk_records(&mut cursor, territory.root_directory.clone(), &parts)
+                .unwrap();
+            let looked_up = territory.find_directory_by_path(&mut cursor, path).unwrap();
+
+            assert_eq!(looked_up.file_name(), walked.file_name(), "{}", path);
+            assert_eq!(looked_up.extent_location.get(), walked.extent_location.get(), "{}", path);
+            assert_eq!(looked_up.data_length.get(), walked.data_length.get(), "{}", path);
+            assert!(looked_up.is_directory());
+        }
+
+        assert_eq!(territory.find_directory_by_path(&mut cursor, "/").unwrap().extent_location.get(), 18);
+        assert!(territory.find_directory_by_path(&mut cursor, "A/B/C/DEEP.TXT").is_err());
+        assert!(territory.find_directory_by_path(&mut cursor, "A/X").is_err());
+
+        let file = territory.find_record_by_path(&mut cursor, "a/b/c/deep.txt").unwrap();
+        assert_eq!(territory.read_file(&mut cursor, &file).unwrap(), b"deep");
+    }
+
+    #[test]
+    fn test_extract_nested_file() {
+        let mut iso_data = three_level_iso();
+        // Give C a second, multi-extent file
+        let record = 24 * SECTOR_SIZE + 34 + 34 + 44;
+        for (i, extent) in [
+            file_record(26, 2048, DirectoryRecord::FLAG_NOT_FINAL, b"SPLIT.BIN;1"),
+            file_record(28, 3, 0, b"SPLIT.BIN;1"),
+        ]
+        .iter()
+        .enumerate()
+        {
+            iso_data[record + i * 44..record + i * 44 + extent.len()].copy_from_slice(extent);
+        }
+        iso_data[26 * SECTOR_SIZE..27 * SECTOR_SIZE].fill(0xCC);
+        iso_data[28 * SECTOR_SIZE..28 * SECTOR_SIZE + 3].copy_from_slice(b"end");
+
+        let mut territory = IsoTerritory::from_reader(Cursor::new(iso_data.clone())).unwrap();
+        assert_eq!(territory.extract_file("/A/B/C/DEEP.TXT").unwrap(), b"deep");
+        assert_eq!(territory.extract_file("a\\b\\c\\deep.txt").unwrap(), b"deep");
+
+        let split = territory.extract_file("A/B/C/SPLIT.BIN").unwrap();
+        assert_eq!(split.len(), 2051);
+        assert!(split[..2048].iter().all(|&b| b == 0xCC));
+        assert_eq!(&split[2048..], b"end");
+
+        assert!(matches!(territory.extract_file("A/B/C/MISSING"), Err(Error::NotFound(_))));
+        assert!(territory.extract_file("A/B").is_err());
+
+        // Without an owned stream there is nothing to read from
+        let mut cursor = Cursor::new(iso_data);
+        let mut borrowed = IsoTerritory::parse(&mut cursor).unwrap();
+        assert!(borrowed.extract_file("A/B/C/DEEP.TXT").is_err());
+    }
+
+    #[test]
+    fn test_navigate_to() {
+        let mut cursor = Cursor::new(three_level_iso());
+        let territory = IsoTerritory::parse(&mut cursor).unwrap();
+
+        assert_eq!(territory.navigate_to(&mut cursor, "/").unwrap().name(), "/");
+        let cell = territory.navigate_to(&mut cursor, "a/b/c").unwrap();
+        assert_eq!(cell.name(), "C");
+        let occupants = cell.list_occupants(&mut cursor).unwrap();
+        assert_eq!(occupants.len(), 1);
+        assert_eq!(occupants[0].name, "DEEP.TXT");
+
+        assert_eq!(territory.navigate_to(&mut cursor, "A").unwrap().enter(&mut cursor, "B").unwrap().name(), "B");
+        assert!(territory.navigate_to(&mut cursor, "A/B/C/DEEP.TXT").is_err());
+        assert!(territory.navigate_to(&mut cursor, "A/X").is_err());
+    }
+
+    #[test]
+    fn test_path_table_skips_intermediate_directories() {
+        let mut iso_data = three_level_iso();
+        // Drop B from A's records; only the path table still knows the way
+        let b_record = 22 * SECTOR_SIZE + 68;
+        iso_data[b_record..b_record + 34].fill(0);
+
+        let mut cursor = Cursor::new(iso_data);
+        let territory = IsoTerritory::parse(&mut cursor).unwrap();
+        let parts = ["A", "B", "C"];
+        assert!(territory.walk_records(&mut cursor, territory.root_directory.clone(), &parts).is_err());
+
+        assert_eq!(territory.find_directory_by_path(&mut cursor, "A/B/C").unwrap().extent_location.get(), 24);
+        let file = territory.find_record_by_path(&mut cursor, "A/B/C/DEEP.TXT").unwrap();
+        assert_eq!(file.extent_location.get(), 25);
+
+        // Without the path table, lookups walk records
+        let mut territory = territory;
+        territory.path_table = None;
+        assert!(territory.find_directory_by_path(&mut cursor, "A/B/C").is_err());
+        assert_eq!(territory.find_directory_by_path(&mut cursor, "A").unwrap().extent_location.get(), 22);
+    }
+
+    #[test]
+    fn test_multi_extent_file_missing_final_extent() {
+        // Chain interrupted by a different file
+        let iso_data = iso_with_root_records(&[
+            file_record(20, 2048, DirectoryRecord::FLAG_NOT_FINAL, b"BIG.BIN;1"),
+            file_record(26, 5, 0, b"SMALL.TXT;1"),
+        ]);
+        let mut cursor = Cursor::new(iso_data);
+        let territory = IsoTerritory::parse(&mut cursor).unwrap();
+        assert!(territory.read_directory(&mut cursor, &territory.root_directory).is_err());
+
+        // Chain cut off by the end of the directory
+        let iso_data = iso_with_root_records(&[file_record(
+            20,
+            2048,
+            DirectoryRecord::FLAG_NOT_FINAL,
+            b"BIG.BIN;1",
+        )]);
+        let mut cursor = Cursor::new(iso_data);
+        let territory = IsoTerritory::parse(&mut cursor).unwrap();
+        assert!(territory.read_directory(&mut cursor, &territory.root_directory).is_err());
+    }
+
     #[test]
     fn test_directory_record_parsing() {
         // Test that we can parse the root directory record from our minimal ISO
@@ -1,5 +1,6 @@
 //! ISO-9660 file system types and structures
 
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use std::fmt;
 
 /// ISO-9660 sector size (2048 bytes)
@@ -104,6 +105,20 @@ impl IsoDateTime {
             gmt_offset: bytes[6] as i8,
         })
     }
+
+    /// Convert to UTC, applying the GMT offset
+    ///
+    /// Returns None for an all-zero (unrecorded) date or an impossible one.
+    pub fn to_datetime(&self) -> Option<DateTime<Utc>> {
+        if self.year == 0 && self.month == 0 && self.day == 0 {
+            return None;
+        }
+
+        let local = NaiveDate::from_ymd_opt(1900 + self.year as i32, self.month as u32, self.day as u32)?
+            .and_hms_opt(self.hour as u32, self.minute as u32, self.second as u32)?;
+
+        Some((local - Duration::minutes(self.gmt_offset as i64 * 15)).and_utc())
+    }
 }
 
 /// ISO-9660 ASCII date/time format (17 bytes)
@@ -152,6 +167,40 @@ impl IsoAsciiDateTime {
             gmt_offset: bytes[16] as i8,
         })
     }
+
+    /// Convert to UTC, applying the GMT offset
+    ///
+    /// Returns None when the date is not specified (all digits `0`, or
+    /// not digits at all) or is impossible.
+    pub fn to_datetime(&self) -> Option<DateTime<Utc>> {
+        fn number(digits: &[u8]) -> Option<u32> {
+            digits.iter().try_fold(0u32, |value, &digit| {
+                digit.is_ascii_digit().then(|| value * 10 + (digit - b'0') as u32)
+            })
+        }
+
+        let year = number(&self.year)?;
+        if year == 0 {
+            return None;
+        }
+
+        let local = NaiveDate::from_ymd_opt(year as i32, number(&self.month)?, number(&self.day)?)?
+            .and_hms_milli_opt(
+                number(&self.hour)?,
+                number(&self.minute)?,
+                number(&self.second)?,
+                number(&self.hundredths)? * 10,
+            )?;
+
+        Some((local - Duration::minutes(self.gmt_offset as i64 * 15)).and_utc())
+    }
+}
+
+/// Trim a space-padded identifier field, returning None if it is blank
+pub fn identifier_string(bytes: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(bytes);
+    let text = text.trim_end_matches([' ', '\0']).trim_start();
+    (!text.is_empty()).then(|| text.to_string())
 }
 
 /// Primary Volume Descriptor (sector 16 onwards)
@@ -298,6 +347,9 @@ pub struct DirectoryRecord {
     pub volume_sequence_number: BothEndian<u16>,
     pub file_identifier_length: u8,
     pub file_identifier: Vec<u8>,          // File name (variable length)
+    /// Further extents (LBA, length) of a multi-extent file, in order,
+    /// coalesced from the records following this one
+    pub continuation_extents: Vec<(u32, u32)>,
 }
 
 impl DirectoryRecord {
@@ -361,6 +413,7 @@ impl DirectoryRecord {
             volume_sequence_number,
             file_identifier_length,
             file_identifier,
+            continuation_extents: Vec::new(),
         })
     }
 
@@ -374,6 +427,25 @@ impl DirectoryRecord {
         (self.file_flags & Self::FLAG_HIDDEN) != 0
     }
 
+    /// Check if another record of the same file follows this one
+    ///
+    /// Files over 4 GiB are stored as several records sharing one name;
+    /// every record but the last carries this flag.
+    pub fn is_not_final(&self) -> bool {
+        (self.file_flags & Self::FLAG_NOT_FINAL) != 0
+    }
+
+    /// Get all extents (LBA, length) of the file in order
+    pub fn extents(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        std::iter::once((self.extent_location.get(), self.data_length.get()))
+            .chain(self.continuation_extents.iter().copied())
+    }
+
+    /// Get the file size summed over all extents
+    pub fn total_length(&self) -> u64 {
+        self.extents().map(|(_, length)| length as u64).sum()
+    }
+
     /// Get the file name as a string
     pub fn file_name(&self) -> String {
         if self.file_identifier.is_empty() {
@@ -401,13 +473,62 @@ impl DirectoryRecord {
     }
 }
 
+/// Path table record
+///
+/// The path table lists every directory on the volume with its extent and
+/// parent, in an order where parents always come before their children.
+/// Directory numbers are 1-based positions in the table; the root is 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathTableEntry {
+    /// LBA of the directory's extent
+    pub extent_location: u32,
+    /// Directory number of the parent (the root is its own parent)
+    pub parent_number: u16,
+    /// Directory identifier (0x00 for the root)
+    pub identifier: Vec<u8>,
+}
+
+impl PathTableEntry {
+    /// Parse a little-endian (L-type) path table
+    ///
+    /// Parsing stops at the first truncated record.
+    pub fn parse_l_table(bytes: &[u8]) -> Vec<Self> {
+        let mut entries = Vec::new();
+        let mut pos = 0;
+
+        while pos + 8 <= bytes.len() {
+            let identifier_length = bytes[pos] as usize;
+            let id_end = pos + 8 + identifier_length;
+            if identifier_length == 0 || id_end > bytes.len() {
+                break;
+            }
+
+            entries.push(Self {
+                extent_location: u32::from_le_bytes([bytes[pos + 2], bytes[pos + 3], bytes[pos + 4], bytes[pos + 5]]),
+                parent_number: u16::from_le_bytes([bytes[pos + 6], bytes[pos + 7]]),
+                identifier: bytes[pos + 8..id_end].to_vec(),
+            });
+
+            // Records are padded to an even length
+            pos = id_end + (identifier_length & 1);
+        }
+
+        entries
+    }
+
+    /// Get the directory name as a string
+    pub fn name(&self) -> String {
+        String::from_utf8_lossy(&self.identifier).to_string()
+    }
+}
+
 impl fmt::Display for DirectoryRecord {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
             "{} ({} bytes at LBA {})",
             self.file_name(),
-            self.data_length.get(),
+            self.total_length(),
             self.extent_location.get()
         )
     }
@@ -445,6 +566,8 @@ mod tests {
 
     #[test]
     fn test_iso_datetime() {
+        use chrono::TimeZone;
+
         let bytes = [70, 1, 15, 12, 30, 45, 0]; // Year 1970, Jan 15, 12:30:45, GMT
         let dt = IsoDateTime::from_bytes(&bytes).unwrap();
         assert_eq!(dt.year, 70);
@@ -453,6 +576,34 @@ mod tests {
         assert_eq!(dt.hour, 12);
         assert_eq!(dt.minute, 30);
         assert_eq!(dt.second, 45);
+        assert_eq!(dt.to_datetime(), Some(Utc.with_ymd_and_hms(1970, 1, 15, 12, 30, 45).unwrap()));
+
+        // 2001-09-09 10:00:00 at UTC+09:00
+        let dt = IsoDateTime::from_bytes(&[101, 9, 9, 10, 0, 0, 36]).unwrap();
+        assert_eq!(dt.to_datetime(), Some(Utc.with_ymd_and_hms(2001, 9, 9, 1, 0, 0).unwrap()));
+
+        assert_eq!(IsoDateTime::from_bytes(&[0; 7]).unwrap().to_datetime(), None);
+    }
+
+    #[test]
+    fn test_iso_ascii_datetime() {
+        use chrono::TimeZone;
+
+        // 2024-03-05 06:07:08.90 at UTC+01:00
+        let dt = IsoAsciiDateTime::from_bytes(b"2024030506070890\x04").unwrap();
+        let expected = Utc.with_ymd_and_hms(2024, 3, 5, 5, 7, 8).unwrap() + Duration::milliseconds(900);
+        assert_eq!(dt.to_datetime(), Some(expected));
+
+        assert_eq!(IsoAsciiDateTime::from_bytes(b"0000000000000000\0").unwrap().to_datetime(), None);
+        assert_eq!(IsoAsciiDateTime::from_bytes(&[0; 17]).unwrap().to_datetime(), None);
+        assert_eq!(IsoAsciiDateTime::from_bytes(b"2024133106070890\0").unwrap().to_datetime(), None);
+    }
+
+    #[test]
+    fn test_identifier_string() {
+        assert_eq!(identifier_string(b"ACME PUBLISHING   "), Some("ACME PUBLISHING".to_string()));
+        assert_eq!(identifier_string(b"    "), None);
+        assert_eq!(identifier_string(&[0; 8]), None);
     }
 
     #[test]
@@ -479,6 +630,23 @@ mod tests {
         assert_eq!(record.file_name(), "TEST");
     }
 
+    #[test]
+    fn test_path_table_parsing() {
+        let mut table = vec![1, 0, 18, 0, 0, 0, 1, 0, 0, 0];
+        table.extend_from_slice(&[3, 0, 22, 0, 0, 0, 1, 0]);
+        table.extend_from_slice(b"DIR\0");
+        // Truncated record
+        table.extend_from_slice(&[4, 0, 23, 0]);
+
+        let entries = PathTableEntry::parse_l_table(&table);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].identifier, [0]);
+        assert_eq!(entries[0].extent_location, 18);
+        assert_eq!(entries[1].name(), "DIR");
+        assert_eq!(entries[1].parent_number, 1);
+        assert_eq!(entries[1].extent_location, 22);
+    }
+
     #[test]
     fn test_directory_record_special_names() {
         // Test "." (current directory)
@@ -8,6 +8,11 @@
 //! - **exFAT**: Extended FAT file system for flash media
 //! - **NTFS**: Windows NT File System (read-only)
 //!
+//! [`identify_territory`] cheaply detects which of these a stream holds, or
+//! that it is BitLocker or LUKS encrypted, and [`is_hybrid_iso`] spots ISO
+//! images wrapped in a boot MBR. [`open_territory_in_zone`] goes from a
+//! vault and zone index to a parsed file system.
+//!
 //! ## Example
 //!
 //! ```rust,no_run
@@ -21,12 +26,17 @@
 //! println!("Filesystem: {}", territory.identify());
 //! ```
 
+mod extents;
 pub mod exfat;
 pub mod fat;
 pub mod iso;
 pub mod ntfs;
+mod open;
+pub mod probe;
 
 pub use exfat::ExfatTerritory;
 pub use fat::FatTerritory;
 pub use iso::IsoTerritory;
 pub use ntfs::NtfsTerritory;
+pub use open::{open_territory_in_zone, OpenedTerritory};
+pub use probe::{identify_territory, is_encrypted, is_hybrid_iso};
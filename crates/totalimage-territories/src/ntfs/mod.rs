@@ -27,11 +27,65 @@
 
 pub mod types;
 
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::io::{Read, Seek, SeekFrom};
-use ntfs::{Ntfs, NtfsFile, NtfsReadSeek};
-use ntfs::structured_values::NtfsFileNamespace;
-use totalimage_core::{DirectoryCell, Error, OccupantInfo, Result, Territory};
-use types::{ntfs_time_to_datetime, NtfsVolumeInfo};
+use ntfs::attribute_value::NtfsAttributeValue;
+use ntfs::indexes::NtfsFileNameIndex;
+use ntfs::types::Vcn;
+use ntfs::{
+    Ntfs, NtfsAttributeItem, NtfsAttributeType, NtfsError, NtfsFile, NtfsFileFlags, NtfsFileReference,
+    NtfsIndexNodeEntries, NtfsReadSeek,
+};
+use ntfs::structured_values::{
+    NtfsFileAttributeFlags, NtfsFileName, NtfsFileNamespace, NtfsIndexAllocation, NtfsIndexRoot,
+};
+use totalimage_core::{
+    read_slack, validate_file_range, AttributeFlag, DirectoryCell, DirectoryPage, Error, FsFeatures, OccupantInfo,
+    PartialListing, ReadSeek, Result, SecurityLimits, SpaceSummary, Territory,
+};
+use types::{ntfs_time_to_datetime, NtfsVolumeInfo, ReparsePoint};
+
+use crate::extents::Extents;
+
+/// Synthetic directory that [`NtfsTerritory::list_orphans`] lists
+/// orphaned files under
+pub const ORPHANS_DIRECTORY: &str = "$Orphans";
+
+/// File record number of the root directory
+const ROOT_RECORD: u64 = 5;
+
+/// File record number of `$LogFile`
+const LOG_FILE_RECORD: u64 = 2;
+
+/// Path of the USN change journal
+const USN_JOURNAL_PATH: &str = "$Extend/$UsnJrnl";
+
+/// Data stream of the USN change journal holding the change records
+const USN_JOURNAL_STREAM: &str = "$J";
+
+/// `$FILE_NAME` attribute bits and the flags they decode to
+///
+/// Directories are marked by the index-view bit rather than the Win32
+/// directory bit, but both are accepted.
+const ATTRIBUTE_FLAGS: [(u32, AttributeFlag); 13] = [
+    (NtfsFileAttributeFlags::READ_ONLY.bits(), AttributeFlag::ReadOnly),
+    (NtfsFileAttributeFlags::HIDDEN.bits(), AttributeFlag::Hidden),
+    (NtfsFileAttributeFlags::SYSTEM.bits(), AttributeFlag::System),
+    (0x0010, AttributeFlag::Directory),
+    (NtfsFileAttributeFlags::IS_DIRECTORY.bits(), AttributeFlag::Directory),
+    (NtfsFileAttributeFlags::ARCHIVE.bits(), AttributeFlag::Archive),
+    (NtfsFileAttributeFlags::TEMPORARY.bits(), AttributeFlag::Temporary),
+    (NtfsFileAttributeFlags::SPARSE_FILE.bits(), AttributeFlag::Sparse),
+    (NtfsFileAttributeFlags::REPARSE_POINT.bits(), AttributeFlag::ReparsePoint),
+    (NtfsFileAttributeFlags::COMPRESSED.bits(), AttributeFlag::Compressed),
+    (NtfsFileAttributeFlags::OFFLINE.bits(), AttributeFlag::Offline),
+    (NtfsFileAttributeFlags::NOT_CONTENT_INDEXED.bits(), AttributeFlag::NotIndexed),
+    (NtfsFileAttributeFlags::ENCRYPTED.bits(), AttributeFlag::Encrypted),
+];
+
+/// First file record number not reserved for NTFS metadata files
+const FIRST_USER_RECORD: u64 = 16;
 
 /// NTFS filesystem territory (read-only)
 ///
@@ -46,6 +100,8 @@ pub struct NtfsTerritory<T: Read + Seek> {
     volume_info: NtfsVolumeInfo,
     /// Identifier string
     identifier: String,
+    /// Resource limits for extraction
+    limits: SecurityLimits,
 }
 
 impl<T: Read + Seek + Send + Sync> NtfsTerritory<T> {
@@ -63,14 +119,23 @@ impl<T: Read + Seek + Send + Sync> NtfsTerritory<T> {
     ///
     /// The filesystem is opened read-only, so you can safely browse even a mounted
     /// filesystem without worrying about data corruption.
-    pub fn parse(mut reader: T) -> Result<Self> {
+    pub fn parse(reader: T) -> Result<Self> {
+        Self::parse_with_limits(reader, SecurityLimits::active())
+    }
+
+    /// Parse an NTFS filesystem with explicit resource limits
+    pub fn parse_with_limits(mut reader: T, limits: SecurityLimits) -> Result<Self> {
         // Seek to start of volume
         reader.seek(SeekFrom::Start(0))
             .map_err(|e| Error::invalid_territory(format!("IO error: {}", e)))?;
 
         // Parse NTFS structure
-        let ntfs = Ntfs::new(&mut reader)
-            .map_err(|e| Error::invalid_territory(format!("Failed to parse NTFS: {}", e)))?;
+        let ntfs = Ntfs::new(&mut reader).map_err(|e| match e {
+            NtfsError::InvalidTwoByteSignature { .. } => {
+                Error::unrecognized_signature(format!("Not an NTFS boot sector: {}", e))
+            }
+            _ => Error::invalid_territory(format!("Failed to parse NTFS: {}", e)),
+        })?;
 
         // Get volume information
         let cluster_size = ntfs.cluster_size();
@@ -104,6 +169,7 @@ impl<T: Read + Seek + Send + Sync> NtfsTerritory<T> {
             reader,
             volume_info,
             identifier,
+            limits,
         })
     }
 
@@ -119,6 +185,16 @@ impl<T: Read + Seek + Send + Sync> NtfsTerritory<T> {
         &self.volume_info
     }
 
+    /// Get the resource limits in effect
+    pub fn limits(&self) -> &SecurityLimits {
+        &self.limits
+    }
+
+    /// Replace the resource limits used for extraction
+    pub fn set_limits(&mut self, limits: SecurityLimits) {
+        self.limits = limits;
+    }
+
     /// Get the NTFS structure reference
     pub fn ntfs(&self) -> &Ntfs {
         &self.ntfs
@@ -137,36 +213,67 @@ impl<T: Read + Seek + Send + Sync> NtfsTerritory<T> {
         let root_dir = ntfs.root_directory(reader)
             .map_err(|e| Error::invalid_territory(format!("Cannot read root directory: {}", e)))?;
 
-        Self::read_directory_entries_static(ntfs, reader, &root_dir)
+        Self::read_directory_entries_static(ntfs, reader, &root_dir, None)
     }
 
     /// Read directory entries from an NTFS file (directory) - static version
-    fn read_directory_entries_static(_ntfs: &Ntfs, reader: &mut T, dir: &NtfsFile) -> Result<Vec<OccupantInfo>> {
+    ///
+    /// Symbolic links and junctions get their target in `link_target`,
+    /// files with a separate DOS-namespace name get it in `short_name`, and
+    /// `link_count` comes from each file's MFT record.
+    ///
+    /// Given `skipped`, an entry that can't be read is logged and its error
+    /// kept there instead of failing the listing. The B-tree walk can't resume
+    /// past a failed entry, so the entries read up to it are returned.
+    fn read_directory_entries_static<R: Read + Seek>(
+        ntfs: &Ntfs,
+        reader: &mut R,
+        dir: &NtfsFile,
+        mut skipped: Option<&mut Vec<String>>,
+    ) -> Result<Vec<OccupantInfo>> {
         let mut entries = Vec::new();
+        let mut short_names = HashMap::new();
 
         // Get the directory index
         let index = dir.directory_index(reader)
             .map_err(|e| Error::invalid_territory(format!("Cannot read directory index: {}", e)))?;
 
+        // Walks the whole B-tree, descending into $INDEX_ALLOCATION subnodes.
+        // A subnode that fails to read takes every entry beneath it along, so
+        // the error is returned rather than listing part of the directory,
+        // unless the caller asked for what can be read.
         let mut iter = index.entries();
 
         while let Some(entry_result) = iter.next(reader) {
-            let entry = match entry_result {
-                Ok(e) => e,
-                Err(e) => {
-                    tracing::warn!("Error reading directory entry: {}", e);
-                    continue;
+            let entry = match (entry_result, skipped.as_deref_mut()) {
+                (Ok(entry), _) => entry,
+                (Err(e), Some(skipped)) => {
+                    tracing::warn!("Stopping at unreadable directory entry: {}", e);
+                    skipped.push(format!("Cannot read directory entry: {}", e));
+                    break;
+                }
+                (Err(e), None) => {
+                    return Err(Error::invalid_territory(format!("Cannot read directory entry: {}", e)));
                 }
             };
 
             // Get filename from index entry
             let filename = match entry.key() {
                 Some(Ok(key)) => key,
-                _ => continue,
+                Some(Err(e)) => {
+                    if let Some(skipped) = skipped.as_deref_mut() {
+                        tracing::warn!("Skipping directory entry with unreadable name: {}", e);
+                        skipped.push(format!("Cannot read directory entry name: {}", e));
+                    }
+                    continue;
+                }
+                None => continue,
             };
 
-            // Skip DOS names and only use Win32 or Win32+DOS names
+            // List Win32 or Win32+DOS names, keeping DOS names as aliases
+            let record = entry.file_reference().file_record_number();
             if filename.namespace() == NtfsFileNamespace::Dos {
+                short_names.insert(record, filename.name().to_string_lossy());
                 continue;
             }
 
@@ -182,25 +289,11 @@ impl<T: Read + Seek + Send + Sync> NtfsTerritory<T> {
                 continue;
             }
 
-            let is_directory = filename.is_directory();
-            let size = filename.allocated_size();
-
-            // Get timestamps from filename
-            let created = ntfs_time_to_datetime(filename.creation_time());
-            let modified = ntfs_time_to_datetime(filename.modification_time());
-            let accessed = ntfs_time_to_datetime(filename.access_time());
-
-            entries.push(OccupantInfo {
-                name: name.to_string(),
-                is_directory,
-                size,
-                created,
-                modified,
-                accessed,
-                attributes: filename.file_attributes().bits(),
-            });
+            entries.push((record, Self::index_occupant(ntfs, reader, &filename, entry.file_reference())));
         }
 
+        let mut entries = attach_short_names(entries, &short_names);
+
         // Sort entries: directories first, then alphabetically
         entries.sort_by(|a, b| {
             match (a.is_directory, b.is_directory) {
@@ -213,6 +306,43 @@ impl<T: Read + Seek + Send + Sync> NtfsTerritory<T> {
         Ok(entries)
     }
 
+    /// Read and parse a file's `$REPARSE_POINT` attribute, if it has one
+    fn read_reparse_point<R: Read + Seek>(
+        ntfs: &Ntfs,
+        reader: &mut R,
+        file_ref: ntfs::NtfsFileReference,
+    ) -> Option<ReparsePoint> {
+        // Reparse data is limited to 16 KiB by the file system
+        const MAX_REPARSE_DATA_SIZE: u64 = 16 * 1024;
+
+        let file = file_ref.to_file(ntfs, reader).ok()?;
+        let mut attrs = file.attributes();
+        while let Some(attr_result) = attrs.next(reader) {
+            let Ok(attr_item) = attr_result else {
+                continue;
+            };
+            let Ok(attr) = attr_item.to_attribute() else {
+                continue;
+            };
+            if attr.ty().ok() != Some(ntfs::NtfsAttributeType::ReparsePoint) {
+                continue;
+            }
+
+            let length = attr.value_length();
+            if length > MAX_REPARSE_DATA_SIZE {
+                tracing::warn!("Reparse point data too large: {} bytes", length);
+                return None;
+            }
+
+            let mut data = vec![0u8; length as usize];
+            let mut value = attr.value(reader).ok()?;
+            value.read_exact(reader, &mut data).ok()?;
+            return ReparsePoint::parse(&data);
+        }
+
+        None
+    }
+
     /// Find a file or directory by path
     pub fn find_by_path(&mut self, path: &str) -> Result<NtfsFile<'_>> {
         let path = path.trim_matches('/').trim_matches('\\');
@@ -264,10 +394,421 @@ impl<T: Read + Seek + Send + Sync> NtfsTerritory<T> {
         Ok(current)
     }
 
+    /// Look a path up through the directory indexes
+    ///
+    /// Returns the `$FILE_NAME` key of the final component in its parent's
+    /// index, along with the file it refers to.
+    fn index_entry_by_path<R: Read + Seek>(
+        ntfs: &Ntfs,
+        reader: &mut R,
+        path: &str,
+    ) -> Result<(NtfsFileName, NtfsFileReference)> {
+        let parts: Vec<&str> = path
+            .split(['/', '\\'])
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        if parts.is_empty() {
+            return Err(Error::not_found("Empty path".to_string()));
+        }
+
+        let mut current = ntfs.root_directory(reader)
+            .map_err(|e| Error::not_found(format!("Cannot read root: {}", e)))?;
+
+        for (i, part) in parts.iter().enumerate() {
+            let (filename, file_ref) = Self::find_index_entry(reader, &current, part)?
+                .ok_or_else(|| Error::not_found(format!("Path component not found: {}", part)))?;
+
+            if i == parts.len() - 1 {
+                return Ok((filename, file_ref));
+            }
+
+            if !filename.is_directory() {
+                return Err(Error::not_found(format!("Not a directory: {}", part)));
+            }
+            current = file_ref.to_file(ntfs, reader)
+                .map_err(|e| Error::not_found(format!("Cannot read file '{}': {}", part, e)))?;
+        }
+
+        Err(Error::not_found(format!("File not found: {}", path)))
+    }
+
+    /// Find a name in a directory's index, ignoring ASCII case
+    ///
+    /// Returns the name's `$FILE_NAME` key and the file it refers to. DOS
+    /// short names are not matched.
+    fn find_index_entry<R: Read + Seek>(
+        reader: &mut R,
+        dir: &NtfsFile,
+        name: &str,
+    ) -> Result<Option<(NtfsFileName, NtfsFileReference)>> {
+        let index = dir.directory_index(reader)
+            .map_err(|e| Error::not_found(format!("Cannot read directory: {}", e)))?;
+
+        let mut iter = index.entries();
+        while let Some(entry_result) = iter.next(reader) {
+            let Ok(entry) = entry_result else { continue };
+            if let Some(Ok(key)) = entry.key() {
+                if key.namespace() != NtfsFileNamespace::Dos
+                    && key.name().to_string_lossy().eq_ignore_ascii_case(name)
+                {
+                    return Ok(Some((key, entry.file_reference())));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get metadata for a path from its directory index entry
+    ///
+    /// Uses the `$FILE_NAME` key in the parent's index, as listing does,
+    /// so only the file's own record is opened, for its link count and
+    /// link target.
+    fn occupant_by_path<R: Read + Seek>(&self, reader: &mut R, path: &str) -> Result<OccupantInfo> {
+        let (filename, file_ref) = Self::index_entry_by_path(&self.ntfs, reader, path)?;
+        Ok(Self::index_occupant(&self.ntfs, reader, &filename, file_ref))
+    }
+
+    /// Build occupant info from a directory index entry's `$FILE_NAME` key
+    ///
+    /// The file's own record is opened for its link count and, for reparse
+    /// points, its link target.
+    fn index_occupant<R: Read + Seek>(
+        ntfs: &Ntfs,
+        reader: &mut R,
+        filename: &NtfsFileName,
+        file_ref: NtfsFileReference,
+    ) -> OccupantInfo {
+        let link_target = if filename.file_attributes().contains(NtfsFileAttributeFlags::REPARSE_POINT) {
+            Self::read_reparse_point(ntfs, reader, file_ref)
+                .and_then(|reparse| reparse.target())
+        } else {
+            None
+        };
+        let link_count = file_ref
+            .to_file(ntfs, reader)
+            .map_or(1, |file| hard_link_count(&file, reader));
+        file_name_occupant(filename, link_target).with_link_count(link_count)
+    }
+
+    /// Read up to `limit` entries of a directory, starting at `cursor`
+    ///
+    /// A cursor names the index node and the entry within it that the page
+    /// starts at (see [`encode_index_cursor`]). The walk takes that entry's
+    /// `$FILE_NAME` key and descends the B-tree to it, so only the nodes on
+    /// the path to the key and the page's own nodes are read, and only the
+    /// page's file records are opened. Returns the occupants and the cursor
+    /// to resume from, or `None` once the end of the index is reached. DOS
+    /// names are looked up in each file's record, since the index may keep
+    /// them on another page.
+    fn read_directory_page<R: Read + Seek>(
+        ntfs: &Ntfs,
+        reader: &mut R,
+        dir: &NtfsFile,
+        cursor: u64,
+        limit: usize,
+    ) -> Result<(Vec<OccupantInfo>, Option<u64>)> {
+        let index = DirectoryIndex::open(dir, reader)?;
+
+        let from = match cursor {
+            0 => None,
+            cursor => {
+                let invalid = || Error::InvalidOperation(format!("Invalid NTFS directory cursor: {}", cursor));
+                let (node, ordinal) = decode_index_cursor(cursor).ok_or_else(invalid)?;
+                let entries = index.node(reader, node).map_err(|_| invalid())?;
+                let entry = entries.into_iter().nth(ordinal).ok_or_else(invalid)?;
+                Some(entry.key.ok_or_else(invalid)?)
+            }
+        };
+
+        let mut page = IndexPage {
+            occupants: Vec::new(),
+            limit,
+            next: None,
+        };
+        Self::walk_index_page(ntfs, reader, &index, None, from.as_ref(), &mut page, 0)?;
+        Ok((page.occupants, page.next))
+    }
+
+    /// Add the entries of an index node and its subnodes that sort at or
+    /// after `from` to `page`, returning whether the page filled up
+    ///
+    /// Entries before `from` are skipped along with their subnodes, which
+    /// only hold smaller keys.
+    fn walk_index_page<R: Read + Seek>(
+        ntfs: &Ntfs,
+        reader: &mut R,
+        index: &DirectoryIndex,
+        node: Option<Vcn>,
+        from: Option<&NtfsFileName>,
+        page: &mut IndexPage,
+        depth: usize,
+    ) -> Result<bool> {
+        // Corrupt subnode links could otherwise loop forever
+        const MAX_INDEX_DEPTH: usize = 32;
+        if depth > MAX_INDEX_DEPTH {
+            return Err(Error::invalid_territory("NTFS directory index is nested too deeply"));
+        }
+
+        for (ordinal, entry) in index.node(reader, node)?.into_iter().enumerate() {
+            let order = match (&entry.key, from) {
+                (Some(key), Some(from)) => collate_file_names(key, from),
+                _ => Ordering::Greater,
+            };
+            if order == Ordering::Less {
+                continue;
+            }
+            // An entry equal to `from` only has smaller keys below it
+            if order == Ordering::Greater {
+                if let Some(subnode) = entry.subnode {
+                    if Self::walk_index_page(ntfs, reader, index, Some(subnode), from, page, depth + 1)? {
+                        return Ok(true);
+                    }
+                }
+            }
+
+            // The last entry of a node has no key, only a subnode
+            let Some(filename) = entry.key else { continue };
+            let name = filename.name().to_string_lossy();
+            if filename.namespace() == NtfsFileNamespace::Dos
+                || name == "."
+                || name == ".."
+                || name.starts_with('$')
+            {
+                continue;
+            }
+
+            // A further entry exists, so the page ends before it
+            if page.occupants.len() == page.limit {
+                page.next = Some(encode_index_cursor(node, ordinal));
+                return Ok(true);
+            }
+
+            let mut occupant = Self::index_occupant(ntfs, reader, &filename, entry.file_ref);
+            occupant.short_name = entry
+                .file_ref
+                .to_file(ntfs, reader)
+                .ok()
+                .and_then(|file| file.name(reader, Some(NtfsFileNamespace::Dos), None))
+                .and_then(|short| short.ok())
+                .map(|short| short.name().to_string_lossy())
+                .filter(|short| !short.eq_ignore_ascii_case(&occupant.name));
+            page.occupants.push(occupant);
+        }
+
+        Ok(false)
+    }
+
+    /// Get the extents of a file's unnamed `$DATA` attribute
+    ///
+    /// Resident data and directories (which have no `$DATA`) have none.
+    fn data_runs_by_path<R: Read + Seek>(&self, reader: &mut R, path: &str) -> Result<Vec<(u64, u64)>> {
+        let (_, file_ref) = Self::index_entry_by_path(&self.ntfs, reader, path)?;
+        let file = file_ref.to_file(&self.ntfs, reader)
+            .map_err(|e| Error::invalid_territory(format!("Cannot read file '{}': {}", path, e)))?;
+
+        let data_item = match file.data(reader, "") {
+            Some(result) => result.map_err(|e| Error::invalid_territory(format!("Cannot read $DATA: {}", e)))?,
+            None => return Ok(Vec::new()),
+        };
+        let data_attr = data_item.to_attribute()
+            .map_err(|e| Error::invalid_territory(format!("Cannot read $DATA attribute: {}", e)))?;
+        let value = data_attr.value(reader)
+            .map_err(|e| Error::invalid_territory(format!("Cannot read $DATA value: {}", e)))?;
+
+        let mut extents = Extents::new(value.len());
+        match value {
+            NtfsAttributeValue::Resident(_) => {}
+            NtfsAttributeValue::NonResident(value) => {
+                for run in value.data_runs() {
+                    let run = run.map_err(|e| Error::invalid_territory(format!("Invalid data run: {}", e)))?;
+                    let more = match run.data_position().value() {
+                        Some(position) => extents.push(position.get(), run.allocated_size()),
+                        None => extents.skip(run.allocated_size()),
+                    };
+                    if !more {
+                        break;
+                    }
+                }
+            }
+            NtfsAttributeValue::AttributeListNonResident(_) => {
+                return Err(Error::unsupported(format!(
+                    "$DATA of '{}' spans several MFT records",
+                    path
+                )));
+            }
+        }
+
+        Ok(extents.into_runs())
+    }
+
+    /// Get the length of a file's unnamed `$DATA` if it is stored in clusters
+    ///
+    /// Returns None for resident data, which lives in the MFT record, and
+    /// for a file without `$DATA`. The length comes from `$DATA` rather than
+    /// the directory index, which can lag behind it.
+    fn nonresident_data_length<R: Read + Seek>(&self, reader: &mut R, path: &str) -> Result<Option<u64>> {
+        let (filename, file_ref) = Self::index_entry_by_path(&self.ntfs, reader, path)?;
+        if filename.is_directory() {
+            return Err(Error::InvalidOperation(format!("Path is a directory: {}", path)));
+        }
+        let file = file_ref.to_file(&self.ntfs, reader)
+            .map_err(|e| Error::invalid_territory(format!("Cannot read file '{}': {}", path, e)))?;
+
+        let data_item = match file.data(reader, "") {
+            Some(result) => result.map_err(|e| Error::invalid_territory(format!("Cannot read $DATA: {}", e)))?,
+            None => return Ok(None),
+        };
+        let data_attr = data_item.to_attribute()
+            .map_err(|e| Error::invalid_territory(format!("Cannot read $DATA attribute: {}", e)))?;
+
+        Ok((!data_attr.is_resident()).then(|| data_attr.value_length()))
+    }
+
+    /// Read a window of a file's unnamed `$DATA` attribute
+    ///
+    /// Seeks within the attribute value, so sparse and resident data are
+    /// read as well as allocated runs.
+    fn read_range_by_path<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        path: &str,
+        offset: u64,
+        length: u64,
+    ) -> Result<Vec<u8>> {
+        let (filename, file_ref) = Self::index_entry_by_path(&self.ntfs, reader, path)?;
+        if filename.is_directory() {
+            return Err(Error::InvalidOperation(format!("Path is a directory: {}", path)));
+        }
+        let file = file_ref.to_file(&self.ntfs, reader)
+            .map_err(|e| Error::invalid_territory(format!("Cannot read file '{}': {}", path, e)))?;
+
+        let data_item = match file.data(reader, "") {
+            Some(result) => result.map_err(|e| Error::invalid_territory(format!("Cannot read $DATA: {}", e)))?,
+            None => return Err(Error::invalid_territory(format!("'{}' has no $DATA attribute", path))),
+        };
+        let data_attr = data_item.to_attribute()
+            .map_err(|e| Error::invalid_territory(format!("Cannot read $DATA attribute: {}", e)))?;
+        validate_file_range(data_attr.value_length(), offset, length)?;
+
+        let mut value = data_attr.value(reader)
+            .map_err(|e| Error::invalid_territory(format!("Cannot read $DATA value: {}", e)))?;
+        value.seek(reader, SeekFrom::Start(offset))
+            .map_err(|e| Error::invalid_territory(format!("Cannot seek in $DATA: {}", e)))?;
+
+        let mut data = vec![0u8; length as usize];
+        value.read_exact(reader, &mut data)
+            .map_err(|e| Error::invalid_territory(format!("Cannot read $DATA: {}", e)))?;
+        Ok(data)
+    }
+
+    /// Get a file by its MFT record number (inode)
+    ///
+    /// Record 0 is `$MFT` itself and record 5 the root directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record is beyond the end of the MFT, can't be
+    /// read, or is not in use
+    pub fn file_by_record_number(&mut self, n: u64) -> Result<NtfsFile<'_>> {
+        let record_count = self.mft_record_count()?;
+        if n >= record_count {
+            return Err(Error::not_found(format!(
+                "MFT record {} is out of range ({} records)",
+                n, record_count
+            )));
+        }
+
+        let file = self.ntfs.file(&mut self.reader, n)
+            .map_err(|e| Error::invalid_territory(format!("Cannot read MFT record {}: {}", n, e)))?;
+
+        if !file.flags().contains(NtfsFileFlags::IN_USE) {
+            return Err(Error::not_found(format!("MFT record {} is not in use", n)));
+        }
+
+        Ok(file)
+    }
+
+    /// Estimate the number of MFT records from the size of `$MFT`'s data
+    ///
+    /// Includes records that are allocated to the MFT but not in use.
+    pub fn mft_record_count(&mut self) -> Result<u64> {
+        let reader = &mut self.reader;
+
+        let mft = self.ntfs.file(reader, 0)
+            .map_err(|e| Error::invalid_territory(format!("Cannot read $MFT: {}", e)))?;
+        let data_item = match mft.data(reader, "") {
+            Some(result) => result.map_err(|e| Error::invalid_territory(format!("Cannot read $MFT data: {}", e)))?,
+            None => return Err(Error::invalid_territory("$MFT has no data".to_string())),
+        };
+        let data_attr = data_item.to_attribute()
+            .map_err(|e| Error::invalid_territory(format!("Cannot read $MFT attribute: {}", e)))?;
+
+        Ok(data_attr.value_length() / self.ntfs.file_record_size() as u64)
+    }
+
+    /// List in-use files whose parent chain doesn't lead to the root
+    ///
+    /// Such orphans are unreachable by path traversal; they are also listed
+    /// by [`read_directory_at_path`](Self::read_directory_at_path) under the
+    /// synthetic [`ORPHANS_DIRECTORY`]. Metadata files are skipped.
+    pub fn list_orphans(&mut self) -> Result<Vec<OccupantInfo>> {
+        let record_count = self.mft_record_count()?;
+        let ntfs = &self.ntfs;
+        let reader = &mut self.reader;
+
+        let mut links = HashMap::new();
+        let mut candidates = Vec::new();
+
+        for record in 0..record_count {
+            let Ok(file) = ntfs.file(reader, record) else { continue };
+            if !file.flags().contains(NtfsFileFlags::IN_USE) {
+                continue;
+            }
+
+            // Extension records carry no $FILE_NAME and aren't files themselves
+            let Some(filename) = preferred_file_name(&file, reader) else { continue };
+            let parent = filename.parent_directory_reference();
+            links.insert(record, RecordLink {
+                sequence: file.sequence_number(),
+                is_directory: file.is_directory(),
+                parent: parent.file_record_number(),
+                parent_sequence: parent.sequence_number(),
+            });
+
+            if record >= FIRST_USER_RECORD {
+                let link_count = hard_link_count(&file, reader);
+                candidates.push((record, file_name_occupant(&filename, None).with_link_count(link_count)));
+            }
+        }
+
+        Ok(candidates
+            .into_iter()
+            .filter(|(record, _)| !resolves_to_root(&links, *record))
+            .map(|(_, occupant)| occupant)
+            .collect())
+    }
+
     /// Read directory at a specific path
+    ///
+    /// [`ORPHANS_DIRECTORY`] lists orphaned files (see [`list_orphans`](Self::list_orphans)).
     pub fn read_directory_at_path(&mut self, path: &str) -> Result<Vec<OccupantInfo>> {
+        Ok(self.read_directory_at_path_partial(path, false)?.occupants)
+    }
+
+    /// Read directory at a specific path, optionally skipping damaged entries
+    ///
+    /// With `best_effort`, entries that fail to read are logged and kept in
+    /// [`PartialListing::skipped`] rather than failing the listing; the
+    /// path leading to the directory must still resolve.
+    pub fn read_directory_at_path_partial(&mut self, path: &str, best_effort: bool) -> Result<PartialListing> {
         let path = path.trim_matches('/').trim_matches('\\');
 
+        if path.eq_ignore_ascii_case(ORPHANS_DIRECTORY) {
+            return self.list_orphans().map(PartialListing::from);
+        }
+
         let ntfs = &self.ntfs;
         let reader = &mut self.reader;
 
@@ -318,11 +859,27 @@ impl<T: Read + Seek + Send + Sync> NtfsTerritory<T> {
             return Err(Error::not_found(format!("Not a directory: {}", path)));
         }
 
-        Self::read_directory_entries_static(ntfs, reader, &dir)
+        let mut skipped = Vec::new();
+        let occupants =
+            Self::read_directory_entries_static(ntfs, reader, &dir, best_effort.then_some(&mut skipped))?;
+        let mut listing = PartialListing::with_skipped(occupants, skipped);
+        for (skipped_path, _) in &mut listing.skipped {
+            *skipped_path = format!("/{}", path);
+        }
+        Ok(listing)
     }
 
     /// Extract file data at a specific path
     pub fn extract_file_data(&mut self, path: &str) -> Result<Vec<u8>> {
+        self.extract_stream_data(path, "")
+    }
+
+    /// Extract a data stream of the file at a specific path
+    ///
+    /// An empty `stream_name` is the file's main data; any other name is an
+    /// alternate data stream, as listed by
+    /// [`list_alternate_data_streams`](Self::list_alternate_data_streams).
+    pub fn extract_stream_data(&mut self, path: &str, stream_name: &str) -> Result<Vec<u8>> {
         let path = path.trim_matches('/').trim_matches('\\');
 
         let ntfs = &self.ntfs;
@@ -374,9 +931,10 @@ impl<T: Read + Seek + Send + Sync> NtfsTerritory<T> {
         }
 
         // Get the $DATA attribute (unnamed = main data stream)
-        let data_item = match file.data(reader, "") {
+        let data_item = match data_stream_item(&file, reader, stream_name) {
             Some(result) => result.map_err(|e| Error::invalid_territory(format!("Cannot read $DATA: {}", e)))?,
-            None => return Err(Error::not_found("File has no data".to_string())),
+            None if stream_name.is_empty() => return Err(Error::not_found("File has no data".to_string())),
+            None => return Err(Error::not_found(format!("Stream not found: {}:{}", path, stream_name))),
         };
 
         let data_attr = data_item.to_attribute()
@@ -384,25 +942,110 @@ impl<T: Read + Seek + Send + Sync> NtfsTerritory<T> {
 
         // Check file size against extraction limit
         let data_size = data_attr.value_length();
-        use totalimage_core::MAX_FILE_EXTRACT_SIZE;
-        if data_size > MAX_FILE_EXTRACT_SIZE {
-            return Err(Error::invalid_territory(format!(
-                "File size {} exceeds extraction limit {}",
-                data_size, MAX_FILE_EXTRACT_SIZE
-            )));
-        }
+        self.limits.validate_extract_size(data_size)?;
 
-        // Read the data
-        let mut data = vec![0u8; data_size as usize];
-        let mut value_reader = data_attr.value(reader)
+        let value = data_attr.value(reader)
             .map_err(|e| Error::invalid_territory(format!("Cannot open data stream: {}", e)))?;
 
+        // Resident data is already in the MFT record read above
+        let mut value_reader = match value {
+            NtfsAttributeValue::Resident(resident) => return Ok(resident.data().to_vec()),
+            value => value,
+        };
+
+        let mut data = vec![0u8; data_size as usize];
         value_reader.read_exact(reader, &mut data)
             .map_err(|e| Error::invalid_territory(format!("Cannot read data: {}", e)))?;
 
         Ok(data)
     }
 
+    /// Count free clusters by scanning the `$Bitmap` metadata file
+    ///
+    /// `reader` must be positioned over the same volume this territory was
+    /// parsed from.
+    pub fn free_cluster_count<R: Read + Seek>(&self, reader: &mut R) -> Result<u64> {
+        /// File record number of `$Bitmap`
+        const BITMAP_RECORD: u64 = 6;
+
+        let total_clusters = self.volume_info.total_size / self.volume_info.cluster_size as u64;
+
+        let bitmap_file = self.ntfs.file(reader, BITMAP_RECORD)
+            .map_err(|e| Error::invalid_territory(format!("Cannot read $Bitmap: {}", e)))?;
+        let data_item = match bitmap_file.data(reader, "") {
+            Some(result) => result.map_err(|e| Error::invalid_territory(format!("Cannot read $Bitmap data: {}", e)))?,
+            None => return Err(Error::invalid_territory("$Bitmap has no data".to_string())),
+        };
+        let data_attr = data_item.to_attribute()
+            .map_err(|e| Error::invalid_territory(format!("Cannot read $Bitmap attribute: {}", e)))?;
+        let mut value_reader = data_attr.value(reader)
+            .map_err(|e| Error::invalid_territory(format!("Cannot open $Bitmap stream: {}", e)))?;
+
+        let bitmap_size = total_clusters.div_ceil(8);
+        if data_attr.value_length() < bitmap_size {
+            return Err(Error::invalid_territory(format!(
+                "$Bitmap is {} bytes, expected at least {}",
+                data_attr.value_length(), bitmap_size
+            )));
+        }
+
+        // Read in chunks; the bitmap of a large volume is tens of megabytes
+        let mut chunk = vec![0u8; 64 * 1024];
+        let mut remaining = bitmap_size;
+        let mut used = 0u64;
+        while remaining > 0 {
+            let len = remaining.min(chunk.len() as u64) as usize;
+            value_reader.read_exact(reader, &mut chunk[..len])
+                .map_err(|e| Error::invalid_territory(format!("Cannot read $Bitmap: {}", e)))?;
+            used += chunk[..len].iter().map(|b| b.count_ones() as u64).sum::<u64>();
+            remaining -= len as u64;
+        }
+
+        // Bits past the last cluster are padding; don't count them as used
+        let trailing_bits = (bitmap_size * 8 - total_clusters) as u32;
+        if trailing_bits > 0 {
+            let last = chunk[((bitmap_size - 1) % chunk.len() as u64) as usize];
+            used -= (last >> (8 - trailing_bits)).count_ones() as u64;
+        }
+
+        Ok(total_clusters - used)
+    }
+
+    /// Check whether the volume has a USN change journal
+    ///
+    /// The journal is `$Extend\$UsnJrnl`, which Windows only creates once
+    /// change tracking has been enabled on the volume.
+    pub fn has_usn_journal(&mut self) -> bool {
+        Self::index_entry_by_path(&self.ntfs, &mut self.reader, USN_JOURNAL_PATH).is_ok()
+    }
+
+    /// Get the size of the USN change journal's `$J` stream
+    ///
+    /// Returns `None` if the volume has no journal or its `$J` stream can't
+    /// be read. `$J` is sparse, as records that have aged out are
+    /// deallocated, so this is its logical size rather than the space the
+    /// journal occupies.
+    pub fn usn_journal_size(&mut self) -> Option<u64> {
+        let ntfs = &self.ntfs;
+        let reader = &mut self.reader;
+
+        let (_, file_ref) = Self::index_entry_by_path(ntfs, reader, USN_JOURNAL_PATH).ok()?;
+        let file = file_ref.to_file(ntfs, reader).ok()?;
+        let data_item = file.data(reader, USN_JOURNAL_STREAM)?.ok()?;
+        Some(data_item.to_attribute().ok()?.value_length())
+    }
+
+    /// Get the size of the `$LogFile` transaction log
+    ///
+    /// Returns `None` if its record or data can't be read.
+    pub fn log_file_size(&mut self) -> Option<u64> {
+        let reader = &mut self.reader;
+
+        let file = self.ntfs.file(reader, LOG_FILE_RECORD).ok()?;
+        let data_item = file.data(reader, "")?.ok()?;
+        Some(data_item.to_attribute().ok()?.value_length())
+    }
+
     /// List alternate data streams for a file
     pub fn list_alternate_data_streams(&mut self, path: &str) -> Result<Vec<String>> {
         let path = path.trim_matches('/').trim_matches('\\');
@@ -490,8 +1133,12 @@ impl<T: Read + Seek + Send + Sync + 'static> Territory for NtfsTerritory<T> {
         Ok(self.volume_info.label.clone().unwrap_or_else(|| "NTFS".to_string()))
     }
 
-    fn headquarters(&self) -> Result<Box<dyn DirectoryCell>> {
-        Ok(Box::new(NtfsRootDirectory))
+    fn headquarters(&self) -> Result<Box<dyn DirectoryCell<'_> + '_>> {
+        Ok(Box::new(NtfsDirectoryCell {
+            territory: self,
+            name: "/".to_string(),
+            record: ROOT_RECORD,
+        }))
     }
 
     fn domain_size(&self) -> u64 {
@@ -507,12 +1154,69 @@ impl<T: Read + Seek + Send + Sync + 'static> Territory for NtfsTerritory<T> {
         self.volume_info.cluster_size as u64
     }
 
+    fn space_summary(&self, stream: &mut dyn ReadSeek) -> Result<SpaceSummary> {
+        let mut reader = stream;
+        let free_clusters = self.free_cluster_count(&mut reader)?;
+        let cluster_size = self.volume_info.cluster_size as u64;
+        Ok(SpaceSummary::from_blocks(
+            self.volume_info.total_size,
+            cluster_size,
+            self.volume_info.total_size / cluster_size,
+            free_clusters,
+        ))
+    }
+
     fn hierarchical(&self) -> bool {
         true // NTFS supports subdirectories
     }
 
-    fn navigate_to(&self, _path: &str) -> Result<Box<dyn DirectoryCell>> {
-        self.headquarters()
+    fn fs_features(&self) -> FsFeatures {
+        FsFeatures {
+            writable: false,
+            timestamps: true,
+            symlinks: true,
+            alternate_data_streams: true,
+            compression: true,
+            hard_links: true,
+            long_names: true,
+            short_names: true,
+            max_file_size: u64::MAX,
+        }
+    }
+
+    fn file_metadata(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<OccupantInfo> {
+        let mut reader = stream;
+        self.occupant_by_path(&mut reader, path)
+    }
+
+    fn data_runs(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<(u64, u64)>> {
+        let mut reader = stream;
+        self.data_runs_by_path(&mut reader, path)
+    }
+
+    fn read_file_range(
+        &self,
+        stream: &mut dyn ReadSeek,
+        path: &str,
+        offset: u64,
+        length: u64,
+    ) -> Result<Vec<u8>> {
+        let mut reader = stream;
+        self.read_range_by_path(&mut reader, path, offset, length)
+    }
+
+    fn file_slack(&self, stream: &mut dyn ReadSeek, path: &str) -> Result<Vec<u8>> {
+        let mut reader = stream;
+        let Some(length) = self.nonresident_data_length(&mut reader, path)? else {
+            return Ok(Vec::new());
+        };
+
+        let runs = self.data_runs_by_path(&mut reader, path)?;
+        read_slack(reader, &runs, length, self.volume_info.cluster_size as u64)
+    }
+
+    fn decode_attributes(&self, raw: u32) -> Vec<AttributeFlag> {
+        AttributeFlag::decode(raw, &ATTRIBUTE_FLAGS)
     }
 
     fn extract_file(&mut self, path: &str) -> Result<Vec<u8>> {
@@ -520,27 +1224,769 @@ impl<T: Read + Seek + Send + Sync + 'static> Territory for NtfsTerritory<T> {
     }
 }
 
-/// NTFS root directory cell (placeholder for trait implementation)
-struct NtfsRootDirectory;
+/// Build occupant info from a `$FILE_NAME` attribute
+fn file_name_occupant(filename: &NtfsFileName, link_target: Option<String>) -> OccupantInfo {
+    OccupantInfo {
+        name: filename.name().to_string_lossy(),
+        is_directory: filename.is_directory(),
+        size: filename.allocated_size(),
+        created: ntfs_time_to_datetime(filename.creation_time()),
+        modified: ntfs_time_to_datetime(filename.modification_time()),
+        accessed: ntfs_time_to_datetime(filename.access_time()),
+        timestamp_is_local: false,
+        attributes: filename.file_attributes().bits(),
+        link_target,
+        short_name: None,
+        link_count: 1,
+        path: None,
+    }
+}
+
+/// Pair directory entries with the DOS names of the same MFT records
+///
+/// A DOS name is only attached when it differs from the long name other
+/// than in case.
+fn attach_short_names(entries: Vec<(u64, OccupantInfo)>, short_names: &HashMap<u64, String>) -> Vec<OccupantInfo> {
+    entries
+        .into_iter()
+        .map(|(record, mut occupant)| {
+            occupant.short_name = short_names
+                .get(&record)
+                .filter(|short| !short.eq_ignore_ascii_case(&occupant.name))
+                .cloned();
+            occupant
+        })
+        .collect()
+}
+
+/// Count a file's hard links from its MFT record
+fn hard_link_count<R: Read + Seek>(file: &NtfsFile, reader: &mut R) -> u32 {
+    let has_dos_alias = file.name(reader, Some(NtfsFileNamespace::Dos), None).is_some();
+    links_excluding_alias(file.hard_link_count(), has_dos_alias)
+}
+
+/// Discount a DOS alias from a record's hard-link count
+///
+/// The record counts every `$FILE_NAME`, including a DOS-namespace alias
+/// stored beside the long name, which names the same link rather than
+/// another one.
+fn links_excluding_alias(recorded: u16, has_dos_alias: bool) -> u32 {
+    u32::from(recorded).saturating_sub(u32::from(has_dos_alias)).max(1)
+}
+
+/// A directory's `$I30` index, read a node at a time
+struct DirectoryIndex<'n, 'f> {
+    /// `$INDEX_ROOT`, holding the top node
+    root: NtfsAttributeItem<'n, 'f>,
+    /// `$INDEX_ALLOCATION`, holding the index records of large directories
+    allocation: Option<NtfsAttributeItem<'n, 'f>>,
+    /// Size of each index record
+    record_size: u32,
+}
+
+impl<'n, 'f> DirectoryIndex<'n, 'f> {
+    /// Find the index attributes of a directory
+    fn open<R: Read + Seek>(dir: &'f NtfsFile<'n>, reader: &mut R) -> Result<Self> {
+        let mut root = None;
+        let mut allocation = None;
+        let mut attrs = dir.attributes();
+        while let Some(item) = attrs.next(reader) {
+            let item = item.map_err(index_error)?;
+            let attr = item.to_attribute().map_err(index_error)?;
+            if attr.name().map(|name| name.to_string_lossy()).ok().as_deref() != Some("$I30") {
+                continue;
+            }
+            match attr.ty() {
+                Ok(NtfsAttributeType::IndexRoot) => root = Some(item),
+                Ok(NtfsAttributeType::IndexAllocation) => allocation = Some(item),
+                _ => {}
+            }
+        }
+
+        let root = root.ok_or_else(|| Error::invalid_territory("Directory has no $INDEX_ROOT"))?;
+        let record_size = root
+            .to_attribute()
+            .and_then(|attr| attr.resident_structured_value::<NtfsIndexRoot>())
+            .map_err(index_error)?
+            .index_record_size();
+        Ok(Self {
+            root,
+            allocation,
+            record_size,
+        })
+    }
+
+    /// Read the entries of the root node, or of the index record at a VCN
+    fn node<R: Read + Seek>(&self, reader: &mut R, node: Option<Vcn>) -> Result<Vec<IndexNodeEntry>> {
+        let Some(vcn) = node else {
+            let attr = self.root.to_attribute().map_err(index_error)?;
+            let root = attr.resident_structured_value::<NtfsIndexRoot>().map_err(index_error)?;
+            return IndexNodeEntry::copy_all(root.entries().map_err(index_error)?);
+        };
+
+        let item = self
+            .allocation
+            .as_ref()
+            .ok_or_else(|| Error::invalid_territory("Directory index has subnodes but no $INDEX_ALLOCATION"))?;
+        let attr = item.to_attribute().map_err(index_error)?;
+        let allocation = attr.structured_value::<_, NtfsIndexAllocation>(reader).map_err(index_error)?;
+        let record = allocation.record_from_vcn(reader, self.record_size, vcn).map_err(index_error)?;
+        IndexNodeEntry::copy_all(record.entries().map_err(index_error)?)
+    }
+}
+
+/// An index entry copied out of its node
+struct IndexNodeEntry {
+    /// The entry's `$FILE_NAME`; `None` for the last entry of a node
+    key: Option<NtfsFileName>,
+    file_ref: NtfsFileReference,
+    /// Node holding the keys that sort before this one
+    subnode: Option<Vcn>,
+}
+
+impl IndexNodeEntry {
+    fn copy_all(entries: NtfsIndexNodeEntries<NtfsFileNameIndex>) -> Result<Vec<Self>> {
+        entries
+            .map(|entry| {
+                let entry = entry.map_err(index_error)?;
+                Ok(Self {
+                    key: entry.key().transpose().map_err(index_error)?,
+                    file_ref: entry.file_reference(),
+                    subnode: entry.subnode_vcn().transpose().map_err(index_error)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A page of directory entries being filled from the index
+struct IndexPage {
+    occupants: Vec<OccupantInfo>,
+    limit: usize,
+    /// Cursor of the first entry past the page, once one is found
+    next: Option<u64>,
+}
+
+fn index_error(e: NtfsError) -> Error {
+    Error::invalid_territory(format!("Cannot read directory index: {}", e))
+}
+
+/// Pack the position of an index entry into a page cursor
+///
+/// The high bits hold the node, 0 for the index root or else the index
+/// record's VCN plus one, and the low 16 bits the entry's ordinal in the
+/// node plus one, so only the start of a directory is cursor 0.
+fn encode_index_cursor(node: Option<Vcn>, ordinal: usize) -> u64 {
+    let node = node.map_or(0, |vcn| vcn.value() as u64 + 1);
+    (node << 16) | (ordinal as u64 + 1)
+}
+
+/// Unpack a cursor made by [`encode_index_cursor`]
+fn decode_index_cursor(cursor: u64) -> Option<(Option<Vcn>, usize)> {
+    let ordinal = (cursor & 0xFFFF).checked_sub(1)? as usize;
+    let node = match cursor >> 16 {
+        0 => None,
+        node => Some(Vcn::from(i64::try_from(node - 1).ok()?)),
+    };
+    Some((node, ordinal))
+}
+
+/// Order file names as NTFS sorts its directory indexes
+///
+/// NTFS compares names by UTF-16 code unit after mapping each through the
+/// volume's `$UpCase` table. The table isn't loaded, so units are mapped by
+/// their simple Unicode uppercase, which the table is built from.
+fn collate_file_names(a: &NtfsFileName, b: &NtfsFileName) -> Ordering {
+    fn upcase(unit: u16) -> u16 {
+        let Some(c) = char::from_u32(unit as u32) else { return unit };
+        let mut upper = c.to_uppercase();
+        match (upper.next(), upper.next()) {
+            (Some(u), None) => u16::try_from(u as u32).unwrap_or(unit),
+            _ => unit,
+        }
+    }
+
+    let (a, b) = (a.name(), b.name());
+    a.u16_iter().map(upcase).cmp(b.u16_iter().map(upcase))
+}
+
+/// Find a file's `$DATA` attribute for a data stream
+///
+/// The unnamed stream is looked up directly. Named streams are matched by
+/// walking the attributes, ignoring ASCII case, since the crate's own lookup
+/// needs the volume's `$UpCase` table loaded.
+fn data_stream_item<'n, 'f, R: Read + Seek>(
+    file: &'f NtfsFile<'n>,
+    reader: &mut R,
+    stream_name: &str,
+) -> Option<ntfs::Result<NtfsAttributeItem<'n, 'f>>> {
+    if stream_name.is_empty() {
+        return file.data(reader, "");
+    }
+
+    let mut attrs = file.attributes();
+    while let Some(attr_result) = attrs.next(reader) {
+        let attr_item = match attr_result {
+            Ok(item) => item,
+            Err(e) => return Some(Err(e)),
+        };
+        let is_stream = attr_item.to_attribute().is_ok_and(|attr| {
+            matches!(attr.ty(), Ok(ntfs::NtfsAttributeType::Data))
+                && attr.name().is_ok_and(|name| name.to_string_lossy().eq_ignore_ascii_case(stream_name))
+        });
+        if is_stream {
+            return Some(Ok(attr_item));
+        }
+    }
+    None
+}
+
+/// Get a file's long name, falling back to any `$FILE_NAME` it has
+fn preferred_file_name<R: Read + Seek>(file: &NtfsFile, reader: &mut R) -> Option<NtfsFileName> {
+    [Some(NtfsFileNamespace::Win32), Some(NtfsFileNamespace::Win32AndDos), None]
+        .into_iter()
+        .find_map(|namespace| file.name(reader, namespace, None).and_then(|name| name.ok()))
+}
+
+/// An in-use MFT record's link to its parent directory
+#[derive(Debug, Clone, Copy)]
+struct RecordLink {
+    /// Sequence number of the record itself
+    sequence: u16,
+    is_directory: bool,
+    /// Parent directory record number from `$FILE_NAME`
+    parent: u64,
+    /// Sequence number the parent reference expects
+    parent_sequence: u16,
+}
 
-impl DirectoryCell for NtfsRootDirectory {
+/// Does following parent references from `record` reach the root directory?
+///
+/// Every parent on the way must be an in-use directory whose sequence number
+/// matches the reference; a reused or missing parent breaks the chain.
+fn resolves_to_root(links: &HashMap<u64, RecordLink>, record: u64) -> bool {
+    let mut current = record;
+
+    // A chain longer than the number of records must contain a cycle
+    for _ in 0..=links.len() {
+        let Some(link) = links.get(&current) else { return false };
+        if current == ROOT_RECORD {
+            return true;
+        }
+
+        match links.get(&link.parent) {
+            Some(parent) if parent.is_directory && parent.sequence == link.parent_sequence => {
+                current = link.parent;
+            }
+            _ => return false,
+        }
+    }
+
+    false
+}
+
+/// NTFS directory cell, reading the directory's index from its MFT record
+struct NtfsDirectoryCell<'a, T: Read + Seek> {
+    territory: &'a NtfsTerritory<T>,
+    name: String,
+    /// File record number of the directory
+    record: u64,
+}
+
+impl<'a, T: Read + Seek + Send + Sync> NtfsDirectoryCell<'a, T> {
+    /// Open the directory's file record
+    fn file<R: Read + Seek>(&self, reader: &mut R) -> Result<NtfsFile<'a>> {
+        self.territory
+            .ntfs
+            .file(reader, self.record)
+            .map_err(|e| Error::invalid_territory(format!("Cannot read directory '{}': {}", self.name, e)))
+    }
+
+    fn entries(&self, stream: &mut dyn ReadSeek, skipped: Option<&mut Vec<String>>) -> Result<Vec<OccupantInfo>> {
+        let mut reader = stream;
+        let dir = self.file(&mut reader)?;
+        NtfsTerritory::<T>::read_directory_entries_static(&self.territory.ntfs, &mut reader, &dir, skipped)
+    }
+}
+
+impl<'a, T: Read + Seek + Send + Sync> DirectoryCell<'a> for NtfsDirectoryCell<'a, T> {
     fn name(&self) -> &str {
-        "/"
+        &self.name
+    }
+
+    fn list_occupants(&self, stream: &mut dyn ReadSeek) -> Result<Vec<OccupantInfo>> {
+        self.entries(stream, None)
+    }
+
+    fn list_occupants_best_effort(&self, stream: &mut dyn ReadSeek) -> Result<PartialListing> {
+        let mut skipped = Vec::new();
+        let occupants = self.entries(stream, Some(&mut skipped))?;
+        Ok(PartialListing::with_skipped(occupants, skipped))
     }
 
-    fn list_occupants(&self) -> Result<Vec<OccupantInfo>> {
-        // Simplified: return empty list
-        Ok(Vec::new())
+    /// Pages resume by descending the index to the cursor's key, opening
+    /// only the page's own records
+    fn list_page(&self, stream: &mut dyn ReadSeek, cursor: u64, limit: usize) -> Result<DirectoryPage> {
+        if limit == 0 {
+            return Ok(DirectoryPage::unmoved(cursor));
+        }
+        let mut reader = stream;
+        let ntfs = &self.territory.ntfs;
+        let dir = self.file(&mut reader)?;
+        let (occupants, next) =
+            NtfsTerritory::<T>::read_directory_page(ntfs, &mut reader, &dir, cursor, limit)?;
+        Ok(DirectoryPage { occupants, next })
     }
 
-    fn enter(&self, _name: &str) -> Result<Box<dyn DirectoryCell>> {
-        Err(Error::not_found("Subdirectory navigation not available".to_string()))
+    fn enter(&self, stream: &mut dyn ReadSeek, name: &str) -> Result<Box<dyn DirectoryCell<'a> + 'a>> {
+        let mut reader = stream;
+        let dir = self.file(&mut reader)?;
+        let (filename, file_ref) = NtfsTerritory::<T>::find_index_entry(&mut reader, &dir, name)?
+            .ok_or_else(|| Error::not_found(format!("Directory not found: {}", name)))?;
+
+        if !filename.is_directory() {
+            return Err(Error::not_found(format!("Not a directory: {}", name)));
+        }
+
+        Ok(Box::new(NtfsDirectoryCell {
+            territory: self.territory,
+            name: filename.name().to_string_lossy(),
+            record: file_ref.file_record_number(),
+        }))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::types::NtfsFileAttribute;
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Cursor;
+    use totalimage_core::ListOptions;
+
+    /// Load the 2 MiB NTFS fixture (see `testdata/README.md`)
+    /// Counts the bytes read through it
+    struct CountingReader<R> {
+        inner: R,
+        read: u64,
+    }
+
+    impl<R: Read> Read for CountingReader<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.read += n as u64;
+            Ok(n)
+        }
+    }
+
+    impl<R: Seek> Seek for CountingReader<R> {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    fn testfs1() -> Cursor<Vec<u8>> {
+        let compressed: &[u8] = include_bytes!("../../testdata/ntfs-testfs1.img.gz");
+        let mut image = Vec::new();
+        GzDecoder::new(compressed).read_to_end(&mut image).unwrap();
+        Cursor::new(image)
+    }
+
+    #[test]
+    fn test_parse_failure_variants() {
+        let mut image = testfs1().into_inner();
+        image[510..512].fill(0);
+        let result = NtfsTerritory::parse(Cursor::new(image));
+        assert!(matches!(result, Err(Error::UnrecognizedSignature(_))));
+    }
+
+    #[test]
+    fn test_attach_short_names() {
+        let entries = vec![
+            (64, OccupantInfo::file("Quarterly Report.docx".to_string(), 10)),
+            (65, OccupantInfo::file("notes.txt".to_string(), 20)),
+            (66, OccupantInfo::directory("Program Files".to_string())),
+        ];
+        let short_names = HashMap::from([
+            (64, "QUARTE~1.DOC".to_string()),
+            (65, "NOTES.TXT".to_string()),
+            (99, "ORPHAN~1".to_string()),
+        ]);
+
+        let entries = attach_short_names(entries, &short_names);
+        assert_eq!(entries[0].short_name.as_deref(), Some("QUARTE~1.DOC"));
+        assert_eq!(entries[1].short_name, None);
+        assert_eq!(entries[2].short_name, None);
+
+        // mkntfs/ntfs-3g store POSIX names only
+        let mut territory = NtfsTerritory::parse(testfs1()).unwrap();
+        let root = territory.read_directory_at_path("/").unwrap();
+        assert!(!root.is_empty());
+        assert!(root.iter().all(|occupant| occupant.short_name.is_none()));
+    }
+
+    #[test]
+    fn test_file_by_record_number() {
+        let mut territory = NtfsTerritory::parse(testfs1()).unwrap();
+
+        let record_count = territory.mft_record_count().unwrap();
+        assert!(record_count > 16, "system records plus user files: {}", record_count);
+
+        let root = territory.file_by_record_number(5).unwrap();
+        assert_eq!(root.file_record_number(), 5);
+        assert!(root.is_directory());
+        let root_position = root.position();
+        let by_path = territory.find_by_path("/").unwrap();
+        assert_eq!(by_path.position(), root_position);
+
+        let mft = territory.file_by_record_number(0).unwrap();
+        assert!(!mft.is_directory());
+
+        // Records 16-23 are reserved for system files and left unused by mkntfs
+        let err = territory.file_by_record_number(16).unwrap_err();
+        assert!(err.to_string().contains("not in use"), "{}", err);
+
+        assert!(territory.file_by_record_number(record_count).is_err());
+        assert!(territory.file_by_record_number(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_large_directory_index() {
+        let mut territory = NtfsTerritory::parse(testfs1()).unwrap();
+
+        // 512 subdirectories overflow $INDEX_ROOT into $INDEX_ALLOCATION
+        let entries = territory.read_directory_at_path("/many_subdirs").unwrap();
+        let mut names: Vec<u32> = entries.iter().map(|e| e.name.parse().unwrap()).collect();
+        assert!(entries.iter().all(|e| e.is_directory));
+        names.sort_unstable();
+        assert_eq!(names, (1..=512).collect::<Vec<_>>());
+
+        // A damaged index record fails the listing instead of truncating it
+        let mut image = testfs1().into_inner();
+        let record = image.windows(4).position(|w| w == b"INDX").unwrap();
+        image[record..record + 4].copy_from_slice(b"XXXX");
+        let mut territory = NtfsTerritory::parse(Cursor::new(image)).unwrap();
+        assert!(territory.read_directory_at_path("/many_subdirs").is_err());
+
+        // The damaged record is the root's; a best-effort listing returns the
+        // entries read before it
+        let full = NtfsTerritory::parse(testfs1()).unwrap().read_directory_at_path_partial("/", true).unwrap();
+        assert_eq!(full.errors, 0);
+        assert!(territory.read_directory_at_path("/").is_err());
+        let partial = territory.read_directory_at_path_partial("/", true).unwrap();
+        assert_eq!(partial.errors, 1);
+        assert_eq!(partial.skipped[0].0, "/");
+        assert!(partial.occupants.len() < full.occupants.len());
+    }
+
+    #[test]
+    fn test_directory_cells() {
+        let mut territory = NtfsTerritory::parse(testfs1()).unwrap();
+        let root = territory.read_directory_at_path("/").unwrap();
+        let subdirs = territory.read_directory_at_path("/many_subdirs").unwrap();
+        let mut stream = testfs1();
+
+        let names = |listing: &[OccupantInfo]| listing.iter().map(|o| o.name.clone()).collect::<Vec<_>>();
+        let listed = territory.list_directory(&mut stream, "/").unwrap();
+        assert_eq!(names(&listed), names(&root));
+        assert_eq!(listed[0].path.as_deref(), Some("/many_subdirs"));
+        assert_eq!(names(&territory.list_directory(&mut stream, "/MANY_SUBDIRS").unwrap()), names(&subdirs));
+        assert!(territory.list_directory(&mut stream, "/many_subdirs/7").unwrap().is_empty());
+
+        assert!(matches!(territory.navigate_to(&mut stream, "/1000-bytes-file"), Err(Error::NotFound(_))));
+        assert!(matches!(territory.navigate_to(&mut stream, "/missing"), Err(Error::NotFound(_))));
+
+        // Pages resume at index entries and together list every entry once,
+        // in index order
+        let mut paged = Vec::new();
+        let mut cursors = Vec::new();
+        let mut next = Some(0);
+        while let Some(position) = next {
+            let page = territory.list_directory_page(&mut stream, "/many_subdirs", position, 50).unwrap();
+            assert!(page.occupants.len() == 50 || page.next.is_none());
+            assert!(page.next != Some(position));
+            paged.extend(page.occupants);
+            cursors.push(position);
+            next = page.next;
+        }
+        assert_eq!(paged.len(), subdirs.len());
+        assert_eq!(paged[0].path.as_deref(), Some("/many_subdirs/1"));
+        assert_eq!(names(&paged), names(&subdirs));
+
+        // The last page seeks to its key rather than re-walking the index
+        // records before it
+        let mut counting = CountingReader { inner: testfs1(), read: 0 };
+        territory.list_directory(&mut counting, "/many_subdirs").unwrap();
+        let whole = std::mem::take(&mut counting.read);
+        let last = territory
+            .list_directory_page(&mut counting, "/many_subdirs", *cursors.last().unwrap(), 50)
+            .unwrap();
+        assert_eq!(names(&last.occupants), names(&subdirs[subdirs.len() - last.occupants.len()..]));
+        assert!(counting.read < whole / 2, "{} of {} bytes", counting.read, whole);
+        assert!(matches!(
+            territory.list_directory_page(&mut stream, "/many_subdirs", 0xFFFF_0000_0001, 50),
+            Err(Error::InvalidOperation(_))
+        ));
+
+        let zero = territory.list_directory_page(&mut stream, "/", 0, 0).unwrap();
+        assert!(zero.occupants.is_empty());
+        assert_eq!(zero.next, Some(0));
+        let first = territory.list_directory_page(&mut stream, "/", 0, 1).unwrap();
+        assert_eq!(first.occupants.len(), 1);
+        let rest = territory.list_directory_page(&mut stream, "/", first.next.unwrap(), usize::MAX).unwrap();
+        assert_eq!(rest.occupants.len() + 1, root.len());
+        assert_eq!(rest.next, None);
+
+        // Unicode-normalized lookups resolve through the same cells
+        assert_eq!(territory.resolve_path(&mut stream, "many_subdirs/7").unwrap(), "/many_subdirs/7");
+        let info = territory.file_metadata_with(&mut stream, "/many_subdirs/7", true).unwrap();
+        assert!(info.is_directory);
+
+        // The fixture's record for many_subdirs/187 fails its update
+        // sequence check, so only a best-effort walk gets past it
+        assert!(territory.walk(&mut stream, "/", None).is_err());
+        let walked = territory.walk_partial(&mut stream, "/", true, None).unwrap();
+        assert_eq!(walked.errors, 1);
+        assert_eq!(walked.skipped[0].0, "/many_subdirs/187");
+        assert_eq!(walked.occupants.len(), root.len() + subdirs.len());
+        let best_effort = ListOptions { best_effort: true, ..Default::default() };
+        let listing = territory.list_directory_partial(&mut stream, "/", &best_effort).unwrap();
+        assert_eq!((listing.occupants.len(), listing.errors), (root.len(), 0));
+    }
+
+    #[test]
+    fn test_list_orphans() {
+        let mut territory = NtfsTerritory::parse(testfs1()).unwrap();
+
+        // Every file of a cleanly unmounted volume is reachable
+        assert!(territory.list_orphans().unwrap().is_empty());
+        assert!(territory.read_directory_at_path("/$Orphans").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_link_count() {
+        assert_eq!(links_excluding_alias(1, false), 1);
+        assert_eq!(links_excluding_alias(3, false), 3);
+        // A long name and its DOS alias are a single link
+        assert_eq!(links_excluding_alias(2, true), 1);
+        assert_eq!(links_excluding_alias(3, true), 2);
+        assert_eq!(links_excluding_alias(0, true), 1);
+
+        let mut territory = NtfsTerritory::parse(testfs1()).unwrap();
+        let root = territory.read_directory_at_path("/").unwrap();
+        assert!(root.iter().all(|occupant| occupant.link_count == 1));
+
+        // Give the file a second link in its MFT record header
+        let position = territory.find_by_path("/file-with-12345").unwrap().position();
+        let record = position.value().unwrap().get() as usize;
+        let mut image = testfs1().into_inner();
+        image[record + 0x12..record + 0x14].copy_from_slice(&2u16.to_le_bytes());
+
+        let mut stream = Cursor::new(image.clone());
+        let mut territory = NtfsTerritory::parse(Cursor::new(image)).unwrap();
+        let root = territory.read_directory_at_path("/").unwrap();
+        let linked = root.iter().find(|occupant| occupant.name == "file-with-12345").unwrap();
+        assert_eq!(linked.link_count, 2);
+        assert!(root.iter().filter(|occupant| occupant.name != "file-with-12345").all(|o| o.link_count == 1));
+
+        let file = territory.file_metadata(&mut stream, "/file-with-12345").unwrap();
+        assert_eq!(file.link_count, 2);
+    }
+
+    #[test]
+    fn test_file_metadata() {
+        let mut stream = testfs1();
+        let territory = NtfsTerritory::parse(testfs1()).unwrap();
+
+        let file = territory.file_metadata(&mut stream, "/FILE-WITH-12345").unwrap();
+        assert_eq!(file.name, "file-with-12345");
+        assert!(!file.is_directory);
+        assert_eq!(file.size, 8);
+        assert!(file.modified.is_some());
+        assert!(!file.timestamp_is_local);
+
+        let nested = territory.file_metadata(&mut stream, "many_subdirs/100").unwrap();
+        assert_eq!(nested.name, "100");
+        assert!(nested.is_directory);
+
+        for missing in ["/many_subdirs/missing", "/empty-file/x", ""] {
+            let err = territory.file_metadata(&mut stream, missing).unwrap_err();
+            assert!(matches!(err, Error::NotFound(_)), "{}: {:?}", missing, err);
+        }
+    }
+
+    #[test]
+    fn test_fs_features() {
+        let territory = NtfsTerritory::parse(testfs1()).unwrap();
+        assert_eq!(
+            territory.fs_features(),
+            FsFeatures {
+                writable: false,
+                timestamps: true,
+                symlinks: true,
+                alternate_data_streams: true,
+                compression: true,
+                hard_links: true,
+                long_names: true,
+                short_names: true,
+                max_file_size: u64::MAX,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_attributes() {
+        let mut stream = testfs1();
+        let territory = NtfsTerritory::parse(testfs1()).unwrap();
+
+        let dir = territory.file_metadata(&mut stream, "many_subdirs").unwrap();
+        assert!(territory.decode_attributes(dir.attributes).contains(&AttributeFlag::Directory));
+
+        assert_eq!(
+            territory.decode_attributes(0x1000_0810),
+            vec![AttributeFlag::Directory, AttributeFlag::Compressed]
+        );
+        assert_eq!(
+            territory.decode_attributes(0x4623),
+            vec![
+                AttributeFlag::ReadOnly,
+                AttributeFlag::Hidden,
+                AttributeFlag::Archive,
+                AttributeFlag::Sparse,
+                AttributeFlag::ReparsePoint,
+                AttributeFlag::Encrypted,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_journal_files() {
+        let mut territory = NtfsTerritory::parse(testfs1()).unwrap();
+
+        let log_file_size = territory.log_file_size().unwrap();
+        assert!(log_file_size > 0);
+        assert!(log_file_size.is_multiple_of(4096));
+
+        // `$Extend` resolves, but mkntfs doesn't create a change journal
+        let extend = NtfsTerritory::<Cursor<Vec<u8>>>::index_entry_by_path(&territory.ntfs, &mut territory.reader, "$Extend");
+        assert!(extend.unwrap().0.is_directory());
+        assert!(!territory.has_usn_journal());
+        assert_eq!(territory.usn_journal_size(), None);
+    }
+
+    #[test]
+    fn test_extract_resident_data() {
+        let mut stream = testfs1();
+        let mut territory = NtfsTerritory::parse(testfs1()).unwrap();
+
+        // Small files are resident and have no data runs
+        let data = territory.extract_file_data("/file-with-12345").unwrap();
+        assert_eq!(data, b"12345");
+        assert!(territory.data_runs(&mut stream, "/file-with-12345").unwrap().is_empty());
+        let generic = territory.read_file_range(&mut stream, "/file-with-12345", 0, 5).unwrap();
+        assert_eq!(data, generic);
+        assert!(territory.extract_file_data("/empty-file").unwrap().is_empty());
+
+        // mkntfs stores a resident `$Info` stream beside `$UpCase`'s data
+        assert_eq!(territory.list_alternate_data_streams("/$UpCase").unwrap(), ["$Info"]);
+        let info = territory.extract_stream_data("/$UpCase", "$Info").unwrap();
+        assert_eq!(info.len(), 32);
+
+        let reader = &mut territory.reader;
+        let upcase = territory.ntfs.file(reader, 10).unwrap();
+        let attr_item = data_stream_item(&upcase, reader, "$info").unwrap().unwrap();
+        let attr = attr_item.to_attribute().unwrap();
+        assert!(attr.is_resident());
+        let mut generic = vec![0u8; attr.value_length() as usize];
+        attr.value(reader).unwrap().read_exact(reader, &mut generic).unwrap();
+        assert_eq!(info, generic);
+
+        // The main data of the same file is non-resident
+        let upcase_data = territory.extract_stream_data("/$UpCase", "").unwrap();
+        assert_eq!(upcase_data.len(), 131072);
+
+        let err = territory.extract_stream_data("/$UpCase", "missing").unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_read_file_range() {
+        let mut stream = testfs1();
+        let mut territory = NtfsTerritory::parse(testfs1()).unwrap();
+
+        for path in ["/1000-bytes-file", "/sparse-file", "/file-with-12345"] {
+            let full = territory.extract_file_data(path).unwrap();
+            let size = full.len() as u64;
+            for (offset, length) in [(0, size), (1, size - 2), (size / 2, size / 4)] {
+                let range = territory.read_file_range(&mut stream, path, offset, length).unwrap();
+                assert_eq!(range, &full[offset as usize..(offset + length) as usize], "{}", path);
+            }
+
+            let err = territory.read_file_range(&mut stream, path, size, 1).unwrap_err();
+            assert!(matches!(err, Error::InvalidOperation(_)), "{}: {:?}", path, err);
+        }
+
+        let err = territory.read_file_range(&mut stream, "/many_subdirs", 0, 0).unwrap_err();
+        assert!(matches!(err, Error::InvalidOperation(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_file_slack() {
+        let mut stream = testfs1();
+        let territory = NtfsTerritory::parse(testfs1()).unwrap();
+        let cluster_size = territory.block_size();
+
+        // Slack starts right after the data and runs to the end of the cluster
+        let runs = territory.data_runs(&mut stream, "/1000-bytes-file").unwrap();
+        let (last_offset, last_length) = *runs.last().unwrap();
+        let slack = territory.file_slack(&mut stream, "/1000-bytes-file").unwrap();
+        assert_eq!(slack.len() as u64, cluster_size - 1000 % cluster_size);
+
+        let mut on_disk = vec![0u8; slack.len()];
+        stream.seek(SeekFrom::Start(last_offset + last_length)).unwrap();
+        stream.read_exact(&mut on_disk).unwrap();
+        assert_eq!(slack, on_disk);
+
+        // Resident data has no clusters and so no slack
+        assert!(territory.file_slack(&mut stream, "/file-with-12345").unwrap().is_empty());
+
+        let err = territory.file_slack(&mut stream, "/many_subdirs").unwrap_err();
+        assert!(matches!(err, Error::InvalidOperation(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn test_resolves_to_root() {
+        let link = |sequence, is_directory, parent, parent_sequence| RecordLink {
+            sequence,
+            is_directory,
+            parent,
+            parent_sequence,
+        };
+        let links: HashMap<u64, RecordLink> = [
+            (ROOT_RECORD, link(5, true, ROOT_RECORD, 5)),
+            (30, link(1, true, ROOT_RECORD, 5)),
+            (31, link(1, false, 30, 1)),
+            // Parent record no longer in use
+            (32, link(1, false, 40, 1)),
+            // Parent record reused since the reference was made
+            (33, link(1, false, 30, 7)),
+            // Parent is a file
+            (34, link(1, false, 31, 1)),
+            // Directories that are each other's parent
+            (35, link(1, true, 36, 1)),
+            (36, link(1, true, 35, 1)),
+            (37, link(1, false, 35, 1)),
+        ]
+        .into_iter()
+        .collect();
+
+        assert!(resolves_to_root(&links, ROOT_RECORD));
+        assert!(resolves_to_root(&links, 30));
+        assert!(resolves_to_root(&links, 31));
+        for orphan in [32, 33, 34, 37, 99] {
+            assert!(!resolves_to_root(&links, orphan), "record {}", orphan);
+        }
+    }
 
     #[test]
     fn test_ntfs_attributes() {
@@ -75,6 +75,91 @@ impl NtfsFileAttribute {
     }
 }
 
+/// Parsed `$REPARSE_POINT` attribute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReparsePoint {
+    /// Reparse tag identifying the owner of the reparse data
+    pub tag: u32,
+    /// Substitute name (the NT path the link resolves to), for links
+    pub substitute_name: Option<String>,
+    /// Print name (the user-facing path), for links
+    pub print_name: Option<String>,
+}
+
+impl ReparsePoint {
+    /// Reparse tag for junctions and volume mount points
+    pub const TAG_MOUNT_POINT: u32 = 0xA0000003;
+    /// Reparse tag for symbolic links
+    pub const TAG_SYMLINK: u32 = 0xA000000C;
+
+    /// Size of the tag, data length and reserved fields preceding the data
+    const HEADER_SIZE: usize = 8;
+
+    /// Parse a reparse data buffer
+    ///
+    /// Names are decoded for symbolic link and mount point tags; other tags
+    /// are returned with no names. Returns None if the buffer is truncated.
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let read_u16 = |offset: usize| -> Option<u16> {
+            Some(u16::from_le_bytes(bytes.get(offset..offset + 2)?.try_into().ok()?))
+        };
+
+        let tag = u32::from_le_bytes(bytes.get(0..4)?.try_into().ok()?);
+        let data_length = read_u16(4)? as usize;
+        let data = bytes.get(Self::HEADER_SIZE..Self::HEADER_SIZE + data_length)?;
+
+        // Both layouts start with the name offsets/lengths; symbolic links
+        // add a flags field before the path buffer
+        let path_buffer_offset = match tag {
+            Self::TAG_MOUNT_POINT => 8,
+            Self::TAG_SYMLINK => 12,
+            _ => {
+                return Some(Self {
+                    tag,
+                    substitute_name: None,
+                    print_name: None,
+                })
+            }
+        };
+        let path_buffer = data.get(path_buffer_offset..)?;
+
+        let name = |field: usize| -> Option<String> {
+            let offset = read_u16(Self::HEADER_SIZE + field)? as usize;
+            let length = read_u16(Self::HEADER_SIZE + field + 2)? as usize;
+            let units: Vec<u16> = path_buffer
+                .get(offset..offset + length)?
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            Some(String::from_utf16_lossy(&units))
+        };
+
+        Some(Self {
+            tag,
+            substitute_name: Some(name(0)?),
+            print_name: Some(name(4)?),
+        })
+    }
+
+    /// Is this a symbolic link or junction/mount point?
+    pub fn is_link(&self) -> bool {
+        matches!(self.tag, Self::TAG_SYMLINK | Self::TAG_MOUNT_POINT)
+    }
+
+    /// Get the link target for display
+    ///
+    /// Prefers the print name, falling back to the substitute name with the
+    /// NT `\??\` prefix removed.
+    pub fn target(&self) -> Option<String> {
+        if let Some(print_name) = self.print_name.as_ref().filter(|n| !n.is_empty()) {
+            return Some(print_name.clone());
+        }
+
+        let substitute = self.substitute_name.as_ref().filter(|n| !n.is_empty())?;
+        Some(substitute.strip_prefix(r"\??\").unwrap_or(substitute).to_string())
+    }
+}
+
 /// NTFS volume information
 #[derive(Debug, Clone)]
 pub struct NtfsVolumeInfo {
@@ -96,6 +181,16 @@ pub struct NtfsVolumeInfo {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_ntfs_time_to_datetime() {
+        use chrono::TimeZone;
+
+        // FILETIME is UTC already: 2021-01-01 00:00:00
+        let time = NtfsTime::from(132_539_328_000_000_000u64);
+        assert_eq!(ntfs_time_to_datetime(time), Some(Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap()));
+        assert_eq!(ntfs_time_to_datetime(NtfsTime::from(0u64)), None);
+    }
+
     #[test]
     fn test_ntfs_file_attributes() {
         let attrs = NtfsFileAttribute::from_u32(0x0021); // ReadOnly | Archive
@@ -104,6 +199,64 @@ mod tests {
         assert!(!attrs.contains(&NtfsFileAttribute::Hidden));
     }
 
+    /// Build a reparse buffer with the given names
+    fn reparse_buffer(tag: u32, substitute: &str, print: &str) -> Vec<u8> {
+        let substitute: Vec<u8> = substitute.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let print: Vec<u8> = print.encode_utf16().flat_map(u16::to_le_bytes).collect();
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&(substitute.len() as u16).to_le_bytes());
+        data.extend_from_slice(&(substitute.len() as u16).to_le_bytes());
+        data.extend_from_slice(&(print.len() as u16).to_le_bytes());
+        if tag == ReparsePoint::TAG_SYMLINK {
+            data.extend_from_slice(&1u32.to_le_bytes()); // SYMLINK_FLAG_RELATIVE
+        }
+        data.extend_from_slice(&substitute);
+        data.extend_from_slice(&print);
+
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&tag.to_le_bytes());
+        buffer.extend_from_slice(&(data.len() as u16).to_le_bytes());
+        buffer.extend_from_slice(&0u16.to_le_bytes());
+        buffer.extend_from_slice(&data);
+        buffer
+    }
+
+    #[test]
+    fn test_parse_symlink_reparse_point() {
+        let buffer = reparse_buffer(ReparsePoint::TAG_SYMLINK, r"..\target\file.txt", r"..\target\file.txt");
+        let reparse = ReparsePoint::parse(&buffer).unwrap();
+
+        assert!(reparse.is_link());
+        assert_eq!(reparse.target().as_deref(), Some(r"..\target\file.txt"));
+    }
+
+    #[test]
+    fn test_parse_mount_point_reparse_point() {
+        // Junctions created without a print name fall back to the substitute name
+        let buffer = reparse_buffer(ReparsePoint::TAG_MOUNT_POINT, r"\??\C:\Users\Public", "");
+        let reparse = ReparsePoint::parse(&buffer).unwrap();
+
+        assert_eq!(reparse.substitute_name.as_deref(), Some(r"\??\C:\Users\Public"));
+        assert_eq!(reparse.target().as_deref(), Some(r"C:\Users\Public"));
+    }
+
+    #[test]
+    fn test_parse_other_reparse_points() {
+        // Deduplication tag: recognised but not a link
+        let mut buffer = vec![0u8; 8];
+        buffer[0..4].copy_from_slice(&0x80000013u32.to_le_bytes());
+        let reparse = ReparsePoint::parse(&buffer).unwrap();
+        assert!(!reparse.is_link());
+        assert_eq!(reparse.target(), None);
+
+        // Truncated buffers are rejected
+        let buffer = reparse_buffer(ReparsePoint::TAG_SYMLINK, "a", "b");
+        assert!(ReparsePoint::parse(&buffer[..buffer.len() - 1]).is_none());
+        assert!(ReparsePoint::parse(&buffer[..6]).is_none());
+    }
+
     #[test]
     fn test_directory_attribute() {
         let attrs = NtfsFileAttribute::from_u32(0x0010);
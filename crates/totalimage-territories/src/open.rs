@@ -0,0 +1,196 @@
+//! Opening the file system in one zone of a vault
+//!
+//! The CLI, web server and MCP tools all start from an image path and a
+//! zone index. [`open_territory_in_zone`] does the shared work: find the
+//! zone table, pick the zone, identify its file system and parse it.
+
+use std::io::{Seek, SeekFrom};
+
+use crate::probe::{self, identify_territory, is_encrypted};
+use crate::{ExfatTerritory, FatTerritory, IsoTerritory, NtfsTerritory};
+use totalimage_core::{Error, ReadSeek, Result, Territory, Vault, Zone};
+use totalimage_pipeline::{PartialPipeline, SharedPipeline, VaultReader};
+use totalimage_zones::detect_zone_table;
+
+/// Sector size zone tables are detected with
+const SECTOR_SIZE: u32 = 512;
+
+/// A file system opened in one zone of a vault
+///
+/// The territory owns a handle on the zone, so [`Territory::extract_file`]
+/// works on it directly. Its other methods take a stream, for which
+/// [`stream`](Self::stream) is a second handle on the same zone.
+pub struct OpenedTerritory {
+    /// The file system
+    pub territory: Box<dyn Territory>,
+    /// Stream over the zone, for the territory's methods that take one
+    pub stream: Box<dyn ReadSeek>,
+    /// The zone the file system was found in
+    pub zone: Zone,
+}
+
+/// Open the file system in zone `zone_index` of a vault
+///
+/// A vault without a zone table is a single unpartitioned zone 0. The
+/// vault is consumed: the returned territory and stream share it.
+///
+/// # Errors
+///
+/// Returns an error if the zone doesn't exist or is empty, if it holds an
+/// encrypted volume or an unsupported file system, or if no file system is
+/// found or it fails to parse
+pub fn open_territory_in_zone(vault: Box<dyn Vault>, zone_index: usize) -> Result<OpenedTerritory> {
+    let vault_length = vault.length();
+    let mut shared = SharedPipeline::new(VaultReader::new(vault));
+
+    // A volume boot sector can pass for an empty MBR, so only a table with
+    // zones partitions the vault
+    let zone = match detect_zone_table(&mut shared, SECTOR_SIZE) {
+        Some(table) if !table.as_zone_table().enumerate_zones().is_empty() => {
+            let table = table.as_zone_table();
+            table.get_zone(zone_index).cloned().ok_or_else(|| {
+                Error::not_found(format!(
+                    "Zone {} not found ({} zones in {})",
+                    zone_index,
+                    table.enumerate_zones().len(),
+                    table.identify()
+                ))
+            })?
+        }
+        _ if zone_index == 0 => Zone {
+            index: 0,
+            offset: 0,
+            length: vault_length,
+            zone_type: "Unpartitioned".to_string(),
+            territory_type: None,
+        },
+        _ => {
+            return Err(Error::not_found(format!(
+                "Zone {} not found (image is not partitioned)",
+                zone_index
+            )))
+        }
+    };
+
+    if zone.length == 0 {
+        return Err(Error::invalid_zone_table(format!("Zone {} has zero length", zone_index)));
+    }
+
+    let open_window = || PartialPipeline::new(shared.clone(), zone.offset, zone.length);
+    let mut stream = open_window()?;
+
+    let territory: Box<dyn Territory> = match identify_territory(&mut stream) {
+        Some(probe::FAT) => Box::new(FatTerritory::from_reader(open_window()?)?),
+        Some(probe::EXFAT) => Box::new(ExfatTerritory::from_reader(open_window()?)?),
+        Some(probe::ISO9660) => Box::new(IsoTerritory::from_reader(open_window()?)?),
+        Some(probe::NTFS) => Box::new(NtfsTerritory::parse(open_window()?)?),
+        Some(kind) if is_encrypted(kind) => {
+            return Err(Error::unsupported(format!(
+                "Zone {} is {} and can't be read without decrypting it",
+                zone_index, kind
+            )))
+        }
+        Some(other) => {
+            return Err(Error::unsupported(format!(
+                "{} file systems are not supported",
+                other
+            )))
+        }
+        None => return Err(Error::invalid_territory("No file system found in zone".to_string())),
+    };
+
+    stream.seek(SeekFrom::Start(0))?;
+    Ok(OpenedTerritory {
+        territory,
+        stream: Box::new(stream),
+        zone,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fat::tests::blank_fat32_volume;
+    use crate::fat::FatWriter;
+    use flate2::read::GzDecoder;
+    use std::io::{Cursor, Read};
+
+    struct MemoryVault(Cursor<Vec<u8>>);
+
+    impl Vault for MemoryVault {
+        fn identify(&self) -> &str {
+            "Memory"
+        }
+
+        fn length(&self) -> u64 {
+            self.0.get_ref().len() as u64
+        }
+
+        fn content(&mut self) -> &mut dyn ReadSeek {
+            &mut self.0
+        }
+    }
+
+    /// Build an MBR disk holding a FAT32 partition and then an NTFS one
+    fn fat_and_ntfs_disk() -> Vec<u8> {
+        let mut fat = FatWriter::open(Cursor::new(blank_fat32_volume())).unwrap();
+        fat.create_file("HELLO.TXT", b"hello from fat").unwrap();
+        let fat = fat.into_inner().into_inner();
+
+        let compressed: &[u8] = include_bytes!("../testdata/ntfs-testfs1.img.gz");
+        let mut ntfs = Vec::new();
+        GzDecoder::new(compressed).read_to_end(&mut ntfs).unwrap();
+
+        let mut disk = vec![0u8; 2048 * 512];
+        let partitions = [(0x0Cu8, &fat), (0x07, &ntfs)];
+        for (i, (kind, volume)) in partitions.into_iter().enumerate() {
+            let entry = 446 + i * 16;
+            let start = (disk.len() / 512) as u32;
+            disk[entry + 4] = kind;
+            disk[entry + 8..entry + 12].copy_from_slice(&start.to_le_bytes());
+            disk[entry + 12..entry + 16].copy_from_slice(&((volume.len() / 512) as u32).to_le_bytes());
+            disk.extend_from_slice(volume);
+        }
+        disk[510..512].copy_from_slice(&[0x55, 0xAA]);
+        disk
+    }
+
+    #[test]
+    fn test_open_fat_and_ntfs_zones() {
+        let disk = fat_and_ntfs_disk();
+        let open = |index| open_territory_in_zone(Box::new(MemoryVault(Cursor::new(disk.clone()))), index);
+
+        let mut fat = open(0).unwrap();
+        assert_eq!(fat.zone.offset, 2048 * 512);
+        assert!(fat.territory.identify().contains("FAT32"));
+        let names: Vec<String> = fat
+            .territory
+            .list_directory(&mut fat.stream, "/")
+            .unwrap()
+            .into_iter()
+            .map(|occupant| occupant.name)
+            .collect();
+        assert_eq!(names, ["HELLO.TXT"]);
+        assert_eq!(fat.territory.extract_file("/HELLO.TXT").unwrap(), b"hello from fat");
+
+        let mut ntfs = open(1).unwrap();
+        assert!(ntfs.territory.identify().starts_with("NTFS"));
+        let names: Vec<String> = ntfs
+            .territory
+            .list_directory(&mut ntfs.stream, "/")
+            .unwrap()
+            .into_iter()
+            .map(|occupant| occupant.name)
+            .collect();
+        assert_eq!(names[0], "many_subdirs");
+        assert!(names.iter().any(|name| name == "1000-bytes-file"));
+        assert_eq!(ntfs.territory.list_directory(&mut ntfs.stream, "/many_subdirs").unwrap().len(), 512);
+        let data = ntfs.territory.extract_file("/1000-bytes-file").unwrap();
+        assert_eq!(data.len(), 1000);
+        let range = ntfs.territory.read_file_range(&mut ntfs.stream, "/1000-bytes-file", 0, 1000).unwrap();
+        assert_eq!(range, data);
+
+        let err = open(2).err().unwrap();
+        assert!(matches!(err, Error::NotFound(_)), "{}", err);
+    }
+}
@@ -0,0 +1,344 @@
+//! Cheap file system detection by signature
+//!
+//! Reads at most a boot sector and one ISO 9660 descriptor byte range, so
+//! callers can dispatch straight to the right territory parser instead of
+//! trying each one in turn.
+
+use std::io::SeekFrom;
+use totalimage_core::ReadSeek;
+
+/// Name returned for FAT12/16/32 volumes
+pub const FAT: &str = "FAT";
+/// Name returned for exFAT volumes
+pub const EXFAT: &str = "exFAT";
+/// Name returned for NTFS volumes
+pub const NTFS: &str = "NTFS";
+/// Name returned for ISO 9660 volumes
+pub const ISO9660: &str = "ISO 9660";
+/// Name returned for BitLocker-encrypted volumes
+pub const BITLOCKER: &str = "BitLocker (encrypted)";
+/// Name returned for LUKS-encrypted volumes
+pub const LUKS: &str = "LUKS (encrypted)";
+
+/// File systems [`open_territory_in_zone`](crate::open_territory_in_zone) can parse
+pub const SUPPORTED: [&str; 4] = [FAT, EXFAT, NTFS, ISO9660];
+
+/// BitLocker OEM name at offset 3, in place of the file system's
+const BITLOCKER_SIGNATURE: &[u8; 8] = b"-FVE-FS-";
+
+/// LUKS header magic at offset 0
+const LUKS_MAGIC: &[u8; 6] = b"LUKS\xba\xbe";
+
+/// Offset of the ISO 9660 primary volume descriptor (sector 16 of 2048 bytes)
+const ISO_DESCRIPTOR_OFFSET: u64 = 16 * 2048;
+
+/// Identify the file system in `stream` from its on-disk signatures
+///
+/// Checks, in order:
+/// - LUKS: `"LUKS\xba\xbe"` magic at offset 0
+/// - BitLocker: `"-FVE-FS-"` OEM name at offset 3
+/// - exFAT: `"EXFAT   "` OEM name at offset 3
+/// - NTFS: `"NTFS    "` OEM name at offset 3
+/// - FAT: a plausible BIOS parameter block and the 0xAA55 boot signature
+/// - ISO 9660: `"CD001"` in the descriptor at sector 16
+///
+/// Returns one of [`FAT`], [`EXFAT`], [`NTFS`] or [`ISO9660`], one of
+/// [`BITLOCKER`] or [`LUKS`] for an encrypted volume whose file system
+/// can't be read, or `None` if nothing matched or the stream could not be
+/// read. The stream position is left unspecified.
+pub fn identify_territory(stream: &mut dyn ReadSeek) -> Option<&'static str> {
+    let mut boot = [0u8; 512];
+    if read_at(stream, 0, &mut boot) {
+        if &boot[..6] == LUKS_MAGIC {
+            return Some(LUKS);
+        }
+        if &boot[3..11] == BITLOCKER_SIGNATURE {
+            return Some(BITLOCKER);
+        }
+        if &boot[3..11] == b"EXFAT   " {
+            return Some(EXFAT);
+        }
+        if &boot[3..11] == b"NTFS    " {
+            return Some(NTFS);
+        }
+        if is_plausible_fat_boot_sector(&boot) {
+            return Some(FAT);
+        }
+    }
+
+    let mut descriptor = [0u8; 6];
+    if read_at(stream, ISO_DESCRIPTOR_OFFSET, &mut descriptor) && &descriptor[1..6] == b"CD001" {
+        return Some(ISO9660);
+    }
+
+    None
+}
+
+/// Is `kind`, as returned by [`identify_territory`], an encrypted volume?
+pub fn is_encrypted(kind: &str) -> bool {
+    kind == BITLOCKER || kind == LUKS
+}
+
+/// Allowed gap between the end of an isohybrid partition and the end of the
+/// image; isohybrid pads images to whole 255-head, 63-sector cylinders
+const HYBRID_END_SLACK: u64 = 255 * 63 * 512;
+
+/// Check whether `stream` is a hybrid ISO (as made by isohybrid)
+///
+/// Hybrid ISOs carry an MBR so they boot from USB media, but their single
+/// partition simply spans the whole image, ISO 9660 volume included. Such
+/// images should be read as the ISO rather than through the partition view.
+///
+/// Returns true when the MBR has exactly one partition, that partition
+/// starts before the ISO descriptor at sector 16 and reaches the end of the
+/// stream (within one cylinder), and the ISO descriptor is present. The
+/// stream position is left unspecified.
+pub fn is_hybrid_iso(stream: &mut dyn ReadSeek) -> bool {
+    let mut mbr = [0u8; 512];
+    if !read_at(stream, 0, &mut mbr) || mbr[510] != 0x55 || mbr[511] != 0xAA {
+        return false;
+    }
+
+    let mut descriptor = [0u8; 6];
+    if !read_at(stream, ISO_DESCRIPTOR_OFFSET, &mut descriptor) || &descriptor[1..6] != b"CD001" {
+        return false;
+    }
+
+    let Ok(length) = stream.seek(SeekFrom::End(0)) else {
+        return false;
+    };
+
+    let mut partitions = mbr[446..510]
+        .chunks_exact(16)
+        .filter(|entry| entry[4] != 0)
+        .map(|entry| {
+            let start = u32::from_le_bytes([entry[8], entry[9], entry[10], entry[11]]) as u64;
+            let sectors = u32::from_le_bytes([entry[12], entry[13], entry[14], entry[15]]) as u64;
+            (start * 512, (start + sectors) * 512)
+        });
+
+    match (partitions.next(), partitions.next()) {
+        (Some((start, end)), None) => {
+            start < ISO_DESCRIPTOR_OFFSET && end + HYBRID_END_SLACK > length
+        }
+        _ => false,
+    }
+}
+
+/// Check the BPB fields a FAT volume must have sane values for
+fn is_plausible_fat_boot_sector(boot: &[u8; 512]) -> bool {
+    let bytes_per_sector = u16::from_le_bytes([boot[11], boot[12]]);
+    let sectors_per_cluster = boot[13];
+    let reserved_sectors = u16::from_le_bytes([boot[14], boot[15]]);
+    let num_fats = boot[16];
+    let total_sectors_16 = u16::from_le_bytes([boot[19], boot[20]]);
+    let media = boot[21];
+    let total_sectors_32 = u32::from_le_bytes([boot[32], boot[33], boot[34], boot[35]]);
+
+    boot[510] == 0x55
+        && boot[511] == 0xAA
+        && matches!(bytes_per_sector, 512 | 1024 | 2048 | 4096)
+        && sectors_per_cluster.is_power_of_two()
+        && reserved_sectors != 0
+        && matches!(num_fats, 1 | 2)
+        && (media == 0xF0 || media >= 0xF8)
+        && (total_sectors_16 != 0 || total_sectors_32 != 0)
+}
+
+/// Fill `buf` from `offset`, returning false if the stream is too short
+fn read_at(stream: &mut dyn ReadSeek, offset: u64, buf: &mut [u8]) -> bool {
+    stream.seek(SeekFrom::Start(offset)).is_ok() && stream.read_exact(buf).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn fat12_boot_sector() -> Vec<u8> {
+        let mut disk = vec![0u8; 4096];
+        disk[0..3].copy_from_slice(&[0xEB, 0x3C, 0x90]);
+        disk[3..11].copy_from_slice(b"MSDOS5.0");
+        disk[11..13].copy_from_slice(&512u16.to_le_bytes());
+        disk[13] = 1; // Sectors per cluster
+        disk[14..16].copy_from_slice(&1u16.to_le_bytes());
+        disk[16] = 2; // FAT copies
+        disk[17..19].copy_from_slice(&224u16.to_le_bytes());
+        disk[19..21].copy_from_slice(&2880u16.to_le_bytes());
+        disk[21] = 0xF0;
+        disk[510] = 0x55;
+        disk[511] = 0xAA;
+        disk
+    }
+
+    fn identify(data: Vec<u8>) -> Option<&'static str> {
+        identify_territory(&mut Cursor::new(data))
+    }
+
+    #[test]
+    fn test_identify_fat() {
+        assert_eq!(identify(fat12_boot_sector()), Some(FAT));
+
+        // Same sector with an implausible BPB is rejected
+        let mut disk = fat12_boot_sector();
+        disk[13] = 3;
+        assert_eq!(identify(disk), None);
+
+        let mut disk = fat12_boot_sector();
+        disk[511] = 0;
+        assert_eq!(identify(disk), None);
+    }
+
+    #[test]
+    fn test_identify_exfat_and_ntfs() {
+        let mut disk = vec![0u8; 512];
+        disk[3..11].copy_from_slice(b"EXFAT   ");
+        disk[510] = 0x55;
+        disk[511] = 0xAA;
+        assert_eq!(identify(disk.clone()), Some(EXFAT));
+
+        // The exFAT name wins even over a plausible FAT BPB
+        let mut fat = fat12_boot_sector();
+        fat[3..11].copy_from_slice(b"EXFAT   ");
+        assert_eq!(identify(fat), Some(EXFAT));
+
+        // NTFS boot sectors carry a BPB that is not a valid FAT one
+        disk[3..11].copy_from_slice(b"NTFS    ");
+        disk[11..13].copy_from_slice(&512u16.to_le_bytes());
+        disk[13] = 8;
+        disk[21] = 0xF8;
+        assert_eq!(identify(disk), Some(NTFS));
+    }
+
+    #[test]
+    fn test_identify_encrypted() {
+        // BitLocker keeps an NTFS-like BPB behind its own OEM name
+        let mut disk = vec![0u8; 512];
+        disk[0..3].copy_from_slice(&[0xEB, 0x58, 0x90]);
+        disk[3..11].copy_from_slice(b"-FVE-FS-");
+        disk[11..13].copy_from_slice(&512u16.to_le_bytes());
+        disk[13] = 8;
+        disk[21] = 0xF8;
+        disk[510] = 0x55;
+        disk[511] = 0xAA;
+        assert_eq!(identify(disk), Some(BITLOCKER));
+
+        let mut disk = vec![0u8; 4096];
+        disk[0..6].copy_from_slice(b"LUKS\xba\xbe");
+        disk[6..8].copy_from_slice(&2u16.to_be_bytes());
+        assert_eq!(identify(disk), Some(LUKS));
+
+        assert!(is_encrypted(BITLOCKER));
+        assert!(is_encrypted(LUKS));
+        assert!(!is_encrypted(NTFS));
+    }
+
+    #[test]
+    fn test_identify_iso9660() {
+        let mut disk = vec![0u8; 17 * 2048];
+        disk[32768] = 1; // Primary volume descriptor
+        disk[32769..32774].copy_from_slice(b"CD001");
+        assert_eq!(identify(disk), Some(ISO9660));
+    }
+
+    /// Build an isohybrid-style image: an ISO 9660 volume with README.TXT in
+    /// its root directory, and an MBR whose only partition covers the image
+    fn hybrid_iso(partition_start: u32) -> Vec<u8> {
+        let mut disk = vec![0u8; 20 * 2048];
+
+        let record = |extent: u32, size: u32, flags: u8, name: &[u8]| -> Vec<u8> {
+            let mut record = vec![0u8; (33 + name.len() + 1) & !1];
+            record[0] = record.len() as u8;
+            record[2..6].copy_from_slice(&extent.to_le_bytes());
+            record[6..10].copy_from_slice(&extent.to_be_bytes());
+            record[10..14].copy_from_slice(&size.to_le_bytes());
+            record[14..18].copy_from_slice(&size.to_be_bytes());
+            record[25] = flags;
+            record[32] = name.len() as u8;
+            record[33..33 + name.len()].copy_from_slice(name);
+            record
+        };
+
+        // Primary volume descriptor, then the set terminator
+        let pvd = 16 * 2048;
+        disk[pvd] = 1;
+        disk[pvd + 1..pvd + 6].copy_from_slice(b"CD001");
+        disk[pvd + 6] = 1;
+        disk[pvd + 80..pvd + 84].copy_from_slice(&20u32.to_le_bytes());
+        disk[pvd + 84..pvd + 88].copy_from_slice(&20u32.to_be_bytes());
+        disk[pvd + 128..pvd + 130].copy_from_slice(&2048u16.to_le_bytes());
+        disk[pvd + 130..pvd + 132].copy_from_slice(&2048u16.to_be_bytes());
+        let root = record(18, 2048, 0x02, &[0]);
+        disk[pvd + 156..pvd + 156 + root.len()].copy_from_slice(&root);
+        disk[17 * 2048] = 255;
+        disk[17 * 2048 + 1..17 * 2048 + 6].copy_from_slice(b"CD001");
+
+        // Root directory: ".", ".." and one file in sector 19
+        let mut offset = 18 * 2048;
+        for entry in [
+            record(18, 2048, 0x02, &[0]),
+            record(18, 2048, 0x02, &[1]),
+            record(19, 11, 0, b"README.TXT;1"),
+        ] {
+            disk[offset..offset + entry.len()].copy_from_slice(&entry);
+            offset += entry.len();
+        }
+        disk[19 * 2048..19 * 2048 + 11].copy_from_slice(b"hello world");
+
+        // MBR with a single bootable partition over the whole image
+        let sectors = disk.len() as u32 / 512 - partition_start;
+        disk[446] = 0x80;
+        disk[450] = 0x17;
+        disk[454..458].copy_from_slice(&partition_start.to_le_bytes());
+        disk[458..462].copy_from_slice(&sectors.to_le_bytes());
+        disk[510] = 0x55;
+        disk[511] = 0xAA;
+        disk
+    }
+
+    #[test]
+    fn test_hybrid_iso_detected() {
+        let disk = hybrid_iso(0);
+        assert!(is_hybrid_iso(&mut Cursor::new(disk.clone())));
+        assert_eq!(identify(disk.clone()), Some(ISO9660));
+
+        // The ISO file system is readable from the start of the image
+        let mut stream = Cursor::new(disk);
+        let iso = crate::IsoTerritory::parse(&mut stream).unwrap();
+        let root = iso.primary_descriptor().root_directory_record.clone();
+        let entries = iso.read_directory(&mut stream, &root).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].file_name(), "README.TXT");
+        assert_eq!(iso.read_file(&mut stream, &entries[0]).unwrap(), b"hello world");
+    }
+
+    #[test]
+    fn test_hybrid_iso_rejected() {
+        // Plain ISO without an MBR
+        let mut disk = hybrid_iso(0);
+        disk[510] = 0;
+        assert!(!is_hybrid_iso(&mut Cursor::new(disk)));
+
+        // Partition starts after the ISO descriptor
+        assert!(!is_hybrid_iso(&mut Cursor::new(hybrid_iso(64))));
+
+        // A second partition means a real partitioned disk
+        let mut disk = hybrid_iso(0);
+        disk[466] = 0x0C;
+        disk[470..474].copy_from_slice(&8u32.to_le_bytes());
+        disk[474..478].copy_from_slice(&8u32.to_le_bytes());
+        assert!(!is_hybrid_iso(&mut Cursor::new(disk)));
+
+        // No ISO descriptor behind the MBR
+        let mut disk = hybrid_iso(0);
+        disk[16 * 2048 + 1] = 0;
+        assert!(!is_hybrid_iso(&mut Cursor::new(disk)));
+    }
+
+    #[test]
+    fn test_identify_unknown() {
+        assert_eq!(identify(vec![0u8; 64 * 1024]), None);
+        assert_eq!(identify(vec![0u8; 100]), None);
+        assert_eq!(identify(Vec::new()), None);
+    }
+}
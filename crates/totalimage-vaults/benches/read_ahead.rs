@@ -0,0 +1,116 @@
+//! Sequential read throughput of a compressed E01 with and without read-ahead
+//!
+//! Run with `cargo bench -p totalimage-vaults --bench read_ahead`.
+
+use std::io::{Cursor, Read, Write};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use flate2::{write::ZlibEncoder, Compression};
+use totalimage_vaults::e01::types::{adler32, COMPRESSED_CHUNK};
+use totalimage_vaults::{E01Vault, ReadAheadConfig};
+
+const SECTORS_PER_CHUNK: usize = 64;
+const CHUNK_SIZE: usize = SECTORS_PER_CHUNK * 512;
+const CHUNK_COUNT: usize = 256;
+
+/// Append a section descriptor whose data follows it directly
+fn push_section(data: &mut Vec<u8>, name: &[u8], body: &[u8], last: bool) {
+    let start = data.len() as u64;
+    let size = 76 + body.len() as u64;
+    let mut section_type = [0u8; 16];
+    section_type[..name.len()].copy_from_slice(name);
+    data.extend_from_slice(&section_type);
+    data.extend_from_slice(&(if last { 0 } else { start + size }).to_le_bytes());
+    data.extend_from_slice(&size.to_le_bytes());
+    data.extend_from_slice(&[0u8; 44]);
+    data.extend_from_slice(body);
+}
+
+/// Build an 8 MiB E01 of zlib-compressed chunks
+fn build_e01() -> Vec<u8> {
+    let mut sectors = Vec::new();
+    let mut entries = Vec::new();
+    let mut state = 0x2545_F491u32;
+
+    for _ in 0..CHUNK_COUNT {
+        // Mildly compressible noise keeps zlib busy
+        let chunk: Vec<u8> = (0..CHUNK_SIZE)
+            .map(|i| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                if i % 4 == 0 { (state & 0x3F) as u8 } else { 0 }
+            })
+            .collect();
+
+        entries.extend_from_slice(&(COMPRESSED_CHUNK | sectors.len() as u32).to_le_bytes());
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&chunk).unwrap();
+        sectors.extend_from_slice(&encoder.finish().unwrap());
+    }
+
+    // Entries are relative to the chunk data after the volume and sectors descriptors
+    let mut table = vec![0u8; 24];
+    table[0..4].copy_from_slice(&(CHUNK_COUNT as u32).to_le_bytes());
+    table[8..16].copy_from_slice(&(13u64 + 76 + 94 + 76).to_le_bytes());
+    let checksum = adler32(&table[..20]);
+    table[20..24].copy_from_slice(&checksum.to_le_bytes());
+    table.extend_from_slice(&entries);
+    table.extend_from_slice(&adler32(&entries).to_le_bytes());
+
+    let mut volume = vec![0u8; 94];
+    volume[0] = 0x01;
+    volume[4..8].copy_from_slice(&(CHUNK_COUNT as u32).to_le_bytes());
+    volume[8..12].copy_from_slice(&(SECTORS_PER_CHUNK as u32).to_le_bytes());
+    volume[12..16].copy_from_slice(&512u32.to_le_bytes());
+    volume[16..24].copy_from_slice(&((CHUNK_COUNT * SECTORS_PER_CHUNK) as u64).to_le_bytes());
+
+    let mut data = b"EVF\x09\x0d\x0a\xff\x00".to_vec();
+    data.push(0x01);
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data.extend_from_slice(&0u16.to_le_bytes());
+    push_section(&mut data, b"volume", &volume, false);
+    push_section(&mut data, b"sectors", &sectors, false);
+    push_section(&mut data, b"table", &table, false);
+    push_section(&mut data, b"done", &[], true);
+    data
+}
+
+fn read_all(image: &[u8], read_ahead: Option<ReadAheadConfig>) -> usize {
+    let mut vault = E01Vault::from_reader(Box::new(Cursor::new(image.to_vec()))).unwrap();
+    vault.set_read_ahead(read_ahead);
+
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut total = 0;
+    loop {
+        let n = vault.read(&mut buf).unwrap();
+        if n == 0 {
+            return total;
+        }
+        total += n;
+    }
+}
+
+fn sequential_read(c: &mut Criterion) {
+    let image = build_e01();
+    let mut group = c.benchmark_group("e01_sequential_read");
+    group.throughput(Throughput::Bytes((CHUNK_COUNT * CHUNK_SIZE) as u64));
+    group.sample_size(20);
+
+    group.bench_function("single_threaded", |b| b.iter(|| read_all(&image, None)));
+
+    for workers in [2, 4] {
+        let config = ReadAheadConfig {
+            workers,
+            ..ReadAheadConfig::default()
+        };
+        group.bench_with_input(BenchmarkId::new("read_ahead", workers), &config, |b, config| {
+            b.iter(|| read_all(&image, Some(*config)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, sequential_read);
+criterion_main!(benches);
@@ -29,7 +29,11 @@ use std::io::{Cursor, Read, Seek, SeekFrom};
 use std::path::Path;
 
 use flate2::read::ZlibDecoder;
-use totalimage_core::{Error, ReadSeek, Result, Vault};
+use sha2::{Digest, Sha512};
+use totalimage_core::{max_allocation_size, validate_allocation_size, Error, Progress, ReadSeek, Result, Vault};
+
+use crate::read_ahead::{ChunkDecoder, ChunkSource, ReadAhead, ReadAheadConfig};
+use crate::verify::{hash_media, to_hex, HashVerification};
 
 pub use types::*;
 
@@ -45,8 +49,12 @@ pub struct Aff4Vault {
     stream: Aff4ImageStream,
     /// Bevy index (chunk offsets)
     bevy_index: Vec<Aff4BevyIndexEntry>,
+    /// Archive index of each bevy segment of the primary stream
+    segments: HashMap<usize, usize>,
     /// Cached decompressed chunks
     chunk_cache: HashMap<usize, Vec<u8>>,
+    /// Worker pool decompressing upcoming chunks, if enabled
+    read_ahead: Option<ReadAhead>,
     /// Current read position
     position: u64,
     /// Identification string
@@ -71,28 +79,38 @@ impl Aff4Vault {
         // Find and parse metadata
         let volume = Self::parse_metadata(&mut archive)?;
 
-        // Get primary image stream
-        let stream = volume
-            .streams
-            .first()
-            .cloned()
-            .ok_or_else(|| Error::invalid_vault("AFF4 container has no image streams"))?;
-
-        // Load bevy index
-        let bevy_index = Self::load_bevy_index(&mut archive, &stream)?;
-
-        let identifier = format!(
-            "AFF4 Image ({} bytes, {} chunks)",
-            stream.size,
-            bevy_index.len()
-        );
+        // Get primary image stream, skipping streams that back logical files.
+        // A logical (AFF4-L) container may have no disk image at all.
+        let primary = volume.streams.iter().find(|stream| {
+            !volume.logical_files.iter().any(|file| file.data_stream.as_ref() == Some(&stream.urn))
+        });
+        let (stream, bevy_index, segments, identifier) = match primary {
+            Some(stream) => {
+                let stream = stream.clone();
+                let bevy_index = Self::load_bevy_index(&mut archive, &stream)?;
+                let segments = Self::index_segments(&archive, &stream);
+                let identifier = format!(
+                    "AFF4 Image ({} bytes, {} chunks)",
+                    stream.size,
+                    bevy_index.len()
+                );
+                (stream, bevy_index, segments, identifier)
+            }
+            None if !volume.logical_files.is_empty() => {
+                let identifier = format!("AFF4 Logical Image ({} files)", volume.logical_files.len());
+                (Aff4ImageStream::default(), Vec::new(), HashMap::new(), identifier)
+            }
+            None => return Err(Error::invalid_vault("AFF4 container has no image streams")),
+        };
 
         Ok(Self {
             archive,
             volume,
             stream,
             bevy_index,
+            segments,
             chunk_cache: HashMap::new(),
+            read_ahead: None,
             position: 0,
             identifier,
         })
@@ -118,17 +136,10 @@ impl Aff4Vault {
         }
 
         // Collect turtle file names first to avoid borrow issues
-        let turtle_files: Vec<String> = (0..archive.len())
-            .filter_map(|i| {
-                archive.by_index(i).ok().and_then(|file| {
-                    let name = file.name().to_string();
-                    if name.ends_with(".turtle") || name.ends_with(".description") {
-                        Some(name)
-                    } else {
-                        None
-                    }
-                })
-            })
+        let turtle_files: Vec<String> = archive
+            .file_names()
+            .filter(|name| name.ends_with(".turtle") || name.ends_with(".description"))
+            .map(str::to_string)
             .collect();
 
         // Now read the turtle files
@@ -182,6 +193,13 @@ impl Aff4Vault {
                 }
             }
 
+            // Stream hashes are typed literals; only SHA-512 is recognized
+            if stmt.predicate.ends_with("#hash") && is_sha512_hex(&stmt.object) {
+                if let Some(stream) = streams.get_mut(&stmt.subject) {
+                    stream.sha512 = Some(stmt.object.to_ascii_lowercase());
+                }
+            }
+
             // Volume properties
             if stmt.predicate.contains("creationTime") {
                 volume.creation_time = Some(stmt.object.clone());
@@ -197,18 +215,15 @@ impl Aff4Vault {
         }
 
         volume.streams = streams.into_values().collect();
+        volume.logical_files = Self::parse_logical_files(&statements);
 
         // Collect all file names first
-        let all_files: Vec<String> = (0..archive.len())
-            .filter_map(|i| archive.by_index(i).ok().map(|f| f.name().to_string()))
-            .collect();
+        let all_files: Vec<String> = archive.file_names().map(str::to_string).collect();
 
         // Try to find data paths for streams
         for stream in &mut volume.streams {
             // Convert URN to file path in ZIP
-            let urn_path = stream.urn
-                .replace("aff4://", "aff4%3A//")
-                .replace(':', "%3A");
+            let urn_path = urn_to_path(&stream.urn);
 
             // Look for bevy files
             for name in &all_files {
@@ -229,6 +244,40 @@ impl Aff4Vault {
         Ok(volume)
     }
 
+    /// Collect the `aff4:FileImage` objects described by the metadata
+    fn parse_logical_files(statements: &[Aff4Statement]) -> Vec<Aff4LogicalFile> {
+        let mut files: HashMap<&str, Aff4LogicalFile> = HashMap::new();
+        for stmt in statements {
+            if stmt.predicate.contains("type") && Aff4ObjectType::from_uri(&stmt.object) == Aff4ObjectType::FileImage {
+                files.entry(&stmt.subject).or_insert_with(|| Aff4LogicalFile {
+                    urn: stmt.subject.clone(),
+                    ..Default::default()
+                });
+            }
+        }
+
+        for stmt in statements {
+            let Some(file) = files.get_mut(stmt.subject.as_str()) else {
+                continue;
+            };
+            if stmt.predicate.ends_with("#size") {
+                file.size = stmt.object.parse().unwrap_or(0);
+            } else if stmt.predicate.ends_with("#originalFileName") {
+                file.original_path = Some(stmt.object.clone());
+            } else if stmt.predicate.ends_with("#dataStream") {
+                file.data_stream = Some(stmt.object.clone());
+            }
+        }
+
+        let mut files: Vec<Aff4LogicalFile> = files.into_values().collect();
+        for file in &mut files {
+            let path = file.original_path.as_deref().unwrap_or(&file.urn);
+            file.name = path.rsplit(['/', '\\']).next().unwrap_or(path).to_string();
+        }
+        files.sort_by(|a, b| a.original_path.cmp(&b.original_path).then_with(|| a.urn.cmp(&b.urn)));
+        files
+    }
+
     /// Load the bevy index for a stream
     fn load_bevy_index(
         archive: &mut zip::ZipArchive<File>,
@@ -237,22 +286,13 @@ impl Aff4Vault {
         let mut index_entries = Vec::new();
 
         // Find all index files for this stream
-        let urn_path = stream.urn
-            .replace("aff4://", "aff4%3A//")
-            .replace(':', "%3A");
+        let urn_path = urn_to_path(&stream.urn);
 
         // Collect index file names first to avoid borrow issues
         let index_files: Vec<String> = (0..archive.len())
-            .filter_map(|i| {
-                archive.by_index(i).ok().and_then(|file| {
-                    let name = file.name().to_string();
-                    if name.ends_with(".index") && (name.contains(&urn_path) || name.contains(&stream.urn)) {
-                        Some(name)
-                    } else {
-                        None
-                    }
-                })
-            })
+            .filter_map(|i| archive.name_for_index(i))
+            .filter(|name| name.ends_with(".index") && (name.contains(&urn_path) || name.contains(&stream.urn)))
+            .map(str::to_string)
             .collect();
 
         // Now read the index files
@@ -285,64 +325,64 @@ impl Aff4Vault {
         Ok(index_entries)
     }
 
-    /// Read and decompress a chunk
-    fn read_chunk(&mut self, chunk_index: usize) -> Result<Vec<u8>> {
-        // Check cache
-        if let Some(cached) = self.chunk_cache.get(&chunk_index) {
-            return Ok(cached.clone());
+    /// Map each bevy segment of a stream to its archive index
+    ///
+    /// Segments are members named by their number as eight hex digits.
+    /// Built once so a chunk read doesn't scan every member of a large
+    /// container.
+    fn index_segments(archive: &zip::ZipArchive<File>, stream: &Aff4ImageStream) -> HashMap<usize, usize> {
+        let urn_path = urn_to_path(&stream.urn);
+        let mut segments = HashMap::new();
+
+        for i in 0..archive.len() {
+            let Some(name) = archive.name_for_index(i) else {
+                continue;
+            };
+            if !(name.contains(&urn_path) || name.contains(&stream.urn)) || name.ends_with(".index") {
+                continue;
+            }
+            let number = name
+                .len()
+                .checked_sub(8)
+                .and_then(|start| name.get(start..))
+                .and_then(|digits| usize::from_str_radix(digits, 16).ok());
+            if let Some(number) = number {
+                segments.entry(number).or_insert(i);
+            }
         }
 
-        if chunk_index >= self.bevy_index.len() {
-            return Err(Error::invalid_vault("Chunk index out of range"));
-        }
+        segments
+    }
 
-        let entry = &self.bevy_index[chunk_index];
-        let chunk_size = self.stream.chunk_size as usize;
+    /// Read a chunk's stored bytes from the bevy segment holding it
+    fn read_segment_chunk(
+        archive: &mut zip::ZipArchive<File>,
+        stream: &Aff4ImageStream,
+        segments: &HashMap<usize, usize>,
+        index: usize,
+        entry: &Aff4BevyIndexEntry,
+    ) -> Result<Vec<u8>> {
+        let chunk_size = stream.chunk_size as usize;
 
         // Find and read the bevy segment containing this chunk
-        let segment_index = chunk_index / self.stream.chunks_per_segment as usize;
-        let segment_name = format!("{:08x}", segment_index);
-
-        // Find the segment file
-        let urn_path = self.stream.urn
-            .replace("aff4://", "aff4%3A//")
-            .replace(':', "%3A");
-        let stream_urn = self.stream.urn.clone();
-
-        // First find the segment file name
-        let segment_file: Option<String> = (0..self.archive.len())
-            .find_map(|i| {
-                self.archive.by_index(i).ok().and_then(|file| {
-                    let name = file.name().to_string();
-                    if (name.contains(&urn_path) || name.contains(&stream_urn))
-                        && name.ends_with(&segment_name)
-                        && !name.ends_with(".index")
-                    {
-                        Some(name)
-                    } else {
-                        None
-                    }
-                })
-            });
-
-        // Now read the segment file
-        let segment_data: Option<Vec<u8>> = if let Some(ref name) = segment_file {
-            if let Ok(mut f) = self.archive.by_name(name) {
-                let mut data = Vec::new();
-                f.read_to_end(&mut data)
-                    .map_err(|e| Error::invalid_vault(format!("Failed to read segment: {}", e)))?;
-                Some(data)
-            } else {
-                None
-            }
-        } else {
-            None
+        let segment_index = index / stream.chunks_per_segment as usize;
+        let segment_data: Option<Vec<u8>> = match segments.get(&segment_index) {
+            Some(&member) => match archive.by_index(member) {
+                Ok(mut f) => {
+                    let mut data = Vec::new();
+                    f.read_to_end(&mut data)
+                        .map_err(|e| Error::invalid_vault(format!("Failed to read segment: {}", e)))?;
+                    Some(data)
+                }
+                Err(_) => None,
+            },
+            None => None,
         };
 
         let segment = segment_data
             .ok_or_else(|| Error::invalid_vault("Bevy segment not found"))?;
 
-        // Extract and decompress the chunk
+        // Extract the chunk
         let chunk_offset = entry.offset as usize % segment.len().max(1);
         let chunk_len = (entry.length as usize).min(segment.len().saturating_sub(chunk_offset));
 
@@ -351,37 +391,36 @@ impl Aff4Vault {
             return Ok(vec![0u8; chunk_size]);
         }
 
-        let compressed = &segment[chunk_offset..chunk_offset + chunk_len];
-
-        let decompressed = match self.stream.compression {
-            Aff4Compression::None => compressed.to_vec(),
-            Aff4Compression::Deflate => {
-                let mut decoder = ZlibDecoder::new(Cursor::new(compressed));
-                let mut data = Vec::with_capacity(chunk_size);
-                match decoder.read_to_end(&mut data) {
-                    Ok(_) => data,
-                    Err(e) => {
-                        tracing::warn!(
-                            "AFF4 chunk {} decompression failed: {}. Returning zeros.",
-                            chunk_index, e
-                        );
-                        // Return zeros instead of corrupted data
-                        vec![0u8; chunk_size]
-                    }
-                }
-            }
-            compression => {
-                // Snappy/LZ4 not yet implemented - return error
-                tracing::warn!(
-                    "AFF4 chunk {} uses unsupported compression: {:?}",
-                    chunk_index, compression
-                );
-                return Err(Error::invalid_vault(format!(
-                    "Unsupported compression type: {:?}",
-                    compression
-                )));
+        Ok(segment[chunk_offset..chunk_offset + chunk_len].to_vec())
+    }
+
+    /// Enable or disable read-ahead decompression
+    ///
+    /// With read-ahead, sequential reads decompress the following chunks
+    /// on a worker pool. Passing `None` stops the workers.
+    pub fn set_read_ahead(&mut self, config: Option<ReadAheadConfig>) {
+        self.read_ahead = config.map(ReadAhead::new);
+    }
+
+    /// Read and decompress a chunk
+    fn read_chunk(&mut self, chunk_index: usize) -> Result<Vec<u8>> {
+        // Check cache
+        if let Some(cached) = self.chunk_cache.get(&chunk_index) {
+            return Ok(cached.clone());
+        }
+
+        if chunk_index >= self.bevy_index.len() {
+            return Err(Error::invalid_vault("Chunk index out of range"));
+        }
+
+        let decompressed = match self.read_ahead.take() {
+            Some(mut read_ahead) => {
+                let chunk = read_ahead.fetch(self, chunk_index);
+                self.read_ahead = Some(read_ahead);
+                chunk
             }
-        };
+            None => self.decode_chunk(chunk_index),
+        }?;
 
         // Cache the chunk with LRU eviction (max 16 entries, ~16MB at 1MB chunks)
         const MAX_CACHE_ENTRIES: usize = 16;
@@ -412,6 +451,180 @@ impl Aff4Vault {
     pub fn chunk_count(&self) -> usize {
         self.bevy_index.len()
     }
+
+    /// Get the logical files stored in the container (AFF4-L)
+    pub fn logical_files(&self) -> Vec<Aff4LogicalFile> {
+        self.volume.logical_files.clone()
+    }
+
+    /// Read the content of a logical file
+    ///
+    /// The content comes from the file's `aff4:dataStream` image stream when
+    /// it has one, otherwise from the ZIP member named after its URN.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file's `size` exceeds the allocation limit, or
+    /// if the backing stream or member can't be found or holds fewer than
+    /// `size` bytes.
+    pub fn read_logical_file(&mut self, file: &Aff4LogicalFile) -> Result<Vec<u8>> {
+        let size = validate_allocation_size(file.size, max_allocation_size(), "AFF4 logical file")?;
+        let stream = file
+            .data_stream
+            .as_ref()
+            .and_then(|urn| self.volume.streams.iter().find(|stream| &stream.urn == urn))
+            .cloned();
+
+        let mut data = match stream {
+            Some(stream) => self.read_image_stream(&stream)?,
+            None => {
+                let name = urn_to_path(&file.urn);
+                let member = self
+                    .archive
+                    .by_name(&name)
+                    .map_err(|_| Error::not_found(format!("No content for AFF4 logical file {}", file.urn)))?;
+                let mut data = Vec::with_capacity(size);
+                member
+                    .take(file.size)
+                    .read_to_end(&mut data)
+                    .map_err(|e| Error::invalid_vault(format!("Failed to read logical file: {}", e)))?;
+                data
+            }
+        };
+
+        if (data.len() as u64) < file.size {
+            return Err(Error::truncated(format!(
+                "AFF4 logical file {} holds {} of {} bytes",
+                file.urn,
+                data.len(),
+                file.size
+            )));
+        }
+        data.truncate(size);
+        Ok(data)
+    }
+
+    /// Read and decompress every chunk of an image stream
+    ///
+    /// The stream's stated size is checked against the allocation limit
+    /// before anything is read, and chunks past that size are ignored.
+    fn read_image_stream(&mut self, stream: &Aff4ImageStream) -> Result<Vec<u8>> {
+        let size = validate_allocation_size(stream.size, max_allocation_size(), "AFF4 image stream")?;
+        let bevy_index = Self::load_bevy_index(&mut self.archive, stream)?;
+        let segments = Self::index_segments(&self.archive, stream);
+        let mut data = Vec::with_capacity(size);
+        for (index, entry) in bevy_index.iter().enumerate() {
+            if data.len() >= size {
+                break;
+            }
+            let stored = Self::read_segment_chunk(&mut self.archive, stream, &segments, index, entry)?;
+            let chunk = chunk_decoder_for(stream, index)(stored)?;
+            let take = chunk.len().min(size - data.len());
+            data.extend_from_slice(&chunk[..take]);
+        }
+        Ok(data)
+    }
+
+    /// Recompute the stream's SHA-512 and compare it with the recorded hash
+    ///
+    /// Reads and decompresses the whole stream. Returns None if the
+    /// metadata records no SHA-512 for it.
+    pub fn verify_integrity(&mut self) -> Result<Option<HashVerification>> {
+        self.verify_integrity_with_progress(None)
+    }
+
+    /// Verify as [`verify_integrity`](Self::verify_integrity) does,
+    /// reporting the bytes hashed to `progress`
+    pub fn verify_integrity_with_progress(
+        &mut self,
+        progress: Option<&dyn Progress>,
+    ) -> Result<Option<HashVerification>> {
+        let Some(expected) = self.stream.sha512.clone() else {
+            return Ok(None);
+        };
+
+        let mut hasher = Sha512::new();
+        hash_media(self, progress, |data| hasher.update(data))?;
+
+        Ok(Some(HashVerification {
+            algorithm: "SHA-512",
+            expected,
+            computed: to_hex(&hasher.finalize()),
+        }))
+    }
+}
+
+/// Path of an object's members within the ZIP container
+///
+/// Member names are URNs with their colons percent-encoded.
+fn urn_to_path(urn: &str) -> String {
+    urn.replace("aff4://", "aff4%3A//").replace(':', "%3A")
+}
+
+/// Build the decoder for a chunk of `stream`
+fn chunk_decoder_for(stream: &Aff4ImageStream, index: usize) -> ChunkDecoder {
+    let compression = stream.compression;
+    let chunk_size = stream.chunk_size as usize;
+
+    Box::new(move |compressed| match compression {
+        Aff4Compression::None => Ok(compressed),
+        Aff4Compression::Deflate => {
+            // A chunk never inflates past the chunk size
+            let mut decoder = ZlibDecoder::new(Cursor::new(&compressed)).take(chunk_size as u64);
+            let mut data = Vec::with_capacity(chunk_size);
+            match decoder.read_to_end(&mut data) {
+                Ok(_) => Ok(data),
+                Err(e) => {
+                    tracing::warn!(
+                        "AFF4 chunk {} decompression failed: {}. Returning zeros.",
+                        index, e
+                    );
+                    // Return zeros instead of corrupted data
+                    Ok(vec![0u8; chunk_size])
+                }
+            }
+        }
+        compression => {
+            // Snappy/LZ4 not yet implemented - return error
+            tracing::warn!(
+                "AFF4 chunk {} uses unsupported compression: {:?}",
+                index, compression
+            );
+            Err(Error::invalid_vault(format!(
+                "Unsupported compression type: {:?}",
+                compression
+            )))
+        }
+    })
+}
+
+/// Check whether a literal looks like a hex SHA-512 digest
+fn is_sha512_hex(value: &str) -> bool {
+    value.len() == 128 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl ChunkSource for Aff4Vault {
+    fn chunk_count(&self) -> usize {
+        self.bevy_index.len()
+    }
+
+    fn chunk_size(&self) -> usize {
+        self.stream.chunk_size as usize
+    }
+
+    fn read_stored_chunk(&mut self, index: usize) -> Result<Vec<u8>> {
+        Self::read_segment_chunk(
+            &mut self.archive,
+            &self.stream,
+            &self.segments,
+            index,
+            &self.bevy_index[index],
+        )
+    }
+
+    fn chunk_decoder(&self, index: usize) -> ChunkDecoder {
+        chunk_decoder_for(&self.stream, index)
+    }
 }
 
 impl Read for Aff4Vault {
@@ -495,6 +708,168 @@ unsafe impl Sync for Aff4Vault {}
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+    use zip::write::SimpleFileOptions;
+
+    /// AFF4-L container with one file stored as a ZIP member and one backed
+    /// by an uncompressed image stream
+    fn logical_container(photo: &[u8]) -> NamedTempFile {
+        let turtle = format!(
+            r#"
+@prefix aff4: <http://aff4.org/Schema#> .
+@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+
+<aff4://vol/notes.txt> rdf:type aff4:FileImage ;
+    aff4:originalFileName "/home/user/notes.txt" ;
+    aff4:size "12" .
+
+<aff4://vol/photo.jpg> rdf:type aff4:FileImage ;
+    aff4:originalFileName "/home/user/pictures/photo.jpg" ;
+    aff4:dataStream <aff4://vol/photo-data> ;
+    aff4:size "{}" .
+
+<aff4://vol/photo-data> rdf:type aff4:ImageStream ;
+    aff4:size "{}" ;
+    aff4:chunkSize "4096" ;
+    aff4:compressionMethod aff4:NullCompressor .
+"#,
+            photo.len(),
+            photo.len()
+        );
+
+        let mut file = NamedTempFile::new().unwrap();
+        let mut zip = zip::ZipWriter::new(file.as_file_mut());
+        let options = SimpleFileOptions::default();
+        for (name, data) in [
+            ("container.description", turtle.as_bytes()),
+            ("aff4%3A//vol/notes.txt", b"hello, world".as_slice()),
+            ("aff4%3A//vol/photo-data/00000000", photo),
+        ] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+        file
+    }
+
+    #[test]
+    fn test_logical_files() {
+        let photo: Vec<u8> = (0..5000u32).map(|i| (i % 253) as u8).collect();
+        let container = logical_container(&photo);
+
+        let mut vault = Aff4Vault::open(container.path()).unwrap();
+        assert_eq!(vault.identify(), "AFF4 Logical Image (2 files)");
+        assert_eq!(vault.length(), 0);
+
+        let files = vault.logical_files();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].name, "notes.txt");
+        assert_eq!(files[0].size, 12);
+        assert_eq!(files[0].original_path.as_deref(), Some("/home/user/notes.txt"));
+        assert_eq!(files[0].data_stream, None);
+        assert_eq!(files[1].name, "photo.jpg");
+        assert_eq!(files[1].data_stream.as_deref(), Some("aff4://vol/photo-data"));
+
+        assert_eq!(vault.read_logical_file(&files[0]).unwrap(), b"hello, world");
+        assert_eq!(vault.read_logical_file(&files[1]).unwrap(), photo);
+
+        let missing = Aff4LogicalFile { urn: "aff4://vol/missing".to_string(), ..Default::default() };
+        assert!(matches!(vault.read_logical_file(&missing), Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn test_logical_file_size_limit() {
+        let container = logical_container(b"abc");
+        let mut vault = Aff4Vault::open(container.path()).unwrap();
+        let mut files = vault.logical_files();
+
+        // A forged size is refused before anything is allocated
+        files[0].size = max_allocation_size() as u64 + 1;
+        assert!(matches!(vault.read_logical_file(&files[0]), Err(Error::InvalidVault(_))));
+
+        let mut stream = vault.volume.streams[0].clone();
+        stream.size = u64::MAX;
+        assert!(matches!(vault.read_image_stream(&stream), Err(Error::InvalidVault(_))));
+    }
+
+    #[test]
+    fn test_image_length() {
+        let disk: Vec<u8> = (0..10_000u32).map(|i| (i % 249) as u8).collect();
+        let turtle = format!(
+            r#"
+@prefix aff4: <http://aff4.org/Schema#> .
+@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+
+<aff4://vol/disk> rdf:type aff4:ImageStream ;
+    aff4:size "{}" ;
+    aff4:chunkSize "4096" ;
+    aff4:compressionMethod aff4:NullCompressor .
+"#,
+            disk.len()
+        );
+
+        let mut file = NamedTempFile::new().unwrap();
+        let mut zip = zip::ZipWriter::new(file.as_file_mut());
+        for (name, data) in [("container.description", turtle.as_bytes()), ("aff4%3A//vol/disk/00000000", &disk)] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let mut vault = Aff4Vault::open(file.path()).unwrap();
+        assert_eq!(vault.length(), disk.len() as u64);
+        assert_eq!(vault.sector_count(512), 20);
+
+        let mut data = Vec::new();
+        vault.content().read_to_end(&mut data).unwrap();
+        assert_eq!(data, disk);
+    }
+
+    #[test]
+    fn test_zip64_container() {
+        // More members than a classic end of central directory can count
+        const SEGMENTS: usize = 70_000;
+        let disk: Vec<u8> = (0..SEGMENTS * 4).map(|i| (i % 251) as u8).collect();
+        let turtle = format!(
+            r#"
+@prefix aff4: <http://aff4.org/Schema#> .
+@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+
+<aff4://vol/disk> rdf:type aff4:ImageStream ;
+    aff4:size "{}" ;
+    aff4:chunkSize "4" ;
+    aff4:chunksInSegment "1" ;
+    aff4:compressionMethod aff4:NullCompressor .
+"#,
+            disk.len()
+        );
+
+        let mut file = NamedTempFile::new().unwrap();
+        let mut zip = zip::ZipWriter::new(file.as_file_mut());
+        let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        zip.start_file("container.description", options).unwrap();
+        zip.write_all(turtle.as_bytes()).unwrap();
+        for (segment, data) in disk.chunks(4).enumerate() {
+            // Give the first segment ZIP64 extra fields as well
+            let options = options.large_file(segment == 0);
+            zip.start_file(format!("aff4%3A//vol/disk/{:08x}", segment), options).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let raw = std::fs::read(file.path()).unwrap();
+        assert!(raw.windows(4).any(|w| w == b"PK\x06\x06"), "expected a ZIP64 end of central directory");
+
+        let mut vault = Aff4Vault::open(file.path()).unwrap();
+        assert_eq!(vault.length(), disk.len() as u64);
+        assert_eq!(vault.bevy_index.len(), SEGMENTS);
+        assert_eq!(vault.segments.len(), SEGMENTS);
+
+        let mut data = Vec::new();
+        vault.content().read_to_end(&mut data).unwrap();
+        assert_eq!(data, disk);
+    }
 
     #[test]
     fn test_aff4_volume_default() {
@@ -539,4 +914,13 @@ mod tests {
         };
         assert!(container.statements.is_empty());
     }
+
+    #[test]
+    fn test_is_sha512_hex() {
+        let digest = to_hex(&Sha512::digest(b"aff4"));
+        assert!(is_sha512_hex(&digest));
+        assert!(is_sha512_hex(&digest.to_ascii_uppercase()));
+        assert!(!is_sha512_hex(&digest[..64]));
+        assert!(!is_sha512_hex(&digest.replace('a', "g")));
+    }
 }
@@ -22,6 +22,8 @@ pub enum Aff4ObjectType {
     ImageStream,
     /// Map stream (sparse image)
     Map,
+    /// Logical file (AFF4-L)
+    FileImage,
     /// Zip volume container
     ZipVolume,
     /// Directory
@@ -35,6 +37,8 @@ impl Aff4ObjectType {
     pub fn from_uri(uri: &str) -> Self {
         if uri.contains("ImageStream") {
             Self::ImageStream
+        } else if uri.contains("FileImage") {
+            Self::FileImage
         } else if uri.contains("Map") {
             Self::Map
         } else if uri.contains("ZipVolume") {
@@ -96,6 +100,8 @@ pub struct Aff4ImageStream {
     pub data_path: Option<String>,
     /// Index path within ZIP
     pub index_path: Option<String>,
+    /// SHA-512 of the stream recorded at acquisition (`aff4:hash`), lowercase hex
+    pub sha512: Option<String>,
 }
 
 impl Default for Aff4ImageStream {
@@ -108,10 +114,28 @@ impl Default for Aff4ImageStream {
             compression: Aff4Compression::Deflate,
             data_path: None,
             index_path: None,
+            sha512: None,
         }
     }
 }
 
+/// Logical file stored in an AFF4-L container (`aff4:FileImage`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Aff4LogicalFile {
+    /// URN of the file object
+    pub urn: String,
+    /// File name, the last component of the original path
+    pub name: String,
+    /// Size of the file in bytes
+    pub size: u64,
+    /// Path the file had on the source system (`aff4:originalFileName`)
+    pub original_path: Option<String>,
+    /// URN of the image stream holding the content (`aff4:dataStream`)
+    ///
+    /// Without one, the content is a ZIP member named after the file's URN.
+    pub data_stream: Option<String>,
+}
+
 /// AFF4 volume metadata
 #[derive(Debug, Clone)]
 pub struct Aff4Volume {
@@ -125,6 +149,8 @@ pub struct Aff4Volume {
     pub tool_version: Option<String>,
     /// Image streams in this volume
     pub streams: Vec<Aff4ImageStream>,
+    /// Logical files in this volume
+    pub logical_files: Vec<Aff4LogicalFile>,
 }
 
 impl Default for Aff4Volume {
@@ -135,6 +161,7 @@ impl Default for Aff4Volume {
             tool: None,
             tool_version: None,
             streams: Vec::new(),
+            logical_files: Vec::new(),
         }
     }
 }
@@ -172,7 +199,7 @@ impl Aff4BevyIndexEntry {
     /// Parse from bytes (little-endian)
     pub fn parse(bytes: &[u8]) -> Result<Self> {
         if bytes.len() < Self::SIZE {
-            return Err(Error::invalid_vault("AFF4 bevy index entry too small"));
+            return Err(Error::truncated("AFF4 bevy index entry too small"));
         }
 
         let offset = u64::from_le_bytes([
@@ -194,6 +221,7 @@ impl TurtleParser {
         let mut statements = Vec::new();
         let mut prefixes: Vec<(String, String)> = Vec::new();
         let mut current_subject = String::new();
+        let mut continued = false;
 
         for line in content.lines() {
             let line = line.trim();
@@ -212,12 +240,13 @@ impl TurtleParser {
             }
 
             // Parse statements
-            if let Some(stmt) = Self::parse_statement(line, &prefixes, &current_subject) {
+            if let Some(stmt) = Self::parse_statement(line, &prefixes, &current_subject, continued) {
                 if !stmt.subject.is_empty() {
                     current_subject = stmt.subject.clone();
                 }
                 statements.push(stmt);
             }
+            continued = line.ends_with(';');
         }
 
         statements
@@ -236,12 +265,17 @@ impl TurtleParser {
     }
 
     /// Parse a statement
+    ///
+    /// A line `continued` from one ending in ';' starts with a predicate of
+    /// the current subject.
     fn parse_statement(
         line: &str,
         prefixes: &[(String, String)],
         current_subject: &str,
+        continued: bool,
     ) -> Option<Aff4Statement> {
-        let line = line.trim_end_matches('.');
+        // Statements end with '.', or ';' / ',' when continued on the next line
+        let line = line.trim_end_matches(['.', ';', ',']).trim_end();
         let parts: Vec<&str> = line.split_whitespace().collect();
 
         if parts.is_empty() {
@@ -249,7 +283,7 @@ impl TurtleParser {
         }
 
         // Determine subject, predicate, object
-        let (subject, pred_idx) = if parts[0].starts_with('<') || parts[0].contains(':') {
+        let (subject, pred_idx) = if !continued && (parts[0].starts_with('<') || parts[0].contains(':')) {
             (Self::expand_uri(parts[0], prefixes), 1)
         } else {
             (current_subject.to_string(), 0)
@@ -317,6 +351,10 @@ mod tests {
             Aff4ObjectType::from_uri("http://aff4.org/Schema#ZipVolume"),
             Aff4ObjectType::ZipVolume
         );
+        assert_eq!(
+            Aff4ObjectType::from_uri("http://aff4.org/Schema#FileImage"),
+            Aff4ObjectType::FileImage
+        );
     }
 
     #[test]
@@ -360,6 +398,21 @@ mod tests {
         assert!(statements.len() >= 2);
     }
 
+    #[test]
+    fn test_turtle_parser_continuations() {
+        let content = r#"
+@prefix aff4: <http://aff4.org/Schema#> .
+<aff4://file> aff4:dataStream <aff4://stream> ;
+    aff4:size "12" .
+"#;
+
+        let statements = TurtleParser::parse(content);
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0].object, "aff4://stream");
+        assert_eq!(statements[1].subject, "aff4://file");
+        assert_eq!(statements[1].object, "12");
+    }
+
     #[test]
     fn test_turtle_expand_uri() {
         let prefixes = vec![
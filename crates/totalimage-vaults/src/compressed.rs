@@ -0,0 +1,310 @@
+//! Compressed raw vault - gzip and zstd wrapped sector images
+//!
+//! Compressed streams can't be seeked, so the image is decompressed once on
+//! open: into memory when small, otherwise into an anonymous temporary file
+//! that is removed when the vault is dropped. Common file extensions:
+//! .img.gz, .img.zst
+
+use flate2::read::MultiGzDecoder;
+use std::fs::File;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use totalimage_core::{Error, ReadSeek, Result, Vault};
+use totalimage_pipeline::MmapPipeline;
+
+use crate::VaultConfig;
+
+/// Gzip magic bytes
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+/// Zstandard frame magic bytes
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// Images up to this size are decompressed into memory (256 MiB)
+pub const IN_MEMORY_LIMIT: u64 = 256 * 1024 * 1024;
+
+/// Default limit on the decompressed image size (64 GiB)
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: u64 = 64 * 1024 * 1024 * 1024;
+
+/// Size of each chunk copied from the decompressor
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Compression wrapping a raw image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+    /// gzip (RFC 1952), including multi-member files
+    Gzip,
+    /// Zstandard
+    Zstd,
+}
+
+impl CompressionFormat {
+    /// Detect the compression format from the first bytes of a file
+    pub fn detect(magic: &[u8]) -> Option<Self> {
+        if magic.starts_with(ZSTD_MAGIC) {
+            Some(Self::Zstd)
+        } else if magic.starts_with(GZIP_MAGIC) {
+            Some(Self::Gzip)
+        } else {
+            None
+        }
+    }
+
+    /// Get a human-readable name for this format
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Gzip => "Gzip-compressed raw image",
+            Self::Zstd => "Zstandard-compressed raw image",
+        }
+    }
+}
+
+/// Compressed raw vault - a raw sector image inside gzip or zstd
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use totalimage_vaults::{CompressedRawVault, VaultConfig};
+/// use totalimage_core::Vault;
+/// use std::path::Path;
+///
+/// let vault = CompressedRawVault::open(Path::new("disk.img.gz"), VaultConfig::default()).unwrap();
+/// println!("Vault type: {}", vault.identify());
+/// println!("Size: {} bytes", vault.length());
+/// ```
+pub struct CompressedRawVault {
+    pipeline: Box<dyn ReadSeek>,
+    length: u64,
+    format: CompressionFormat,
+}
+
+impl CompressedRawVault {
+    /// Open a compressed raw vault from a file path
+    ///
+    /// Decompression stops with an error once the image exceeds
+    /// [`DEFAULT_MAX_DECOMPRESSED_SIZE`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is not gzip or zstd compressed, the
+    /// compressed data is corrupt, or the decompressed size exceeds the limit
+    pub fn open(path: &Path, config: VaultConfig) -> Result<Self> {
+        Self::open_with_limit(path, config, DEFAULT_MAX_DECOMPRESSED_SIZE)
+    }
+
+    /// Open a compressed raw vault with a custom decompressed size limit
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the compressed disk image
+    /// * `config` - Configuration for opening the vault; `use_mmap` applies
+    ///   to images spilled to a temporary file
+    /// * `max_size` - Maximum decompressed size in bytes
+    ///
+    /// # Errors
+    ///
+    /// Same as [`open`](Self::open)
+    pub fn open_with_limit(path: &Path, config: VaultConfig, max_size: u64) -> Result<Self> {
+        let mut file = File::open(path)?;
+
+        let mut magic = [0u8; 4];
+        let magic_len = file.read(&mut magic)?;
+        let format = CompressionFormat::detect(&magic[..magic_len]).ok_or_else(|| {
+            Error::unrecognized_signature("Not a gzip or zstd compressed image".to_string())
+        })?;
+        file.seek(SeekFrom::Start(0))?;
+
+        let decoder: Box<dyn Read> = match format {
+            CompressionFormat::Gzip => Box::new(MultiGzDecoder::new(file)),
+            CompressionFormat::Zstd => Box::new(zstd::stream::read::Decoder::new(file)?),
+        };
+
+        let (pipeline, length) = decompress(decoder, config, max_size, IN_MEMORY_LIMIT)?;
+
+        Ok(Self {
+            pipeline,
+            length,
+            format,
+        })
+    }
+
+    /// Get the compression format of the image
+    pub fn format(&self) -> CompressionFormat {
+        self.format
+    }
+}
+
+/// Decompress `decoder` into a seekable stream, enforcing `max_size`
+///
+/// Data is held in memory until it exceeds `in_memory_limit`, then moved to
+/// a temporary file.
+fn decompress(
+    mut decoder: Box<dyn Read>,
+    config: VaultConfig,
+    max_size: u64,
+    in_memory_limit: u64,
+) -> Result<(Box<dyn ReadSeek>, u64)> {
+    let mut memory = Vec::new();
+    let mut spill: Option<File> = None;
+    let mut chunk = vec![0u8; CHUNK_SIZE];
+    let mut length = 0u64;
+
+    loop {
+        let n = decoder.read(&mut chunk)?;
+        if n == 0 {
+            break;
+        }
+
+        length += n as u64;
+        if length > max_size {
+            return Err(Error::invalid_vault(format!(
+                "Decompressed image exceeds the {} byte limit",
+                max_size
+            )));
+        }
+
+        match spill.as_mut() {
+            Some(file) => file.write_all(&chunk[..n])?,
+            None if length > in_memory_limit => {
+                let mut file = tempfile::tempfile()?;
+                file.write_all(&memory)?;
+                file.write_all(&chunk[..n])?;
+                memory = Vec::new();
+                spill = Some(file);
+            }
+            None => memory.extend_from_slice(&chunk[..n]),
+        }
+    }
+
+    let pipeline: Box<dyn ReadSeek> = match spill {
+        Some(file) if config.use_mmap && length > 0 => Box::new(MmapPipeline::from_file(&file)?),
+        Some(mut file) => {
+            file.seek(SeekFrom::Start(0))?;
+            Box::new(file)
+        }
+        None => Box::new(Cursor::new(memory)),
+    };
+
+    Ok((pipeline, length))
+}
+
+impl Vault for CompressedRawVault {
+    fn identify(&self) -> &str {
+        self.format.name()
+    }
+
+    fn length(&self) -> u64 {
+        self.length
+    }
+
+    fn content(&mut self) -> &mut dyn ReadSeek {
+        &mut *self.pipeline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use tempfile::NamedTempFile;
+
+    /// Synthetic raw image with a marker at a known offset
+    fn test_image() -> Vec<u8> {
+        let mut image: Vec<u8> = (0u8..=255).cycle().take(64 * 1024).collect();
+        image[40_000..40_008].copy_from_slice(b"MARKER!!");
+        image
+    }
+
+    fn write_temp(data: &[u8], suffix: &str) -> NamedTempFile {
+        let mut tmpfile = NamedTempFile::with_suffix(suffix).unwrap();
+        tmpfile.write_all(data).unwrap();
+        tmpfile.flush().unwrap();
+        tmpfile
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_detect_compression_format() {
+        assert_eq!(CompressionFormat::detect(&[0x1f, 0x8b, 0x08, 0x00]), Some(CompressionFormat::Gzip));
+        assert_eq!(CompressionFormat::detect(&[0x28, 0xb5, 0x2f, 0xfd]), Some(CompressionFormat::Zstd));
+        assert_eq!(CompressionFormat::detect(&[0x1f]), None);
+        assert_eq!(CompressionFormat::detect(b"conectix"), None);
+    }
+
+    #[test]
+    fn test_open_gzip_image() {
+        let image = test_image();
+        let tmpfile = write_temp(&gzip(&image), ".img.gz");
+
+        let mut vault = CompressedRawVault::open(tmpfile.path(), VaultConfig::default()).unwrap();
+        assert_eq!(vault.identify(), "Gzip-compressed raw image");
+        assert_eq!(vault.length(), image.len() as u64);
+
+        let mut buf = [0u8; 8];
+        vault.content().seek(SeekFrom::Start(40_000)).unwrap();
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"MARKER!!");
+
+        vault.content().seek(SeekFrom::Start(0)).unwrap();
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, &[0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_open_zstd_image() {
+        let image = test_image();
+        let compressed = zstd::stream::encode_all(&image[..], 3).unwrap();
+        let tmpfile = write_temp(&compressed, ".img.zst");
+
+        let mut vault = CompressedRawVault::open(tmpfile.path(), VaultConfig::default()).unwrap();
+        assert_eq!(vault.format(), CompressionFormat::Zstd);
+        assert_eq!(vault.length(), image.len() as u64);
+
+        let mut buf = [0u8; 8];
+        vault.content().seek(SeekFrom::Start(40_000)).unwrap();
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"MARKER!!");
+    }
+
+    #[test]
+    fn test_decompression_limit() {
+        // 64 KiB of zeros compresses to a few hundred bytes
+        let tmpfile = write_temp(&gzip(&[0u8; 64 * 1024]), ".img.gz");
+
+        let result = CompressedRawVault::open_with_limit(tmpfile.path(), VaultConfig::default(), 4096);
+        assert!(matches!(result, Err(Error::InvalidVault(_))));
+
+        let vault = CompressedRawVault::open_with_limit(tmpfile.path(), VaultConfig::default(), 64 * 1024).unwrap();
+        assert_eq!(vault.length(), 64 * 1024);
+    }
+
+    #[test]
+    fn test_spill_to_temp_file() {
+        let image = test_image();
+        for use_mmap in [true, false] {
+            // Reads of 1 MiB or less from a Cursor arrive in one chunk, so
+            // a zero limit spills on the first chunk
+            let decoder = Box::new(Cursor::new(image.clone()));
+            let (mut pipeline, length) =
+                decompress(decoder, VaultConfig { use_mmap, ..Default::default() }, u64::MAX, 0).unwrap();
+            assert_eq!(length, image.len() as u64);
+
+            let mut buf = [0u8; 8];
+            pipeline.seek(SeekFrom::Start(40_000)).unwrap();
+            pipeline.read_exact(&mut buf).unwrap();
+            assert_eq!(&buf, b"MARKER!!");
+        }
+    }
+
+    #[test]
+    fn test_open_uncompressed_rejected() {
+        let tmpfile = write_temp(&test_image(), ".img");
+        assert!(CompressedRawVault::open(tmpfile.path(), VaultConfig::default()).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! Apple disk image (DMG) vault
+//!
+//! A UDIF disk image ends in a 512-byte "koly" trailer locating its data
+//! fork, which holds the disk in chunks described by a property list. Only
+//! images whose data fork is the whole disk, uncompressed and in order, are
+//! opened; compressed and sparse images are recognized and refused with an
+//! explanation. Common file extension: .dmg
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use totalimage_core::{Error, ReadSeek, Result, Vault};
+use totalimage_pipeline::{MmapPipeline, PartialPipeline};
+
+use crate::VaultConfig;
+
+/// Sector size UDIF counts the disk in
+const UDIF_SECTOR_SIZE: u64 = 512;
+
+/// UDIF trailer at the end of a DMG
+///
+/// All fields are big-endian.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KolyTrailer {
+    /// Trailer format version (4 for current images)
+    pub version: u32,
+    /// Offset of the data fork in the file
+    pub data_fork_offset: u64,
+    /// Length of the data fork in bytes
+    pub data_fork_length: u64,
+    /// Offset of the property list describing the data fork's chunks
+    pub xml_offset: u64,
+    /// Length of the property list in bytes
+    pub xml_length: u64,
+    /// Size of the disk in 512-byte sectors
+    pub sector_count: u64,
+}
+
+impl KolyTrailer {
+    /// Size of the trailer in bytes
+    pub const SIZE: usize = 512;
+
+    /// Trailer signature
+    pub const MAGIC: &'static [u8; 4] = b"koly";
+
+    /// Parse a trailer, returning None if the signature doesn't match
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Self::SIZE || &bytes[0..4] != Self::MAGIC {
+            return None;
+        }
+
+        let u32_at = |offset: usize| u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        let u64_at = |offset: usize| u64::from_be_bytes(bytes[offset..offset + 8].try_into().unwrap());
+        Some(Self {
+            version: u32_at(4),
+            data_fork_offset: u64_at(24),
+            data_fork_length: u64_at(32),
+            xml_offset: u64_at(216),
+            xml_length: u64_at(224),
+            sector_count: u64_at(492),
+        })
+    }
+
+    /// Get the size of the disk in bytes
+    pub fn disk_size(&self) -> Option<u64> {
+        self.sector_count.checked_mul(UDIF_SECTOR_SIZE)
+    }
+
+    /// Read the trailer from the last 512 bytes of `reader`, if it has one
+    pub fn read<R: Read + Seek + ?Sized>(reader: &mut R) -> Result<Option<Self>> {
+        let len = reader.seek(SeekFrom::End(0))?;
+        if len < Self::SIZE as u64 {
+            return Ok(None);
+        }
+
+        let mut bytes = [0u8; Self::SIZE];
+        reader.seek(SeekFrom::End(-(Self::SIZE as i64)))?;
+        reader.read_exact(&mut bytes)?;
+        Ok(Self::from_bytes(&bytes))
+    }
+}
+
+/// DMG vault - an uncompressed UDIF disk image
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use totalimage_vaults::{DmgVault, VaultConfig};
+/// use totalimage_core::Vault;
+/// use std::path::Path;
+///
+/// let vault = DmgVault::open(Path::new("disk.dmg"), VaultConfig::default()).unwrap();
+/// println!("Size: {} bytes", vault.length());
+/// ```
+pub struct DmgVault {
+    pipeline: PartialPipeline<Box<dyn ReadSeek>>,
+    trailer: KolyTrailer,
+}
+
+impl DmgVault {
+    /// Open a DMG vault from a file path
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file has no UDIF trailer, if its data fork
+    /// lies outside the file, or if the data fork isn't the whole disk
+    /// stored uncompressed (as in compressed or sparse images), which is
+    /// unsupported
+    pub fn open(path: &Path, config: VaultConfig) -> Result<Self> {
+        let mut file = File::open(path)?;
+        let trailer = KolyTrailer::read(&mut file)?
+            .ok_or_else(|| Error::unrecognized_signature("No UDIF (koly) trailer found".to_string()))?;
+
+        let file_len = file.metadata()?.len();
+        let data_end = trailer.data_fork_offset.checked_add(trailer.data_fork_length);
+        if !matches!(data_end, Some(end) if end <= file_len - KolyTrailer::SIZE as u64) {
+            return Err(Error::invalid_vault("UDIF data fork lies outside the file"));
+        }
+
+        if trailer.disk_size() != Some(trailer.data_fork_length) {
+            return Err(Error::unsupported(format!(
+                "UDIF-wrapped DMG detected; only uncompressed UDIF is supported \
+                 (the data fork holds {} bytes of a {} sector disk)",
+                trailer.data_fork_length, trailer.sector_count
+            )));
+        }
+
+        let base: Box<dyn ReadSeek> = if config.use_mmap && file_len > 0 {
+            Box::new(MmapPipeline::from_file(&file)?)
+        } else {
+            Box::new(file)
+        };
+        let pipeline = PartialPipeline::new(base, trailer.data_fork_offset, trailer.data_fork_length)?;
+
+        Ok(Self { pipeline, trailer })
+    }
+
+    /// Get the image's UDIF trailer
+    pub fn trailer(&self) -> &KolyTrailer {
+        &self.trailer
+    }
+}
+
+impl Vault for DmgVault {
+    fn identify(&self) -> &str {
+        "Apple UDIF disk image"
+    }
+
+    fn length(&self) -> u64 {
+        self.trailer.data_fork_length
+    }
+
+    fn content(&mut self) -> &mut dyn ReadSeek {
+        &mut self.pipeline
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    /// Build a koly trailer for a data fork at `offset`
+    pub(crate) fn koly_trailer(offset: u64, length: u64, sector_count: u64) -> [u8; KolyTrailer::SIZE] {
+        let mut trailer = [0u8; KolyTrailer::SIZE];
+        trailer[0..4].copy_from_slice(KolyTrailer::MAGIC);
+        trailer[4..8].copy_from_slice(&4u32.to_be_bytes());
+        trailer[8..12].copy_from_slice(&512u32.to_be_bytes());
+        trailer[24..32].copy_from_slice(&offset.to_be_bytes());
+        trailer[32..40].copy_from_slice(&length.to_be_bytes());
+        trailer[492..500].copy_from_slice(&sector_count.to_be_bytes());
+        trailer
+    }
+
+    #[test]
+    fn test_koly_trailer_parse() {
+        let trailer = KolyTrailer::from_bytes(&koly_trailer(0, 4096, 8)).unwrap();
+        assert_eq!(trailer.version, 4);
+        assert_eq!(trailer.data_fork_length, 4096);
+        assert_eq!(trailer.sector_count, 8);
+        assert_eq!(trailer.disk_size(), Some(4096));
+
+        assert!(KolyTrailer::from_bytes(&[0u8; 512]).is_none());
+        assert!(KolyTrailer::from_bytes(b"koly").is_none());
+    }
+
+    #[test]
+    fn test_open_uncompressed_dmg() {
+        let disk: Vec<u8> = (0..2048u32).map(|i| (i % 253) as u8).collect();
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&disk).unwrap();
+        file.write_all(&koly_trailer(0, 2048, 4)).unwrap();
+        file.flush().unwrap();
+
+        for use_mmap in [true, false] {
+            let config = VaultConfig { use_mmap, ..Default::default() };
+            let mut vault = DmgVault::open(file.path(), config).unwrap();
+            assert_eq!(vault.length(), 2048);
+
+            let mut data = Vec::new();
+            vault.content().read_to_end(&mut data).unwrap();
+            assert_eq!(data, disk);
+        }
+    }
+
+    #[test]
+    fn test_compressed_dmg_unsupported() {
+        // A 1000-byte data fork can't hold an 8-sector disk uncompressed
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&[0x78; 1000]).unwrap();
+        file.write_all(&koly_trailer(0, 1000, 8)).unwrap();
+        file.flush().unwrap();
+
+        let err = DmgVault::open(file.path(), VaultConfig::default()).err().unwrap();
+        assert!(matches!(err, Error::Unsupported(_)), "{:?}", err);
+        assert!(err.to_string().contains("only uncompressed UDIF"));
+
+        // Data fork past the end of the file
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(&koly_trailer(0, 4096, 8)).unwrap();
+        file.flush().unwrap();
+        assert!(DmgVault::open(file.path(), VaultConfig::default()).is_err());
+    }
+}
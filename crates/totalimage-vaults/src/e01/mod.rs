@@ -12,13 +12,13 @@
 //! ┌──────────────────────────┐
 //! │   File Header (13 bytes) │  EVF signature + segment number
 //! ├──────────────────────────┤
-//! │   Header Section         │  Case metadata (compressed)
+//! │   Header Sections        │  Case metadata (compressed)
 //! ├──────────────────────────┤
 //! │   Volume Section         │  Media information
 //! ├──────────────────────────┤
 //! │   Sectors Section(s)     │  Compressed data chunks
 //! ├──────────────────────────┤
-//! │   Table Section          │  Chunk offset table
+//! │   Table Section(s)       │  Chunk offset table and its backup
 //! ├──────────────────────────┤
 //! │   Hash Section           │  MD5 hash of uncompressed data
 //! ├──────────────────────────┤
@@ -33,7 +33,10 @@ use std::io::{Cursor, Read, Seek, SeekFrom};
 use std::path::Path;
 
 use flate2::read::ZlibDecoder;
-use totalimage_core::{Error, ReadSeek, Result, Vault};
+use totalimage_core::{max_allocation_size, validate_allocation_size, Error, Progress, ReadSeek, Result, Vault};
+
+use crate::read_ahead::{ChunkDecoder, ChunkSource, ReadAhead, ReadAheadConfig};
+use crate::verify::{hash_media, to_hex, HashVerification};
 
 pub use types::*;
 
@@ -54,6 +57,8 @@ pub struct E01Vault {
     hash: Option<E01HashSection>,
     /// Decompressed data cache (virtual disk view)
     cache: E01Cache,
+    /// Worker pool decompressing upcoming chunks, if enabled
+    read_ahead: Option<ReadAhead>,
     /// Identification string
     identifier: String,
 }
@@ -118,10 +123,14 @@ impl E01Vault {
         let mut volume: Option<E01VolumeSection> = None;
         let mut chunk_table: Vec<E01ChunkInfo> = Vec::new();
         let mut hash: Option<E01HashSection> = None;
-        let mut sectors_data: Vec<(u64, u64)> = Vec::new(); // (offset, size)
+        // End of the latest sectors section's data, which bounds its last chunk
+        let mut sectors_end: Option<u64> = None;
+        // Whether the latest sectors section's table has been read, so its
+        // table2 backup copy is skipped
+        let mut table_read = false;
 
-        // Start parsing sections after file header
-        let mut section_offset = file_header.fields_start as u64;
+        // Sections start right after the file header
+        let mut section_offset = E01FileHeader::SIZE as u64;
 
         loop {
             reader.seek(SeekFrom::Start(section_offset))?;
@@ -132,65 +141,35 @@ impl E01Vault {
             }
 
             let section = E01SectionDescriptor::parse(&section_bytes)?;
+            let data_offset = section_offset + E01SectionDescriptor::SIZE as u64;
+            let data_size = section.section_size.saturating_sub(E01SectionDescriptor::SIZE as u64);
 
             match section.section_type {
                 SectionType::Volume | SectionType::Disk => {
                     // Read volume data
-                    let data_offset = section_offset + E01SectionDescriptor::SIZE as u64;
                     reader.seek(SeekFrom::Start(data_offset))?;
-
-                    let data_size = section.section_size - E01SectionDescriptor::SIZE as u64;
                     let mut vol_data = vec![0u8; data_size.min(1024) as usize];
                     reader.read_exact(&mut vol_data)?;
 
                     volume = Some(E01VolumeSection::parse(&vol_data)?);
                 }
                 SectionType::Sectors | SectionType::Data => {
-                    // Record sectors section location
-                    let data_offset = section_offset + E01SectionDescriptor::SIZE as u64;
-                    let data_size = section.section_size - E01SectionDescriptor::SIZE as u64;
-                    sectors_data.push((data_offset, data_size));
+                    // Record where this section's chunks end
+                    sectors_end = Some(data_offset + data_size);
+                    table_read = false;
                 }
+                SectionType::Table2 if table_read => {}
                 SectionType::Table | SectionType::Table2 => {
-                    // Parse chunk offset table
-                    let data_offset = section_offset + E01SectionDescriptor::SIZE as u64;
                     reader.seek(SeekFrom::Start(data_offset))?;
-
-                    // Table contains 4-byte entries (offsets within sectors section)
-                    let data_size = section.section_size - E01SectionDescriptor::SIZE as u64;
-                    let entry_count = data_size / 4;
-
-                    let mut table_data = vec![0u8; data_size as usize];
-                    reader.read_exact(&mut table_data)?;
-
-                    // Parse table entries
-                    for i in 0..entry_count as usize {
-                        if i * 4 + 4 <= table_data.len() {
-                            let base_offset = u32::from_le_bytes([
-                                table_data[i * 4],
-                                table_data[i * 4 + 1],
-                                table_data[i * 4 + 2],
-                                table_data[i * 4 + 3],
-                            ]);
-
-                            // MSB indicates compression
-                            let is_compressed = base_offset & 0x80000000 == 0;
-                            let offset = (base_offset & 0x7FFFFFFF) as u64;
-
-                            chunk_table.push(E01ChunkInfo {
-                                offset,
-                                compressed_size: 0, // Will be calculated
-                                is_compressed,
-                            });
-                        }
-                    }
+                    let chunks = read_table(&mut *reader, data_size, sectors_end.unwrap_or(section_offset))?;
+                    chunk_table.extend(chunks);
+                    table_read = true;
                 }
                 SectionType::Hash => {
                     // Parse hash section
-                    let data_offset = section_offset + E01SectionDescriptor::SIZE as u64;
                     reader.seek(SeekFrom::Start(data_offset))?;
 
-                    let mut hash_data = [0u8; 20];
+                    let mut hash_data = [0u8; E01HashSection::SIZE];
                     reader.read_exact(&mut hash_data)?;
 
                     hash = Some(E01HashSection::parse(&hash_data)?);
@@ -210,28 +189,6 @@ impl E01Vault {
 
         let volume = volume.ok_or_else(|| Error::invalid_vault("E01 missing volume section"))?;
 
-        // Calculate chunk sizes from offsets
-        if !sectors_data.is_empty() && !chunk_table.is_empty() {
-            let base_offset = sectors_data[0].0;
-
-            for i in 0..chunk_table.len() {
-                // Adjust offset to be absolute
-                chunk_table[i].offset += base_offset;
-
-                // Calculate compressed size from next offset
-                let next_offset = if i + 1 < chunk_table.len() {
-                    chunk_table[i + 1].offset + base_offset - base_offset
-                } else {
-                    // Last chunk - use sectors section size
-                    let total_size: u64 = sectors_data.iter().map(|(_, s)| s).sum();
-                    base_offset + total_size
-                };
-
-                let current = chunk_table[i].offset;
-                chunk_table[i].compressed_size = (next_offset - current).min(u32::MAX as u64) as u32;
-            }
-        }
-
         let total_size = volume.media_size();
         let identifier = format!(
             "E01 {} {} sectors ({} bytes/sector)",
@@ -247,6 +204,7 @@ impl E01Vault {
             chunk_table,
             hash,
             cache: E01Cache::new(total_size),
+            read_ahead: None,
             identifier,
         })
     }
@@ -266,6 +224,34 @@ impl E01Vault {
         self.hash.as_ref().map(|h| h.md5_hex())
     }
 
+    /// Recompute the media MD5 and compare it with the hash section
+    ///
+    /// Reads and decompresses the whole image. Returns None if the image
+    /// has no hash section to compare against.
+    pub fn verify_integrity(&mut self) -> Result<Option<HashVerification>> {
+        self.verify_integrity_with_progress(None)
+    }
+
+    /// Verify as [`verify_integrity`](Self::verify_integrity) does,
+    /// reporting the bytes hashed to `progress`
+    pub fn verify_integrity_with_progress(
+        &mut self,
+        progress: Option<&dyn Progress>,
+    ) -> Result<Option<HashVerification>> {
+        let Some(expected) = self.md5_hash() else {
+            return Ok(None);
+        };
+
+        let mut context = md5::Context::new();
+        hash_media(self, progress, |data| context.consume(data))?;
+
+        Ok(Some(HashVerification {
+            algorithm: "MD5",
+            expected,
+            computed: to_hex(&context.compute().0),
+        }))
+    }
+
     /// Get the file header information
     pub fn file_header(&self) -> &E01FileHeader {
         &self.file_header
@@ -276,39 +262,27 @@ impl E01Vault {
         self.chunk_table.len()
     }
 
+    /// Enable or disable read-ahead decompression
+    ///
+    /// With read-ahead, sequential reads decompress the following chunks
+    /// on a worker pool. Passing `None` stops the workers.
+    pub fn set_read_ahead(&mut self, config: Option<ReadAheadConfig>) {
+        self.read_ahead = config.map(ReadAhead::new);
+    }
+
     /// Decompress a chunk
     fn decompress_chunk(&mut self, chunk_index: usize) -> Result<Vec<u8>> {
         if chunk_index >= self.chunk_table.len() {
             return Err(Error::invalid_vault("Chunk index out of range"));
         }
 
-        let chunk = &self.chunk_table[chunk_index];
-        let chunk_size = self.volume.chunk_size() as usize;
-
-        // Read compressed data
-        self.reader.seek(SeekFrom::Start(chunk.offset))?;
-        let mut compressed = vec![0u8; chunk.compressed_size as usize];
-        self.reader.read_exact(&mut compressed)?;
-
-        if chunk.is_compressed && !compressed.is_empty() {
-            // Decompress using zlib
-            let mut decoder = ZlibDecoder::new(Cursor::new(&compressed));
-            let mut decompressed = Vec::with_capacity(chunk_size);
-
-            match decoder.read_to_end(&mut decompressed) {
-                Ok(_) => Ok(decompressed),
-                Err(e) => {
-                    tracing::warn!(
-                        "E01 chunk decompression failed: {}. Returning zeros.",
-                        e
-                    );
-                    // Return zeros instead of corrupted compressed data
-                    Ok(vec![0u8; chunk_size])
-                }
+        match self.read_ahead.take() {
+            Some(mut read_ahead) => {
+                let chunk = read_ahead.fetch(self, chunk_index);
+                self.read_ahead = Some(read_ahead);
+                chunk
             }
-        } else {
-            // Not compressed
-            Ok(compressed)
+            None => self.decode_chunk(chunk_index),
         }
     }
 
@@ -357,6 +331,117 @@ impl Vault for E01Vault {
     }
 }
 
+impl ChunkSource for E01Vault {
+    fn chunk_count(&self) -> usize {
+        self.chunk_table.len()
+    }
+
+    fn chunk_size(&self) -> usize {
+        self.volume.chunk_size() as usize
+    }
+
+    fn read_stored_chunk(&mut self, index: usize) -> Result<Vec<u8>> {
+        let chunk = &self.chunk_table[index];
+        self.reader.seek(SeekFrom::Start(chunk.offset))?;
+        let mut stored = vec![0u8; chunk.compressed_size as usize];
+        self.reader.read_exact(&mut stored)?;
+        Ok(stored)
+    }
+
+    fn chunk_decoder(&self, index: usize) -> ChunkDecoder {
+        let is_compressed = self.chunk_table[index].is_compressed;
+        let chunk_size = self.volume.chunk_size() as usize;
+
+        Box::new(move |mut stored| {
+            if !is_compressed {
+                // Uncompressed chunks carry an Adler-32 of their data
+                if stored.len() >= 4 {
+                    let data_len = stored.len() - 4;
+                    let checksum = u32::from_le_bytes([
+                        stored[data_len], stored[data_len + 1], stored[data_len + 2], stored[data_len + 3],
+                    ]);
+                    stored.truncate(data_len);
+                    if adler32(&stored) != checksum {
+                        tracing::warn!("E01 chunk checksum mismatch");
+                    }
+                }
+                return Ok(stored);
+            }
+            if stored.is_empty() {
+                return Ok(stored);
+            }
+
+            // Decompress using zlib
+            let mut decoder = ZlibDecoder::new(Cursor::new(&stored));
+            let mut decompressed = Vec::with_capacity(chunk_size);
+
+            match decoder.read_to_end(&mut decompressed) {
+                Ok(_) => Ok(decompressed),
+                Err(e) => {
+                    tracing::warn!(
+                        "E01 chunk decompression failed: {}. Returning zeros.",
+                        e
+                    );
+                    // Return zeros instead of corrupted compressed data
+                    Ok(vec![0u8; chunk_size])
+                }
+            }
+        })
+    }
+}
+
+/// Read a table section body of `data_size` bytes at the reader's position
+///
+/// Each entry is a 31-bit offset from the header's base offset, with the
+/// top bit set for zlib-compressed chunks. A chunk runs to the next
+/// entry's offset, and the last one to `chunks_end`.
+fn read_table(reader: &mut dyn ReadSeek, data_size: u64, chunks_end: u64) -> Result<Vec<E01ChunkInfo>> {
+    let mut header_bytes = [0u8; E01TableHeader::SIZE];
+    reader.read_exact(&mut header_bytes)?;
+    let header = E01TableHeader::parse(&header_bytes)?;
+    if !header.is_valid(&header_bytes) {
+        tracing::warn!("E01 table header checksum mismatch");
+    }
+
+    let entries_size = header.entry_count as u64 * 4;
+    if E01TableHeader::SIZE as u64 + entries_size > data_size {
+        return Err(Error::truncated("E01 table entries exceed the section"));
+    }
+    let entries_size = validate_allocation_size(entries_size, max_allocation_size(), "E01 table")?;
+    let mut entries = vec![0u8; entries_size];
+    reader.read_exact(&mut entries)?;
+
+    // The entries' checksum follows them when the section has room for it
+    if E01TableHeader::SIZE as u64 + entries_size as u64 + 4 <= data_size {
+        let mut checksum = [0u8; 4];
+        reader.read_exact(&mut checksum)?;
+        if adler32(&entries) != u32::from_le_bytes(checksum) {
+            tracing::warn!("E01 table entries checksum mismatch");
+        }
+    }
+
+    let mut chunks: Vec<E01ChunkInfo> = entries
+        .chunks_exact(4)
+        .map(|entry| {
+            let entry = u32::from_le_bytes([entry[0], entry[1], entry[2], entry[3]]);
+            E01ChunkInfo {
+                offset: header.base_offset + (entry & !COMPRESSED_CHUNK) as u64,
+                compressed_size: 0,
+                is_compressed: entry & COMPRESSED_CHUNK != 0,
+            }
+        })
+        .collect();
+
+    // Calculate stored sizes from the following chunk's offset
+    for i in 0..chunks.len() {
+        let next_offset = chunks.get(i + 1).map_or(chunks_end, |next| next.offset);
+        let size = next_offset.saturating_sub(chunks[i].offset);
+        chunks[i].compressed_size = size.min(u32::MAX as u64) as u32;
+    }
+
+    Ok(chunks)
+}
+
 // Implement Read and Seek for E01Vault to support the Vault trait
 impl Read for E01Vault {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
@@ -407,9 +492,9 @@ mod tests {
 
         // File header (13 bytes)
         data.extend_from_slice(&EVF_SIGNATURE);
-        data.push(0x01); // padding
+        data.push(0x01); // fields start
         data.extend_from_slice(&1u16.to_le_bytes()); // segment 1
-        data.extend_from_slice(&13u16.to_le_bytes()); // fields start at 13
+        data.extend_from_slice(&0u16.to_le_bytes()); // fields end
 
         // Volume section descriptor (76 bytes) at offset 13
         let mut section_type = [0u8; 16];
@@ -475,6 +560,7 @@ mod tests {
     fn test_e01_compression_enum() {
         assert_eq!(E01Compression::from(0), E01Compression::None);
         assert_eq!(E01Compression::from(1), E01Compression::Deflate);
+        assert_eq!(E01Compression::from(2), E01Compression::Deflate);
         assert_eq!(E01Compression::from(99), E01Compression::Unknown(99));
     }
 
@@ -485,4 +571,155 @@ mod tests {
         assert_eq!(cache.total_size, 1024);
         assert!(cache.cached_chunk.is_none());
     }
+
+    /// Append a section descriptor whose data follows it directly
+    fn push_section(data: &mut Vec<u8>, name: &[u8], body: &[u8], last: bool) {
+        let start = data.len() as u64;
+        let size = E01SectionDescriptor::SIZE as u64 + body.len() as u64;
+        let mut section_type = [0u8; 16];
+        section_type[..name.len()].copy_from_slice(name);
+        data.extend_from_slice(&section_type);
+        data.extend_from_slice(&(if last { 0 } else { start + size }).to_le_bytes());
+        data.extend_from_slice(&size.to_le_bytes());
+        data.extend_from_slice(&[0u8; 44]); // padding + checksum
+        data.extend_from_slice(body);
+    }
+
+    /// Offset of the first chunk's stored data in a [`create_chunked_e01`] image
+    const FIRST_CHUNK_OFFSET: usize = 13 + 2 * E01SectionDescriptor::SIZE + 94;
+
+    /// Create an E01 of zlib-compressed 32 KiB chunks, with the media's MD5
+    /// and a table2 backup of the chunk table
+    fn create_chunked_e01(chunk_count: usize) -> (Vec<u8>, Vec<u8>) {
+        use flate2::{write::ZlibEncoder, Compression};
+        use std::io::Write;
+
+        let chunk_size = 64 * 512;
+        let media: Vec<u8> = (0..chunk_count * chunk_size)
+            .map(|i| ((i / chunk_size + 1) * (i % 4099) % 251) as u8)
+            .collect();
+
+        let mut sectors = Vec::new();
+        let mut entries = Vec::new();
+        for chunk in media.chunks(chunk_size) {
+            entries.extend_from_slice(&(COMPRESSED_CHUNK | sectors.len() as u32).to_le_bytes());
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(chunk).unwrap();
+            sectors.extend_from_slice(&encoder.finish().unwrap());
+        }
+
+        let mut table = vec![0u8; E01TableHeader::SIZE];
+        table[0..4].copy_from_slice(&(chunk_count as u32).to_le_bytes());
+        table[8..16].copy_from_slice(&(FIRST_CHUNK_OFFSET as u64).to_le_bytes());
+        let checksum = adler32(&table[..20]);
+        table[20..24].copy_from_slice(&checksum.to_le_bytes());
+        table.extend_from_slice(&entries);
+        table.extend_from_slice(&adler32(&entries).to_le_bytes());
+
+        let mut volume = vec![0u8; 94];
+        volume[0] = 0x01;
+        volume[4..8].copy_from_slice(&(chunk_count as u32).to_le_bytes());
+        volume[8..12].copy_from_slice(&64u32.to_le_bytes());
+        volume[12..16].copy_from_slice(&512u32.to_le_bytes());
+        volume[16..24].copy_from_slice(&(chunk_count as u64 * 64).to_le_bytes());
+        volume[52] = 1;
+
+        let mut data = Vec::new();
+        data.extend_from_slice(&EVF_SIGNATURE);
+        data.push(0x01);
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        push_section(&mut data, b"volume", &volume, false);
+        push_section(&mut data, b"sectors", &sectors, false);
+        push_section(&mut data, b"table", &table, false);
+        push_section(&mut data, b"table2", &table, false);
+
+        let mut hash = vec![0u8; 36];
+        hash[..16].copy_from_slice(&md5::compute(&media).0);
+        push_section(&mut data, b"hash", &hash, false);
+        push_section(&mut data, b"done", &[], true);
+
+        (data, media)
+    }
+
+    #[test]
+    fn test_e01_multi_chunk_read() {
+        let (data, media) = create_chunked_e01(3);
+        let mut vault = E01Vault::from_reader(Box::new(Cursor::new(data))).unwrap();
+        assert_eq!(vault.chunk_count(), 3);
+        assert_eq!(vault.length(), media.len() as u64);
+        assert_eq!(E01Compression::from(vault.volume().compression), E01Compression::Deflate);
+
+        let mut contents = Vec::new();
+        vault.read_to_end(&mut contents).unwrap();
+        assert_eq!(contents, media);
+    }
+
+    #[test]
+    fn test_e01_read_ahead_matches_single_threaded() {
+        let (data, media) = create_chunked_e01(10);
+
+        let mut single = E01Vault::from_reader(Box::new(Cursor::new(data.clone()))).unwrap();
+        let mut expected = Vec::new();
+        single.read_to_end(&mut expected).unwrap();
+        assert_eq!(expected, media);
+
+        let mut vault = E01Vault::from_reader(Box::new(Cursor::new(data))).unwrap();
+        vault.set_read_ahead(Some(ReadAheadConfig {
+            workers: 3,
+            chunks_ahead: 4,
+            max_buffer_bytes: 1024 * 1024,
+        }));
+
+        // Small sequential reads cross every chunk boundary
+        let mut contents = Vec::new();
+        let mut buf = [0u8; 5000];
+        loop {
+            let n = vault.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            contents.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(contents, expected);
+
+        // Seeks backwards and forwards restart the window at the new position
+        for offset in [2 * 32768 + 100, 9 * 32768 - 10, 32768, 5 * 32768 + 7] {
+            let mut window = vec![0u8; 40_000];
+            vault.seek(SeekFrom::Start(offset as u64)).unwrap();
+            let mut filled = 0;
+            while filled < window.len() {
+                let n = vault.read(&mut window[filled..]).unwrap();
+                if n == 0 {
+                    break;
+                }
+                filled += n;
+            }
+            assert_eq!(&window[..filled], &expected[offset..(offset + filled)]);
+            assert_eq!(filled, window.len().min(expected.len() - offset));
+        }
+    }
+
+    #[test]
+    fn test_e01_verify_integrity() {
+        let (data, media) = create_chunked_e01(3);
+        let mut vault = E01Vault::from_reader(Box::new(Cursor::new(data.clone()))).unwrap();
+        vault.seek(SeekFrom::Start(1000)).unwrap();
+
+        let verification = vault.verify_integrity().unwrap().unwrap();
+        assert_eq!(verification.algorithm, "MD5");
+        assert_eq!(verification.computed, to_hex(&md5::compute(&media).0));
+        assert!(verification.is_match());
+        assert_eq!(vault.stream_position().unwrap(), 1000);
+
+        // A damaged chunk no longer inflates to the hashed contents
+        let mut corrupted = data;
+        corrupted[FIRST_CHUNK_OFFSET + 10] ^= 0xFF;
+        let mut vault = E01Vault::from_reader(Box::new(Cursor::new(corrupted))).unwrap();
+        assert!(!vault.verify_integrity().unwrap().unwrap().is_match());
+
+        // Nothing to verify without a hash section
+        let mut vault = E01Vault::from_reader(Box::new(Cursor::new(create_minimal_e01()))).unwrap();
+        assert!(vault.verify_integrity().unwrap().is_none());
+    }
 }
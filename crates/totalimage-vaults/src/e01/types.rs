@@ -11,11 +11,16 @@ pub const EVF_SIGNATURE: [u8; 8] = [0x45, 0x56, 0x46, 0x09, 0x0D, 0x0A, 0xFF, 0x
 /// Legacy EWF signature (EnCase 1-6)
 pub const EWF_SIGNATURE: [u8; 8] = [0x45, 0x56, 0x46, 0x09, 0x0D, 0x0A, 0x00, 0x00];
 
+/// Table entry flag marking a zlib-compressed chunk
+pub const COMPRESSED_CHUNK: u32 = 0x8000_0000;
+
 /// Section type identifiers
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SectionType {
-    /// Header section with case info
+    /// Header section with case info (ASCII)
     Header,
+    /// Header2 section with case info (UTF-16)
+    Header2,
     /// Volume section with media info
     Volume,
     /// Disk section (alternative volume)
@@ -48,7 +53,7 @@ impl SectionType {
 
         match type_str {
             "header" => Self::Header,
-            "header2" => Self::Header,
+            "header2" => Self::Header2,
             "volume" => Self::Volume,
             "disk" => Self::Disk,
             "sectors" => Self::Sectors,
@@ -67,6 +72,7 @@ impl SectionType {
         let mut bytes = [0u8; 16];
         let s = match self {
             Self::Header => "header",
+            Self::Header2 => "header2",
             Self::Volume => "volume",
             Self::Disk => "disk",
             Self::Sectors => "sectors",
@@ -90,8 +96,8 @@ pub struct E01FileHeader {
     pub signature: [u8; 8],
     /// Segment number (1-based)
     pub segment_number: u16,
-    /// Fields start offset
-    pub fields_start: u16,
+    /// Fields end marker (0 in every known image)
+    pub fields_end: u16,
 }
 
 impl E01FileHeader {
@@ -101,7 +107,7 @@ impl E01FileHeader {
     /// Parse file header from bytes
     pub fn parse(data: &[u8]) -> Result<Self> {
         if data.len() < Self::SIZE {
-            return Err(Error::invalid_vault("E01 header too short"));
+            return Err(Error::truncated("E01 header too short"));
         }
 
         let mut signature = [0u8; 8];
@@ -111,17 +117,17 @@ impl E01FileHeader {
         if signature != EVF_SIGNATURE && signature != EWF_SIGNATURE {
             // Check for partial match (at least EVF prefix)
             if &signature[0..3] != b"EVF" {
-                return Err(Error::invalid_vault("Invalid E01 signature"));
+                return Err(Error::unrecognized_signature("Invalid E01 signature"));
             }
         }
 
         let segment_number = u16::from_le_bytes([data[9], data[10]]);
-        let fields_start = u16::from_le_bytes([data[11], data[12]]);
+        let fields_end = u16::from_le_bytes([data[11], data[12]]);
 
         Ok(Self {
             signature,
             segment_number,
-            fields_start,
+            fields_end,
         })
     }
 
@@ -153,7 +159,7 @@ impl E01SectionDescriptor {
     /// Parse section descriptor from bytes
     pub fn parse(data: &[u8]) -> Result<Self> {
         if data.len() < Self::SIZE {
-            return Err(Error::invalid_vault("E01 section descriptor too short"));
+            return Err(Error::truncated("E01 section descriptor too short"));
         }
 
         let mut type_bytes = [0u8; 16];
@@ -198,7 +204,7 @@ pub struct E01VolumeSection {
     pub bytes_per_sector: u32,
     /// Sector count
     pub sector_count: u64,
-    /// Compression method (0=none, 1=deflate)
+    /// Compression level (0=none, 1=fast, 2=best)
     pub compression: u8,
 }
 
@@ -206,7 +212,7 @@ impl E01VolumeSection {
     /// Parse volume section from bytes
     pub fn parse(data: &[u8]) -> Result<Self> {
         if data.len() < 94 {
-            return Err(Error::invalid_vault("E01 volume section too short"));
+            return Err(Error::truncated("E01 volume section too short"));
         }
 
         // Skip reserved bytes at start
@@ -220,8 +226,7 @@ impl E01VolumeSection {
             data[20], data[21], data[22], data[23],
         ]);
 
-        // Compression is at offset 88
-        let compression = if data.len() > 88 { data[88] } else { 1 };
+        let compression = data[52];
 
         Ok(Self {
             media_type,
@@ -253,26 +258,70 @@ pub struct E01TableEntry {
     pub size: u32,
 }
 
+/// E01 table section header (24 bytes), followed by the entries
+#[derive(Debug, Clone)]
+pub struct E01TableHeader {
+    /// Number of 4-byte entries that follow
+    pub entry_count: u32,
+    /// File offset the entries are relative to
+    pub base_offset: u64,
+    /// Adler-32 of the first 20 bytes
+    pub checksum: u32,
+}
+
+impl E01TableHeader {
+    /// Size of the table header
+    pub const SIZE: usize = 24;
+
+    /// Parse table header from bytes
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < Self::SIZE {
+            return Err(Error::truncated("E01 table header too short"));
+        }
+
+        let entry_count = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+        let base_offset = u64::from_le_bytes([
+            data[8], data[9], data[10], data[11],
+            data[12], data[13], data[14], data[15],
+        ]);
+        let checksum = u32::from_le_bytes([data[20], data[21], data[22], data[23]]);
+
+        Ok(Self {
+            entry_count,
+            base_offset,
+            checksum,
+        })
+    }
+
+    /// Check the header against its checksum
+    pub fn is_valid(&self, data: &[u8]) -> bool {
+        data.len() >= Self::SIZE && adler32(&data[..20]) == self.checksum
+    }
+}
+
 /// E01 hash section data
 #[derive(Debug, Clone)]
 pub struct E01HashSection {
     /// MD5 hash of uncompressed data
     pub md5_hash: [u8; 16],
-    /// Checksum (CRC32 or Adler-32)
+    /// Adler-32 of the first 32 bytes
     pub checksum: u32,
 }
 
 impl E01HashSection {
+    /// Size of the hash section body
+    pub const SIZE: usize = 36;
+
     /// Parse hash section from bytes
     pub fn parse(data: &[u8]) -> Result<Self> {
-        if data.len() < 20 {
-            return Err(Error::invalid_vault("E01 hash section too short"));
+        if data.len() < Self::SIZE {
+            return Err(Error::truncated("E01 hash section too short"));
         }
 
         let mut md5_hash = [0u8; 16];
         md5_hash.copy_from_slice(&data[0..16]);
 
-        let checksum = u32::from_le_bytes([data[16], data[17], data[18], data[19]]);
+        let checksum = u32::from_le_bytes([data[32], data[33], data[34], data[35]]);
 
         Ok(Self { md5_hash, checksum })
     }
@@ -301,7 +350,7 @@ impl From<u8> for E01Compression {
     fn from(value: u8) -> Self {
         match value {
             0 => Self::None,
-            1 => Self::Deflate,
+            1 | 2 => Self::Deflate,
             v => Self::Unknown(v),
         }
     }
@@ -350,6 +399,17 @@ impl std::fmt::Display for E01MediaType {
     }
 }
 
+/// Adler-32 checksum, as used for EWF section and chunk checksums
+pub fn adler32(data: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % MOD_ADLER;
+        b = (b + a) % MOD_ADLER;
+    }
+    (b << 16) | a
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -373,6 +433,7 @@ mod tests {
     fn test_section_type_roundtrip() {
         let types = [
             SectionType::Header,
+            SectionType::Header2,
             SectionType::Volume,
             SectionType::Sectors,
             SectionType::Table,
@@ -391,18 +452,40 @@ mod tests {
     fn test_e01_file_header_parse() {
         let mut data = vec![0u8; 13];
         data[0..8].copy_from_slice(&EVF_SIGNATURE);
+        data[8] = 1; // fields start
         data[9] = 1; // segment 1
-        data[10] = 0;
-        data[11] = 13; // fields start at offset 13
-        data[12] = 0;
 
         let header = E01FileHeader::parse(&data).unwrap();
         assert_eq!(header.signature, EVF_SIGNATURE);
         assert_eq!(header.segment_number, 1);
-        assert_eq!(header.fields_start, 13);
+        assert_eq!(header.fields_end, 0);
         assert!(header.is_evf());
     }
 
+    #[test]
+    fn test_adler32() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn test_table_header_parse() {
+        let mut data = vec![0u8; 24];
+        data[0..4].copy_from_slice(&3u32.to_le_bytes());
+        data[8..16].copy_from_slice(&0x1234u64.to_le_bytes());
+        let checksum = adler32(&data[..20]);
+        data[20..].copy_from_slice(&checksum.to_le_bytes());
+
+        let header = E01TableHeader::parse(&data).unwrap();
+        assert_eq!(header.entry_count, 3);
+        assert_eq!(header.base_offset, 0x1234);
+        assert!(header.is_valid(&data));
+
+        data[0] = 4;
+        assert!(!header.is_valid(&data));
+        assert!(matches!(E01TableHeader::parse(&data[..20]), Err(Error::Truncated(_))));
+    }
+
     #[test]
     fn test_e01_hash_hex() {
         let hash = E01HashSection {
@@ -437,4 +520,15 @@ mod tests {
         assert_eq!(E01MediaType::Removable.to_string(), "Removable");
         assert_eq!(E01MediaType::Optical.to_string(), "Optical");
     }
+
+    #[test]
+    fn test_parse_failure_variants() {
+        assert!(matches!(E01FileHeader::parse(b"EVF"), Err(Error::Truncated(_))));
+        assert!(matches!(
+            E01FileHeader::parse(b"conectix\0\0\0\0\0"),
+            Err(Error::UnrecognizedSignature(_))
+        ));
+        assert!(matches!(E01SectionDescriptor::parse(&[0u8; 16]), Err(Error::Truncated(_))));
+        assert!(matches!(E01VolumeSection::parse(&[0u8; 16]), Err(Error::Truncated(_))));
+    }
 }
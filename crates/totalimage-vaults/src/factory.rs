@@ -2,11 +2,16 @@
 //!
 //! This module provides automatic detection and opening of disk image formats.
 
-use crate::{Aff4Vault, E01Vault, RawVault, VaultConfig, VhdVault};
+use crate::dmg::KolyTrailer;
+use crate::vdi::VdiHeader;
+use crate::{
+    Aff4Vault, CompressedRawVault, CompressionFormat, DmgVault, E01Vault, RawVault, SplitRawVault, VaultConfig,
+    VdiVault, VhdVault,
+};
 use std::fs::File;
 use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
-use totalimage_core::{Result, Vault};
+use totalimage_core::{ReadSeek, Result, Vault};
 
 /// Detected vault type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,11 +24,39 @@ pub enum VaultType {
     E01,
     /// Advanced Forensic Format 4
     Aff4,
+    /// gzip or zstd compressed raw sector image
+    CompressedRaw,
+    /// Raw sector image split into numbered segments (.001, .002, ...)
+    SplitRaw,
+    /// Apple UDIF disk image (.dmg)
+    Dmg,
+    /// VirtualBox disk image (.vdi)
+    Vdi,
     /// Unknown format
     Unknown,
 }
 
 impl VaultType {
+    /// Every format [`open_vault`] can open
+    pub const ALL: [VaultType; 8] = [
+        VaultType::Raw,
+        VaultType::Vhd,
+        VaultType::E01,
+        VaultType::Aff4,
+        VaultType::CompressedRaw,
+        VaultType::SplitRaw,
+        VaultType::Dmg,
+        VaultType::Vdi,
+    ];
+
+    /// Can vaults of this type be opened for writing?
+    ///
+    /// Only raw images honour [`VaultConfig::writable`]; the rest are
+    /// read-only.
+    pub fn is_writable(&self) -> bool {
+        matches!(self, VaultType::Raw)
+    }
+
     /// Get a human-readable name for this vault type
     pub fn name(&self) -> &'static str {
         match self {
@@ -31,6 +64,10 @@ impl VaultType {
             VaultType::Vhd => "Microsoft VHD",
             VaultType::E01 => "EnCase E01",
             VaultType::Aff4 => "AFF4 Container",
+            VaultType::CompressedRaw => "Compressed Raw Image",
+            VaultType::SplitRaw => "Split Raw Image",
+            VaultType::Dmg => "Apple Disk Image",
+            VaultType::Vdi => "VirtualBox Disk Image",
             VaultType::Unknown => "Unknown",
         }
     }
@@ -45,77 +82,125 @@ const ZIP_MAGIC: &[u8] = &[0x50, 0x4b, 0x03, 0x04]; // AFF4 is ZIP-based
 ///
 /// Uses magic bytes for detection, falling back to file extension.
 pub fn detect_vault_type(path: &Path) -> Result<VaultType> {
-    // Try to read magic bytes
     let mut file = File::open(path)?;
+    if let Some(vault_type) = detect_signature(&mut file) {
+        return Ok(vault_type);
+    }
+
+    // Fall back to extension
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        match ext.to_lowercase().as_str() {
+            "vhd" | "vhdx" => return Ok(VaultType::Vhd),
+            "e01" | "ex01" | "s01" | "l01" => return Ok(VaultType::E01),
+            "aff4" | "af4" => return Ok(VaultType::Aff4),
+            "gz" | "zst" => return Ok(VaultType::CompressedRaw),
+            "000" | "001" => return Ok(VaultType::SplitRaw),
+            "dmg" => return Ok(VaultType::Dmg),
+            "vdi" => return Ok(VaultType::Vdi),
+            "img" | "ima" | "flp" | "vfd" | "dsk" | "iso" | "bin" | "raw" | "dd" => {
+                return Ok(VaultType::Raw)
+            }
+            _ => {}
+        }
+    }
+
+    // Default to raw if we can't determine the type
+    Ok(VaultType::Raw)
+}
+
+/// Detect the vault type of an image held in a stream, such as one in memory
+///
+/// Uses magic bytes only, as there is no file name to fall back on; an
+/// image with no recognized signature is raw. The stream is left at its
+/// start.
+///
+/// # Example
+///
+/// ```rust
+/// use totalimage_vaults::{detect_vault_type_from_reader, VaultType};
+/// use std::io::Cursor;
+///
+/// let mut image = Cursor::new(vec![0u8; 4096]);
+/// assert_eq!(detect_vault_type_from_reader(&mut image).unwrap(), VaultType::Raw);
+/// ```
+pub fn detect_vault_type_from_reader(reader: &mut dyn ReadSeek) -> Result<VaultType> {
+    let vault_type = detect_signature(reader).unwrap_or(VaultType::Raw);
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(vault_type)
+}
 
+/// Detect the vault type from the magic bytes of an image
+fn detect_signature<R: Read + Seek + ?Sized>(reader: &mut R) -> Option<VaultType> {
+    // Try to read magic bytes
+    reader.seek(SeekFrom::Start(0)).ok()?;
     let mut magic = [0u8; 16];
-    let bytes_read = file.read(&mut magic).unwrap_or(0);
+    let bytes_read = reader.read(&mut magic).unwrap_or(0);
 
     if bytes_read >= 8 {
         // Check VHD magic at start or end (footer can be at start for dynamic VHD)
         if &magic[0..8] == VHD_MAGIC {
-            return Ok(VaultType::Vhd);
+            return Some(VaultType::Vhd);
         }
 
         // Check E01 magic
         if &magic[0..8] == E01_MAGIC {
-            return Ok(VaultType::E01);
+            return Some(VaultType::E01);
         }
 
         // Check ZIP magic (potential AFF4)
         if &magic[0..4] == ZIP_MAGIC {
             // Further check for AFF4 by looking for container.description
-            if is_aff4_container(path) {
-                return Ok(VaultType::Aff4);
+            if is_aff4_container(&mut *reader) {
+                return Some(VaultType::Aff4);
             }
         }
     }
 
+    // Check the VDI signature after the image's text banner
+    let mut vdi = [0u8; VdiHeader::SIGNATURE_OFFSET + 4];
+    let vdi_read = reader.seek(SeekFrom::Start(0)).is_ok() && reader.read_exact(&mut vdi).is_ok();
+    if vdi_read && VdiHeader::has_signature(&vdi) {
+        return Some(VaultType::Vdi);
+    }
+
+    // Check gzip/zstd magic
+    if CompressionFormat::detect(&magic[..bytes_read]).is_some() {
+        return Some(VaultType::CompressedRaw);
+    }
+
     // Check VHD footer at end of file
-    if let Ok(metadata) = file.metadata() {
-        let file_size = metadata.len();
+    if let Ok(file_size) = reader.seek(SeekFrom::End(0)) {
         if file_size >= 512 {
-            if file.seek(SeekFrom::End(-512)).is_ok() {
+            if reader.seek(SeekFrom::End(-512)).is_ok() {
                 let mut footer = [0u8; 8];
-                if file.read_exact(&mut footer).is_ok() && &footer == VHD_MAGIC {
-                    return Ok(VaultType::Vhd);
+                if reader.read_exact(&mut footer).is_ok() && &footer == VHD_MAGIC {
+                    return Some(VaultType::Vhd);
                 }
             }
         }
     }
 
-    // Fall back to extension
-    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-        match ext.to_lowercase().as_str() {
-            "vhd" | "vhdx" => return Ok(VaultType::Vhd),
-            "e01" | "ex01" | "s01" | "l01" => return Ok(VaultType::E01),
-            "aff4" | "af4" => return Ok(VaultType::Aff4),
-            "img" | "ima" | "flp" | "vfd" | "dsk" | "iso" | "bin" | "raw" | "dd" => {
-                return Ok(VaultType::Raw)
-            }
-            _ => {}
-        }
+    // Check for a UDIF trailer, which would otherwise pass for a raw image
+    if matches!(KolyTrailer::read(reader), Ok(Some(_))) {
+        return Some(VaultType::Dmg);
     }
 
-    // Default to raw if we can't determine the type
-    Ok(VaultType::Raw)
+    None
 }
 
-/// Check if a ZIP file is an AFF4 container
-fn is_aff4_container(path: &Path) -> bool {
+/// Check if a ZIP archive is an AFF4 container
+fn is_aff4_container<R: Read + Seek>(reader: R) -> bool {
     // Try to open as ZIP and look for AFF4 markers
-    if let Ok(file) = File::open(path) {
-        if let Ok(mut archive) = zip::ZipArchive::new(file) {
-            // AFF4 containers have container.description or .turtle files
-            for i in 0..archive.len() {
-                if let Ok(entry) = archive.by_index(i) {
-                    let name = entry.name();
-                    if name.ends_with(".turtle")
-                        || name.ends_with(".description")
-                        || name.contains("container.description")
-                    {
-                        return true;
-                    }
+    if let Ok(mut archive) = zip::ZipArchive::new(reader) {
+        // AFF4 containers have container.description or .turtle files
+        for i in 0..archive.len() {
+            if let Ok(entry) = archive.by_index(i) {
+                let name = entry.name();
+                if name.ends_with(".turtle")
+                    || name.ends_with(".description")
+                    || name.contains("container.description")
+                {
+                    return true;
                 }
             }
         }
@@ -166,11 +251,29 @@ pub fn open_vault_as(path: &Path, vault_type: VaultType, config: VaultConfig) ->
             Ok(Box::new(vault))
         }
         VaultType::E01 => {
-            let vault = E01Vault::open(path)?;
+            let mut vault = E01Vault::open(path)?;
+            vault.set_read_ahead(config.read_ahead);
             Ok(Box::new(vault))
         }
         VaultType::Aff4 => {
-            let vault = Aff4Vault::open(path)?;
+            let mut vault = Aff4Vault::open(path)?;
+            vault.set_read_ahead(config.read_ahead);
+            Ok(Box::new(vault))
+        }
+        VaultType::CompressedRaw => {
+            let vault = CompressedRawVault::open(path, config)?;
+            Ok(Box::new(vault))
+        }
+        VaultType::SplitRaw => {
+            let vault = SplitRawVault::open(path, config)?;
+            Ok(Box::new(vault))
+        }
+        VaultType::Dmg => {
+            let vault = DmgVault::open(path, config)?;
+            Ok(Box::new(vault))
+        }
+        VaultType::Vdi => {
+            let vault = VdiVault::open(path, config)?;
             Ok(Box::new(vault))
         }
         VaultType::Unknown => {
@@ -188,6 +291,10 @@ pub fn supported_formats() -> Vec<(&'static str, &'static [&'static str])> {
         ("Microsoft VHD", &["vhd"]),
         ("EnCase E01", &["e01", "ex01", "s01", "l01"]),
         ("AFF4 Container", &["aff4", "af4"]),
+        ("Compressed Raw Image", &["gz", "zst"]),
+        ("Split Raw Image", &["000", "001"]),
+        ("Apple Disk Image", &["dmg"]),
+        ("VirtualBox Disk Image", &["vdi"]),
     ]
 }
 
@@ -242,6 +349,117 @@ mod tests {
         assert_eq!(result, VaultType::E01);
     }
 
+    #[test]
+    fn test_detect_compressed_by_magic() {
+        for magic in [&[0x1f, 0x8b, 0x08, 0x00][..], &[0x28, 0xb5, 0x2f, 0xfd][..]] {
+            let mut temp = NamedTempFile::with_suffix(".dat").unwrap();
+            temp.write_all(magic).unwrap();
+            temp.write_all(&[0u8; 508]).unwrap();
+            temp.flush().unwrap();
+
+            let result = detect_vault_type(temp.path()).unwrap();
+            assert_eq!(result, VaultType::CompressedRaw);
+        }
+    }
+
+    #[test]
+    fn test_open_gzipped_raw_image() {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+
+        let image: Vec<u8> = (0u8..=255).cycle().take(4096).collect();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&image).unwrap();
+
+        let mut temp = NamedTempFile::with_suffix(".img.gz").unwrap();
+        temp.write_all(&encoder.finish().unwrap()).unwrap();
+        temp.flush().unwrap();
+
+        let mut vault = open_vault(temp.path(), VaultConfig::default()).unwrap();
+        assert_eq!(vault.identify(), "Gzip-compressed raw image");
+        assert_eq!(vault.length(), 4096);
+
+        let mut buf = [0u8; 4];
+        vault.content().seek(SeekFrom::Start(1000)).unwrap();
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [232, 233, 234, 235]);
+    }
+
+    #[test]
+    fn test_open_split_raw_image() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("disk.001"), [1u8; 512]).unwrap();
+        std::fs::write(dir.path().join("disk.002"), [2u8; 512]).unwrap();
+
+        let first = dir.path().join("disk.001");
+        assert_eq!(detect_vault_type(&first).unwrap(), VaultType::SplitRaw);
+
+        let mut vault = open_vault(&first, VaultConfig::default()).unwrap();
+        assert_eq!(vault.length(), 1024);
+
+        let mut buf = [0u8; 2];
+        vault.content().seek(SeekFrom::Start(511)).unwrap();
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [1, 2]);
+    }
+
+    #[test]
+    fn test_detect_dmg_by_trailer() {
+        use crate::dmg::tests::koly_trailer;
+
+        let mut temp = NamedTempFile::with_suffix(".img").unwrap();
+        temp.write_all(&[0u8; 1000]).unwrap();
+        temp.write_all(&koly_trailer(0, 1000, 8)).unwrap();
+        temp.flush().unwrap();
+
+        assert_eq!(detect_vault_type(temp.path()).unwrap(), VaultType::Dmg);
+
+        // A compressed DMG is refused with an explanation, not read as raw
+        let err = open_vault(temp.path(), VaultConfig::default()).err().unwrap();
+        assert!(err.to_string().contains("UDIF-wrapped DMG detected"), "{}", err);
+    }
+
+    #[test]
+    fn test_open_vdi_by_signature() {
+        use crate::vdi::tests::build_vdi;
+
+        let mut temp = NamedTempFile::with_suffix(".img").unwrap();
+        temp.write_all(&build_vdi(1, 8192, 2, &[(1, &[0x5A; 16])])).unwrap();
+        temp.flush().unwrap();
+
+        assert_eq!(detect_vault_type(temp.path()).unwrap(), VaultType::Vdi);
+        let mut vault = open_vault(temp.path(), VaultConfig::default()).unwrap();
+        assert_eq!(vault.length(), 8192);
+
+        let mut buf = [0u8; 2];
+        vault.content().seek(SeekFrom::Start(4095)).unwrap();
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [0, 0x5A]);
+    }
+
+    #[test]
+    fn test_detect_from_reader() {
+        use std::io::Cursor;
+
+        let mut image = Cursor::new(vec![0u8; 4096]);
+        image.set_position(100);
+        assert_eq!(detect_vault_type_from_reader(&mut image).unwrap(), VaultType::Raw);
+        assert_eq!(image.position(), 0);
+
+        let mut e01 = vec![0u8; 4096];
+        e01[..8].copy_from_slice(E01_MAGIC);
+        assert_eq!(detect_vault_type_from_reader(&mut Cursor::new(e01)).unwrap(), VaultType::E01);
+
+        // Fixed VHDs end in their footer
+        let mut vhd = vec![0u8; 4096];
+        vhd[4096 - 512..4096 - 504].copy_from_slice(VHD_MAGIC);
+        assert_eq!(detect_vault_type_from_reader(&mut Cursor::new(vhd)).unwrap(), VaultType::Vhd);
+
+        let mut dmg = vec![0u8; 2048];
+        dmg.extend_from_slice(&crate::dmg::tests::koly_trailer(0, 2048, 4));
+        assert_eq!(detect_vault_type_from_reader(&mut Cursor::new(dmg)).unwrap(), VaultType::Dmg);
+    }
+
     #[test]
     fn test_vault_type_name() {
         assert_eq!(VaultType::Raw.name(), "Raw Sector Image");
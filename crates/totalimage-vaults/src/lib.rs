@@ -4,9 +4,13 @@
 //!
 //! This crate provides implementations of various disk image container formats:
 //! - **RawVault**: Plain sector images (.img, .ima, .flp, .vfd, .dsk, .iso)
+//! - **SplitRawVault**: Raw images split into numbered segments (.001, .002, ...)
+//! - **CompressedRawVault**: gzip or zstd compressed raw images (.img.gz, .img.zst)
 //! - **VhdVault**: Microsoft VHD format (Fixed and Dynamic)
 //! - **E01Vault**: EnCase forensic format
 //! - **Aff4Vault**: Advanced Forensic Format 4
+//! - **DmgVault**: Apple UDIF disk images, uncompressed only
+//! - **VdiVault**: VirtualBox disk images (Dynamic and Fixed)
 //!
 //! ## Example
 //!
@@ -23,13 +27,27 @@
 //! ```
 
 pub mod aff4;
+pub mod compressed;
+pub mod dmg;
 pub mod e01;
 pub mod factory;
 pub mod raw;
+pub mod read_ahead;
+pub mod split;
+pub mod vdi;
+pub mod verify;
 pub mod vhd;
 
 pub use aff4::Aff4Vault;
+pub use compressed::{CompressedRawVault, CompressionFormat};
+pub use dmg::DmgVault;
 pub use e01::E01Vault;
-pub use factory::{detect_vault_type, open_vault, open_vault_as, supported_formats, VaultType};
-pub use raw::{RawVault, VaultConfig};
-pub use vhd::{VhdChainVault, VhdVault};
+pub use factory::{
+    detect_vault_type, detect_vault_type_from_reader, open_vault, open_vault_as, supported_formats, VaultType,
+};
+pub use raw::{RawAccess, RawVault, VaultConfig};
+pub use read_ahead::ReadAheadConfig;
+pub use split::SplitRawVault;
+pub use vdi::VdiVault;
+pub use verify::HashVerification;
+pub use vhd::{ParentCheck, ParentVerification, VhdChainVault, VhdVault};
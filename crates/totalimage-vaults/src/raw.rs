@@ -3,25 +3,77 @@
 //! This module implements the simplest vault type: a raw sector image with no
 //! container metadata. Common file extensions: .img, .ima, .flp, .vfd, .dsk, .iso
 
-use std::fs::File;
-use std::io::{Read, Seek};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
 use std::path::Path;
-use totalimage_core::{Result, Vault, ReadSeek};
+use totalimage_core::{Error, Result, Vault, ReadSeek, ReadSeekExt, ReadWriteSeek};
 use totalimage_pipeline::MmapPipeline;
 
+use crate::read_ahead::ReadAheadConfig;
+use crate::vhd::ParentCheck;
+
 /// Configuration for opening a vault
 #[derive(Debug, Clone)]
 pub struct VaultConfig {
     /// Use memory mapping for direct action (high performance)
     pub use_mmap: bool,
+    /// Largest image to memory-map, in bytes
+    ///
+    /// Larger images, and any whose mapping fails, are read through the
+    /// file instead. `None` maps any image the address space can hold.
+    pub mmap_max_size: Option<u64>,
+    /// Open the image read-write so it can be edited in place
+    ///
+    /// Memory mappings are read-only, so this takes precedence over
+    /// `use_mmap` for vaults that support writing.
+    pub writable: bool,
+    /// Decompress upcoming chunks on worker threads during sequential reads
+    ///
+    /// Applies to chunked compressed containers (E01 and AFF4); off by default.
+    pub read_ahead: Option<ReadAheadConfig>,
+    /// How differencing VHD parents are checked when a chain is opened
+    pub vhd_parent_check: ParentCheck,
 }
 
 impl Default for VaultConfig {
     fn default() -> Self {
-        Self { use_mmap: true }
+        Self {
+            use_mmap: true,
+            mmap_max_size: None,
+            writable: false,
+            read_ahead: None,
+            vhd_parent_check: ParentCheck::default(),
+        }
     }
 }
 
+/// Storage behind a raw vault
+enum RawBacking {
+    /// Memory-mapped image file
+    Mapped(MmapPipeline),
+    /// Read-only stream (file or caller-supplied stream)
+    ReadOnly(Box<dyn ReadSeek>),
+    /// File opened read-write
+    File(File),
+    /// In-memory image
+    Memory(Cursor<Vec<u8>>),
+}
+
+/// How a raw vault reads its image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawAccess {
+    /// Memory-mapped file
+    MemoryMapped,
+    /// Reads through the file
+    File,
+    /// Reads and writes through the file
+    ReadWriteFile,
+    /// Caller-supplied stream
+    Stream,
+    /// In-memory image
+    Memory,
+}
+
 /// Raw vault - a simple passthrough to the underlying file
 ///
 /// This is the most common and simplest vault type. It provides direct access
@@ -39,8 +91,11 @@ impl Default for VaultConfig {
 /// println!("Size: {} bytes", vault.length());
 /// ```
 pub struct RawVault {
-    pipeline: Box<dyn ReadSeek>,
+    pipeline: RawBacking,
+    access: RawAccess,
     length: u64,
+    /// Image file, kept for hole queries when the vault was opened from a path
+    file: Option<File>,
 }
 
 impl RawVault {
@@ -53,20 +108,45 @@ impl RawVault {
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be opened or accessed
+    /// Returns an error if the path is neither a regular file nor, on Unix,
+    /// a block device, if the file cannot be opened or accessed, or if
+    /// `config.writable` is set and the file cannot be opened for writing
     pub fn open(path: &Path, config: VaultConfig) -> Result<Self> {
-        let file = File::open(path)?;
-        let length = file.metadata()?.len();
+        let device = check_image_path(path)?;
+
+        if config.writable {
+            let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+            let length = image_length(&mut file, device)?;
+            return Ok(Self {
+                file: Some(file.try_clone()?),
+                pipeline: RawBacking::File(file),
+                access: RawAccess::ReadWriteFile,
+                length,
+            });
+        }
 
-        let pipeline: Box<dyn ReadSeek> = if config.use_mmap {
-            // Direct action: memory-mapped file
-            Box::new(MmapPipeline::from_file(&file)?)
+        let mut file = File::open(path)?;
+        let length = image_length(&mut file, device)?;
+
+        // A device's metadata has no length to map
+        let mmap = if config.use_mmap && !device {
+            map_image(&file, length, config.mmap_max_size)
         } else {
+            None
+        };
+        let (pipeline, access) = match mmap {
+            // Direct action: memory-mapped file
+            Some(mmap) => (RawBacking::Mapped(mmap), RawAccess::MemoryMapped),
             // Standard file stream
-            Box::new(file)
+            None => (RawBacking::ReadOnly(Box::new(file.try_clone()?)), RawAccess::File),
         };
 
-        Ok(Self { pipeline, length })
+        Ok(Self {
+            pipeline,
+            access,
+            length,
+            file: Some(file),
+        })
     }
 
     /// Create a new raw vault from any readable and seekable stream
@@ -77,14 +157,55 @@ impl RawVault {
     /// * `length` - The length of the stream in bytes
     pub fn from_stream<R: Read + Seek + Send + Sync + 'static>(stream: R, length: u64) -> Self {
         Self {
-            pipeline: Box::new(stream),
+            pipeline: RawBacking::ReadOnly(Box::new(stream)),
+            access: RawAccess::Stream,
             length,
+            file: None,
         }
     }
 
+    /// Create a raw vault over an image already in memory
+    ///
+    /// The vault is writable through [`Vault::content_mut`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use totalimage_vaults::RawVault;
+    /// use totalimage_core::Vault;
+    ///
+    /// let vault = RawVault::from_bytes(vec![0u8; 4096]);
+    /// assert_eq!(vault.length(), 4096);
+    /// ```
+    pub fn from_bytes(data: Vec<u8>) -> Self {
+        Self {
+            length: data.len() as u64,
+            pipeline: RawBacking::Memory(Cursor::new(data)),
+            access: RawAccess::Memory,
+            file: None,
+        }
+    }
+
+    /// Create a raw vault from a boxed stream, sizing it by seeking to its end
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the stream can't be seeked
+    pub fn from_reader(mut reader: Box<dyn ReadSeek>) -> Result<Self> {
+        let length = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(0))?;
+        Ok(Self {
+            pipeline: RawBacking::ReadOnly(reader),
+            access: RawAccess::Stream,
+            length,
+            file: None,
+        })
+    }
+
     /// Manufacture a new blank raw vault (for image creation)
     ///
-    /// Creates a new in-memory raw vault filled with zeros.
+    /// Creates a new in-memory raw vault filled with zeros. The vault is
+    /// writable through [`Vault::content_mut`].
     ///
     /// # Arguments
     ///
@@ -101,18 +222,214 @@ impl RawVault {
     /// assert_eq!(vault.length(), 1_474_560);
     /// ```
     pub fn manufacture(size: u64) -> Self {
-        use std::io::Cursor;
+        Self::from_bytes(vec![0u8; size as usize])
+    }
 
-        let buffer = vec![0u8; size as usize];
-        let cursor = Cursor::new(buffer);
+    /// Get how the image is read
+    ///
+    /// A vault asked to memory-map its image reports
+    /// [`RawAccess::File`] if the image was too large to map.
+    pub fn access(&self) -> RawAccess {
+        self.access
+    }
 
-        Self {
-            pipeline: Box::new(cursor),
-            length: size,
+    /// Find the byte ranges of the image that are entirely zero
+    ///
+    /// Holes in a sparse image file are found with `SEEK_DATA`/`SEEK_HOLE`
+    /// where the platform supports them, without reading them. Everything
+    /// else is read in `block_size` blocks, aligned to the start of the
+    /// image, and all-zero blocks are reported. Returns `(offset, length)`
+    /// pairs in image order with adjacent ranges merged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `block_size` is zero or the image can't be read.
+    pub fn sparse_map(&mut self, block_size: u64) -> Result<Vec<(u64, u64)>> {
+        if block_size == 0 {
+            return Err(Error::InvalidOperation("Sparse map block size must be non-zero".to_string()));
+        }
+
+        let length = self.length;
+        let data = match &mut self.file {
+            Some(file) => data_extents(file, length)?,
+            None => vec![(0, length)],
+        };
+
+        let stream = self.content();
+        let position = stream.stream_position()?;
+        let mut zeros = Vec::new();
+        let mut buf = vec![0u8; block_size as usize];
+        let mut scanned = 0;
+
+        for (start, end) in data {
+            push_range(&mut zeros, scanned, start);
+
+            stream.seek(SeekFrom::Start(start))?;
+            let mut offset = start;
+            while offset < end {
+                let block_end = (offset + 1).next_multiple_of(block_size).min(end);
+                let block = &mut buf[..(block_end - offset) as usize];
+                stream.read_exact(block)?;
+                if block.iter().all(|&b| b == 0) {
+                    push_range(&mut zeros, offset, block_end);
+                }
+                offset = block_end;
+            }
+            scanned = end;
+        }
+        push_range(&mut zeros, scanned, length);
+
+        stream.seek(SeekFrom::Start(position))?;
+        Ok(zeros)
+    }
+}
+
+/// Check that `path` is something a raw image can be read from
+///
+/// Accepts regular files and, on Unix, block devices such as `/dev/sdb`, so
+/// physical disks can be read directly. Returns whether it is a device.
+fn check_image_path(path: &Path) -> Result<bool> {
+    let file_type = std::fs::metadata(path)?.file_type();
+    if file_type.is_file() {
+        return Ok(false);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        if file_type.is_block_device() {
+            return Ok(true);
+        }
+    }
+
+    let kind = if file_type.is_dir() {
+        "a directory"
+    } else {
+        "neither a regular file nor a block device"
+    };
+    Err(Error::invalid_vault(format!(
+        "Cannot open {} as a raw image: it is {}",
+        path.display(),
+        kind
+    )))
+}
+
+/// Memory-map an image, or return `None` to read it through the file
+///
+/// Images over `max_size`, or too large for the address space, aren't
+/// mapped. A failed mapping is logged rather than returned, since the
+/// file can still be read.
+fn map_image(file: &File, length: u64, max_size: Option<u64>) -> Option<MmapPipeline> {
+    let limit = max_size.unwrap_or(usize::MAX as u64).min(usize::MAX as u64);
+    if length > limit {
+        tracing::warn!(
+            "Image is {} bytes, over the {}-byte memory-map limit; reading it through the file",
+            length,
+            limit
+        );
+        return None;
+    }
+
+    match MmapPipeline::from_file(file) {
+        Ok(mmap) => Some(mmap),
+        Err(e) => {
+            tracing::warn!("Cannot memory-map image ({}); reading it through the file", e);
+            None
         }
     }
 }
 
+/// Get the length of an opened image
+///
+/// Block devices report a length of 0 in their metadata, so a device is
+/// sized by seeking to its end instead.
+fn image_length(file: &mut File, device: bool) -> Result<u64> {
+    if !device {
+        return Ok(file.metadata()?.len());
+    }
+
+    let length = file.seek(SeekFrom::End(0))?;
+    file.seek(SeekFrom::Start(0))?;
+    Ok(length)
+}
+
+/// Append `start..end` to a sorted range list, merging it with the last
+/// range when they touch
+fn push_range(ranges: &mut Vec<(u64, u64)>, start: u64, end: u64) {
+    if start >= end {
+        return;
+    }
+    match ranges.last_mut() {
+        Some((offset, length)) if *offset + *length == start => *length += end - start,
+        _ => ranges.push((start, end - start)),
+    }
+}
+
+/// Find the allocated `(start, end)` ranges of the first `length` bytes of
+/// a file
+///
+/// Uses `SEEK_DATA`/`SEEK_HOLE`. If the file system can't answer, the whole
+/// file is treated as allocated. The file's position is restored afterwards.
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd", target_os = "macos"))]
+fn data_extents(file: &mut File, length: u64) -> io::Result<Vec<(u64, u64)>> {
+    use std::os::unix::io::AsRawFd;
+
+    let fd = file.as_raw_fd();
+    // Returns None when there is no data (or hole) at or after `offset`
+    let seek = |offset: u64, whence: libc::c_int| -> io::Result<Option<u64>> {
+        // SAFETY: lseek only moves the descriptor's position, which is
+        // restored before returning
+        let result = unsafe { libc::lseek(fd, offset as libc::off_t, whence) };
+        if result >= 0 {
+            Ok(Some(result as u64))
+        } else {
+            let err = io::Error::last_os_error();
+            match err.raw_os_error() {
+                Some(libc::ENXIO) => Ok(None),
+                _ => Err(err),
+            }
+        }
+    };
+
+    let position = file.stream_position()?;
+    let mut extents = Vec::new();
+    let mut offset = 0;
+    let result = loop {
+        if offset >= length {
+            break Ok(());
+        }
+        let start = match seek(offset, libc::SEEK_DATA) {
+            Ok(Some(start)) if start < length => start,
+            Ok(_) => break Ok(()),
+            Err(e) => break Err(e),
+        };
+        let end = match seek(start, libc::SEEK_HOLE) {
+            Ok(end) => end.unwrap_or(length).min(length),
+            Err(e) => break Err(e),
+        };
+        extents.push((start, end));
+        offset = end;
+    };
+    file.seek(SeekFrom::Start(position))?;
+
+    match result {
+        Ok(()) => Ok(extents),
+        Err(e) => {
+            tracing::debug!("Hole detection unavailable, reading the whole image: {}", e);
+            Ok(vec![(0, length)])
+        }
+    }
+}
+
+/// Find the allocated `(start, end)` ranges of the first `length` bytes of
+/// a file
+///
+/// Without hole detection the whole file is treated as allocated.
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "freebsd", target_os = "macos")))]
+fn data_extents(_file: &mut File, length: u64) -> io::Result<Vec<(u64, u64)>> {
+    Ok(vec![(0, length)])
+}
+
 impl Vault for RawVault {
     fn identify(&self) -> &str {
         "Raw sector image"
@@ -123,7 +440,36 @@ impl Vault for RawVault {
     }
 
     fn content(&mut self) -> &mut dyn ReadSeek {
-        &mut *self.pipeline
+        match &mut self.pipeline {
+            RawBacking::Mapped(mmap) => mmap,
+            RawBacking::ReadOnly(stream) => &mut **stream,
+            RawBacking::File(file) => file,
+            RawBacking::Memory(cursor) => cursor,
+        }
+    }
+
+    /// Mapped and in-memory images are copied from directly, without moving
+    /// the content's position
+    fn read_exact_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<()> {
+        let bytes: &[u8] = match &self.pipeline {
+            RawBacking::Mapped(mmap) => mmap.as_full_slice(),
+            RawBacking::Memory(cursor) => cursor.get_ref(),
+            RawBacking::ReadOnly(_) | RawBacking::File(_) => return self.content().read_exact_at(offset, buf),
+        };
+        let start = usize::try_from(offset).ok().filter(|&start| start <= bytes.len());
+        let source = start
+            .and_then(|start| bytes[start..].get(..buf.len()))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "read past end of image"))?;
+        buf.copy_from_slice(source);
+        Ok(())
+    }
+
+    fn content_mut(&mut self) -> Option<&mut dyn ReadWriteSeek> {
+        match &mut self.pipeline {
+            RawBacking::Mapped(_) | RawBacking::ReadOnly(_) => None,
+            RawBacking::File(file) => Some(file),
+            RawBacking::Memory(cursor) => Some(cursor),
+        }
     }
 }
 
@@ -194,6 +540,21 @@ mod tests {
         assert_eq!(vault.length(), 100);
     }
 
+    #[test]
+    fn test_raw_vault_rejects_directory() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let err = RawVault::open(dir.path(), VaultConfig::default()).err().unwrap();
+        assert!(matches!(err, Error::InvalidVault(_)));
+        assert!(err.to_string().contains("is a directory"), "{}", err);
+
+        #[cfg(unix)]
+        {
+            let err = RawVault::open(Path::new("/dev/null"), VaultConfig::default()).err().unwrap();
+            assert!(err.to_string().contains("neither a regular file nor a block device"), "{}", err);
+        }
+    }
+
     #[test]
     fn test_raw_vault_open_with_mmap() {
         let mut tmpfile = NamedTempFile::new().unwrap();
@@ -201,7 +562,7 @@ mod tests {
         tmpfile.write_all(&data).unwrap();
         tmpfile.flush().unwrap();
 
-        let config = VaultConfig { use_mmap: true };
+        let config = VaultConfig { use_mmap: true, ..Default::default() };
         let mut vault = RawVault::open(tmpfile.path(), config).unwrap();
 
         let mut buf = [0u8; 10];
@@ -216,11 +577,233 @@ mod tests {
         tmpfile.write_all(&data).unwrap();
         tmpfile.flush().unwrap();
 
-        let config = VaultConfig { use_mmap: false };
+        let config = VaultConfig { use_mmap: false, ..Default::default() };
         let mut vault = RawVault::open(tmpfile.path(), config).unwrap();
 
         let mut buf = [0u8; 10];
         vault.content().read(&mut buf).unwrap();
         assert_eq!(&buf, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
     }
+
+    #[test]
+    fn test_raw_vault_mmap_fallback() {
+        let mut tmpfile = NamedTempFile::new().unwrap();
+        let data: Vec<u8> = (0u8..=255).cycle().take(5000).collect();
+        tmpfile.write_all(&data).unwrap();
+        tmpfile.flush().unwrap();
+
+        let vault = RawVault::open(tmpfile.path(), VaultConfig::default()).unwrap();
+        assert_eq!(vault.access(), RawAccess::MemoryMapped);
+
+        // Over the limit, the image is read through the file
+        let config = VaultConfig { mmap_max_size: Some(0), ..Default::default() };
+        let mut vault = RawVault::open(tmpfile.path(), config).unwrap();
+        assert_eq!(vault.access(), RawAccess::File);
+
+        let mut buf = vec![0u8; 1000];
+        vault.content().seek(SeekFrom::Start(4000)).unwrap();
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(buf, &data[4000..]);
+
+        let config = VaultConfig { mmap_max_size: Some(5000), ..Default::default() };
+        let vault = RawVault::open(tmpfile.path(), config).unwrap();
+        assert_eq!(vault.access(), RawAccess::MemoryMapped);
+    }
+
+    #[test]
+    fn test_raw_vault_read_only_by_default() {
+        let mut tmpfile = NamedTempFile::new().unwrap();
+        tmpfile.write_all(&[0u8; 100]).unwrap();
+        tmpfile.flush().unwrap();
+
+        for use_mmap in [true, false] {
+            let config = VaultConfig { use_mmap, ..Default::default() };
+            let mut vault = RawVault::open(tmpfile.path(), config).unwrap();
+            assert!(vault.content_mut().is_none());
+        }
+
+        let mut vault = RawVault::from_stream(Cursor::new(vec![0u8; 10]), 10);
+        assert!(vault.content_mut().is_none());
+    }
+
+    #[test]
+    fn test_raw_vault_writable() {
+        use std::io::SeekFrom;
+
+        let mut tmpfile = NamedTempFile::new().unwrap();
+        tmpfile.write_all(&[0u8; 100]).unwrap();
+        tmpfile.flush().unwrap();
+
+        // Writable takes precedence over mmap
+        let config = VaultConfig { use_mmap: true, writable: true, ..Default::default() };
+        let mut vault = RawVault::open(tmpfile.path(), config).unwrap();
+
+        let content = vault.content_mut().unwrap();
+        content.seek(SeekFrom::Start(42)).unwrap();
+        content.write_all(&[0xAB]).unwrap();
+        content.flush().unwrap();
+
+        let mut buf = [0u8; 1];
+        vault.content().seek(SeekFrom::Start(42)).unwrap();
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [0xAB]);
+        drop(vault);
+
+        // The change reached the file
+        assert_eq!(std::fs::read(tmpfile.path()).unwrap()[42], 0xAB);
+    }
+
+    #[test]
+    fn test_read_exact_at() {
+        use std::io::SeekFrom;
+
+        let data: Vec<u8> = (0..5000u32).map(|i| (i % 251) as u8).collect();
+        let mut tmpfile = NamedTempFile::new().unwrap();
+        tmpfile.write_all(&data).unwrap();
+        tmpfile.flush().unwrap();
+
+        let mut vaults = vec![
+            RawVault::open(tmpfile.path(), VaultConfig { use_mmap: true, ..Default::default() }).unwrap(),
+            RawVault::open(tmpfile.path(), VaultConfig { use_mmap: false, ..Default::default() }).unwrap(),
+            RawVault::from_stream(Cursor::new(data.clone()), 5000),
+            RawVault::manufacture(5000),
+        ];
+        vaults[3].content_mut().unwrap().write_all(&data).unwrap();
+        assert_eq!(vaults[0].access(), RawAccess::MemoryMapped);
+
+        for vault in &mut vaults {
+            for (offset, len) in [(0, 512), (4000, 1000), (1234, 1), (5000, 0)] {
+                let mut positional = vec![0u8; len];
+                vault.read_exact_at(offset, &mut positional).unwrap();
+
+                let mut manual = vec![0u8; len];
+                vault.content().seek(SeekFrom::Start(offset)).unwrap();
+                vault.content().read_exact(&mut manual).unwrap();
+
+                assert_eq!(positional, manual, "{:?} at {}", vault.access(), offset);
+                assert_eq!(positional, data[offset as usize..offset as usize + len]);
+            }
+
+            let mut buf = [0u8; 16];
+            assert!(vault.read_exact_at(4990, &mut buf).is_err());
+            assert!(vault.read_exact_at(u64::MAX, &mut buf).is_err());
+        }
+    }
+
+    #[test]
+    fn test_read_sectors() {
+        let data: Vec<u8> = (0..5000u32).map(|i| (i % 251) as u8).collect();
+        let mut vault = RawVault::from_bytes(data.clone());
+
+        let mut sector = [0u8; 512];
+        vault.read_sector(0, 512, &mut sector).unwrap();
+        assert_eq!(sector, data[..512]);
+        vault.read_sector(8, 512, &mut sector).unwrap();
+        assert_eq!(sector, data[4096..4608]);
+        assert_eq!(vault.read_sectors(1, 8, 512).unwrap(), data[512..4608]);
+        assert!(vault.read_sectors(9, 0, 512).unwrap().is_empty());
+
+        // Sector 9 is only partly inside the 5000-byte vault
+        let err = vault.read_sector(9, 512, &mut sector).unwrap_err();
+        assert!(err.to_string().contains("beyond the end"), "{}", err);
+        assert!(vault.read_sectors(8, 2, 512).is_err());
+        assert!(vault.read_sector(u64::MAX, 512, &mut sector).is_err());
+        assert!(vault.read_sectors(0, u64::MAX, 512).is_err());
+
+        // Unsupported sector size, or a buffer of the wrong size
+        assert!(vault.read_sectors(0, 1, 500).is_err());
+        assert!(vault.read_sector(0, 4096, &mut sector).is_err());
+    }
+
+    #[test]
+    fn test_raw_vault_through_vault_reader() {
+        use totalimage_pipeline::VaultReader;
+
+        let data: Vec<u8> = (0..200).collect();
+        let vault = RawVault::from_stream(Cursor::new(data), 200);
+        let mut reader = VaultReader::new(Box::new(vault));
+        assert_eq!(reader.len(), 200);
+
+        let mut buf = [0u8; 4];
+        reader.seek(std::io::SeekFrom::Start(100)).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [100, 101, 102, 103]);
+
+        assert_eq!(reader.seek(std::io::SeekFrom::Current(-2)).unwrap(), 102);
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [102, 103, 104, 105]);
+
+        assert_eq!(reader.into_inner().identify(), "Raw sector image");
+    }
+
+    #[test]
+    fn test_sparse_map() {
+        use std::io::SeekFrom;
+
+        // Data, a written zero block, a 248 KiB hole, then data again
+        let mut tmpfile = NamedTempFile::new().unwrap();
+        tmpfile.write_all(&[0xAA; 4096]).unwrap();
+        tmpfile.write_all(&[0u8; 4096]).unwrap();
+        tmpfile.seek(SeekFrom::Start(256 * 1024)).unwrap();
+        tmpfile.write_all(&[0xBB; 4096]).unwrap();
+        tmpfile.write_all(&[0u8; 100]).unwrap();
+        tmpfile.flush().unwrap();
+        let expected = vec![(4096, 256 * 1024 - 4096), (260 * 1024, 100)];
+
+        for use_mmap in [true, false] {
+            let config = VaultConfig { use_mmap, ..Default::default() };
+            let mut vault = RawVault::open(tmpfile.path(), config).unwrap();
+            vault.content().seek(SeekFrom::Start(10)).unwrap();
+            assert_eq!(vault.sparse_map(4096).unwrap(), expected);
+            assert_eq!(vault.content().stream_position().unwrap(), 10);
+        }
+
+        // Streams have no holes to query, so the same ranges come from reading
+        let data = std::fs::read(tmpfile.path()).unwrap();
+        let mut vault = RawVault::from_stream(Cursor::new(data), 260 * 1024 + 100);
+        assert_eq!(vault.sparse_map(4096).unwrap(), expected);
+
+        assert!(vault.sparse_map(0).is_err());
+    }
+
+    #[test]
+    fn test_raw_vault_from_bytes() {
+        use totalimage_core::ZoneTable;
+        use totalimage_zones::MbrZoneTable;
+
+        // One partition of type 0x0C from sector 2048 for 4096 sectors
+        let mut disk = vec![0u8; 64 * 1024];
+        disk[446 + 4] = 0x0C;
+        disk[446 + 8..446 + 12].copy_from_slice(&2048u32.to_le_bytes());
+        disk[446 + 12..446 + 16].copy_from_slice(&4096u32.to_le_bytes());
+        disk[510] = 0x55;
+        disk[511] = 0xAA;
+
+        let mut vault = RawVault::from_bytes(disk.clone());
+        assert_eq!(vault.access(), RawAccess::Memory);
+        assert_eq!(vault.length(), 64 * 1024);
+
+        let table = MbrZoneTable::parse(vault.content(), 512).unwrap();
+        let zones = table.enumerate_zones();
+        assert_eq!(zones.len(), 1);
+        assert_eq!(zones[0].offset, 2048 * 512);
+        assert_eq!(zones[0].length, 4096 * 512);
+
+        // A boxed reader is sized by seeking to its end
+        let mut vault = RawVault::from_reader(Box::new(Cursor::new(disk))).unwrap();
+        assert_eq!(vault.access(), RawAccess::Stream);
+        assert_eq!(vault.length(), 64 * 1024);
+        assert_eq!(MbrZoneTable::parse(vault.content(), 512).unwrap().enumerate_zones().len(), 1);
+    }
+
+    #[test]
+    fn test_raw_vault_manufacture_writable() {
+        let mut vault = RawVault::manufacture(512);
+        vault.content_mut().unwrap().write_all(b"TOTAL").unwrap();
+
+        let mut buf = [0u8; 5];
+        vault.content().seek(std::io::SeekFrom::Start(0)).unwrap();
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"TOTAL");
+    }
 }
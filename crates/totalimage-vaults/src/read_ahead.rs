@@ -0,0 +1,313 @@
+//! Read-ahead chunk decompression for compressed forensic containers
+//!
+//! E01 and AFF4 images store media as independently compressed chunks.
+//! Decompressing them one at a time on the reading thread makes large
+//! sequential reads CPU-bound; [`ReadAhead`] instead hands the chunks after
+//! the read position to a pool of worker threads once it sees a sequential
+//! pattern, so the reader usually finds the next chunk already decoded.
+//!
+//! Stored (compressed) bytes are always read on the caller's thread; only
+//! decoding runs on the workers.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use totalimage_core::Result;
+
+/// Configuration for read-ahead decompression
+///
+/// Read-ahead is opt-in; set [`VaultConfig::read_ahead`](crate::VaultConfig::read_ahead)
+/// to enable it for the vaults that support it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadAheadConfig {
+    /// Number of worker threads decompressing chunks
+    pub workers: usize,
+    /// Maximum number of chunks decompressed ahead of the read position
+    pub chunks_ahead: usize,
+    /// Upper bound on memory held by chunks decompressed ahead, in bytes
+    ///
+    /// Caps `chunks_ahead` for images with large chunks.
+    pub max_buffer_bytes: usize,
+}
+
+impl Default for ReadAheadConfig {
+    fn default() -> Self {
+        Self {
+            workers: thread::available_parallelism().map_or(2, |n| n.get()).min(8),
+            chunks_ahead: 8,
+            max_buffer_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// Turns a chunk's stored bytes into its contents; runs on a worker thread
+pub(crate) type ChunkDecoder = Box<dyn FnOnce(Vec<u8>) -> Result<Vec<u8>> + Send>;
+
+/// A container whose media is split into independently decoded chunks
+pub(crate) trait ChunkSource {
+    /// Number of chunks in the image
+    fn chunk_count(&self) -> usize;
+
+    /// Nominal decoded size of a chunk, used to bound the look-ahead buffer
+    fn chunk_size(&self) -> usize;
+
+    /// Read a chunk's stored bytes from the container
+    fn read_stored_chunk(&mut self, index: usize) -> Result<Vec<u8>>;
+
+    /// Get the decoder for a chunk's stored bytes
+    fn chunk_decoder(&self, index: usize) -> ChunkDecoder;
+
+    /// Read and decode a chunk on the calling thread
+    fn decode_chunk(&mut self, index: usize) -> Result<Vec<u8>> {
+        let stored = self.read_stored_chunk(index)?;
+        self.chunk_decoder(index)(stored)
+    }
+}
+
+/// A chunk queued for decoding
+struct Job {
+    index: usize,
+    stored: Vec<u8>,
+    decode: ChunkDecoder,
+}
+
+/// Worker pool decoding the chunks after a sequential read position
+pub(crate) struct ReadAhead {
+    config: ReadAheadConfig,
+    jobs: Option<Sender<Job>>,
+    results: Receiver<(usize, Result<Vec<u8>>)>,
+    workers: Vec<JoinHandle<()>>,
+    /// Decoded chunks waiting to be read
+    ready: HashMap<usize, Result<Vec<u8>>>,
+    /// Chunks queued or being decoded whose results are still wanted
+    in_flight: HashSet<usize>,
+    /// Last chunk fetched, to detect sequential reads
+    last_index: Option<usize>,
+}
+
+impl ReadAhead {
+    /// Start the worker pool
+    pub(crate) fn new(config: ReadAheadConfig) -> Self {
+        let (job_tx, job_rx) = mpsc::channel::<Job>();
+        let (result_tx, results) = mpsc::channel();
+        let job_rx = Arc::new(Mutex::new(job_rx));
+
+        let workers = (0..config.workers.max(1))
+            .map(|_| {
+                let job_rx = Arc::clone(&job_rx);
+                let result_tx = result_tx.clone();
+                thread::spawn(move || loop {
+                    let job = match job_rx.lock() {
+                        Ok(rx) => rx.recv(),
+                        Err(_) => break,
+                    };
+                    let Ok(job) = job else { break };
+                    if result_tx.send((job.index, (job.decode)(job.stored))).is_err() {
+                        break;
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            config,
+            jobs: Some(job_tx),
+            results,
+            workers,
+            ready: HashMap::new(),
+            in_flight: HashSet::new(),
+            last_index: None,
+        }
+    }
+
+    /// Get a decoded chunk, queueing the chunks after it on a sequential read
+    ///
+    /// A fetch that doesn't follow the previous one is treated as a seek:
+    /// everything decoded or queued for the old position is discarded.
+    pub(crate) fn fetch<S: ChunkSource + ?Sized>(&mut self, source: &mut S, index: usize) -> Result<Vec<u8>> {
+        let sequential = self.last_index.is_some_and(|last| index == last + 1);
+        self.last_index = Some(index);
+
+        self.collect_finished();
+        if sequential {
+            self.ready.retain(|&i, _| i >= index);
+            self.in_flight.retain(|&i| i >= index);
+        } else {
+            self.ready.clear();
+            self.in_flight.clear();
+        }
+
+        if let Some(chunk) = self.ready.remove(&index) {
+            if sequential {
+                self.queue_ahead(source, index);
+            }
+            return chunk;
+        }
+
+        if self.in_flight.remove(&index) {
+            if sequential {
+                self.queue_ahead(source, index);
+            }
+            if let Some(chunk) = self.wait_for(index) {
+                return chunk;
+            }
+        } else if sequential {
+            // Queue first so the workers run while this chunk decodes here
+            self.queue_ahead(source, index);
+        }
+
+        source.decode_chunk(index)
+    }
+
+    /// Number of chunks to keep decoded ahead for a chunk size
+    fn window(&self, chunk_size: usize) -> usize {
+        self.config
+            .chunks_ahead
+            .min(self.config.max_buffer_bytes / chunk_size.max(1))
+    }
+
+    /// Queue the chunks in the window after `index` that aren't yet decoded
+    fn queue_ahead<S: ChunkSource + ?Sized>(&mut self, source: &mut S, index: usize) {
+        let Some(jobs) = &self.jobs else { return };
+        let end = index
+            .saturating_add(self.window(source.chunk_size()))
+            .min(source.chunk_count().saturating_sub(1));
+
+        for next in index + 1..=end {
+            if self.ready.contains_key(&next) || self.in_flight.contains(&next) {
+                continue;
+            }
+
+            // A read error surfaces when the reader reaches the chunk
+            let Ok(stored) = source.read_stored_chunk(next) else { break };
+            let job = Job {
+                index: next,
+                stored,
+                decode: source.chunk_decoder(next),
+            };
+            if jobs.send(job).is_err() {
+                break;
+            }
+            self.in_flight.insert(next);
+        }
+    }
+
+    /// Move finished chunks that are still wanted into `ready`
+    fn collect_finished(&mut self) {
+        while let Ok((index, chunk)) = self.results.try_recv() {
+            if self.in_flight.remove(&index) {
+                self.ready.insert(index, chunk);
+            }
+        }
+    }
+
+    /// Block until a queued chunk is decoded
+    ///
+    /// Returns None if the workers have gone away.
+    fn wait_for(&mut self, index: usize) -> Option<Result<Vec<u8>>> {
+        loop {
+            let (finished, chunk) = self.results.recv().ok()?;
+            if finished == index {
+                return Some(chunk);
+            }
+            if self.in_flight.remove(&finished) {
+                self.ready.insert(finished, chunk);
+            }
+        }
+    }
+}
+
+impl Drop for ReadAhead {
+    fn drop(&mut self) {
+        // Closing the job queue stops the workers once they finish
+        self.jobs = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Chunks are their index repeated; records which were decoded here
+    struct Numbered {
+        count: usize,
+        stored_reads: Vec<usize>,
+    }
+
+    impl ChunkSource for Numbered {
+        fn chunk_count(&self) -> usize {
+            self.count
+        }
+
+        fn chunk_size(&self) -> usize {
+            4
+        }
+
+        fn read_stored_chunk(&mut self, index: usize) -> Result<Vec<u8>> {
+            self.stored_reads.push(index);
+            Ok(vec![index as u8; 4])
+        }
+
+        fn chunk_decoder(&self, _index: usize) -> ChunkDecoder {
+            Box::new(|stored| Ok(stored.iter().map(|b| b + 1).collect()))
+        }
+    }
+
+    fn config(chunks_ahead: usize) -> ReadAheadConfig {
+        ReadAheadConfig {
+            workers: 2,
+            chunks_ahead,
+            max_buffer_bytes: 1024,
+        }
+    }
+
+    #[test]
+    fn test_sequential_fetch_reads_ahead() {
+        let mut source = Numbered { count: 10, stored_reads: Vec::new() };
+        let mut read_ahead = ReadAhead::new(config(3));
+
+        for index in 0..10 {
+            assert_eq!(read_ahead.fetch(&mut source, index).unwrap(), vec![index as u8 + 1; 4]);
+        }
+
+        // Every chunk was read from the container exactly once
+        let mut reads = source.stored_reads.clone();
+        reads.sort_unstable();
+        assert_eq!(reads, (0..10).collect::<Vec<_>>());
+        // Chunk 2 onward was queued before it was asked for
+        assert_eq!(&source.stored_reads[..5], &[0, 2, 3, 4, 1]);
+    }
+
+    #[test]
+    fn test_seek_discards_window() {
+        let mut source = Numbered { count: 100, stored_reads: Vec::new() };
+        let mut read_ahead = ReadAhead::new(config(4));
+
+        read_ahead.fetch(&mut source, 0).unwrap();
+        read_ahead.fetch(&mut source, 1).unwrap();
+        assert_eq!(read_ahead.in_flight.len() + read_ahead.ready.len(), 4);
+
+        // A seek drops the old window and doesn't read ahead yet
+        assert_eq!(read_ahead.fetch(&mut source, 50).unwrap(), vec![51; 4]);
+        assert!(read_ahead.in_flight.is_empty() && read_ahead.ready.is_empty());
+
+        assert_eq!(read_ahead.fetch(&mut source, 51).unwrap(), vec![52; 4]);
+        assert_eq!(read_ahead.fetch(&mut source, 52).unwrap(), vec![53; 4]);
+    }
+
+    #[test]
+    fn test_window_bounded_by_memory() {
+        let read_ahead = ReadAhead::new(ReadAheadConfig {
+            workers: 1,
+            chunks_ahead: 16,
+            max_buffer_bytes: 10,
+        });
+        assert_eq!(read_ahead.window(4), 2);
+        assert_eq!(read_ahead.window(64), 0);
+    }
+}
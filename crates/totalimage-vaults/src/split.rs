@@ -0,0 +1,290 @@
+//! Split raw vault - dd-style images split into numbered segments
+//!
+//! Acquisition tools commonly split raw images into fixed-size segments
+//! named with a zero-padded numeric extension: image.001, image.002, ...
+//! (or starting at .000). The segments are concatenated into one virtual
+//! image.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use totalimage_core::{Error, ReadSeek, Result, Vault};
+use totalimage_pipeline::MmapPipeline;
+
+use crate::VaultConfig;
+
+/// One segment of a split image
+struct Segment {
+    path: PathBuf,
+    stream: Box<dyn ReadSeek>,
+    /// Offset of the segment's first byte in the virtual image
+    offset: u64,
+    length: u64,
+}
+
+/// Split raw vault - a raw sector image stored as numbered segments
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use totalimage_vaults::{SplitRawVault, VaultConfig};
+/// use totalimage_core::Vault;
+/// use std::path::Path;
+///
+/// let vault = SplitRawVault::open(Path::new("disk.001"), VaultConfig::default()).unwrap();
+/// println!("Segments: {}", vault.segment_count());
+/// println!("Size: {} bytes", vault.length());
+/// ```
+pub struct SplitRawVault {
+    segments: Vec<Segment>,
+    length: u64,
+    position: u64,
+}
+
+impl SplitRawVault {
+    /// Open a split raw vault from the path of its first segment
+    ///
+    /// Sibling segments are discovered by incrementing the numeric
+    /// extension, keeping its zero padding, until a segment is missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The path does not have a numeric extension
+    /// - A segment cannot be opened
+    /// - Any segment other than the last differs in size from the first
+    pub fn open(path: &Path, config: VaultConfig) -> Result<Self> {
+        let paths = Self::discover_segments(path)?;
+
+        let mut segments = Vec::with_capacity(paths.len());
+        let mut offset = 0u64;
+
+        for path in paths {
+            let file = File::open(&path)?;
+            let length = file.metadata()?.len();
+
+            let stream: Box<dyn ReadSeek> = if config.use_mmap && length > 0 {
+                Box::new(MmapPipeline::from_file(&file)?)
+            } else {
+                Box::new(file)
+            };
+
+            segments.push(Segment {
+                path,
+                stream,
+                offset,
+                length,
+            });
+            offset += length;
+        }
+
+        let segment_size = segments[0].length;
+        if let Some(odd) = segments[..segments.len() - 1]
+            .iter()
+            .find(|segment| segment.length != segment_size)
+        {
+            return Err(Error::invalid_vault(format!(
+                "Split image segment {} is {} bytes, expected {}",
+                odd.path.display(),
+                odd.length,
+                segment_size
+            )));
+        }
+
+        Ok(Self {
+            segments,
+            length: offset,
+            position: 0,
+        })
+    }
+
+    /// Find the first segment and all consecutive siblings
+    fn discover_segments(first: &Path) -> Result<Vec<PathBuf>> {
+        let extension = first
+            .extension()
+            .and_then(|e| e.to_str())
+            .filter(|e| !e.is_empty() && e.bytes().all(|b| b.is_ascii_digit()))
+            .ok_or_else(|| {
+                Error::invalid_vault(format!(
+                    "Split image segment has no numeric extension: {}",
+                    first.display()
+                ))
+            })?;
+
+        let width = extension.len();
+        let start: u64 = extension
+            .parse()
+            .map_err(|_| Error::invalid_vault(format!("Invalid segment number: {}", extension)))?;
+
+        let mut paths = vec![first.to_path_buf()];
+        for number in start + 1.. {
+            let candidate = first.with_extension(format!("{:0width$}", number, width = width));
+            if !candidate.is_file() {
+                break;
+            }
+            paths.push(candidate);
+        }
+
+        Ok(paths)
+    }
+
+    /// Get the number of segments
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Get the paths of all segments in order
+    pub fn segment_paths(&self) -> Vec<&Path> {
+        self.segments.iter().map(|s| s.path.as_path()).collect()
+    }
+
+    /// Find the segment containing a virtual offset
+    fn segment_at(&self, offset: u64) -> usize {
+        self.segments
+            .partition_point(|segment| segment.offset + segment.length <= offset)
+    }
+}
+
+impl Read for SplitRawVault {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut total_read = 0;
+
+        while total_read < buf.len() && self.position < self.length {
+            let index = self.segment_at(self.position);
+            let segment = &mut self.segments[index];
+
+            let segment_offset = self.position - segment.offset;
+            let available = (segment.length - segment_offset) as usize;
+            let chunk_size = (buf.len() - total_read).min(available);
+
+            segment.stream.seek(SeekFrom::Start(segment_offset))?;
+            let bytes_read = segment
+                .stream
+                .read(&mut buf[total_read..total_read + chunk_size])?;
+
+            if bytes_read == 0 {
+                break;
+            }
+
+            total_read += bytes_read;
+            self.position += bytes_read as u64;
+        }
+
+        Ok(total_read)
+    }
+}
+
+impl Seek for SplitRawVault {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => offset as i64,
+            SeekFrom::End(offset) => self.length as i64 + offset,
+            SeekFrom::Current(offset) => self.position as i64 + offset,
+        };
+
+        if new_pos < 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Seek before beginning of split image",
+            ));
+        }
+
+        self.position = (new_pos as u64).min(self.length);
+        Ok(self.position)
+    }
+}
+
+impl Vault for SplitRawVault {
+    fn identify(&self) -> &str {
+        "Split raw sector image"
+    }
+
+    fn length(&self) -> u64 {
+        self.length
+    }
+
+    fn content(&mut self) -> &mut dyn ReadSeek {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Write `data` split into `segment_size` segments named image.NNN
+    fn write_segments(dir: &TempDir, data: &[u8], segment_size: usize, first: u32) -> PathBuf {
+        for (i, chunk) in data.chunks(segment_size).enumerate() {
+            let path = dir.path().join(format!("image.{:03}", first as usize + i));
+            std::fs::write(path, chunk).unwrap();
+        }
+        dir.path().join(format!("image.{:03}", first))
+    }
+
+    fn test_data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_read_across_segments() {
+        let dir = TempDir::new().unwrap();
+        let data = test_data(2048);
+        let first = write_segments(&dir, &data, 1024, 1);
+
+        for use_mmap in [true, false] {
+            let mut vault = SplitRawVault::open(&first, VaultConfig { use_mmap, ..Default::default() }).unwrap();
+            assert_eq!(vault.identify(), "Split raw sector image");
+            assert_eq!(vault.segment_count(), 2);
+            assert_eq!(vault.length(), 2048);
+
+            // Read straddling the boundary between image.001 and image.002
+            let mut buf = [0u8; 100];
+            vault.content().seek(SeekFrom::Start(1000)).unwrap();
+            vault.content().read_exact(&mut buf).unwrap();
+            assert_eq!(&buf[..], &data[1000..1100]);
+
+            // Reads stop at the end of the image
+            vault.content().seek(SeekFrom::End(-10)).unwrap();
+            assert_eq!(vault.content().read(&mut buf).unwrap(), 10);
+            assert_eq!(&buf[..10], &data[2038..]);
+            assert_eq!(vault.content().read(&mut buf).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn test_short_last_segment_and_zero_start() {
+        let dir = TempDir::new().unwrap();
+        let data = test_data(2500);
+        let first = write_segments(&dir, &data, 1000, 0);
+
+        let mut vault = SplitRawVault::open(&first, VaultConfig::default()).unwrap();
+        assert_eq!(vault.segment_count(), 3);
+        assert_eq!(vault.length(), 2500);
+        assert!(vault.segment_paths()[2].ends_with("image.002"));
+
+        let mut buf = vec![0u8; 2500];
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(buf, data);
+    }
+
+    #[test]
+    fn test_mismatched_segment_sizes_rejected() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("image.001"), [0u8; 1024]).unwrap();
+        std::fs::write(dir.path().join("image.002"), [0u8; 512]).unwrap();
+        std::fs::write(dir.path().join("image.003"), [0u8; 1024]).unwrap();
+
+        let result = SplitRawVault::open(&dir.path().join("image.001"), VaultConfig::default());
+        assert!(matches!(result, Err(Error::InvalidVault(_))));
+    }
+
+    #[test]
+    fn test_non_numeric_extension_rejected() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("image.img");
+        std::fs::write(&path, [0u8; 512]).unwrap();
+
+        assert!(SplitRawVault::open(&path, VaultConfig::default()).is_err());
+    }
+}
@@ -0,0 +1,495 @@
+//! VirtualBox disk image (VDI) vault
+//!
+//! A VDI starts with a text banner and a header locating a block map,
+//! which gives the position of each fixed-size block of the disk among the
+//! data blocks that follow. Dynamic images leave unwritten blocks out of
+//! the file; fixed images store every block. Differencing and undo images
+//! need their parent and are refused. Common file extension: .vdi
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use totalimage_core::{max_allocation_size, validate_allocation_size, Error, ReadSeek, Result, Vault};
+use totalimage_pipeline::MmapPipeline;
+
+use crate::VaultConfig;
+
+/// Kind of VDI image
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VdiImageType {
+    /// Blocks are allocated as they're written
+    Dynamic,
+    /// Every block is allocated when the image is created
+    Fixed,
+    /// Undo image for a parent
+    Undo,
+    /// Differencing image for a parent
+    Differencing,
+    /// Unrecognized type code
+    Unknown(u32),
+}
+
+impl VdiImageType {
+    fn from_u32(value: u32) -> Self {
+        match value {
+            1 => Self::Dynamic,
+            2 => Self::Fixed,
+            3 => Self::Undo,
+            4 => Self::Differencing,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
+/// VDI header, version 1.1
+///
+/// All fields are little-endian.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VdiHeader {
+    /// Format version, major in the high 16 bits
+    pub version: u32,
+    /// Kind of image
+    pub image_type: VdiImageType,
+    /// Offset of the block map in the file
+    pub blocks_offset: u32,
+    /// Offset of the first data block in the file
+    pub data_offset: u32,
+    /// Size of the disk in bytes
+    pub disk_size: u64,
+    /// Size of a block in bytes
+    pub block_size: u32,
+    /// Bytes of extra data stored before each block
+    pub block_extra_size: u32,
+    /// Number of blocks in the disk
+    pub block_count: u32,
+    /// Number of blocks stored in the file
+    pub blocks_allocated: u32,
+    /// UUID of the image
+    pub uuid: [u8; 16],
+    /// UUID of the parent of a differencing image
+    pub parent_uuid: [u8; 16],
+}
+
+impl VdiHeader {
+    /// Bytes to read to parse the header
+    pub const SIZE: usize = 0x1C8;
+
+    /// Signature at offset 0x40
+    pub const SIGNATURE: u32 = 0xBEDA107F;
+
+    /// Offset of the signature, after the text banner
+    pub const SIGNATURE_OFFSET: usize = 0x40;
+
+    /// Block map entry of a block that isn't stored
+    pub const BLOCK_FREE: u32 = 0xFFFFFFFF;
+
+    /// Block map entry of a block that is stored as all zeros
+    pub const BLOCK_ZERO: u32 = 0xFFFFFFFE;
+
+    /// Does `bytes` start with a VDI signature?
+    pub fn has_signature(bytes: &[u8]) -> bool {
+        bytes.len() >= Self::SIGNATURE_OFFSET + 4
+            && u32::from_le_bytes(bytes[Self::SIGNATURE_OFFSET..Self::SIGNATURE_OFFSET + 4].try_into().unwrap())
+                == Self::SIGNATURE
+    }
+
+    /// Parse a header, returning None if the signature doesn't match
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Self::SIZE || !Self::has_signature(bytes) {
+            return None;
+        }
+
+        let u32_at = |offset: usize| u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap());
+        let uuid_at = |offset: usize| -> [u8; 16] { bytes[offset..offset + 16].try_into().unwrap() };
+        Some(Self {
+            version: u32_at(0x44),
+            image_type: VdiImageType::from_u32(u32_at(0x4C)),
+            blocks_offset: u32_at(0x154),
+            data_offset: u32_at(0x158),
+            disk_size: u64::from_le_bytes(bytes[0x170..0x178].try_into().unwrap()),
+            block_size: u32_at(0x178),
+            block_extra_size: u32_at(0x17C),
+            block_count: u32_at(0x180),
+            blocks_allocated: u32_at(0x184),
+            uuid: uuid_at(0x188),
+            parent_uuid: uuid_at(0x1A8),
+        })
+    }
+
+    /// Check the header describes an image that can be read from a file of
+    /// `file_len` bytes
+    fn validate(&self, file_len: u64) -> Result<()> {
+        if self.version >> 16 != 1 || self.version & 0xFFFF < 1 {
+            return Err(Error::unsupported(format!(
+                "VDI version {}.{} is unsupported; only 1.1 images can be read",
+                self.version >> 16,
+                self.version & 0xFFFF
+            )));
+        }
+        if !matches!(self.image_type, VdiImageType::Dynamic | VdiImageType::Fixed) {
+            return Err(Error::unsupported(format!(
+                "{:?} VDI images are unsupported; only dynamic and fixed images can be read",
+                self.image_type
+            )));
+        }
+        if self.block_size == 0 || !self.block_size.is_power_of_two() {
+            return Err(Error::invalid_vault(format!("Invalid VDI block size: {}", self.block_size)));
+        }
+        if (self.block_count as u64) * (self.block_size as u64) < self.disk_size {
+            return Err(Error::invalid_vault(format!(
+                "VDI block map covers {} blocks of {} bytes, less than the {} byte disk",
+                self.block_count, self.block_size, self.disk_size
+            )));
+        }
+        if self.block_extra_size as u64 + self.data_offset as u64 > file_len {
+            return Err(Error::invalid_vault(format!(
+                "VDI block data at {} with {} bytes of extra data lies past the end of the file",
+                self.data_offset, self.block_extra_size
+            )));
+        }
+        Ok(())
+    }
+
+    /// Get the file offset of the stored block with index `stored`
+    fn stored_block_offset(&self, stored: u32) -> Option<u64> {
+        let stride = (self.block_extra_size as u64).checked_add(self.block_size as u64)?;
+        (stored as u64)
+            .checked_mul(stride)?
+            .checked_add(self.data_offset as u64)?
+            .checked_add(self.block_extra_size as u64)
+    }
+}
+
+/// VDI vault - a dynamic or fixed VirtualBox disk image
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use totalimage_vaults::{VdiVault, VaultConfig};
+/// use totalimage_core::Vault;
+/// use std::path::Path;
+///
+/// let vault = VdiVault::open(Path::new("disk.vdi"), VaultConfig::default()).unwrap();
+/// println!("Size: {} bytes", vault.length());
+/// ```
+pub struct VdiVault {
+    pipeline: VdiPipeline<Box<dyn ReadSeek>>,
+    header: VdiHeader,
+}
+
+impl VdiVault {
+    /// Open a VDI vault from a file path
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file has no VDI signature, if its header or
+    /// block map is invalid, or if it isn't a version 1.1 dynamic or fixed
+    /// image, which is unsupported
+    pub fn open(path: &Path, config: VaultConfig) -> Result<Self> {
+        let mut file = File::open(path)?;
+        let mut bytes = [0u8; VdiHeader::SIZE];
+        file.read_exact(&mut bytes)
+            .map_err(|_| Error::truncated("File too small to be a VDI"))?;
+        let header = VdiHeader::from_bytes(&bytes)
+            .ok_or_else(|| Error::unrecognized_signature("No VDI signature found".to_string()))?;
+        let file_len = file.metadata()?.len();
+        header.validate(file_len)?;
+
+        let map_size = validate_allocation_size(
+            header.block_count as u64 * 4,
+            max_allocation_size(),
+            "VDI block map",
+        )?;
+        let mut map_bytes = vec![0u8; map_size];
+        file.seek(SeekFrom::Start(header.blocks_offset as u64))?;
+        file.read_exact(&mut map_bytes)?;
+        let block_map: Vec<u32> = map_bytes
+            .chunks_exact(4)
+            .map(|entry| u32::from_le_bytes(entry.try_into().unwrap()))
+            .collect();
+
+        for (block_index, &stored) in block_map.iter().enumerate() {
+            if matches!(stored, VdiHeader::BLOCK_FREE | VdiHeader::BLOCK_ZERO) {
+                continue;
+            }
+            let end = header
+                .stored_block_offset(stored)
+                .and_then(|offset| offset.checked_add(header.block_size as u64));
+            if end.is_none_or(|end| end > file_len) {
+                return Err(Error::invalid_vault(format!(
+                    "VDI block {} is stored as block {}, past the end of the file",
+                    block_index, stored
+                )));
+            }
+        }
+
+        let base: Box<dyn ReadSeek> = if config.use_mmap && file_len > 0 {
+            Box::new(MmapPipeline::from_file(&file)?)
+        } else {
+            Box::new(file)
+        };
+        let pipeline = VdiPipeline {
+            base,
+            block_map,
+            header: header.clone(),
+            block_size: header.block_size as u64,
+            virtual_size: header.disk_size,
+            position: 0,
+        };
+
+        Ok(Self { pipeline, header })
+    }
+
+    /// Get the image's VDI header
+    pub fn header(&self) -> &VdiHeader {
+        &self.header
+    }
+
+    /// Get the block map, one entry per block of the disk
+    ///
+    /// An entry is the index of the block among those stored in the file,
+    /// or [`VdiHeader::BLOCK_FREE`] or [`VdiHeader::BLOCK_ZERO`] for a
+    /// block that isn't stored.
+    pub fn block_map(&self) -> &[u32] {
+        &self.pipeline.block_map
+    }
+}
+
+impl Vault for VdiVault {
+    fn identify(&self) -> &str {
+        match self.header.image_type {
+            VdiImageType::Fixed => "VirtualBox VDI (Fixed)",
+            _ => "VirtualBox VDI (Dynamic)",
+        }
+    }
+
+    fn length(&self) -> u64 {
+        self.header.disk_size
+    }
+
+    fn content(&mut self) -> &mut dyn ReadSeek {
+        &mut self.pipeline
+    }
+}
+
+/// Pipeline translating disk offsets through a VDI block map
+struct VdiPipeline<R: Read + Seek> {
+    base: R,
+    block_map: Vec<u32>,
+    header: VdiHeader,
+    block_size: u64,
+    virtual_size: u64,
+    position: u64,
+}
+
+impl<R: Read + Seek> VdiPipeline<R> {
+    /// Get the file offset of block `block_index`, or None if it isn't stored
+    fn block_offset(&self, block_index: usize) -> io::Result<Option<u64>> {
+        match self.block_map.get(block_index).copied() {
+            None | Some(VdiHeader::BLOCK_FREE) | Some(VdiHeader::BLOCK_ZERO) => Ok(None),
+            Some(stored) => self.header.stored_block_offset(stored).map(Some).ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "VDI block offset overflows")
+            }),
+        }
+    }
+}
+
+impl<R: Read + Seek> Read for VdiPipeline<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position >= self.virtual_size {
+            return Ok(0);
+        }
+
+        let to_read = (buf.len() as u64).min(self.virtual_size - self.position) as usize;
+        let mut total_read = 0;
+
+        while total_read < to_read {
+            let current_offset = self.position + total_read as u64;
+            let block_index = (current_offset / self.block_size) as usize;
+            let within_block = current_offset % self.block_size;
+            let chunk_size = ((to_read - total_read) as u64).min(self.block_size - within_block) as usize;
+            let chunk = &mut buf[total_read..total_read + chunk_size];
+
+            match self.block_offset(block_index)? {
+                Some(block_offset) => {
+                    self.base.seek(SeekFrom::Start(block_offset + within_block))?;
+                    self.base.read_exact(chunk)?;
+                }
+                // Unallocated blocks read as zeros
+                None => chunk.fill(0),
+            }
+            total_read += chunk_size;
+        }
+
+        self.position += total_read as u64;
+        Ok(total_read)
+    }
+}
+
+impl<R: Read + Seek> Seek for VdiPipeline<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.virtual_size.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+
+        match new_pos {
+            Some(new_pos) if new_pos <= self.virtual_size => {
+                self.position = new_pos;
+                Ok(new_pos)
+            }
+            Some(_) => Err(io::Error::new(io::ErrorKind::InvalidInput, "Seek beyond end of VDI")),
+            None => Err(io::Error::new(io::ErrorKind::InvalidInput, "Seek before beginning of VDI")),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    const BLOCK_SIZE: usize = 4096;
+
+    /// Build a VDI of `block_count` blocks with `stored` blocks in the file,
+    /// each given by its disk block index and contents
+    pub(crate) fn build_vdi(image_type: u32, disk_size: u64, block_count: u32, stored: &[(usize, &[u8])]) -> Vec<u8> {
+        let blocks_offset = 0x200;
+        let data_offset = 0x400;
+
+        let mut image = vec![0u8; data_offset + stored.len() * BLOCK_SIZE];
+        let banner = b"<<< Oracle VM VirtualBox Disk Image >>>\n";
+        image[..banner.len()].copy_from_slice(banner);
+        image[0x40..0x44].copy_from_slice(&VdiHeader::SIGNATURE.to_le_bytes());
+        image[0x44..0x48].copy_from_slice(&0x0001_0001u32.to_le_bytes());
+        image[0x48..0x4C].copy_from_slice(&0x190u32.to_le_bytes());
+        image[0x4C..0x50].copy_from_slice(&image_type.to_le_bytes());
+        image[0x154..0x158].copy_from_slice(&(blocks_offset as u32).to_le_bytes());
+        image[0x158..0x15C].copy_from_slice(&(data_offset as u32).to_le_bytes());
+        image[0x170..0x178].copy_from_slice(&disk_size.to_le_bytes());
+        image[0x178..0x17C].copy_from_slice(&(BLOCK_SIZE as u32).to_le_bytes());
+        image[0x180..0x184].copy_from_slice(&block_count.to_le_bytes());
+        image[0x184..0x188].copy_from_slice(&(stored.len() as u32).to_le_bytes());
+        image[0x188..0x198].copy_from_slice(&[0xAB; 16]);
+
+        let mut block_map = vec![VdiHeader::BLOCK_FREE; block_count as usize];
+        for (stored_index, (block_index, data)) in stored.iter().enumerate() {
+            block_map[*block_index] = stored_index as u32;
+            let offset = data_offset + stored_index * BLOCK_SIZE;
+            image[offset..offset + data.len()].copy_from_slice(data);
+        }
+        for (i, entry) in block_map.iter().enumerate() {
+            image[blocks_offset + i * 4..blocks_offset + i * 4 + 4].copy_from_slice(&entry.to_le_bytes());
+        }
+        image
+    }
+
+    fn write_temp(image: &[u8]) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(image).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    #[test]
+    fn test_vdi_header_parse() {
+        let image = build_vdi(1, 4 * BLOCK_SIZE as u64, 4, &[]);
+        let header = VdiHeader::from_bytes(&image).unwrap();
+        assert_eq!(header.image_type, VdiImageType::Dynamic);
+        assert_eq!(header.disk_size, 4 * BLOCK_SIZE as u64);
+        assert_eq!(header.block_size, BLOCK_SIZE as u32);
+        assert_eq!(header.block_count, 4);
+        assert_eq!(header.uuid, [0xAB; 16]);
+
+        assert!(VdiHeader::from_bytes(&[0u8; VdiHeader::SIZE]).is_none());
+        assert!(VdiHeader::from_bytes(&image[..0x100]).is_none());
+    }
+
+    #[test]
+    fn test_open_dynamic_vdi() {
+        // Blocks 1 and 3 of 4 are stored, in reverse order
+        let one = vec![0x11u8; BLOCK_SIZE];
+        let three: Vec<u8> = (0..BLOCK_SIZE).map(|i| (i % 251) as u8).collect();
+        let disk_size = 3 * BLOCK_SIZE as u64 + 1000;
+        let file = write_temp(&build_vdi(1, disk_size, 4, &[(3, &three), (1, &one)]));
+
+        for use_mmap in [true, false] {
+            let config = VaultConfig { use_mmap, ..Default::default() };
+            let mut vault = VdiVault::open(file.path(), config).unwrap();
+            assert_eq!(vault.length(), disk_size);
+            assert_eq!(vault.identify(), "VirtualBox VDI (Dynamic)");
+            assert_eq!(vault.block_map(), &[VdiHeader::BLOCK_FREE, 1, VdiHeader::BLOCK_FREE, 0]);
+
+            let mut data = Vec::new();
+            vault.content().read_to_end(&mut data).unwrap();
+            assert_eq!(data.len() as u64, disk_size);
+            assert!(data[..BLOCK_SIZE].iter().all(|&b| b == 0));
+            assert_eq!(data[BLOCK_SIZE..2 * BLOCK_SIZE], one);
+            assert!(data[2 * BLOCK_SIZE..3 * BLOCK_SIZE].iter().all(|&b| b == 0));
+            assert_eq!(data[3 * BLOCK_SIZE..], three[..1000]);
+
+            // A read spanning a stored and an unallocated block
+            let mut buf = [0xFFu8; 8];
+            vault.content().seek(SeekFrom::Start(2 * BLOCK_SIZE as u64 - 4)).unwrap();
+            vault.content().read_exact(&mut buf).unwrap();
+            assert_eq!(buf, [0x11, 0x11, 0x11, 0x11, 0, 0, 0, 0]);
+            assert!(vault.content().seek(SeekFrom::End(1)).is_err());
+        }
+    }
+
+    #[test]
+    fn test_open_fixed_vdi() {
+        let blocks: Vec<Vec<u8>> = (0..2u8).map(|i| vec![i + 1; BLOCK_SIZE]).collect();
+        let file = write_temp(&build_vdi(2, 2 * BLOCK_SIZE as u64, 2, &[(0, &blocks[0]), (1, &blocks[1])]));
+
+        let mut vault = VdiVault::open(file.path(), VaultConfig::default()).unwrap();
+        assert_eq!(vault.identify(), "VirtualBox VDI (Fixed)");
+        let mut data = Vec::new();
+        vault.content().read_to_end(&mut data).unwrap();
+        assert_eq!(data, blocks.concat());
+    }
+
+    #[test]
+    fn test_unsupported_vdi() {
+        let file = write_temp(&build_vdi(4, BLOCK_SIZE as u64, 1, &[]));
+        let err = VdiVault::open(file.path(), VaultConfig::default()).err().unwrap();
+        assert!(matches!(err, Error::Unsupported(_)), "{:?}", err);
+
+        // Too few blocks for the disk
+        let file = write_temp(&build_vdi(1, 2 * BLOCK_SIZE as u64, 1, &[]));
+        assert!(VdiVault::open(file.path(), VaultConfig::default()).is_err());
+
+        let file = write_temp(&[0u8; 100]);
+        assert!(VdiVault::open(file.path(), VaultConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_vdi_blocks_past_end_of_file() {
+        // A block map entry past the stored blocks
+        let mut image = build_vdi(1, 2 * BLOCK_SIZE as u64, 2, &[(0, &[1; 16])]);
+        image[0x204..0x208].copy_from_slice(&7u32.to_le_bytes());
+        let file = write_temp(&image);
+        let err = VdiVault::open(file.path(), VaultConfig::default()).err().unwrap();
+        assert!(err.to_string().contains("past the end of the file"), "{}", err);
+
+        // A huge block index with huge blocks
+        let mut image = build_vdi(1, 2 * BLOCK_SIZE as u64, 2, &[(0, &[1; 16])]);
+        image[0x178..0x17C].copy_from_slice(&(1u32 << 31).to_le_bytes());
+        image[0x180..0x184].copy_from_slice(&1u32.to_le_bytes());
+        image[0x200..0x204].copy_from_slice(&0xFFFF_FFF0u32.to_le_bytes());
+        let file = write_temp(&image);
+        assert!(VdiVault::open(file.path(), VaultConfig::default()).is_err());
+
+        // Extra data past the end of the file, and an offset that overflows
+        let mut image = build_vdi(1, 2 * BLOCK_SIZE as u64, 2, &[]);
+        image[0x17C..0x180].copy_from_slice(&u32::MAX.to_le_bytes());
+        let file = write_temp(&image);
+        assert!(VdiVault::open(file.path(), VaultConfig::default()).is_err());
+
+        let mut header = VdiHeader::from_bytes(&image).unwrap();
+        header.block_size = 1 << 31;
+        assert_eq!(header.stored_block_offset(u32::MAX - 2), None);
+    }
+}
@@ -0,0 +1,97 @@
+//! Verification of the media hashes forensic containers embed
+//!
+//! E01 images carry an MD5 of the acquired media and AFF4 images usually
+//! record a SHA-512 of each image stream. Verifying either means reading
+//! the whole image back and hashing it, so callers should expect it to
+//! take as long as a full sequential read.
+
+use std::io::SeekFrom;
+
+use totalimage_core::progress::report;
+use totalimage_core::{Progress, ProgressUpdate, ReadSeek, Result};
+
+/// Outcome of recomputing a container's embedded media hash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashVerification {
+    /// Hash algorithm (e.g. "MD5")
+    pub algorithm: &'static str,
+    /// Hash recorded in the container, lowercase hex
+    pub expected: String,
+    /// Hash of the media as read, lowercase hex
+    pub computed: String,
+}
+
+impl HashVerification {
+    /// Check whether the media still matches the recorded hash
+    pub fn is_match(&self) -> bool {
+        self.expected.eq_ignore_ascii_case(&self.computed)
+    }
+}
+
+/// Feed a stream's contents, from the start, to a hasher
+///
+/// The stream's position is restored afterwards. `progress` gets a
+/// [`Bytes`](ProgressUpdate::Bytes) update after each block.
+pub(crate) fn hash_media(
+    stream: &mut dyn ReadSeek,
+    progress: Option<&dyn Progress>,
+    mut update: impl FnMut(&[u8]),
+) -> Result<()> {
+    let position = stream.stream_position()?;
+    let total = stream.seek(SeekFrom::End(0))?;
+    stream.seek(SeekFrom::Start(0))?;
+
+    let mut buf = vec![0u8; 1024 * 1024];
+    let mut processed = 0;
+    loop {
+        let n = stream.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        update(&buf[..n]);
+        processed += n as u64;
+        report(progress, ProgressUpdate::Bytes { processed, total: Some(total) });
+    }
+
+    stream.seek(SeekFrom::Start(position))?;
+    Ok(())
+}
+
+/// Format a digest as lowercase hex
+pub(crate) fn to_hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Seek};
+
+    #[test]
+    fn test_hash_media_restores_position() {
+        let mut stream = Cursor::new((0..=255u8).cycle().take(3 * 1024 * 1024 + 7).collect::<Vec<_>>());
+        stream.seek(SeekFrom::Start(100)).unwrap();
+
+        let mut total = 0;
+        let updates = std::sync::Mutex::new(Vec::new());
+        let progress = |update: ProgressUpdate| updates.lock().unwrap().push(update);
+        hash_media(&mut stream, Some(&progress), |data| total += data.len()).unwrap();
+        assert_eq!(total, 3 * 1024 * 1024 + 7);
+        assert_eq!(stream.position(), 100);
+
+        // One update per 1 MiB block
+        let updates = updates.into_inner().unwrap();
+        assert_eq!(updates.len(), 4);
+        assert_eq!(updates[3], ProgressUpdate::Bytes { processed: total as u64, total: Some(total as u64) });
+    }
+
+    #[test]
+    fn test_is_match() {
+        let verification = HashVerification {
+            algorithm: "MD5",
+            expected: "D41D8CD98F00B204E9800998ECF8427E".to_string(),
+            computed: to_hex(&md5::compute(b"").0),
+        };
+        assert!(verification.is_match());
+    }
+}
@@ -7,6 +7,9 @@
 //! - **Fixed VHD**: Simple format where data is stored contiguously with a footer at the end
 //! - **Dynamic VHD**: Sparse format using a Block Allocation Table (BAT) for space efficiency
 //!
+//! Dynamic VHDs opened with [`VaultConfig::writable`] can be written through
+//! [`Vault::content_mut`]; writes into sparse blocks allocate them.
+//!
 //! ## Format Overview
 //!
 //! VHD files have a 512-byte footer at the end containing metadata.
@@ -15,18 +18,71 @@
 
 pub mod types;
 
-use std::fs::File;
-use std::io::{self, Read, Seek, SeekFrom};
+use chrono::{DateTime, Utc};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
-use totalimage_core::{ReadSeek, Result, Vault};
+use totalimage_core::{BackingLink, ReadSeek, ReadWriteSeek, Result, Vault};
 use totalimage_pipeline::{MmapPipeline, PartialPipeline};
 use types::{BlockAllocationTable, ParentLocatorEntry, VhdDynamicHeader, VhdFooter, VhdType};
 
 use crate::VaultConfig;
 
+/// Read and validate the footer, falling back to the copy at the start
+///
+/// Dynamic and differencing VHDs mirror the footer in their first 512
+/// bytes, so a truncated or damaged file can still be opened from that
+/// copy. Returns the footer, its raw bytes, and whether the trailing footer
+/// was the valid one. When neither validates, the trailing footer's error
+/// is returned.
+fn read_footer(file: &mut File, file_len: u64) -> Result<(VhdFooter, [u8; VhdFooter::SIZE], bool)> {
+    let read_at = |file: &mut File, offset: u64| -> Result<([u8; VhdFooter::SIZE], Result<VhdFooter>)> {
+        file.seek(SeekFrom::Start(offset))?;
+        let mut bytes = [0u8; VhdFooter::SIZE];
+        file.read_exact(&mut bytes)?;
+        let footer = VhdFooter::parse(&bytes).and_then(|footer| {
+            if footer.verify_checksum() {
+                Ok(footer)
+            } else {
+                Err(totalimage_core::Error::checksum_mismatch(
+                    "VHD footer checksum verification failed",
+                ))
+            }
+        });
+        Ok((bytes, footer))
+    };
+
+    let (trailing_bytes, trailing) = read_at(file, file_len - VhdFooter::SIZE as u64)?;
+    let (leading_bytes, leading) = read_at(file, 0)?;
+    // Fixed VHDs have no leading copy; their first sector is disk data
+    let leading = leading.ok().filter(|footer| footer.disk_type != VhdType::Fixed);
+
+    match (trailing, leading) {
+        (Ok(footer), leading) => {
+            if leading.is_some() && leading_bytes != trailing_bytes {
+                tracing::warn!("VHD footer copies disagree; using the trailing footer");
+            }
+            Ok((footer, trailing_bytes, true))
+        }
+        (Err(e), Some(footer)) => {
+            tracing::warn!("VHD trailing footer is invalid ({}); using the copy at the start", e);
+            Ok((footer, leading_bytes, false))
+        }
+        (Err(e), None) => Err(e),
+    }
+}
+
+/// Storage behind a VHD vault
+enum VhdBacking {
+    /// Read-only stream of the virtual disk
+    ReadOnly(Box<dyn ReadSeek>),
+    /// Dynamic VHD file opened read-write
+    Writable(Box<VhdDynamicPipeline<File>>),
+}
+
 /// VHD vault - Microsoft Virtual Hard Disk container
 pub struct VhdVault {
-    pipeline: Box<dyn ReadSeek>,
+    pipeline: VhdBacking,
     footer: VhdFooter,
     dynamic_header: Option<VhdDynamicHeader>,
     bat: Option<BlockAllocationTable>,
@@ -44,32 +100,36 @@ impl VhdVault {
     ///
     /// Returns an error if:
     /// - The file cannot be opened
-    /// - The VHD footer is invalid or corrupted
+    /// - Neither the trailing footer nor, for dynamic and differencing
+    ///   VHDs, the copy at the start of the file is valid
     /// - The dynamic header or BAT is invalid (for dynamic VHDs)
+    /// - `config.writable` is set and the VHD isn't a dynamic VHD or can't
+    ///   be opened for writing
     pub fn open(path: &Path, config: VaultConfig) -> Result<Self> {
         let mut file = File::open(path)?;
         let file_len = file.metadata()?.len();
 
         if file_len < VhdFooter::SIZE as u64 {
-            return Err(totalimage_core::Error::invalid_vault(
+            return Err(totalimage_core::Error::truncated(
                 "File too small to be a VHD",
             ));
         }
 
-        // Read footer from last 512 bytes
-        file.seek(SeekFrom::End(-(VhdFooter::SIZE as i64)))?;
-        let mut footer_bytes = [0u8; VhdFooter::SIZE];
-        file.read_exact(&mut footer_bytes)?;
-
-        let footer = VhdFooter::parse(&footer_bytes)?;
+        let (footer, footer_bytes, trailing_valid) = read_footer(&mut file, file_len)?;
 
-        // Verify footer checksum
-        if !footer.verify_checksum() {
-            return Err(totalimage_core::Error::invalid_vault(
-                "VHD footer checksum verification failed",
+        if config.writable && !trailing_valid {
+            return Err(totalimage_core::Error::unsupported(
+                "VHD trailing footer is damaged; open read-only to recover the disk",
             ));
         }
 
+        if config.writable && footer.disk_type != VhdType::Dynamic {
+            return Err(totalimage_core::Error::unsupported(format!(
+                "Writing is only supported for dynamic VHDs, not {:?}",
+                footer.disk_type
+            )));
+        }
+
         // Handle different VHD types
         match footer.disk_type {
             VhdType::Fixed => {
@@ -85,7 +145,7 @@ impl VhdVault {
                 let pipeline = Box::new(PartialPipeline::new(base, 0, content_len)?);
 
                 Ok(Self {
-                    pipeline,
+                    pipeline: VhdBacking::ReadOnly(pipeline),
                     footer,
                     dynamic_header: None,
                     bat: None,
@@ -108,7 +168,7 @@ impl VhdVault {
 
                 // Verify dynamic header checksum
                 if !dynamic_header.verify_checksum() {
-                    return Err(totalimage_core::Error::invalid_vault(
+                    return Err(totalimage_core::Error::checksum_mismatch(
                         "VHD dynamic header checksum verification failed",
                     ));
                 }
@@ -122,19 +182,29 @@ impl VhdVault {
                 let bat = BlockAllocationTable::parse(&bat_bytes, dynamic_header.block_size)?;
 
                 // Create dynamic pipeline
-                let file = File::open(path)?;
-                let base: Box<dyn ReadSeek> = if config.use_mmap {
-                    Box::new(MmapPipeline::from_file(&file)?)
+                let pipeline = if config.writable {
+                    let file = OpenOptions::new().read(true).write(true).open(path)?;
+                    let mut pipeline = VhdDynamicPipeline::new(file, bat.clone(), footer.current_size)?;
+                    pipeline.allocator = Some(BlockAllocator {
+                        table_offset: dynamic_header.table_offset,
+                        footer: footer_bytes,
+                        footer_offset: file_len - VhdFooter::SIZE as u64,
+                    });
+                    VhdBacking::Writable(Box::new(pipeline))
                 } else {
-                    Box::new(file)
+                    let file = File::open(path)?;
+                    let base: Box<dyn ReadSeek> = if config.use_mmap {
+                        Box::new(MmapPipeline::from_file(&file)?)
+                    } else {
+                        Box::new(file)
+                    };
+                    VhdBacking::ReadOnly(Box::new(VhdDynamicPipeline::new(
+                        base,
+                        bat.clone(),
+                        footer.current_size,
+                    )?))
                 };
 
-                let pipeline = Box::new(VhdDynamicPipeline::new(
-                    base,
-                    bat.clone(),
-                    footer.current_size,
-                )?);
-
                 Ok(Self {
                     pipeline,
                     footer,
@@ -154,14 +224,34 @@ impl VhdVault {
         &self.footer
     }
 
+    /// Get the disk UUID from the footer in canonical GUID form
+    pub fn disk_uuid_string(&self) -> String {
+        self.footer.uuid_string()
+    }
+
+    /// Get the time the VHD was created
+    pub fn creation_time(&self) -> DateTime<Utc> {
+        self.footer.creation_time()
+    }
+
+    /// Get the application that created the VHD, e.g. "vpc" or "win"
+    pub fn creator_application(&self) -> String {
+        self.footer.creator_application()
+    }
+
     /// Get the dynamic header (if this is a dynamic/differencing VHD)
     pub fn dynamic_header(&self) -> Option<&VhdDynamicHeader> {
         self.dynamic_header.as_ref()
     }
 
     /// Get the block allocation table (if this is a dynamic/differencing VHD)
+    ///
+    /// Includes blocks allocated by writes since the VHD was opened.
     pub fn bat(&self) -> Option<&BlockAllocationTable> {
-        self.bat.as_ref()
+        match &self.pipeline {
+            VhdBacking::Writable(pipeline) => Some(&pipeline.bat),
+            VhdBacking::ReadOnly(_) => self.bat.as_ref(),
+        }
     }
 
     /// Check if this is a dynamic VHD
@@ -214,10 +304,11 @@ impl VhdVault {
         }
 
         // Seek to the locator data
-        self.pipeline.seek(SeekFrom::Start(locator.platform_data_offset))?;
+        let stream = self.content();
+        stream.seek(SeekFrom::Start(locator.platform_data_offset))?;
 
         let mut data = vec![0u8; locator.platform_data_length as usize];
-        self.pipeline.read_exact(&mut data)?;
+        stream.read_exact(&mut data)?;
 
         if locator.is_windows_unicode() {
             // UTF-16LE encoded path
@@ -268,6 +359,97 @@ impl VhdVault {
     }
 }
 
+/// How a differencing VHD's parent is checked against the child's record of it
+///
+/// The child stores its parent's UUID and modification time. A parent that
+/// doesn't match was replaced or changed after the child was made, so
+/// reads through the chain may be corrupt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParentCheck {
+    /// Refuse to open a chain with a mismatched parent instead of logging a
+    /// warning
+    pub strict: bool,
+    /// Also compare the parent file's modification time with the time the
+    /// child recorded
+    ///
+    /// Copying an image usually changes its modification time, so this is
+    /// off by default.
+    pub check_timestamp: bool,
+}
+
+/// Result of checking one parent in a VHD chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParentVerification {
+    /// Index in the chain of the differencing VHD; its parent is the next one
+    pub child_index: usize,
+    /// Parent UUID recorded in the child's dynamic header
+    pub expected_uuid: [u8; 16],
+    /// UUID in the parent's footer
+    pub actual_uuid: [u8; 16],
+    /// Whether the parent file's modification time matches the child's
+    /// record, or None if it wasn't checked or couldn't be read
+    pub timestamp_matches: Option<bool>,
+}
+
+impl ParentVerification {
+    /// Check whether the parent's UUID is the one the child expects
+    pub fn uuid_matches(&self) -> bool {
+        self.expected_uuid == self.actual_uuid
+    }
+
+    /// Check whether the parent passed every check made
+    pub fn is_match(&self) -> bool {
+        self.uuid_matches() && self.timestamp_matches != Some(false)
+    }
+}
+
+/// Check a parent VHD against the child that refers to it
+fn verify_parent(
+    child_index: usize,
+    child: &VhdVault,
+    parent: &VhdVault,
+    parent_path: &Path,
+    check: ParentCheck,
+) -> Result<ParentVerification> {
+    let header = child.dynamic_header.as_ref();
+    let verification = ParentVerification {
+        child_index,
+        expected_uuid: header.map(|h| h.parent_uuid).unwrap_or_default(),
+        actual_uuid: parent.footer.uuid,
+        timestamp_matches: if check.check_timestamp {
+            vhd_modification_time(parent_path).map(|time| Some(time) == header.map(|h| h.parent_timestamp))
+        } else {
+            None
+        },
+    };
+
+    if !verification.is_match() {
+        let message = if verification.uuid_matches() {
+            format!("Parent VHD {} was modified after its child was created", parent_path.display())
+        } else {
+            format!(
+                "Parent VHD {} has UUID {}, but its child expects {}",
+                parent_path.display(),
+                types::format_uuid(&verification.actual_uuid),
+                types::format_uuid(&verification.expected_uuid)
+            )
+        };
+        if check.strict {
+            return Err(totalimage_core::Error::invalid_vault(message));
+        }
+        tracing::warn!("{}; reads through the chain may be corrupt", message);
+    }
+
+    Ok(verification)
+}
+
+/// Get a file's modification time in seconds since the VHD epoch
+fn vhd_modification_time(path: &Path) -> Option<u32> {
+    let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+    let unix = modified.duration_since(std::time::UNIX_EPOCH).ok()?.as_secs();
+    u32::try_from(unix.checked_sub(VhdFooter::EPOCH_OFFSET as u64)?).ok()
+}
+
 /// VHD Chain Vault - Handles differencing VHDs with parent chains
 ///
 /// This vault resolves the parent chain and reads data from the
@@ -276,28 +458,48 @@ impl VhdVault {
 pub struct VhdChainVault {
     /// VHD chain from child (index 0) to root parent (last index)
     chain: Vec<VhdVault>,
+    /// Path of each VHD in the chain
+    paths: Vec<PathBuf>,
     /// Virtual disk size
     virtual_size: u64,
     /// Block size (from child VHD)
     block_size: u32,
     /// Current read position
     position: u64,
+    /// Result of checking each parent against its child
+    parent_checks: Vec<ParentVerification>,
 }
 
 impl VhdChainVault {
     /// Open a VHD chain starting from a differencing VHD
+    ///
+    /// Each parent's UUID, and optionally its modification time, is checked
+    /// against the child as `config.vhd_parent_check` directs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a VHD in the chain can't be opened, a parent
+    /// can't be found, the chain is too deep, or, with a strict parent
+    /// check, a parent doesn't match its child
     pub fn open(path: &Path, config: VaultConfig) -> Result<Self> {
-        let mut chain = Vec::new();
+        let mut chain: Vec<VhdVault> = Vec::new();
+        let mut paths = Vec::new();
+        let mut parent_checks = Vec::new();
         let mut current_path = path.to_path_buf();
 
         // Follow the parent chain until we reach a non-differencing VHD
         loop {
             let mut vault = VhdVault::open(&current_path, config.clone())?;
+            if let Some(child) = chain.last() {
+                let check = verify_parent(chain.len() - 1, child, &vault, &current_path, config.vhd_parent_check)?;
+                parent_checks.push(check);
+            }
 
             if vault.is_differencing() {
                 // Find parent path from locators
                 let parent_path = Self::find_parent_path(&mut vault, &current_path)?;
                 chain.push(vault);
+                paths.push(current_path.clone());
 
                 if let Some(parent) = parent_path {
                     current_path = parent;
@@ -309,6 +511,7 @@ impl VhdChainVault {
             } else {
                 // Non-differencing VHD (root of chain)
                 chain.push(vault);
+                paths.push(current_path);
                 break;
             }
 
@@ -330,12 +533,20 @@ impl VhdChainVault {
 
         Ok(Self {
             chain,
+            paths,
             virtual_size,
             block_size,
             position: 0,
+            parent_checks,
         })
     }
 
+    /// Get the result of checking each parent against its child, from the
+    /// child's parent outwards
+    pub fn parent_checks(&self) -> &[ParentVerification] {
+        &self.parent_checks
+    }
+
     /// Find the parent path from a differencing VHD
     fn find_parent_path(vault: &mut VhdVault, child_path: &Path) -> Result<Option<PathBuf>> {
         // First try the parent locators
@@ -393,9 +604,9 @@ impl VhdChainVault {
             ));
         }
 
-        let vault = &mut self.chain[chain_index];
-        vault.pipeline.seek(SeekFrom::Start(offset))?;
-        vault.pipeline.read(buf)
+        let stream = self.chain[chain_index].content();
+        stream.seek(SeekFrom::Start(offset))?;
+        stream.read(buf)
     }
 }
 
@@ -473,6 +684,18 @@ impl Vault for VhdChainVault {
         self.virtual_size
     }
 
+    fn backing_chain(&self) -> Vec<BackingLink> {
+        self.chain
+            .iter()
+            .zip(&self.paths)
+            .map(|(vault, path)| BackingLink {
+                path: Some(path.clone()),
+                format: vault.identify().to_string(),
+                virtual_size: vault.length(),
+            })
+            .collect()
+    }
+
     fn content(&mut self) -> &mut dyn ReadSeek {
         self
     }
@@ -497,10 +720,33 @@ impl Vault for VhdVault {
     }
 
     fn content(&mut self) -> &mut dyn ReadSeek {
-        &mut *self.pipeline
+        match &mut self.pipeline {
+            VhdBacking::ReadOnly(pipeline) => &mut **pipeline,
+            VhdBacking::Writable(pipeline) => &mut **pipeline,
+        }
+    }
+
+    fn content_mut(&mut self) -> Option<&mut dyn ReadWriteSeek> {
+        match &mut self.pipeline {
+            VhdBacking::ReadOnly(_) => None,
+            VhdBacking::Writable(pipeline) => Some(&mut **pipeline),
+        }
     }
 }
 
+/// Size of the sector bitmap preceding each block's data
+const BLOCK_BITMAP_SIZE: u64 = 512;
+
+/// Where and how to allocate new blocks in a writable dynamic VHD
+struct BlockAllocator {
+    /// File offset of the BAT
+    table_offset: u64,
+    /// Footer copied to the end of the file after each allocation
+    footer: [u8; VhdFooter::SIZE],
+    /// File offset of the trailing footer, where the next block goes
+    footer_offset: u64,
+}
+
 /// Pipeline for dynamic VHD files
 ///
 /// This pipeline translates virtual offsets to physical offsets using the BAT.
@@ -509,6 +755,8 @@ struct VhdDynamicPipeline<R: Read + Seek> {
     bat: BlockAllocationTable,
     virtual_size: u64,
     position: u64,
+    /// Set when the base is writable
+    allocator: Option<BlockAllocator>,
 }
 
 impl<R: Read + Seek> VhdDynamicPipeline<R> {
@@ -518,10 +766,44 @@ impl<R: Read + Seek> VhdDynamicPipeline<R> {
             bat,
             virtual_size,
             position: 0,
+            allocator: None,
         })
     }
 }
 
+impl<R: Read + Write + Seek> VhdDynamicPipeline<R> {
+    /// Allocate a sparse block in place of the trailing footer
+    ///
+    /// The block's bitmap marks every sector present and its data is
+    /// zeroed, so it reads back as it did while sparse. The footer moves to
+    /// the new end of file before the BAT entry is written. Returns the
+    /// block's file offset.
+    fn allocate_block(&mut self, block_index: usize) -> io::Result<u64> {
+        let allocator = self
+            .allocator
+            .as_mut()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::PermissionDenied, "VHD is not writable"))?;
+
+        let block_offset = allocator.footer_offset.div_ceil(512) * 512;
+        let sector = u32::try_from(block_offset / 512)
+            .ok()
+            .filter(|&sector| sector != u32::MAX)
+            .ok_or_else(|| io::Error::other("VHD has grown beyond the BAT's addressable size"))?;
+
+        self.base.seek(SeekFrom::Start(block_offset))?;
+        self.base.write_all(&[0xFF; BLOCK_BITMAP_SIZE as usize])?;
+        self.base.write_all(&vec![0u8; self.bat.block_size as usize])?;
+        self.base.write_all(&allocator.footer)?;
+
+        self.base.seek(SeekFrom::Start(allocator.table_offset + block_index as u64 * 4))?;
+        self.base.write_all(&sector.to_be_bytes())?;
+
+        allocator.footer_offset = block_offset + BLOCK_BITMAP_SIZE + self.bat.block_size as u64;
+        self.bat.entries[block_index] = sector;
+        Ok(block_offset)
+    }
+}
+
 impl<R: Read + Seek> Read for VhdDynamicPipeline<R> {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         if self.position >= self.virtual_size {
@@ -549,8 +831,7 @@ impl<R: Read + Seek> Read for VhdDynamicPipeline<R> {
             if let Some(physical_offset) = self.bat.get_block_offset(block_index) {
                 // Block is allocated: read from physical location
                 // Note: Each block has a 512-byte bitmap at the start
-                let bitmap_size = 512u64;
-                let physical_pos = physical_offset + bitmap_size + block_offset;
+                let physical_pos = physical_offset + BLOCK_BITMAP_SIZE + block_offset;
 
                 self.base.seek(SeekFrom::Start(physical_pos))?;
                 let bytes_read = self.base.read(&mut buf[total_read..total_read + chunk_size])?;
@@ -574,6 +855,42 @@ impl<R: Read + Seek> Read for VhdDynamicPipeline<R> {
     }
 }
 
+impl<R: Read + Write + Seek> Write for VhdDynamicPipeline<R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.position >= self.virtual_size {
+            return Ok(0);
+        }
+
+        let to_write = buf.len().min((self.virtual_size - self.position) as usize);
+        let mut total_written = 0;
+
+        while total_written < to_write {
+            let current_offset = self.position + total_written as u64;
+            let block_index = self.bat.offset_to_block(current_offset);
+            let block_offset = self.bat.offset_within_block(current_offset);
+            let remaining_in_block = self.bat.block_size as u64 - block_offset;
+            let chunk_size = ((to_write - total_written) as u64).min(remaining_in_block) as usize;
+
+            let physical_offset = match self.bat.get_block_offset(block_index) {
+                Some(offset) => offset,
+                None if block_index < self.bat.entries.len() => self.allocate_block(block_index)?,
+                None => return Err(io::Error::other("Write beyond the end of the BAT")),
+            };
+
+            self.base.seek(SeekFrom::Start(physical_offset + BLOCK_BITMAP_SIZE + block_offset))?;
+            self.base.write_all(&buf[total_written..total_written + chunk_size])?;
+            total_written += chunk_size;
+        }
+
+        self.position += total_written as u64;
+        Ok(total_written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.base.flush()
+    }
+}
+
 impl<R: Read + Seek> Seek for VhdDynamicPipeline<R> {
     fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
         let new_pos = match pos {
@@ -673,6 +990,42 @@ mod tests {
         footer
     }
 
+    #[test]
+    fn test_footer_uuid_and_timestamp() {
+        let mut footer = create_test_footer(1024, VhdType::Fixed);
+        footer.uuid = [
+            0x6B, 0xA7, 0xB8, 0x10, 0x9D, 0xAD, 0x11, 0xD1,
+            0x80, 0xB4, 0x00, 0xC0, 0x4F, 0xD4, 0x30, 0xC8,
+        ];
+        footer.timestamp = 0x2A5D_4E80; // 2022-07-10 08:46:56 UTC
+        footer.creator_app = *b"vpc ";
+        footer.checksum = footer.calculate_checksum();
+
+        let mut vhd_data: Vec<u8> = vec![0u8; 1024];
+        let mut footer_bytes = [0u8; VhdFooter::SIZE];
+        footer.serialize(&mut footer_bytes);
+        vhd_data.extend_from_slice(&footer_bytes);
+
+        let mut tmpfile = NamedTempFile::new().unwrap();
+        tmpfile.write_all(&vhd_data).unwrap();
+        tmpfile.flush().unwrap();
+
+        let vault = VhdVault::open(tmpfile.path(), VaultConfig::default()).unwrap();
+        assert_eq!(vault.disk_uuid_string(), "6BA7B810-9DAD-11D1-80B4-00C04FD430C8");
+        assert_eq!(vault.creator_application(), "vpc");
+        assert_eq!(
+            vault.creation_time().to_rfc3339(),
+            "2022-07-10T08:46:56+00:00"
+        );
+    }
+
+    #[test]
+    fn test_footer_zero_timestamp_is_vhd_epoch() {
+        let footer = create_test_footer(1024, VhdType::Fixed);
+        assert_eq!(footer.creation_time().to_rfc3339(), "2000-01-01T00:00:00+00:00");
+        assert_eq!(footer.creator_application(), "test");
+    }
+
     #[test]
     fn test_vhd_vault_fixed_open() {
         let vhd_data = create_test_fixed_vhd(1024);
@@ -732,7 +1085,7 @@ mod tests {
         tmpfile.flush().unwrap();
 
         let result = VhdVault::open(tmpfile.path(), VaultConfig::default());
-        assert!(result.is_err());
+        assert!(matches!(result, Err(totalimage_core::Error::UnrecognizedSignature(_))));
     }
 
     #[test]
@@ -749,11 +1102,55 @@ mod tests {
 
         let result = VhdVault::open(tmpfile.path(), VaultConfig::default());
         assert!(result.is_err());
-        if let Err(totalimage_core::Error::InvalidVault(msg)) = result {
-            assert!(msg.contains("checksum"));
+        match result {
+            Err(totalimage_core::Error::ChecksumVerification(msg)) => assert!(msg.contains("checksum")),
+            other => panic!("unexpected result: {:?}", other.err()),
         }
     }
 
+    #[test]
+    fn test_vhd_vault_dynamic_leading_footer_fallback() {
+        let block_size = 4096;
+        let mut vhd_data = create_test_dynamic_vhd(16384, block_size, &[1]);
+        let block_data: Vec<u8> = (0..block_size).map(|i| (i % 256) as u8).collect();
+        let trailing = vhd_data.len() - VhdFooter::SIZE;
+
+        // Damage the trailing footer's checksum, leaving the leading copy
+        vhd_data[trailing + 64] ^= 0xFF;
+        let mut tmpfile = NamedTempFile::new().unwrap();
+        tmpfile.write_all(&vhd_data).unwrap();
+        tmpfile.flush().unwrap();
+
+        let mut vault = VhdVault::open(tmpfile.path(), VaultConfig::default()).unwrap();
+        assert_eq!(vault.length(), 16384);
+        let mut buf = vec![0u8; block_size as usize];
+        vault.content().seek(SeekFrom::Start(block_size as u64)).unwrap();
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(buf, block_data);
+
+        // Appending blocks would overwrite data, so writing is refused
+        let writable = VaultConfig {
+            writable: true,
+            ..VaultConfig::default()
+        };
+        assert!(VhdVault::open(tmpfile.path(), writable).is_err());
+
+        // A file truncated mid-footer recovers the same way
+        vhd_data.truncate(trailing + 100);
+        let mut tmpfile = NamedTempFile::new().unwrap();
+        tmpfile.write_all(&vhd_data).unwrap();
+        tmpfile.flush().unwrap();
+        assert!(VhdVault::open(tmpfile.path(), VaultConfig::default()).is_ok());
+
+        // With both copies damaged the trailing footer's error is reported
+        vhd_data[64] ^= 0xFF;
+        let mut tmpfile = NamedTempFile::new().unwrap();
+        tmpfile.write_all(&vhd_data).unwrap();
+        tmpfile.flush().unwrap();
+        let result = VhdVault::open(tmpfile.path(), VaultConfig::default());
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_vhd_vault_file_too_small() {
         let mut tmpfile = NamedTempFile::new().unwrap();
@@ -761,7 +1158,7 @@ mod tests {
         tmpfile.flush().unwrap();
 
         let result = VhdVault::open(tmpfile.path(), VaultConfig::default());
-        assert!(result.is_err());
+        assert!(matches!(result, Err(totalimage_core::Error::Truncated(_))));
     }
 
     #[test]
@@ -813,19 +1210,40 @@ mod tests {
 
     /// Create a synthetic dynamic VHD for testing
     fn create_test_dynamic_vhd(virtual_size: u64, block_size: u32, allocated_blocks: &[usize]) -> Vec<u8> {
+        create_test_sparse_vhd(virtual_size, block_size, allocated_blocks, None)
+    }
+
+    /// Create a synthetic dynamic VHD, or a differencing VHD when given the
+    /// file name of its parent
+    fn create_test_sparse_vhd(
+        virtual_size: u64,
+        block_size: u32,
+        allocated_blocks: &[usize],
+        parent_name: Option<&str>,
+    ) -> Vec<u8> {
         let mut vhd = Vec::new();
 
         // Calculate number of blocks needed
         let block_count = ((virtual_size + block_size as u64 - 1) / block_size as u64) as u32;
 
         // Create footer (at beginning for dynamic VHD)
-        let footer = create_test_footer(virtual_size, VhdType::Dynamic);
+        let disk_type = match parent_name {
+            Some(_) => VhdType::Differencing,
+            None => VhdType::Dynamic,
+        };
+        let footer = create_test_footer(virtual_size, disk_type);
         let mut footer_bytes = [0u8; VhdFooter::SIZE];
         footer.serialize(&mut footer_bytes);
         vhd.extend_from_slice(&footer_bytes);
 
         // Create dynamic header
-        let dyn_header = create_test_dynamic_header(block_count, block_size);
+        let mut dyn_header = create_test_dynamic_header(block_count, block_size);
+        if let Some(name) = parent_name {
+            for (i, unit) in name.encode_utf16().enumerate() {
+                dyn_header.parent_unicode_name[i] = unit;
+            }
+            dyn_header.checksum = dyn_header.calculate_checksum();
+        }
         let mut dyn_header_bytes = [0u8; VhdDynamicHeader::SIZE];
         dyn_header.serialize(&mut dyn_header_bytes);
         vhd.extend_from_slice(&dyn_header_bytes);
@@ -979,6 +1397,67 @@ mod tests {
         assert_eq!(&buf[..], &[0u8; 100]);
     }
 
+    #[test]
+    fn test_vhd_vault_dynamic_write_allocates_block() {
+        let block_size = 4096;
+        let virtual_size = 16384;
+        let vhd_data = create_test_dynamic_vhd(virtual_size, block_size, &[0]);
+        let mut tmpfile = NamedTempFile::new().unwrap();
+        tmpfile.write_all(&vhd_data).unwrap();
+        tmpfile.flush().unwrap();
+
+        let writable = VaultConfig {
+            writable: true,
+            ..VaultConfig::default()
+        };
+        let mut vault = VhdVault::open(tmpfile.path(), writable).unwrap();
+        assert!(vault.bat().unwrap().get_block_offset(2).is_none());
+
+        // Write into block 0 (allocated) and across sparse blocks 2 and 3
+        let stream = vault.content_mut().unwrap();
+        stream.seek(SeekFrom::Start(10)).unwrap();
+        stream.write_all(b"header").unwrap();
+        stream.seek(SeekFrom::Start(3 * block_size as u64 - 4)).unwrap();
+        stream.write_all(b"spans two blocks").unwrap();
+        stream.flush().unwrap();
+
+        let mut buf = [0u8; 16];
+        vault.content().seek(SeekFrom::Start(3 * block_size as u64 - 4)).unwrap();
+        vault.content().read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"spans two blocks");
+        assert!(vault.bat().unwrap().get_block_offset(2).is_some());
+        drop(vault);
+
+        // Two blocks plus their bitmaps were added before the footer
+        let grown = std::fs::metadata(tmpfile.path()).unwrap().len();
+        assert_eq!(grown, vhd_data.len() as u64 + 2 * (512 + block_size as u64));
+
+        let mut vault = VhdVault::open(tmpfile.path(), VaultConfig::default()).unwrap();
+        assert!(vault.content_mut().is_none());
+
+        let mut disk = Vec::new();
+        vault.content().read_to_end(&mut disk).unwrap();
+        assert_eq!(disk.len(), virtual_size as usize);
+        assert_eq!(&disk[10..16], b"header");
+        assert_eq!(disk[9], 9);
+        assert_eq!(&disk[3 * block_size as usize - 4..3 * block_size as usize + 12], b"spans two blocks");
+        assert!(disk[block_size as usize..3 * block_size as usize - 4].iter().all(|&b| b == 0));
+        assert!(disk[3 * block_size as usize + 12..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_vhd_vault_writable_requires_dynamic() {
+        let mut tmpfile = NamedTempFile::new().unwrap();
+        tmpfile.write_all(&create_test_fixed_vhd(4096)).unwrap();
+        tmpfile.flush().unwrap();
+
+        let writable = VaultConfig {
+            writable: true,
+            ..VaultConfig::default()
+        };
+        assert!(VhdVault::open(tmpfile.path(), writable).is_err());
+    }
+
     #[test]
     fn test_vhd_vault_dynamic_cross_block_read() {
         let block_size = 4096; // Small blocks for testing
@@ -1020,11 +1499,96 @@ mod tests {
 
         let result = VhdVault::open(tmpfile.path(), VaultConfig::default());
         assert!(result.is_err());
-        if let Err(totalimage_core::Error::InvalidVault(msg)) = result {
-            assert!(msg.contains("checksum"));
+        match result {
+            Err(totalimage_core::Error::ChecksumVerification(msg)) => assert!(msg.contains("checksum")),
+            other => panic!("unexpected result: {:?}", other.err()),
         }
     }
 
+    #[test]
+    fn test_vhd_chain_backing_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().join("base.vhd");
+        let child_path = dir.path().join("child.vhd");
+        std::fs::write(&base_path, create_test_dynamic_vhd(4096, 2048, &[0, 1])).unwrap();
+        std::fs::write(&child_path, create_test_sparse_vhd(4096, 2048, &[1], Some("base.vhd"))).unwrap();
+
+        let vault = VhdVault::open_with_parents(&child_path, VaultConfig::default()).unwrap();
+        assert_eq!(vault.chain_depth(), 2);
+
+        let chain = vault.backing_chain();
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].path.as_deref(), Some(child_path.as_path()));
+        assert_eq!(chain[0].format, "Microsoft VHD (Differencing)");
+        assert_eq!(chain[1].path.as_deref(), Some(base_path.as_path()));
+        assert_eq!(chain[1].format, "Microsoft VHD (Dynamic)");
+        assert!(chain.iter().all(|link| link.virtual_size == 4096));
+
+        // A vault that stands alone is its own chain
+        let base = VhdVault::open(&base_path, VaultConfig::default()).unwrap();
+        let chain = base.backing_chain();
+        assert_eq!(chain.len(), 1);
+        assert_eq!(chain[0].format, "Microsoft VHD (Dynamic)");
+        assert_eq!(chain[0].virtual_size, 4096);
+    }
+
+    #[test]
+    fn test_vhd_chain_parent_verification() {
+        let dir = tempfile::tempdir().unwrap();
+        let base_path = dir.path().join("base.vhd");
+        let child_path = dir.path().join("child.vhd");
+        std::fs::write(&base_path, create_test_dynamic_vhd(4096, 2048, &[0, 1])).unwrap();
+
+        // Rewrite the child's record of its parent
+        let write_child = |parent_uuid: [u8; 16], parent_timestamp: u32| {
+            let mut child = create_test_sparse_vhd(4096, 2048, &[1], Some("base.vhd"));
+            let header_bytes: &mut [u8; VhdDynamicHeader::SIZE] = (&mut child[512..1536]).try_into().unwrap();
+            let mut header = VhdDynamicHeader::parse(header_bytes).unwrap();
+            header.parent_uuid = parent_uuid;
+            header.parent_timestamp = parent_timestamp;
+            header.checksum = header.calculate_checksum();
+            header.serialize(header_bytes);
+            std::fs::write(&child_path, child).unwrap();
+        };
+
+        // The test footers all carry the nil UUID
+        write_child([0; 16], 0);
+        let vault = VhdVault::open_with_parents(&child_path, VaultConfig::default()).unwrap();
+        assert_eq!(vault.parent_checks().len(), 1);
+        assert!(vault.parent_checks()[0].is_match());
+        assert_eq!(vault.parent_checks()[0].timestamp_matches, None);
+
+        // A parent with another UUID is reported, and refused when strict
+        write_child([0xAB; 16], 0);
+        let vault = VhdVault::open_with_parents(&child_path, VaultConfig::default()).unwrap();
+        let check = &vault.parent_checks()[0];
+        assert_eq!(check.child_index, 0);
+        assert_eq!(check.expected_uuid, [0xAB; 16]);
+        assert_eq!(check.actual_uuid, [0; 16]);
+        assert!(!check.uuid_matches());
+        assert!(!check.is_match());
+
+        let strict = VaultConfig {
+            vhd_parent_check: ParentCheck { strict: true, check_timestamp: false },
+            ..VaultConfig::default()
+        };
+        let err = VhdVault::open_with_parents(&child_path, strict).err().unwrap();
+        assert!(err.to_string().contains("ABABABAB-ABAB-ABAB-ABAB-ABABABABABAB"), "{}", err);
+
+        // Timestamps are only compared when asked
+        let timestamps = VaultConfig {
+            vhd_parent_check: ParentCheck { strict: true, check_timestamp: true },
+            ..VaultConfig::default()
+        };
+        write_child([0; 16], 0);
+        let err = VhdVault::open_with_parents(&child_path, timestamps.clone()).err().unwrap();
+        assert!(err.to_string().contains("modified after"), "{}", err);
+
+        write_child([0; 16], vhd_modification_time(&base_path).unwrap());
+        let vault = VhdVault::open_with_parents(&child_path, timestamps).unwrap();
+        assert_eq!(vault.parent_checks()[0].timestamp_matches, Some(true));
+    }
+
     #[test]
     fn test_vhd_dynamic_pipeline_seek() {
         let block_size = 4096;
@@ -2,6 +2,7 @@
 //!
 //! This module contains the core data structures for parsing Microsoft VHD files.
 
+use chrono::{DateTime, Utc};
 use totalimage_core::Result;
 
 /// VHD disk type enumeration
@@ -93,10 +94,13 @@ impl VhdFooter {
     /// Size of the VHD footer in bytes
     pub const SIZE: usize = 512;
 
+    /// Seconds between the Unix epoch and the VHD epoch (2000-01-01)
+    pub(crate) const EPOCH_OFFSET: i64 = 946_684_800;
+
     /// Parse VHD footer from raw bytes
     pub fn parse(bytes: &[u8]) -> Result<Self> {
         if bytes.len() < Self::SIZE {
-            return Err(totalimage_core::Error::invalid_vault(
+            return Err(totalimage_core::Error::truncated(
                 "VHD footer too small",
             ));
         }
@@ -107,7 +111,7 @@ impl VhdFooter {
 
         // Verify cookie
         if &cookie != Self::COOKIE {
-            return Err(totalimage_core::Error::invalid_vault(format!(
+            return Err(totalimage_core::Error::unrecognized_signature(format!(
                 "Invalid VHD footer cookie: expected 'conectix', got '{}'",
                 String::from_utf8_lossy(&cookie)
             )));
@@ -176,6 +180,11 @@ impl VhdFooter {
     /// The checksum is the one's complement of the sum of all bytes in the
     /// footer, with the checksum field itself set to zero during calculation.
     pub fn verify_checksum(&self) -> bool {
+        self.calculate_checksum() == self.checksum
+    }
+
+    /// Calculate the checksum the footer's current fields should carry
+    pub fn calculate_checksum(&self) -> u32 {
         // Serialize footer back to bytes
         let mut bytes = [0u8; Self::SIZE];
         self.serialize(&mut bytes);
@@ -191,9 +200,7 @@ impl VhdFooter {
         }
 
         // One's complement
-        let calculated = !sum;
-
-        calculated == self.checksum
+        !sum
     }
 
     /// Serialize footer to bytes
@@ -215,6 +222,33 @@ impl VhdFooter {
         bytes[84] = self.saved_state;
         bytes[85..512].copy_from_slice(&self.reserved);
     }
+
+    /// Get the disk UUID in canonical `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX` form
+    ///
+    /// VHD stores the UUID as 16 big-endian bytes.
+    pub fn uuid_string(&self) -> String {
+        format_uuid(&self.uuid)
+    }
+
+    /// Get the creation time
+    ///
+    /// The footer timestamp counts seconds since 2000-01-01 00:00:00 UTC.
+    pub fn creation_time(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp(Self::EPOCH_OFFSET + self.timestamp as i64, 0)
+            .unwrap_or_default()
+    }
+
+    /// Get the creator application, e.g. "vpc " or "win ", with padding trimmed
+    pub fn creator_application(&self) -> String {
+        String::from_utf8_lossy(&self.creator_app)
+            .trim_end_matches(['\0', ' '])
+            .to_string()
+    }
+}
+
+/// Format a big-endian VHD UUID in canonical `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX` form
+pub(crate) fn format_uuid(uuid: &[u8; 16]) -> String {
+    uuid::Uuid::from_bytes(*uuid).hyphenated().to_string().to_uppercase()
 }
 
 /// VHD Dynamic Header structure (1024 bytes)
@@ -248,7 +282,7 @@ impl VhdDynamicHeader {
     /// Parse VHD dynamic header from raw bytes
     pub fn parse(bytes: &[u8]) -> Result<Self> {
         if bytes.len() < Self::SIZE {
-            return Err(totalimage_core::Error::invalid_vault(
+            return Err(totalimage_core::Error::truncated(
                 "VHD dynamic header too small",
             ));
         }
@@ -259,7 +293,7 @@ impl VhdDynamicHeader {
 
         // Verify cookie
         if &cookie != Self::COOKIE {
-            return Err(totalimage_core::Error::invalid_vault(format!(
+            return Err(totalimage_core::Error::unrecognized_signature(format!(
                 "Invalid VHD dynamic header cookie: expected 'cxsparse', got '{}'",
                 String::from_utf8_lossy(&cookie)
             )));
@@ -322,6 +356,11 @@ impl VhdDynamicHeader {
 
     /// Verify the dynamic header checksum
     pub fn verify_checksum(&self) -> bool {
+        self.calculate_checksum() == self.checksum
+    }
+
+    /// Calculate the checksum the header's current fields should carry
+    pub fn calculate_checksum(&self) -> u32 {
         // Serialize header back to bytes
         let mut bytes = [0u8; Self::SIZE];
         self.serialize(&mut bytes);
@@ -337,9 +376,7 @@ impl VhdDynamicHeader {
         }
 
         // One's complement
-        let calculated = !sum;
-
-        calculated == self.checksum
+        !sum
     }
 
     /// Serialize dynamic header to bytes
@@ -579,20 +616,26 @@ mod tests {
     fn test_vhd_footer_invalid_cookie() {
         let mut bytes = [0u8; 512];
         bytes[0..8].copy_from_slice(b"notvalid");
-        assert!(VhdFooter::parse(&bytes).is_err());
+        assert!(matches!(
+            VhdFooter::parse(&bytes),
+            Err(totalimage_core::Error::UnrecognizedSignature(_))
+        ));
     }
 
     #[test]
     fn test_vhd_footer_too_small() {
         let bytes = [0u8; 100];
-        assert!(VhdFooter::parse(&bytes).is_err());
+        assert!(matches!(VhdFooter::parse(&bytes), Err(totalimage_core::Error::Truncated(_))));
     }
 
     #[test]
     fn test_vhd_dynamic_header_invalid_cookie() {
         let mut bytes = [0u8; 1024];
         bytes[0..8].copy_from_slice(b"notvalid");
-        assert!(VhdDynamicHeader::parse(&bytes).is_err());
+        assert!(matches!(
+            VhdDynamicHeader::parse(&bytes),
+            Err(totalimage_core::Error::UnrecognizedSignature(_))
+        ));
     }
 
     #[test]
@@ -166,7 +166,6 @@ impl MetadataCache {
     }
 
     /// Get directory listing from cache
-    #[allow(dead_code)] // Reserved for future directory caching feature
     pub fn get_dir_listing<T>(&self, path: &str) -> Result<Option<T>, Box<dyn std::error::Error>>
     where
         T: for<'de> Deserialize<'de>,
@@ -191,7 +190,6 @@ impl MetadataCache {
     }
 
     /// Set directory listing in cache
-    #[allow(dead_code)] // Reserved for future directory caching feature
     pub fn set_dir_listing<T>(
         &self,
         path: &str,
@@ -7,8 +7,8 @@ mod cache;
 
 use axum::{
     extract::{Query, State},
-    http::StatusCode,
-    response::{IntoResponse, Json},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    response::{IntoResponse, Json, Response},
     routing::get,
     Router,
 };
@@ -16,14 +16,36 @@ use cache::MetadataCache;
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use std::sync::Arc;
-use totalimage_core::{validate_file_path, Result as TotalImageResult, ZoneTable};
-use totalimage_vaults::{open_vault, VaultConfig};
-use totalimage_zones::{GptZoneTable, MbrZoneTable};
+use totalimage_core::{
+    validate_file_path, DirectoryPage, Error, ListOptions, OccupantInfo, ReadSeek, Result as TotalImageResult,
+    Territory,
+};
+use totalimage_territories::{open_territory_in_zone, probe};
+use totalimage_vaults::{open_vault, VaultConfig, VaultType};
+use totalimage_zones::{detect_zone_table, DetectedZoneTable};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// Environment variable holding the comma-separated origins allowed by CORS
+const CORS_ORIGINS_VAR: &str = "TOTALIMAGE_WEB_CORS_ORIGINS";
+
+/// Environment variable overriding the largest listing `limit` a request may apply
+const MAX_LIMIT_VAR: &str = "TOTALIMAGE_WEB_MAX_LIMIT";
+
+/// Largest number of entries one listing response may hold, unless
+/// overridden through [`MAX_LIMIT_VAR`]
+const DEFAULT_MAX_LIMIT: usize = 1000;
+
+/// Upper bound on directory entries visited by one stats walk
+const MAX_WALK_NODES: usize = 100_000;
 
 /// Shared application state
 #[derive(Clone)]
 struct AppState {
     cache: Arc<MetadataCache>,
+    /// Largest listing `limit` a request may apply; larger ones are clamped
+    max_limit: usize,
+    /// Largest stats walk `limit` a request may apply; larger ones are clamped
+    max_walk_nodes: usize,
 }
 
 #[tokio::main]
@@ -66,23 +88,24 @@ async fn main() {
         }
     };
 
-    let state = AppState { cache };
+    let max_limit = max_limit(std::env::var(MAX_LIMIT_VAR).ok().as_deref());
+    let state = AppState {
+        cache,
+        max_limit,
+        max_walk_nodes: MAX_WALK_NODES,
+    };
 
     // TODO: Production hardening (SEC-007)
     // - Add rate limiting: tower::limit::RateLimitLayer
     // - Add request timeouts: tower::timeout::TimeoutLayer (30s)
     // - Add concurrency limits: tower::limit::ConcurrencyLimitLayer (10)
-    // - Configure CORS policy for API access
     // - Add request size limits (10 MB max)
     // - Enable TLS/HTTPS support
     // See: steering/GAP-ANALYSIS.md#SEC-007
 
     // Build application routes
-    let app = Router::new()
-        .route("/health", get(health))
-        .route("/api/vault/info", get(vault_info))
-        .route("/api/vault/zones", get(vault_zones))
-        .with_state(state);
+    let cors = cors_layer(std::env::var(CORS_ORIGINS_VAR).ok().as_deref());
+    let app = app(state).layer(cors);
 
     // Run server
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
@@ -92,18 +115,130 @@ async fn main() {
     println!();
     println!("   Endpoints:");
     println!("   - GET  /health");
+    println!("   - GET  /api/formats");
     println!("   - GET  /api/vault/info?path=<image_file>");
     println!("   - GET  /api/vault/zones?path=<image_file>");
+    println!("   - GET  /api/vault/files?path=<image_file>&zone=<index>&dir=<directory>&sort=<key>&filter=<glob>&since=<date>");
+    println!("   - GET  /api/vault/stats?path=<image_file>&zone=<index>&dir=<directory>&limit=<entries>");
+    println!("   - GET  /api/vault/file?path=<image_file>&zone=<index>&file=<file_path>");
 
     let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
     axum::serve(listener, app).await.unwrap();
 }
 
+/// Build the application router
+fn app(state: AppState) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/api/formats", get(formats))
+        .route("/api/vault/info", get(vault_info))
+        .route("/api/vault/zones", get(vault_zones))
+        .route("/api/vault/files", get(vault_files))
+        .route("/api/vault/stats", get(vault_stats))
+        .route("/api/vault/file", get(vault_file))
+        .with_state(state)
+}
+
+/// Build the CORS policy from a comma-separated list of allowed origins
+///
+/// Origins that aren't valid header values are skipped with a warning.
+/// Without a list any origin is allowed, which is only fit for local use.
+fn cors_layer(origins: Option<&str>) -> CorsLayer {
+    let cors = CorsLayer::new()
+        .allow_methods([Method::GET])
+        .allow_headers(Any);
+
+    let Some(origins) = origins else {
+        tracing::warn!(
+            "{} is not set; allowing cross-origin requests from any origin",
+            CORS_ORIGINS_VAR
+        );
+        return cors.allow_origin(Any);
+    };
+
+    let origins: Vec<HeaderValue> = origins
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .filter_map(|origin| match HeaderValue::from_str(origin) {
+            // A wildcard is only meaningful on its own, as the unset default
+            Ok(value) if origin != "*" => Some(value),
+            _ => {
+                tracing::warn!("Ignoring invalid CORS origin: {:?}", origin);
+                None
+            }
+        })
+        .collect();
+
+    tracing::info!("Allowing cross-origin requests from {} origin(s)", origins.len());
+    cors.allow_origin(AllowOrigin::list(origins))
+}
+
+/// Parse the configured maximum listing `limit`
+///
+/// Falls back to [`DEFAULT_MAX_LIMIT`] when unset, and warns when the value
+/// isn't a positive number.
+fn max_limit(value: Option<&str>) -> usize {
+    let Some(value) = value else {
+        return DEFAULT_MAX_LIMIT;
+    };
+
+    match value.trim().parse() {
+        Ok(limit) if limit > 0 => limit,
+        _ => {
+            tracing::warn!("Ignoring invalid {}: {:?}", MAX_LIMIT_VAR, value);
+            DEFAULT_MAX_LIMIT
+        }
+    }
+}
+
 /// Health check endpoint
 async fn health() -> &'static str {
     "OK"
 }
 
+/// Partition tables totalimage-zones can parse
+const ZONE_TABLES: [&str; 4] = ["MBR", "GPT", "APM", "BSD disklabel"];
+
+/// A format the server can parse
+#[derive(Serialize, Deserialize, Clone)]
+struct FormatInfo {
+    name: String,
+    /// Whether the format can be written as well as read
+    read_write: bool,
+}
+
+/// Supported formats response
+#[derive(Serialize, Deserialize, Clone)]
+struct FormatsResponse {
+    vaults: Vec<FormatInfo>,
+    zone_tables: Vec<FormatInfo>,
+    territories: Vec<FormatInfo>,
+}
+
+/// GET /api/formats
+///
+/// Lists the image, partition table and file system formats compiled in.
+/// Only raw images and FAT file systems can be written.
+async fn formats() -> Json<FormatsResponse> {
+    let format = |name: &str, read_write| FormatInfo {
+        name: name.to_string(),
+        read_write,
+    };
+
+    Json(FormatsResponse {
+        vaults: VaultType::ALL
+            .iter()
+            .map(|vault| format(vault.name(), vault.is_writable()))
+            .collect(),
+        zone_tables: ZONE_TABLES.iter().map(|table| format(table, false)).collect(),
+        territories: probe::SUPPORTED
+            .iter()
+            .map(|&kind| format(kind, kind == probe::FAT))
+            .collect(),
+    })
+}
+
 /// Query parameters for vault endpoints
 #[derive(Deserialize)]
 struct VaultQuery {
@@ -143,6 +278,152 @@ struct ZoneInfo {
     zone_type: String,
 }
 
+/// Query parameters for directory listings
+#[derive(Deserialize)]
+struct FilesQuery {
+    path: String,
+    /// Zone holding the file system; the whole image when unpartitioned
+    #[serde(default)]
+    zone: usize,
+    /// Directory to list, the root by default
+    #[serde(default)]
+    dir: String,
+    /// `name`, `size` or `mtime`, prefixed with `-` to sort descending;
+    /// on-disk order by default
+    sort: Option<String>,
+    /// Glob the names must match, e.g. `*.txt`
+    filter: Option<String>,
+    /// Only entries modified after this RFC 3339 timestamp or date
+    since: Option<String>,
+    /// Cursor from a previous page's `next`; the start by default
+    #[serde(default)]
+    cursor: u64,
+    /// Entries per page; clamped to the server's maximum, which is also
+    /// the default. A limit of 0 is rejected with 400, as it would never
+    /// move the cursor on
+    limit: Option<usize>,
+    /// Skip damaged entries instead of failing the listing
+    #[serde(default)]
+    best_effort: bool,
+}
+
+/// Directory listing response
+#[derive(Serialize, Deserialize, Clone)]
+struct DirectoryListingResponse {
+    dir: String,
+    files: Vec<OccupantInfo>,
+    /// Cursor of the next page, when `limit` cut the listing short
+    next: Option<u64>,
+    /// Damaged entries a best-effort listing skipped
+    #[serde(default)]
+    errors: usize,
+    /// Path and error message of each skip counted in `errors`
+    #[serde(default)]
+    skipped: Vec<(String, String)>,
+    /// `limit` as given in the request
+    requested_limit: Option<usize>,
+    /// Page size used, after clamping to the server's maximum
+    applied_limit: usize,
+}
+
+/// Query parameters for directory statistics
+#[derive(Deserialize)]
+struct StatsQuery {
+    path: String,
+    /// Zone holding the file system; the whole image when unpartitioned
+    #[serde(default)]
+    zone: usize,
+    /// Directory to summarize, the root by default
+    #[serde(default)]
+    dir: String,
+    /// Most entries to visit; clamped to the server's walk maximum, which
+    /// is also the default
+    limit: Option<usize>,
+}
+
+/// Recursive statistics for a directory
+#[derive(Serialize, Deserialize, Clone, Default)]
+struct DirectoryStatsResponse {
+    file_count: u64,
+    dir_count: u64,
+    total_bytes: u64,
+    largest_file: Option<LargestFile>,
+    /// The walk stopped at `applied_limit` entries; counts are partial
+    truncated: bool,
+    /// `limit` as given in the request
+    requested_limit: Option<usize>,
+    /// Limit the walk ran with, after clamping to the server's maximum
+    applied_limit: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct LargestFile {
+    path: String,
+    size: u64,
+}
+
+/// Query parameters for file downloads
+#[derive(Deserialize)]
+struct FileQuery {
+    path: String,
+    /// Zone holding the file system; the whole image when unpartitioned
+    #[serde(default)]
+    zone: usize,
+    /// Path of the file within the file system
+    file: String,
+}
+
+/// What a `Range` request header asks of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RangeRequest {
+    /// No usable range; send the whole file
+    Full,
+    /// Send bytes `start..=end`
+    Partial { start: u64, end: u64 },
+    /// The range lies wholly beyond the end of the file
+    Unsatisfiable,
+}
+
+impl RangeRequest {
+    /// Interpret a `Range` header for a file of `size` bytes
+    ///
+    /// Handles a single `bytes=start-end`, `bytes=start-` or `bytes=-suffix`
+    /// range. Headers that are malformed or list several ranges are ignored,
+    /// as RFC 9110 allows, and the whole file is sent.
+    fn parse(header: Option<&str>, size: u64) -> Self {
+        let Some(spec) = header.and_then(|h| h.trim().strip_prefix("bytes=")) else {
+            return Self::Full;
+        };
+        let Some((first, last)) = spec.trim().split_once('-') else {
+            return Self::Full;
+        };
+        if last.contains(',') {
+            return Self::Full;
+        }
+
+        let (first, last) = (first.trim(), last.trim());
+        let (start, end) = match (first.parse::<u64>(), last.parse::<u64>()) {
+            (Ok(start), Ok(end)) if start <= end => (start, end),
+            (Ok(start), Err(_)) if last.is_empty() => (start, u64::MAX),
+            (Err(_), Ok(suffix)) if first.is_empty() => {
+                if suffix == 0 {
+                    return Self::Unsatisfiable;
+                }
+                (size.saturating_sub(suffix), u64::MAX)
+            }
+            _ => return Self::Full,
+        };
+
+        if start >= size {
+            return Self::Unsatisfiable;
+        }
+        Self::Partial {
+            start,
+            end: end.min(size - 1),
+        }
+    }
+}
+
 /// GET /api/vault/info?path=<image_file>
 async fn vault_info(
     State(state): State<AppState>,
@@ -217,21 +498,18 @@ fn get_vault_info(image_path: &str) -> TotalImageResult<VaultInfoResponse> {
 
     // Try to parse partition table
     let sector_size = 512;
-    let partition_table = if let Ok(mbr) = MbrZoneTable::parse(vault.content(), sector_size) {
-        Some(PartitionTableInfo {
-            table_type: mbr.identify().to_string(),
-            partition_count: mbr.enumerate_zones().len(),
-            disk_signature: Some(format!("0x{:08X}", mbr.disk_signature())),
-        })
-    } else if let Ok(gpt) = GptZoneTable::parse(vault.content(), sector_size) {
-        Some(PartitionTableInfo {
-            table_type: gpt.identify().to_string(),
-            partition_count: gpt.enumerate_zones().len(),
-            disk_signature: None,
-        })
-    } else {
-        None
-    };
+    let partition_table = detect_zone_table(vault.content(), sector_size).map(|table| {
+        let disk_signature = match &table {
+            DetectedZoneTable::Mbr(mbr) => Some(format!("0x{:08X}", mbr.disk_signature())),
+            _ => None,
+        };
+        let table = table.as_zone_table();
+        PartitionTableInfo {
+            table_type: table.identify().to_string(),
+            partition_count: table.enumerate_zones().len(),
+            disk_signature,
+        }
+    });
 
     Ok(VaultInfoResponse {
         path: image_path.to_string(),
@@ -248,26 +526,10 @@ fn get_vault_zones(image_path: &str) -> TotalImageResult<VaultZonesResponse> {
 
     let sector_size = 512;
 
-    // Try MBR first
-    if let Ok(mbr) = MbrZoneTable::parse(vault.content(), sector_size) {
-        let zones = mbr
-            .enumerate_zones()
-            .iter()
-            .map(|z| ZoneInfo {
-                index: z.index,
-                offset: z.offset,
-                length: z.length,
-                zone_type: z.zone_type.clone(),
-            })
-            .collect();
-
-        Ok(VaultZonesResponse {
-            path: image_path.to_string(),
-            partition_table: mbr.identify().to_string(),
-            zones,
-        })
-    } else if let Ok(gpt) = GptZoneTable::parse(vault.content(), sector_size) {
-        let zones = gpt
+    // A GPT-protective MBR is reported as the GPT behind it
+    if let Some(table) = detect_zone_table(vault.content(), sector_size) {
+        let table = table.as_zone_table();
+        let zones = table
             .enumerate_zones()
             .iter()
             .map(|z| ZoneInfo {
@@ -280,7 +542,7 @@ fn get_vault_zones(image_path: &str) -> TotalImageResult<VaultZonesResponse> {
 
         Ok(VaultZonesResponse {
             path: image_path.to_string(),
-            partition_table: gpt.identify().to_string(),
+            partition_table: table.identify().to_string(),
             zones,
         })
     } else {
@@ -291,3 +553,564 @@ fn get_vault_zones(image_path: &str) -> TotalImageResult<VaultZonesResponse> {
         })
     }
 }
+
+/// GET /api/vault/files?path=<image_file>&zone=<index>&dir=<directory>&sort=<key>&filter=<glob>&since=<date>&cursor=<next>&limit=<n>&best_effort=<bool>
+///
+/// Unsorted, unfiltered pages resume from the cursor's position in the
+/// directory rather than re-reading it from the start. A sorted, filtered
+/// or best-effort listing is built whole and the cursor indexes into it.
+async fn vault_files(State(state): State<AppState>, Query(params): Query<FilesQuery>) -> Response {
+    let dir = format!("/{}", params.dir.trim_matches(['/', '\\']));
+    if params.limit == Some(0) {
+        return error_response(Error::InvalidOperation("limit must be at least 1".to_string()));
+    }
+    let applied_limit = params.limit.map_or(state.max_limit, |limit| limit.min(state.max_limit));
+    let result = ListOptions::parse(params.sort.as_deref(), params.filter.as_deref(), params.since.as_deref())
+        .and_then(|options| {
+            let options = ListOptions { best_effort: params.best_effort, ..options };
+            with_territory(&params.path, params.zone, |territory, stream| {
+                if options == ListOptions::default() {
+                    return Ok((territory.list_directory_page(stream, &dir, params.cursor, applied_limit)?, Vec::new()));
+                }
+                let listing = territory.list_directory_partial(stream, &dir, &options)?;
+                let page = DirectoryPage::from_listing(listing.occupants, params.cursor, applied_limit);
+                Ok((page, listing.skipped))
+            })
+        });
+
+    match result {
+        Ok((page, skipped)) => (
+            StatusCode::OK,
+            Json(DirectoryListingResponse {
+                dir,
+                files: page.occupants,
+                next: page.next,
+                errors: skipped.len(),
+                skipped,
+                requested_limit: params.limit,
+                applied_limit,
+            }),
+        )
+            .into_response(),
+        Err(e) => error_response(e),
+    }
+}
+
+/// GET /api/vault/stats?path=<image_file>&zone=<index>&dir=<directory>
+async fn vault_stats(
+    State(state): State<AppState>,
+    Query(params): Query<StatsQuery>,
+) -> impl IntoResponse {
+    let dir = format!("/{}", params.dir.trim_matches(['/', '\\']));
+    let applied_limit = params.limit.map_or(state.max_walk_nodes, |limit| limit.min(state.max_walk_nodes));
+    let cache_key = format!("stats:{}:{}:{}:{}", params.path, params.zone, dir, applied_limit);
+
+    // Check cache first
+    if let Ok(Some(mut cached_stats)) = state.cache.get_dir_listing::<DirectoryStatsResponse>(&cache_key) {
+        tracing::info!("Cache HIT for stats: {}", cache_key);
+        cached_stats.requested_limit = params.limit;
+        return (StatusCode::OK, Json(cached_stats)).into_response();
+    }
+
+    tracing::info!("Cache MISS for stats: {}", cache_key);
+
+    match get_directory_stats(&params.path, params.zone, &dir, applied_limit) {
+        Ok(mut stats) => {
+            stats.requested_limit = params.limit;
+            // Store in cache
+            if let Err(e) = state.cache.set_dir_listing(&cache_key, &stats) {
+                tracing::warn!("Failed to cache stats: {}", e);
+            }
+            (StatusCode::OK, Json(stats)).into_response()
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+/// GET /api/vault/file?path=<image_file>&zone=<index>&file=<file_path>
+///
+/// Honors a single-range `Range` header with `206 Partial Content`.
+async fn vault_file(Query(params): Query<FileQuery>, headers: HeaderMap) -> Response {
+    let range = headers.get(header::RANGE).and_then(|value| value.to_str().ok());
+
+    let result = with_territory(&params.path, params.zone, |territory, stream| {
+        let info = territory.file_metadata(stream, &params.file)?;
+        if info.is_directory {
+            return Err(Error::InvalidOperation(format!("Path is a directory: {}", params.file)));
+        }
+
+        let request = RangeRequest::parse(range, info.size);
+        let data = match request {
+            RangeRequest::Full => territory.read_file_range(stream, &params.file, 0, info.size)?,
+            RangeRequest::Partial { start, end } => {
+                territory.read_file_range(stream, &params.file, start, end - start + 1)?
+            }
+            RangeRequest::Unsatisfiable => Vec::new(),
+        };
+        Ok((info.size, request, data))
+    });
+
+    let (size, request, data) = match result {
+        Ok(file) => file,
+        Err(e) => return error_response(e),
+    };
+
+    let headers = [
+        (header::ACCEPT_RANGES, "bytes".to_string()),
+        (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+    ];
+    match request {
+        RangeRequest::Full => (StatusCode::OK, headers, data).into_response(),
+        RangeRequest::Partial { start, end } => (
+            StatusCode::PARTIAL_CONTENT,
+            headers,
+            [(header::CONTENT_RANGE, format!("bytes {}-{}/{}", start, end, size))],
+            data,
+        )
+            .into_response(),
+        RangeRequest::Unsatisfiable => (
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            [(header::CONTENT_RANGE, format!("bytes */{}", size))],
+        )
+            .into_response(),
+    }
+}
+
+/// Build a JSON error response with a status matching the error
+fn error_response(e: Error) -> Response {
+    let status = match e {
+        Error::NotFound(_) => StatusCode::NOT_FOUND,
+        Error::InvalidOperation(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (
+        status,
+        Json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    )
+        .into_response()
+}
+
+fn get_directory_stats(
+    image_path: &str,
+    zone_index: usize,
+    dir: &str,
+    max_nodes: usize,
+) -> TotalImageResult<DirectoryStatsResponse> {
+    with_territory(image_path, zone_index, |territory, stream| {
+        walk_directory_stats(territory, stream, dir, max_nodes)
+    })
+}
+
+/// Open the file system in a zone of an image and run `f` on it
+///
+/// `f` gets the territory along with the zone's stream to read it through.
+fn with_territory<T>(
+    image_path: &str,
+    zone_index: usize,
+    f: impl FnOnce(&dyn Territory, &mut dyn ReadSeek) -> TotalImageResult<T>,
+) -> TotalImageResult<T> {
+    // Validate path to prevent path traversal attacks
+    let path = validate_file_path(image_path)?;
+    let vault = open_vault(&path, VaultConfig::default())?;
+    let mut opened = open_territory_in_zone(vault, zone_index)?;
+
+    f(opened.territory.as_ref(), &mut *opened.stream)
+}
+
+/// Recursively total the files and directories below `dir`
+///
+/// Stops after visiting `max_nodes` entries and marks the result truncated,
+/// bounding the cost of huge or cyclic trees.
+fn walk_directory_stats(
+    territory: &dyn Territory,
+    stream: &mut dyn ReadSeek,
+    dir: &str,
+    max_nodes: usize,
+) -> TotalImageResult<DirectoryStatsResponse> {
+    let mut stats = DirectoryStatsResponse {
+        applied_limit: max_nodes,
+        ..Default::default()
+    };
+    let mut visited = 0;
+    let mut pending = vec![(dir.trim_end_matches('/').to_string(), territory.navigate_to(stream, dir)?)];
+
+    while let Some((path, cell)) = pending.pop() {
+        for occupant in cell.list_occupants(stream)? {
+            if visited == max_nodes {
+                stats.truncated = true;
+                return Ok(stats);
+            }
+            visited += 1;
+
+            let occupant_path = format!("{}/{}", path, occupant.name);
+            if occupant.is_directory {
+                stats.dir_count += 1;
+                let child = cell.enter(stream, &occupant.name)?;
+                pending.push((occupant_path, child));
+            } else {
+                stats.file_count += 1;
+                stats.total_bytes += occupant.size;
+                if stats.largest_file.as_ref().is_none_or(|largest| occupant.size > largest.size) {
+                    stats.largest_file = Some(LargestFile {
+                        path: occupant_path,
+                        size: occupant.size,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tempfile::TempDir;
+    use tower::ServiceExt;
+
+    /// Write an unpartitioned FAT12 floppy holding
+    /// `A.TXT`, `DOCS/B.BIN` and `DOCS/SUB/C.DAT`
+    ///
+    /// Only `C.DAT` has data, [`c_dat_byte`] at each offset, in clusters
+    /// 4 to 13.
+    fn write_fat_image(dir: &TempDir) -> String {
+        let mut disk = vec![0u8; 1_474_560];
+        disk[0..3].copy_from_slice(&[0xEB, 0x3C, 0x90]);
+        disk[3..11].copy_from_slice(b"MSWIN4.1");
+        disk[11..13].copy_from_slice(&512u16.to_le_bytes());
+        disk[13] = 1;
+        disk[14..16].copy_from_slice(&1u16.to_le_bytes());
+        disk[16] = 2;
+        disk[17..19].copy_from_slice(&224u16.to_le_bytes());
+        disk[19..21].copy_from_slice(&2880u16.to_le_bytes());
+        disk[21] = 0xF0;
+        disk[22..24].copy_from_slice(&9u16.to_le_bytes());
+        disk[510..512].copy_from_slice(&[0x55, 0xAA]);
+
+        // Clusters 2 (DOCS) and 3 (DOCS/SUB) end their chains
+        disk[512..518].copy_from_slice(&[0xF0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+        for cluster in 4..=13usize {
+            let value: u16 = if cluster == 13 { 0xFFF } else { cluster as u16 + 1 };
+            let offset = 512 + cluster * 3 / 2;
+            let packed = u16::from_le_bytes([disk[offset], disk[offset + 1]]);
+            let packed = if cluster % 2 == 0 {
+                (packed & 0xF000) | value
+            } else {
+                (packed & 0x000F) | (value << 4)
+            };
+            disk[offset..offset + 2].copy_from_slice(&packed.to_le_bytes());
+        }
+
+        let mut entry = |offset: usize, name: &[u8; 11], attributes: u8, cluster: u16, size: u32| {
+            disk[offset..offset + 11].copy_from_slice(name);
+            disk[offset + 11] = attributes;
+            disk[offset + 26..offset + 28].copy_from_slice(&cluster.to_le_bytes());
+            disk[offset + 28..offset + 32].copy_from_slice(&size.to_le_bytes());
+        };
+
+        let root = 512 + 2 * 9 * 512;
+        let cluster2 = root + 224 * 32;
+        let cluster3 = cluster2 + 512;
+        entry(root, b"A       TXT", 0x20, 0, 10);
+        entry(root + 32, b"DOCS       ", 0x10, 2, 0);
+        entry(cluster2, b"B       BIN", 0x20, 0, 300);
+        entry(cluster2 + 32, b"SUB        ", 0x10, 3, 0);
+        entry(cluster3, b"C       DAT", 0x20, 4, 5000);
+
+        let cluster4 = cluster3 + 512;
+        for i in 0..5000 {
+            disk[cluster4 + i] = c_dat_byte(i);
+        }
+
+        let path = dir.path().join("floppy.img");
+        std::fs::write(&path, disk).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    fn c_dat_byte(offset: usize) -> u8 {
+        (offset % 251) as u8
+    }
+
+    fn test_app(dir: &TempDir) -> Router {
+        let cache = MetadataCache::new(dir.path().join("cache.redb")).unwrap();
+        app(AppState {
+            cache: Arc::new(cache),
+            max_limit: DEFAULT_MAX_LIMIT,
+            max_walk_nodes: MAX_WALK_NODES,
+        })
+    }
+
+    async fn get_json(app: Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = app
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_formats() {
+        let dir = TempDir::new().unwrap();
+        let (status, formats) = get_json(test_app(&dir), "/api/formats").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let body = formats.to_string();
+        for name in ["FAT", "NTFS", "ISO", "exFAT", "VHD", "E01"] {
+            assert!(body.contains(name), "{} missing from {}", name, body);
+        }
+
+        let read_write = |list: &str, name: &str| {
+            formats[list]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|format| format["name"] == name)
+                .map(|format| format["read_write"].clone())
+        };
+        assert_eq!(read_write("territories", "FAT"), Some(true.into()));
+        assert_eq!(read_write("territories", "NTFS"), Some(false.into()));
+        assert_eq!(read_write("vaults", "Raw Sector Image"), Some(true.into()));
+        assert_eq!(read_write("vaults", "EnCase E01"), Some(false.into()));
+        assert_eq!(read_write("zone_tables", "GPT"), Some(false.into()));
+    }
+
+    #[tokio::test]
+    async fn test_vault_stats() {
+        let dir = TempDir::new().unwrap();
+        let image = write_fat_image(&dir);
+
+        let (status, stats) = get_json(test_app(&dir), &format!("/api/vault/stats?path={}", image)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(stats["file_count"], 3);
+        assert_eq!(stats["dir_count"], 2);
+        assert_eq!(stats["total_bytes"], 5310);
+        assert_eq!(stats["largest_file"]["path"], "/DOCS/SUB/C.DAT");
+        assert_eq!(stats["largest_file"]["size"], 5000);
+        assert_eq!(stats["truncated"], false);
+
+        // A subdirectory, served from the cache the second time
+        let uri = format!("/api/vault/stats?path={}&zone=0&dir=/DOCS", image);
+        for _ in 0..2 {
+            let (status, stats) = get_json(test_app(&dir), &uri).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(stats["file_count"], 2);
+            assert_eq!(stats["dir_count"], 1);
+            assert_eq!(stats["total_bytes"], 5300);
+        }
+
+        let (status, _) = get_json(test_app(&dir), &format!("/api/vault/stats?path={}&dir=/MISSING", image)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_vault_files() {
+        let dir = TempDir::new().unwrap();
+        let image = write_fat_image(&dir);
+
+        let (status, listing) = get_json(test_app(&dir), &format!("/api/vault/files?path={}&dir=DOCS", image)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listing["dir"], "/DOCS");
+        assert_eq!(listing["files"][0]["path"], "/DOCS/B.BIN");
+        assert_eq!(listing["files"][1]["path"], "/DOCS/SUB");
+        assert_eq!(listing["errors"], 0);
+
+        let uri = format!("/api/vault/files?path={}&dir=DOCS&best_effort=true&limit=1", image);
+        let (status, listing) = get_json(test_app(&dir), &uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listing["files"][0]["name"], "B.BIN");
+        assert_eq!(listing["errors"], 0);
+        assert_eq!(listing["skipped"], serde_json::json!([]));
+        assert_eq!(listing["next"], 1);
+
+        let uri = format!("/api/vault/files?path={}&sort=-size", image);
+        let (_, listing) = get_json(test_app(&dir), &uri).await;
+        let names: Vec<_> = listing["files"].as_array().unwrap().iter().map(|f| f["name"].clone()).collect();
+        assert_eq!(names, ["A.TXT", "DOCS"]);
+
+        let uri = format!("/api/vault/files?path={}&dir=/DOCS&filter=*.bin", image);
+        let (_, listing) = get_json(test_app(&dir), &uri).await;
+        assert_eq!(listing["files"].as_array().unwrap().len(), 1);
+        assert_eq!(listing["files"][0]["name"], "B.BIN");
+
+        let uri = format!("/api/vault/files?path={}&limit=1", image);
+        let (_, listing) = get_json(test_app(&dir), &uri).await;
+        assert_eq!(listing["files"].as_array().unwrap().len(), 1);
+        assert_eq!(listing["files"][0]["name"], "A.TXT");
+        let uri = format!("/api/vault/files?path={}&limit=1&cursor={}", image, listing["next"]);
+        let (_, listing) = get_json(test_app(&dir), &uri).await;
+        assert_eq!(listing["files"][0]["name"], "DOCS");
+        assert_eq!(listing["next"], serde_json::Value::Null);
+
+        let uri = format!("/api/vault/files?path={}&sort=colour", image);
+        let (status, _) = get_json(test_app(&dir), &uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let uri = format!("/api/vault/files?path={}&limit=0", image);
+        let (status, body) = get_json(test_app(&dir), &uri).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], "Invalid operation: limit must be at least 1");
+    }
+
+    #[tokio::test]
+    async fn test_vault_files_limit_clamped() {
+        let dir = TempDir::new().unwrap();
+        let image = write_fat_image(&dir);
+        let cache = MetadataCache::new(dir.path().join("cache.redb")).unwrap();
+        let app = app(AppState {
+            cache: Arc::new(cache),
+            max_limit: max_limit(Some("1")),
+            max_walk_nodes: MAX_WALK_NODES,
+        });
+
+        let uri = format!("/api/vault/files?path={}&limit=1000000", image);
+        let (status, listing) = get_json(app.clone(), &uri).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listing["requested_limit"], 1_000_000);
+        assert_eq!(listing["applied_limit"], 1);
+        assert_eq!(listing["files"].as_array().unwrap().len(), 1);
+        assert_eq!(listing["next"], 1);
+
+        // An omitted limit pages at the maximum too, sorted or not
+        for uri in [
+            format!("/api/vault/files?path={}", image),
+            format!("/api/vault/files?path={}&sort=name", image),
+        ] {
+            let (_, listing) = get_json(app.clone(), &uri).await;
+            assert_eq!(listing["requested_limit"], serde_json::Value::Null);
+            assert_eq!(listing["applied_limit"], 1);
+            assert_eq!(listing["files"].as_array().unwrap().len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_vault_stats_limit_clamped() {
+        let dir = TempDir::new().unwrap();
+        let image = write_fat_image(&dir);
+        let cache = MetadataCache::new(dir.path().join("cache.redb")).unwrap();
+        let app = app(AppState {
+            cache: Arc::new(cache),
+            max_limit: DEFAULT_MAX_LIMIT,
+            max_walk_nodes: 3,
+        });
+
+        let (status, stats) = get_json(app.clone(), &format!("/api/vault/stats?path={}&limit=1000000", image)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(stats["requested_limit"], 1_000_000);
+        assert_eq!(stats["applied_limit"], 3);
+        assert_eq!(stats["truncated"], true);
+
+        let (_, stats) = get_json(app.clone(), &format!("/api/vault/stats?path={}&limit=2", image)).await;
+        assert_eq!(stats["applied_limit"], 2);
+        assert_eq!(stats["file_count"].as_u64().unwrap() + stats["dir_count"].as_u64().unwrap(), 2);
+
+        let (_, stats) = get_json(app, &format!("/api/vault/stats?path={}", image)).await;
+        assert_eq!(stats["requested_limit"], serde_json::Value::Null);
+        assert_eq!(stats["applied_limit"], 3);
+
+        assert_eq!(max_limit(None), 1000);
+        assert_eq!(max_limit(Some("250")), 250);
+        assert_eq!(max_limit(Some("0")), DEFAULT_MAX_LIMIT);
+        assert_eq!(max_limit(Some("lots")), DEFAULT_MAX_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn test_cors_origin_list() {
+        let cors = cors_layer(Some("https://a.example, bad\norigin,*,https://b.example:8443,"));
+        let dir = TempDir::new().unwrap();
+
+        for (origin, allowed) in [
+            ("https://a.example", true),
+            ("https://b.example:8443", true),
+            ("https://c.example", false),
+        ] {
+            let request = Request::get("/health")
+                .header("origin", origin)
+                .body(Body::empty())
+                .unwrap();
+            let response = test_app(&dir).layer(cors.clone()).oneshot(request).await.unwrap();
+
+            let allow_origin = response.headers().get("access-control-allow-origin");
+            assert_eq!(allow_origin.is_some(), allowed, "{}", origin);
+            if allowed {
+                assert_eq!(allow_origin.unwrap(), origin);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_vault_file_ranges() {
+        let dir = TempDir::new().unwrap();
+        let image = write_fat_image(&dir);
+        let uri = format!("/api/vault/file?path={}&file=/DOCS/SUB/C.DAT", image);
+
+        let request = |range: Option<&str>| {
+            let mut request = Request::get(&uri);
+            if let Some(range) = range {
+                request = request.header("range", range);
+            }
+            request.body(Body::empty()).unwrap()
+        };
+
+        let response = test_app(&dir).oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["accept-ranges"], "bytes");
+        let full = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(full.iter().enumerate().all(|(i, &b)| b == c_dat_byte(i)));
+        assert_eq!(full.len(), 5000);
+
+        for (range, content_range, expected) in [
+            ("bytes=100-199", "bytes 100-199/5000", 100..200),
+            ("bytes=4900-", "bytes 4900-4999/5000", 4900..5000),
+            ("bytes=-10", "bytes 4990-4999/5000", 4990..5000),
+            ("bytes=4000-9999", "bytes 4000-4999/5000", 4000..5000),
+        ] {
+            let response = test_app(&dir).oneshot(request(Some(range))).await.unwrap();
+            assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT, "{}", range);
+            assert_eq!(response.headers()["content-range"], content_range);
+            assert_eq!(response.headers()["accept-ranges"], "bytes");
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            assert_eq!(body, full[expected], "{}", range);
+        }
+
+        let response = test_app(&dir).oneshot(request(Some("bytes=5000-"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(response.headers()["content-range"], "bytes */5000");
+
+        let (status, _) = get_json(test_app(&dir), &format!("/api/vault/file?path={}&file=/DOCS", image)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = get_json(test_app(&dir), &format!("/api/vault/file?path={}&file=/NOPE.TXT", image)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_parse_range_header() {
+        use RangeRequest::*;
+
+        assert_eq!(RangeRequest::parse(None, 100), Full);
+        assert_eq!(RangeRequest::parse(Some("bytes=0-0"), 100), Partial { start: 0, end: 0 });
+        assert_eq!(RangeRequest::parse(Some("bytes=-500"), 100), Partial { start: 0, end: 99 });
+        assert_eq!(RangeRequest::parse(Some("bytes=-0"), 100), Unsatisfiable);
+        assert_eq!(RangeRequest::parse(Some("bytes=0-"), 0), Unsatisfiable);
+        // Malformed and multi-range headers are ignored
+        for header in ["bytes=5-2", "bytes=a-b", "bytes=-", "items=0-1", "bytes=0-1,5-6"] {
+            assert_eq!(RangeRequest::parse(Some(header), 100), Full, "{}", header);
+        }
+    }
+
+    #[test]
+    fn test_stats_walk_truncated() {
+        let dir = TempDir::new().unwrap();
+        let image = write_fat_image(&dir);
+
+        let stats = get_directory_stats(&image, 0, "/", 3).unwrap();
+        assert!(stats.truncated);
+        assert_eq!(stats.file_count + stats.dir_count, 3);
+    }
+}
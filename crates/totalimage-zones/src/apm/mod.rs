@@ -0,0 +1,261 @@
+//! APM (Apple Partition Map) partition table implementation
+
+pub mod types;
+
+use totalimage_core::{Error, ReadSeek, ReadSeekExt, Result, Zone, ZoneTable};
+use types::{ApmPartitionEntry, DriverDescriptor};
+
+/// Apple Partition Map
+///
+/// The partitioning scheme used by classic (pre-Intel) Macintosh disks and
+/// still found on older Mac CD-ROMs and hybrid ISOs.
+///
+/// # Structure
+///
+/// ```text
+/// Block 0:     Driver Descriptor Record ("ER")
+/// Block 1..N:  Partition map entries ("PM"), one per block, N taken
+///              from the pmMapBlkCnt field of each entry
+/// ```
+///
+/// The map describes itself as an `Apple_partition_map` entry. Entries of
+/// type `Apple_Free` describe unallocated space and are not reported as zones.
+#[derive(Debug, Clone)]
+pub struct ApmZoneTable {
+    zones: Vec<Zone>,
+    descriptor: DriverDescriptor,
+    entries: Vec<ApmPartitionEntry>,
+}
+
+impl ApmZoneTable {
+    /// Upper bound on the number of partition map entries
+    ///
+    /// Apple's tools create 63-entry maps; anything far beyond that is a
+    /// corrupt count rather than a real map.
+    pub const MAX_ENTRIES: u32 = 1024;
+
+    /// Parse an APM from a readable and seekable stream
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - A stream positioned at the start of the disk
+    /// * `sector_size` - The block size in bytes (512 for disks, often 2048
+    ///   for optical media)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The DDR or first partition map signature is missing
+    /// - The map entry count is zero or implausibly large
+    /// - The stream cannot be read
+    pub fn parse(stream: &mut dyn ReadSeek, sector_size: u32) -> Result<Self> {
+        let block_size = sector_size as u64;
+        let mut block = vec![0u8; sector_size as usize];
+
+        stream.read_exact_at(0, &mut block)?;
+        let descriptor = DriverDescriptor::from_bytes(&block).ok_or_else(|| {
+            Error::unrecognized_signature("Invalid APM driver descriptor signature".to_string())
+        })?;
+
+        let mut entries = Vec::new();
+        let mut map_block_count = 1;
+        let mut block_index = 1u32;
+
+        while block_index <= map_block_count {
+            stream.read_exact_at(block_index as u64 * block_size, &mut block)?;
+
+            let entry = ApmPartitionEntry::from_bytes(&block).ok_or_else(|| {
+                Error::invalid_zone_table(format!(
+                    "Invalid APM partition map entry signature at block {}",
+                    block_index
+                ))
+            })?;
+
+            if block_index == 1 {
+                map_block_count = entry.map_block_count;
+                if map_block_count == 0 || map_block_count > Self::MAX_ENTRIES {
+                    return Err(Error::invalid_zone_table(format!(
+                        "Invalid APM partition map entry count: {}",
+                        map_block_count
+                    )));
+                }
+            }
+
+            entries.push(entry);
+            block_index += 1;
+        }
+
+        let zones = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| !entry.is_free() && entry.block_count != 0)
+            .map(|(i, entry)| {
+                let zone_type = if entry.name.is_empty() {
+                    entry.partition_type.clone()
+                } else {
+                    format!("{} ({})", entry.partition_type, entry.name)
+                };
+
+                Zone::new(
+                    i,
+                    entry.start_block as u64 * block_size,
+                    entry.block_count as u64 * block_size,
+                    zone_type,
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            zones,
+            descriptor,
+            entries,
+        })
+    }
+
+    /// Parse an APM using the block size recorded in its DDR
+    ///
+    /// Falls back to 512-byte blocks if the DDR block size is not a
+    /// supported sector size.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`parse`](Self::parse)
+    pub fn parse_detect(stream: &mut dyn ReadSeek) -> Result<Self> {
+        let mut ddr = [0u8; 8];
+        stream.read_exact_at(0, &mut ddr)?;
+
+        let sector_size = match DriverDescriptor::from_bytes(&ddr) {
+            Some(descriptor) if matches!(descriptor.block_size, 512 | 1024 | 2048 | 4096) => {
+                descriptor.block_size as u32
+            }
+            _ => 512,
+        };
+
+        Self::parse(stream, sector_size)
+    }
+
+    /// Get the Driver Descriptor Record
+    pub fn descriptor(&self) -> &DriverDescriptor {
+        &self.descriptor
+    }
+
+    /// Get all partition map entries, including free space and the map itself
+    pub fn entries(&self) -> &[ApmPartitionEntry] {
+        &self.entries
+    }
+}
+
+impl ZoneTable for ApmZoneTable {
+    fn identify(&self) -> &str {
+        "Apple Partition Map"
+    }
+
+    fn enumerate_zones(&self) -> &[Zone] {
+        &self.zones
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Create a synthetic APM: the map, two partitions and trailing free space
+    fn create_test_apm(sector_size: usize) -> Vec<u8> {
+        let mut disk = vec![0u8; 64 * sector_size];
+
+        disk[0..2].copy_from_slice(b"ER");
+        disk[2..4].copy_from_slice(&(sector_size as u16).to_be_bytes());
+        disk[4..8].copy_from_slice(&64u32.to_be_bytes());
+
+        let map: [(u32, u32, &str, &str); 4] = [
+            (1, 4, "Apple", "Apple_partition_map"),
+            (8, 16, "Macintosh HD", "Apple_HFS"),
+            (24, 32, "", "Apple_UNIX_SVR2"),
+            (56, 8, "", "Apple_Free"),
+        ];
+
+        for (i, (start, count, name, partition_type)) in map.into_iter().enumerate() {
+            let block = (i + 1) * sector_size;
+            let entry = &mut disk[block..block + sector_size];
+            entry[0..2].copy_from_slice(b"PM");
+            entry[4..8].copy_from_slice(&(map.len() as u32).to_be_bytes());
+            entry[8..12].copy_from_slice(&start.to_be_bytes());
+            entry[12..16].copy_from_slice(&count.to_be_bytes());
+            entry[16..16 + name.len()].copy_from_slice(name.as_bytes());
+            entry[48..48 + partition_type.len()].copy_from_slice(partition_type.as_bytes());
+        }
+
+        disk
+    }
+
+    #[test]
+    fn test_parse_apm() {
+        let mut cursor = Cursor::new(create_test_apm(512));
+        let table = ApmZoneTable::parse(&mut cursor, 512).unwrap();
+
+        assert_eq!(table.identify(), "Apple Partition Map");
+        assert_eq!(table.descriptor().block_size, 512);
+        assert_eq!(table.entries().len(), 4);
+
+        // Free space is omitted; the map entry itself is reported
+        let zones = table.enumerate_zones();
+        assert_eq!(zones.len(), 3);
+        assert_eq!(zones[1].index, 1);
+        assert_eq!(zones[1].offset, 8 * 512);
+        assert_eq!(zones[1].length, 16 * 512);
+        assert_eq!(zones[1].zone_type, "Apple_HFS (Macintosh HD)");
+        assert_eq!(zones[2].offset, 24 * 512);
+        assert_eq!(zones[2].length, 32 * 512);
+        assert_eq!(zones[2].zone_type, "Apple_UNIX_SVR2");
+    }
+
+    #[test]
+    fn test_parse_apm_2048_byte_blocks() {
+        let mut cursor = Cursor::new(create_test_apm(2048));
+        let table = ApmZoneTable::parse(&mut cursor, 2048).unwrap();
+
+        let zones = table.enumerate_zones();
+        assert_eq!(zones.len(), 3);
+        assert_eq!(zones[1].offset, 8 * 2048);
+        assert_eq!(zones[1].length, 16 * 2048);
+    }
+
+    #[test]
+    fn test_parse_detect_block_size() {
+        let table = ApmZoneTable::parse_detect(&mut Cursor::new(create_test_apm(2048))).unwrap();
+        assert_eq!(table.enumerate_zones()[1].offset, 8 * 2048);
+
+        // Unsupported DDR block size falls back to 512
+        let mut disk = create_test_apm(512);
+        disk[2..4].copy_from_slice(&0u16.to_be_bytes());
+        let table = ApmZoneTable::parse_detect(&mut Cursor::new(disk)).unwrap();
+        assert_eq!(table.enumerate_zones()[1].offset, 8 * 512);
+    }
+
+    #[test]
+    fn test_parse_invalid_apm() {
+        // Missing DDR signature
+        let mut disk = create_test_apm(512);
+        disk[0] = 0;
+        let err = ApmZoneTable::parse(&mut Cursor::new(disk), 512).unwrap_err();
+        assert!(err.is_unrecognized(), "{:?}", err);
+
+        // Entry count claims more entries than the map contains
+        let mut disk = create_test_apm(512);
+        disk[512 + 4..512 + 8].copy_from_slice(&5u32.to_be_bytes());
+        let err = ApmZoneTable::parse(&mut Cursor::new(disk), 512).unwrap_err();
+        assert!(!err.is_unrecognized(), "{:?}", err);
+
+        // Implausible entry count
+        let mut disk = create_test_apm(512);
+        disk[512 + 4..512 + 8].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(ApmZoneTable::parse(&mut Cursor::new(disk), 512).is_err());
+
+        // An MBR is not an APM
+        let mut mbr = vec![0u8; 1024];
+        mbr[0x1FE] = 0x55;
+        mbr[0x1FF] = 0xAA;
+        assert!(ApmZoneTable::parse(&mut Cursor::new(mbr), 512).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! Apple Partition Map structures
+//!
+//! All multi-byte fields are big-endian.
+
+/// Driver Descriptor Record (block 0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DriverDescriptor {
+    /// Device block size in bytes (`sbBlkSize`)
+    pub block_size: u16,
+    /// Number of blocks on the device (`sbBlkCount`)
+    pub block_count: u32,
+}
+
+impl DriverDescriptor {
+    /// DDR signature ("ER")
+    pub const SIGNATURE: &'static [u8; 2] = b"ER";
+
+    /// Parse a DDR, returning None if the signature is missing
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 8 || &bytes[0..2] != Self::SIGNATURE {
+            return None;
+        }
+
+        Some(Self {
+            block_size: u16::from_be_bytes([bytes[2], bytes[3]]),
+            block_count: u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+        })
+    }
+}
+
+/// Partition map entry
+///
+/// Each entry occupies one block, starting at block 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApmPartitionEntry {
+    /// Number of entries in the partition map (`pmMapBlkCnt`)
+    pub map_block_count: u32,
+    /// First physical block of the partition (`pmPyPartStart`)
+    pub start_block: u32,
+    /// Number of blocks in the partition (`pmPartBlkCnt`)
+    pub block_count: u32,
+    /// Partition name (`pmPartName`)
+    pub name: String,
+    /// Partition type, e.g. `Apple_HFS` (`pmParType`)
+    pub partition_type: String,
+}
+
+impl ApmPartitionEntry {
+    /// Partition map entry signature ("PM")
+    pub const SIGNATURE: &'static [u8; 2] = b"PM";
+
+    /// Number of bytes of an entry that are parsed
+    pub const ENTRY_SIZE: usize = 80;
+
+    /// Type string of entries describing unallocated space
+    pub const TYPE_FREE: &'static str = "Apple_Free";
+
+    /// Parse a partition map entry, returning None if the signature is missing
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Self::ENTRY_SIZE || &bytes[0..2] != Self::SIGNATURE {
+            return None;
+        }
+
+        let read_u32 = |offset: usize| {
+            u32::from_be_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+        };
+
+        Some(Self {
+            map_block_count: read_u32(4),
+            start_block: read_u32(8),
+            block_count: read_u32(12),
+            name: read_c_string(&bytes[16..48]),
+            partition_type: read_c_string(&bytes[48..80]),
+        })
+    }
+
+    /// Does this entry describe unallocated space?
+    pub fn is_free(&self) -> bool {
+        self.partition_type == Self::TYPE_FREE
+    }
+}
+
+/// Decode a NUL-padded ASCII field
+fn read_c_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_driver_descriptor() {
+        let mut block = [0u8; 512];
+        block[0..2].copy_from_slice(b"ER");
+        block[2..4].copy_from_slice(&2048u16.to_be_bytes());
+        block[4..8].copy_from_slice(&1000u32.to_be_bytes());
+
+        let ddr = DriverDescriptor::from_bytes(&block).unwrap();
+        assert_eq!(ddr.block_size, 2048);
+        assert_eq!(ddr.block_count, 1000);
+
+        block[0] = 0;
+        assert!(DriverDescriptor::from_bytes(&block).is_none());
+    }
+
+    #[test]
+    fn test_partition_entry() {
+        let mut block = [0u8; 512];
+        block[0..2].copy_from_slice(b"PM");
+        block[4..8].copy_from_slice(&3u32.to_be_bytes());
+        block[8..12].copy_from_slice(&64u32.to_be_bytes());
+        block[12..16].copy_from_slice(&128u32.to_be_bytes());
+        block[16..24].copy_from_slice(b"Untitled");
+        block[48..57].copy_from_slice(b"Apple_HFS");
+
+        let entry = ApmPartitionEntry::from_bytes(&block).unwrap();
+        assert_eq!(entry.map_block_count, 3);
+        assert_eq!(entry.start_block, 64);
+        assert_eq!(entry.block_count, 128);
+        assert_eq!(entry.name, "Untitled");
+        assert_eq!(entry.partition_type, "Apple_HFS");
+        assert!(!entry.is_free());
+
+        assert!(ApmPartitionEntry::from_bytes(&block[..40]).is_none());
+    }
+}
@@ -0,0 +1,330 @@
+//! FAT BIOS Parameter Block
+//!
+//! The BPB opens the boot sector of every FAT volume, partitioned or not.
+//! It lives here rather than in `totalimage-territories` so that it parses
+//! from a byte slice without the `std` feature, like [`crate::mbr::types`].
+
+use core::fmt;
+
+/// FAT type variants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FatType {
+    Fat12,
+    Fat16,
+    Fat32,
+}
+
+impl fmt::Display for FatType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FatType::Fat12 => write!(f, "FAT12"),
+            FatType::Fat16 => write!(f, "FAT16"),
+            FatType::Fat32 => write!(f, "FAT32"),
+        }
+    }
+}
+
+/// Why a boot sector's BPB was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BpbError {
+    /// Fewer than 512 bytes were given
+    Truncated,
+    /// The boot sector is exFAT's, whose BPB is zeroed
+    Exfat,
+    /// Sectors per cluster is zero
+    InvalidSectorsPerCluster,
+    /// Bytes per sector isn't 512, 1024, 2048 or 4096
+    InvalidBytesPerSector(u16),
+    /// The FAT and root directory take more sectors than the volume has
+    DataSectorsUnderflow,
+    /// A derived offset or size doesn't fit its type
+    Overflow(&'static str),
+}
+
+impl fmt::Display for BpbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "BPB too short"),
+            Self::Exfat => write!(f, "Boot sector is exFAT, not FAT"),
+            Self::InvalidSectorsPerCluster => write!(f, "Invalid sectors_per_cluster: 0"),
+            Self::InvalidBytesPerSector(value) => write!(f, "Invalid bytes_per_sector: {}", value),
+            Self::DataSectorsUnderflow => write!(f, "BPB data sectors underflow"),
+            Self::Overflow(what) => write!(f, "{} overflow", what),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BpbError {}
+
+#[cfg(feature = "std")]
+impl From<BpbError> for totalimage_core::Error {
+    fn from(err: BpbError) -> Self {
+        use alloc::string::ToString;
+
+        match err {
+            BpbError::Truncated => Self::truncated(err.to_string()),
+            BpbError::Exfat => Self::unrecognized_signature(err.to_string()),
+            _ => Self::invalid_territory(err.to_string()),
+        }
+    }
+}
+
+/// BIOS Parameter Block (BPB) - Common to all FAT variants
+///
+/// The BPB contains filesystem metadata and geometry information.
+#[derive(Debug, Clone)]
+pub struct BiosParameterBlock {
+    /// Bytes per sector (typically 512)
+    pub bytes_per_sector: u16,
+    /// Sectors per cluster (power of 2)
+    pub sectors_per_cluster: u8,
+    /// Number of reserved sectors (including boot sector)
+    pub reserved_sectors: u16,
+    /// Number of FAT copies (typically 2)
+    pub num_fats: u8,
+    /// Maximum root directory entries (FAT12/16 only, 0 for FAT32)
+    pub root_entries: u16,
+    /// Total sectors (16-bit, 0 if using 32-bit field)
+    pub total_sectors_16: u16,
+    /// Media descriptor byte
+    pub media_descriptor: u8,
+    /// Sectors per FAT (FAT12/16 only, 0 for FAT32)
+    pub sectors_per_fat_16: u16,
+    /// Sectors per track (for CHS addressing)
+    pub sectors_per_track: u16,
+    /// Number of heads (for CHS addressing)
+    pub num_heads: u16,
+    /// Hidden sectors (LBA offset of partition)
+    pub hidden_sectors: u32,
+    /// Total sectors (32-bit, used if total_sectors_16 is 0)
+    pub total_sectors_32: u32,
+    /// Sectors per FAT (FAT32 only, 0 for FAT12/16)
+    pub sectors_per_fat_32: u32,
+    /// Sector number of the FSInfo structure (FAT32 only, 0 for FAT12/16)
+    pub fs_info_sector: u16,
+    /// FAT type determined from cluster count
+    pub fat_type: FatType,
+}
+
+impl BiosParameterBlock {
+    /// Parse BPB from boot sector bytes
+    ///
+    /// # Security
+    /// Uses checked arithmetic to prevent integer overflow attacks
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BpbError> {
+        if bytes.len() < 512 {
+            return Err(BpbError::Truncated);
+        }
+
+        // exFAT boot sectors zero the BPB, which would otherwise fail the
+        // checks below with a misleading message
+        if &bytes[3..11] == b"EXFAT   " {
+            return Err(BpbError::Exfat);
+        }
+
+        // Parse common BPB fields (offsets 11-35)
+        let bytes_per_sector = u16::from_le_bytes([bytes[11], bytes[12]]);
+        let sectors_per_cluster = bytes[13];
+        let reserved_sectors = u16::from_le_bytes([bytes[14], bytes[15]]);
+        let num_fats = bytes[16];
+        let root_entries = u16::from_le_bytes([bytes[17], bytes[18]]);
+        let total_sectors_16 = u16::from_le_bytes([bytes[19], bytes[20]]);
+        let media_descriptor = bytes[21];
+        let sectors_per_fat_16 = u16::from_le_bytes([bytes[22], bytes[23]]);
+        let sectors_per_track = u16::from_le_bytes([bytes[24], bytes[25]]);
+        let num_heads = u16::from_le_bytes([bytes[26], bytes[27]]);
+        let hidden_sectors = u32::from_le_bytes([bytes[28], bytes[29], bytes[30], bytes[31]]);
+        let total_sectors_32 = u32::from_le_bytes([bytes[32], bytes[33], bytes[34], bytes[35]]);
+
+        // Validate sectors_per_cluster to prevent divide by zero
+        if sectors_per_cluster == 0 {
+            return Err(BpbError::InvalidSectorsPerCluster);
+        }
+
+        // Validate bytes_per_sector
+        if !matches!(bytes_per_sector, 512 | 1024 | 2048 | 4096) {
+            return Err(BpbError::InvalidBytesPerSector(bytes_per_sector));
+        }
+
+        // Determine total sectors
+        let total_sectors = if total_sectors_16 != 0 {
+            total_sectors_16 as u32
+        } else {
+            total_sectors_32
+        };
+
+        // Calculate data region size to determine FAT type. Both products
+        // are of 32-bit values, so they can't overflow a u64.
+        let root_entries_bytes = root_entries as u64 * 32;
+        let bytes_per_sector_minus_1 = bytes_per_sector.saturating_sub(1) as u64;
+        let root_dir_sectors = ((root_entries_bytes + bytes_per_sector_minus_1) / bytes_per_sector as u64) as u32;
+
+        let sectors_per_fat_32 = if sectors_per_fat_16 != 0 {
+            0
+        } else {
+            // FAT32: read from offset 36
+            u32::from_le_bytes([bytes[36], bytes[37], bytes[38], bytes[39]])
+        };
+        let sectors_per_fat = if sectors_per_fat_16 != 0 {
+            sectors_per_fat_16 as u32
+        } else {
+            sectors_per_fat_32
+        };
+
+        let fat_size = num_fats as u64 * sectors_per_fat as u64;
+
+        // Calculate total non-data sectors
+        let non_data_sectors = (reserved_sectors as u64)
+            .checked_add(fat_size)
+            .and_then(|v| v.checked_add(root_dir_sectors as u64))
+            .ok_or(BpbError::Overflow("BPB sector calculation"))?;
+
+        // Calculate data sectors with overflow check
+        let data_sectors = (total_sectors as u64)
+            .checked_sub(non_data_sectors)
+            .ok_or(BpbError::DataSectorsUnderflow)? as u32;
+
+        let cluster_count = data_sectors / sectors_per_cluster as u32;
+
+        // Determine FAT type based on cluster count
+        let fat_type = if cluster_count < 4085 {
+            FatType::Fat12
+        } else if cluster_count < 65525 {
+            FatType::Fat16
+        } else {
+            FatType::Fat32
+        };
+
+        let fs_info_sector = if fat_type == FatType::Fat32 {
+            u16::from_le_bytes([bytes[48], bytes[49]])
+        } else {
+            0
+        };
+
+        Ok(Self {
+            bytes_per_sector,
+            sectors_per_cluster,
+            reserved_sectors,
+            num_fats,
+            root_entries,
+            total_sectors_16,
+            media_descriptor,
+            sectors_per_fat_16,
+            sectors_per_track,
+            num_heads,
+            hidden_sectors,
+            total_sectors_32,
+            sectors_per_fat_32,
+            fs_info_sector,
+            fat_type,
+        })
+    }
+
+    /// Get the total number of sectors
+    pub fn total_sectors(&self) -> u32 {
+        if self.total_sectors_16 != 0 {
+            self.total_sectors_16 as u32
+        } else {
+            self.total_sectors_32
+        }
+    }
+
+    /// Get sectors per FAT
+    pub fn sectors_per_fat(&self) -> u32 {
+        if self.sectors_per_fat_16 != 0 {
+            self.sectors_per_fat_16 as u32
+        } else {
+            self.sectors_per_fat_32
+        }
+    }
+
+    /// Calculate the byte offset of the first FAT
+    ///
+    /// # Security
+    /// Uses checked arithmetic to prevent overflow
+    pub fn fat_offset(&self) -> Result<u32, BpbError> {
+        (self.reserved_sectors as u32)
+            .checked_mul(self.bytes_per_sector as u32)
+            .ok_or(BpbError::Overflow("FAT offset"))
+    }
+
+    /// Calculate the byte offset of the root directory
+    ///
+    /// # Security
+    /// Uses checked arithmetic to prevent overflow
+    pub fn root_dir_offset(&self) -> Result<u32, BpbError> {
+        let total_fat_size = self.sectors_per_fat() as u64 * self.bytes_per_sector as u64 * self.num_fats as u64;
+
+        (self.fat_offset()? as u64)
+            .checked_add(total_fat_size)
+            .and_then(|v| v.try_into().ok())
+            .ok_or(BpbError::Overflow("Root dir offset"))
+    }
+
+    /// Calculate the byte offset of the data region
+    ///
+    /// # Security
+    /// Uses checked arithmetic to prevent overflow
+    pub fn data_offset(&self) -> Result<u32, BpbError> {
+        let root_entries_bytes = self.root_entries as u64 * 32;
+        let bytes_per_sector_minus_1 = self.bytes_per_sector.saturating_sub(1) as u64;
+        let root_dir_sectors = (root_entries_bytes + bytes_per_sector_minus_1) / self.bytes_per_sector as u64;
+        let root_dir_size = root_dir_sectors * self.bytes_per_sector as u64;
+
+        (self.root_dir_offset()? as u64)
+            .checked_add(root_dir_size)
+            .and_then(|v| v.try_into().ok())
+            .ok_or(BpbError::Overflow("Data offset"))
+    }
+
+    /// Get bytes per cluster
+    ///
+    /// # Security
+    /// Uses checked arithmetic to prevent overflow
+    pub fn bytes_per_cluster(&self) -> Result<u32, BpbError> {
+        (self.sectors_per_cluster as u32)
+            .checked_mul(self.bytes_per_sector as u32)
+            .ok_or(BpbError::Overflow("Bytes per cluster"))
+    }
+
+    /// Get the number of clusters in the data region
+    ///
+    /// Valid cluster numbers are `2..cluster_count() + 2`.
+    pub fn cluster_count(&self) -> Result<u32, BpbError> {
+        let data_start_sector = self.data_offset()? / self.bytes_per_sector as u32;
+        let data_sectors = self.total_sectors().saturating_sub(data_start_sector);
+        Ok(data_sectors / self.sectors_per_cluster as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bpb_from_slice() {
+        let mut bytes = [0u8; 512];
+        bytes[11..13].copy_from_slice(&512u16.to_le_bytes());
+        bytes[13] = 1;
+        bytes[14..16].copy_from_slice(&1u16.to_le_bytes());
+        bytes[16] = 2;
+        bytes[17..19].copy_from_slice(&224u16.to_le_bytes());
+        bytes[19..21].copy_from_slice(&2880u16.to_le_bytes());
+        bytes[22..24].copy_from_slice(&9u16.to_le_bytes());
+
+        let bpb = BiosParameterBlock::from_bytes(&bytes).unwrap();
+        assert_eq!(bpb.fat_type, FatType::Fat12);
+        assert_eq!(bpb.fat_offset(), Ok(512));
+        assert_eq!(bpb.root_dir_offset(), Ok(19 * 512));
+        assert_eq!(bpb.data_offset(), Ok(33 * 512));
+        assert_eq!(bpb.cluster_count(), Ok(2880 - 33));
+
+        assert_eq!(BiosParameterBlock::from_bytes(&bytes[..64]).unwrap_err(), BpbError::Truncated);
+        bytes[3..11].copy_from_slice(b"EXFAT   ");
+        assert_eq!(BiosParameterBlock::from_bytes(&bytes).unwrap_err(), BpbError::Exfat);
+        bytes[3..11].fill(0);
+        bytes[11..13].copy_from_slice(&500u16.to_le_bytes());
+        assert_eq!(BiosParameterBlock::from_bytes(&bytes).unwrap_err(), BpbError::InvalidBytesPerSector(500));
+    }
+}
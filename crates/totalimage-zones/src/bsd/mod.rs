@@ -0,0 +1,260 @@
+//! BSD disklabel partition table implementation
+
+pub mod types;
+
+use totalimage_core::{Error, ReadSeek, ReadSeekExt, Result, Zone, ZoneTable};
+use types::{BsdFsType, BsdPartitionEntry, DisklabelHeader};
+
+/// BSD disklabel
+///
+/// The partitioning scheme of FreeBSD, OpenBSD and NetBSD. A disklabel
+/// either covers a whole disk or subdivides an MBR slice of type 0xA5
+/// (FreeBSD), 0xA6 (OpenBSD) or 0xA9 (NetBSD).
+///
+/// # Structure
+///
+/// ```text
+/// Sector 1 of the disk or slice:
+/// Offset  Size  Field
+/// ------  ----  -----
+/// 0       4     d_magic (0x82564557)
+/// 40      4     d_secsize
+/// 132     4     d_magic2 (0x82564557)
+/// 138     2     d_npartitions
+/// 148     16*N  Partition entries (p_size, p_offset, ..., p_fstype)
+/// ```
+///
+/// Partitions are lettered from `a`; `c` conventionally spans the whole
+/// disk or slice. Entries that are empty or of type `unused` (which
+/// includes `c`) are not reported as zones.
+#[derive(Debug, Clone)]
+pub struct BsdDisklabelZoneTable {
+    zones: Vec<Zone>,
+    header: DisklabelHeader,
+    entries: Vec<BsdPartitionEntry>,
+}
+
+impl BsdDisklabelZoneTable {
+    /// Sector of the disk or slice holding the disklabel
+    pub const LABEL_SECTOR: u64 = 1;
+
+    /// Index of the raw partition (`c`) spanning the disk or slice
+    pub const RAW_PARTITION: usize = 2;
+
+    /// Upper bound on the number of partition entries
+    ///
+    /// The most entries that fit in a 512-byte label sector.
+    pub const MAX_PARTITIONS: u16 = 22;
+
+    /// Parse a disklabel covering a whole disk
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - A stream positioned at the start of the disk
+    /// * `sector_size` - The sector size in bytes (usually 512)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Either disklabel magic is missing
+    /// - The partition count is zero or implausibly large
+    /// - The stream cannot be read
+    pub fn parse(stream: &mut dyn ReadSeek, sector_size: u32) -> Result<Self> {
+        Self::parse_at(stream, sector_size, 0)
+    }
+
+    /// Parse the disklabel inside an MBR slice
+    ///
+    /// OpenBSD, NetBSD and older FreeBSD record partition offsets relative
+    /// to the disk, newer FreeBSD relative to the slice. Offsets are taken
+    /// as disk-relative when the raw partition starts where the slice does.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`parse`](Self::parse)
+    pub fn parse_slice(stream: &mut dyn ReadSeek, sector_size: u32, slice: &Zone) -> Result<Self> {
+        Self::parse_at(stream, sector_size, slice.offset)
+    }
+
+    fn parse_at(stream: &mut dyn ReadSeek, sector_size: u32, base: u64) -> Result<Self> {
+        let mut sector = vec![0u8; sector_size.max(512) as usize];
+        stream.read_exact_at(base + Self::LABEL_SECTOR * sector_size as u64, &mut sector)?;
+
+        let header = DisklabelHeader::from_bytes(&sector).ok_or_else(|| {
+            Error::unrecognized_signature("Invalid BSD disklabel magic".to_string())
+        })?;
+
+        if header.partition_count == 0 || header.partition_count > Self::MAX_PARTITIONS {
+            return Err(Error::invalid_zone_table(format!(
+                "Invalid BSD disklabel partition count: {}",
+                header.partition_count
+            )));
+        }
+
+        let entries: Vec<BsdPartitionEntry> = sector[DisklabelHeader::PARTITIONS_OFFSET..]
+            .chunks_exact(BsdPartitionEntry::ENTRY_SIZE)
+            .take(header.partition_count as usize)
+            .filter_map(BsdPartitionEntry::from_bytes)
+            .collect();
+
+        // Prefer the label's own sector size when it's a plausible one
+        let label_sector_size = match header.sector_size {
+            512 | 1024 | 2048 | 4096 => header.sector_size as u64,
+            _ => sector_size as u64,
+        };
+
+        let disk_relative = entries
+            .get(Self::RAW_PARTITION)
+            .is_some_and(|raw| raw.offset as u64 * label_sector_size == base);
+        let origin = if disk_relative { 0 } else { base };
+
+        let zones = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.size != 0 && entry.fs_type != BsdFsType::UNUSED)
+            .map(|(i, entry)| {
+                Zone::new(
+                    i,
+                    origin + entry.offset as u64 * label_sector_size,
+                    entry.size as u64 * label_sector_size,
+                    format!("{}: {}", partition_letter(i), entry.fs_type.name()),
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            zones,
+            header,
+            entries,
+        })
+    }
+
+    /// Get the disklabel header
+    pub fn header(&self) -> &DisklabelHeader {
+        &self.header
+    }
+
+    /// Get all partition entries, including empty and unused ones
+    pub fn entries(&self) -> &[BsdPartitionEntry] {
+        &self.entries
+    }
+}
+
+impl ZoneTable for BsdDisklabelZoneTable {
+    fn identify(&self) -> &str {
+        "BSD Disklabel"
+    }
+
+    fn enumerate_zones(&self) -> &[Zone] {
+        &self.zones
+    }
+}
+
+/// Get the letter BSD uses for a partition index (`a` for 0)
+fn partition_letter(index: usize) -> char {
+    (b'a' + index as u8) as char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MbrZoneTable;
+    use std::io::Cursor;
+
+    /// Write a disklabel with `a` (4.2BSD), `b` (swap) and the raw `c`
+    /// partition, with offsets relative to `origin_sector`
+    fn write_disklabel(disk: &mut [u8], label_offset: usize, origin_sector: u32, slice_sectors: u32) {
+        let label = &mut disk[label_offset..label_offset + 512];
+        label[0..4].copy_from_slice(&DisklabelHeader::MAGIC.to_le_bytes());
+        label[40..44].copy_from_slice(&512u32.to_le_bytes());
+        label[60..64].copy_from_slice(&slice_sectors.to_le_bytes());
+        label[132..136].copy_from_slice(&DisklabelHeader::MAGIC.to_le_bytes());
+        label[138..140].copy_from_slice(&8u16.to_le_bytes());
+
+        let partitions: [(u32, u32, u8); 3] = [
+            (16, 64, 7),
+            (16 + 64, 32, 1),
+            (0, slice_sectors, 0),
+        ];
+        for (i, (offset, size, fs_type)) in partitions.into_iter().enumerate() {
+            let entry = &mut label[148 + i * 16..164 + i * 16];
+            entry[0..4].copy_from_slice(&size.to_le_bytes());
+            entry[4..8].copy_from_slice(&(origin_sector + offset).to_le_bytes());
+            entry[12] = fs_type;
+        }
+    }
+
+    /// Create an MBR disk with a FreeBSD slice at sector 63
+    fn create_sliced_disk(disk_relative: bool) -> Vec<u8> {
+        let mut disk = vec![0u8; 256 * 512];
+        disk[0x1BE + 4] = 0xA5;
+        disk[0x1BE + 8..0x1BE + 12].copy_from_slice(&63u32.to_le_bytes());
+        disk[0x1BE + 12..0x1BE + 16].copy_from_slice(&128u32.to_le_bytes());
+        disk[0x1FE] = 0x55;
+        disk[0x1FF] = 0xAA;
+
+        let origin = if disk_relative { 63 } else { 0 };
+        write_disklabel(&mut disk, 64 * 512, origin, 128);
+        disk
+    }
+
+    #[test]
+    fn test_parse_whole_disk() {
+        let mut disk = vec![0u8; 128 * 512];
+        write_disklabel(&mut disk, 512, 0, 128);
+
+        let table = BsdDisklabelZoneTable::parse(&mut Cursor::new(disk), 512).unwrap();
+        assert_eq!(table.identify(), "BSD Disklabel");
+        assert_eq!(table.header().partition_count, 8);
+        assert_eq!(table.entries().len(), 8);
+
+        // The raw partition and unused entries are omitted
+        let zones = table.enumerate_zones();
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[0].index, 0);
+        assert_eq!(zones[0].offset, 16 * 512);
+        assert_eq!(zones[0].length, 64 * 512);
+        assert_eq!(zones[0].zone_type, "a: 4.2BSD");
+        assert_eq!(zones[1].index, 1);
+        assert_eq!(zones[1].offset, 80 * 512);
+        assert_eq!(zones[1].length, 32 * 512);
+        assert_eq!(zones[1].zone_type, "b: swap");
+    }
+
+    #[test]
+    fn test_parse_slice() {
+        for disk_relative in [false, true] {
+            let mut cursor = Cursor::new(create_sliced_disk(disk_relative));
+            let mbr = MbrZoneTable::parse(&mut cursor, 512).unwrap();
+            let slices = mbr.bsd_slices();
+            assert_eq!(slices.len(), 1);
+            assert_eq!(slices[0].zone_type, "FreeBSD");
+
+            let table = BsdDisklabelZoneTable::parse_slice(&mut cursor, 512, slices[0]).unwrap();
+            let zones = table.enumerate_zones();
+            assert_eq!(zones.len(), 2);
+            assert_eq!(zones[0].offset, (63 + 16) * 512);
+            assert_eq!(zones[1].offset, (63 + 80) * 512);
+            assert_eq!(zones[1].length, 32 * 512);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_disklabel() {
+        // No label at all
+        let disk = vec![0u8; 4096];
+        assert!(matches!(
+            BsdDisklabelZoneTable::parse(&mut Cursor::new(disk), 512),
+            Err(Error::UnrecognizedSignature(_))
+        ));
+
+        // Implausible partition count
+        let mut disk = vec![0u8; 128 * 512];
+        write_disklabel(&mut disk, 512, 0, 128);
+        disk[512 + 138..512 + 140].copy_from_slice(&200u16.to_le_bytes());
+        assert!(BsdDisklabelZoneTable::parse(&mut Cursor::new(disk), 512).is_err());
+
+        // The label of a slice is not at sector 1 of the disk
+        assert!(BsdDisklabelZoneTable::parse(&mut Cursor::new(create_sliced_disk(false)), 512).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! BSD disklabel structures
+//!
+//! All multi-byte fields are little-endian (the byte order of the x86
+//! systems whose disks these images come from).
+
+/// Disklabel header (`struct disklabel`, up to the partition table)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisklabelHeader {
+    /// Bytes per sector (`d_secsize`)
+    pub sector_size: u32,
+    /// Total sectors on the unit (`d_secperunit`)
+    pub sectors_per_unit: u32,
+    /// Number of partition entries that follow (`d_npartitions`)
+    pub partition_count: u16,
+}
+
+impl DisklabelHeader {
+    /// Disklabel magic, stored both at the start and after the drive data
+    pub const MAGIC: u32 = 0x8256_4557;
+
+    /// Offset of `d_magic2`
+    pub const MAGIC2_OFFSET: usize = 132;
+
+    /// Offset of the first partition entry
+    pub const PARTITIONS_OFFSET: usize = 148;
+
+    /// Parse a disklabel header, returning None if either magic is missing
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Self::PARTITIONS_OFFSET {
+            return None;
+        }
+
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+        };
+
+        if read_u32(0) != Self::MAGIC || read_u32(Self::MAGIC2_OFFSET) != Self::MAGIC {
+            return None;
+        }
+
+        Some(Self {
+            sector_size: read_u32(40),
+            sectors_per_unit: read_u32(60),
+            partition_count: u16::from_le_bytes([bytes[138], bytes[139]]),
+        })
+    }
+}
+
+/// Disklabel partition entry (`struct partition`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BsdPartitionEntry {
+    /// Number of sectors in the partition (`p_size`)
+    pub size: u32,
+    /// First sector of the partition (`p_offset`)
+    pub offset: u32,
+    /// File system type (`p_fstype`)
+    pub fs_type: BsdFsType,
+}
+
+impl BsdPartitionEntry {
+    /// Size of each partition entry
+    pub const ENTRY_SIZE: usize = 16;
+
+    /// Parse a partition entry
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Self::ENTRY_SIZE {
+            return None;
+        }
+
+        Some(Self {
+            size: u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            offset: u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+            fs_type: BsdFsType(bytes[12]),
+        })
+    }
+}
+
+/// Disklabel file system type (`p_fstype`)
+///
+/// Codes up to 13 are shared by all BSDs; later codes diverge, so only the
+/// ones FreeBSD, NetBSD and OpenBSD agree on are named.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BsdFsType(pub u8);
+
+impl BsdFsType {
+    /// Unused entry
+    pub const UNUSED: Self = Self(0);
+
+    /// Get a human-readable name for this file system type
+    pub fn name(&self) -> &str {
+        match self.0 {
+            0 => "unused",
+            1 => "swap",
+            2 => "Version 6",
+            3 => "Version 7",
+            4 => "System V",
+            5 => "4.1BSD",
+            6 => "Eighth Edition",
+            7 => "4.2BSD",
+            8 => "MSDOS",
+            9 => "4.4LFS",
+            10 => "unknown",
+            11 => "HPFS",
+            12 => "ISO9660",
+            13 => "boot",
+            17 => "ext2fs",
+            18 => "NTFS",
+            20 => "ccd",
+            _ => "Unknown",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_magic() {
+        let mut label = [0u8; 512];
+        label[0..4].copy_from_slice(&DisklabelHeader::MAGIC.to_le_bytes());
+        label[132..136].copy_from_slice(&DisklabelHeader::MAGIC.to_le_bytes());
+        label[40..44].copy_from_slice(&512u32.to_le_bytes());
+        label[138..140].copy_from_slice(&8u16.to_le_bytes());
+
+        let header = DisklabelHeader::from_bytes(&label).unwrap();
+        assert_eq!(header.sector_size, 512);
+        assert_eq!(header.partition_count, 8);
+
+        // Both magics are required
+        label[132] = 0;
+        assert!(DisklabelHeader::from_bytes(&label).is_none());
+    }
+
+    #[test]
+    fn test_fs_type_name() {
+        assert_eq!(BsdFsType(7).name(), "4.2BSD");
+        assert_eq!(BsdFsType(1).name(), "swap");
+        assert_eq!(BsdFsType(200).name(), "Unknown");
+    }
+}
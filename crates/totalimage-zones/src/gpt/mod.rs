@@ -2,9 +2,14 @@
 
 pub mod types;
 
+#[cfg(feature = "std")]
 use std::io::SeekFrom;
-use totalimage_core::{Error, ReadSeek, Result, Zone, ZoneTable};
-use types::{GptHeader, GptPartitionEntry};
+#[cfg(feature = "std")]
+use crate::warnings::{check_zones, ZoneWarning};
+#[cfg(feature = "std")]
+use totalimage_core::{Error, ReadSeek, ReadSeekExt, Result, Zone, ZoneTable};
+#[cfg(feature = "std")]
+use types::{format_guid, GptHeader, GptPartitionEntry};
 
 /// GPT partition table
 ///
@@ -22,12 +27,16 @@ use types::{GptHeader, GptPartitionEntry};
 /// Last 33:  Backup partition entries array
 /// Last 1:   Backup GPT header
 /// ```
+#[cfg(feature = "std")]
 #[derive(Debug, Clone)]
 pub struct GptZoneTable {
     zones: Vec<Zone>,
+    entries: Vec<GptPartitionEntry>,
     header: GptHeader,
+    warnings: Vec<ZoneWarning>,
 }
 
+#[cfg(feature = "std")]
 impl GptZoneTable {
     /// Parse a GPT from a readable and seekable stream
     ///
@@ -40,9 +49,14 @@ impl GptZoneTable {
     ///
     /// Returns an error if:
     /// - The GPT signature is invalid
+    /// - The header's layout doesn't fit `sector_size` or the disk, which
+    ///   usually means the wrong sector size was given
     /// - The stream cannot be read
-    /// - The partition table is corrupted
+    /// - The partition table is corrupted, or its entry size is smaller
+    ///   than 128 bytes or not a multiple of 8
     pub fn parse(stream: &mut dyn ReadSeek, sector_size: u32) -> Result<Self> {
+        let disk_size = stream.seek(SeekFrom::End(0))?;
+
         // GPT header is at LBA 1 (second sector)
         let header_lba = 1u64;
         let header_offset = header_lba * sector_size as u64;
@@ -51,12 +65,23 @@ impl GptZoneTable {
 
         // Read GPT header
         let mut header_bytes = vec![0u8; sector_size as usize];
-        stream.read_exact(&mut header_bytes)?;
-
-        let header = GptHeader::from_bytes(&header_bytes).ok_or_else(|| {
-            Error::invalid_zone_table("Invalid GPT header signature".to_string())
+        let header = match stream.read_exact(&mut header_bytes) {
+            Ok(()) => GptHeader::from_bytes(&header_bytes),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => None,
+            Err(e) => return Err(e.into()),
+        };
+        // A header found at another sector size means a GPT disk read wrongly
+        let header = header.ok_or_else(|| {
+            let msg = "Invalid GPT header signature";
+            match sector_size_hint(stream, sector_size) {
+                Some(hint) => Error::invalid_zone_table(format!("{}{}", msg, hint)),
+                None => Error::unrecognized_signature(msg),
+            }
         })?;
 
+        // Catch a mismatched sector size before it surfaces as a CRC failure
+        check_layout(&header, sector_size, disk_size)?;
+
         // Verify header CRC32 (SEC-006: Checksum enforcement)
         if !header.verify_header_crc32(&header_bytes) {
             return Err(Error::ChecksumVerification(
@@ -64,18 +89,27 @@ impl GptZoneTable {
             ));
         }
 
-        // Read partition entries
+        // Entries may grow past the 128 bytes parsed here, but never shrink
+        if header.partition_entry_size < GptPartitionEntry::ENTRY_SIZE as u32
+            || header.partition_entry_size % 8 != 0
+        {
+            return Err(Error::invalid_zone_table(format!(
+                "GPT partition entry size {} is invalid: must be a multiple of 8 and at least {}",
+                header.partition_entry_size,
+                GptPartitionEntry::ENTRY_SIZE
+            )));
+        }
+
+        // Read partition entries, striding by the header's entry size
         let entries_lba = header.partition_entries_lba;
         let entries_offset = entries_lba * sector_size as u64;
         let num_entries = header.num_partition_entries;
         let entry_size = header.partition_entry_size as usize;
 
-        stream.seek(SeekFrom::Start(entries_offset))?;
-
         // Read all partition entries at once for CRC32 verification
         let total_entries_size = num_entries as usize * entry_size;
         let mut all_entries_bytes = vec![0u8; total_entries_size];
-        stream.read_exact(&mut all_entries_bytes)?;
+        stream.read_exact_at(entries_offset, &mut all_entries_bytes)?;
 
         // Verify partition entries CRC32 (SEC-006: Checksum enforcement)
         if !header.verify_partition_entries_crc32(&all_entries_bytes) {
@@ -86,6 +120,7 @@ impl GptZoneTable {
 
         // Parse individual partition entries
         let mut zones = Vec::new();
+        let mut entries = Vec::new();
 
         for i in 0..num_entries {
             let entry_start = i as usize * entry_size;
@@ -114,9 +149,11 @@ impl GptZoneTable {
             let zone = Zone::new(i as usize, zone_offset, zone_length, zone_type);
 
             zones.push(zone);
+            entries.push(entry);
         }
 
-        Ok(Self { zones, header })
+        let warnings = check_zones(&zones, disk_size);
+        Ok(Self { zones, entries, header, warnings })
     }
 
     /// Get the disk GUID
@@ -124,11 +161,39 @@ impl GptZoneTable {
         &self.header.disk_guid
     }
 
+    /// Get the disk GUID as a string
+    pub fn disk_guid_string(&self) -> String {
+        format_guid(&self.header.disk_guid)
+    }
+
+    /// Get the partition entry backing the zone with the given index
+    pub fn partition_entry(&self, zone_index: usize) -> Option<&GptPartitionEntry> {
+        self.zones
+            .iter()
+            .position(|zone| zone.index == zone_index)
+            .map(|position| &self.entries[position])
+    }
+
+    /// Get the partition type GUID of a zone as a string
+    pub fn type_guid_string(&self, zone_index: usize) -> Option<String> {
+        self.partition_entry(zone_index).map(GptPartitionEntry::type_guid_string)
+    }
+
+    /// Get the unique partition GUID of a zone as a string
+    pub fn unique_guid_string(&self, zone_index: usize) -> Option<String> {
+        self.partition_entry(zone_index).map(GptPartitionEntry::unique_guid_string)
+    }
+
     /// Get the GPT header
     pub fn header(&self) -> &GptHeader {
         &self.header
     }
 
+    /// Get the partitions found to overlap or run past the end of the disk
+    pub fn validation_warnings(&self) -> &[ZoneWarning] {
+        &self.warnings
+    }
+
     /// Get the number of usable sectors on the disk
     pub fn usable_lba_count(&self) -> u64 {
         if self.header.last_usable_lba >= self.header.first_usable_lba {
@@ -139,6 +204,61 @@ impl GptZoneTable {
     }
 }
 
+/// Check that the header's LBAs make sense for `sector_size`
+#[cfg(feature = "std")]
+fn check_layout(header: &GptHeader, sector_size: u32, disk_size: u64) -> Result<()> {
+    let mismatch = |detail: String| {
+        Error::invalid_zone_table(format!(
+            "GPT header inconsistent with {}-byte sectors: {} (is the sector size wrong?)",
+            sector_size, detail
+        ))
+    };
+
+    if header.current_lba != 1 {
+        return Err(mismatch(format!("header claims LBA {} but was read from LBA 1", header.current_lba)));
+    }
+    if header.partition_entries_lba < 2 || header.first_usable_lba < 3 {
+        return Err(mismatch(format!(
+            "partition entries at LBA {} and first usable LBA {} overlap the header",
+            header.partition_entries_lba, header.first_usable_lba
+        )));
+    }
+
+    let entries_end = header
+        .partition_entries_lba
+        .checked_mul(sector_size as u64)
+        .and_then(|start| {
+            start.checked_add(header.num_partition_entries as u64 * header.partition_entry_size as u64)
+        });
+    if entries_end.is_none_or(|end| end > disk_size) {
+        return Err(mismatch(format!(
+            "partition entries at LBA {} extend past the end of the {}-byte disk",
+            header.partition_entries_lba, disk_size
+        )));
+    }
+
+    Ok(())
+}
+
+/// Suggest another common sector size if the GPT header is found there
+#[cfg(feature = "std")]
+fn sector_size_hint(stream: &mut dyn ReadSeek, sector_size: u32) -> Option<String> {
+    [512u32, 4096]
+        .into_iter()
+        .filter(|&other| other != sector_size)
+        .find(|&other| {
+            let mut signature = [0u8; 8];
+            stream.read_exact_at(other as u64, &mut signature).is_ok() && &signature == GptHeader::SIGNATURE
+        })
+        .map(|other| {
+            format!(
+                "; a GPT header was found for {}-byte sectors, but {}-byte sectors were requested",
+                other, sector_size
+            )
+        })
+}
+
+#[cfg(feature = "std")]
 impl ZoneTable for GptZoneTable {
     fn identify(&self) -> &str {
         "GUID Partition Table"
@@ -149,7 +269,7 @@ impl ZoneTable for GptZoneTable {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use std::io::Cursor;
@@ -242,6 +362,15 @@ mod tests {
             disk[entry_offset + 56 + i * 2 + 1] = bytes[1];
         }
 
+        update_crcs(&mut disk);
+        disk
+    }
+
+    /// Recompute the partition entries and header CRC32s of a test GPT
+    fn update_crcs(disk: &mut [u8]) {
+        let header_offset = 512;
+        let entries_offset = 2 * 512;
+
         // Calculate and set partition entries CRC32
         let entries_size = 128 * 128; // num_entries * entry_size
         let entries_crc = crc32fast::hash(&disk[entries_offset..entries_offset + entries_size]);
@@ -255,8 +384,31 @@ mod tests {
         header_for_crc[19] = 0;
         let header_crc = crc32fast::hash(&header_for_crc);
         disk[header_offset + 16..header_offset + 20].copy_from_slice(&header_crc.to_le_bytes());
+    }
 
-        disk
+    #[test]
+    fn test_validation_warnings() {
+        let disk = create_test_gpt();
+        let table = GptZoneTable::parse(&mut Cursor::new(disk.clone()), 512).unwrap();
+        assert!(table.validation_warnings().is_empty());
+
+        // Entry 1 copies entry 0 and moves it to LBA 150-1199, overlapping
+        // entry 0 and running past the 1000-sector disk
+        let mut disk = disk;
+        disk.copy_within(1024..1024 + 128, 1024 + 128);
+        disk[1024 + 128 + 32..1024 + 128 + 40].copy_from_slice(&150u64.to_le_bytes());
+        disk[1024 + 128 + 40..1024 + 128 + 48].copy_from_slice(&1199u64.to_le_bytes());
+        update_crcs(&mut disk);
+
+        let table = GptZoneTable::parse(&mut Cursor::new(disk), 512).unwrap();
+        assert_eq!(table.enumerate_zones().len(), 2);
+        assert_eq!(
+            table.validation_warnings(),
+            [
+                ZoneWarning::OutOfBounds { index: 1, end: 1200 * 512, disk_size: 1000 * 512 },
+                ZoneWarning::Overlap { first: 0, second: 1, shared: 50 * 512 },
+            ]
+        );
     }
 
     #[test]
@@ -270,6 +422,82 @@ mod tests {
         assert_eq!(table.enumerate_zones().len(), 1);
     }
 
+    #[test]
+    fn test_detect_prefers_gpt_over_protective_mbr() {
+        let mut disk = create_test_gpt();
+
+        // Protective MBR: one 0xEE partition covering the disk
+        disk[446 + 4] = 0xEE;
+        disk[446 + 8..446 + 12].copy_from_slice(&1u32.to_le_bytes());
+        disk[446 + 12..446 + 16].copy_from_slice(&999u32.to_le_bytes());
+        disk[510] = 0x55;
+        disk[511] = 0xAA;
+
+        let mut cursor = Cursor::new(disk);
+        assert!(crate::MbrZoneTable::parse(&mut cursor, 512).unwrap().is_gpt_protective());
+
+        let detected = crate::detect_zone_table(&mut cursor, 512).unwrap();
+        assert!(matches!(detected, crate::DetectedZoneTable::Gpt(_)));
+
+        let zones = detected.as_zone_table().enumerate_zones();
+        assert_eq!(zones.len(), 1);
+        assert_eq!(zones[0].offset, 100 * 512);
+        assert!(zones.iter().all(|z| z.zone_type != "GPT Protective"));
+
+        // With the GPT header destroyed only the protective MBR remains
+        let mut disk = cursor.into_inner();
+        disk[512..520].copy_from_slice(b"NOT GPT!");
+        let detected = crate::detect_zone_table(&mut Cursor::new(disk), 512).unwrap();
+        assert!(matches!(detected, crate::DetectedZoneTable::Mbr(ref mbr) if mbr.is_gpt_protective()));
+    }
+
+    #[test]
+    fn test_partition_scheme_hybrid_mbr() {
+        let mut disk = create_test_gpt();
+        disk[510] = 0x55;
+        disk[511] = 0xAA;
+
+        // Real FAT32 partition at LBA 300-399, outside the GPT's one partition
+        disk[446 + 4] = 0x0C;
+        disk[446 + 8..446 + 12].copy_from_slice(&300u32.to_le_bytes());
+        disk[446 + 12..446 + 16].copy_from_slice(&100u32.to_le_bytes());
+
+        let scheme = crate::parse_partition_scheme(&mut Cursor::new(disk.clone()), 512);
+        assert!(scheme.mbr.is_some());
+        assert!(scheme.gpt.is_some());
+        assert!(!scheme.is_protective());
+        assert!(scheme.is_hybrid());
+        assert_eq!(
+            scheme.warnings,
+            [
+                crate::SchemeWarning::UnprotectedGpt,
+                crate::SchemeWarning::UncoveredMbrPartition { index: 0, offset: 300 * 512, length: 100 * 512 },
+            ]
+        );
+
+        // Add a protective partition and mirror the GPT partition instead
+        disk[446 + 8..446 + 12].copy_from_slice(&100u32.to_le_bytes());
+        disk[462 + 4] = 0xEE;
+        disk[462 + 8..462 + 12].copy_from_slice(&1u32.to_le_bytes());
+        disk[462 + 12..462 + 16].copy_from_slice(&99u32.to_le_bytes());
+
+        let scheme = crate::parse_partition_scheme(&mut Cursor::new(disk.clone()), 512);
+        assert!(scheme.is_hybrid());
+        assert_eq!(scheme.warnings, [crate::SchemeWarning::HybridMbr { partitions: 1 }]);
+
+        // A purely protective MBR agrees with its GPT
+        disk[446..462].fill(0);
+        let scheme = crate::parse_partition_scheme(&mut Cursor::new(disk.clone()), 512);
+        assert!(scheme.is_protective());
+        assert!(!scheme.is_hybrid());
+        assert!(scheme.warnings.is_empty());
+
+        disk[512..520].copy_from_slice(b"NOT GPT!");
+        let scheme = crate::parse_partition_scheme(&mut Cursor::new(disk), 512);
+        assert!(scheme.gpt.is_none());
+        assert_eq!(scheme.warnings, [crate::SchemeWarning::MissingGpt]);
+    }
+
     #[test]
     fn test_parse_gpt_zone_details() {
         let gpt_data = create_test_gpt();
@@ -295,7 +523,32 @@ mod tests {
         let mut cursor = Cursor::new(gpt_data);
         let result = GptZoneTable::parse(&mut cursor, 512);
 
-        assert!(result.is_err());
+        assert!(matches!(result, Err(Error::UnrecognizedSignature(_))));
+    }
+
+    #[test]
+    fn test_parse_with_wrong_sector_size() {
+        let mut cursor = Cursor::new(create_test_gpt());
+
+        let err = GptZoneTable::parse(&mut cursor, 4096).unwrap_err();
+        match err {
+            Error::InvalidZoneTable(msg) => {
+                assert!(msg.contains("found for 512-byte sectors"), "{}", msg);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        // A header whose LBAs don't fit the disk is reported before its CRC
+        let mut disk = cursor.into_inner();
+        disk[512 + 72..512 + 80].copy_from_slice(&5000u64.to_le_bytes());
+        let err = GptZoneTable::parse(&mut Cursor::new(disk), 512).unwrap_err();
+        match err {
+            Error::InvalidZoneTable(msg) => {
+                assert!(msg.contains("is the sector size wrong?"), "{}", msg);
+                assert!(msg.contains("extend past the end"), "{}", msg);
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
     }
 
     #[test]
@@ -325,6 +578,49 @@ mod tests {
         assert!(matches!(result, Err(Error::ChecksumVerification(_))));
     }
 
+    #[test]
+    fn test_gpt_wide_partition_entries() {
+        // 64 entries of 256 bytes fill the same 32 sectors as 128 of 128
+        let mut disk = create_test_gpt();
+        disk[512 + 80..512 + 84].copy_from_slice(&64u32.to_le_bytes());
+        disk[512 + 84..512 + 88].copy_from_slice(&256u32.to_le_bytes());
+
+        // Entry 1 starts 256 bytes in: a copy of entry 0 at LBA 200-299
+        disk.copy_within(1024..1024 + 128, 1024 + 256);
+        disk[1024 + 256 + 32..1024 + 256 + 40].copy_from_slice(&200u64.to_le_bytes());
+        disk[1024 + 256 + 40..1024 + 256 + 48].copy_from_slice(&299u64.to_le_bytes());
+        // The growth area past byte 128 is still covered by the CRC
+        disk[1024 + 200] = 0xAA;
+        update_crcs(&mut disk);
+
+        let table = GptZoneTable::parse(&mut Cursor::new(disk.clone()), 512).unwrap();
+        let zones = table.enumerate_zones();
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[0].offset, 100 * 512);
+        assert_eq!(zones[1].index, 1);
+        assert_eq!(zones[1].offset, 200 * 512);
+
+        disk[1024 + 200] = 0xBB;
+        let result = GptZoneTable::parse(&mut Cursor::new(disk), 512);
+        assert!(matches!(result, Err(Error::ChecksumVerification(_))));
+    }
+
+    #[test]
+    fn test_gpt_invalid_entry_size() {
+        for entry_size in [0u32, 64, 132] {
+            let mut disk = create_test_gpt();
+            disk[512 + 84..512 + 88].copy_from_slice(&entry_size.to_le_bytes());
+            update_crcs(&mut disk);
+
+            match GptZoneTable::parse(&mut Cursor::new(disk), 512) {
+                Err(Error::InvalidZoneTable(msg)) => {
+                    assert!(msg.contains("entry size"), "{}", msg)
+                }
+                other => panic!("{}: unexpected result: {:?}", entry_size, other),
+            }
+        }
+    }
+
     #[test]
     fn test_gpt_disk_guid() {
         let gpt_data = create_test_gpt();
@@ -337,6 +633,26 @@ mod tests {
         assert_eq!(table.disk_guid(), &expected_guid);
     }
 
+    #[test]
+    fn test_gpt_guid_strings() {
+        let mut cursor = Cursor::new(create_test_gpt());
+        let table = GptZoneTable::parse(&mut cursor, 512).unwrap();
+
+        assert_eq!(table.disk_guid_string(), "78563412-BC9A-F0DE-1234-56789ABCDEF0");
+        assert_eq!(
+            table.type_guid_string(0).as_deref(),
+            Some("0FC63DAF-8483-4772-8E79-3D69D8477DE4")
+        );
+        assert_eq!(
+            table.unique_guid_string(0).as_deref(),
+            Some("04030201-0605-0807-090A-0B0C0D0E0F10")
+        );
+
+        // Unused entries have no zone
+        assert!(table.partition_entry(1).is_none());
+        assert!(table.unique_guid_string(1).is_none());
+    }
+
     #[test]
     fn test_gpt_usable_lba_count() {
         let gpt_data = create_test_gpt();
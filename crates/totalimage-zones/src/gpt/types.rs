@@ -1,6 +1,13 @@
 //! GPT partition types and structures
+//!
+//! Everything here parses from byte slices and, apart from
+//! [`PartitionTypeGuid::from_guid_string`], is available without the `std`
+//! feature.
 
-use std::fmt;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
 
 /// GPT partition type GUID
 ///
@@ -36,17 +43,108 @@ impl PartitionTypeGuid {
         0x84, 0xe5, 0x09, 0x33, 0xc8, 0x4b, 0x4f, 0x4f,
     ]);
 
+    /// Microsoft Reserved Partition
+    pub const MICROSOFT_RESERVED: Self = Self([
+        0x16, 0xe3, 0xc9, 0xe3, 0x5c, 0x0b, 0xb8, 0x4d,
+        0x81, 0x7d, 0xf9, 0x2d, 0xf0, 0x02, 0x15, 0xae,
+    ]);
+
+    /// Windows Recovery Environment
+    pub const WINDOWS_RECOVERY: Self = Self([
+        0xa4, 0xbb, 0x94, 0xde, 0xd1, 0x06, 0x40, 0x4d,
+        0xa1, 0x6a, 0xbf, 0xd5, 0x01, 0x79, 0xd6, 0xac,
+    ]);
+
+    /// BIOS boot partition (GRUB)
+    pub const BIOS_BOOT: Self = Self([
+        0x48, 0x61, 0x68, 0x21, 0x49, 0x64, 0x6f, 0x6e,
+        0x74, 0x4e, 0x65, 0x65, 0x64, 0x45, 0x46, 0x49,
+    ]);
+
+    /// Linux LVM
+    pub const LINUX_LVM: Self = Self([
+        0x79, 0xd3, 0xd6, 0xe6, 0x07, 0xf5, 0xc2, 0x44,
+        0xa2, 0x3c, 0x23, 0x8f, 0x2a, 0x3d, 0xf9, 0x28,
+    ]);
+
+    /// Linux RAID
+    pub const LINUX_RAID: Self = Self([
+        0x0f, 0x88, 0x9d, 0xa1, 0xfc, 0x05, 0x3b, 0x4d,
+        0xa0, 0x06, 0x74, 0x3f, 0x0f, 0x84, 0x91, 0x1e,
+    ]);
+
+    /// Apple HFS+
+    pub const APPLE_HFS: Self = Self([
+        0x00, 0x53, 0x46, 0x48, 0x00, 0x00, 0xaa, 0x11,
+        0xaa, 0x11, 0x00, 0x30, 0x65, 0x43, 0xec, 0xac,
+    ]);
+
+    /// Apple APFS container
+    pub const APPLE_APFS: Self = Self([
+        0xef, 0x57, 0x34, 0x7c, 0x00, 0x00, 0xaa, 0x11,
+        0xaa, 0x11, 0x00, 0x30, 0x65, 0x43, 0xec, 0xac,
+    ]);
+
+    /// Well-known partition types and their friendly names
+    pub const WELL_KNOWN: &'static [(Self, &'static str)] = &[
+        (Self::UNUSED, "Unused"),
+        (Self::EFI_SYSTEM, "EFI System"),
+        (Self::MICROSOFT_BASIC_DATA, "Microsoft Basic Data"),
+        (Self::MICROSOFT_RESERVED, "Microsoft Reserved"),
+        (Self::WINDOWS_RECOVERY, "Windows Recovery"),
+        (Self::BIOS_BOOT, "BIOS Boot"),
+        (Self::LINUX_FILESYSTEM, "Linux filesystem"),
+        (Self::LINUX_SWAP, "Linux swap"),
+        (Self::LINUX_LVM, "Linux LVM"),
+        (Self::LINUX_RAID, "Linux RAID"),
+        (Self::APPLE_HFS, "Apple HFS+"),
+        (Self::APPLE_APFS, "Apple APFS"),
+    ];
+
     /// Get a human-readable name for this partition type
-    pub fn name(&self) -> &str {
-        match *self {
-            Self::UNUSED => "Unused",
-            Self::EFI_SYSTEM => "EFI System",
-            Self::MICROSOFT_BASIC_DATA => "Microsoft Basic Data",
-            Self::LINUX_FILESYSTEM => "Linux filesystem",
-            Self::LINUX_SWAP => "Linux swap",
-            _ => "Unknown",
-        }
+    pub fn name(&self) -> &'static str {
+        Self::WELL_KNOWN
+            .iter()
+            .find(|(guid, _)| guid == self)
+            .map_or("Unknown", |(_, name)| name)
+    }
+
+    /// Look up a well-known partition type from its GUID string
+    ///
+    /// Accepts the canonical `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX` form in
+    /// either case. Returns None for malformed or unknown GUIDs.
+    #[cfg(feature = "std")]
+    pub fn from_guid_string(guid: &str) -> Option<Self> {
+        let bytes = uuid::Uuid::parse_str(guid).ok()?.to_bytes_le();
+        Self::WELL_KNOWN
+            .iter()
+            .map(|(known, _)| *known)
+            .find(|known| known.0 == bytes)
     }
+
+    /// Format as a GUID string
+    pub fn to_guid_string(&self) -> String {
+        format_guid(&self.0)
+    }
+}
+
+/// Format an on-disk GUID as an uppercase string
+///
+/// GPT stores GUIDs mixed-endian: the first three fields are little-endian
+/// and the last two are big-endian, so the raw bytes can't be hex-dumped
+/// in order.
+pub fn format_guid(bytes: &[u8; 16]) -> String {
+    let mut node = [0u8; 8];
+    node[2..].copy_from_slice(&bytes[10..]);
+    format!(
+        "{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:012X}",
+        u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        u16::from_le_bytes([bytes[4], bytes[5]]),
+        u16::from_le_bytes([bytes[6], bytes[7]]),
+        bytes[8],
+        bytes[9],
+        u64::from_be_bytes(node)
+    )
 }
 
 impl fmt::Display for PartitionTypeGuid {
@@ -57,7 +155,9 @@ impl fmt::Display for PartitionTypeGuid {
 
 /// GPT partition entry
 ///
-/// Each partition entry is 128 bytes and describes one partition on the disk.
+/// Each partition entry describes one partition on the disk. Entries are at
+/// least 128 bytes; larger ones, as declared by the header, carry reserved
+/// space after the fields parsed here.
 #[derive(Debug, Clone)]
 pub struct GptPartitionEntry {
     /// Partition type GUID
@@ -125,6 +225,16 @@ impl GptPartitionEntry {
         self.partition_type_guid == PartitionTypeGuid::UNUSED
     }
 
+    /// Get the partition type GUID as a string
+    pub fn type_guid_string(&self) -> String {
+        self.partition_type_guid.to_guid_string()
+    }
+
+    /// Get the unique partition GUID as a string
+    pub fn unique_guid_string(&self) -> String {
+        format_guid(&self.unique_partition_guid)
+    }
+
     /// Get the size of this partition in LBA sectors
     pub fn size_lba(&self) -> u64 {
         if self.last_lba >= self.first_lba {
@@ -135,18 +245,20 @@ impl GptPartitionEntry {
     }
 
     /// Parse UTF-16LE partition name from bytes
+    ///
+    /// Only the null padding after the name is trimmed; interior nulls are
+    /// kept. Surrogate pairs decode to one character, and lone surrogates
+    /// become U+FFFD rather than failing.
     fn parse_name(bytes: &[u8]) -> String {
-        // Convert bytes to u16 values (UTF-16LE)
-        let mut utf16_chars = Vec::new();
-        for i in (0..bytes.len()).step_by(2) {
-            let char_code = u16::from_le_bytes([bytes[i], bytes[i + 1]]);
-            if char_code == 0 {
-                break; // Null terminator
-            }
-            utf16_chars.push(char_code);
+        let mut units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        while units.last() == Some(&0) {
+            units.pop();
         }
 
-        String::from_utf16_lossy(&utf16_chars)
+        String::from_utf16_lossy(&units)
     }
 }
 
@@ -319,6 +431,7 @@ impl GptHeader {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn test_partition_type_guid_names() {
@@ -327,6 +440,49 @@ mod tests {
         assert_eq!(PartitionTypeGuid::LINUX_FILESYSTEM.name(), "Linux filesystem");
     }
 
+    #[test]
+    fn test_format_guid() {
+        assert_eq!(
+            PartitionTypeGuid::EFI_SYSTEM.to_guid_string(),
+            "C12A7328-F81F-11D2-BA4B-00A0C93EC93B"
+        );
+        assert_eq!(
+            PartitionTypeGuid::MICROSOFT_BASIC_DATA.to_guid_string(),
+            "EBD0A0A2-B9E5-4433-87C0-68B6B72699C7"
+        );
+        assert_eq!(
+            PartitionTypeGuid::LINUX_FILESYSTEM.to_guid_string(),
+            "0FC63DAF-8483-4772-8E79-3D69D8477DE4"
+        );
+        assert_eq!(
+            format_guid(&[
+                0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07,
+                0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f,
+            ]),
+            "03020100-0504-0706-0809-0A0B0C0D0E0F"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_partition_type_from_guid_string() {
+        for (guid, name) in PartitionTypeGuid::WELL_KNOWN {
+            let parsed = PartitionTypeGuid::from_guid_string(&guid.to_guid_string()).unwrap();
+            assert_eq!(parsed, *guid);
+            assert_eq!(parsed.name(), *name);
+        }
+
+        let reserved = PartitionTypeGuid::from_guid_string("e3c9e316-0b5c-4db8-817d-f92df00215ae");
+        assert_eq!(reserved.map(|g| g.name()), Some("Microsoft Reserved"));
+        assert_eq!(
+            PartitionTypeGuid::from_guid_string("0657FD6D-A4AB-43C4-84E5-0933C84B4F4F"),
+            Some(PartitionTypeGuid::LINUX_SWAP)
+        );
+
+        assert!(PartitionTypeGuid::from_guid_string("00112233-4455-6677-8899-AABBCCDDEEFF").is_none());
+        assert!(PartitionTypeGuid::from_guid_string("not a guid").is_none());
+    }
+
     #[test]
     fn test_partition_entry_is_unused() {
         let mut entry_bytes = vec![0u8; GptPartitionEntry::ENTRY_SIZE];
@@ -351,6 +507,36 @@ mod tests {
         assert_eq!(entry.size_lba(), 100);
     }
 
+    /// Build an entry whose name field holds `units`
+    fn entry_named(units: &[u16]) -> GptPartitionEntry {
+        let mut entry_bytes = vec![0u8; GptPartitionEntry::ENTRY_SIZE];
+        for (i, unit) in units.iter().enumerate() {
+            entry_bytes[56 + i * 2..58 + i * 2].copy_from_slice(&unit.to_le_bytes());
+        }
+        GptPartitionEntry::from_bytes(&entry_bytes)
+    }
+
+    #[test]
+    fn test_partition_name_surrogate_pairs() {
+        let units: Vec<u16> = "Data \u{1F600} \u{20000}".encode_utf16().collect();
+        assert_eq!(entry_named(&units).name, "Data \u{1F600} \u{20000}");
+
+        // A name filling all 36 units has no terminator
+        let units: Vec<u16> = "\u{1F4BE}".repeat(18).encode_utf16().collect();
+        assert_eq!(units.len(), 36);
+        assert_eq!(entry_named(&units).name, "\u{1F4BE}".repeat(18));
+
+        // Lone surrogates are replaced rather than rejected
+        assert_eq!(entry_named(&[0x41, 0xD800, 0x42, 0xDC00]).name, "A\u{FFFD}B\u{FFFD}");
+    }
+
+    #[test]
+    fn test_partition_name_interior_nulls() {
+        let units = [0x41, 0x42, 0, 0x43, 0x44];
+        assert_eq!(entry_named(&units).name, "AB\0CD");
+        assert_eq!(entry_named(&[]).name, "");
+    }
+
     #[test]
     fn test_gpt_header_signature_validation() {
         let mut header_bytes = vec![0u8; GptHeader::HEADER_SIZE];
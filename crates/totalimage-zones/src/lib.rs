@@ -5,11 +5,22 @@
 //! This crate provides implementations of various partition table formats:
 //! - **MBR**: Master Boot Record (BIOS/legacy partitioning)
 //! - **GPT**: GUID Partition Table (UEFI/modern partitioning)
+//! - **APM**: Apple Partition Map (classic Macintosh media, hybrid ISOs)
+//! - **BSD**: BSD disklabel (whole disks or inside an MBR BSD slice)
 //! - **Direct**: No partition table (entire disk is one zone)
 //!
+//! [`bpb`] parses the BIOS Parameter Block at the start of a FAT volume.
+//!
+//! [`detect_zone_table`] picks whichever of these a disk uses, and
+//! [`ZoneTableExt`] adds [`open_zone`](ZoneTableExt::open_zone) to every zone
+//! table for reading a zone's contents through a windowed pipeline.
+//! [`parse_partition_scheme`] reads an MBR and a GPT side by side and
+//! reports where they disagree.
+//!
 //! ## Example
 //!
 //! ```rust,no_run
+//! # #[cfg(feature = "std")] {
 //! use totalimage_zones::{mbr::MbrZoneTable, gpt::GptZoneTable};
 //! use totalimage_core::ZoneTable;
 //! use std::fs::File;
@@ -22,10 +33,195 @@
 //! for zone in table.enumerate_zones() {
 //!     println!("  {}", zone);
 //! }
+//! # }
+//! ```
+//!
+//! ## `no_std`
+//!
+//! The `std` feature, on by default, provides everything that reads from a
+//! stream. Without it the crate is `#![no_std]` and needs only `alloc`,
+//! keeping the slice-in, struct-out parsers in [`mbr::types`],
+//! [`gpt::types`] and [`bpb`] for embedded and WASM builds:
+//!
+//! ```rust
+//! use totalimage_zones::mbr::types::{MbrPartitionType, MbrSector};
+//!
+//! let mut sector = [0u8; 512];
+//! sector[0x1BE + 4] = 0x0C;
+//! sector[0x1BE + 8..0x1BE + 16].copy_from_slice(&[0, 8, 0, 0, 0, 0, 1, 0]);
+//! sector[0x1FE..].copy_from_slice(&[0x55, 0xAA]);
+//!
+//! let mbr = MbrSector::from_bytes(&sector).unwrap();
+//! assert!(mbr.has_boot_signature());
+//! assert_eq!(mbr.entries[0].partition_type, MbrPartitionType::Fat32Lba);
+//! assert_eq!(mbr.entries[0].lba_start, 2048);
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub mod mbr;
 pub mod gpt;
+pub mod bpb;
+#[cfg(feature = "std")]
+pub mod apm;
+#[cfg(feature = "std")]
+pub mod bsd;
+#[cfg(feature = "std")]
+pub mod scheme;
+#[cfg(feature = "std")]
+pub mod warnings;
 
+#[cfg(feature = "std")]
 pub use mbr::MbrZoneTable;
+#[cfg(feature = "std")]
 pub use gpt::GptZoneTable;
+#[cfg(feature = "std")]
+pub use apm::ApmZoneTable;
+#[cfg(feature = "std")]
+pub use bsd::BsdDisklabelZoneTable;
+#[cfg(feature = "std")]
+pub use scheme::{parse_partition_scheme, PartitionScheme, SchemeWarning};
+#[cfg(feature = "std")]
+pub use warnings::ZoneWarning;
+
+#[cfg(feature = "std")]
+use totalimage_core::{Error, ReadSeek, Result, ZoneTable};
+#[cfg(feature = "std")]
+use totalimage_pipeline::PartialPipeline;
+
+/// A partition table found by [`detect_zone_table`]
+#[cfg(feature = "std")]
+pub enum DetectedZoneTable {
+    /// Master Boot Record
+    Mbr(MbrZoneTable),
+    /// GUID Partition Table
+    Gpt(GptZoneTable),
+    /// Apple Partition Map
+    Apm(ApmZoneTable),
+}
+
+#[cfg(feature = "std")]
+impl DetectedZoneTable {
+    /// Get the detected table as a [`ZoneTable`]
+    pub fn as_zone_table(&self) -> &dyn ZoneTable {
+        match self {
+            Self::Mbr(mbr) => mbr,
+            Self::Gpt(gpt) => gpt,
+            Self::Apm(apm) => apm,
+        }
+    }
+
+    /// Get the partitions found to overlap or run past the end of the disk
+    ///
+    /// Always empty for APM, whose layout isn't checked.
+    pub fn validation_warnings(&self) -> &[ZoneWarning] {
+        match self {
+            Self::Mbr(mbr) => mbr.validation_warnings(),
+            Self::Gpt(gpt) => gpt.validation_warnings(),
+            Self::Apm(_) => &[],
+        }
+    }
+}
+
+/// Detect the partition table on a disk
+///
+/// Tries MBR, then GPT, then APM. A GPT-protective MBR defers to the GPT
+/// it protects; the protective MBR itself is only returned if that GPT
+/// can't be parsed. Returns `None` for unpartitioned media.
+#[cfg(feature = "std")]
+pub fn detect_zone_table(stream: &mut dyn ReadSeek, sector_size: u32) -> Option<DetectedZoneTable> {
+    if let Ok(mbr) = MbrZoneTable::parse(stream, sector_size) {
+        if !mbr.is_gpt_protective() {
+            return Some(DetectedZoneTable::Mbr(mbr));
+        }
+        return Some(match GptZoneTable::parse(stream, sector_size) {
+            Ok(gpt) => DetectedZoneTable::Gpt(gpt),
+            Err(_) => DetectedZoneTable::Mbr(mbr),
+        });
+    }
+
+    if let Ok(gpt) = GptZoneTable::parse(stream, sector_size) {
+        return Some(DetectedZoneTable::Gpt(gpt));
+    }
+
+    ApmZoneTable::parse_detect(stream).ok().map(DetectedZoneTable::Apm)
+}
+
+/// Convenience methods available on every [`ZoneTable`]
+///
+/// This lives here rather than on `ZoneTable` itself because
+/// `totalimage-core` cannot depend on `totalimage-pipeline`.
+#[cfg(feature = "std")]
+pub trait ZoneTableExt: ZoneTable {
+    /// Open a zone as a pipeline windowed onto its byte range of `content`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is out of range, the zone is empty, or
+    /// the zone starts beyond the end of `content`
+    fn open_zone<'a>(
+        &self,
+        content: &'a mut dyn ReadSeek,
+        index: usize,
+    ) -> Result<PartialPipeline<&'a mut dyn ReadSeek>> {
+        let zone = self.get_zone(index).ok_or_else(|| {
+            Error::not_found(format!(
+                "Zone {} not found ({} zones in {})",
+                index,
+                self.enumerate_zones().len(),
+                self.identify()
+            ))
+        })?;
+
+        if zone.length == 0 {
+            return Err(Error::invalid_zone_table(format!(
+                "Zone {} has zero length",
+                index
+            )));
+        }
+
+        Ok(PartialPipeline::new(content, zone.offset, zone.length)?)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ZoneTable + ?Sized> ZoneTableExt for T {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use totalimage_core::Zone;
+
+    struct FixedZones(Vec<Zone>);
+
+    impl ZoneTable for FixedZones {
+        fn identify(&self) -> &str {
+            "Fixed"
+        }
+
+        fn enumerate_zones(&self) -> &[Zone] {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn test_open_zone_validation() {
+        let table: Box<dyn ZoneTable> = Box::new(FixedZones(vec![
+            Zone::new(0, 512, 0, "Empty".to_string()),
+            Zone::new(1, 8192, 512, "Beyond end".to_string()),
+            Zone::new(2, 512, 4096, "Truncated".to_string()),
+        ]));
+        let mut content = Cursor::new(vec![0u8; 2048]);
+
+        assert!(table.open_zone(&mut content, 0).is_err());
+        assert!(table.open_zone(&mut content, 1).is_err());
+        assert!(table.open_zone(&mut content, 3).is_err());
+
+        // Zones running past the image are clamped to the available data
+        let zone = table.open_zone(&mut content, 2).unwrap();
+        assert_eq!(zone.length(), 1536);
+    }
+}
@@ -2,9 +2,14 @@
 
 pub mod types;
 
+#[cfg(feature = "std")]
 use std::io::SeekFrom;
-use totalimage_core::{Error, ReadSeek, Result, Zone, ZoneTable};
-use types::{CHSAddress, MbrPartitionType};
+#[cfg(feature = "std")]
+use crate::warnings::{check_zones, ZoneWarning};
+#[cfg(feature = "std")]
+use totalimage_core::{Error, ReadSeek, ReadSeekExt, Result, Zone, ZoneTable};
+#[cfg(feature = "std")]
+use types::{CHSAddress, MbrPartitionType, MbrSector};
 
 /// MBR partition table
 ///
@@ -23,13 +28,18 @@ use types::{CHSAddress, MbrPartitionType};
 /// 0x1EE   16    Partition entry 4
 /// 0x1FE   2     Boot signature (0xAA55)
 /// ```
+#[cfg(feature = "std")]
 #[derive(Debug, Clone)]
 pub struct MbrZoneTable {
     zones: Vec<Zone>,
+    entries: Vec<PartitionAddressing>,
+    boot_code: Vec<u8>,
     disk_signature: u32,
     boot_signature: u16,
+    warnings: Vec<ZoneWarning>,
 }
 
+#[cfg(feature = "std")]
 impl MbrZoneTable {
     /// The boot signature that must be present at offset 0x1FE
     pub const BOOT_SIGNATURE: u16 = 0xAA55;
@@ -52,84 +62,141 @@ impl MbrZoneTable {
     /// Number of partition entries in MBR
     pub const NUM_PARTITIONS: usize = 4;
 
+    /// Heads per cylinder assumed when the disk geometry is unknown
+    pub const DEFAULT_HEADS: u16 = 255;
+
+    /// Sectors per track assumed when the disk geometry is unknown
+    pub const DEFAULT_SECTORS_PER_TRACK: u16 = 63;
+
+    /// Number of cylinders addressable through CHS fields
+    const CHS_CYLINDERS: u32 = 1024;
+
     /// Parse an MBR from a readable and seekable stream
     ///
+    /// The MBR is always the first 512 bytes of the disk, even when sectors
+    /// are larger; the rest of a 4096-byte LBA 0 is ignored. Partition LBAs
+    /// count `sector_size`-byte sectors, so zone offsets and lengths scale
+    /// with it.
+    ///
     /// # Arguments
     ///
     /// * `stream` - A stream positioned at the start of the disk
-    /// * `sector_size` - The sector size in bytes (usually 512)
+    /// * `sector_size` - The sector size in bytes (usually 512, or 4096 on
+    ///   4Kn disks)
     ///
     /// # Errors
     ///
     /// Returns an error if:
+    /// - `sector_size` is not a power of two of at least 512
     /// - The boot signature is invalid
     /// - The stream cannot be read
     /// - The partition table is corrupted
     pub fn parse(stream: &mut dyn ReadSeek, sector_size: u32) -> Result<Self> {
+        if sector_size < Self::MBR_SIZE as u32 || !sector_size.is_power_of_two() {
+            return Err(Error::InvalidOperation(format!(
+                "Invalid sector size for MBR: {} (expected a power of two of at least 512)",
+                sector_size
+            )));
+        }
+
+        let disk_size = stream.seek(SeekFrom::End(0))?;
+
         // Read entire MBR sector
-        stream.seek(SeekFrom::Start(0))?;
-        let mut mbr = [0u8; Self::MBR_SIZE];
-        stream.read_exact(&mut mbr)?;
+        let mut bytes = [0u8; Self::MBR_SIZE];
+        stream.read_exact_at(0, &mut bytes)?;
+        let mbr = MbrSector::from_bytes(&bytes)
+            .ok_or_else(|| Error::invalid_zone_table("MBR sector is truncated".to_string()))?;
 
         // Verify boot signature
-        let boot_signature = u16::from_le_bytes([
-            mbr[Self::BOOT_SIGNATURE_OFFSET as usize],
-            mbr[Self::BOOT_SIGNATURE_OFFSET as usize + 1],
-        ]);
-
-        if boot_signature != Self::BOOT_SIGNATURE {
-            return Err(Error::invalid_zone_table(format!(
+        if !mbr.has_boot_signature() {
+            return Err(Error::unrecognized_signature(format!(
                 "Invalid MBR boot signature: expected 0x{:04X}, got 0x{:04X}",
                 Self::BOOT_SIGNATURE,
-                boot_signature
+                mbr.boot_signature
             )));
         }
 
-        // Read disk signature
-        let disk_signature = u32::from_le_bytes([
-            mbr[Self::DISK_SIGNATURE_OFFSET as usize],
-            mbr[Self::DISK_SIGNATURE_OFFSET as usize + 1],
-            mbr[Self::DISK_SIGNATURE_OFFSET as usize + 2],
-            mbr[Self::DISK_SIGNATURE_OFFSET as usize + 3],
-        ]);
-
         // Parse partition entries
         let mut zones = Vec::new();
+        let mut entries = Vec::new();
 
-        for i in 0..Self::NUM_PARTITIONS {
-            let offset = Self::PARTITION_TABLE_OFFSET as usize + (i * Self::PARTITION_ENTRY_SIZE);
-            let entry = &mbr[offset..offset + Self::PARTITION_ENTRY_SIZE];
-
-            // Parse partition entry fields
-            let _status = entry[0];
-            let _chs_start = CHSAddress::from_bytes(&entry[1..4]);
-            let partition_type = MbrPartitionType::from_byte(entry[4]);
-            let _chs_end = CHSAddress::from_bytes(&entry[5..8]);
-            let lba_start = u32::from_le_bytes([entry[8], entry[9], entry[10], entry[11]]);
-            let lba_length = u32::from_le_bytes([entry[12], entry[13], entry[14], entry[15]]);
-
+        for (i, entry) in mbr.entries.iter().enumerate() {
             // Skip empty partitions
-            if partition_type == MbrPartitionType::Empty || lba_length == 0 {
+            if entry.is_empty() {
                 continue;
             }
 
             // Calculate byte offsets
-            let zone_offset = lba_start as u64 * sector_size as u64;
-            let zone_length = lba_length as u64 * sector_size as u64;
+            let zone_offset = entry.lba_start as u64 * sector_size as u64;
+            let zone_length = entry.lba_length as u64 * sector_size as u64;
 
             // Create zone
-            let zone = Zone::new(i, zone_offset, zone_length, partition_type.name().to_string());
+            let zone = Zone::new(i, zone_offset, zone_length, entry.partition_type.name().to_string());
 
             zones.push(zone);
+            entries.push(PartitionAddressing {
+                index: i,
+                chs_start: entry.chs_start,
+                chs_end: entry.chs_end,
+                lba_start: entry.lba_start,
+                lba_length: entry.lba_length,
+            });
         }
 
+        let warnings = check_zones(&zones, disk_size);
         Ok(Self {
             zones,
-            disk_signature,
-            boot_signature,
+            entries,
+            boot_code: mbr.boot_code.to_vec(),
+            disk_signature: mbr.disk_signature,
+            boot_signature: mbr.boot_signature,
+            warnings,
         })
     }
 
+    /// Build a protective MBR for a GPT disk of `total_sectors` sectors
+    ///
+    /// The single partition has type 0xEE and covers the disk from LBA 1,
+    /// with its size clamped to 0xFFFFFFFF sectors as the UEFI specification
+    /// requires for larger disks. The ending CHS address is that of the last
+    /// covered sector, or 0xFF/0xFF/0xFF if it lies beyond the CHS range.
+    pub fn protective_mbr(total_sectors: u64) -> [u8; Self::MBR_SIZE] {
+        let mut mbr = [0u8; Self::MBR_SIZE];
+        let size = total_sectors.saturating_sub(1).min(u32::MAX as u64) as u32;
+
+        let start_chs = CHSAddress { cylinder: 0, head: 0, sector: 2 };
+        let end_chs = CHSAddress::from_lba(
+            size as u64,
+            Self::DEFAULT_HEADS,
+            Self::DEFAULT_SECTORS_PER_TRACK,
+        )
+        .map_or([0xFF; 3], |chs| chs.to_bytes());
+
+        let entry = &mut mbr[Self::PARTITION_TABLE_OFFSET as usize..][..Self::PARTITION_ENTRY_SIZE];
+        entry[1..4].copy_from_slice(&start_chs.to_bytes());
+        entry[4] = MbrPartitionType::GptProtective.to_byte();
+        entry[5..8].copy_from_slice(&end_chs);
+        entry[8..12].copy_from_slice(&1u32.to_le_bytes());
+        entry[12..16].copy_from_slice(&size.to_le_bytes());
+
+        mbr[Self::BOOT_SIGNATURE_OFFSET as usize..].copy_from_slice(&Self::BOOT_SIGNATURE.to_le_bytes());
+        mbr
+    }
+
+    /// Get the 446 bytes of bootstrap code at the start of the MBR
+    pub fn boot_code(&self) -> &[u8] {
+        &self.boot_code
+    }
+
+    /// Name the bootloader that installed the bootstrap code
+    ///
+    /// Recognizes GRUB, the Windows and MS-DOS boot code, Syslinux and
+    /// LILO by their leading bytes. Returns None for unrecognized or blank
+    /// boot code.
+    pub fn boot_code_signature(&self) -> Option<&'static str> {
+        types::identify_boot_code(&self.boot_code)
+    }
+
     /// Get the disk signature
     pub fn disk_signature(&self) -> u32 {
         self.disk_signature
@@ -140,6 +207,11 @@ impl MbrZoneTable {
         self.boot_signature
     }
 
+    /// Get the partitions found to overlap or run past the end of the disk
+    pub fn validation_warnings(&self) -> &[ZoneWarning] {
+        &self.warnings
+    }
+
     /// Check if this MBR contains a GPT protective partition
     ///
     /// A GPT protective partition indicates that this is actually a GPT disk
@@ -147,8 +219,110 @@ impl MbrZoneTable {
     pub fn is_gpt_protective(&self) -> bool {
         self.zones.iter().any(|z| z.zone_type == "GPT Protective")
     }
+
+    /// Get the zones that are BSD slices
+    ///
+    /// Each of these holds a BSD disklabel that subdivides the slice; see
+    /// [`BsdDisklabelZoneTable::parse_slice`](crate::bsd::BsdDisklabelZoneTable::parse_slice).
+    pub fn bsd_slices(&self) -> Vec<&Zone> {
+        let bsd_types = [MbrPartitionType::FreeBsd, MbrPartitionType::OpenBsd, MbrPartitionType::NetBsd];
+        self.zones
+            .iter()
+            .filter(|z| bsd_types.iter().any(|t| z.zone_type == t.name()))
+            .collect()
+    }
+
+    /// Check that each partition's CHS addresses agree with its LBA fields
+    ///
+    /// Uses the common 255 heads / 63 sectors per track translation. See
+    /// [`validate_chs_consistency_with_geometry`](Self::validate_chs_consistency_with_geometry).
+    pub fn validate_chs_consistency(&self) -> Vec<String> {
+        self.validate_chs_consistency_with_geometry(
+            Self::DEFAULT_HEADS,
+            Self::DEFAULT_SECTORS_PER_TRACK,
+        )
+    }
+
+    /// Check that each partition's CHS addresses agree with its LBA fields
+    ///
+    /// Disagreement usually means the table was corrupted or edited by hand.
+    /// Returns one human-readable warning per problem found:
+    /// - CHS start/end addresses that translate to a different LBA
+    /// - CHS addresses with an invalid sector number of 0
+    /// - the 0xFE/0xFF/0xFF marker used for addresses beyond the CHS
+    ///   addressable range
+    ///
+    /// All-zero CHS fields, as written by some LBA-only tools, are ignored.
+    pub fn validate_chs_consistency_with_geometry(
+        &self,
+        heads_per_cylinder: u16,
+        sectors_per_track: u16,
+    ) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let chs_limit =
+            Self::CHS_CYLINDERS as u64 * heads_per_cylinder as u64 * sectors_per_track as u64;
+
+        for entry in &self.entries {
+            let lba_end = entry.lba_start as u64 + entry.lba_length as u64 - 1;
+            let addresses = [
+                ("start", entry.chs_start, entry.lba_start as u64),
+                ("end", entry.chs_end, lba_end),
+            ];
+
+            for (which, chs, lba) in addresses {
+                if chs.is_unset() {
+                    continue;
+                }
+
+                if chs.is_overflow_marker() {
+                    if lba < chs_limit {
+                        warnings.push(format!(
+                            "Partition {}: CHS {} uses the beyond-CHS-range marker ({}) but LBA {} is addressable",
+                            entry.index, which, chs, lba
+                        ));
+                    } else {
+                        warnings.push(format!(
+                            "Partition {}: CHS {} exceeds the CHS addressable range (LBA {})",
+                            entry.index, which, lba
+                        ));
+                    }
+                    continue;
+                }
+
+                if chs.sector == 0 {
+                    warnings.push(format!(
+                        "Partition {}: CHS {} ({}) has invalid sector 0",
+                        entry.index, which, chs
+                    ));
+                    continue;
+                }
+
+                let expected = chs.to_lba(heads_per_cylinder, sectors_per_track) as u64;
+                if expected != lba {
+                    warnings.push(format!(
+                        "Partition {}: CHS {} ({}) translates to LBA {} but LBA {} is {}",
+                        entry.index, which, chs, expected, which, lba
+                    ));
+                }
+            }
+        }
+
+        warnings
+    }
+}
+
+/// CHS and LBA fields of a non-empty partition entry
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+struct PartitionAddressing {
+    index: usize,
+    chs_start: CHSAddress,
+    chs_end: CHSAddress,
+    lba_start: u32,
+    lba_length: u32,
 }
 
+#[cfg(feature = "std")]
 impl ZoneTable for MbrZoneTable {
     fn identify(&self) -> &str {
         "Master Boot Record"
@@ -159,7 +333,7 @@ impl ZoneTable for MbrZoneTable {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use std::io::Cursor;
@@ -204,6 +378,90 @@ mod tests {
         mbr
     }
 
+    #[test]
+    fn test_validation_warnings() {
+        // The test partition covers LBA 2048-4095
+        let mut disk = create_test_mbr();
+        disk.resize(4096 * 512, 0);
+        let table = MbrZoneTable::parse(&mut Cursor::new(disk.clone()), 512).unwrap();
+        assert!(table.validation_warnings().is_empty());
+
+        // Partition 2 at LBA 3072-5119 overlaps partition 0 and runs past the end
+        let entry = 0x1BE + 2 * 16;
+        disk[entry + 4] = 0x83;
+        disk[entry + 8..entry + 12].copy_from_slice(&3072u32.to_le_bytes());
+        disk[entry + 12..entry + 16].copy_from_slice(&2048u32.to_le_bytes());
+
+        let table = MbrZoneTable::parse(&mut Cursor::new(disk), 512).unwrap();
+        assert_eq!(table.enumerate_zones().len(), 2);
+        assert_eq!(
+            table.validation_warnings(),
+            [
+                ZoneWarning::OutOfBounds { index: 2, end: 5120 * 512, disk_size: 4096 * 512 },
+                ZoneWarning::Overlap { first: 0, second: 2, shared: 1024 * 512 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_space_accounting_gap() {
+        // Partition 1 at LBA 6144-8191 leaves LBA 4096-6143 unpartitioned
+        let mut disk = create_test_mbr();
+        disk.resize(10240 * 512, 0);
+        let entry = 0x1BE + 16;
+        disk[entry + 4] = 0x83;
+        disk[entry + 8..entry + 12].copy_from_slice(&6144u32.to_le_bytes());
+        disk[entry + 12..entry + 16].copy_from_slice(&2048u32.to_le_bytes());
+
+        let table = MbrZoneTable::parse(&mut Cursor::new(disk), 512).unwrap();
+        let accounting = table.space_accounting(10240 * 512);
+        assert_eq!(
+            accounting.gaps,
+            [(0, 2048 * 512), (4096 * 512, 2048 * 512), (8192 * 512, 2048 * 512)]
+        );
+        assert_eq!(accounting.accounted, 4096 * 512);
+        assert_eq!(accounting.unaccounted, 6144 * 512);
+    }
+
+    #[test]
+    fn test_parse_4kn_mbr() {
+        // The same table on 4096-byte sectors: LBA 2048 is 8 MiB in
+        let mut disk = create_test_mbr();
+        disk.resize(4096 * 4096, 0);
+
+        let table = MbrZoneTable::parse(&mut Cursor::new(disk.clone()), 4096).unwrap();
+        let zone = &table.enumerate_zones()[0];
+        assert_eq!(zone.offset, 2048 * 4096);
+        assert_eq!(zone.length, 2048 * 4096);
+        assert!(table.validation_warnings().is_empty());
+
+        let mut cursor = Cursor::new(disk);
+        for sector_size in [0, 256, 520] {
+            assert!(MbrZoneTable::parse(&mut cursor, sector_size).is_err(), "{}", sector_size);
+        }
+    }
+
+    #[test]
+    fn test_boot_code_signature() {
+        let mut mbr_data = create_test_mbr();
+        let table = MbrZoneTable::parse(&mut Cursor::new(mbr_data.clone()), 512).unwrap();
+        assert_eq!(table.boot_code().len(), 446);
+        assert_eq!(table.boot_code_signature(), None);
+
+        // Windows 7 MBR code
+        let windows = [0x33, 0xC0, 0x8E, 0xD0, 0xBC, 0x00, 0x7C, 0x8E, 0xC0, 0x8E, 0xD8, 0xBE, 0x00, 0x7C, 0xBF, 0x00];
+        mbr_data[..windows.len()].copy_from_slice(&windows);
+        let table = MbrZoneTable::parse(&mut Cursor::new(mbr_data.clone()), 512).unwrap();
+        assert_eq!(table.boot_code_signature(), Some("Windows Vista and later"));
+        assert_eq!(&table.boot_code()[..windows.len()], &windows);
+
+        // GRUB legacy stage1
+        mbr_data[..3].copy_from_slice(&[0xEB, 0x48, 0x90]);
+        mbr_data[0x17E..0x183].copy_from_slice(b"GRUB ");
+        let table = MbrZoneTable::parse(&mut Cursor::new(mbr_data), 512).unwrap();
+        assert_eq!(table.boot_code_signature(), Some("GRUB Legacy"));
+    }
+
     #[test]
     fn test_parse_valid_mbr() {
         let mbr_data = create_test_mbr();
@@ -232,6 +490,81 @@ mod tests {
         assert_eq!(zones[0].zone_type, "FAT32 (LBA)");
     }
 
+    #[test]
+    fn test_open_zone() {
+        use crate::ZoneTableExt;
+        use std::io::Read;
+        use totalimage_core::ReadSeek;
+
+        let mut disk = create_test_mbr();
+        disk.resize(4096 * 512, 0);
+        disk[2048 * 512..2048 * 512 + 4].copy_from_slice(b"ZONE");
+
+        let mut cursor = Cursor::new(disk);
+        let table = MbrZoneTable::parse(&mut cursor, 512).unwrap();
+        let content: &mut dyn ReadSeek = &mut cursor;
+
+        let mut zone = table.open_zone(content, 0).unwrap();
+        assert_eq!(zone.length(), 2048 * 512);
+        let mut magic = [0u8; 4];
+        zone.read_exact(&mut magic).unwrap();
+        assert_eq!(&magic, b"ZONE");
+
+        assert!(table.open_zone(&mut cursor, 1).is_err());
+    }
+
+    /// Write CHS start/end bytes for the first partition entry
+    fn set_chs(mbr: &mut [u8], start: [u8; 3], end: [u8; 3]) {
+        mbr[0x1BF..0x1C2].copy_from_slice(&start);
+        mbr[0x1C3..0x1C6].copy_from_slice(&end);
+    }
+
+    #[test]
+    fn test_chs_consistent() {
+        let mut mbr_data = create_test_mbr();
+        // LBA 2048 = C:0/H:32/S:33, LBA 4095 = C:0/H:65/S:1 at 255/63
+        let start = CHSAddress { cylinder: 0, head: 32, sector: 33 };
+        let end = CHSAddress { cylinder: 0, head: 65, sector: 1 };
+        set_chs(&mut mbr_data, start.to_bytes(), end.to_bytes());
+
+        let table = MbrZoneTable::parse(&mut Cursor::new(mbr_data), 512).unwrap();
+        assert!(table.validate_chs_consistency().is_empty());
+
+        // Same entry against a different geometry disagrees
+        assert_eq!(table.validate_chs_consistency_with_geometry(255, 32).len(), 2);
+    }
+
+    #[test]
+    fn test_chs_lba_mismatch() {
+        // Test MBR has CHS start C:0/H:0/S:2 (LBA 1) but LBA start 2048,
+        // and an all-zero CHS end which is not checked
+        let table = MbrZoneTable::parse(&mut Cursor::new(create_test_mbr()), 512).unwrap();
+        let warnings = table.validate_chs_consistency();
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Partition 0"));
+        assert!(warnings[0].contains("translates to LBA 1 but LBA start is 2048"));
+    }
+
+    #[test]
+    fn test_chs_overflow_marker() {
+        let mut mbr_data = create_test_mbr();
+        set_chs(&mut mbr_data, [0xFE, 0xFF, 0xFF], [0xFE, 0xFF, 0xFF]);
+
+        // Partition within the CHS range using the marker is inconsistent
+        let table = MbrZoneTable::parse(&mut Cursor::new(mbr_data.clone()), 512).unwrap();
+        let warnings = table.validate_chs_consistency();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|w| w.contains("but LBA") && w.contains("is addressable")));
+
+        // Partition beyond 1024 * 255 * 63 sectors is flagged as out of range
+        mbr_data[0x1C6..0x1CA].copy_from_slice(&20_000_000u32.to_le_bytes());
+        let table = MbrZoneTable::parse(&mut Cursor::new(mbr_data), 512).unwrap();
+        let warnings = table.validate_chs_consistency();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("exceeds the CHS addressable range (LBA 20000000)"));
+    }
+
     #[test]
     fn test_parse_invalid_boot_signature() {
         let mut mbr_data = create_test_mbr();
@@ -240,11 +573,9 @@ mod tests {
         let mut cursor = Cursor::new(mbr_data);
         let result = MbrZoneTable::parse(&mut cursor, 512);
 
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("Invalid MBR boot signature"));
+        let err = result.unwrap_err();
+        assert!(matches!(err, Error::UnrecognizedSignature(_)));
+        assert!(err.to_string().contains("Invalid MBR boot signature"));
     }
 
     #[test]
@@ -282,4 +613,26 @@ mod tests {
 
         assert!(table.is_gpt_protective());
     }
+
+    #[test]
+    fn test_protective_mbr() {
+        // 1 GiB disk, within the CHS range
+        let mbr = MbrZoneTable::protective_mbr(2_097_152);
+        let table = MbrZoneTable::parse(&mut Cursor::new(mbr.to_vec()), 512).unwrap();
+        assert!(table.is_gpt_protective());
+        assert_eq!(table.boot_signature(), MbrZoneTable::BOOT_SIGNATURE);
+        let zones = table.enumerate_zones();
+        assert_eq!(zones.len(), 1);
+        assert_eq!(zones[0].offset, 512);
+        assert_eq!(zones[0].length, 2_097_151 * 512);
+        assert!(table.validate_chs_consistency().is_empty());
+        assert_eq!(&mbr[0x1BF..0x1C2], &[0x00, 0x02, 0x00]);
+
+        // 4 TiB disk: size clamped, end CHS past the addressable range
+        let mbr = MbrZoneTable::protective_mbr(1 << 33);
+        let table = MbrZoneTable::parse(&mut Cursor::new(mbr.to_vec()), 512).unwrap();
+        assert!(table.is_gpt_protective());
+        assert_eq!(table.enumerate_zones()[0].length, u32::MAX as u64 * 512);
+        assert_eq!(&mbr[0x1C3..0x1C6], &[0xFF, 0xFF, 0xFF]);
+    }
 }
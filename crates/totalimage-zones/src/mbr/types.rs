@@ -1,6 +1,9 @@
 //! MBR partition types and CHS addressing
+//!
+//! Everything here parses from byte slices and is available without the
+//! `std` feature.
 
-use std::fmt;
+use core::fmt;
 
 /// MBR partition type codes
 ///
@@ -32,6 +35,12 @@ pub enum MbrPartitionType {
     LinuxSwap = 0x82,
     /// Linux native (ext2/ext3/ext4)
     LinuxNative = 0x83,
+    /// FreeBSD slice (holds a BSD disklabel)
+    FreeBsd = 0xA5,
+    /// OpenBSD slice (holds a BSD disklabel)
+    OpenBsd = 0xA6,
+    /// NetBSD slice (holds a BSD disklabel)
+    NetBsd = 0xA9,
     /// GPT protective MBR
     GptProtective = 0xEE,
     /// EFI system partition
@@ -56,6 +65,9 @@ impl MbrPartitionType {
             0x0F => Self::ExtendedLba,
             0x82 => Self::LinuxSwap,
             0x83 => Self::LinuxNative,
+            0xA5 => Self::FreeBsd,
+            0xA6 => Self::OpenBsd,
+            0xA9 => Self::NetBsd,
             0xEE => Self::GptProtective,
             0xEF => Self::EfiSystem,
             _ => Self::Unknown(b),
@@ -77,6 +89,9 @@ impl MbrPartitionType {
             Self::ExtendedLba => 0x0F,
             Self::LinuxSwap => 0x82,
             Self::LinuxNative => 0x83,
+            Self::FreeBsd => 0xA5,
+            Self::OpenBsd => 0xA6,
+            Self::NetBsd => 0xA9,
             Self::GptProtective => 0xEE,
             Self::EfiSystem => 0xEF,
             Self::Unknown(b) => b,
@@ -98,6 +113,9 @@ impl MbrPartitionType {
             Self::ExtendedLba => "Extended (LBA)",
             Self::LinuxSwap => "Linux swap",
             Self::LinuxNative => "Linux",
+            Self::FreeBsd => "FreeBSD",
+            Self::OpenBsd => "OpenBSD",
+            Self::NetBsd => "NetBSD",
             Self::GptProtective => "GPT Protective",
             Self::EfiSystem => "EFI System",
             Self::Unknown(_b) => return "Unknown",
@@ -155,6 +173,38 @@ impl CHSAddress {
         ]
     }
 
+    /// Convert an LBA to CHS under the given geometry
+    ///
+    /// Returns `None` if the address lies beyond the 1024 cylinders CHS can
+    /// express.
+    pub fn from_lba(lba: u64, heads_per_cylinder: u16, sectors_per_track: u16) -> Option<Self> {
+        let sectors_per_cylinder = heads_per_cylinder as u64 * sectors_per_track as u64;
+        let cylinder = lba / sectors_per_cylinder;
+        if cylinder >= 1024 {
+            return None;
+        }
+        let remainder = lba % sectors_per_cylinder;
+
+        Some(Self {
+            cylinder: cylinder as u16,
+            head: (remainder / sectors_per_track as u64) as u8,
+            sector: (remainder % sectors_per_track as u64) as u8 + 1,
+        })
+    }
+
+    /// Is this address all zeroes (left unset by LBA-only partitioning tools)?
+    pub fn is_unset(&self) -> bool {
+        self.cylinder == 0 && self.head == 0 && self.sector == 0
+    }
+
+    /// Is this the 1023/254/63 (or 1023/255/63) marker written for
+    /// addresses beyond the CHS addressable range?
+    ///
+    /// On disk this is the classic 0xFE/0xFF/0xFF byte sequence.
+    pub fn is_overflow_marker(&self) -> bool {
+        self.cylinder == 1023 && self.head >= 254 && self.sector == 63
+    }
+
     /// Convert CHS to LBA (approximate, requires disk geometry)
     pub fn to_lba(&self, heads_per_cylinder: u16, sectors_per_track: u16) -> u32 {
         let c = self.cylinder as u32;
@@ -171,6 +221,180 @@ impl fmt::Display for CHSAddress {
     }
 }
 
+/// One 16-byte partition table entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MbrPartitionEntry {
+    /// Boot indicator (0x80 for the active partition)
+    pub status: u8,
+    /// CHS address of the first sector
+    pub chs_start: CHSAddress,
+    /// Partition type code
+    pub partition_type: MbrPartitionType,
+    /// CHS address of the last sector
+    pub chs_end: CHSAddress,
+    /// First sector
+    pub lba_start: u32,
+    /// Number of sectors
+    pub lba_length: u32,
+}
+
+impl MbrPartitionEntry {
+    /// Size of a partition entry in bytes
+    pub const SIZE: usize = 16;
+
+    /// Parse from 16 bytes
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Self::SIZE {
+            return None;
+        }
+        Some(Self {
+            status: bytes[0],
+            chs_start: CHSAddress::from_bytes(&bytes[1..4]),
+            partition_type: MbrPartitionType::from_byte(bytes[4]),
+            chs_end: CHSAddress::from_bytes(&bytes[5..8]),
+            lba_start: u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+            lba_length: u32::from_le_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]),
+        })
+    }
+
+    /// Is this entry unused (empty type or no sectors)?
+    pub fn is_empty(&self) -> bool {
+        self.partition_type == MbrPartitionType::Empty || self.lba_length == 0
+    }
+}
+
+/// A bootloader recognized by the start of its MBR bootstrap code
+struct BootCodeSignature {
+    name: &'static str,
+    /// Bytes the bootstrap code starts with
+    prefix: &'static [u8],
+    /// Bytes that must also appear somewhere in the bootstrap code
+    marker: Option<&'static [u8]>,
+}
+
+impl BootCodeSignature {
+    fn matches(&self, code: &[u8]) -> bool {
+        if !code.starts_with(self.prefix) {
+            return false;
+        }
+        match self.marker {
+            Some(marker) => code.windows(marker.len()).any(|window| window == marker),
+            None => true,
+        }
+    }
+}
+
+/// Known MBR bootstrap code, most specific first
+const BOOT_CODE_SIGNATURES: &[BootCodeSignature] = &[
+    BootCodeSignature {
+        name: "GRUB 2",
+        prefix: &[0xEB, 0x63, 0x90],
+        marker: Some(b"GRUB "),
+    },
+    BootCodeSignature {
+        name: "GRUB Legacy",
+        prefix: &[0xEB, 0x48, 0x90],
+        marker: Some(b"GRUB "),
+    },
+    BootCodeSignature {
+        name: "Windows Vista and later",
+        prefix: &[0x33, 0xC0, 0x8E, 0xD0, 0xBC, 0x00, 0x7C, 0x8E, 0xC0, 0x8E, 0xD8, 0xBE, 0x00, 0x7C],
+        marker: None,
+    },
+    BootCodeSignature {
+        name: "Windows NT/2000/XP",
+        prefix: &[0x33, 0xC0, 0x8E, 0xD0, 0xBC, 0x00, 0x7C, 0xFB, 0x50, 0x07, 0x50, 0x1F, 0xFC, 0xBE, 0x1B, 0x7C],
+        marker: None,
+    },
+    BootCodeSignature {
+        name: "MS-DOS / Windows 9x",
+        prefix: &[0xFA, 0x33, 0xC0, 0x8E, 0xD0, 0xBC, 0x00, 0x7C, 0x8B, 0xF4, 0x50, 0x07, 0x50, 0x1F],
+        marker: None,
+    },
+    BootCodeSignature {
+        name: "Syslinux",
+        prefix: &[0x33, 0xC0, 0xFA, 0x8E, 0xD8, 0x8E, 0xD0, 0xBC, 0x00, 0x7C, 0x89, 0xE6],
+        marker: None,
+    },
+    BootCodeSignature {
+        name: "LILO",
+        prefix: &[0xFA, 0xEB],
+        marker: Some(b"LILO"),
+    },
+];
+
+/// Name the bootloader whose MBR bootstrap code `code` is
+///
+/// Returns None for unrecognized code, including an all-zero bootstrap
+/// area (a disk that was never made bootable).
+pub fn identify_boot_code(code: &[u8]) -> Option<&'static str> {
+    BOOT_CODE_SIGNATURES
+        .iter()
+        .find(|signature| signature.matches(code))
+        .map(|signature| signature.name)
+}
+
+/// The fields of a 512-byte MBR sector
+///
+/// Parsing never fails on content, so a sector without the 0xAA55 boot
+/// signature still parses; check [`has_boot_signature`](Self::has_boot_signature)
+/// before trusting the entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MbrSector {
+    /// Bootstrap code at 0x000
+    pub boot_code: [u8; MbrSector::BOOT_CODE_SIZE],
+    /// Windows disk signature at 0x1B8
+    pub disk_signature: u32,
+    /// Boot signature at 0x1FE (0xAA55 when valid)
+    pub boot_signature: u16,
+    /// The four primary partition entries, empty ones included
+    pub entries: [MbrPartitionEntry; 4],
+}
+
+impl MbrSector {
+    /// Size of an MBR in bytes
+    pub const SIZE: usize = 512;
+
+    /// The boot signature that must be present at offset 0x1FE
+    pub const BOOT_SIGNATURE: u16 = 0xAA55;
+
+    /// Offset of the first partition entry
+    pub const PARTITION_TABLE_OFFSET: usize = 0x1BE;
+
+    /// Size of the bootstrap code area at the start of the sector
+    pub const BOOT_CODE_SIZE: usize = 446;
+
+    /// Parse from the first 512 bytes of a disk
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < Self::SIZE {
+            return None;
+        }
+
+        let entry = |i: usize| {
+            let offset = Self::PARTITION_TABLE_OFFSET + i * MbrPartitionEntry::SIZE;
+            MbrPartitionEntry::from_bytes(&bytes[offset..offset + MbrPartitionEntry::SIZE])
+        };
+        let mut boot_code = [0u8; Self::BOOT_CODE_SIZE];
+        boot_code.copy_from_slice(&bytes[..Self::BOOT_CODE_SIZE]);
+        Some(Self {
+            boot_code,
+            disk_signature: u32::from_le_bytes([bytes[0x1B8], bytes[0x1B9], bytes[0x1BA], bytes[0x1BB]]),
+            boot_signature: u16::from_le_bytes([bytes[0x1FE], bytes[0x1FF]]),
+            entries: [entry(0)?, entry(1)?, entry(2)?, entry(3)?],
+        })
+    }
+
+    /// Does the sector end with the 0xAA55 boot signature?
+    pub fn has_boot_signature(&self) -> bool {
+        self.boot_signature == Self::BOOT_SIGNATURE
+    }
+
+    /// Name the bootloader the bootstrap code belongs to, if it's a known one
+    pub fn boot_code_signature(&self) -> Option<&'static str> {
+        identify_boot_code(&self.boot_code)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,4 +452,67 @@ mod tests {
         let lba = chs.to_lba(16, 63);
         assert_eq!(lba, 63); // (0 * 16 + 1) * 63 + 0 = 63
     }
+
+    #[test]
+    fn test_chs_overflow_marker() {
+        let chs = CHSAddress::from_bytes(&[0xFE, 0xFF, 0xFF]);
+        assert!(chs.is_overflow_marker());
+        assert!(CHSAddress::from_bytes(&[0xFF, 0xFF, 0xFF]).is_overflow_marker());
+        assert!(!CHSAddress::from_bytes(&[0x01, 0x01, 0x00]).is_overflow_marker());
+
+        assert!(CHSAddress::from_bytes(&[0, 0, 0]).is_unset());
+        assert!(!chs.is_unset());
+    }
+
+    #[test]
+    fn test_mbr_sector_from_slice() {
+        let mut sector = [0u8; 512];
+        sector[0x1B8..0x1BC].copy_from_slice(&0xDEADBEEFu32.to_le_bytes());
+        let entry = [0x80, 0x20, 0x21, 0x00, 0x07, 0xFE, 0xFF, 0xFF, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00];
+        sector[0x1CE..0x1DE].copy_from_slice(&entry);
+        sector[0x1FE..].copy_from_slice(&[0x55, 0xAA]);
+
+        let mbr = MbrSector::from_bytes(&sector).unwrap();
+        assert!(mbr.has_boot_signature());
+        assert_eq!(mbr.disk_signature, 0xDEADBEEF);
+        assert!(mbr.entries[0].is_empty());
+
+        let ntfs = mbr.entries[1];
+        assert_eq!(ntfs.status, 0x80);
+        assert_eq!(ntfs.partition_type, MbrPartitionType::Ntfs);
+        assert_eq!(ntfs.chs_start, CHSAddress { cylinder: 0, head: 32, sector: 33 });
+        assert!(ntfs.chs_end.is_overflow_marker());
+        assert_eq!((ntfs.lba_start, ntfs.lba_length), (2048, 0x100000));
+
+        sector[0x1FF] = 0;
+        assert!(!MbrSector::from_bytes(&sector).unwrap().has_boot_signature());
+        assert!(MbrSector::from_bytes(&sector[..511]).is_none());
+    }
+
+    #[test]
+    fn test_boot_code_signature() {
+        // GRUB 2 boot.img jumps over its BPB area and names itself near the end
+        let mut sector = [0u8; 512];
+        sector[..3].copy_from_slice(&[0xEB, 0x63, 0x90]);
+        sector[0x180..0x185].copy_from_slice(b"GRUB ");
+        let mbr = MbrSector::from_bytes(&sector).unwrap();
+        assert_eq!(mbr.boot_code_signature(), Some("GRUB 2"));
+        assert_eq!(&mbr.boot_code[..3], &[0xEB, 0x63, 0x90]);
+
+        // The jump alone isn't enough
+        sector[0x180..0x185].fill(0);
+        assert_eq!(MbrSector::from_bytes(&sector).unwrap().boot_code_signature(), None);
+
+        let windows_7 = [0x33, 0xC0, 0x8E, 0xD0, 0xBC, 0x00, 0x7C, 0x8E, 0xC0, 0x8E, 0xD8, 0xBE, 0x00, 0x7C, 0xBF, 0x00];
+        assert_eq!(identify_boot_code(&windows_7), Some("Windows Vista and later"));
+
+        let windows_xp = [0x33, 0xC0, 0x8E, 0xD0, 0xBC, 0x00, 0x7C, 0xFB, 0x50, 0x07, 0x50, 0x1F, 0xFC, 0xBE, 0x1B, 0x7C];
+        assert_eq!(identify_boot_code(&windows_xp), Some("Windows NT/2000/XP"));
+
+        let syslinux = [0x33, 0xC0, 0xFA, 0x8E, 0xD8, 0x8E, 0xD0, 0xBC, 0x00, 0x7C, 0x89, 0xE6, 0x06, 0x57];
+        assert_eq!(identify_boot_code(&syslinux), Some("Syslinux"));
+
+        assert_eq!(identify_boot_code(&[0u8; MbrSector::BOOT_CODE_SIZE]), None);
+        assert_eq!(identify_boot_code(&[]), None);
+    }
 }
@@ -0,0 +1,133 @@
+//! Combined view of the MBR and GPT on a disk
+//!
+//! [`detect_zone_table`](crate::detect_zone_table) picks one table to read
+//! partitions from. Some boot setups keep a real MBR alongside a GPT, and
+//! BIOS and UEFI then see different partitions. [`parse_partition_scheme`]
+//! reads both tables and reports where they disagree, so neither is
+//! silently preferred.
+
+use std::fmt;
+use totalimage_core::{ReadSeek, Zone, ZoneTable};
+
+use crate::{GptZoneTable, MbrZoneTable};
+
+/// Zone type the MBR parser gives a GPT protective partition
+const PROTECTIVE_ZONE_TYPE: &str = "GPT Protective";
+
+/// A disagreement between a disk's MBR and GPT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemeWarning {
+    /// The MBR holds partitions alongside its GPT protective partition
+    HybridMbr {
+        /// Number of partitions besides the protective one
+        partitions: usize,
+    },
+    /// A GPT is present but the MBR has no GPT protective partition
+    UnprotectedGpt,
+    /// The MBR has a GPT protective partition but no GPT could be read
+    MissingGpt,
+    /// An MBR partition doesn't lie within any GPT partition
+    UncoveredMbrPartition {
+        /// Index of the MBR partition
+        index: usize,
+        /// Byte offset of the partition
+        offset: u64,
+        /// Length of the partition in bytes
+        length: u64,
+    },
+}
+
+impl fmt::Display for SchemeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HybridMbr { partitions } => write!(
+                f,
+                "Hybrid MBR: {} partition(s) besides the GPT protective partition",
+                partitions
+            ),
+            Self::UnprotectedGpt => write!(f, "GPT present, but the MBR is not protective"),
+            Self::MissingGpt => write!(f, "MBR is GPT protective, but no GPT could be read"),
+            Self::UncoveredMbrPartition { index, offset, length } => write!(
+                f,
+                "MBR partition {} ({} bytes at byte {}) is not covered by any GPT partition",
+                index, length, offset
+            ),
+        }
+    }
+}
+
+/// The partition tables found on a disk and how they relate
+#[derive(Debug, Clone)]
+pub struct PartitionScheme {
+    /// The MBR in sector 0, protective or not
+    pub mbr: Option<MbrZoneTable>,
+    /// The GPT at LBA 1
+    pub gpt: Option<GptZoneTable>,
+    /// Disagreements between the two tables
+    pub warnings: Vec<SchemeWarning>,
+}
+
+impl PartitionScheme {
+    /// Is the MBR purely protective, holding nothing but GPT protective
+    /// partitions?
+    pub fn is_protective(&self) -> bool {
+        self.mbr.as_ref().is_some_and(|mbr| {
+            mbr.is_gpt_protective() && mbr.enumerate_zones().iter().all(is_protective_zone)
+        })
+    }
+
+    /// Does the MBR hold real partitions alongside a GPT?
+    pub fn is_hybrid(&self) -> bool {
+        self.gpt.is_some() && self.mbr.as_ref().is_some_and(|mbr| mbr_partitions(mbr).next().is_some())
+    }
+}
+
+/// Read both the MBR and the GPT of a disk and compare them
+///
+/// Either table may be missing or unreadable. An MBR partition is covered
+/// when it lies within a GPT partition, as a hybrid MBR's mirrored entries
+/// do; GPT partitions missing from the MBR are normal and not reported.
+pub fn parse_partition_scheme(stream: &mut dyn ReadSeek, sector_size: u32) -> PartitionScheme {
+    let mbr = MbrZoneTable::parse(stream, sector_size).ok();
+    let gpt = GptZoneTable::parse(stream, sector_size).ok();
+
+    let mut warnings = Vec::new();
+    match (&mbr, &gpt) {
+        (Some(mbr), Some(gpt)) => {
+            let partitions: Vec<&Zone> = mbr_partitions(mbr).collect();
+            if !mbr.is_gpt_protective() {
+                warnings.push(SchemeWarning::UnprotectedGpt);
+            } else if !partitions.is_empty() {
+                warnings.push(SchemeWarning::HybridMbr { partitions: partitions.len() });
+            }
+
+            for zone in partitions {
+                let end = zone.offset.saturating_add(zone.length);
+                let covered = gpt
+                    .enumerate_zones()
+                    .iter()
+                    .any(|gpt_zone| gpt_zone.offset <= zone.offset && end <= gpt_zone.offset + gpt_zone.length);
+                if !covered {
+                    warnings.push(SchemeWarning::UncoveredMbrPartition {
+                        index: zone.index,
+                        offset: zone.offset,
+                        length: zone.length,
+                    });
+                }
+            }
+        }
+        (Some(mbr), None) if mbr.is_gpt_protective() => warnings.push(SchemeWarning::MissingGpt),
+        _ => {}
+    }
+
+    PartitionScheme { mbr, gpt, warnings }
+}
+
+fn is_protective_zone(zone: &Zone) -> bool {
+    zone.zone_type == PROTECTIVE_ZONE_TYPE
+}
+
+/// The MBR's partitions other than GPT protective ones
+fn mbr_partitions(mbr: &MbrZoneTable) -> impl Iterator<Item = &Zone> {
+    mbr.enumerate_zones().iter().filter(|zone| !is_protective_zone(zone))
+}
@@ -0,0 +1,102 @@
+//! Post-parse checks of a zone table's layout
+//!
+//! A corrupt or crafted partition table can declare partitions that run past
+//! the end of the disk or share sectors with each other. Parsers record these
+//! as [`ZoneWarning`]s instead of failing, so the rest of the table can still
+//! be examined.
+
+use std::fmt;
+use totalimage_core::Zone;
+
+/// A problem with the placement of a zone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZoneWarning {
+    /// The zone ends past the end of the disk
+    OutOfBounds {
+        /// Index of the zone
+        index: usize,
+        /// Byte offset just past the zone's end
+        end: u64,
+        /// Size of the disk in bytes
+        disk_size: u64,
+    },
+    /// Two zones share part of the disk
+    Overlap {
+        /// Index of the zone starting first
+        first: usize,
+        /// Index of the other zone
+        second: usize,
+        /// Number of bytes the zones share
+        shared: u64,
+    },
+}
+
+impl fmt::Display for ZoneWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { index, end, disk_size } => write!(
+                f,
+                "Partition {} ends at byte {}, past the end of the {}-byte disk",
+                index, end, disk_size
+            ),
+            Self::Overlap { first, second, shared } => write!(
+                f,
+                "Partitions {} and {} overlap by {} bytes",
+                first, second, shared
+            ),
+        }
+    }
+}
+
+/// Check every zone against the disk size and against each other
+///
+/// Zones of zero length occupy nothing and are never reported as overlapping.
+pub(crate) fn check_zones(zones: &[Zone], disk_size: u64) -> Vec<ZoneWarning> {
+    let mut warnings = Vec::new();
+
+    for zone in zones {
+        let end = zone.offset.saturating_add(zone.length);
+        if end > disk_size {
+            warnings.push(ZoneWarning::OutOfBounds { index: zone.index, end, disk_size });
+        }
+    }
+
+    let mut sorted: Vec<&Zone> = zones.iter().filter(|zone| zone.length > 0).collect();
+    sorted.sort_by_key(|zone| (zone.offset, zone.index));
+    for (i, first) in sorted.iter().enumerate() {
+        let first_end = first.offset.saturating_add(first.length);
+        for second in sorted[i + 1..].iter().take_while(|zone| zone.offset < first_end) {
+            let shared = first_end.min(second.offset.saturating_add(second.length)) - second.offset;
+            warnings.push(ZoneWarning::Overlap { first: first.index, second: second.index, shared });
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(index: usize, offset: u64, length: u64) -> Zone {
+        Zone::new(index, offset, length, "Test".to_string())
+    }
+
+    #[test]
+    fn test_check_zones() {
+        let zones = [zone(0, 1024, 4096), zone(1, 8192, 1024), zone(2, 5120, 2048), zone(3, 0, 0)];
+        assert!(check_zones(&zones, 16384).is_empty());
+
+        // Zone 2 now starts inside zone 0 and runs into zone 1
+        let zones = [zone(0, 1024, 4096), zone(1, 8192, 1024), zone(2, 4096, 5000)];
+        assert_eq!(
+            check_zones(&zones, 9000),
+            vec![
+                ZoneWarning::OutOfBounds { index: 1, end: 9216, disk_size: 9000 },
+                ZoneWarning::OutOfBounds { index: 2, end: 9096, disk_size: 9000 },
+                ZoneWarning::Overlap { first: 0, second: 2, shared: 1024 },
+                ZoneWarning::Overlap { first: 2, second: 1, shared: 904 },
+            ]
+        );
+    }
+}